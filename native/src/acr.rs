@@ -0,0 +1,235 @@
+//! Accessibility Conformance Report (VPAT 2.x) skeleton for color criteria.
+//!
+//! Aggregates a check result per WCAG criterion (using the criteria tagged on
+//! each result) and pre-fills the conformance level and remarks columns.
+//! The output is a starting point for the compliance team, not a final ACR:
+//! static analysis can't observe runtime styling, so remarks say so.
+//!
+//! Titles, remarks and the Markdown table follow the requested locale; the
+//! `conformance` field always holds the English VPAT term so tooling can
+//! match on it.
+
+use std::collections::HashSet;
+
+use crate::i18n::{self, Locale, MessageKey};
+use crate::types::{AcrReport, AcrRow, CheckResultJs, ContrastResult};
+
+/// Criteria covered by the contrast engine, in ACR table order: (id, level).
+pub const ACR_CRITERIA: &[(&str, &str)] = &[
+    ("1.4.3", "AA"),
+    ("1.4.6", "AAA"),
+    ("1.4.11", "AA"),
+    ("2.4.7", "AA"),
+];
+
+/// VPAT 2.x conformance terms (machine values, never localized).
+pub const SUPPORTS: &str = "Supports";
+pub const PARTIALLY_SUPPORTS: &str = "Partially Supports";
+pub const DOES_NOT_SUPPORT: &str = "Does Not Support";
+pub const NOT_EVALUATED: &str = "Not Evaluated";
+
+/// Per-criterion tallies.
+#[derive(Debug, Default)]
+struct Tally {
+    passed: u32,
+    violations: u32,
+    ignored: u32,
+    files_with_violations: HashSet<String>,
+}
+
+fn tally(criterion: &str, result: &CheckResultJs) -> Tally {
+    let tagged = |r: &&ContrastResult| r.wcag_criteria.iter().flatten().any(|c| c == criterion);
+    let mut t = Tally {
+        passed: result.passed.iter().filter(tagged).count() as u32,
+        ignored: result.ignored.iter().filter(tagged).count() as u32,
+        ..Tally::default()
+    };
+    for v in result.violations.iter().filter(tagged) {
+        t.violations += 1;
+        t.files_with_violations.insert(v.file.clone());
+    }
+    t
+}
+
+/// Conformance level from tallies: no data → Not Evaluated, no violations →
+/// Supports, some passing pairs → Partially Supports, otherwise Does Not Support.
+fn conformance(t: &Tally) -> &'static str {
+    let checked = t.passed + t.violations;
+    if checked == 0 {
+        NOT_EVALUATED
+    } else if t.violations == 0 {
+        SUPPORTS
+    } else if t.passed > 0 {
+        PARTIALLY_SUPPORTS
+    } else {
+        DOES_NOT_SUPPORT
+    }
+}
+
+/// Localized label of a VPAT conformance term.
+fn conformance_label(locale: Locale, conformance: &str) -> &'static str {
+    let key = match conformance {
+        SUPPORTS => MessageKey::AcrSupports,
+        PARTIALLY_SUPPORTS => MessageKey::AcrPartiallySupports,
+        DOES_NOT_SUPPORT => MessageKey::AcrDoesNotSupport,
+        _ => MessageKey::AcrNotEvaluated,
+    };
+    i18n::message(locale, key)
+}
+
+fn remarks(t: &Tally, locale: Locale) -> String {
+    let checked = t.passed + t.violations;
+    if checked == 0 {
+        return i18n::message(locale, MessageKey::AcrRemarkNoPairs).to_string();
+    }
+    let pct = format!("{:.1}", t.passed as f64 / checked as f64 * 100.0);
+    let mut text = i18n::format_message(
+        locale,
+        MessageKey::AcrRemarkPassRate,
+        &[&t.passed.to_string(), &checked.to_string(), &pct],
+    );
+    if t.violations > 0 {
+        text.push(' ');
+        text.push_str(&i18n::format_message(
+            locale,
+            MessageKey::AcrRemarkFailures,
+            &[&t.violations.to_string(), &t.files_with_violations.len().to_string()],
+        ));
+    }
+    if t.ignored > 0 {
+        text.push(' ');
+        text.push_str(&i18n::format_message(
+            locale,
+            MessageKey::AcrRemarkSuppressed,
+            &[&t.ignored.to_string()],
+        ));
+    }
+    text
+}
+
+/// Build the ACR skeleton (structured rows + Markdown table) from a check result.
+pub fn generate_acr(result: &CheckResultJs, locale: Locale) -> AcrReport {
+    let rows: Vec<AcrRow> = ACR_CRITERIA
+        .iter()
+        .map(|(id, level)| {
+            let t = tally(id, result);
+            AcrRow {
+                criterion: id.to_string(),
+                title: i18n::criterion_title(locale, id).unwrap_or_default().to_string(),
+                level: level.to_string(),
+                conformance: conformance(&t).to_string(),
+                remarks: remarks(&t, locale),
+            }
+        })
+        .collect();
+    let markdown = render_markdown(&rows, locale);
+    AcrReport { rows, markdown }
+}
+
+fn render_markdown(rows: &[AcrRow], locale: Locale) -> String {
+    let mut md = format!(
+        "| {} | {} | {} |\n",
+        i18n::message(locale, MessageKey::AcrHeaderCriteria),
+        i18n::message(locale, MessageKey::AcrHeaderConformance),
+        i18n::message(locale, MessageKey::AcrHeaderRemarks),
+    );
+    md.push_str("|---|---|---|\n");
+    for row in rows {
+        md.push_str(&format!(
+            "| {} {} ({}) | {} | {} |\n",
+            row.criterion,
+            row.title,
+            i18n::format_message(locale, MessageKey::AcrLevel, &[&row.level]),
+            conformance_label(locale, &row.conformance),
+            row.remarks
+        ));
+    }
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::checker::check_all_pairs;
+    use crate::types::ColorPair;
+
+    fn make_pair(file: &str, text_hex: &str, pair_type: &str) -> ColorPair {
+        ColorPair {
+            file: file.to_string(),
+            is_large_text: Some(false),
+            pair_type: Some(pair_type.to_string()),
+            ..ColorPair::fixture("text-x", text_hex)
+        }
+    }
+
+    fn to_js(pairs: &[ColorPair], threshold: &str) -> CheckResultJs {
+        check_all_pairs(pairs, threshold, "#ffffff").into()
+    }
+
+    fn row<'a>(report: &'a AcrReport, id: &str) -> &'a AcrRow {
+        report.rows.iter().find(|r| r.criterion == id).unwrap()
+    }
+
+    #[test]
+    fn all_passing_supports() {
+        let report = generate_acr(&to_js(&[make_pair("a.tsx", "#000000", "text")], "AA"), Locale::En);
+        assert_eq!(row(&report, "1.4.3").conformance, SUPPORTS);
+    }
+
+    #[test]
+    fn mixed_results_partially_support() {
+        let pairs = [
+            make_pair("a.tsx", "#000000", "text"),
+            make_pair("b.tsx", "#dddddd", "text"),
+        ];
+        let report = generate_acr(&to_js(&pairs, "AA"), Locale::En);
+        let r = row(&report, "1.4.3");
+        assert_eq!(r.conformance, PARTIALLY_SUPPORTS);
+        assert!(r.remarks.contains("1 of 2"));
+        assert!(r.remarks.contains("1 failing pairs across 1 files"));
+    }
+
+    #[test]
+    fn only_failures_does_not_support() {
+        let report = generate_acr(&to_js(&[make_pair("a.tsx", "#eeeeee", "border")], "AA"), Locale::En);
+        assert_eq!(row(&report, "1.4.11").conformance, DOES_NOT_SUPPORT);
+    }
+
+    #[test]
+    fn missing_data_not_evaluated() {
+        let report = generate_acr(&to_js(&[make_pair("a.tsx", "#000000", "text")], "AA"), Locale::En);
+        // AA run never tags 1.4.6
+        assert_eq!(row(&report, "1.4.6").conformance, NOT_EVALUATED);
+        assert_eq!(row(&report, "2.4.7").conformance, NOT_EVALUATED);
+    }
+
+    #[test]
+    fn suppressed_failures_noted_in_remarks() {
+        let mut ignored = make_pair("a.tsx", "#eeeeee", "text");
+        ignored.ignored = Some(true);
+        let pairs = [make_pair("b.tsx", "#000000", "text"), ignored];
+        let report = generate_acr(&to_js(&pairs, "AA"), Locale::En);
+        let r = row(&report, "1.4.3");
+        assert_eq!(r.conformance, SUPPORTS);
+        assert!(r.remarks.contains("1 failing pairs are suppressed"));
+    }
+
+    #[test]
+    fn markdown_table_has_row_per_criterion() {
+        let report = generate_acr(&to_js(&[], "AA"), Locale::En);
+        assert!(report.markdown.starts_with("| Criteria | Conformance Level | Remarks and Explanations |"));
+        assert_eq!(report.markdown.lines().count(), 2 + ACR_CRITERIA.len());
+        assert!(report.markdown.contains("| 1.4.3 Contrast (Minimum) (Level AA) | Not Evaluated |"));
+    }
+
+    #[test]
+    fn italian_locale_localizes_text_but_not_conformance_key() {
+        let report = generate_acr(&to_js(&[make_pair("a.tsx", "#000000", "text")], "AA"), Locale::It);
+        let r = row(&report, "1.4.3");
+        assert_eq!(r.conformance, SUPPORTS);
+        assert_eq!(r.title, "Contrasto (minimo)");
+        assert!(r.remarks.starts_with("1 coppie di colori su 1"));
+        assert!(report.markdown.starts_with("| Criteri | Livello di conformità |"));
+        assert!(report.markdown.contains("| 1.4.3 Contrasto (minimo) (Livello AA) | Supportato |"));
+    }
+}
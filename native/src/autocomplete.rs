@@ -0,0 +1,124 @@
+//! Contrast hints for editor class autocomplete.
+//!
+//! Each candidate text class is checked against the background it would land
+//! on, so the completion list can grey out classes that would fail there.
+
+use crate::math::checker::{check_contrast, is_violation};
+use crate::pairs::class_resolver::resolve_class_to_hex;
+use crate::theme::Theme;
+use crate::types::{CandidateContrast, ColorPair, ResolvedColor};
+
+/// `context_bg` as a color: a hex value or a bg class resolved through the theme.
+fn resolve_context_bg(theme: &Theme, context_bg: &str) -> Option<ResolvedColor> {
+    if context_bg.starts_with('#') {
+        return Some(ResolvedColor { hex: context_bg.to_string(), alpha: None });
+    }
+    resolve_class_to_hex(context_bg, &theme.color_map)
+}
+
+/// Ratio and verdict of every candidate text class on `context_bg` (a bg
+/// class such as "bg-card", or a hex), in input order. Candidates that don't
+/// resolve to a color (`text-lg`, unknown tokens) get no verdict. None when
+/// `context_bg` doesn't resolve.
+pub fn rank_candidate_classes(theme: &Theme, context_bg: &str, candidates: &[String]) -> Option<Vec<CandidateContrast>> {
+    let bg = resolve_context_bg(theme, context_bg)?;
+    Some(
+        candidates
+            .iter()
+            .map(|class_name| {
+                let base = class_name.rsplit(':').next().unwrap_or(class_name);
+                let Some(fg) = resolve_class_to_hex(base, &theme.color_map) else {
+                    return CandidateContrast {
+                        class_name: class_name.clone(),
+                        hex: None,
+                        ratio: None,
+                        pass: None,
+                        pass_large: None,
+                        apca_lc: None,
+                    };
+                };
+                let pair = ColorPair {
+                    bg_class: context_bg.to_string(),
+                    text_class: class_name.clone(),
+                    bg_hex: Some(bg.hex.clone()),
+                    text_hex: Some(fg.hex.clone()),
+                    bg_alpha: bg.alpha,
+                    text_alpha: fg.alpha,
+                    pair_type: Some("text".to_string()),
+                    ..Default::default()
+                };
+                let result = check_contrast(&pair, &theme.page_bg);
+                let pass_large = if theme.threshold == "AAA" { result.pass_aaa_large } else { result.pass_aa_large };
+                CandidateContrast {
+                    class_name: class_name.clone(),
+                    hex: Some(fg.hex),
+                    ratio: Some(result.ratio),
+                    pass: Some(!is_violation(&result, &theme.threshold)),
+                    pass_large: Some(pass_large),
+                    apca_lc: result.apca_lc,
+                }
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ColorMapEntry, ThemeConfig};
+
+    fn theme(threshold: &str) -> Theme {
+        let colors = [
+            ("--color-card", "#ffffff", None),
+            ("--color-gray-400", "#9ca3af", None),
+            ("--color-gray-500", "#6b7280", None),
+            ("--color-gray-900", "#111827", None),
+            ("--color-overlay", "#000000", Some(0.5)),
+        ];
+        Theme::from_config(&ThemeConfig {
+            color_map: colors
+                .iter()
+                .map(|(name, hex, alpha)| ColorMapEntry { name: name.to_string(), hex: hex.to_string(), alpha: *alpha })
+                .collect(),
+            threshold: Some(threshold.to_string()),
+            ..Default::default()
+        })
+    }
+
+    fn candidates(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn verdicts_in_input_order() {
+        let ranked = rank_candidate_classes(
+            &theme("AA"),
+            "bg-card",
+            &candidates(&["text-gray-400", "hover:text-gray-500", "text-lg", "text-gray-900/50"]),
+        )
+        .unwrap();
+        let verdicts: Vec<(&str, Option<bool>)> = ranked.iter().map(|c| (c.class_name.as_str(), c.pass)).collect();
+        assert_eq!(
+            verdicts,
+            vec![
+                ("text-gray-400", Some(false)),
+                ("hover:text-gray-500", Some(true)),
+                ("text-lg", None),
+                ("text-gray-900/50", Some(false)),
+            ]
+        );
+        assert_eq!(ranked[0].pass_large, Some(false));
+        assert!((ranked[1].ratio.unwrap() - 4.83).abs() < 0.01);
+        assert_eq!(ranked[3].pass_large, Some(true));
+    }
+
+    #[test]
+    fn threshold_and_context_forms() {
+        let aaa = rank_candidate_classes(&theme("AAA"), "#ffffff", &candidates(&["text-gray-500"])).unwrap();
+        assert_eq!((aaa[0].pass, aaa[0].pass_large), (Some(false), Some(true)));
+        // translucent bg composited over the page bg
+        let overlay = rank_candidate_classes(&theme("AA"), "bg-overlay", &candidates(&["text-gray-900"])).unwrap();
+        assert_eq!(overlay[0].pass, Some(false));
+        assert!(rank_candidate_classes(&theme("AA"), "bg-unknown", &candidates(&["text-gray-900"])).is_none());
+    }
+}
@@ -0,0 +1,183 @@
+//! Crash-safe on-disk cache container (scan cache, quarantine list, ...).
+//!
+//! Every cache file starts with one header line naming the format version,
+//! the cache kind, and the payload's length and SHA-256:
+//!
+//! ```text
+//! a11y-audit-cache 1 scan 1234 9f86d081884c7d65...
+//! <payload>
+//! ```
+//!
+//! Files are written to a temporary sibling and renamed into place, so a
+//! crash never leaves a half-written cache. A file that is truncated, fails
+//! its checksum or comes from another format version (CI cache restores do
+//! produce these) is deleted and reported as a miss: callers fall back to a
+//! cold scan and rebuild it instead of failing. Files that can't be read, or
+//! that hold another kind of cache, are reported but never deleted.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use crate::manifest::sha256_hex;
+
+/// Bump when the header or payload encoding changes; older files are rebuilt.
+pub const FORMAT_VERSION: u32 = 1;
+
+const MAGIC: &str = "a11y-audit-cache";
+
+/// Outcome of reading a cache file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CacheRead {
+    Hit(String),
+    Missing,
+    /// The file exists but reading it failed (file kept)
+    Unreadable(String),
+    /// A cache of another kind, e.g. another tool's (file kept)
+    OtherKind(String),
+    /// Another format version (file removed)
+    Stale,
+    /// Truncated, unparsable or failing its checksum (file removed)
+    Corrupt,
+}
+
+fn header(kind: &str, payload: &str) -> String {
+    format!("{} {} {} {} {}\n", MAGIC, FORMAT_VERSION, kind, payload.len(), sha256_hex(payload.as_bytes()))
+}
+
+/// Write `payload` as a `kind` cache at `path`, atomically replacing any previous file.
+pub fn write_cache(path: &Path, kind: &str, payload: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(format!(".tmp-{}", std::process::id()));
+    let tmp = Path::new(&tmp_name);
+
+    let written = (|| {
+        let mut file = fs::File::create(tmp)?;
+        file.write_all(header(kind, payload).as_bytes())?;
+        file.write_all(payload.as_bytes())?;
+        file.sync_all()
+    })();
+    match written.and_then(|_| fs::rename(tmp, path)) {
+        Ok(()) => Ok(()),
+        Err(err) => {
+            let _ = fs::remove_file(tmp);
+            Err(err)
+        }
+    }
+}
+
+/// Validate `content` as a `kind` cache file.
+fn parse(content: &str, kind: &str) -> CacheRead {
+    let Some((first_line, payload)) = content.split_once('\n') else {
+        return CacheRead::Corrupt;
+    };
+    let fields: Vec<&str> = first_line.split(' ').collect();
+    let [magic, version, file_kind, len, checksum] = fields[..] else {
+        return CacheRead::Corrupt;
+    };
+    if magic != MAGIC {
+        return CacheRead::Corrupt;
+    }
+    if version != FORMAT_VERSION.to_string() {
+        return CacheRead::Stale;
+    }
+    if file_kind != kind {
+        return CacheRead::OtherKind(file_kind.to_string());
+    }
+    if len.parse::<usize>().ok() != Some(payload.len()) || sha256_hex(payload.as_bytes()) != checksum {
+        return CacheRead::Corrupt;
+    }
+    CacheRead::Hit(payload.to_string())
+}
+
+/// Read the `kind` cache at `path`. Stale and corrupt files are deleted so
+/// the next write starts clean; only a file that was read can be either.
+pub fn read_cache(path: &Path, kind: &str) -> CacheRead {
+    let outcome = match fs::read(path) {
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return CacheRead::Missing,
+        Err(err) => CacheRead::Unreadable(err.to_string()),
+        Ok(bytes) => match String::from_utf8(bytes) {
+            Ok(content) => parse(&content, kind),
+            Err(_) => CacheRead::Corrupt,
+        },
+    };
+    match &outcome {
+        CacheRead::Stale | CacheRead::Corrupt => {
+            log::warn!("discarding {:?} {} cache {}: rebuilding", outcome, kind, path.display());
+            let _ = fs::remove_file(path);
+        }
+        CacheRead::Unreadable(err) => log::warn!("cannot read {} cache {}: {}", kind, path.display(), err),
+        CacheRead::OtherKind(found) => {
+            log::warn!("{} holds a {} cache, not {}: leaving it alone", path.display(), found, kind)
+        }
+        CacheRead::Hit(_) | CacheRead::Missing => {}
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn cache_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("a11y-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("nested").join("scan.cache")
+    }
+
+    #[test]
+    fn round_trip_and_replace() {
+        let path = cache_path("round-trip");
+        assert_eq!(read_cache(&path, "scan"), CacheRead::Missing);
+        write_cache(&path, "scan", "{\"files\":[]}\nsecond line").unwrap();
+        assert_eq!(read_cache(&path, "scan"), CacheRead::Hit("{\"files\":[]}\nsecond line".to_string()));
+        write_cache(&path, "scan", "").unwrap();
+        assert_eq!(read_cache(&path, "scan"), CacheRead::Hit(String::new()));
+        let siblings = fs::read_dir(path.parent().unwrap()).unwrap().count();
+        assert_eq!(siblings, 1, "no temporary file left behind");
+    }
+
+    #[test]
+    fn truncated_or_tampered_files_are_discarded() {
+        let path = cache_path("corrupt");
+        write_cache(&path, "quarantine", "[\"src/A.tsx\",\"src/B.tsx\"]").unwrap();
+        let full = fs::read_to_string(&path).unwrap();
+
+        fs::write(&path, &full[..full.len() - 5]).unwrap();
+        assert_eq!(read_cache(&path, "quarantine"), CacheRead::Corrupt);
+        assert!(!path.exists());
+
+        fs::write(&path, full.replace("A.tsx", "C.tsx")).unwrap();
+        assert_eq!(read_cache(&path, "quarantine"), CacheRead::Corrupt);
+
+        fs::write(&path, &full[..10]).unwrap();
+        assert_eq!(read_cache(&path, "quarantine"), CacheRead::Corrupt);
+        fs::write(&path, [0xff, 0xfe, b'\n']).unwrap();
+        assert_eq!(read_cache(&path, "quarantine"), CacheRead::Corrupt);
+    }
+
+    #[test]
+    fn other_versions_are_stale_and_other_kinds_kept() {
+        let path = cache_path("stale");
+        write_cache(&path, "scan", "payload").unwrap();
+        assert_eq!(read_cache(&path, "baseline"), CacheRead::OtherKind("scan".to_string()));
+        assert_eq!(read_cache(&path, "scan"), CacheRead::Hit("payload".to_string()));
+
+        let old = header("scan", "payload").replacen(" 1 ", " 0 ", 1) + "payload";
+        fs::write(&path, old).unwrap();
+        assert_eq!(read_cache(&path, "scan"), CacheRead::Stale);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn unreadable_files_are_kept() {
+        let path = cache_path("unreadable");
+        fs::create_dir_all(&path).unwrap();
+        assert!(matches!(read_cache(&path, "scan"), CacheRead::Unreadable(_)));
+        assert!(path.is_dir());
+    }
+}
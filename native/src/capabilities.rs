@@ -0,0 +1,68 @@
+//! Machine-readable description of what this engine build supports.
+//!
+//! Wrappers check `features` / `file_types` / rule versions instead of
+//! pinning an exact native version, so an upgrade that adds a capability
+//! doesn't break them and one that lacks it can be detected up front.
+
+use crate::cache_file::FORMAT_VERSION;
+use crate::manifest::rule_versions;
+use crate::parser::FILE_TYPES;
+use crate::rules::ALL_RULES;
+use crate::types::Capabilities;
+
+/// Features every native build has. The engine has no LSP server of its own:
+/// editor integrations build on `editor` (hover / region index) and `snapshot`.
+const FEATURES: &[&str] = &[
+    "walk",
+    "cache",
+    "diff",
+    "editor",
+    "workspace",
+    "hot-reload",
+    "snapshot",
+    "fix-plan",
+    "palette-optimizer",
+    "acr",
+    "wcag3",
+    "alternate-contrast",
+    "vision-simulation",
+    "self-test",
+    "results-api",
+];
+
+pub fn capabilities() -> Capabilities {
+    let mut features: Vec<String> = FEATURES.iter().map(|f| f.to_string()).collect();
+    if cfg!(target_arch = "wasm32") {
+        features.push("wasm".to_string());
+    }
+    if cfg!(feature = "parquet") {
+        features.push("parquet-export".to_string());
+    }
+    Capabilities {
+        engine_version: env!("CARGO_PKG_VERSION").to_string(),
+        file_types: FILE_TYPES.iter().map(|t| t.to_string()).collect(),
+        rules: rule_versions(),
+        selectable_rules: ALL_RULES.iter().map(|r| r.to_string()).collect(),
+        features,
+        cache_format_version: FORMAT_VERSION,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::scan_source;
+
+    #[test]
+    fn advertised_file_types_are_scanned() {
+        let caps = capabilities();
+        assert_eq!(caps.engine_version, env!("CARGO_PKG_VERSION"));
+        assert!(!caps.features.iter().any(|f| f == "wasm"));
+        assert!(caps.rules.iter().any(|r| r.rule == "wcag21-contrast"));
+        let markup = r#"<template><p class="text-gray-500" className="text-gray-500">a</p></template>"#;
+        for suffix in &caps.file_types {
+            let (regions, ..) = scan_source(&format!("src/App{}", suffix), markup, &Default::default());
+            assert!(!regions.is_empty(), "{}", suffix);
+        }
+    }
+}
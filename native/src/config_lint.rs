@@ -0,0 +1,165 @@
+//! Config lint: `container_config` / `portal_config` entries the scanned code
+//! disagrees with, so the config stays truthful as the codebase evolves.
+//!
+//! - duplicate: a component listed twice with the same value
+//! - conflict: a component listed twice with different values, or in both
+//!   configs (the first entry wins during the scan)
+//! - unmatched: a component no scanned element renders as or inside
+//! - contradicted: a container whose own class attribute sets an explicit bg
+//!   other than its configured one in at least `CONTRADICTED_SHARE` of its
+//!   occurrences, so the configured bg rarely applies
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::parser::context_tracker::find_explicit_bg_in_raw_tag;
+use crate::types::{ClassRegion, ConfigLintFinding, ContainerEntry, ExtractOptions, PreExtractedFile};
+
+/// Share of a container's occurrences (with a class attribute) that must set
+/// a contradicting bg for it to be reported.
+const CONTRADICTED_SHARE: f64 = 0.5;
+
+/// Lint the configs of `options` against `files`, its scan result.
+pub fn lint(options: &ExtractOptions, files: &[PreExtractedFile]) -> Vec<ConfigLintFinding> {
+    let configs = [("container", &options.container_config), ("portal", &options.portal_config)];
+    let mut findings = Vec::new();
+
+    // Duplicates and conflicts, in config order
+    let mut first: HashMap<&str, (&str, &str)> = HashMap::new();
+    for (config, entries) in configs {
+        for ContainerEntry { component, bg_class } in entries.iter() {
+            let Some(&(first_config, first_value)) = first.get(component.as_str()) else {
+                first.insert(component, (config, bg_class));
+                continue;
+            };
+            let (kind, message) = if first_config != config {
+                let message = format!("{} is configured as both a container and a portal", component);
+                ("conflict", message)
+            } else if first_value == bg_class {
+                ("duplicate", format!("{} is listed twice in the {} config", component, config))
+            } else {
+                let message = format!(
+                    "{} is listed as {} and as {} in the {} config; {} applies",
+                    component, first_value, bg_class, config, first_value
+                );
+                ("conflict", message)
+            };
+            findings.push(finding(kind, component, config, message));
+        }
+    }
+
+    // Own regions of each element (twins left out), and every rendered tag
+    let regions: Vec<&ClassRegion> = files
+        .iter()
+        .flat_map(|file| &file.regions)
+        .filter(|r| r.theme.is_none() && r.breakpoint.is_none() && r.interactive_state.is_none())
+        .filter(|r| r.variant_key.is_none())
+        .collect();
+    let rendered: HashSet<&str> = regions
+        .iter()
+        .flat_map(|r| std::iter::once(r.tag_name.as_str()).chain(r.ancestry.iter().map(String::as_str)))
+        .collect();
+
+    let mut seen = HashSet::new();
+    for (config, entries) in configs {
+        for entry in entries.iter().filter(|e| seen.insert((config, e.component.as_str()))) {
+            if !rendered.contains(entry.component.as_str()) {
+                let message = format!("no scanned element renders as or inside {}", entry.component);
+                findings.push(finding("unmatched", &entry.component, config, message));
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for entry in options.container_config.iter().filter(|e| seen.insert(e.component.as_str())) {
+        let mut occurrences = 0;
+        let mut explicit_bgs: BTreeMap<String, u32> = BTreeMap::new();
+        for region in regions.iter().filter(|r| r.tag_name == entry.component) {
+            let count = region.occurrences.unwrap_or(1);
+            occurrences += count;
+            if let Some(bg) = find_explicit_bg_in_raw_tag(&region.content).filter(|bg| *bg != entry.bg_class) {
+                *explicit_bgs.entry(bg).or_default() += count;
+            }
+        }
+        let contradictions: u32 = explicit_bgs.values().sum();
+        if contradictions == 0 || (contradictions as f64) < CONTRADICTED_SHARE * occurrences as f64 {
+            continue;
+        }
+        let mut explicit_bgs: Vec<(String, u32)> = explicit_bgs.into_iter().collect();
+        explicit_bgs.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        let message = format!(
+            "{} of {} {} elements set their own bg ({}) instead of the configured {}",
+            contradictions, occurrences, entry.component, explicit_bgs[0].0, entry.bg_class
+        );
+        findings.push(ConfigLintFinding {
+            explicit_bgs: Some(explicit_bgs.into_iter().map(|(bg, _)| bg).collect()),
+            occurrences: Some(occurrences),
+            contradictions: Some(contradictions),
+            ..finding("contradicted", &entry.component, "container", message)
+        });
+    }
+    findings
+}
+
+fn finding(kind: &str, component: &str, config: &str, message: String) -> ConfigLintFinding {
+    ConfigLintFinding {
+        kind: kind.to_string(),
+        component: component.to_string(),
+        config: config.to_string(),
+        message,
+        explicit_bgs: None,
+        occurrences: None,
+        contradictions: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::extract_and_scan;
+    use crate::types::FileInput;
+
+    fn entries(pairs: &[(&str, &str)]) -> Vec<ContainerEntry> {
+        pairs
+            .iter()
+            .map(|(component, bg)| ContainerEntry { component: component.to_string(), bg_class: bg.to_string() })
+            .collect()
+    }
+
+    #[test]
+    fn reports_config_the_code_disagrees_with() {
+        let source = r#"<Card className="bg-slate-900 p-4"><p className="text-white">a</p></Card>
+<Card className="bg-slate-900"><p className="text-white">b</p></Card>
+<Card><p className="text-black">c</p></Card>
+<Panel><p className="text-black">d</p></Panel>"#;
+        let options = ExtractOptions {
+            file_contents: vec![FileInput { path: "a.tsx".to_string(), content: source.to_string() }],
+            container_config: entries(&[
+                ("Card", "bg-card"),
+                ("Panel", "bg-muted"),
+                ("Panel", "bg-muted"),
+                ("Sheet", "bg-popover"),
+                ("Sheet", "bg-white"),
+            ]),
+            portal_config: entries(&[("Dialog", "reset"), ("Card", "reset")]),
+            default_bg: "bg-background".to_string(),
+            ..Default::default()
+        };
+        let findings = lint(&options, &extract_and_scan(&options));
+        let summary: Vec<(&str, &str, &str)> =
+            findings.iter().map(|f| (f.kind.as_str(), f.component.as_str(), f.config.as_str())).collect();
+        assert_eq!(
+            summary,
+            [
+                ("duplicate", "Panel", "container"),
+                ("conflict", "Sheet", "container"),
+                ("conflict", "Card", "portal"),
+                ("unmatched", "Sheet", "container"),
+                ("unmatched", "Dialog", "portal"),
+                ("contradicted", "Card", "container"),
+            ]
+        );
+        let contradicted = &findings[5];
+        assert_eq!(contradicted.explicit_bgs.as_deref(), Some(&["bg-slate-900".to_string()][..]));
+        assert_eq!((contradicted.contradictions, contradicted.occurrences), (Some(2), Some(2)));
+    }
+}
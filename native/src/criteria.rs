@@ -0,0 +1,180 @@
+//! WCAG 2.x success criterion mapping for contrast results.
+//!
+//! Each result is tagged with the criteria it is evidence for (primary first)
+//! and the sufficient techniques that describe the check, so reports and
+//! downstream rule catalogs carry audit-grade traceability. The stable rule
+//! id of the check (`AA-TEXT-CONTRAST`, ...) is what reports, result ids and
+//! scoped `a11y-ignore <RULE-ID>: <reason>` and `a11y-ignore[<code>]`
+//! comments refer to.
+
+use crate::types::ContrastResult;
+
+/// Text contrast at AA (1.4.3).
+pub const AA_TEXT_CONTRAST: &str = "AA-TEXT-CONTRAST";
+
+/// Text contrast at AAA (1.4.6).
+pub const AAA_TEXT_CONTRAST: &str = "AAA-TEXT-CONTRAST";
+
+/// Border / ring / outline contrast (1.4.11).
+pub const NONTEXT_CONTRAST: &str = "NONTEXT-CONTRAST";
+
+/// Ring / outline shown on keyboard focus (1.4.11 + 2.4.7).
+pub const FOCUS_INDICATOR: &str = "FOCUS-INDICATOR";
+
+/// Text rendered nearly transparent, whatever its ratio (1.4.3; opt-in, see
+/// `text_alpha`).
+pub const LOW_ALPHA_TEXT: &str = "LOW-ALPHA-TEXT";
+
+/// Every contrast rule id, as accepted by scoped `a11y-ignore` comments.
+pub const ALL_CONTRAST_RULES: &[&str] =
+    &[AA_TEXT_CONTRAST, AAA_TEXT_CONTRAST, NONTEXT_CONTRAST, FOCUS_INDICATOR, LOW_ALPHA_TEXT];
+
+/// Rule ids named by an `a11y-ignore[<code>, ...]` code: a rule id in any
+/// case (`aa-text-contrast`) or a family of them (`contrast-text` = AA and
+/// AAA text, `contrast-nontext`, `contrast-focus`, `contrast` = all). Other
+/// codes (element rules such as `use-of-color`) name themselves.
+pub fn rule_ids_of_code(code: &str) -> Vec<String> {
+    let code = code.trim().to_ascii_lowercase();
+    let family: &[&str] = match code.as_str() {
+        "contrast" => ALL_CONTRAST_RULES,
+        "contrast-text" => &[AA_TEXT_CONTRAST, AAA_TEXT_CONTRAST],
+        "contrast-nontext" => &[NONTEXT_CONTRAST],
+        "contrast-focus" => &[FOCUS_INDICATOR],
+        _ => match ALL_CONTRAST_RULES.iter().find(|r| r.eq_ignore_ascii_case(&code)) {
+            Some(rule) => return vec![rule.to_string()],
+            None => return vec![code],
+        },
+    };
+    family.iter().map(|r| r.to_string()).collect()
+}
+
+/// Rule id + criteria + techniques attached to one result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CriteriaMapping {
+    pub rule_id: &'static str,
+    pub criteria: Vec<&'static str>,
+    pub techniques: Vec<&'static str>,
+}
+
+/// Map a checked pair to its success criteria.
+///
+/// - text pairs → 1.4.3 Contrast (Minimum) at AA, 1.4.6 Contrast (Enhanced) at AAA
+/// - border / ring / outline → 1.4.11 Non-text Contrast
+/// - ring / outline in `focus-visible` state → additionally 2.4.7 Focus Visible
+pub fn map_criteria(
+    pair_type: Option<&str>,
+    interactive_state: Option<&str>,
+    is_large_text: bool,
+    threshold: &str,
+) -> CriteriaMapping {
+    let is_non_text = pair_type.is_some_and(|t| t != "text");
+
+    if is_non_text {
+        let is_focus_indicator = interactive_state == Some("focus-visible")
+            && matches!(pair_type, Some("ring") | Some("outline"));
+        if is_focus_indicator {
+            return CriteriaMapping {
+                rule_id: FOCUS_INDICATOR,
+                criteria: vec!["1.4.11", "2.4.7"],
+                techniques: vec!["G195", "G149", "C15"],
+            };
+        }
+        return CriteriaMapping {
+            rule_id: NONTEXT_CONTRAST,
+            criteria: vec!["1.4.11"],
+            techniques: vec!["G207", "G209"],
+        };
+    }
+
+    if threshold == "AAA" {
+        CriteriaMapping {
+            rule_id: AAA_TEXT_CONTRAST,
+            criteria: vec!["1.4.6"],
+            techniques: if is_large_text { vec!["G18"] } else { vec!["G17"] },
+        }
+    } else {
+        CriteriaMapping {
+            rule_id: AA_TEXT_CONTRAST,
+            criteria: vec!["1.4.3"],
+            techniques: if is_large_text { vec!["G145"] } else { vec!["G18"] },
+        }
+    }
+}
+
+/// Rule id of a checked result: its `rule_id`, else the AA rule of its pair.
+pub fn rule_of(result: &ContrastResult) -> &str {
+    result.rule_id.as_deref().unwrap_or_else(|| {
+        let state = result.interactive_state.as_deref();
+        map_criteria(result.pair_type.as_deref(), state, result.is_large_text.unwrap_or(false), "AA").rule_id
+    })
+}
+
+/// Minimum ratio `rule_id` requires (WCAG thresholds; non-text rules use 3:1).
+pub fn required_ratio(rule_id: &str, is_large_text: bool) -> f64 {
+    match rule_id {
+        AAA_TEXT_CONTRAST if is_large_text => 4.5,
+        AAA_TEXT_CONTRAST => 7.0,
+        AA_TEXT_CONTRAST if !is_large_text => 4.5,
+        _ => 3.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignore_codes_name_rule_ids() {
+        assert_eq!(rule_ids_of_code("contrast-text"), [AA_TEXT_CONTRAST, AAA_TEXT_CONTRAST]);
+        assert_eq!(rule_ids_of_code("Nontext-Contrast"), [NONTEXT_CONTRAST]);
+        assert_eq!(rule_ids_of_code("contrast").len(), ALL_CONTRAST_RULES.len());
+        assert_eq!(rule_ids_of_code("use-of-color"), ["use-of-color"]);
+    }
+
+    #[test]
+    fn text_aa_maps_to_1_4_3() {
+        let m = map_criteria(Some("text"), None, false, "AA");
+        assert_eq!(m.criteria, vec!["1.4.3"]);
+        assert_eq!(m.techniques, vec!["G18"]);
+    }
+
+    #[test]
+    fn large_text_aa_uses_g145() {
+        let m = map_criteria(None, None, true, "AA");
+        assert_eq!(m.criteria, vec!["1.4.3"]);
+        assert_eq!(m.techniques, vec!["G145"]);
+    }
+
+    #[test]
+    fn text_aaa_maps_to_1_4_6() {
+        assert_eq!(map_criteria(Some("text"), None, false, "AAA").criteria, vec!["1.4.6"]);
+        assert_eq!(map_criteria(Some("text"), None, false, "AAA").techniques, vec!["G17"]);
+        assert_eq!(map_criteria(Some("text"), None, true, "AAA").techniques, vec!["G18"]);
+    }
+
+    #[test]
+    fn border_maps_to_1_4_11_regardless_of_level() {
+        assert_eq!(map_criteria(Some("border"), None, false, "AA").criteria, vec!["1.4.11"]);
+        assert_eq!(map_criteria(Some("border"), None, false, "AAA").criteria, vec!["1.4.11"]);
+    }
+
+    #[test]
+    fn focus_ring_adds_2_4_7() {
+        let m = map_criteria(Some("ring"), Some("focus-visible"), false, "AA");
+        assert_eq!(m.criteria, vec!["1.4.11", "2.4.7"]);
+        assert_eq!(m.rule_id, FOCUS_INDICATOR);
+    }
+
+    #[test]
+    fn hover_ring_is_plain_non_text() {
+        let m = map_criteria(Some("ring"), Some("hover"), false, "AA");
+        assert_eq!(m.criteria, vec!["1.4.11"]);
+        assert_eq!(m.rule_id, NONTEXT_CONTRAST);
+    }
+
+    #[test]
+    fn focus_text_stays_text_criterion() {
+        let m = map_criteria(None, Some("focus-visible"), false, "AA");
+        assert_eq!(m.criteria, vec!["1.4.3"]);
+    }
+}
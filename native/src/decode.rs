@@ -0,0 +1,183 @@
+//! Source decoding for files read from disk.
+//!
+//! Legacy templates are not always UTF-8. A byte order mark decides first
+//! (UTF-8, UTF-16 LE/BE); without one, text with a NUL byte in every other
+//! position of mostly-ASCII content is read as BOM-less UTF-16 (such bytes
+//! are valid but never real UTF-8 source), valid UTF-8 is taken as is, and
+//! anything else as Windows-1252 (the superset of Latin-1 browsers use for
+//! `iso-8859-1`). Decoding to a `String` up front
+//! keeps line and column arithmetic valid for every parser.
+
+/// Encodings the decoder reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Windows1252,
+}
+
+impl Encoding {
+    /// Parse a WHATWG-style label ("utf-8", "utf-16le", "latin1", ...).
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" => Some(Self::Utf8),
+            "utf-16le" | "utf-16" => Some(Self::Utf16Le),
+            "utf-16be" => Some(Self::Utf16Be),
+            "windows-1252" | "cp1252" | "latin1" | "latin-1" | "iso-8859-1" => Some(Self::Windows1252),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Utf8 => "utf-8",
+            Self::Utf16Le => "utf-16le",
+            Self::Utf16Be => "utf-16be",
+            Self::Windows1252 => "windows-1252",
+        }
+    }
+}
+
+/// Bytes sampled by the BOM-less UTF-16 heuristic.
+const SNIFF_LEN: usize = 4096;
+
+/// Windows-1252 code points of bytes 0x80-0x9F (undefined bytes map to the
+/// C1 control of the same value, as in browsers).
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+/// Encoding announced by a byte order mark, with the BOM's length.
+fn bom(bytes: &[u8]) -> Option<(Encoding, usize)> {
+    match bytes {
+        [0xEF, 0xBB, 0xBF, ..] => Some((Encoding::Utf8, 3)),
+        [0xFF, 0xFE, ..] => Some((Encoding::Utf16Le, 2)),
+        [0xFE, 0xFF, ..] => Some((Encoding::Utf16Be, 2)),
+        _ => None,
+    }
+}
+
+/// BOM-less UTF-16 of mostly-ASCII text: NUL high bytes in most code units
+/// and (almost) no NUL low bytes.
+fn sniff_utf16(bytes: &[u8]) -> Option<Encoding> {
+    let sample = &bytes[..bytes.len().min(SNIFF_LEN) & !1];
+    let units = sample.len() / 2;
+    if units == 0 {
+        return None;
+    }
+    let even_nuls = sample.iter().step_by(2).filter(|&&b| b == 0).count();
+    let odd_nuls = sample.iter().skip(1).step_by(2).filter(|&&b| b == 0).count();
+    if odd_nuls * 2 > units && even_nuls * 10 < units {
+        Some(Encoding::Utf16Le)
+    } else if even_nuls * 2 > units && odd_nuls * 10 < units {
+        Some(Encoding::Utf16Be)
+    } else {
+        None
+    }
+}
+
+/// Encoding and BOM length of `bytes` (see the module doc).
+pub fn detect(bytes: &[u8]) -> (Encoding, usize) {
+    if let Some(found) = bom(bytes) {
+        return found;
+    }
+    if let Some(found) = sniff_utf16(bytes) {
+        return (found, 0);
+    }
+    if std::str::from_utf8(bytes).is_ok() {
+        return (Encoding::Utf8, 0);
+    }
+    (Encoding::Windows1252, 0)
+}
+
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> String {
+    let units = bytes.chunks_exact(2).map(|pair| unit([pair[0], pair[1]]));
+    let mut text: String = char::decode_utf16(units).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER)).collect();
+    if bytes.len() % 2 == 1 {
+        text.push(char::REPLACEMENT_CHARACTER);
+    }
+    text
+}
+
+/// `bytes` as text in `encoding` (detected when None), without BOM, and the
+/// encoding used. Undecodable sequences become U+FFFD.
+pub fn decode(bytes: &[u8], encoding: Option<Encoding>) -> (String, Encoding) {
+    let (encoding, bom_len) = match (encoding, bom(bytes)) {
+        (Some(forced), Some((found, len))) if forced == found => (forced, len),
+        (Some(forced), _) => (forced, 0),
+        (None, _) => detect(bytes),
+    };
+    let bytes = &bytes[bom_len..];
+    let text = match encoding {
+        Encoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+        Encoding::Utf16Le => decode_utf16(bytes, u16::from_le_bytes),
+        Encoding::Utf16Be => decode_utf16(bytes, u16::from_be_bytes),
+        Encoding::Windows1252 => bytes
+            .iter()
+            .map(|&b| match b {
+                0x80..=0x9F => WINDOWS_1252_HIGH[(b - 0x80) as usize],
+                _ => b as char,
+            })
+            .collect(),
+    };
+    (text, encoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16(text: &str, big_endian: bool) -> Vec<u8> {
+        text.encode_utf16()
+            .flat_map(|u| if big_endian { u.to_be_bytes() } else { u.to_le_bytes() })
+            .collect()
+    }
+
+    #[test]
+    fn boms_decide_and_are_stripped() {
+        let source = "<p className=\"text-gray-500\">è</p>";
+        let mut utf8 = vec![0xEF, 0xBB, 0xBF];
+        utf8.extend_from_slice(source.as_bytes());
+        assert_eq!(decode(&utf8, None), (source.to_string(), Encoding::Utf8));
+        let mut le = vec![0xFF, 0xFE];
+        le.extend(utf16(source, false));
+        assert_eq!(decode(&le, None), (source.to_string(), Encoding::Utf16Le));
+        let mut be = vec![0xFE, 0xFF];
+        be.extend(utf16(source, true));
+        assert_eq!(decode(&be, None), (source.to_string(), Encoding::Utf16Be));
+    }
+
+    #[test]
+    fn bomless_sources_are_sniffed() {
+        let source = "<p className=\"text-gray-500\">caffè</p>\n";
+        assert_eq!(decode(source.as_bytes(), None).1, Encoding::Utf8);
+        assert_eq!(decode(&utf16(source, false), None), (source.to_string(), Encoding::Utf16Le));
+        assert_eq!(decode(&utf16(source, true), None), (source.to_string(), Encoding::Utf16Be));
+        // Latin-1 "caffè" with a Windows-1252 curly quote
+        let latin1 = b"<p className=\"text-gray-500\">caff\xE8 \x93ok\x94</p>";
+        let (text, encoding) = decode(latin1, None);
+        assert_eq!(encoding, Encoding::Windows1252);
+        assert_eq!(text, "<p className=\"text-gray-500\">caffè \u{201C}ok\u{201D}</p>");
+    }
+
+    #[test]
+    fn bomless_ascii_utf16_is_not_taken_for_utf8() {
+        let source = "<p className=\"text-gray-500\">Save</p>\n";
+        assert_eq!(decode(source.as_bytes(), None).1, Encoding::Utf8);
+        assert_eq!(decode(&utf16(source, false), None), (source.to_string(), Encoding::Utf16Le));
+        assert_eq!(decode(&utf16(source, true), None), (source.to_string(), Encoding::Utf16Be));
+    }
+
+    #[test]
+    fn forced_encoding_wins() {
+        assert_eq!(Encoding::from_label("ISO-8859-1"), Some(Encoding::Windows1252));
+        assert_eq!(Encoding::from_label("ebcdic"), None);
+        // Valid UTF-8 read as Latin-1 on request
+        let (text, _) = decode("è".as_bytes(), Some(Encoding::Windows1252));
+        assert_eq!(text, "Ã¨");
+    }
+}
@@ -0,0 +1,417 @@
+//! Changed-line attribution from a unified diff or explicit line ranges.
+//!
+//! Lets CI policies block only on violations whose line was added or edited
+//! in the change under review, and review bots post inline comments on them
+//! (`annotate`), without the JS wrapper re-implementing hunk arithmetic.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::criteria::{required_ratio, rule_of, LOW_ALPHA_TEXT};
+use crate::i18n::{self, Locale, MessageKey};
+use crate::math::text_alpha::effective_alpha;
+use crate::types::{CheckResultJs, ContrastResult, DiffAnnotation, DiffOptions};
+
+/// Added/modified lines of the new side of a diff, per file.
+#[derive(Debug, Clone, Default)]
+pub struct ChangedLines {
+    /// path → inclusive (start, end) line ranges, sorted and merged
+    files: HashMap<String, Vec<(u32, u32)>>,
+}
+
+impl ChangedLines {
+    /// Build from `DiffOptions`: the unified diff and the explicit ranges are merged.
+    pub fn from_options(options: &DiffOptions) -> Self {
+        let mut changed = match &options.unified_diff {
+            Some(diff) => parse_unified_diff(diff),
+            None => Self::default(),
+        };
+        for range in options.ranges.iter().flatten() {
+            changed.add(&range.path, range.start_line, range.end_line.max(range.start_line));
+        }
+        changed.normalize();
+        changed
+    }
+
+    fn add(&mut self, path: &str, start: u32, end: u32) {
+        self.files
+            .entry(path.replace('\\', "/"))
+            .or_default()
+            .push((start, end));
+    }
+
+    fn normalize(&mut self) {
+        for ranges in self.files.values_mut() {
+            ranges.sort_unstable();
+            let mut merged: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+            for &(start, end) in ranges.iter() {
+                match merged.last_mut() {
+                    Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+                    _ => merged.push((start, end)),
+                }
+            }
+            *ranges = merged;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Changed (start, end) line ranges per diff path.
+    pub fn ranges(&self) -> impl Iterator<Item = (&str, &[(u32, u32)])> {
+        self.files.iter().map(|(file, ranges)| (file.as_str(), ranges.as_slice()))
+    }
+
+    /// Whether `line` of `path` was added or modified.
+    pub fn contains(&self, path: &str, line: u32) -> bool {
+        self.overlaps(path, line, line)
+    }
+
+    /// Whether any of lines `start..=end` of `path` was added or modified.
+    pub fn overlaps(&self, path: &str, start: u32, end: u32) -> bool {
+        self.files.iter().any(|(file, ranges)| {
+            path_matches(path, file) && ranges.iter().any(|&(s, e)| start <= e && end >= s)
+        })
+    }
+}
+
+/// Whether `path` refers to `candidate`. Diff and CLI paths are usually
+/// repo-relative while scanned paths may be absolute, so a match on a whole
+/// trailing path segment counts too. Separators and a leading `./` are ignored.
+pub fn path_matches(path: &str, candidate: &str) -> bool {
+    let path = path.replace('\\', "/");
+    let candidate = candidate.replace('\\', "/");
+    let candidate = candidate.trim_start_matches("./");
+    !candidate.is_empty() && (path == candidate || path.ends_with(&format!("/{}", candidate)))
+}
+
+/// Parse the new-side added lines of a unified diff (`git diff` output).
+///
+/// Deleted files (`+++ /dev/null`) contribute nothing; removed lines have no
+/// new-side line and are skipped.
+pub fn parse_unified_diff(diff: &str) -> ChangedLines {
+    let mut changed = ChangedLines::default();
+    for_each_added_line(diff, |path, added| changed.add(path, added.line, added.line));
+    changed.normalize();
+    changed
+}
+
+/// Where an added line sits in a unified diff.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AddedLine {
+    /// New-side line number
+    line: u32,
+    /// 1-based line count below the file's first hunk header, later hunk
+    /// headers included (GitHub's review comment `position`)
+    position: u32,
+    /// 0-based hunk index within the file
+    hunk: u32,
+    /// 1-based line within the hunk, header excluded
+    hunk_line: u32,
+}
+
+/// Call `f` with the path and position of every added line of `diff`.
+fn for_each_added_line(diff: &str, mut f: impl FnMut(&str, AddedLine)) {
+    let mut current: Option<String> = None;
+    // Lines still expected in the current hunk (old side, new side)
+    let mut old_remaining: u32 = 0;
+    let mut new_remaining: u32 = 0;
+    let mut new_line: u32 = 0;
+    // Hunks seen in the current file, lines since its first header and
+    // lines since the current header
+    let mut hunks: u32 = 0;
+    let mut position: u32 = 0;
+    let mut hunk_line: u32 = 0;
+
+    for line in diff.lines() {
+        let in_hunk = old_remaining > 0 || new_remaining > 0;
+        if !in_hunk {
+            if let Some(target) = line.strip_prefix("+++ ") {
+                let target = target.split('\t').next().unwrap_or("").trim();
+                current = if target == "/dev/null" {
+                    None
+                } else {
+                    Some(target.strip_prefix("b/").unwrap_or(target).to_string())
+                };
+                hunks = 0;
+                position = 0;
+            } else if line.starts_with("@@") {
+                if let Some((old_len, start, new_len)) = parse_hunk_header(line) {
+                    old_remaining = old_len;
+                    new_remaining = new_len;
+                    new_line = start;
+                    if hunks > 0 {
+                        position += 1;
+                    }
+                    hunks += 1;
+                    hunk_line = 0;
+                }
+            } else if line.starts_with('\\') && hunks > 0 {
+                // "\ No newline at end of file" after the hunk's last line
+                position += 1;
+            }
+            continue;
+        }
+
+        position += 1;
+        hunk_line += 1;
+        match line.as_bytes().first() {
+            Some(b'+') => {
+                if let Some(path) = &current {
+                    f(path, AddedLine { line: new_line, position, hunk: hunks - 1, hunk_line });
+                }
+                new_line += 1;
+                new_remaining = new_remaining.saturating_sub(1);
+            }
+            Some(b'-') => old_remaining = old_remaining.saturating_sub(1),
+            Some(b'\\') => {} // "\ No newline at end of file"
+            _ => {
+                new_line += 1;
+                old_remaining = old_remaining.saturating_sub(1);
+                new_remaining = new_remaining.saturating_sub(1);
+            }
+        }
+    }
+}
+
+/// `@@ -a[,b] +c[,d] @@` → (old length, new start, new length)
+fn parse_hunk_header(line: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = line.split_whitespace().skip(1);
+    let old = parts.next()?.strip_prefix('-')?;
+    let new = parts.next()?.strip_prefix('+')?;
+    let len = |spec: &str| -> Option<(u32, u32)> {
+        match spec.split_once(',') {
+            Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+            None => Some((spec.parse().ok()?, 1)),
+        }
+    };
+    let (_, old_len) = len(old)?;
+    let (new_start, new_len) = len(new)?;
+    Some((old_len, new_start, new_len))
+}
+
+/// Set `introduced_in_diff` on every result of `result`.
+pub fn mark_introduced(result: &mut CheckResultJs, changed: &ChangedLines) {
+    let all = result
+        .violations
+        .iter_mut()
+        .chain(result.passed.iter_mut())
+        .chain(result.ignored.iter_mut());
+    for r in all {
+        mark_one(r, changed);
+    }
+}
+
+fn mark_one(result: &mut ContrastResult, changed: &ChangedLines) {
+    let end = result.end_line.unwrap_or(result.line).max(result.line);
+    result.introduced_in_diff = Some(changed.overlaps(&result.file, result.line, end));
+}
+
+/// Inline review comments for the violations of `result` on lines `diff`
+/// adds: one per added line, anchored to the first added line among each
+/// violation's lines (`line..=end_line`), in diff path and line order.
+pub fn annotate(diff: &str, result: &CheckResultJs, locale: Locale) -> Vec<DiffAnnotation> {
+    let mut added: HashMap<String, Vec<AddedLine>> = HashMap::new();
+    for_each_added_line(diff, |path, line| added.entry(path.replace('\\', "/")).or_default().push(line));
+
+    let mut annotations: BTreeMap<(String, u32), DiffAnnotation> = BTreeMap::new();
+    for violation in &result.violations {
+        let end = violation.end_line.unwrap_or(violation.line).max(violation.line);
+        let anchor = added
+            .iter()
+            .filter(|(path, _)| path_matches(&violation.file, path))
+            .flat_map(|(path, lines)| lines.iter().map(move |l| (path, l)))
+            .filter(|(_, l)| (violation.line..=end).contains(&l.line))
+            .min_by_key(|(path, l)| (l.line, path.as_str()));
+        let Some((path, at)) = anchor else {
+            continue;
+        };
+        annotations
+            .entry((path.clone(), at.line))
+            .or_insert_with(|| DiffAnnotation {
+                path: path.clone(),
+                line: at.line,
+                position: at.position,
+                hunk: at.hunk,
+                hunk_line: at.hunk_line,
+                body: String::new(),
+                violations: Vec::new(),
+            })
+            .violations
+            .push(violation.clone());
+    }
+
+    let mut annotations: Vec<DiffAnnotation> = annotations.into_values().collect();
+    for annotation in &mut annotations {
+        let entries: Vec<String> = annotation.violations.iter().map(|v| comment_entry(v, locale)).collect();
+        annotation.body = match entries.as_slice() {
+            [entry] => entry.clone(),
+            _ => entries.iter().map(|e| format!("- {}", e.replace('\n', "\n  "))).collect::<Vec<_>>().join("\n"),
+        };
+    }
+    annotations
+}
+
+/// Comment text for one violation: rule id, state, classes, ratio and fix.
+fn comment_entry(violation: &ContrastResult, locale: Locale) -> String {
+    let rule_id = rule_of(violation);
+    let required = required_ratio(rule_id, violation.is_large_text.unwrap_or(false))
+        * violation.safety_margin.unwrap_or(1.0);
+    let state = violation.interactive_state.as_deref().map(|s| format!(" ({})", s)).unwrap_or_default();
+    let detail = if rule_id == LOW_ALPHA_TEXT {
+        let alpha = format!("{:.0}", effective_alpha(violation) * 100.0);
+        i18n::format_message(locale, MessageKey::ReviewLowAlpha, &[&violation.text_class, &violation.bg_class, &alpha])
+    } else {
+        i18n::format_message(
+            locale,
+            MessageKey::ReviewViolation,
+            &[
+                &violation.text_class,
+                &violation.bg_class,
+                &violation.ratio.to_string(),
+                &((required * 100.0).round() / 100.0).to_string(),
+            ],
+        )
+    };
+    let mut entry = format!("**{}**{}: {}", rule_id, state, detail);
+    if let Some(fix) = &violation.remediation {
+        entry.push('\n');
+        entry.push_str(&i18n::format_message(locale, MessageKey::ReviewFix, &[fix]));
+    }
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::LineRange;
+
+    const DIFF: &str = "diff --git a/src/Card.tsx b/src/Card.tsx
+index 1111111..2222222 100644
+--- a/src/Card.tsx
++++ b/src/Card.tsx
+@@ -1,3 +1,4 @@
+ <div>
+-  <p className=\"text-gray-400\">old</p>
++  <p className=\"text-gray-300\">new</p>
++  <p className=\"text-gray-200\">added</p>
+ </div>
+@@ -10,2 +11,3 @@ export function Card() {
+ <span />
++<b className=\"text-white\" />
+ <i />
+diff --git a/src/Gone.tsx b/src/Gone.tsx
+deleted file mode 100644
+--- a/src/Gone.tsx
++++ /dev/null
+@@ -1 +0,0 @@
+-<p />
+";
+
+    #[test]
+    fn parses_added_lines_per_hunk() {
+        let changed = parse_unified_diff(DIFF);
+        assert!(!changed.contains("src/Card.tsx", 1));
+        assert!(changed.contains("src/Card.tsx", 2));
+        assert!(changed.contains("src/Card.tsx", 3));
+        assert!(!changed.contains("src/Card.tsx", 4));
+        assert!(!changed.contains("src/Card.tsx", 11));
+        assert!(changed.contains("src/Card.tsx", 12));
+        assert!(!changed.contains("src/Card.tsx", 13));
+    }
+
+    #[test]
+    fn deleted_file_contributes_nothing() {
+        let changed = parse_unified_diff(DIFF);
+        assert!(!changed.contains("src/Gone.tsx", 1));
+    }
+
+    #[test]
+    fn absolute_scan_paths_match_repo_relative_diff() {
+        let changed = parse_unified_diff(DIFF);
+        assert!(changed.contains("/home/ci/repo/src/Card.tsx", 2));
+        assert!(!changed.contains("/home/ci/repo/lib/src/Cards.tsx", 2));
+    }
+
+    #[test]
+    fn plus_plus_line_inside_hunk_is_content() {
+        let diff = "+++ b/a.ts\n@@ -0,0 +1,2 @@\n+++counter;\n+x\n";
+        let changed = parse_unified_diff(diff);
+        assert!(changed.contains("a.ts", 1));
+        assert!(changed.contains("a.ts", 2));
+    }
+
+    #[test]
+    fn explicit_ranges_merge_with_diff() {
+        let options = DiffOptions {
+            unified_diff: Some(DIFF.to_string()),
+            ranges: Some(vec![LineRange {
+                path: "src/Other.tsx".to_string(),
+                start_line: 5,
+                end_line: 7,
+            }]),
+        };
+        let changed = ChangedLines::from_options(&options);
+        assert!(changed.contains("src/Other.tsx", 6));
+        assert!(!changed.contains("src/Other.tsx", 8));
+        assert!(changed.contains("src/Card.tsx", 2));
+    }
+
+    #[test]
+    fn marks_results() {
+        use crate::math::checker::check_all_pairs;
+        use crate::types::ColorPair;
+        let pair = |line: u32| ColorPair {
+            file: "src/Card.tsx".to_string(),
+            line,
+            bg_hex: Some("#ffffff".to_string()),
+            text_hex: Some("#eeeeee".to_string()),
+            ..Default::default()
+        };
+        // A class value wrapped over lines 11-12 was edited on its second line
+        let wrapped = ColorPair { end_line: Some(12), ..pair(11) };
+        let r = check_all_pairs(&[pair(2), pair(4), wrapped], "AA", "#ffffff");
+        let mut js = CheckResultJs::from(r);
+        mark_introduced(&mut js, &parse_unified_diff(DIFF));
+        assert_eq!(js.violations[0].introduced_in_diff, Some(true));
+        assert_eq!(js.violations[1].introduced_in_diff, Some(false));
+        assert_eq!(js.violations[2].introduced_in_diff, Some(true));
+    }
+
+    #[test]
+    fn annotates_violations_on_added_lines() {
+        use crate::math::checker::check_all_pairs;
+        use crate::types::ColorPair;
+        let pair = |line: u32, text_class: &str| ColorPair {
+            file: "/repo/src/Card.tsx".to_string(),
+            line,
+            text_class: text_class.to_string(),
+            bg_class: "bg-white".to_string(),
+            bg_hex: Some("#ffffff".to_string()),
+            text_hex: Some("#d1d5db".to_string()),
+            ..Default::default()
+        };
+        let hover = ColorPair { interactive_state: Some("hover".to_string()), ..pair(3, "hover:text-gray-300") };
+        let wrapped = ColorPair { end_line: Some(12), ..pair(11, "text-white") };
+        let pairs = [pair(4, "text-gray-100"), pair(3, "text-gray-200"), hover, wrapped, pair(2, "text-gray-300")];
+        let mut js = CheckResultJs::from(check_all_pairs(&pairs, "AA", "#ffffff"));
+        js.violations[4].remediation = Some("replace `text-gray-300` with `text-gray-600` (ratio 7.56)".to_string());
+
+        let annotations = annotate(DIFF, &js, Locale::En);
+        let anchors: Vec<(&str, u32, u32, u32, u32, usize)> = annotations
+            .iter()
+            .map(|a| (a.path.as_str(), a.line, a.position, a.hunk, a.hunk_line, a.violations.len()))
+            .collect();
+        assert_eq!(
+            anchors,
+            [("src/Card.tsx", 2, 3, 0, 3, 1), ("src/Card.tsx", 3, 4, 0, 4, 2), ("src/Card.tsx", 12, 8, 1, 2, 1)]
+        );
+        assert_eq!(
+            annotations[0].body,
+            "**AA-TEXT-CONTRAST**: `text-gray-300` on `bg-white`: contrast 1.47:1, 4.5:1 required\n\
+             Fix: replace `text-gray-300` with `text-gray-600` (ratio 7.56)"
+        );
+        assert!(annotations[1].body.starts_with("- **AA-TEXT-CONTRAST**: `text-gray-200`"));
+        assert!(annotations[1].body.contains("\n- **AA-TEXT-CONTRAST** (hover): `hover:text-gray-300`"));
+    }
+}
@@ -1,8 +1,18 @@
-use std::collections::HashMap;
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::time::{Duration, Instant};
 
 use rayon::prelude::*;
 
-use crate::types::{ContainerEntry, ExtractOptions, FileInput, PreExtractedFile};
+use crate::limits::LimitTracker;
+use crate::metrics::{self, RunMetrics};
+use crate::parser::ScanConfig;
+use crate::sampling;
+use crate::file_kinds::{self, FilePolicy};
+use crate::sniff;
+use crate::vendored::PackageResolver;
+use crate::walker;
+use crate::types::{ExtractOptions, FileInput, LimitsHit, PreExtractedFile, ScanReport};
 
 /// Parse multiple JSX files in parallel and return extracted ClassRegion data.
 ///
@@ -10,40 +20,263 @@ use crate::types::{ContainerEntry, ExtractOptions, FileInput, PreExtractedFile};
 /// `ScanOrchestrator` instance (no shared mutable state across files).
 ///
 /// This is the main "hot path" entry point called from JS via NAPI.
+/// When `options.metrics_path` is set, an OpenMetrics snapshot of the run is
+/// written there after parsing completes. When `options.walk` is set, the
+/// walked files are scanned along with `options.file_contents`.
 pub fn extract_and_scan(options: &ExtractOptions) -> Vec<PreExtractedFile> {
-    let container_config: HashMap<String, String> = options
-        .container_config
-        .iter()
-        .map(|e| (e.component.clone(), e.bg_class.clone()))
-        .collect();
-
-    let portal_config: HashMap<String, String> = options
-        .portal_config
-        .iter()
-        .map(|e| (e.component.clone(), e.bg_class.clone()))
-        .collect();
-
-    options
-        .file_contents
+    extract_and_scan_batched(options, |_| ControlFlow::Continue(()))
+}
+
+/// Like `extract_and_scan()`, but files listed in `options.changed_paths` are
+/// parsed first, as their own batch, and handed to `on_batch` before the rest
+/// of the run starts. With `options.changed_only`, the rest is skipped, and
+/// so is every later batch once `on_batch` returns `Break`.
+///
+/// The returned files keep that order: changed files first, then the others,
+/// each batch in input order.
+pub fn extract_and_scan_batched<F>(options: &ExtractOptions, on_batch: F) -> Vec<PreExtractedFile>
+where
+    F: FnMut(&[PreExtractedFile]) -> ControlFlow<()>,
+{
+    run(&walker::resolve_inputs(options), on_batch).0
+}
+
+/// Shared body of the entry points; also returns the limits reached, if any.
+fn run<F>(options: &ExtractOptions, mut on_batch: F) -> (Vec<PreExtractedFile>, Option<LimitsHit>)
+where
+    F: FnMut(&[PreExtractedFile]) -> ControlFlow<()>,
+{
+    let run_start = Instant::now();
+    let mut scan_config = ScanConfig::from_options(options);
+    // Layout annotations apply across files, so collect them from the whole
+    // input set — even files excluded by sampling or `changed_only`.
+    crate::parser::layout::merge_layouts(&options.file_contents, &mut scan_config.container_config);
+    let unknown = crate::rules::RuleVisitor::unknown_rules(&scan_config.rules);
+    if !unknown.is_empty() {
+        log::warn!("ignoring unknown rules: {}", unknown.join(", "));
+    }
+
+    let inputs = sampling::select(&options.file_contents, options.sample, options.seed);
+    if inputs.len() < options.file_contents.len() {
+        log::info!(
+            "sampling {} of {} files",
+            inputs.len(),
+            options.file_contents.len()
+        );
+    }
+
+    let inputs = eligible(options, inputs);
+
+    let mut batches: Vec<(Vec<&FileInput>, bool)> = match &options.changed_paths {
+        Some(changed) => {
+            let (first, rest): (Vec<&FileInput>, Vec<&FileInput>) =
+                inputs.iter().partition(|f| is_changed(&f.path, changed));
+            if options.changed_only == Some(true) {
+                vec![(first, true)]
+            } else {
+                vec![(first, true), (rest, false)]
+            }
+        }
+        None => vec![(inputs.clone(), false)],
+    };
+
+    let limits = options.limits.clone().unwrap_or_default();
+    let mut tracker = LimitTracker::default();
+    crate::limits::admit_files(&mut batches, &limits, &mut tracker);
+    let deadline = crate::limits::deadline(&limits, run_start);
+
+    let mut packages = PackageResolver::default();
+    let mut timed: Vec<(PreExtractedFile, Duration)> = Vec::with_capacity(inputs.len());
+    let mut scanned_inputs: Vec<&FileInput> = Vec::with_capacity(inputs.len());
+    for (batch, changed) in batches {
+        if batch.is_empty() {
+            continue;
+        }
+        let batch_start = Instant::now();
+        let results = scan_batch(&batch, &scan_config, changed, deadline);
+        let mut batch_timed = Vec::with_capacity(results.len());
+        let mut out_of_time = Vec::new();
+        for (input, result) in batch.iter().zip(results) {
+            match result {
+                Some((mut file, elapsed)) => {
+                    crate::limits::truncate_regions(&mut file, &limits, &mut tracker);
+                    file.package = packages.resolve(&file.path);
+                    file.file_kind = file_kinds::classify(&file.path).map(|kind| kind.as_str().to_string());
+                    scanned_inputs.push(input);
+                    batch_timed.push((file, elapsed));
+                }
+                None => out_of_time.push(input.path.clone()),
+            }
+        }
+        tracker.skipped_by_budget(out_of_time);
+        if changed {
+            log::info!(
+                "scanned {} changed files in {:.3}ms",
+                batch_timed.len(),
+                batch_start.elapsed().as_secs_f64() * 1000.0
+            );
+        }
+        let files: Vec<PreExtractedFile> = batch_timed.iter().map(|(f, _)| f.clone()).collect();
+        let flow = on_batch(&files);
+        timed.extend(batch_timed);
+        if flow.is_break() {
+            log::info!("run stopped after a batch of {} files", files.len());
+            break;
+        }
+    }
+
+    log::info!(
+        "scanned {} files in {:.3}ms",
+        timed.len(),
+        run_start.elapsed().as_secs_f64() * 1000.0
+    );
+
+    if let Some(path) = &options.metrics_path {
+        let run_metrics = collect_metrics(&scanned_inputs, &timed, run_start.elapsed());
+        // Metrics are best-effort: a write failure must never fail the audit itself.
+        if let Err(err) = metrics::write_metrics(Path::new(path), &run_metrics) {
+            log::warn!("could not write metrics to {}: {}", path, err);
+        }
+    }
+
+    let files = timed.into_iter().map(|(file, _)| file).collect();
+    (files, tracker.into_report())
+}
+
+/// `inputs` without the files the run skips whatever the sample: `.js`
+/// modules without JSX (`sniff_js`), kinds with a Skip policy and, with
+/// `changed_only`, unchanged files.
+fn eligible<'a>(options: &ExtractOptions, inputs: Vec<&'a FileInput>) -> Vec<&'a FileInput> {
+    let inputs = if options.sniff_js == Some(true) {
+        let before = inputs.len();
+        let kept: Vec<&FileInput> = inputs
+            .into_par_iter()
+            .filter(|f| !sniff::is_sniffed_path(&f.path) || sniff::looks_like_jsx(&f.content))
+            .collect();
+        log::debug!("skipped {} .js files without JSX", before - kept.len());
+        kept
+    } else {
+        inputs
+    };
+
+    let inputs = match &options.file_kind_policies {
+        Some(policies) => {
+            let before = inputs.len();
+            let kept: Vec<&FileInput> = inputs
+                .into_iter()
+                .filter(|f| file_kinds::policy_for_path(policies, &f.path) != FilePolicy::Skip)
+                .collect();
+            log::debug!("skipped {} files by file kind policy", before - kept.len());
+            kept
+        }
+        None => inputs,
+    };
+
+    match &options.changed_paths {
+        Some(changed) if options.changed_only == Some(true) => {
+            inputs.into_iter().filter(|f| is_changed(&f.path, changed)).collect()
+        }
+        _ => inputs,
+    }
+}
+
+/// Parse one batch of files in parallel, keeping input order. Files not
+/// started before `deadline` are skipped (`None`).
+fn scan_batch(
+    inputs: &[&FileInput],
+    scan_config: &ScanConfig,
+    changed: bool,
+    deadline: Option<Instant>,
+) -> Vec<Option<(PreExtractedFile, Duration)>> {
+    inputs
         .par_iter()
         .map(|file_input| {
-            let regions = crate::parser::scan_file(
-                &file_input.content,
-                &container_config,
-                &portal_config,
-                &options.default_bg,
+            let file_start = Instant::now();
+            if deadline.is_some_and(|d| file_start >= d) {
+                return None;
+            }
+            let (regions, findings, diagnostics, file_ignore) =
+                crate::parser::scan_source(&file_input.path, &file_input.content, scan_config);
+            let elapsed = file_start.elapsed();
+            log::debug!(
+                "parsed {} in {:.3}ms ({} regions)",
+                file_input.path,
+                elapsed.as_secs_f64() * 1000.0,
+                regions.len()
             );
-            PreExtractedFile {
+            if let Some(first) = diagnostics.first() {
+                log::info!("{} only partially scanned: {} (line {})", file_input.path, first.message, first.line);
+            }
+            let file = PreExtractedFile {
                 path: file_input.path.clone(),
                 regions,
-            }
+                changed: changed.then_some(true),
+                findings: (!scan_config.rules.is_empty()).then_some(findings),
+                diagnostics: (!diagnostics.is_empty()).then_some(diagnostics),
+                ignored: file_ignore.is_some().then_some(true),
+                ..Default::default()
+            };
+            Some((file, elapsed))
         })
         .collect()
 }
 
+/// Whether `path` is one of `changed` (see `diff::path_matches`).
+fn is_changed(path: &str, changed: &[String]) -> bool {
+    changed.iter().any(|c| crate::diff::path_matches(path, c))
+}
+
+/// Run `extract_and_scan()` and attach the reproducibility manifest, plus the
+/// sampling summary when `options.sample` restricted the run and the limits
+/// reached when `options.limits` cut it short.
+pub fn extract_and_scan_with_report(options: &ExtractOptions) -> ScanReport {
+    let options = &*walker::resolve_inputs(options);
+    let (files, limits_hit) = run(options, |_| ControlFlow::Continue(()));
+    let sampling = sampling::effective_rate(options.sample).map(|rate| {
+        // Extrapolate to the files the run would scan unsampled, not to every input
+        let eligible_files = eligible(options, options.file_contents.iter().collect()).len();
+        sampling::summarize(
+            rate,
+            options.seed.unwrap_or(sampling::DEFAULT_SEED),
+            options.file_contents.len(),
+            eligible_files,
+            &files,
+        )
+    });
+    ScanReport {
+        files,
+        manifest: crate::manifest::build_manifest(options),
+        sampling,
+        limits_hit,
+    }
+}
+
+/// Aggregate per-file timings and region counts into a run snapshot.
+fn collect_metrics(
+    inputs: &[&FileInput],
+    timed: &[(PreExtractedFile, Duration)],
+    total: Duration,
+) -> RunMetrics {
+    let mut run = RunMetrics {
+        files_scanned: timed.len() as u64,
+        bytes_scanned: inputs.iter().map(|f| f.content.len() as u64).sum(),
+        total_duration_secs: total.as_secs_f64(),
+        ..RunMetrics::default()
+    };
+    for (file, elapsed) in timed {
+        let secs = elapsed.as_secs_f64();
+        run.parse_duration_secs += secs;
+        run.max_file_duration_secs = run.max_file_duration_secs.max(secs);
+        run.regions_extracted += file.regions.len() as u64;
+        run.regions_ignored += file.regions.iter().filter(|r| r.ignored == Some(true)).count() as u64;
+    }
+    run
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{ContainerEntry, FileKindPolicies, ScanLimits};
 
     fn make_options(files: Vec<(&str, &str)>, containers: &[(&str, &str)]) -> ExtractOptions {
         ExtractOptions {
@@ -63,6 +296,7 @@ mod tests {
                 .collect(),
             portal_config: vec![],
             default_bg: "bg-background".to_string(),
+            ..Default::default()
         }
     }
 
@@ -98,6 +332,130 @@ mod tests {
         assert!(paths.contains(&"c.tsx"));
     }
 
+    #[test]
+    fn custom_class_attributes_extracted() {
+        let options = ExtractOptions {
+            class_attributes: Some(vec!["containerClassName".to_string()]),
+            ..make_options(
+                vec![("field.tsx", r##"<Field containerClassName="bg-card" className="text-white" />"##)],
+                &[],
+            )
+        };
+        let results = extract_and_scan(&options);
+        let contents: Vec<&str> = results[0].regions.iter().map(|r| r.content.as_str()).collect();
+        assert_eq!(contents, vec!["bg-card", "text-white"]);
+    }
+
+    #[test]
+    fn custom_class_functions_extracted() {
+        let options = ExtractOptions {
+            class_functions: Some(vec!["classNames".to_string()]),
+            ..make_options(vec![("a.tsx", r##"<a className={classNames("text-white", "bg-card")} />"##)], &[])
+        };
+        let results = extract_and_scan(&options);
+        assert_eq!(results[0].regions[0].content, "text-white bg-card");
+        assert_eq!(results[0].regions[0].provenance.as_deref(), Some("cn"));
+    }
+
+    #[test]
+    fn css_module_classes_resolved() {
+        let source = r##"import styles from "./Card.module.css";
+<div className={styles.card}><p className={styles["card-title"]}>a</p><p className={styles.muted}>b</p></div>"##;
+        let options = ExtractOptions {
+            css_modules: Some(crate::parser::css_modules::parse_css_module(
+                "src/Card.module.css",
+                ".card { background: #fff } .card-title { color: #333 }",
+            )),
+            ..make_options(vec![("src/Card.tsx", source)], &[])
+        };
+        let results = extract_and_scan(&options);
+        let regions = &results[0].regions;
+        assert_eq!(regions[0].content, "bg-[#ffffff]");
+        assert_eq!(regions[0].provenance.as_deref(), Some("css-module"));
+        assert_eq!(regions[1].content, "text-[#333333]");
+        // Not in the map: flagged like any other dynamic class
+        assert_eq!(regions[2].content, "");
+        assert_eq!(regions[2].unresolved_dynamic_class, Some(true));
+    }
+
+    #[test]
+    fn top_level_constants_substituted() {
+        let source = r##"const headerClasses = "bg-slate-900 text-white";
+// const commented = "text-red-500";
+export function Header() {
+  const local = "text-black";
+  return <header className={headerClasses}><p className={local}>don't</p></header>;
+}"##;
+        let results = extract_and_scan(&make_options(vec![("src/Header.tsx", source)], &[]));
+        let regions = &results[0].regions;
+        assert_eq!(regions[0].content, "bg-slate-900 text-white");
+        assert_eq!(regions[0].provenance.as_deref(), Some("attribute"));
+        // Block-scoped constants are not tracked
+        assert_eq!(regions[1].unresolved_dynamic_class, Some(true));
+    }
+
+    #[test]
+    fn variant_map_entries_become_regions() {
+        let source = r##"const variants: Record<Variant, string> = {
+  primary: "bg-blue-600 text-white",
+  "ghost": 'text-blue-600',
+  size: sizes.md,
+};
+export const Button = ({ variant }) => <button className={variants[variant]}>a</button>;
+export const Link = (props) => <a className={variants[props.kind]}>b</a>;
+export const Ghost = () => <span className={variants.ghost}>c</span>;"##;
+        let results = extract_and_scan(&make_options(vec![("src/Button.tsx", source)], &[]));
+        let regions: Vec<(&str, Option<&str>, &str)> = results[0]
+            .regions
+            .iter()
+            .map(|r| (r.content.as_str(), r.variant_key.as_deref(), r.tag_name.as_str()))
+            .collect();
+        assert_eq!(
+            regions,
+            [
+                ("bg-blue-600 text-white", Some("variant=primary"), "button"),
+                ("text-blue-600", Some("variant=ghost"), "button"),
+                ("bg-blue-600 text-white", Some("kind=primary"), "a"),
+                ("text-blue-600", Some("kind=ghost"), "a"),
+                ("text-blue-600", None, "span"),
+            ]
+        );
+        assert_eq!(results[0].regions[0].provenance.as_deref(), Some("variant-map"));
+        // Both entries sit on the parent's bg
+        assert_eq!(results[0].regions[1].context_bg, "bg-background");
+    }
+
+    #[test]
+    fn imported_library_constants_resolved() {
+        let styles = r##"export const headerClasses = "bg-slate-900 text-white";
+export const buttonVariants = { primary: "bg-blue-600 text-white", ghost: "text-blue-600" };"##;
+        let source = r##"import { headerClasses, buttonVariants as variants } from "./styles";
+import * as theme from "@/styles";
+export const Header = ({ variant }) => (
+  <header className={headerClasses}>
+    <button className={variants[variant]}>a</button>
+    <p className={theme.headerClasses}>b</p>
+  </header>
+);"##;
+        let options = ExtractOptions {
+            library_files: Some(vec![FileInput { path: "src/styles.ts".to_string(), content: styles.to_string() }]),
+            ..make_options(vec![("src/Header.tsx", source)], &[])
+        };
+        let results = extract_and_scan(&options);
+        assert_eq!(results.len(), 1);
+        let regions: Vec<(&str, Option<&str>)> =
+            results[0].regions.iter().map(|r| (r.content.as_str(), r.variant_key.as_deref())).collect();
+        assert_eq!(
+            regions,
+            [
+                ("bg-slate-900 text-white", None),
+                ("bg-blue-600 text-white", Some("variant=primary")),
+                ("text-blue-600", Some("variant=ghost")),
+                ("bg-slate-900 text-white", None),
+            ]
+        );
+    }
+
     #[test]
     fn container_config_propagated() {
         let options = make_options(
@@ -111,6 +469,37 @@ mod tests {
         assert_eq!(results[0].regions[0].context_bg, "bg-card");
     }
 
+    #[test]
+    fn layout_annotation_applies_across_files() {
+        let options = make_options(
+            vec![
+                (
+                    "Shell.tsx",
+                    "// @a11y-layout bg:bg-slate-950\nexport function Shell({ children }) { return <>{children}</>; }",
+                ),
+                ("page.tsx", r##"<Shell><p className="text-white">x</p></Shell>"##),
+            ],
+            &[],
+        );
+        let results = extract_and_scan(&options);
+        let page = results.iter().find(|f| f.path == "page.tsx").unwrap();
+        assert_eq!(page.regions[0].context_bg, "bg-slate-950");
+    }
+
+    #[test]
+    fn vendored_files_attributed_to_package() {
+        let options = make_options(
+            vec![
+                ("node_modules/@ourorg/ui/dist/Badge.jsx", r##"<span className="text-white">x</span>"##),
+                ("src/App.tsx", r##"<p className="text-white">x</p>"##),
+            ],
+            &[],
+        );
+        let results = extract_and_scan(&options);
+        assert_eq!(results[0].package.as_deref(), Some("@ourorg/ui"));
+        assert_eq!(results[1].package, None);
+    }
+
     #[test]
     fn empty_files_returns_empty_regions() {
         let options = make_options(vec![("empty.tsx", "")], &[]);
@@ -148,6 +537,7 @@ mod tests {
             container_config: vec![],
             portal_config: vec![],
             default_bg: "bg-background".to_string(),
+            ..Default::default()
         };
         let results = extract_and_scan(&options);
         assert_eq!(results.len(), 50);
@@ -156,4 +546,311 @@ mod tests {
             assert_eq!(result.regions.len(), 1, "file {} has {} regions", result.path, result.regions.len());
         }
     }
+
+    #[test]
+    fn metrics_written_when_path_set() {
+        let path = std::env::temp_dir().join(format!("a11y-engine-metrics-{}.prom", std::process::id()));
+        let mut options = make_options(
+            vec![
+                ("a.tsx", r##"<div className="bg-red-500 text-white">a</div>"##),
+                ("b.tsx", "// a11y-ignore\n<p className=\"text-black\">b</p>"),
+            ],
+            &[],
+        );
+        options.metrics_path = Some(path.to_string_lossy().to_string());
+        extract_and_scan(&options);
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert!(text.contains("\na11y_audit_files_scanned 2\n"));
+        assert!(text.contains("\na11y_audit_regions_extracted 2\n"));
+        assert!(text.contains("\na11y_audit_regions_ignored 1\n"));
+        assert!(text.ends_with("# EOF\n"));
+    }
+
+    #[test]
+    fn ignore_file_annotation_ignores_every_region() {
+        let generated = r##"<p className="text-gray-400">a</p>
+{/* a11y-ignore: tooltip */}
+<b className="text-gray-300">b</b>
+{/* a11y-ignore NONTEXT-CONTRAST */}
+<i className="text-gray-300 border-gray-200">c</i>
+// a11y-ignore-file: generated code"##;
+        let options = make_options(
+            vec![("gen.tsx", generated), ("app.tsx", r##"<p className="text-gray-400">a</p>"##)],
+            &[],
+        );
+        let results = extract_and_scan(&options);
+        assert_eq!(results[0].ignored, Some(true));
+        let reasons: Vec<(Option<bool>, Option<&str>)> =
+            results[0].regions.iter().map(|r| (r.ignored, r.ignore_reason.as_deref())).collect();
+        assert_eq!(
+            reasons,
+            [
+                (Some(true), Some("generated code")),
+                (Some(true), Some("tooltip")),
+                (Some(true), Some("generated code")),
+            ]
+        );
+        assert_eq!(results[0].regions[2].ignored_rules, None);
+        assert_eq!(results[1].ignored, None);
+        assert_eq!(results[1].regions[0].ignored, None);
+    }
+
+    #[test]
+    fn ignore_range_annotation_ignores_regions_inside() {
+        let source = r##"<p className="text-gray-400">a</p>
+{/* a11y-ignore-start: legacy banner */}
+<b className="text-gray-300">b</b>
+<i className="text-gray-300">c</i>
+{/* a11y-ignore-end */}
+<u className="text-gray-300">d</u>
+{/* a11y-ignore-start */}
+<s className="text-gray-300">e</s>"##;
+        let results = extract_and_scan(&make_options(vec![("a.tsx", source)], &[]));
+        let reasons: Vec<Option<&str>> = results[0].regions.iter().map(|r| r.ignore_reason.as_deref()).collect();
+        assert_eq!(reasons, [None, Some("legacy banner"), Some("legacy banner"), None, Some("suppressed")]);
+        let diagnostics = results[0].diagnostics.as_deref().unwrap_or_default();
+        let kinds: Vec<(u32, &str)> = diagnostics.iter().map(|d| (d.line, d.kind.as_str())).collect();
+        assert_eq!(kinds, [(7, "unclosed-ignore-range")]);
+        assert_eq!(diagnostics[0].message, "a11y-ignore-start never closed by a11y-ignore-end");
+
+        let mut options = make_options(vec![("a.tsx", source)], &[]);
+        options.locale = Some("it".to_string());
+        let results = extract_and_scan(&options);
+        let diagnostics = results[0].diagnostics.as_deref().unwrap_or_default();
+        assert_eq!(diagnostics[0].message, "a11y-ignore-start mai chiuso da a11y-ignore-end");
+    }
+
+    #[test]
+    fn unwritable_metrics_path_does_not_fail_scan() {
+        let mut options = make_options(vec![("a.tsx", r##"<p className="text-white">a</p>"##)], &[]);
+        options.metrics_path = Some("/nonexistent-dir/metrics.prom".to_string());
+        let results = extract_and_scan(&options);
+        assert_eq!(results[0].regions.len(), 1);
+    }
+
+    #[test]
+    fn report_includes_manifest() {
+        let options = make_options(vec![("a.tsx", r##"<p className="text-white">a</p>"##)], &[]);
+        let report = extract_and_scan_with_report(&options);
+        assert_eq!(report.files.len(), 1);
+        assert_eq!(report.manifest.files.len(), 1);
+        assert_eq!(report.manifest.files[0].path, "a.tsx");
+        assert!(report.sampling.is_none());
+        assert!(report.limits_hit.is_none());
+    }
+
+    #[test]
+    fn limits_return_partial_results_with_report() {
+        let mut options = make_options(
+            vec![
+                ("a.tsx", r##"<p className="text-white">a</p><p className="text-black">b</p>"##),
+                ("b.tsx", r##"<p className="text-white">a</p>"##),
+            ],
+            &[],
+        );
+        options.limits = Some(ScanLimits {
+            max_files: Some(1),
+            max_regions_per_file: Some(1),
+            ..Default::default()
+        });
+        let report = extract_and_scan_with_report(&options);
+        assert_eq!(report.files.len(), 1);
+        assert_eq!(report.files[0].regions.len(), 1);
+        let hit = report.limits_hit.unwrap();
+        assert_eq!(hit.limits, vec!["maxFiles", "maxRegionsPerFile"]);
+        assert_eq!(hit.skipped_files, vec!["b.tsx"]);
+        assert_eq!(hit.truncated_files, vec!["a.tsx"]);
+    }
+
+    #[test]
+    fn exhausted_time_budget_skips_remaining_files() {
+        let mut options = make_options(vec![("a.tsx", r##"<p className="text-white">a</p>"##)], &[]);
+        options.limits = Some(ScanLimits {
+            time_budget_ms: Some(0),
+            ..Default::default()
+        });
+        let report = extract_and_scan_with_report(&options);
+        assert!(report.files.is_empty());
+        assert_eq!(report.limits_hit.unwrap().limits, vec!["timeBudgetMs"]);
+    }
+
+    #[test]
+    fn sniff_js_skips_plain_modules_only() {
+        let mut options = make_options(
+            vec![
+                ("App.js", r##"export const App = () => <p className="text-white">a</p>;"##),
+                ("config.js", r##"module.exports = { className: "text-white" };"##),
+                ("Plain.tsx", r##"<p className="text-white">b</p>"##),
+            ],
+            &[],
+        );
+        options.sniff_js = Some(true);
+        let paths: Vec<String> = extract_and_scan(&options).into_iter().map(|f| f.path).collect();
+        assert_eq!(paths, vec!["App.js", "Plain.tsx"]);
+    }
+
+    #[test]
+    fn skip_policy_leaves_kind_out_of_scan() {
+        let mut options = make_options(
+            vec![
+                ("src/App.tsx", r##"<p className="text-white">a</p>"##),
+                ("src/App.test.tsx", r##"<p className="text-white">b</p>"##),
+                ("src/App.stories.tsx", r##"<p className="text-white">c</p>"##),
+            ],
+            &[],
+        );
+        options.file_kind_policies = Some(FileKindPolicies { test: Some("skip".to_string()), ..Default::default() });
+        let files = extract_and_scan(&options);
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/App.tsx", "src/App.stories.tsx"]);
+        assert_eq!(files[1].file_kind.as_deref(), Some("story"));
+    }
+
+    #[test]
+    fn changed_paths_match_relative_and_absolute() {
+        let changed = vec!["src/a.tsx".to_string(), "./b.tsx".to_string()];
+        assert!(is_changed("src/a.tsx", &changed));
+        assert!(is_changed("/repo/src/a.tsx", &changed));
+        assert!(is_changed("C:\\repo\\b.tsx", &changed));
+        assert!(!is_changed("/repo/other/xsrc/a.tsx", &changed));
+        assert!(!is_changed("/repo/c.tsx", &changed));
+    }
+
+    #[test]
+    fn changed_files_scanned_first_as_own_batch() {
+        let mut options = make_options(
+            vec![
+                ("a.tsx", r##"<p className="text-a">a</p>"##),
+                ("b.tsx", r##"<p className="text-b">b</p>"##),
+                ("c.tsx", r##"<p className="text-c">c</p>"##),
+            ],
+            &[],
+        );
+        options.changed_paths = Some(vec!["c.tsx".to_string()]);
+        let mut batches: Vec<Vec<String>> = Vec::new();
+        let files = extract_and_scan_batched(&options, |batch| {
+            batches.push(batch.iter().map(|f| f.path.clone()).collect());
+            ControlFlow::Continue(())
+        });
+        assert_eq!(batches, vec![vec!["c.tsx".to_string()], vec!["a.tsx".to_string(), "b.tsx".to_string()]]);
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["c.tsx", "a.tsx", "b.tsx"]);
+        assert_eq!(files[0].changed, Some(true));
+        assert_eq!(files[1].changed, None);
+    }
+
+    #[test]
+    fn break_from_on_batch_stops_the_run() {
+        let mut options = make_options(
+            vec![("a.tsx", r##"<p className="text-a">a</p>"##), ("b.tsx", r##"<p className="text-b">b</p>"##)],
+            &[],
+        );
+        options.changed_paths = Some(vec!["b.tsx".to_string()]);
+        let mut calls = 0;
+        let files = extract_and_scan_batched(&options, |_| {
+            calls += 1;
+            ControlFlow::Break(())
+        });
+        assert_eq!(calls, 1);
+        assert_eq!(files.iter().map(|f| f.path.as_str()).collect::<Vec<_>>(), vec!["b.tsx"]);
+    }
+
+    #[test]
+    fn changed_only_skips_unchanged_files() {
+        let mut options = make_options(
+            vec![
+                ("a.tsx", r##"<p className="text-a">a</p>"##),
+                ("b.tsx", r##"<p className="text-b">b</p>"##),
+            ],
+            &[],
+        );
+        options.changed_paths = Some(vec!["b.tsx".to_string()]);
+        options.changed_only = Some(true);
+        let files = extract_and_scan(&options);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "b.tsx");
+    }
+
+    #[test]
+    fn sampled_run_scans_subset_and_reports_summary() {
+        let paths: Vec<String> = (0..100).map(|i| format!("f{}.tsx", i)).collect();
+        let files: Vec<(&str, &str)> = paths
+            .iter()
+            .map(|p| (p.as_str(), r##"<p className="text-white">a</p>"##))
+            .collect();
+        let mut options = make_options(files, &[]);
+        options.sample = Some(0.2);
+        options.seed = Some(42);
+        let report = extract_and_scan_with_report(&options);
+        let summary = report.sampling.unwrap();
+        assert_eq!(summary.total_files, 100);
+        assert_eq!(summary.sampled_files as usize, report.files.len());
+        assert!(report.files.len() < 100 && !report.files.is_empty());
+        // every sampled file has one region, so the estimate recovers the total
+        assert_eq!(summary.estimated_regions, 100.0);
+        // manifest still covers every input file
+        assert_eq!(report.manifest.files.len(), 100);
+    }
+
+    #[test]
+    fn sampled_run_extrapolates_to_eligible_files_only() {
+        let paths: Vec<String> = (0..100)
+            .map(|i| if i % 2 == 0 { format!("f{}.tsx", i) } else { format!("m{}.js", i) })
+            .collect();
+        let files: Vec<(&str, &str)> = paths
+            .iter()
+            .map(|p| {
+                let jsx = r##"<p className="text-white">a</p>"##;
+                (p.as_str(), if p.ends_with(".js") { "export const x = 1;" } else { jsx })
+            })
+            .collect();
+        let mut options = make_options(files, &[]);
+        options.sample = Some(0.5);
+        options.seed = Some(42);
+        options.sniff_js = Some(true);
+        let summary = extract_and_scan_with_report(&options).sampling.unwrap();
+        assert_eq!((summary.total_files, summary.eligible_files), (100, 50));
+        assert_eq!(summary.estimated_regions, 50.0);
+
+        options.changed_paths = Some(paths.iter().step_by(2).take(10).cloned().collect());
+        options.changed_only = Some(true);
+        let report = extract_and_scan_with_report(&options);
+        let summary = report.sampling.unwrap();
+        assert!(!report.files.is_empty());
+        assert_eq!(summary.eligible_files, 10);
+        assert_eq!(summary.estimated_regions, 10.0);
+    }
+
+    #[test]
+    fn rules_opt_in_adds_findings() {
+        let source = r##"<div>
+  <span className="h-2 w-2 rounded-full bg-red-500" />
+</div>"##;
+        let mut options = make_options(vec![("a.tsx", source)], &[]);
+        assert!(extract_and_scan(&options)[0].findings.is_none());
+
+        options.rules = Some(vec!["use-of-color".to_string()]);
+        let files = extract_and_scan(&options);
+        let findings = files[0].findings.as_ref().unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line, 2);
+        // the contrast regions are unaffected
+        assert_eq!(files[0].regions.len(), 1);
+    }
+
+    #[test]
+    fn dispatches_vue_files_by_extension() {
+        let vue = "<template>\n  <Card>\n    <p :class=\"{ 'text-muted': dim }\">x</p>\n  </Card>\n</template>\n";
+        let options = make_options(
+            vec![("App.vue", vue), ("App.tsx", r##"<p className="text-white">a</p>"##)],
+            &[("Card", "bg-card")],
+        );
+        let files = extract_and_scan(&options);
+        assert_eq!(files[0].regions.len(), 1);
+        assert_eq!(files[0].regions[0].content, "text-muted");
+        assert_eq!(files[0].regions[0].start_line, 3);
+        assert_eq!(files[0].regions[0].context_bg, "bg-card");
+        assert_eq!(files[1].regions[0].content, "text-white");
+    }
 }
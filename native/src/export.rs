@@ -0,0 +1,261 @@
+//! Flattened result table for analytics: one row per checked pair, one
+//! column per scalar field, exported as CSV or (with the `parquet` feature)
+//! Parquet.
+
+use crate::types::{CheckResultJs, ContrastResult};
+
+/// A table cell; None is an empty CSV field / a Parquet null.
+enum Cell {
+    Text(Option<String>),
+    Int(Option<u32>),
+    Float(Option<f64>),
+    Bool(Option<bool>),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Text,
+    Int,
+    Float,
+    Bool,
+}
+
+impl Cell {
+    #[cfg_attr(not(feature = "parquet"), allow(dead_code))]
+    fn is_present(&self) -> bool {
+        match self {
+            Cell::Text(v) => v.is_some(),
+            Cell::Int(v) => v.is_some(),
+            Cell::Float(v) => v.is_some(),
+            Cell::Bool(v) => v.is_some(),
+        }
+    }
+}
+
+struct Column {
+    name: &'static str,
+    /// Variant of `Cell` `value` returns
+    #[cfg_attr(not(feature = "parquet"), allow(dead_code))]
+    kind: Kind,
+    value: fn(&str, &ContrastResult) -> Cell,
+}
+
+/// Columns of the table, in order. `status` is the list the row came from.
+const COLUMNS: &[Column] = &[
+    Column { name: "status", kind: Kind::Text, value: |status, _| Cell::Text(Some(status.to_string())) },
+    Column { name: "file", kind: Kind::Text, value: |_, r| Cell::Text(Some(r.file.clone())) },
+    Column { name: "line", kind: Kind::Int, value: |_, r| Cell::Int(Some(r.line)) },
+    Column { name: "end_line", kind: Kind::Int, value: |_, r| Cell::Int(r.end_line) },
+    Column { name: "tag_name", kind: Kind::Text, value: |_, r| Cell::Text(r.tag_name.clone()) },
+    Column { name: "text_class", kind: Kind::Text, value: |_, r| Cell::Text(Some(r.text_class.clone())) },
+    Column { name: "bg_class", kind: Kind::Text, value: |_, r| Cell::Text(Some(r.bg_class.clone())) },
+    Column { name: "text_hex", kind: Kind::Text, value: |_, r| Cell::Text(r.text_hex.clone()) },
+    Column { name: "bg_hex", kind: Kind::Text, value: |_, r| Cell::Text(r.bg_hex.clone()) },
+    Column { name: "ratio", kind: Kind::Float, value: |_, r| Cell::Float(Some(r.ratio)) },
+    Column { name: "apca_lc", kind: Kind::Float, value: |_, r| Cell::Float(r.apca_lc) },
+    Column { name: "pass_aa", kind: Kind::Bool, value: |_, r| Cell::Bool(Some(r.pass_aa)) },
+    Column { name: "pass_aaa", kind: Kind::Bool, value: |_, r| Cell::Bool(Some(r.pass_aaa)) },
+    Column { name: "is_large_text", kind: Kind::Bool, value: |_, r| Cell::Bool(r.is_large_text) },
+    Column { name: "pair_type", kind: Kind::Text, value: |_, r| Cell::Text(r.pair_type.clone()) },
+    Column { name: "interactive_state", kind: Kind::Text, value: |_, r| Cell::Text(r.interactive_state.clone()) },
+    Column { name: "theme", kind: Kind::Text, value: |_, r| Cell::Text(r.theme.clone()) },
+    Column { name: "breakpoint", kind: Kind::Text, value: |_, r| Cell::Text(r.breakpoint.clone()) },
+    Column { name: "package", kind: Kind::Text, value: |_, r| Cell::Text(r.package.clone()) },
+    Column { name: "severity", kind: Kind::Text, value: |_, r| Cell::Text(r.severity.clone()) },
+    Column { name: "file_kind", kind: Kind::Text, value: |_, r| Cell::Text(r.file_kind.clone()) },
+    Column { name: "id", kind: Kind::Text, value: |_, r| Cell::Text(r.id.clone()) },
+    Column { name: "rule_id", kind: Kind::Text, value: |_, r| Cell::Text(r.rule_id.clone()) },
+    Column { name: "fingerprint", kind: Kind::Text, value: |_, r| Cell::Text(r.fingerprint.clone()) },
+    Column { name: "inherited_from", kind: Kind::Text, value: |_, r| Cell::Text(r.inherited_from.clone()) },
+    Column { name: "ignore_reason", kind: Kind::Text, value: |_, r| Cell::Text(r.ignore_reason.clone()) },
+];
+
+/// Every result with the list it came from: "violation", "passed",
+/// "ignored", "informational" or "inherited".
+pub fn rows(result: &CheckResultJs) -> Vec<(&'static str, &ContrastResult)> {
+    let lists: [(&'static str, &[ContrastResult]); 5] = [
+        ("violation", &result.violations),
+        ("passed", &result.passed),
+        ("ignored", &result.ignored),
+        ("informational", result.informational.as_deref().unwrap_or_default()),
+        ("inherited", result.inherited.as_deref().unwrap_or_default()),
+    ];
+    lists.into_iter().flat_map(|(status, list)| list.iter().map(move |r| (status, r))).collect()
+}
+
+/// The table as RFC 4180 CSV with a header row (`\n` line endings).
+pub fn to_csv(result: &CheckResultJs) -> String {
+    let header: Vec<&str> = COLUMNS.iter().map(|c| c.name).collect();
+    let mut out = header.join(",");
+    out.push('\n');
+    for (status, r) in rows(result) {
+        let fields: Vec<String> = COLUMNS.iter().map(|c| csv_field((c.value)(status, r))).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn csv_field(cell: Cell) -> String {
+    match cell {
+        Cell::Text(Some(text)) if text.contains([',', '"', '\n', '\r']) => {
+            format!("\"{}\"", text.replace('"', "\"\""))
+        }
+        Cell::Text(value) => value.unwrap_or_default(),
+        Cell::Int(value) => value.map(|v| v.to_string()).unwrap_or_default(),
+        Cell::Float(value) => value.map(|v| v.to_string()).unwrap_or_default(),
+        Cell::Bool(value) => value.map(|v| v.to_string()).unwrap_or_default(),
+    }
+}
+
+/// The table as JSON: one object per row, keyed by column name (nulls kept).
+pub fn to_json_rows<'a>(rows: impl IntoIterator<Item = (&'a str, &'a ContrastResult)>) -> serde_json::Value {
+    use serde_json::{Map, Value};
+    let objects = rows.into_iter().map(|(status, r)| {
+        let fields = COLUMNS.iter().map(|c| {
+            let value = match (c.value)(status, r) {
+                Cell::Text(v) => v.map_or(Value::Null, Value::from),
+                Cell::Int(v) => v.map_or(Value::Null, Value::from),
+                Cell::Float(v) => v.map_or(Value::Null, Value::from),
+                Cell::Bool(v) => v.map_or(Value::Null, Value::from),
+            };
+            (c.name.to_string(), value)
+        });
+        Value::Object(fields.collect::<Map<String, Value>>())
+    });
+    Value::Array(objects.collect())
+}
+
+/// Write the table to `path` as a single-row-group Parquet file (every
+/// column optional; text as UTF8, `line`/`end_line` as INT64).
+#[cfg(feature = "parquet")]
+pub fn write_parquet(path: &std::path::Path, result: &CheckResultJs) -> Result<(), String> {
+    use parquet::data_type::{BoolType, ByteArray, ByteArrayType, DoubleType, Int64Type};
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+    use std::sync::Arc;
+
+    let fields: Vec<String> = COLUMNS
+        .iter()
+        .map(|c| match c.kind {
+            Kind::Text => format!("OPTIONAL BYTE_ARRAY {} (UTF8);", c.name),
+            Kind::Int => format!("OPTIONAL INT64 {};", c.name),
+            Kind::Float => format!("OPTIONAL DOUBLE {};", c.name),
+            Kind::Bool => format!("OPTIONAL BOOLEAN {};", c.name),
+        })
+        .collect();
+    let schema = parse_message_type(&format!("message result {{ {} }}", fields.join(" ")))
+        .map_err(|err| err.to_string())?;
+
+    let rows = rows(result);
+    let file = std::fs::File::create(path).map_err(|err| err.to_string())?;
+    let props = Arc::new(WriterProperties::builder().build());
+    let mut writer = SerializedFileWriter::new(file, Arc::new(schema), props).map_err(|err| err.to_string())?;
+    let mut row_group = writer.next_row_group().map_err(|err| err.to_string())?;
+    for column in COLUMNS {
+        let cells: Vec<Cell> = rows.iter().map(|(status, r)| (column.value)(status, r)).collect();
+        // Definition level 1 = value present, 0 = null
+        let levels: Vec<i16> = cells.iter().map(|cell| i16::from(cell.is_present())).collect();
+        let mut col = row_group
+            .next_column()
+            .map_err(|err| err.to_string())?
+            .ok_or_else(|| format!("schema has no column {}", column.name))?;
+        let written = match column.kind {
+            Kind::Text => {
+                let values: Vec<ByteArray> = cells
+                    .into_iter()
+                    .filter_map(|cell| match cell {
+                        Cell::Text(v) => v.map(|v| ByteArray::from(v.as_str())),
+                        _ => None,
+                    })
+                    .collect();
+                col.typed::<ByteArrayType>().write_batch(&values, Some(&levels), None)
+            }
+            Kind::Int => {
+                let values: Vec<i64> = cells
+                    .into_iter()
+                    .filter_map(|cell| match cell {
+                        Cell::Int(v) => v.map(i64::from),
+                        _ => None,
+                    })
+                    .collect();
+                col.typed::<Int64Type>().write_batch(&values, Some(&levels), None)
+            }
+            Kind::Float => {
+                let values: Vec<f64> = cells
+                    .into_iter()
+                    .filter_map(|cell| match cell {
+                        Cell::Float(v) => v,
+                        _ => None,
+                    })
+                    .collect();
+                col.typed::<DoubleType>().write_batch(&values, Some(&levels), None)
+            }
+            Kind::Bool => {
+                let values: Vec<bool> = cells
+                    .into_iter()
+                    .filter_map(|cell| match cell {
+                        Cell::Bool(v) => v,
+                        _ => None,
+                    })
+                    .collect();
+                col.typed::<BoolType>().write_batch(&values, Some(&levels), None)
+            }
+        };
+        written.map_err(|err| err.to_string())?;
+        col.close().map_err(|err| err.to_string())?;
+    }
+    row_group.close().map_err(|err| err.to_string())?;
+    writer.close().map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::checker::check_contrast;
+    use crate::types::ColorPair;
+
+    fn sample() -> CheckResultJs {
+        let pair = |file: &str, text_class: &str| ColorPair {
+            file: file.to_string(),
+            line: 3,
+            text_class: text_class.to_string(),
+            bg_class: "bg-white".to_string(),
+            text_hex: Some("#9ca3af".to_string()),
+            bg_hex: Some("#ffffff".to_string()),
+            ..Default::default()
+        };
+        CheckResultJs {
+            violations: vec![check_contrast(&pair("src/App.tsx", "text-gray-400"), "#ffffff")],
+            passed: vec![check_contrast(&pair("src/a,\"b\".tsx", "text-black"), "#ffffff")],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn csv_has_one_row_per_result() {
+        let csv = to_csv(&sample());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("status,file,line,end_line,tag_name,text_class,bg_class"));
+        assert!(lines[1].starts_with("violation,src/App.tsx,3,,,text-gray-400,bg-white,#9ca3af,#ffffff,"));
+        assert!(lines[2].starts_with("passed,\"src/a,\"\"b\"\".tsx\",3,"));
+        assert_eq!(lines[1].split(',').count(), COLUMNS.len());
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_round_trips_row_count() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let path = std::env::temp_dir().join(format!("a11y-export-{}.parquet", std::process::id()));
+        write_parquet(&path, &sample()).unwrap();
+        let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.file_metadata().num_rows(), 2);
+        assert_eq!(metadata.file_metadata().schema_descr().num_columns(), COLUMNS.len());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,194 @@
+//! Path classifiers for files nobody ships: tests, stories, end-to-end
+//! suites and mocks.
+//!
+//! Each kind gets a policy from `FileKindPolicies`: "skip" leaves its files
+//! out of the scan (`ExtractOptions.file_kind_policies`) and drops their
+//! results, "informational" reports their violations apart from the rest
+//! (`CheckResultJs.informational`), "full" (default) audits them like app code.
+
+use crate::types::{CheckResultJs, ContrastResult, FileKindPolicies};
+
+/// Kind of a non-shipping source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    /// `*.test.tsx`, `*.spec.tsx`, files under `__tests__/` or `tests/`
+    Test,
+    /// `*.stories.tsx`, `*.story.tsx`, files under `__stories__/`
+    Story,
+    /// `*.e2e.tsx`, `*.cy.tsx`, files under `e2e/`, `cypress/` or `playwright/`
+    E2e,
+    /// `*.mock.tsx`, files under `__mocks__/`, `mocks/`, `__fixtures__/` or `fixtures/`
+    Mock,
+}
+
+impl FileKind {
+    /// "test" | "story" | "e2e" | "mock"
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Test => "test",
+            Self::Story => "story",
+            Self::E2e => "e2e",
+            Self::Mock => "mock",
+        }
+    }
+}
+
+/// What happens to the files of one kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FilePolicy {
+    /// Not scanned, no results
+    Skip,
+    /// Scanned; violations reported apart, outside the pass/fail outcome
+    Informational,
+    /// Audited like app code
+    #[default]
+    Full,
+}
+
+impl FilePolicy {
+    /// Parse "skip" | "informational" | "full" (case-insensitive); anything
+    /// else is "full".
+    pub fn parse(value: Option<&str>) -> Self {
+        match value.map(str::to_ascii_lowercase).as_deref() {
+            Some("skip") => Self::Skip,
+            Some("informational") => Self::Informational,
+            _ => Self::Full,
+        }
+    }
+}
+
+/// Directory names marking each kind, checked before file name markers.
+const KIND_DIRS: &[(&str, FileKind)] = &[
+    ("e2e", FileKind::E2e),
+    ("cypress", FileKind::E2e),
+    ("playwright", FileKind::E2e),
+    ("__stories__", FileKind::Story),
+    ("__mocks__", FileKind::Mock),
+    ("mocks", FileKind::Mock),
+    ("__fixtures__", FileKind::Mock),
+    ("fixtures", FileKind::Mock),
+    ("__tests__", FileKind::Test),
+    ("tests", FileKind::Test),
+];
+
+/// Dotted file name segments marking each kind (`Button.stories.tsx`).
+const KIND_SUFFIXES: &[(&str, FileKind)] = &[
+    ("e2e", FileKind::E2e),
+    ("cy", FileKind::E2e),
+    ("stories", FileKind::Story),
+    ("story", FileKind::Story),
+    ("mock", FileKind::Mock),
+    ("test", FileKind::Test),
+    ("spec", FileKind::Test),
+];
+
+/// Kind of the file at `path` (`/` or `\` separated), None for app code.
+/// Directory markers win over file names, so `e2e/login.spec.tsx` is e2e.
+pub fn classify(path: &str) -> Option<FileKind> {
+    let mut segments: Vec<&str> = path.split(['/', '\\']).filter(|s| !s.is_empty()).collect();
+    let file_name = segments.pop()?;
+    let by_dir = KIND_DIRS
+        .iter()
+        .find(|(dir, _)| segments.contains(dir))
+        .map(|&(_, kind)| kind);
+    by_dir.or_else(|| {
+        // Segments between the stem and the extension: `Button.stories.tsx` → "stories"
+        let mut parts: Vec<&str> = file_name.split('.').skip(1).collect();
+        parts.pop();
+        KIND_SUFFIXES
+            .iter()
+            .find(|(suffix, _)| parts.contains(suffix))
+            .map(|&(_, kind)| kind)
+    })
+}
+
+/// Policy `policies` set for `kind`.
+pub fn policy_for(policies: &FileKindPolicies, kind: FileKind) -> FilePolicy {
+    let value = match kind {
+        FileKind::Test => &policies.test,
+        FileKind::Story => &policies.story,
+        FileKind::E2e => &policies.e2e,
+        FileKind::Mock => &policies.mock,
+    };
+    FilePolicy::parse(value.as_deref())
+}
+
+/// Policy for the file at `path` (app code is always "full").
+pub fn policy_for_path(policies: &FileKindPolicies, path: &str) -> FilePolicy {
+    classify(path).map_or(FilePolicy::Full, |kind| policy_for(policies, kind))
+}
+
+/// Set `file_kind` on every result, drop the results of "skip" kinds and
+/// move the violations of "informational" kinds into `informational`.
+pub fn apply_policies(result: &mut CheckResultJs, policies: &FileKindPolicies) {
+    let skipped = |r: &ContrastResult| policy_for_path(policies, &r.file) == FilePolicy::Skip;
+    result.passed.retain(|r| !skipped(r));
+    result.ignored.retain(|r| !skipped(r));
+    let mut informational = result.informational.take().unwrap_or_default();
+    let mut kept = Vec::with_capacity(result.violations.len());
+    for r in std::mem::take(&mut result.violations) {
+        match policy_for_path(policies, &r.file) {
+            FilePolicy::Skip => {}
+            FilePolicy::Informational => informational.push(r),
+            FilePolicy::Full => kept.push(r),
+        }
+    }
+    result.violations = kept;
+    for r in result
+        .violations
+        .iter_mut()
+        .chain(result.passed.iter_mut())
+        .chain(result.ignored.iter_mut())
+        .chain(informational.iter_mut())
+    {
+        r.file_kind = classify(&r.file).map(|kind| kind.as_str().to_string());
+    }
+    log::debug!("{} violations reported as informational", informational.len());
+    result.informational = (!informational.is_empty()).then_some(informational);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::checker::check_contrast;
+    use crate::types::ColorPair;
+
+    #[test]
+    fn classify_paths() {
+        assert_eq!(classify("src/Button.test.tsx"), Some(FileKind::Test));
+        assert_eq!(classify("src/__tests__/Button.tsx"), Some(FileKind::Test));
+        assert_eq!(classify("src/Button.stories.tsx"), Some(FileKind::Story));
+        assert_eq!(classify("e2e/login.spec.tsx"), Some(FileKind::E2e));
+        assert_eq!(classify("src\\__mocks__\\api.tsx"), Some(FileKind::Mock));
+        assert_eq!(classify("src/testimonials/Quote.tsx"), None);
+        assert_eq!(classify("src/Button.tsx"), None);
+    }
+
+    #[test]
+    fn policies_split_results() {
+        let result_at =
+            |file: &str| check_contrast(&ColorPair { file: file.to_string(), ..Default::default() }, "#ffffff");
+        let mut result = CheckResultJs {
+            violations: vec![
+                result_at("src/App.tsx"),
+                result_at("src/App.test.tsx"),
+                result_at("src/App.stories.tsx"),
+            ],
+            passed: vec![result_at("src/App.test.tsx"), result_at("src/App.tsx")],
+            ..Default::default()
+        };
+        let policies = FileKindPolicies {
+            test: Some("skip".to_string()),
+            story: Some("Informational".to_string()),
+            ..Default::default()
+        };
+        apply_policies(&mut result, &policies);
+        let files = |results: &[ContrastResult]| results.iter().map(|r| r.file.clone()).collect::<Vec<_>>();
+        assert_eq!(files(&result.violations), vec!["src/App.tsx"]);
+        assert_eq!(files(&result.passed), vec!["src/App.tsx"]);
+        let informational = result.informational.unwrap();
+        assert_eq!(files(&informational), vec!["src/App.stories.tsx"]);
+        assert_eq!(informational[0].file_kind.as_deref(), Some("story"));
+        assert_eq!(result.violations[0].file_kind, None);
+    }
+}
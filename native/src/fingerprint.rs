@@ -0,0 +1,125 @@
+//! Location-independent violation fingerprints.
+//!
+//! A fingerprint identifies *what* failed (the resolved colors, the class
+//! that produced the foreground, the pair kind and state), not *where*: the
+//! same styling choice gets the same fingerprint in a library's source, its
+//! published build and every app that copies or re-renders it.
+//!
+//! A result id (`AA-TEXT-CONTRAST-3f9c0a1b2d4e`) adds the rule and the file
+//! to the fingerprint: it names one failure of one file, survives edits that
+//! move it to another line, and is the same in every report of a run.
+
+use std::collections::HashMap;
+
+use crate::criteria::rule_of;
+use crate::manifest::sha256_hex;
+use crate::types::{CheckResultJs, ContrastResult};
+
+/// Hex digits kept from the SHA-256 digest.
+const FINGERPRINT_LEN: usize = 16;
+
+/// Hex digits of the result id's hash.
+const RESULT_ID_HASH_LEN: usize = 12;
+
+/// Fingerprint of a result: file, line and the (context-dependent) bg class
+/// name are excluded; the resolved bg color is what matters.
+pub fn fingerprint(result: &ContrastResult) -> String {
+    let key = format!(
+        "{}|{}|{:?}|{:?}|{}|{}|{}|{}",
+        result.bg_hex.as_deref().unwrap_or(""),
+        result.text_hex.as_deref().unwrap_or(""),
+        result.bg_alpha,
+        result.text_alpha,
+        result.text_class,
+        result.pair_type.as_deref().unwrap_or("text"),
+        result.interactive_state.as_deref().unwrap_or(""),
+        result.is_large_text.unwrap_or(false),
+    );
+    sha256_hex(key.as_bytes())[..FINGERPRINT_LEN].to_string()
+}
+
+/// Id of a result with fingerprint `fingerprint`: its rule id (see
+/// `criteria::rule_of`) and a hash of the fingerprint and file.
+pub fn result_id(result: &ContrastResult, fingerprint: &str) -> String {
+    let rule_id = rule_of(result);
+    let key = format!("{}|{}|{}", rule_id, fingerprint, result.file.replace('\\', "/"));
+    format!("{}-{}", rule_id, &sha256_hex(key.as_bytes())[..RESULT_ID_HASH_LEN])
+}
+
+/// Set `fingerprint` and `id` on every result. The same failure repeated in
+/// a file gets `-2`, `-3`, ... suffixes in result order.
+pub fn assign_fingerprints(result: &mut CheckResultJs) {
+    let mut seen: HashMap<String, u32> = HashMap::new();
+    for r in result
+        .violations
+        .iter_mut()
+        .chain(result.passed.iter_mut())
+        .chain(result.ignored.iter_mut())
+    {
+        let fingerprint = fingerprint(r);
+        let id = result_id(r, &fingerprint);
+        let count = seen.entry(id.clone()).or_default();
+        *count += 1;
+        r.id = Some(if *count == 1 { id } else { format!("{}-{}", id, count) });
+        r.fingerprint = Some(fingerprint);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::checker::check_contrast;
+    use crate::types::ColorPair;
+
+    fn result(file: &str, line: u32, bg_class: &str) -> ContrastResult {
+        check_contrast(
+            &ColorPair {
+                file: file.to_string(),
+                line,
+                bg_class: bg_class.to_string(),
+                text_class: "text-gray-300".to_string(),
+                bg_hex: Some("#ffffff".to_string()),
+                text_hex: Some("#d1d5db".to_string()),
+                ..Default::default()
+            },
+            "#ffffff",
+        )
+    }
+
+    #[test]
+    fn independent_of_location_and_context_class() {
+        let a = fingerprint(&result("packages/ui/src/Badge.tsx", 12, "bg-white"));
+        let b = fingerprint(&result("apps/web/src/Page.tsx", 80, "(implicit) bg-background"));
+        assert_eq!(a, b);
+        assert_eq!(a.len(), FINGERPRINT_LEN);
+    }
+
+    #[test]
+    fn differs_by_state_and_colors() {
+        let base = result("a.tsx", 1, "bg-white");
+        let mut hover = base.clone();
+        hover.interactive_state = Some("hover".to_string());
+        let mut darker = base.clone();
+        darker.text_hex = Some("#000000".to_string());
+        assert_ne!(fingerprint(&base), fingerprint(&hover));
+        assert_ne!(fingerprint(&base), fingerprint(&darker));
+    }
+
+    #[test]
+    fn result_ids_are_stable_and_unique() {
+        let mut moved = result("src/a.tsx", 40, "bg-white");
+        moved.rule_id = Some("AA-TEXT-CONTRAST".to_string());
+        let mut check = CheckResultJs {
+            violations: vec![result("src/a.tsx", 1, "bg-white"), result("src/a.tsx", 9, "bg-white")],
+            ..Default::default()
+        };
+        check.passed.push(result("src/b.tsx", 1, "bg-white"));
+        assign_fingerprints(&mut check);
+        let id = check.violations[0].id.clone().unwrap();
+        assert!(id.starts_with("AA-TEXT-CONTRAST-"));
+        assert_eq!(id.len(), "AA-TEXT-CONTRAST-".len() + RESULT_ID_HASH_LEN);
+        assert_eq!(result_id(&moved, &fingerprint(&moved)), id);
+        assert_eq!(check.violations[1].id, Some(format!("{}-2", id)));
+        assert_ne!(check.passed[0].id.as_deref(), Some(id.as_str()));
+    }
+}
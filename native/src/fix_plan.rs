@@ -0,0 +1,296 @@
+//! Bulk autofix planning: one token-level change per root cause.
+//!
+//! Violations are clustered by the theme tokens of their (foreground,
+//! background) classes. For each cluster the foreground token is moved
+//! toward black or white (whichever needs the smaller step) until every
+//! violation in the cluster passes, and the candidate value is replayed
+//! against every checked result reading the token, as text or background,
+//! to project its impact: violations fixed anywhere and passing pairs that
+//! would regress.
+//!
+//! `simulate_token_change` runs the same replay for a value chosen by hand,
+//! so design can evaluate palette tweaks before committing them.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::math::checker::{check_all_pairs_with_options, check_contrast, is_violation, pair_of};
+use crate::math::color_parse::to_hex;
+use crate::math::composite::composite_over;
+use crate::math::hex::strip_hex_alpha;
+use crate::pairs::class_resolver::css_variable_of;
+use crate::types::{
+    CheckOptions, CheckResultJs, ContrastResult, FixPlan, FixPlanOptions, TokenFix, TokenSimulation,
+    TokenSimulationInput,
+};
+
+/// Proposals returned when `FixPlanOptions.max_fixes` is not set.
+pub const DEFAULT_MAX_FIXES: u32 = 20;
+
+/// Mix steps tried toward black / white (1% each).
+const MIX_STEPS: u32 = 100;
+
+/// Threshold a result was checked at: strict-profile results use AAA.
+fn threshold_of<'a>(result: &ContrastResult, threshold: &'a str) -> &'a str {
+    if result.strict_profile == Some(true) {
+        "AAA"
+    } else {
+        threshold
+    }
+}
+
+/// Whether `result` reads `token` as its text or background color.
+fn reads_token(result: &ContrastResult, token: &str) -> bool {
+    css_variable_of(&result.text_class).as_deref() == Some(token)
+        || css_variable_of(&result.bg_class).as_deref() == Some(token)
+}
+
+/// `result` re-checked with the token's color replaced by `hex`.
+fn recheck_with(result: &ContrastResult, token: &str, hex: &str, page_bg: &str) -> ContrastResult {
+    let mut pair = pair_of(result);
+    if css_variable_of(&result.text_class).as_deref() == Some(token) {
+        pair.text_hex = Some(hex.to_string());
+    }
+    if css_variable_of(&result.bg_class).as_deref() == Some(token) {
+        pair.bg_hex = Some(hex.to_string());
+    }
+    let mut after = check_contrast(&pair, page_bg);
+    after.wcag_criteria = result.wcag_criteria.clone();
+    after.wcag_techniques = result.wcag_techniques.clone();
+    after.strict_profile = result.strict_profile;
+    after
+}
+
+/// Whether `result` fails once the token's color is replaced by `hex`.
+fn fails_with(result: &ContrastResult, token: &str, hex: &str, threshold: &str, page_bg: &str) -> bool {
+    is_violation(&recheck_with(result, token, hex, page_bg), threshold_of(result, threshold))
+}
+
+/// Smallest mix of `current` toward black or white fixing every violation of the cluster.
+fn passing_value(cluster: &[&ContrastResult], token: &str, current: &str, threshold: &str, page_bg: &str) -> Option<String> {
+    (1..=MIX_STEPS).find_map(|step| {
+        let amount = step as f64 / MIX_STEPS as f64;
+        ["#000000", "#ffffff"].iter().find_map(|target| {
+            let candidate = composite_over(target, current, amount);
+            cluster
+                .iter()
+                .all(|v| !fails_with(v, token, &candidate, threshold, page_bg))
+                .then_some(candidate)
+        })
+    })
+}
+
+/// Cluster violations by token pair and propose one foreground token change per cluster.
+pub fn plan_fixes(result: &CheckResultJs, options: &FixPlanOptions) -> FixPlan {
+    let threshold = options.threshold.as_deref().unwrap_or("AA");
+    let page_bg = options.page_bg.as_deref().unwrap_or("#ffffff");
+
+    let mut clusters: BTreeMap<(String, String), Vec<&ContrastResult>> = BTreeMap::new();
+    let mut unplanned = 0u32;
+    for v in &result.violations {
+        match css_variable_of(&v.text_class) {
+            Some(token) if v.text_hex.is_some() => {
+                let bg = css_variable_of(&v.bg_class).unwrap_or_else(|| v.bg_class.clone());
+                clusters.entry((token, bg)).or_default().push(v);
+            }
+            _ => unplanned += 1,
+        }
+    }
+
+    let checked: Vec<(&ContrastResult, bool)> = result
+        .violations
+        .iter()
+        .map(|r| (r, true))
+        .chain(result.passed.iter().map(|r| (r, false)))
+        .collect();
+
+    let mut fixes = Vec::new();
+    for ((token, _), cluster) in clusters {
+        let current = strip_hex_alpha(cluster[0].text_hex.as_deref().unwrap_or_default());
+        let Some(proposed) = passing_value(&cluster, &token, &current, threshold, page_bg) else {
+            unplanned += cluster.len() as u32;
+            continue;
+        };
+        let (mut fixed, mut regressions) = (0u32, 0u32);
+        for (r, was_violation) in &checked {
+            if !reads_token(r, &token) {
+                continue;
+            }
+            let fails = fails_with(r, &token, &proposed, threshold, page_bg);
+            if *was_violation && !fails {
+                fixed += 1;
+            } else if !*was_violation && fails {
+                regressions += 1;
+            }
+        }
+        let files: BTreeSet<&str> = cluster.iter().map(|v| v.file.as_str()).collect();
+        fixes.push(TokenFix {
+            token,
+            current_hex: current,
+            proposed_hex: proposed,
+            text_class: cluster[0].text_class.clone(),
+            bg_class: cluster[0].bg_class.clone(),
+            violations: cluster.len() as u32,
+            fixed,
+            regressions,
+            files: files.into_iter().map(str::to_string).collect(),
+        });
+    }
+
+    fixes.sort_by(|a, b| {
+        b.fixed
+            .cmp(&a.fixed)
+            .then(a.regressions.cmp(&b.regressions))
+            .then_with(|| a.token.cmp(&b.token))
+    });
+    fixes.truncate(options.max_fixes.unwrap_or(DEFAULT_MAX_FIXES) as usize);
+    FixPlan { fixes, unplanned }
+}
+
+/// Recompute every checked result reading `token` with `new_value` (any CSS
+/// color) and report before/after pass counts. `input.pairs` are checked
+/// first when no `input.result` is given. None if `new_value` doesn't parse.
+pub fn simulate_token_change(input: &TokenSimulationInput, token: &str, new_value: &str) -> Option<TokenSimulation> {
+    let new_hex = strip_hex_alpha(&to_hex(new_value)?);
+    let threshold = input.threshold.as_deref().unwrap_or("AA");
+    let page_bg = input.page_bg.as_deref().unwrap_or("#ffffff");
+    let checked_pairs;
+    let result = match (&input.result, &input.pairs) {
+        (Some(result), _) => result,
+        (None, pairs) => {
+            let options = CheckOptions {
+                threshold: input.threshold.clone(),
+                page_bg: input.page_bg.clone(),
+                ..Default::default()
+            };
+            checked_pairs = check_all_pairs_with_options(pairs.as_deref().unwrap_or_default(), &options);
+            &checked_pairs
+        }
+    };
+
+    let mut simulation = TokenSimulation {
+        token: token.to_string(),
+        new_hex: new_hex.clone(),
+        affected: 0,
+        passed_before: 0,
+        passed_after: 0,
+        fixed: Vec::new(),
+        regressed: Vec::new(),
+    };
+    let checked = result.violations.iter().map(|r| (r, true)).chain(result.passed.iter().map(|r| (r, false)));
+    for (r, was_violation) in checked {
+        if !reads_token(r, token) {
+            continue;
+        }
+        let after = recheck_with(r, token, &new_hex, page_bg);
+        let fails = is_violation(&after, threshold_of(r, threshold));
+        simulation.affected += 1;
+        simulation.passed_before += !was_violation as u32;
+        simulation.passed_after += !fails as u32;
+        if was_violation && !fails {
+            simulation.fixed.push(after);
+        } else if !was_violation && fails {
+            simulation.regressed.push(after);
+        }
+    }
+    Some(simulation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::checker::check_all_pairs_with_options;
+    use crate::types::{CheckOptions, ColorPair};
+
+    fn pair(file: &str, text_class: &str, text_hex: &str, bg_class: &str, bg_hex: &str) -> ColorPair {
+        ColorPair {
+            file: file.to_string(),
+            bg_class: bg_class.to_string(),
+            bg_hex: Some(bg_hex.to_string()),
+            pair_type: Some("text".to_string()),
+            ..ColorPair::fixture(text_class, text_hex)
+        }
+    }
+
+    fn result(pairs: &[ColorPair]) -> CheckResultJs {
+        check_all_pairs_with_options(pairs, &CheckOptions::default())
+    }
+
+    #[test]
+    fn clusters_share_one_token_change() {
+        let pairs: Vec<ColorPair> = (0..5)
+            .map(|i| pair(&format!("f{}.tsx", i % 2), "text-muted-foreground", "#a1a1aa", "bg-background", "#ffffff"))
+            .collect();
+        let plan = plan_fixes(&result(&pairs), &FixPlanOptions::default());
+        assert_eq!(plan.fixes.len(), 1);
+        let fix = &plan.fixes[0];
+        assert_eq!(fix.token, "--color-muted-foreground");
+        assert_eq!(fix.current_hex, "#a1a1aa");
+        assert_eq!((fix.violations, fix.fixed, fix.regressions), (5, 5, 0));
+        assert_eq!(fix.files, vec!["f0.tsx", "f1.tsx"]);
+        assert!(crate::math::wcag::contrast_ratio(&fix.proposed_hex, "#ffffff") >= 4.5);
+    }
+
+    #[test]
+    fn projects_regressions_where_token_is_a_background() {
+        // Darkening --color-accent fixes the text pair but breaks dark gray text on bg-accent
+        let pairs = vec![
+            pair("a.tsx", "text-accent", "#60a5fa", "bg-white", "#ffffff"),
+            pair("b.tsx", "text-gray-800", "#1f2937", "bg-accent", "#60a5fa"),
+        ];
+        let plan = plan_fixes(&result(&pairs), &FixPlanOptions::default());
+        let fix = &plan.fixes[0];
+        assert_eq!(fix.token, "--color-accent");
+        assert_eq!((fix.fixed, fix.regressions), (1, 1));
+    }
+
+    #[test]
+    fn arbitrary_colors_are_unplanned() {
+        let pairs = vec![pair("a.tsx", "text-[#aaaaaa]", "#aaaaaa", "bg-white", "#ffffff")];
+        let plan = plan_fixes(&result(&pairs), &FixPlanOptions::default());
+        assert!(plan.fixes.is_empty());
+        assert_eq!(plan.unplanned, 1);
+    }
+
+    #[test]
+    fn biggest_impact_first_and_capped() {
+        let mut pairs = vec![pair("a.tsx", "text-gray-400", "#9ca3af", "bg-white", "#ffffff")];
+        pairs.extend((0..3).map(|_| pair("b.tsx", "text-muted", "#a1a1aa", "bg-white", "#ffffff")));
+        let options = FixPlanOptions { max_fixes: Some(1), ..Default::default() };
+        let plan = plan_fixes(&result(&pairs), &options);
+        assert_eq!(plan.fixes.len(), 1);
+        assert_eq!(plan.fixes[0].token, "--color-muted");
+    }
+
+    #[test]
+    fn simulation_reports_before_and_after() {
+        let pairs = vec![
+            pair("a.tsx", "text-muted-foreground", "#a1a1aa", "bg-background", "#ffffff"),
+            pair("b.tsx", "text-muted-foreground", "#a1a1aa", "bg-background", "#ffffff"),
+            pair("c.tsx", "text-white", "#ffffff", "bg-muted-foreground", "#a1a1aa"),
+            pair("d.tsx", "text-gray-900", "#111827", "bg-white", "#ffffff"),
+        ];
+        let input = TokenSimulationInput { pairs: Some(pairs.clone()), ..Default::default() };
+        let sim = simulate_token_change(&input, "--color-muted-foreground", "#52525b").unwrap();
+        assert_eq!(sim.new_hex, "#52525b");
+        assert_eq!(sim.affected, 3);
+        assert_eq!((sim.passed_before, sim.passed_after), (0, 3));
+        assert_eq!(sim.fixed.len(), 3);
+        assert!(sim.regressed.is_empty());
+
+        // Same answer from an already-checked result
+        let from_result = TokenSimulationInput { result: Some(result(&pairs)), ..Default::default() };
+        let sim = simulate_token_change(&from_result, "--color-muted-foreground", "oklch(0.985 0 0)").unwrap();
+        assert_eq!(sim.affected, 3);
+        assert_eq!(sim.passed_after, 0);
+    }
+
+    #[test]
+    fn simulation_flags_regressions_and_rejects_bad_values() {
+        let pairs = vec![pair("a.tsx", "text-primary", "#1d4ed8", "bg-white", "#ffffff")];
+        let input = TokenSimulationInput { pairs: Some(pairs), ..Default::default() };
+        let sim = simulate_token_change(&input, "--color-primary", "#93c5fd").unwrap();
+        assert_eq!(sim.regressed.len(), 1);
+        assert!(sim.regressed[0].ratio < 4.5);
+        assert!(simulate_token_change(&input, "--color-primary", "not-a-color").is_none());
+    }
+}
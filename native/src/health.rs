@@ -0,0 +1,166 @@
+//! Single trending "a11y color health score" for a check run.
+//!
+//! Formula (0-100, higher is better):
+//!
+//! ```text
+//! pass_rate = Σ weight(passed) / Σ weight(passed + violations)   (1.0 when nothing was checked)
+//! S         = ignored / (checked + ignored)                       suppression ratio
+//! U         = unresolved / (checked + ignored + unresolved)       unresolved ratio
+//! score     = round(100 × pass_rate × (1 − S/2) × (1 − U/2))
+//! ```
+//!
+//! Text at rest weighs 1.0; non-text (border/ring/outline) and hover/focus
+//! state pairs weigh 0.5. Suppressed and unresolved pairs are unknowns rather
+//! than failures, so each only halves its share of the score: a codebase
+//! cannot reach 100 by suppressing or hiding colors from the resolver.
+//! Disabled elements are exempt from contrast and don't count anywhere.
+
+use std::collections::BTreeMap;
+
+use crate::types::{CheckResultJs, ColorPair, ContrastResult, HealthScore, SuppressionCount};
+
+/// Bump when the formula changes so dashboards can annotate the trend break.
+pub const FORMULA_VERSION: u32 = 1;
+
+/// Weight of a checked pair in the pass rate.
+fn weight(result: &ContrastResult) -> f64 {
+    let is_non_text = result.pair_type.as_deref().is_some_and(|t| t != "text");
+    if is_non_text || result.interactive_state.is_some() {
+        0.5
+    } else {
+        1.0
+    }
+}
+
+fn ratio(part: u32, whole: u32) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+fn round3(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+/// Score a check result; `pairs` are the pairs it was computed from (for the
+/// unresolved count).
+pub fn score(pairs: &[ColorPair], result: &CheckResultJs) -> HealthScore {
+    let passed_weight: f64 = result.passed.iter().map(weight).sum();
+    let total_weight = passed_weight + result.violations.iter().map(weight).sum::<f64>();
+    let pass_rate = if total_weight == 0.0 { 1.0 } else { passed_weight / total_weight };
+
+    let checked = (result.passed.len() + result.violations.len()) as u32;
+    let ignored = result.ignored.len() as u32;
+    let unresolved = pairs
+        .iter()
+        .filter(|p| (p.bg_hex.is_none() || p.text_hex.is_none()) && p.is_disabled != Some(true))
+        .count() as u32;
+    let suppression_ratio = ratio(ignored, checked + ignored);
+    let unresolved_ratio = ratio(unresolved, checked + ignored + unresolved);
+
+    let score = 100.0 * pass_rate * (1.0 - suppression_ratio / 2.0) * (1.0 - unresolved_ratio / 2.0);
+    HealthScore {
+        score: score.round() as u32,
+        formula_version: FORMULA_VERSION,
+        weighted_pass_rate: round3(pass_rate),
+        suppression_ratio: round3(suppression_ratio),
+        unresolved_ratio: round3(unresolved_ratio),
+        checked,
+        suppressed: ignored,
+        unresolved,
+        suppressions: suppression_counts(&result.ignored),
+    }
+}
+
+/// Suppressed violations per `a11y-ignore` reason, most frequent first.
+fn suppression_counts(ignored: &[ContrastResult]) -> Vec<SuppressionCount> {
+    let mut by_reason: BTreeMap<&str, u32> = BTreeMap::new();
+    for r in ignored {
+        *by_reason.entry(r.ignore_reason.as_deref().unwrap_or("")).or_default() += 1;
+    }
+    let mut counts: Vec<SuppressionCount> = by_reason
+        .into_iter()
+        .map(|(reason, count)| SuppressionCount { reason: reason.to_string(), count })
+        .collect();
+    counts.sort_by_key(|c| std::cmp::Reverse(c.count));
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::checker::check_all_pairs_with_options;
+    use crate::types::CheckOptions;
+
+    fn pair(text_hex: Option<&str>, pair_type: &str) -> ColorPair {
+        ColorPair {
+            text_hex: text_hex.map(str::to_string),
+            pair_type: Some(pair_type.to_string()),
+            ..ColorPair::fixture("text-x", "")
+        }
+    }
+
+    fn ignored(text_hex: &str, reason: &str) -> ColorPair {
+        ColorPair {
+            ignored: Some(true),
+            ignore_reason: Some(reason.to_string()),
+            ..pair(Some(text_hex), "text")
+        }
+    }
+
+    #[test]
+    fn clean_run_scores_100() {
+        let pairs = vec![pair(Some("#000000"), "text"), pair(Some("#000000"), "border")];
+        let health = check_all_pairs_with_options(&pairs, &CheckOptions::default()).health.unwrap();
+        assert_eq!(health.score, 100);
+        assert_eq!(health.weighted_pass_rate, 1.0);
+        assert_eq!(health.checked, 2);
+    }
+
+    #[test]
+    fn empty_run_scores_100() {
+        let health = score(&[], &CheckResultJs::default());
+        assert_eq!(health.score, 100);
+        assert_eq!(health.suppression_ratio, 0.0);
+    }
+
+    #[test]
+    fn non_text_failures_weigh_half() {
+        // text pass (1.0) + border fail (0.5) → 1.0 / 1.5
+        let pairs = vec![pair(Some("#000000"), "text"), pair(Some("#eeeeee"), "border")];
+        let health = check_all_pairs_with_options(&pairs, &CheckOptions::default()).health.unwrap();
+        assert_eq!(health.weighted_pass_rate, 0.667);
+        assert_eq!(health.score, 67);
+    }
+
+    #[test]
+    fn suppressions_and_unresolved_reduce_score() {
+        let pairs = vec![
+            pair(Some("#000000"), "text"),
+            ignored("#eeeeee", "legacy"),
+            ignored("#dddddd", "legacy"),
+            ignored("#cccccc", "brand"),
+            pair(None, "text"),
+        ];
+        let health = check_all_pairs_with_options(&pairs, &CheckOptions::default()).health.unwrap();
+        assert_eq!(health.suppressed, 3);
+        assert_eq!(health.unresolved, 1);
+        assert_eq!(health.suppression_ratio, 0.75);
+        assert_eq!(health.unresolved_ratio, 0.2);
+        // 100 × 1.0 × (1 − 0.375) × (1 − 0.1)
+        assert_eq!(health.score, 56);
+        let reasons: Vec<(&str, u32)> = health.suppressions.iter().map(|s| (s.reason.as_str(), s.count)).collect();
+        assert_eq!(reasons, vec![("legacy", 2), ("brand", 1)]);
+    }
+
+    #[test]
+    fn disabled_unresolved_pairs_dont_count() {
+        let disabled = ColorPair { is_disabled: Some(true), ..pair(None, "text") };
+        let pairs = vec![pair(Some("#000000"), "text"), disabled];
+        let health = check_all_pairs_with_options(&pairs, &CheckOptions::default()).health.unwrap();
+        assert_eq!(health.unresolved, 0);
+        assert_eq!(health.score, 100);
+    }
+}
@@ -0,0 +1,103 @@
+//! Editor hover payload: the class region under a cursor position with its
+//! resolved colors, contrast results and fix advice, as one object.
+//!
+//! The VS Code hover provider calls this with the open document instead of
+//! re-implementing region lookup over raw check results. The region is found
+//! in the document's `region_index` entry and checked against a registered
+//! theme like a pre-commit snippet.
+
+use std::slice;
+
+use crate::math::checker::check_all_pairs;
+use crate::pairs::region_resolver::{resolve_regions, ResolvedPairs};
+use crate::region_index::FileIndex;
+use crate::remediation::attach_remediation;
+use crate::theme::Theme;
+use crate::types::HoverInfo;
+
+/// Hover payload for the class region at (`line`, `column`), both 1-based,
+/// of the indexed file `path`. None when no region starts on that line.
+pub fn hover_info(theme: &Theme, path: &str, index: &FileIndex, line: u32, column: u32) -> Option<HoverInfo> {
+    let (id, span) = index.at(line, column)?;
+    let region = &index.regions()[id];
+
+    let mut resolved = ResolvedPairs::default();
+    resolve_regions(path, slice::from_ref(region), &theme.color_map, &theme.theme_mode, &mut resolved);
+    let mut checked = check_all_pairs(&resolved.pairs, &theme.threshold, &theme.page_bg);
+    attach_remediation(&mut checked.violations, &theme.color_map, &theme.threshold, &theme.page_bg, theme.scan_config.locale);
+
+    Some(HoverInfo {
+        file: path.to_string(),
+        line,
+        column_start: span.map(|(start, _)| start),
+        column_end: span.map(|(_, end)| end),
+        region: region.clone(),
+        violations: checked.violations,
+        passed: checked.passed,
+        ignored: checked.ignored,
+        skipped: resolved.skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ColorMapEntry, Snippet, ThemeConfig};
+
+    fn theme() -> Theme {
+        let colors = [
+            ("--color-background", "#ffffff"),
+            ("--color-gray-400", "#9ca3af"),
+            ("--color-gray-500", "#6b7280"),
+            ("--color-gray-900", "#111827"),
+        ];
+        Theme::from_config(&ThemeConfig {
+            color_map: colors
+                .iter()
+                .map(|(name, hex)| ColorMapEntry { name: name.to_string(), hex: hex.to_string(), alpha: None })
+                .collect(),
+            default_bg: "bg-background".to_string(),
+            ..Default::default()
+        })
+    }
+
+    fn hover(line: u32, column: u32) -> Option<HoverInfo> {
+        let theme = theme();
+        let snippet =
+            Snippet { path: "src/Card.tsx".to_string(), content: SOURCE.to_string(), base_context: None, ancestors: None };
+        hover_info(&theme, &snippet.path, &FileIndex::scan(&theme, &snippet), line, column)
+    }
+
+    const SOURCE: &str = r#"<div>
+  <p className="text-gray-400">a</p> <b className="text-gray-900 font-bold">b</b>
+  <span className={cn(
+    "text-gray-500")}>c</span>
+</div>"#;
+
+    #[test]
+    fn region_under_the_cursor() {
+        let info = hover(2, 18).unwrap();
+        assert_eq!(info.region.content, "text-gray-400");
+        assert_eq!((info.column_start, info.column_end), (Some(17), Some(29)));
+        assert_eq!(info.violations.len(), 1);
+        assert!(info.violations[0].remediation.as_deref().unwrap().contains("`text-gray-500`"));
+        assert!(info.passed.is_empty());
+
+        let info = hover(2, 55).unwrap();
+        assert_eq!(info.region.content, "text-gray-900 font-bold");
+        assert_eq!(info.passed.len(), 1);
+        assert!(info.violations.is_empty());
+    }
+
+    #[test]
+    fn ambiguous_or_empty_positions() {
+        // Between the two regions of line 2
+        assert!(hover(2, 5).is_none());
+        assert!(hover(1, 1).is_none());
+        assert!(hover(0, 1).is_none());
+        // The only region on its line, even off its classes
+        let info = hover(3, 1).unwrap();
+        assert_eq!(info.region.content, "text-gray-500");
+        assert_eq!(info.column_start, None);
+    }
+}
@@ -0,0 +1,246 @@
+//! Message catalog for every human-readable string the engine produces
+//! (ignore reasons, report labels, ACR remarks).
+//!
+//! Messages use positional placeholders `{0}`, `{1}`, ... filled by
+//! `format_message()`. Machine-readable values (conformance keys, clause ids,
+//! criterion ids) are never localized.
+
+use serde::Serialize;
+
+/// Supported report locales.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum Locale {
+    #[default]
+    En,
+    It,
+}
+
+impl Locale {
+    /// Parse a BCP 47 tag ("it", "it-IT", "en_US"); unknown tags fall back to English.
+    pub fn parse(tag: Option<&str>) -> Self {
+        let primary = tag
+            .unwrap_or("")
+            .split(['-', '_'])
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+        match primary.as_str() {
+            "it" => Self::It,
+            _ => Self::En,
+        }
+    }
+}
+
+/// Catalog keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKey {
+    IgnoreDisabled,
+    IgnoreInvisible,
+    IgnoreSuppressed,
+    IgnoreStandalone,
+    IgnoreRangeUnclosed,
+    CriterionContrastMinimum,
+    CriterionContrastEnhanced,
+    CriterionNonTextContrast,
+    CriterionFocusVisible,
+    AcrHeaderCriteria,
+    AcrHeaderConformance,
+    AcrHeaderRemarks,
+    AcrLevel,
+    AcrSupports,
+    AcrPartiallySupports,
+    AcrDoesNotSupport,
+    AcrNotEvaluated,
+    AcrRemarkNoPairs,
+    AcrRemarkPassRate,
+    AcrRemarkFailures,
+    AcrRemarkSuppressed,
+    RuleUseOfColorErrorText,
+    RuleUseOfColorStatusDot,
+    RuleRequiredMarkerUnbound,
+    RuleClickOnStatic,
+    RemediationReplace,
+    RemediationLargeText,
+    RemediationEither,
+    RemediationRaiseOpacity,
+    ReviewViolation,
+    ReviewLowAlpha,
+    ReviewFix,
+}
+
+impl MessageKey {
+    pub const ALL: &'static [MessageKey] = &[
+        Self::IgnoreDisabled,
+        Self::IgnoreInvisible,
+        Self::IgnoreSuppressed,
+        Self::IgnoreStandalone,
+        Self::IgnoreRangeUnclosed,
+        Self::CriterionContrastMinimum,
+        Self::CriterionContrastEnhanced,
+        Self::CriterionNonTextContrast,
+        Self::CriterionFocusVisible,
+        Self::AcrHeaderCriteria,
+        Self::AcrHeaderConformance,
+        Self::AcrHeaderRemarks,
+        Self::AcrLevel,
+        Self::AcrSupports,
+        Self::AcrPartiallySupports,
+        Self::AcrDoesNotSupport,
+        Self::AcrNotEvaluated,
+        Self::AcrRemarkNoPairs,
+        Self::AcrRemarkPassRate,
+        Self::AcrRemarkFailures,
+        Self::AcrRemarkSuppressed,
+        Self::RuleUseOfColorErrorText,
+        Self::RuleUseOfColorStatusDot,
+        Self::RuleRequiredMarkerUnbound,
+        Self::RuleClickOnStatic,
+        Self::RemediationReplace,
+        Self::RemediationLargeText,
+        Self::RemediationEither,
+        Self::RemediationRaiseOpacity,
+        Self::ReviewViolation,
+        Self::ReviewLowAlpha,
+        Self::ReviewFix,
+    ];
+}
+
+/// Raw catalog entry for `key` in `locale`.
+pub fn message(locale: Locale, key: MessageKey) -> &'static str {
+    use MessageKey::*;
+    match locale {
+        Locale::En => match key {
+            IgnoreDisabled => "disabled element (WCAG SC 1.4.3 exemption)",
+            IgnoreInvisible => "invisible (effective opacity {0}% < {1}% threshold)",
+            IgnoreSuppressed => "suppressed",
+            IgnoreStandalone => "standalone class helper call (no element context)",
+            IgnoreRangeUnclosed => "a11y-ignore-start never closed by a11y-ignore-end",
+            CriterionContrastMinimum => "Contrast (Minimum)",
+            CriterionContrastEnhanced => "Contrast (Enhanced)",
+            CriterionNonTextContrast => "Non-text Contrast",
+            CriterionFocusVisible => "Focus Visible",
+            AcrHeaderCriteria => "Criteria",
+            AcrHeaderConformance => "Conformance Level",
+            AcrHeaderRemarks => "Remarks and Explanations",
+            AcrLevel => "Level {0}",
+            AcrSupports => "Supports",
+            AcrPartiallySupports => "Partially Supports",
+            AcrDoesNotSupport => "Does Not Support",
+            AcrNotEvaluated => "Not Evaluated",
+            AcrRemarkNoPairs => "No color pairs for this criterion were found by static analysis.",
+            AcrRemarkPassRate => "{0} of {1} statically checked color pairs ({2}%) meet the criterion.",
+            AcrRemarkFailures => "{0} failing pairs across {1} files.",
+            AcrRemarkSuppressed => "{0} failing pairs are suppressed with a documented reason.",
+            RuleUseOfColorErrorText => "error text distinguished by color alone: add an icon, a text prefix or aria-invalid on the field",
+            RuleUseOfColorStatusDot => "status indicator conveyed by color alone: add a text label or aria-label",
+            RuleRequiredMarkerUnbound => "required marker, but its field has no required or aria-required attribute",
+            RuleClickOnStatic => "onClick on <{0}> is not keyboard accessible; missing: {1}",
+            RemediationReplace => "replace `{0}` with `{1}` (ratio {2})",
+            RemediationLargeText => "increase size to ≥18.66px bold or ≥24px",
+            RemediationEither => "{0} or {1}",
+            RemediationRaiseOpacity => "raise the opacity of the element or its ancestors (the text renders at {0}%)",
+            ReviewViolation => "`{0}` on `{1}`: contrast {2}:1, {3}:1 required",
+            ReviewLowAlpha => "`{0}` on `{1}`: text rendered at {2}% opacity",
+            ReviewFix => "Fix: {0}",
+        },
+        Locale::It => match key {
+            IgnoreDisabled => "elemento disabilitato (esenzione WCAG SC 1.4.3)",
+            IgnoreInvisible => "invisibile (opacità effettiva {0}% < soglia {1}%)",
+            IgnoreSuppressed => "soppresso",
+            IgnoreStandalone => "chiamata helper di classi isolata (nessun contesto di elemento)",
+            IgnoreRangeUnclosed => "a11y-ignore-start mai chiuso da a11y-ignore-end",
+            CriterionContrastMinimum => "Contrasto (minimo)",
+            CriterionContrastEnhanced => "Contrasto (avanzato)",
+            CriterionNonTextContrast => "Contrasto non testuale",
+            CriterionFocusVisible => "Focus visibile",
+            AcrHeaderCriteria => "Criteri",
+            AcrHeaderConformance => "Livello di conformità",
+            AcrHeaderRemarks => "Osservazioni e spiegazioni",
+            AcrLevel => "Livello {0}",
+            AcrSupports => "Supportato",
+            AcrPartiallySupports => "Parzialmente supportato",
+            AcrDoesNotSupport => "Non supportato",
+            AcrNotEvaluated => "Non valutato",
+            AcrRemarkNoPairs => "L'analisi statica non ha trovato coppie di colori per questo criterio.",
+            AcrRemarkPassRate => "{0} coppie di colori su {1} verificate staticamente ({2}%) soddisfano il criterio.",
+            AcrRemarkFailures => "{0} coppie non conformi in {1} file.",
+            AcrRemarkSuppressed => "{0} coppie non conformi sono soppresse con una motivazione documentata.",
+            RuleUseOfColorErrorText => "testo di errore distinto solo dal colore: aggiungere un'icona, un prefisso testuale o aria-invalid sul campo",
+            RuleUseOfColorStatusDot => "indicatore di stato espresso solo dal colore: aggiungere un'etichetta testuale o aria-label",
+            RuleRequiredMarkerUnbound => "indicatore di campo obbligatorio, ma il campo non ha l'attributo required o aria-required",
+            RuleClickOnStatic => "onClick su <{0}> non è accessibile da tastiera; mancano: {1}",
+            RemediationReplace => "sostituire `{0}` con `{1}` (rapporto {2})",
+            RemediationLargeText => "aumentare la dimensione a ≥18,66px grassetto o ≥24px",
+            RemediationEither => "{0} oppure {1}",
+            RemediationRaiseOpacity => "aumentare l'opacità dell'elemento o dei suoi antenati (il testo è reso al {0}%)",
+            ReviewViolation => "`{0}` su `{1}`: contrasto {2}:1, richiesto {3}:1",
+            ReviewLowAlpha => "`{0}` su `{1}`: testo reso al {2}% di opacità",
+            ReviewFix => "Correzione: {0}",
+        },
+    }
+}
+
+/// Catalog entry with `{N}` placeholders replaced by `args[N]`.
+pub fn format_message(locale: Locale, key: MessageKey, args: &[&str]) -> String {
+    let mut text = message(locale, key).to_string();
+    for (i, arg) in args.iter().enumerate() {
+        text = text.replace(&format!("{{{}}}", i), arg);
+    }
+    text
+}
+
+/// Localized title of a WCAG criterion handled by the engine.
+pub fn criterion_title(locale: Locale, criterion: &str) -> Option<&'static str> {
+    let key = match criterion {
+        "1.4.3" => MessageKey::CriterionContrastMinimum,
+        "1.4.6" => MessageKey::CriterionContrastEnhanced,
+        "1.4.11" => MessageKey::CriterionNonTextContrast,
+        "2.4.7" => MessageKey::CriterionFocusVisible,
+        _ => return None,
+    };
+    Some(message(locale, key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(text: &str) -> usize {
+        (0..10).filter(|i| text.contains(&format!("{{{}}}", i))).count()
+    }
+
+    #[test]
+    fn parse_locale_tags() {
+        assert_eq!(Locale::parse(Some("it")), Locale::It);
+        assert_eq!(Locale::parse(Some("it-IT")), Locale::It);
+        assert_eq!(Locale::parse(Some("en_US")), Locale::En);
+        assert_eq!(Locale::parse(Some("xx")), Locale::En);
+        assert_eq!(Locale::parse(None), Locale::En);
+    }
+
+    #[test]
+    fn every_key_translated_with_same_placeholders() {
+        for key in MessageKey::ALL {
+            let en = message(Locale::En, *key);
+            let it = message(Locale::It, *key);
+            assert!(!en.is_empty() && !it.is_empty(), "{:?}", key);
+            assert_eq!(placeholders(en), placeholders(it), "{:?}", key);
+        }
+    }
+
+    #[test]
+    fn format_fills_placeholders() {
+        assert_eq!(
+            format_message(Locale::En, MessageKey::IgnoreInvisible, &["5", "10"]),
+            "invisible (effective opacity 5% < 10% threshold)"
+        );
+        assert_eq!(format_message(Locale::It, MessageKey::AcrLevel, &["AA"]), "Livello AA");
+    }
+
+    #[test]
+    fn criterion_titles_localized() {
+        assert_eq!(criterion_title(Locale::En, "1.4.11"), Some("Non-text Contrast"));
+        assert_eq!(criterion_title(Locale::It, "1.4.3"), Some("Contrasto (minimo)"));
+        assert_eq!(criterion_title(Locale::It, "9.9.9"), None);
+    }
+}
@@ -0,0 +1,330 @@
+//! Ignore migration: the `a11y-ignore` comments that grandfather a
+//! baseline's violations, as text edits, so a team adopting the tool can
+//! suppress its existing violations mechanically and drop the baseline.
+//!
+//! Violations are matched against the baseline like `reconcileViolations`
+//! does (same hash, per-hash counts consumed in input order); each matched
+//! violation gets one comment before its element's opening tag, shared by
+//! every violation of that element and scoped to their rule ids, so a new
+//! kind of failure on the element is still reported:
+//! - `// a11y-ignore AA-TEXT-CONTRAST: <reason>` on its own line (`/* ... */`
+//!   mid-line) in JS expression position
+//! - `{/* a11y-ignore AA-TEXT-CONTRAST: <reason> */}` among JSX children
+//! - `<!-- a11y-ignore AA-TEXT-CONTRAST: <reason> -->` in Vue / Astro /
+//!   Angular / HTML templates
+//!
+//! Violations without a `rule_id` get an unscoped `a11y-ignore: <reason>`.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::manifest::sha256_hex;
+use crate::parser::{angular, astro, html, vue};
+use crate::types::{ContrastResult, FileInput, IgnoreEdit, IgnoreMigration, IgnoreMigrationOptions};
+
+/// Reason written into the comments when `IgnoreMigrationOptions.reason` is unset.
+pub const DEFAULT_REASON: &str = "baseline";
+
+/// Baseline violation counts: file → violation hash → count (the
+/// `violations` of a baseline file).
+pub type BaselineCounts = HashMap<String, HashMap<String, u32>>;
+
+/// Edits suppressing the violations of `violations` recorded in `baseline`,
+/// placed in the sources of `files`.
+pub fn migrate(
+    files: &[FileInput],
+    violations: &[ContrastResult],
+    baseline: &BaselineCounts,
+    options: &IgnoreMigrationOptions,
+) -> IgnoreMigration {
+    let case_insensitive = options.case_insensitive_paths == Some(true);
+    let reason = options.reason.as_deref().unwrap_or(DEFAULT_REASON);
+    let mut remaining: HashMap<(String, String), u32> = baseline
+        .iter()
+        .flat_map(|(file, hashes)| {
+            hashes.iter().map(move |(hash, count)| ((path_key(file, case_insensitive), hash.clone()), *count))
+        })
+        .collect();
+
+    let sources: HashMap<String, &str> =
+        files.iter().map(|f| (path_key(&f.path, case_insensitive), f.content.as_str())).collect();
+    // One edit per (file, insertion offset)
+    let mut insertions: BTreeMap<(String, usize), Insertion> = BTreeMap::new();
+    let mut missing_files = BTreeSet::new();
+    let mut grandfathered = 0;
+    for violation in violations {
+        let file = path_key(&violation.file, case_insensitive);
+        let Some(count) = remaining.get_mut(&(file.clone(), violation_hash(violation, case_insensitive))) else {
+            continue;
+        };
+        if *count == 0 {
+            continue;
+        }
+        *count -= 1;
+        grandfathered += 1;
+        let Some(source) = sources.get(&file) else {
+            missing_files.insert(violation.file.clone());
+            continue;
+        };
+        let Some(offset) = element_start(source, violation) else {
+            missing_files.insert(violation.file.clone());
+            continue;
+        };
+        let insertion = insertions.entry((file, offset)).or_insert_with(|| Insertion {
+            file: &violation.file,
+            source,
+            rules: Some(BTreeSet::new()),
+            violations: 0,
+        });
+        match (insertion.rules.as_mut(), violation.rule_id.as_deref()) {
+            (Some(rules), Some(rule_id)) => {
+                rules.insert(rule_id);
+            }
+            _ => insertion.rules = None,
+        }
+        insertion.violations += 1;
+    }
+
+    let edits = insertions
+        .into_iter()
+        .map(|((_, offset), insertion)| {
+            let annotation = match insertion.rules {
+                Some(rules) => format!("a11y-ignore {}: {}", rules.into_iter().collect::<Vec<_>>().join(", "), reason),
+                None => format!("a11y-ignore: {}", reason),
+            };
+            let edit = edit_at(insertion.file, insertion.source, offset, &annotation);
+            IgnoreEdit { violations: insertion.violations, ..edit }
+        })
+        .collect();
+    IgnoreMigration {
+        edits,
+        grandfathered,
+        missing_files: missing_files.into_iter().collect(),
+    }
+}
+
+/// The violations sharing one comment.
+struct Insertion<'a> {
+    file: &'a str,
+    source: &'a str,
+    /// Rule ids of the violations; None once one has none (unscoped comment)
+    rules: Option<BTreeSet<&'a str>>,
+    violations: u32,
+}
+
+/// `path` with `/` separators and no leading `./` (case-folded when asked),
+/// as the baseline keys it.
+fn path_key(path: &str, case_insensitive: bool) -> String {
+    let mut portable = path.replace('\\', "/");
+    while let Some(rest) = portable.strip_prefix("./") {
+        portable = rest.to_string();
+    }
+    if case_insensitive {
+        portable.to_lowercase()
+    } else {
+        portable
+    }
+}
+
+/// Baseline hash of a violation (same as the TS `generateViolationHash`):
+/// line and ratio are left out, class lists are sorted.
+pub fn violation_hash(violation: &ContrastResult, case_insensitive: bool) -> String {
+    let sorted = |classes: &str| {
+        let mut classes: Vec<&str> = classes.split_whitespace().collect();
+        classes.sort_unstable();
+        classes.join(" ")
+    };
+    let identity = [
+        path_key(&violation.file, case_insensitive),
+        sorted(&violation.bg_class),
+        sorted(&violation.text_class),
+        violation.pair_type.clone().unwrap_or_else(|| "text".to_string()),
+        violation.interactive_state.clone().unwrap_or_else(|| "base".to_string()),
+    ]
+    .join("::");
+    sha256_hex(identity.as_bytes())
+}
+
+/// Offset of the `<` opening the violation's element: the last `<tag` before
+/// its text class on its line (the end of the line if the class isn't
+/// there), else the start of the line's code.
+fn element_start(source: &str, violation: &ContrastResult) -> Option<usize> {
+    let line_start = line_offset(source, violation.line)?;
+    let line_end = source[line_start..].find('\n').map_or(source.len(), |p| line_start + p);
+    let line = &source[line_start..line_end];
+    let before = line.find(violation.text_class.as_str()).map_or(line_end, |p| line_start + p);
+    let tag = violation.tag_name.as_deref().filter(|t| !t.is_empty());
+    let open = tag.and_then(|tag| {
+        let needle = format!("<{}", tag);
+        source[..before].rmatch_indices(needle.as_str()).map(|(p, _)| p).find(|&p| {
+            let next = source.as_bytes().get(p + needle.len()).copied().unwrap_or(b' ');
+            !(next.is_ascii_alphanumeric() || matches!(next, b'-' | b'_' | b'.'))
+        })
+    });
+    Some(open.unwrap_or(line_start + (line.len() - line.trim_start().len())))
+}
+
+/// Byte offset of the start of 1-based `line`.
+fn line_offset(source: &str, line: u32) -> Option<usize> {
+    if line <= 1 {
+        return Some(0);
+    }
+    source.match_indices('\n').nth(line as usize - 2).map(|(p, _)| p + 1)
+}
+
+/// The `annotation` comment inserted at `offset` of `source`: a line of its
+/// own when the element starts its line, else inline before it.
+fn edit_at(file: &str, source: &str, offset: usize, annotation: &str) -> IgnoreEdit {
+    let line_start = source[..offset].rfind('\n').map_or(0, |p| p + 1);
+    let indent = &source[line_start..offset];
+    let own_line = indent.trim().is_empty();
+    let comment = if vue::is_vue_path(file)
+        || astro::is_astro_path(file)
+        || angular::is_angular_path(file)
+        || html::is_html_path(file)
+    {
+        format!("<!-- {} -->", annotation)
+    } else if in_jsx_children(&source[..offset]) {
+        format!("{{/* {} */}}", annotation)
+    } else if own_line {
+        format!("// {}", annotation)
+    } else {
+        format!("/* {} */", annotation)
+    };
+    let (column, text) = if own_line {
+        (1, format!("{}{}\n", indent, comment))
+    } else {
+        ((offset - line_start) as u32 + 1, format!("{} ", comment))
+    };
+    IgnoreEdit {
+        file: file.to_string(),
+        line: source[..line_start].matches('\n').count() as u32 + 1,
+        column,
+        text,
+        violations: 0,
+    }
+}
+
+/// Whether a tag after `before` is a JSX child (after a tag, an expression
+/// container or text) rather than a JS expression (`return`, `(`, `=>`, ...).
+fn in_jsx_children(before: &str) -> bool {
+    let before = before.trim_end();
+    if before.ends_with("=>") {
+        return false;
+    }
+    match before.bytes().last() {
+        Some(b'>' | b'}') => true,
+        Some(c) if c.is_ascii_alphanumeric() => {
+            let word = before.rsplit(|c: char| !c.is_ascii_alphanumeric()).next().unwrap_or_default();
+            !matches!(word, "return" | "yield" | "await" | "default")
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::checker::check_contrast;
+    use crate::types::ColorPair;
+
+    fn violation(file: &str, line: u32, tag: &str, text_class: &str) -> ContrastResult {
+        let pair = ColorPair {
+            file: file.to_string(),
+            line,
+            text_class: text_class.to_string(),
+            bg_class: "bg-white".to_string(),
+            text_hex: Some("#9ca3af".to_string()),
+            bg_hex: Some("#ffffff".to_string()),
+            tag_name: Some(tag.to_string()),
+            ..Default::default()
+        };
+        check_contrast(&pair, "#ffffff")
+    }
+
+    fn baseline_of(violations: &[&ContrastResult]) -> BaselineCounts {
+        let mut baseline = BaselineCounts::new();
+        for v in violations {
+            *baseline.entry(v.file.clone()).or_default().entry(violation_hash(v, false)).or_default() += 1;
+        }
+        baseline
+    }
+
+    #[test]
+    fn hash_matches_the_ts_baseline() {
+        let v = violation("./src\\App.tsx", 1, "p", "text-gray-400 font-bold");
+        // sha256("src/App.tsx::bg-white::font-bold text-gray-400::text::base")
+        assert_eq!(violation_hash(&v, false), "3a6116f63c606b68fc3815d0ad0b157e963cdd221c0a751dfe95a1f9580fc476");
+    }
+
+    #[test]
+    fn comments_fit_where_the_element_sits() {
+        let source = r#"export function Card() {
+  return (
+    <div className="p-4">
+      <p className="text-gray-400">a</p> <span className="text-gray-300">b</span>
+    </div>
+  );
+}
+const icon = <i className="text-gray-300" />;"#;
+        let p = violation("src/Card.tsx", 4, "p", "text-gray-400");
+        let span = violation("src/Card.tsx", 4, "span", "text-gray-300");
+        let icon = violation("src/Card.tsx", 8, "i", "text-gray-300");
+        let fresh = violation("src/Card.tsx", 4, "p", "text-gray-500");
+        let baseline = baseline_of(&[&p, &span, &icon]);
+        let files = [FileInput { path: "src/Card.tsx".to_string(), content: source.to_string() }];
+        let migration = migrate(&files, &[p.clone(), p, span, icon, fresh], &baseline, &Default::default());
+
+        assert_eq!(migration.grandfathered, 3);
+        let edits: Vec<(u32, u32, &str, u32)> =
+            migration.edits.iter().map(|e| (e.line, e.column, e.text.as_str(), e.violations)).collect();
+        assert_eq!(
+            edits,
+            [
+                (4, 1, "      {/* a11y-ignore: baseline */}\n", 1),
+                (4, 42, "{/* a11y-ignore: baseline */} ", 1),
+                (8, 14, "/* a11y-ignore: baseline */ ", 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn templates_and_missing_sources() {
+        let source = "<template>\n  <p class=\"text-gray-400\">a</p>\n</template>";
+        let p = violation("src/A.vue", 2, "p", "text-gray-400");
+        let other = violation("src/B.tsx", 1, "p", "text-gray-400");
+        let files = [FileInput { path: "src/A.vue".to_string(), content: source.to_string() }];
+        let options = IgnoreMigrationOptions { reason: Some("legacy".to_string()), ..Default::default() };
+        let migration = migrate(&files, &[p.clone(), other.clone()], &baseline_of(&[&p, &other]), &options);
+        assert_eq!(migration.edits.len(), 1);
+        assert_eq!(migration.edits[0].text, "  <!-- a11y-ignore: legacy -->\n");
+        assert_eq!(migration.missing_files, ["src/B.tsx"]);
+    }
+
+    #[test]
+    fn applied_edits_suppress_the_violations() {
+        use crate::engine::extract_and_scan;
+        use crate::types::ExtractOptions;
+
+        let source = "export const A = () => (\n  <div>\n    <p className=\"text-gray-400\">a</p>\n  </div>\n);";
+        let mut p = violation("a.tsx", 3, "p", "text-gray-400");
+        p.rule_id = Some("AA-TEXT-CONTRAST".to_string());
+        let files = [FileInput { path: "a.tsx".to_string(), content: source.to_string() }];
+        let migration = migrate(&files, std::slice::from_ref(&p), &baseline_of(&[&p]), &Default::default());
+        let edit = &migration.edits[0];
+        assert_eq!(edit.text, "    {/* a11y-ignore AA-TEXT-CONTRAST: baseline */}\n");
+        let offset = line_offset(source, edit.line).unwrap() + edit.column as usize - 1;
+        let migrated = format!("{}{}{}", &source[..offset], edit.text, &source[offset..]);
+
+        let options = ExtractOptions {
+            file_contents: vec![FileInput { path: "a.tsx".to_string(), content: migrated }],
+            default_bg: "bg-background".to_string(),
+            ..Default::default()
+        };
+        let results = extract_and_scan(&options);
+        let region = results[0].regions.iter().find(|r| r.content == "text-gray-400").unwrap();
+        assert_eq!(region.content, "text-gray-400");
+        assert_eq!(region.ignored, Some(true));
+        assert_eq!(region.ignore_reason.as_deref(), Some("baseline"));
+        assert_eq!(region.ignored_rules, Some(vec!["AA-TEXT-CONTRAST".to_string()]));
+    }
+}
@@ -5,21 +5,123 @@ pub mod types;
 pub mod math;
 pub mod parser;
 pub mod engine;
+pub mod metrics;
+pub mod logging;
+pub mod manifest;
+pub mod criteria;
+pub mod profiles;
+pub mod acr;
+pub mod i18n;
+pub mod sampling;
+pub mod diff;
+pub mod pairs;
+pub mod theme;
+pub mod snippets;
+pub mod sniff;
+pub mod limits;
+pub mod walker;
+pub mod decode;
+pub mod vendored;
+pub mod fingerprint;
+pub mod rules;
+pub mod health;
+pub mod fix_plan;
+pub mod palette_optimizer;
+pub mod remediation;
+pub mod hover;
+pub mod region_index;
+pub mod autocomplete;
+pub mod workspace;
+pub mod reload;
+pub mod snapshot;
+pub mod cache_file;
+pub mod capabilities;
+pub mod self_test;
+pub mod file_kinds;
+pub mod export;
+pub mod ignore_migration;
+pub mod serve;
+pub mod scorecard;
+pub mod config_lint;
 
-use types::{CheckResultJs, ColorPair, ExtractOptions, PreExtractedFile};
+use std::ops::ControlFlow;
+
+use napi::{JsFunction, JsUnknown};
+use types::{
+    AcrReport, CandidateContrast, Capabilities, CheckOptions, CheckResultJs, ColorPair, ConfigDelta, ConfigLintFinding, ContrastResult,
+    CssModuleClass, DiffAnnotation, DiffOptions, ExtractOptions, FileInput, FileKindPolicies, FixPlan, FixPlanOptions, HoverInfo, IgnoreMigration, IgnoreMigrationOptions,
+    IndexedRegion,
+    PaletteOptimization, PaletteOptimizerOptions, PreExtractedFile, ProfileReport, ResultSnapshot, RoutedFile,
+    ScanReport, Scorecard, SelfTestReport, Snippet, ThemeConfig, TokenSimulation, TokenSimulationInput, WorkspaceRoot,
+};
 
 #[napi]
 pub fn health_check() -> String {
     "a11y-audit-native ok".to_string()
 }
 
+/// Engine version, supported file types, rules with their versions and
+/// optional features, so wrappers can feature-detect across upgrades.
+#[napi]
+pub fn get_capabilities() -> Capabilities {
+    capabilities::capabilities()
+}
+
+/// Run the built-in conformance suite (known color pairs and snippets) in
+/// this binary; failed cases carry expected vs. actual values.
+#[napi]
+pub fn self_test() -> SelfTestReport {
+    self_test::self_test()
+}
+
 /// Parse multiple JSX files in parallel and return extracted ClassRegion data.
 /// Main entry point for the parsing phase.
 #[napi]
 pub fn extract_and_scan(options: ExtractOptions) -> Vec<PreExtractedFile> {
+    logging::init(options.log_level.as_deref());
     engine::extract_and_scan(&options)
 }
 
+/// Like `extract_and_scan`, but files in `options.changed_paths` are parsed
+/// first and passed to `on_batch` before the rest of the run is parsed, so CI
+/// can report on touched files immediately. Each later batch is passed too;
+/// if `on_batch` throws, the run stops and the error is returned.
+#[napi(ts_args_type = "options: ExtractOptions, onBatch: (files: Array<PreExtractedFile>) => void")]
+pub fn extract_and_scan_prioritized(
+    options: ExtractOptions,
+    on_batch: JsFunction,
+) -> napi::Result<Vec<PreExtractedFile>> {
+    logging::init(options.log_level.as_deref());
+    let mut callback_err = None;
+    let files = engine::extract_and_scan_batched(&options, |batch| {
+        match on_batch.call1::<Vec<PreExtractedFile>, JsUnknown>(batch.to_vec()) {
+            Ok(_) => ControlFlow::Continue(()),
+            Err(err) => {
+                callback_err = Some(err);
+                ControlFlow::Break(())
+            }
+        }
+    });
+    match callback_err {
+        Some(err) => Err(err),
+        None => Ok(files),
+    }
+}
+
+/// Like `extract_and_scan`, but also returns the run manifest (engine version,
+/// options/theme hashes, per-file content hashes, rule versions).
+#[napi]
+pub fn extract_and_scan_with_report(options: ExtractOptions) -> ScanReport {
+    logging::init(options.log_level.as_deref());
+    engine::extract_and_scan_with_report(&options)
+}
+
+/// Heuristic JSX detection for `.js` sources (pragma, react import, tags).
+#[napi]
+pub fn looks_like_jsx(content: String) -> bool {
+    sniff::looks_like_jsx(&content)
+}
+
 /// Check contrast for all color pairs against WCAG/APCA thresholds.
 /// Returns violations, passed, ignored, and skip counts.
 #[napi]
@@ -28,12 +130,316 @@ pub fn check_contrast_pairs(
     threshold: String,
     page_bg: String,
 ) -> CheckResultJs {
-    let result = math::checker::check_all_pairs(&pairs, &threshold, &page_bg);
-    CheckResultJs {
-        violations: result.violations,
-        passed: result.passed,
-        ignored: result.ignored,
-        ignored_count: result.ignored_count,
-        skipped_count: result.skipped_count,
+    logging::init(None);
+    math::checker::check_all_pairs(&pairs, &threshold, &page_bg).into()
+}
+
+/// Like `check_contrast_pairs`, with optional settings and opt-in
+/// experimental checks (`experimental: ["wcag3"]`).
+#[napi]
+pub fn check_contrast_pairs_with_options(pairs: Vec<ColorPair>, options: CheckOptions) -> CheckResultJs {
+    logging::init(None);
+    math::checker::check_all_pairs_with_options(&pairs, &options)
+}
+
+/// Regroup checked results by regulatory clause numbering.
+/// `profile`: "wcag" | "en301549" | "section508"; `locale` picks section titles.
+#[napi]
+pub fn group_results_by_profile(
+    results: Vec<ContrastResult>,
+    profile: String,
+    locale: Option<String>,
+) -> napi::Result<ProfileReport> {
+    let parsed = profiles::ReportProfile::parse(&profile).ok_or_else(|| {
+        napi::Error::from_reason(format!("unknown report profile: {}", profile))
+    })?;
+    Ok(profiles::group_by_profile(&results, parsed, i18n::Locale::parse(locale.as_deref())))
+}
+
+/// Pre-fill an ACR (VPAT 2.x) table for the color criteria from a check result.
+/// `locale` ("en" default, "it") picks titles, remarks and table labels.
+#[napi]
+pub fn generate_acr(result: CheckResultJs, locale: Option<String>) -> AcrReport {
+    acr::generate_acr(&result, i18n::Locale::parse(locale.as_deref()))
+}
+
+/// Per-component scorecard (variants audited, pass rate, worst pair,
+/// suppressions) as structured rows, JSON and a Markdown table.
+#[napi]
+pub fn generate_scorecard(result: CheckResultJs) -> Scorecard {
+    scorecard::generate_scorecard(&result)
+}
+
+/// Mark every result with `introduced_in_diff`: whether its line was added or
+/// modified according to the unified diff and/or explicit line ranges.
+#[napi]
+pub fn mark_introduced_in_diff(mut result: CheckResultJs, diff: DiffOptions) -> CheckResultJs {
+    let changed = diff::ChangedLines::from_options(&diff);
+    diff::mark_introduced(&mut result, &changed);
+    result
+}
+
+/// Inline review comments for the violations on lines `unified_diff` adds,
+/// with hunk-relative positions; `locale` ("en" default, "it") picks the text.
+#[napi]
+pub fn annotate_diff(unified_diff: String, result: CheckResultJs, locale: Option<String>) -> Vec<DiffAnnotation> {
+    diff::annotate(&unified_diff, &result, i18n::Locale::parse(locale.as_deref()))
+}
+
+/// Set `package` ("name@version") on results from vendored node_modules files,
+/// so upstream components are reported separately from app code.
+#[napi]
+pub fn attribute_vendored_packages(mut result: CheckResultJs) -> CheckResultJs {
+    vendored::attribute_packages(&mut result);
+    result
+}
+
+/// Tag test, story, e2e and mock results with `file_kind`, drop the "skip"
+/// kinds and move violations of "informational" kinds into `informational`.
+#[napi]
+pub fn apply_file_kind_policies(mut result: CheckResultJs, policies: FileKindPolicies) -> CheckResultJs {
+    file_kinds::apply_policies(&mut result, &policies);
+    result
+}
+
+/// `container_config` / `portal_config` entries of `options` that are
+/// duplicated, conflicting, never rendered or mostly overridden by explicit
+/// bg classes in `files` (the `extract_and_scan` result of `options`).
+#[napi]
+pub fn lint_config(options: ExtractOptions, files: Vec<PreExtractedFile>) -> Vec<ConfigLintFinding> {
+    config_lint::lint(&options, &files)
+}
+
+/// `a11y-ignore` comment insertions grandfathering the `violations` recorded
+/// in `baseline` (a baseline file's `violations`: file → hash → count),
+/// placed in the sources of `files`.
+#[napi]
+pub fn migrate_ignores(
+    files: Vec<FileInput>,
+    violations: Vec<ContrastResult>,
+    baseline: ignore_migration::BaselineCounts,
+    options: Option<IgnoreMigrationOptions>,
+) -> IgnoreMigration {
+    ignore_migration::migrate(&files, &violations, &baseline, &options.unwrap_or_default())
+}
+
+/// The colors of each plain class rule of the CSS module stylesheet `css`,
+/// for `ExtractOptions.css_modules` (`module` is the stylesheet's path).
+#[napi]
+pub fn parse_css_module(module: String, css: String) -> Vec<CssModuleClass> {
+    parser::css_modules::parse_css_module(&module, &css)
+}
+
+/// The flattened result table (one row per result, `status` = its list) as CSV.
+#[napi]
+pub fn export_results_csv(result: CheckResultJs) -> String {
+    export::to_csv(&result)
+}
+
+/// Write the flattened result table to `path` as Parquet (builds with the
+/// `parquet` feature only; see `capabilities().features`).
+#[cfg(feature = "parquet")]
+#[napi]
+pub fn export_results_parquet(result: CheckResultJs, path: String) -> napi::Result<()> {
+    export::write_parquet(std::path::Path::new(&path), &result).map_err(napi::Error::from_reason)
+}
+
+/// Collapse app violations that repeat a vendored package's own violation
+/// (same fingerprint) into `inherited`, tagged "inherited from name@version".
+#[napi]
+pub fn collapse_inherited_violations(mut result: CheckResultJs) -> CheckResultJs {
+    vendored::collapse_inherited(&mut result);
+    result
+}
+
+/// Set `fingerprint` (location-independent failure identity) and the stable
+/// result `id` on every result.
+#[napi]
+pub fn fingerprint_results(mut result: CheckResultJs) -> CheckResultJs {
+    fingerprint::assign_fingerprints(&mut result);
+    result
+}
+
+/// Cluster violations by shared token pair and propose one token-level change
+/// per cluster, with its projected impact (violations fixed, regressions).
+#[napi]
+pub fn plan_fixes(result: CheckResultJs, options: Option<FixPlanOptions>) -> FixPlan {
+    fix_plan::plan_fixes(&result, &options.unwrap_or_default())
+}
+
+/// What-if: recompute every pair reading `token` (e.g. "--color-muted-foreground")
+/// with `new_value` (any CSS color) and report before/after pass counts.
+#[napi]
+pub fn simulate_token_change(
+    input: TokenSimulationInput,
+    token: String,
+    new_value: String,
+) -> napi::Result<TokenSimulation> {
+    fix_plan::simulate_token_change(&input, &token, &new_value)
+        .ok_or_else(|| napi::Error::from_reason(format!("invalid color: {}", new_value)))
+}
+
+/// Search small OKLCH changes of failing tokens reaching a target pass rate
+/// within a ΔE limit; returns ranked proposals.
+#[napi]
+pub fn optimize_palette(input: TokenSimulationInput, options: Option<PaletteOptimizerOptions>) -> PaletteOptimization {
+    palette_optimizer::optimize_palette(&input, &options.unwrap_or_default())
+}
+
+/// Register theme/config inputs once; returns a handle for `audit_snippets`.
+#[napi]
+pub fn register_theme(config: ThemeConfig) -> u32 {
+    theme::register(theme::Theme::from_config(&config))
+}
+
+/// Hot-reload a registered theme from edited config (watch mode): the new
+/// theme replaces the old one atomically, indexed files are re-scanned or
+/// re-checked as needed, and files whose violations changed are returned.
+#[napi]
+pub fn update_config(theme_handle: u32, config: ThemeConfig) -> napi::Result<ConfigDelta> {
+    reload::update_config(theme_handle, &config)
+        .ok_or_else(|| napi::Error::from_reason(format!("unknown theme handle: {}", theme_handle)))
+}
+
+/// Immutable copy of the current results of every file indexed under a
+/// theme; later updates and reloads don't affect it and aren't blocked by it.
+#[napi]
+pub fn snapshot(theme_handle: u32) -> napi::Result<ResultSnapshot> {
+    snapshot::snapshot(theme_handle)
+        .ok_or_else(|| napi::Error::from_reason(format!("unknown theme handle: {}", theme_handle)))
+}
+
+/// Serve the live results of a theme's indexed files as a JSON API
+/// (`/summary`, `/results`, `/files`, `/file?path=`) on `address` (default
+/// "127.0.0.1:0", a free local port). Returns the bound "host:port".
+#[napi]
+pub fn serve_results(theme_handle: u32, address: Option<String>) -> napi::Result<String> {
+    registered_theme(theme_handle)?;
+    serve::start(theme_handle, address.as_deref().unwrap_or("127.0.0.1:0"))
+        .map(|addr| addr.to_string())
+        .map_err(|err| napi::Error::from_reason(format!("could not serve results: {}", err)))
+}
+
+/// Stop the results API of a theme. Returns false if none was running.
+#[napi]
+pub fn stop_serving_results(theme_handle: u32) -> bool {
+    serve::stop(theme_handle)
+}
+
+/// Release a theme handle and its indexed files. Returns false if it was not registered.
+#[napi]
+pub fn release_theme(handle: u32) -> bool {
+    serve::stop(handle);
+    region_index::release(handle);
+    workspace::release_theme(handle);
+    theme::release(handle)
+}
+
+/// Pre-commit fast path: audit in-memory snippets against a registered theme
+/// and return only the violations.
+#[napi]
+pub fn audit_snippets(theme_handle: u32, snippets: Vec<Snippet>) -> napi::Result<Vec<ContrastResult>> {
+    let theme = theme::get(theme_handle).ok_or_else(|| {
+        napi::Error::from_reason(format!("unknown theme handle: {}", theme_handle))
+    })?;
+    Ok(snippets::audit_snippets(&theme, &snippets))
+}
+
+/// Index the class regions of open documents under a theme, for
+/// `hover_info`, `region_at` and `regions_in_diff`. Unchanged files are not
+/// re-scanned. Returns the number of regions indexed.
+#[napi]
+pub fn index_files(theme_handle: u32, snippets: Vec<Snippet>) -> napi::Result<u32> {
+    let theme = registered_theme(theme_handle)?;
+    Ok(snippets
+        .iter()
+        .map(|snippet| region_index::file_index(theme_handle, &theme, snippet).regions().len() as u32)
+        .sum())
+}
+
+/// Indexed region at (`line`, `column`), both 1-based, of `path` (for code actions).
+#[napi]
+pub fn region_at(theme_handle: u32, path: String, line: u32, column: u32) -> Option<IndexedRegion> {
+    region_index::region_at(theme_handle, &path, line, column)
+}
+
+/// Indexed regions starting on a line added or modified by the diff.
+#[napi]
+pub fn regions_in_diff(theme_handle: u32, diff: DiffOptions) -> Vec<IndexedRegion> {
+    region_index::regions_in_diff(theme_handle, &diff::ChangedLines::from_options(&diff))
+}
+
+/// Editor hover payload for the class region at (`line`, `column`), both
+/// 1-based, of an open document: its resolved colors, results and fix advice.
+/// The document is (re-)indexed first if its content changed.
+#[napi]
+pub fn hover_info(theme_handle: u32, snippet: Snippet, line: u32, column: u32) -> napi::Result<Option<HoverInfo>> {
+    let theme = registered_theme(theme_handle)?;
+    let index = region_index::file_index(theme_handle, &theme, &snippet);
+    Ok(hover::hover_info(&theme, &snippet.path, &index, line, column))
+}
+
+/// Ratio and verdict of each candidate text class on `context_bg` (bg class
+/// or hex), in input order, so editor autocomplete can grey out failing classes.
+#[napi]
+pub fn rank_candidate_classes(
+    theme_handle: u32,
+    context_bg: String,
+    candidates: Vec<String>,
+) -> napi::Result<Vec<CandidateContrast>> {
+    let theme = registered_theme(theme_handle)?;
+    autocomplete::rank_candidate_classes(&theme, &context_bg, &candidates)
+        .ok_or_else(|| napi::Error::from_reason(format!("unresolvable context bg: {}", context_bg)))
+}
+
+/// Audit files under `root` with a registered theme; in a multi-root
+/// workspace each project root gets its own theme.
+#[napi]
+pub fn add_workspace_root(root: String, theme_handle: u32) -> napi::Result<()> {
+    registered_theme(theme_handle)?;
+    workspace::add_root(&root, theme_handle);
+    Ok(())
+}
+
+/// Stop routing files to `root`. Returns false for unknown roots.
+#[napi]
+pub fn remove_workspace_root(root: String) -> bool {
+    workspace::remove_root(&root)
+}
+
+/// Project a file belongs to (longest registered root containing it).
+#[napi]
+pub fn workspace_root_of(path: String) -> Option<WorkspaceRoot> {
+    workspace::root_of(&path)
+}
+
+/// Route changed documents to their projects and re-index each under its
+/// project's theme; files outside every root come back unrouted.
+#[napi]
+pub fn update_workspace_files(snippets: Vec<Snippet>) -> Vec<RoutedFile> {
+    workspace::update_files(&snippets)
+}
+
+/// Payload of the `kind` cache file at `path` (e.g. "scan", "quarantine").
+/// Missing, unreadable, other-kind, truncated, checksum-failing and
+/// other-version files all return null (only the bad files that were read
+/// are deleted), so callers degrade to a cold scan.
+#[napi]
+pub fn read_cache_file(path: String, kind: String) -> Option<String> {
+    match cache_file::read_cache(std::path::Path::new(&path), &kind) {
+        cache_file::CacheRead::Hit(payload) => Some(payload),
+        _ => None,
     }
 }
+
+/// Write a `kind` cache file with a versioned, checksummed header; the
+/// previous file is replaced atomically.
+#[napi]
+pub fn write_cache_file(path: String, kind: String, payload: String) -> napi::Result<()> {
+    cache_file::write_cache(std::path::Path::new(&path), &kind, &payload)
+        .map_err(|e| napi::Error::from_reason(format!("could not write cache {}: {}", path, e)))
+}
+
+fn registered_theme(handle: u32) -> napi::Result<std::sync::Arc<theme::Theme>> {
+    theme::get(handle).ok_or_else(|| napi::Error::from_reason(format!("unknown theme handle: {}", handle)))
+}
@@ -0,0 +1,168 @@
+//! Resource quotas for a scan (`ExtractOptions.limits`).
+//!
+//! Quotas degrade the run instead of failing it: files past `max_files` /
+//! `max_total_bytes` or not started within `time_budget_ms` are skipped,
+//! regions past `max_regions_per_file` are dropped, and the report lists
+//! which limits were hit so a partial result is never mistaken for a full one.
+
+use std::time::{Duration, Instant};
+
+use crate::types::{FileInput, LimitsHit, PreExtractedFile, ScanLimits};
+
+pub const MAX_FILES: &str = "maxFiles";
+pub const MAX_TOTAL_BYTES: &str = "maxTotalBytes";
+pub const MAX_REGIONS_PER_FILE: &str = "maxRegionsPerFile";
+pub const TIME_BUDGET_MS: &str = "timeBudgetMs";
+
+/// Limits reached during one run.
+#[derive(Debug, Default)]
+pub struct LimitTracker {
+    limits: Vec<&'static str>,
+    skipped_files: Vec<String>,
+    truncated_files: Vec<String>,
+}
+
+impl LimitTracker {
+    fn hit(&mut self, limit: &'static str) {
+        if !self.limits.contains(&limit) {
+            log::warn!("scan limit {} reached; results are partial", limit);
+            self.limits.push(limit);
+        }
+    }
+
+    /// Record files skipped because the wall-clock budget ran out.
+    pub fn skipped_by_budget(&mut self, paths: Vec<String>) {
+        if !paths.is_empty() {
+            self.hit(TIME_BUDGET_MS);
+            self.skipped_files.extend(paths);
+        }
+    }
+
+    /// `None` when no limit was reached.
+    pub fn into_report(self) -> Option<LimitsHit> {
+        (!self.limits.is_empty()).then(|| LimitsHit {
+            limits: self.limits.iter().map(|l| l.to_string()).collect(),
+            skipped_files: self.skipped_files,
+            truncated_files: self.truncated_files,
+        })
+    }
+}
+
+/// Apply `max_files` and `max_total_bytes` to the batches in scan order, so
+/// earlier batches (changed files) keep priority over later ones.
+pub fn admit_files(
+    batches: &mut [(Vec<&FileInput>, bool)],
+    limits: &ScanLimits,
+    tracker: &mut LimitTracker,
+) {
+    let mut files = 0u64;
+    let mut bytes = 0u64;
+    for (batch, _) in batches.iter_mut() {
+        batch.retain(|f| {
+            let size = f.content.len() as u64;
+            let over = if limits.max_files.is_some_and(|max| files >= max as u64) {
+                Some(MAX_FILES)
+            } else if limits.max_total_bytes.is_some_and(|max| bytes + size > max.max(0) as u64) {
+                Some(MAX_TOTAL_BYTES)
+            } else {
+                None
+            };
+            match over {
+                Some(limit) => {
+                    tracker.hit(limit);
+                    tracker.skipped_files.push(f.path.clone());
+                    false
+                }
+                None => {
+                    files += 1;
+                    bytes += size;
+                    true
+                }
+            }
+        });
+    }
+}
+
+/// Instant after which no new file is started.
+pub fn deadline(limits: &ScanLimits, run_start: Instant) -> Option<Instant> {
+    limits
+        .time_budget_ms
+        .map(|ms| run_start + Duration::from_millis(ms as u64))
+}
+
+/// Drop regions past `max_regions_per_file`.
+pub fn truncate_regions(file: &mut PreExtractedFile, limits: &ScanLimits, tracker: &mut LimitTracker) {
+    if let Some(max) = limits.max_regions_per_file {
+        if file.regions.len() > max as usize {
+            file.regions.truncate(max as usize);
+            tracker.hit(MAX_REGIONS_PER_FILE);
+            tracker.truncated_files.push(file.path.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(path: &str, size: usize) -> FileInput {
+        FileInput {
+            path: path.to_string(),
+            content: "x".repeat(size),
+        }
+    }
+
+    #[test]
+    fn no_limits_no_report() {
+        let files = [input("a.tsx", 10)];
+        let mut batches = vec![(files.iter().collect::<Vec<_>>(), false)];
+        let mut tracker = LimitTracker::default();
+        admit_files(&mut batches, &ScanLimits::default(), &mut tracker);
+        assert_eq!(batches[0].0.len(), 1);
+        assert!(tracker.into_report().is_none());
+    }
+
+    #[test]
+    fn max_files_keeps_earlier_batches() {
+        let changed = [input("changed.tsx", 1)];
+        let rest = [input("a.tsx", 1), input("b.tsx", 1)];
+        let mut batches = vec![
+            (changed.iter().collect::<Vec<_>>(), true),
+            (rest.iter().collect::<Vec<_>>(), false),
+        ];
+        let limits = ScanLimits { max_files: Some(2), ..Default::default() };
+        let mut tracker = LimitTracker::default();
+        admit_files(&mut batches, &limits, &mut tracker);
+        assert_eq!(batches[0].0.len(), 1);
+        assert_eq!(batches[1].0.len(), 1);
+        let report = tracker.into_report().unwrap();
+        assert_eq!(report.limits, vec![MAX_FILES]);
+        assert_eq!(report.skipped_files, vec!["b.tsx"]);
+    }
+
+    #[test]
+    fn max_total_bytes_skips_files_that_do_not_fit() {
+        let files = [input("a.tsx", 60), input("big.tsx", 50), input("c.tsx", 30)];
+        let mut batches = vec![(files.iter().collect::<Vec<_>>(), false)];
+        let limits = ScanLimits { max_total_bytes: Some(100), ..Default::default() };
+        let mut tracker = LimitTracker::default();
+        admit_files(&mut batches, &limits, &mut tracker);
+        let kept: Vec<&str> = batches[0].0.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(kept, vec!["a.tsx", "c.tsx"]);
+        assert_eq!(tracker.into_report().unwrap().limits, vec![MAX_TOTAL_BYTES]);
+    }
+
+    #[test]
+    fn regions_truncated_per_file() {
+        let mut file = PreExtractedFile {
+            path: "a.tsx".to_string(),
+            regions: vec![Default::default(); 5],
+            ..Default::default()
+        };
+        let limits = ScanLimits { max_regions_per_file: Some(3), ..Default::default() };
+        let mut tracker = LimitTracker::default();
+        truncate_regions(&mut file, &limits, &mut tracker);
+        assert_eq!(file.regions.len(), 3);
+        assert_eq!(tracker.into_report().unwrap().truncated_files, vec!["a.tsx"]);
+    }
+}
@@ -0,0 +1,99 @@
+use std::io::Write as _;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Environment variable consulted when no explicit level is passed in options.
+pub const LOG_ENV_VAR: &str = "A11Y_AUDIT_LOG";
+
+/// Minimal stderr backend for the `log` facade.
+///
+/// The native module is loaded into a Node process that has no Rust logger of
+/// its own, so we install this one on first use. If an embedding Rust host has
+/// already installed a logger, `set_logger` fails and we only adjust the level.
+struct StderrLogger;
+
+static LOGGER: StderrLogger = StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let _ = writeln!(
+            std::io::stderr().lock(),
+            "[a11y-audit {} {}] {}",
+            record.level(),
+            record.target(),
+            record.args()
+        );
+    }
+
+    fn flush(&self) {
+        let _ = std::io::stderr().flush();
+    }
+}
+
+/// Parse a level name ("off", "error", "warn", "info", "debug", "trace").
+/// Case-insensitive; returns None for unknown names.
+pub fn parse_level(value: &str) -> Option<LevelFilter> {
+    value.trim().parse::<LevelFilter>().ok()
+}
+
+/// Resolve the effective level: explicit option > environment > `Warn`.
+/// Unknown names fall through to the next source instead of disabling logging.
+pub fn resolve_level(option: Option<&str>, env: Option<&str>) -> LevelFilter {
+    option
+        .and_then(parse_level)
+        .or_else(|| env.and_then(parse_level))
+        .unwrap_or(LevelFilter::Warn)
+}
+
+/// Install the stderr logger (once) and apply the resolved level.
+/// Called at every NAPI entry point so each run can pick its own verbosity.
+pub fn init(option: Option<&str>) {
+    let env = std::env::var(LOG_ENV_VAR).ok();
+    let level = resolve_level(option, env.as_deref());
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(level);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_known_levels() {
+        assert_eq!(parse_level("debug"), Some(LevelFilter::Debug));
+        assert_eq!(parse_level("TRACE"), Some(LevelFilter::Trace));
+        assert_eq!(parse_level(" off "), Some(LevelFilter::Off));
+    }
+
+    #[test]
+    fn parse_unknown_level() {
+        assert_eq!(parse_level("verbose"), None);
+    }
+
+    #[test]
+    fn option_wins_over_env() {
+        assert_eq!(resolve_level(Some("info"), Some("trace")), LevelFilter::Info);
+    }
+
+    #[test]
+    fn env_used_when_option_missing() {
+        assert_eq!(resolve_level(None, Some("debug")), LevelFilter::Debug);
+    }
+
+    #[test]
+    fn invalid_option_falls_back_to_env() {
+        assert_eq!(resolve_level(Some("loud"), Some("error")), LevelFilter::Error);
+    }
+
+    #[test]
+    fn defaults_to_warn() {
+        assert_eq!(resolve_level(None, None), LevelFilter::Warn);
+    }
+}
@@ -0,0 +1,177 @@
+use sha2::{Digest, Sha256};
+
+use crate::i18n::Locale;
+use crate::parser::ScanConfig;
+use crate::types::{ExtractOptions, ManifestFile, RuleVersion, RunManifest, WalkOptions};
+
+/// Version of every rule whose output can change between engine releases.
+/// Bump a rule's version whenever its verdicts can differ for the same input,
+/// so manifests from two runs prove whether their results are comparable.
+pub const RULE_VERSIONS: &[(&str, &str)] = &[
+    ("jsx-extraction", "1"),
+    ("wcag21-contrast", "1"),
+    ("apca-w3", "0.1.9"),
+    ("disabled-exemption", "1"),
+    ("opacity-visibility", "1"),
+    ("use-of-color", "1"),
+    ("required-marker", "1"),
+    ("click-on-static-element", "1"),
+];
+
+/// Hex-encoded SHA-256 digest (same digest the TS baseline uses for violation hashes).
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    let digest = Sha256::digest(bytes);
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Build the reproducibility manifest for a run over `options`.
+///
+/// - `options_hash` covers every result-affecting option (file contents,
+///   output paths, walk root, log verbosity and report locale excluded), serialized via serde so new
+///   options are picked up automatically.
+/// - `theme_hash` covers only the parser config the options build (containers,
+///   portals, default bg, named contexts, class helpers, ...; report locale
+///   excluded) so theme drift can be told apart from other changes.
+/// - `files` is sorted by path so manifests diff cleanly.
+pub fn build_manifest(options: &ExtractOptions) -> RunManifest {
+    let mut files: Vec<ManifestFile> = options
+        .file_contents
+        .iter()
+        .map(|f| ManifestFile {
+            path: f.path.clone(),
+            content_hash: sha256_hex(f.content.as_bytes()),
+        })
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    RunManifest {
+        engine_version: env!("CARGO_PKG_VERSION").to_string(),
+        options_hash: options_hash(options),
+        theme_hash: theme_hash(options),
+        files,
+        rule_versions: rule_versions(),
+    }
+}
+
+/// `RULE_VERSIONS` as manifest entries.
+pub fn rule_versions() -> Vec<RuleVersion> {
+    RULE_VERSIONS
+        .iter()
+        .map(|(rule, version)| RuleVersion {
+            rule: rule.to_string(),
+            version: version.to_string(),
+        })
+        .collect()
+}
+
+fn options_hash(options: &ExtractOptions) -> String {
+    let normalized = ExtractOptions {
+        file_contents: Vec::new(),
+        metrics_path: None,
+        log_level: None,
+        locale: None,
+        // The walk root is machine-specific; the walked files are hashed per file
+        walk: options.walk.clone().map(|w| WalkOptions {
+            root: String::new(),
+            ..w
+        }),
+        ..options.clone()
+    };
+    let json = serde_json::to_vec(&normalized).unwrap_or_default();
+    sha256_hex(&json)
+}
+
+fn theme_hash(options: &ExtractOptions) -> String {
+    let config = ScanConfig { locale: Locale::default(), ..ScanConfig::from_options(options) };
+    // Through a JSON value, whose maps sort their keys
+    let json = serde_json::to_value(&config).and_then(|value| serde_json::to_vec(&value)).unwrap_or_default();
+    sha256_hex(&json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ContainerEntry, FileInput, NamedContext};
+
+    fn make_options(files: &[(&str, &str)]) -> ExtractOptions {
+        ExtractOptions {
+            file_contents: files
+                .iter()
+                .map(|(path, content)| FileInput {
+                    path: path.to_string(),
+                    content: content.to_string(),
+                })
+                .collect(),
+            container_config: ["Card", "Dialog", "Popover", "Sheet"]
+                .iter()
+                .map(|component| ContainerEntry {
+                    component: component.to_string(),
+                    bg_class: "bg-card".to_string(),
+                })
+                .collect(),
+            default_bg: "bg-background".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sha256_known_vector() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn files_sorted_with_content_hashes() {
+        let manifest = build_manifest(&make_options(&[("b.tsx", "b"), ("a.tsx", "a")]));
+        assert_eq!(manifest.files[0].path, "a.tsx");
+        assert_eq!(manifest.files[1].path, "b.tsx");
+        assert_eq!(manifest.files[0].content_hash, sha256_hex(b"a"));
+    }
+
+    #[test]
+    fn same_inputs_same_hashes() {
+        let a = build_manifest(&make_options(&[("a.tsx", "x")]));
+        let b = build_manifest(&make_options(&[("a.tsx", "x")]));
+        assert_eq!(a.options_hash, b.options_hash);
+        assert_eq!(a.theme_hash, b.theme_hash);
+    }
+
+    #[test]
+    fn options_hash_ignores_file_contents_and_outputs() {
+        let a = build_manifest(&make_options(&[("a.tsx", "x")]));
+        let mut opts = make_options(&[("other.tsx", "y")]);
+        opts.metrics_path = Some("/tmp/m.prom".to_string());
+        opts.log_level = Some("trace".to_string());
+        opts.locale = Some("it".to_string());
+        let b = build_manifest(&opts);
+        assert_eq!(a.options_hash, b.options_hash);
+    }
+
+    #[test]
+    fn theme_change_changes_both_hashes() {
+        let a = build_manifest(&make_options(&[]));
+        let mut opts = make_options(&[]);
+        opts.default_bg = "bg-muted".to_string();
+        let b = build_manifest(&opts);
+        assert_ne!(a.theme_hash, b.theme_hash);
+        assert_ne!(a.options_hash, b.options_hash);
+
+        opts.named_contexts = Some(vec![NamedContext { name: "hero".to_string(), bg_class: "bg-slate-900".to_string() }]);
+        assert_ne!(build_manifest(&opts).theme_hash, b.theme_hash);
+        opts.locale = Some("it".to_string());
+        opts.class_functions = Some(vec!["tw".to_string()]);
+        let c = build_manifest(&opts);
+        opts.locale = None;
+        assert_eq!(build_manifest(&opts).theme_hash, c.theme_hash);
+    }
+
+    #[test]
+    fn carries_engine_and_rule_versions() {
+        let manifest = build_manifest(&make_options(&[]));
+        assert_eq!(manifest.engine_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(manifest.rule_versions.len(), RULE_VERSIONS.len());
+        assert!(manifest.rule_versions.iter().any(|r| r.rule == "wcag21-contrast"));
+    }
+}
@@ -0,0 +1,66 @@
+//! Weber and Michelson contrast, for research correlating audit results with
+//! user-testing data.
+//!
+//! Both are computed from the same composited colors and WCAG relative
+//! luminance (without the 0.05 flare term) as `ratio`, so they line up with
+//! each result's WCAG/APCA values. They are informational only: set with
+//! `CheckOptions.alternate_contrast` and never used for pass/fail.
+
+use super::checker::{effective_colors, pair_of};
+use super::wcag::relative_luminance;
+use crate::types::CheckResultJs;
+
+/// Decimal places kept (luminance differences near black are tiny).
+const DECIMALS: i32 = 4;
+
+fn round(value: f64) -> f64 {
+    let scale = 10f64.powi(DECIMALS);
+    (value * scale).round() / scale
+}
+
+/// Weber contrast of the text against its background, (Lt - Lb) / Lb:
+/// negative for dark text on a lighter bg. None on a pure black bg.
+pub fn weber(text_hex: &str, bg_hex: &str) -> Option<f64> {
+    let (text, bg) = (relative_luminance(text_hex), relative_luminance(bg_hex));
+    (bg > 0.0).then(|| round((text - bg) / bg))
+}
+
+/// Michelson contrast, (Lmax - Lmin) / (Lmax + Lmin), from 0 to 1. None when
+/// both colors are pure black.
+pub fn michelson(text_hex: &str, bg_hex: &str) -> Option<f64> {
+    let (a, b) = (relative_luminance(text_hex), relative_luminance(bg_hex));
+    let (max, min) = if a > b { (a, b) } else { (b, a) };
+    (max + min > 0.0).then(|| round((max - min) / (max + min)))
+}
+
+/// Set `weber_contrast` and `michelson_contrast` on every result.
+pub fn annotate(result: &mut CheckResultJs, page_bg: &str) {
+    for r in result
+        .violations
+        .iter_mut()
+        .chain(result.passed.iter_mut())
+        .chain(result.ignored.iter_mut())
+    {
+        let (fg, bg) = effective_colors(&pair_of(r), page_bg);
+        r.weber_contrast = weber(&fg, &bg);
+        r.michelson_contrast = michelson(&fg, &bg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_values() {
+        assert_eq!(weber("#000000", "#ffffff"), Some(-1.0));
+        assert_eq!(michelson("#000000", "#ffffff"), Some(1.0));
+        assert_eq!(michelson("#ffffff", "#000000"), Some(1.0));
+        assert_eq!(weber("#ffffff", "#000000"), None);
+        assert_eq!(michelson("#000000", "#000000"), None);
+        assert_eq!(michelson("#767676", "#767676"), Some(0.0));
+        // #767676: relative luminance 0.1812
+        assert_eq!(weber("#767676", "#ffffff"), Some(-0.8188));
+        assert_eq!(michelson("#767676", "#ffffff"), Some(0.6932));
+    }
+}
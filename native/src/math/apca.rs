@@ -1,3 +1,5 @@
+use crate::types::{ApcaFontOption, ApcaInfo};
+
 /// Calculate APCA Lightness Contrast (Lc) value.
 /// Positive Lc = dark text on light bg.
 /// Negative Lc = light text on dark bg.
@@ -67,10 +69,126 @@ pub fn calc_apca_lc(text_hex: &str, bg_hex: &str) -> f64 {
     output_contrast * 100.0
 }
 
+/// Font weights of the lookup table columns.
+const FONT_WEIGHTS: [u32; 9] = [100, 200, 300, 400, 500, 600, 700, 800, 900];
+/// Table marker: contrast too low for any text at this weight.
+const NOT_TEXT: f64 = 999.0;
+/// Table marker: only usable for non-text (spot elements, dividers).
+const NON_TEXT_ONLY: f64 = 777.0;
+
+/// Minimum font size (px) per weight (100..900) at each Lc step.
+///
+/// Port of: apca-w3 0.1.9 → fontLookupAPCA() (the ascending Lc table); rows
+/// are used as floors, so a measured Lc between steps gets the stricter row.
+const FONT_LOOKUP: &[(f64, [f64; 9])] = &[
+    (0.0, [999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0]),
+    (15.0, [777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0]),
+    (25.0, [777.0, 777.0, 777.0, 120.0, 120.0, 108.0, 96.0, 96.0, 96.0]),
+    (30.0, [777.0, 777.0, 120.0, 108.0, 108.0, 96.0, 72.0, 72.0, 72.0]),
+    (35.0, [777.0, 120.0, 108.0, 96.0, 72.0, 60.0, 48.0, 48.0, 48.0]),
+    (40.0, [120.0, 108.0, 96.0, 60.0, 48.0, 42.0, 32.0, 32.0, 32.0]),
+    (45.0, [108.0, 96.0, 72.0, 42.0, 32.0, 28.0, 24.0, 24.0, 24.0]),
+    (50.0, [96.0, 72.0, 60.0, 32.0, 28.0, 24.0, 21.0, 21.0, 21.0]),
+    (55.0, [80.0, 60.0, 48.0, 28.0, 24.0, 21.0, 18.0, 18.0, 18.0]),
+    (60.0, [72.0, 48.0, 42.0, 24.0, 21.0, 18.0, 16.0, 16.0, 18.0]),
+    (65.0, [68.0, 46.0, 32.0, 21.75, 19.0, 17.0, 15.0, 16.0, 18.0]),
+    (70.0, [64.0, 44.0, 28.0, 19.5, 18.0, 16.0, 14.5, 16.0, 18.0]),
+    (75.0, [60.0, 42.0, 24.0, 18.0, 16.0, 15.0, 14.0, 16.0, 18.0]),
+    (80.0, [56.0, 38.25, 23.0, 17.25, 15.81, 14.81, 14.0, 16.0, 18.0]),
+    (85.0, [52.0, 34.5, 22.0, 16.5, 15.625, 14.625, 14.0, 16.0, 18.0]),
+    (90.0, [48.0, 32.0, 21.0, 16.0, 15.5, 14.5, 14.0, 16.0, 18.0]),
+    (95.0, [45.0, 28.0, 19.5, 15.5, 15.0, 14.0, 13.5, 16.0, 18.0]),
+    (100.0, [42.0, 26.5, 18.5, 15.0, 14.5, 13.5, 13.0, 16.0, 18.0]),
+    (105.0, [39.0, 25.0, 18.0, 14.0, 14.0, 13.0, 12.0, 16.0, 18.0]),
+];
+
+/// Polarity of a signed Lc: positive = dark text on light bg.
+pub fn polarity(lc: f64) -> &'static str {
+    if lc >= 0.0 { "BoW" } else { "WoB" }
+}
+
+/// APCA use-case band of |Lc| (Bronze simple mode levels).
+pub fn lc_band(lc: f64) -> &'static str {
+    match lc.abs() {
+        l if l >= 90.0 => "fluent",
+        l if l >= 75.0 => "body",
+        l if l >= 60.0 => "content",
+        l if l >= 45.0 => "headline",
+        l if l >= 30.0 => "spot",
+        l if l >= 15.0 => "non-text",
+        _ => "invisible",
+    }
+}
+
+/// Smallest font size per weight that is readable at |Lc|; weights too thin
+/// for any text at this contrast are omitted.
+pub fn min_font_sizes(lc: f64) -> Vec<ApcaFontOption> {
+    let abs = lc.abs();
+    let Some((_, sizes)) = FONT_LOOKUP.iter().rev().find(|(step, _)| abs >= *step) else {
+        return Vec::new();
+    };
+    FONT_WEIGHTS
+        .iter()
+        .zip(sizes)
+        .filter(|(_, size)| **size != NOT_TEXT && **size != NON_TEXT_ONLY)
+        .map(|(weight, size)| ApcaFontOption {
+            weight: *weight,
+            size_px: *size,
+        })
+        .collect()
+}
+
+/// Structured APCA metadata for a measured Lc. Font sizes only apply to text.
+pub fn apca_info(lc: f64, is_text: bool) -> ApcaInfo {
+    ApcaInfo {
+        polarity: polarity(lc).to_string(),
+        lc_band: lc_band(lc).to_string(),
+        min_font_sizes: if is_text { min_font_sizes(lc) } else { Vec::new() },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn polarity_from_sign() {
+        assert_eq!(polarity(calc_apca_lc("#000000", "#ffffff")), "BoW");
+        assert_eq!(polarity(calc_apca_lc("#ffffff", "#000000")), "WoB");
+    }
+
+    #[test]
+    fn bands_use_absolute_lc() {
+        assert_eq!(lc_band(92.0), "fluent");
+        assert_eq!(lc_band(-76.0), "body");
+        assert_eq!(lc_band(47.5), "headline");
+        assert_eq!(lc_band(10.0), "invisible");
+    }
+
+    #[test]
+    fn font_sizes_use_stricter_row_between_steps() {
+        // Lc 62 → row 60: 400 weight needs 24px, 700 needs 16px
+        let sizes = min_font_sizes(62.0);
+        let at = |w: u32| sizes.iter().find(|o| o.weight == w).map(|o| o.size_px);
+        assert_eq!(at(400), Some(24.0));
+        assert_eq!(at(700), Some(16.0));
+        assert_eq!(sizes.len(), 9);
+    }
+
+    #[test]
+    fn low_contrast_drops_thin_weights() {
+        let sizes = min_font_sizes(-32.0);
+        assert_eq!(sizes.first().map(|o| o.weight), Some(300));
+        assert!(min_font_sizes(20.0).is_empty());
+    }
+
+    #[test]
+    fn non_text_gets_no_font_sizes() {
+        let info = apca_info(70.0, false);
+        assert_eq!(info.lc_band, "content");
+        assert!(info.min_font_sizes.is_empty());
+    }
+
     // Cross-reference values from: node -e "const {calcAPCA} = require('apca-w3'); ..."
     #[test]
     fn black_on_white() {
@@ -1,10 +1,14 @@
-use crate::types::{ColorPair, ContrastResult};
+use std::borrow::Cow;
 
-/// Check contrast for a single color pair.
-/// Performs alpha compositing, then WCAG ratio + APCA Lc.
-///
-/// Port of: src/core/contrast-checker.ts → checkContrast()
-pub fn check_contrast(pair: &ColorPair, page_bg: &str) -> ContrastResult {
+use super::severity::SeverityWeights;
+use super::strict_profile::StrictProfile;
+use crate::i18n::Locale;
+use crate::pairs::class_resolver::ColorMap;
+use crate::types::{CheckOptions, CheckResultJs, ColorPair, ContrastResult, ResolvedColor};
+
+/// Opaque (fg, bg) colors of a pair as rendered: bg alpha composited over the
+/// page bg, then text alpha over that.
+pub fn effective_colors(pair: &ColorPair, page_bg: &str) -> (String, String) {
     let bg_hex = pair.bg_hex.as_deref().unwrap_or(page_bg);
     let text_hex = pair.text_hex.as_deref().unwrap_or("#000000");
 
@@ -19,14 +23,27 @@ pub fn check_contrast(pair: &ColorPair, page_bg: &str) -> ContrastResult {
         Some(a) if a < 0.999 => super::composite::composite_over(text_hex, &effective_bg, a),
         _ => text_hex.to_string(),
     };
+    (effective_fg, effective_bg)
+}
+
+/// Check contrast for a single color pair.
+/// Performs alpha compositing, then WCAG ratio + APCA Lc.
+///
+/// Port of: src/core/contrast-checker.ts → checkContrast()
+pub fn check_contrast(pair: &ColorPair, page_bg: &str) -> ContrastResult {
+    let (effective_fg, effective_bg) = effective_colors(pair, page_bg);
 
     let ratio_raw = super::wcag::contrast_ratio(&effective_fg, &effective_bg);
     let ratio = (ratio_raw * 100.0).round() / 100.0;
     let is_large = pair.is_large_text.unwrap_or(false);
-    let wcag = super::wcag::check_wcag_thresholds(ratio_raw, is_large);
+    // A margin of m requires m × each threshold: equivalent to judging ratio / m
+    let margin = pair.safety_margin.filter(|m| *m > 1.0);
+    let wcag = super::wcag::check_wcag_thresholds(ratio_raw / margin.unwrap_or(1.0), is_large);
 
     let apca_lc_raw = super::apca::calc_apca_lc(&effective_fg, &effective_bg);
     let apca_lc = Some((apca_lc_raw * 100.0).round() / 100.0);
+    let is_text = pair.pair_type.as_deref().is_none_or(|t| t == "text");
+    let apca = apca_lc.map(|lc| super::apca::apca_info(lc, is_text));
 
     ContrastResult {
         file: pair.file.clone(),
@@ -42,6 +59,7 @@ pub fn check_contrast(pair: &ColorPair, page_bg: &str) -> ContrastResult {
         interactive_state: pair.interactive_state.clone(),
         ignored: pair.ignored,
         ignore_reason: pair.ignore_reason.clone(),
+        ignore_rules: pair.ignore_rules.clone(),
         context_source: pair.context_source.clone(),
         effective_opacity: pair.effective_opacity,
         is_disabled: pair.is_disabled,
@@ -52,11 +70,92 @@ pub fn check_contrast(pair: &ColorPair, page_bg: &str) -> ContrastResult {
         pass_aaa: wcag.pass_aaa,
         pass_aaa_large: wcag.pass_aaa_large,
         apca_lc,
+        apca,
         deuteranopia_ratio: None,
         protanopia_ratio: None,
+        achromatopsia_ratio: None,
+        achromatopsia_distinguishable: None,
+        low_vision_ratio: None,
+        low_vision_distinguishable: None,
+        rule_id: None,
+        wcag_criteria: None,
+        wcag_techniques: None,
+        introduced_in_diff: None,
+        package: None,
+        fingerprint: None,
+        id: None,
+        inherited_from: None,
+        wcag3_pass: None,
+        weber_contrast: None,
+        michelson_contrast: None,
+        required_marker: pair.required_marker,
+        strict_profile: None,
+        remediation: None,
+        end_line: pair.end_line,
+        tag_name: pair.tag_name.clone(),
+        role: pair.role.clone(),
+        ancestry: pair.ancestry.clone(),
+        severity: None,
+        safety_margin: margin,
+        theme: pair.theme.clone(),
+        provenance: pair.provenance.clone(),
+        breakpoint: pair.breakpoint.clone(),
+        file_kind: None,
+    }
+}
+
+/// Whether a checked result fails `threshold` ("AA" | "AAA"). Non-text
+/// elements (border, ring, outline) use the large-text thresholds.
+pub fn is_violation(result: &ContrastResult, threshold: &str) -> bool {
+    let is_non_text = result.pair_type.as_deref().is_some_and(|t| t != "text");
+    let uses_large_threshold = is_non_text || result.is_large_text.unwrap_or(false);
+    match (threshold == "AAA", uses_large_threshold) {
+        (true, true) => !result.pass_aaa_large,
+        (true, false) => !result.pass_aaa,
+        (false, true) => !result.pass_aa_large,
+        (false, false) => !result.pass_aa,
+    }
+}
+
+/// The pair a result was checked from (for re-checking with other colors).
+pub fn pair_of(result: &ContrastResult) -> ColorPair {
+    ColorPair {
+        file: result.file.clone(),
+        line: result.line,
+        bg_class: result.bg_class.clone(),
+        text_class: result.text_class.clone(),
+        bg_hex: result.bg_hex.clone(),
+        text_hex: result.text_hex.clone(),
+        bg_alpha: result.bg_alpha,
+        text_alpha: result.text_alpha,
+        is_large_text: result.is_large_text,
+        pair_type: result.pair_type.clone(),
+        interactive_state: result.interactive_state.clone(),
+        ignored: result.ignored,
+        ignore_reason: result.ignore_reason.clone(),
+        ignore_rules: result.ignore_rules.clone(),
+        context_source: result.context_source.clone(),
+        effective_opacity: result.effective_opacity,
+        is_disabled: result.is_disabled,
+        unresolved_current_color: result.unresolved_current_color,
+        required_marker: result.required_marker,
+        end_line: result.end_line,
+        tag_name: result.tag_name.clone(),
+        role: result.role.clone(),
+        ancestry: result.ancestry.clone(),
+        safety_margin: result.safety_margin,
+        theme: result.theme.clone(),
+        provenance: result.provenance.clone(),
+        breakpoint: result.breakpoint.clone(),
     }
 }
 
+/// Whether an `a11y-ignore` covers `rule_id` for the result: unscoped, or
+/// scoped to rules including it (`a11y-ignore <RULE-ID>`).
+pub fn is_suppressed(result: &ContrastResult, rule_id: &str) -> bool {
+    result.ignored == Some(true) && result.ignore_rules.as_ref().is_none_or(|rules| rules.iter().any(|r| r == rule_id))
+}
+
 /// Check all pairs and categorize into violations/passed/ignored/skipped.
 ///
 /// Port of: src/core/contrast-checker.ts → checkAllPairs()
@@ -65,6 +164,17 @@ pub fn check_all_pairs(
     threshold: &str, // "AA" or "AAA"
     page_bg: &str,
 ) -> CheckResult {
+    check_all_pairs_with_profile(pairs, threshold, page_bg, None)
+}
+
+/// `check_all_pairs()`, with pairs matched by `strict` evaluated at AAA.
+pub fn check_all_pairs_with_profile(
+    pairs: &[ColorPair],
+    threshold: &str,
+    page_bg: &str,
+    strict: Option<&StrictProfile>,
+) -> CheckResult {
+    let run_threshold = threshold;
     let mut violations = Vec::new();
     let mut passed = Vec::new();
     let mut ignored = Vec::new();
@@ -84,29 +194,42 @@ pub fn check_all_pairs(
             continue;
         }
 
-        let result = check_contrast(pair, page_bg);
-
-        // Determine violation based on conformance level and pair type
-        // Non-text elements (border, ring, outline) use large-text thresholds
-        let is_non_text = pair.pair_type.as_deref().map_or(false, |t| t != "text");
-        let uses_large_threshold = is_non_text || pair.is_large_text.unwrap_or(false);
+        let mut result = check_contrast(pair, page_bg);
 
-        let is_violation = if threshold == "AAA" {
-            if uses_large_threshold {
-                !result.pass_aaa_large
-            } else {
-                !result.pass_aaa
-            }
+        let is_strict = strict.is_some_and(|s| s.applies(pair));
+        let threshold = if is_strict {
+            result.strict_profile = Some(true);
+            "AAA"
         } else {
-            // AA
-            if uses_large_threshold {
-                !result.pass_aa_large
-            } else {
-                !result.pass_aa
-            }
+            run_threshold
         };
 
-        if is_violation && pair.ignored == Some(true) {
+        let mapping = crate::criteria::map_criteria(
+            pair.pair_type.as_deref(),
+            pair.interactive_state.as_deref(),
+            pair.is_large_text.unwrap_or(false),
+            threshold,
+        );
+        result.rule_id = Some(mapping.rule_id.to_string());
+        result.wcag_criteria = Some(mapping.criteria.iter().map(|c| c.to_string()).collect());
+        result.wcag_techniques = Some(mapping.techniques.iter().map(|t| t.to_string()).collect());
+
+        let is_violation = is_violation(&result, threshold);
+
+        log::trace!(
+            "{}:{} {} on {} ratio={} violation={}",
+            pair.file,
+            pair.line,
+            pair.text_class,
+            pair.bg_class,
+            result.ratio,
+            is_violation
+        );
+
+        // A scoped `a11y-ignore <RULE-ID>` suppresses its rules only
+        let is_ignored = is_suppressed(&result, mapping.rule_id);
+
+        if is_violation && is_ignored {
             ignored_count += 1;
             ignored.push(result);
         } else if is_violation {
@@ -116,6 +239,16 @@ pub fn check_all_pairs(
         }
     }
 
+    log::debug!(
+        "checked {} pairs at {}: {} violations, {} passed, {} ignored, {} skipped",
+        pairs.len(),
+        run_threshold,
+        violations.len(),
+        passed.len(),
+        ignored_count,
+        skipped_count
+    );
+
     CheckResult {
         violations,
         passed,
@@ -133,6 +266,75 @@ pub struct CheckResult {
     pub skipped_count: u32,
 }
 
+impl From<CheckResult> for CheckResultJs {
+    fn from(result: CheckResult) -> Self {
+        Self {
+            violations: result.violations,
+            passed: result.passed,
+            ignored: result.ignored,
+            ignored_count: result.ignored_count,
+            skipped_count: result.skipped_count,
+            ..Default::default()
+        }
+    }
+}
+
+/// Whether `flag` is listed in `options.experimental`.
+pub fn experimental_enabled(options: &CheckOptions, flag: &str) -> bool {
+    options.experimental.iter().flatten().any(|f| f == flag)
+}
+
+/// `check_all_pairs()` with defaults from `CheckOptions`, plus the health
+/// score and the opt-in checks (low-alpha text, experimental scoring,
+/// state-change perceivability). A `safety_margin` is carried on each pair, so the
+/// remediation re-checks apply it too.
+pub fn check_all_pairs_with_options(pairs: &[ColorPair], options: &CheckOptions) -> CheckResultJs {
+    let threshold = options.threshold.as_deref().unwrap_or("AA");
+    let page_bg = options.page_bg.as_deref().unwrap_or("#ffffff");
+    let strict = StrictProfile::from_options(options);
+    let pairs: Cow<[ColorPair]> = match options.safety_margin.filter(|m| *m > 1.0) {
+        Some(margin) => pairs.iter().map(|p| ColorPair { safety_margin: Some(margin), ..p.clone() }).collect(),
+        None => Cow::Borrowed(pairs),
+    };
+    let pairs = pairs.as_ref();
+    let mut result: CheckResultJs =
+        check_all_pairs_with_profile(pairs, threshold, page_bg, strict.as_ref()).into();
+    if options.check_text_alpha == Some(true) {
+        let min_alpha = options.min_text_alpha.unwrap_or(super::text_alpha::DEFAULT_MIN_TEXT_ALPHA);
+        super::text_alpha::flag_low_alpha(&mut result, min_alpha);
+    }
+    result.health = Some(crate::health::score(pairs, &result));
+    let palette: ColorMap = options
+        .color_map
+        .iter()
+        .flatten()
+        .map(|e| (e.name.clone(), ResolvedColor { hex: e.hex.clone(), alpha: e.alpha }))
+        .collect();
+    let locale = Locale::parse(options.locale.as_deref());
+    crate::remediation::attach_remediation(&mut result.violations, &palette, threshold, page_bg, locale);
+    if experimental_enabled(options, super::wcag3::FLAG) {
+        result.wcag3 = Some(super::wcag3::score(&mut result));
+    }
+    if let Some(weights) = SeverityWeights::from_options(options) {
+        weights.annotate(&mut result.violations);
+    }
+    if options.alternate_contrast == Some(true) {
+        super::alt_contrast::annotate(&mut result, page_bg);
+    }
+    if options.vision_simulation == Some(true) {
+        super::cvd::annotate(&mut result, page_bg);
+    }
+    if options.check_state_changes == Some(true) {
+        let min_delta_e = options
+            .min_state_delta_e
+            .unwrap_or(super::state_delta::DEFAULT_MIN_DELTA_E);
+        result.imperceptible_state_changes = Some(
+            super::state_delta::imperceptible_state_changes(pairs, page_bg, min_delta_e),
+        );
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,10 +355,20 @@ mod tests {
             interactive_state: None,
             ignored: None,
             ignore_reason: None,
+            ignore_rules: None,
             context_source: None,
             effective_opacity: None,
             is_disabled: None,
             unresolved_current_color: None,
+            required_marker: None,
+            tag_name: None,
+            role: None,
+            end_line: None,
+            ancestry: None,
+            safety_margin: None,
+            theme: None,
+            provenance: None,
+            breakpoint: None,
         }
     }
 
@@ -180,6 +392,105 @@ mod tests {
         assert!((result.ratio - rounded).abs() < 0.001);
     }
 
+    #[test]
+    fn wcag3_scoring_only_when_flagged() {
+        let pairs = vec![make_pair("#ffffff", "#000000")];
+        let plain = check_all_pairs_with_options(&pairs, &CheckOptions::default());
+        assert!(plain.wcag3.is_none());
+        assert_eq!(plain.passed[0].wcag3_pass, None);
+
+        let options = CheckOptions {
+            experimental: Some(vec!["wcag3".to_string()]),
+            ..Default::default()
+        };
+        let flagged = check_all_pairs_with_options(&pairs, &options);
+        assert_eq!(flagged.wcag3.unwrap().rating, 4);
+        assert_eq!(flagged.passed[0].wcag3_pass, Some(true));
+    }
+
+    #[test]
+    fn alternate_contrast_only_when_requested() {
+        let mut translucent = make_pair("#ffffff", "#000000");
+        translucent.text_alpha = Some(0.5);
+        let pairs = vec![make_pair("#ffffff", "#767676"), translucent];
+        let plain = check_all_pairs_with_options(&pairs, &CheckOptions::default());
+        assert_eq!((plain.passed[0].weber_contrast, plain.passed[0].michelson_contrast), (None, None));
+
+        let options = CheckOptions { alternate_contrast: Some(true), ..Default::default() };
+        let result = check_all_pairs_with_options(&pairs, &options);
+        let all: Vec<&ContrastResult> = result.passed.iter().chain(&result.violations).collect();
+        let plain_gray = all.iter().find(|r| r.text_alpha.is_none()).unwrap();
+        assert_eq!(plain_gray.weber_contrast, Some(-0.8188));
+        assert_eq!(plain_gray.michelson_contrast, Some(0.6932));
+        // Composited like `ratio`: 50% black over white, not pure black
+        let composited = all.iter().find(|r| r.text_alpha.is_some()).unwrap();
+        assert!(composited.michelson_contrast.unwrap() < 1.0);
+    }
+
+    #[test]
+    fn vision_simulation_only_when_requested() {
+        let mut border = make_pair("#ffffff", "#008000");
+        border.pair_type = Some("border".to_string());
+        let pairs = vec![make_pair("#ffffff", "#000000"), border];
+        let plain = check_all_pairs_with_options(&pairs, &CheckOptions::default());
+        assert!(plain.passed.iter().chain(&plain.violations).all(|r| r.achromatopsia_ratio.is_none()));
+
+        let options = CheckOptions { vision_simulation: Some(true), ..Default::default() };
+        let result = check_all_pairs_with_options(&pairs, &options);
+        let all: Vec<&ContrastResult> = result.passed.iter().chain(&result.violations).collect();
+        let is_border = |r: &&&ContrastResult| r.pair_type.as_deref() == Some("border");
+        let text = all.iter().find(|r| !is_border(r)).unwrap();
+        assert_eq!((text.achromatopsia_ratio, text.achromatopsia_distinguishable), (Some(21.0), Some(true)));
+        assert_eq!(text.low_vision_distinguishable, Some(true));
+        let border = all.iter().find(is_border).unwrap();
+        assert!(border.low_vision_ratio.unwrap() < border.ratio);
+        assert_eq!(border.low_vision_distinguishable, Some(border.low_vision_ratio.unwrap() >= 3.0));
+    }
+
+    #[test]
+    fn safety_margin_raises_every_threshold() {
+        // #767676 on white: 4.54:1, passes AA only nominally
+        let mut border = make_pair("#ffffff", "#949494");
+        border.pair_type = Some("border".to_string());
+        let pairs = vec![make_pair("#ffffff", "#767676"), border];
+        let nominal = check_all_pairs_with_options(&pairs, &CheckOptions::default());
+        assert_eq!((nominal.passed.len(), nominal.violations.len()), (2, 0));
+        assert!(nominal.passed.iter().all(|r| r.safety_margin.is_none()));
+
+        let options = CheckOptions { safety_margin: Some(1.2), ..Default::default() };
+        let result = check_all_pairs_with_options(&pairs, &options);
+        assert_eq!(result.violations.len(), 2);
+        assert!(result.violations.iter().all(|r| r.safety_margin == Some(1.2)));
+        // The reported ratio stays nominal; only the verdict moves
+        let text = result.violations.iter().find(|r| r.pair_type.as_deref() == Some("text")).unwrap();
+        assert_eq!((text.ratio, text.pass_aa, text.pass_aa_large), (4.54, false, true));
+
+        let loosened = CheckOptions { safety_margin: Some(0.5), ..Default::default() };
+        assert_eq!(check_all_pairs_with_options(&pairs, &loosened).violations.len(), 0);
+    }
+
+    #[test]
+    fn strict_profile_checks_headings_at_aaa() {
+        // #767676 on white: 4.54:1 — passes AA, fails AAA
+        let mut heading = make_pair("#ffffff", "#767676");
+        heading.tag_name = Some("h2".to_string());
+        let body = make_pair("#ffffff", "#767676");
+        let pairs = vec![heading, body];
+
+        let plain = check_all_pairs_with_options(&pairs, &CheckOptions::default());
+        assert_eq!(plain.violations.len(), 0);
+
+        let options = CheckOptions {
+            strict_profile: Some(true),
+            ..Default::default()
+        };
+        let strict = check_all_pairs_with_options(&pairs, &options);
+        assert_eq!(strict.violations.len(), 1);
+        assert_eq!(strict.violations[0].strict_profile, Some(true));
+        assert_eq!(strict.violations[0].wcag_criteria.as_deref().unwrap_or_default(), ["1.4.6"]);
+        assert_eq!(strict.passed[0].strict_profile, None);
+    }
+
     #[test]
     fn apca_lc_computed() {
         let pair = make_pair("#ffffff", "#000000");
@@ -261,6 +572,19 @@ mod tests {
         assert_eq!(result.ignored.len(), 1);
     }
 
+    #[test]
+    fn scoped_ignore_suppresses_its_rules_only() {
+        let mut text = make_pair("#ffffff", "#cccccc");
+        text.ignored = Some(true);
+        text.ignore_rules = Some(vec!["NONTEXT-CONTRAST".to_string()]);
+        let mut border = text.clone();
+        border.pair_type = Some("border".to_string());
+        let result = check_all_pairs(&[text, border], "AA", "#ffffff");
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.violations[0].rule_id.as_deref(), Some("AA-TEXT-CONTRAST"));
+        assert_eq!(result.ignored[0].rule_id.as_deref(), Some("NONTEXT-CONTRAST"));
+    }
+
     #[test]
     fn disabled_pair_skipped() {
         let mut pair = make_pair("#ffffff", "#cccccc");
@@ -299,6 +623,23 @@ mod tests {
         assert_eq!(result.violations.len(), 1);
     }
 
+    #[test]
+    fn results_tagged_with_success_criteria() {
+        let mut border = make_pair("#ffffff", "#cccccc");
+        border.pair_type = Some("border".to_string());
+        let pairs = vec![make_pair("#ffffff", "#cccccc"), border];
+        let result = check_all_pairs(&pairs, "AA", "#ffffff");
+        assert_eq!(result.violations[0].wcag_criteria.as_deref().unwrap_or_default(), ["1.4.3"]);
+        assert_eq!(result.violations[0].wcag_techniques.as_deref().unwrap_or_default(), ["G18"]);
+        assert_eq!(result.violations[1].wcag_criteria.as_deref().unwrap_or_default(), ["1.4.11"]);
+    }
+
+    #[test]
+    fn passed_results_also_tagged() {
+        let result = check_all_pairs(&[make_pair("#ffffff", "#000000")], "AAA", "#ffffff");
+        assert_eq!(result.passed[0].wcag_criteria.as_deref().unwrap_or_default(), ["1.4.6"]);
+    }
+
     #[test]
     fn multiple_pairs_categorized() {
         let pairs = vec![
@@ -15,8 +15,7 @@ pub fn to_hex(value: &str) -> Option<String> {
     }
 
     // Direct hex passthrough (normalize 3->6, 4->8 digit)
-    if trimmed.starts_with('#') {
-        let raw = &trimmed[1..];
+    if let Some(raw) = trimmed.strip_prefix('#') {
         return match raw.len() {
             3 => {
                 let expanded: String = raw.chars().flat_map(|c| [c, c]).collect();
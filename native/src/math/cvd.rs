@@ -0,0 +1,93 @@
+//! Vision simulation: achromatopsia (complete color blindness) and reduced
+//! acuity / contrast sensitivity.
+//!
+//! Informational only, set with `CheckOptions.vision_simulation`: each result
+//! reports its ratio under the simulated condition and whether the pair stays
+//! distinguishable there. Protanopia / deuteranopia (`protanopia_ratio`,
+//! `deuteranopia_ratio`) are pre-wired but not simulated yet.
+
+use super::checker::{effective_colors, pair_of};
+use super::hex::parse_hex_rgb;
+use super::wcag::contrast_ratio;
+use crate::types::{CheckResultJs, ContrastResult};
+
+/// Ratio at which a simulated pair still counts as distinguishable (the WCAG
+/// minimum for large text and non-text contrast).
+pub const DISTINGUISHABLE_RATIO: f64 = 3.0;
+
+/// Share of log contrast left after low-vision blur, calibrated to the
+/// acuity loss (about 20/80) WCAG's AAA thresholds compensate for: 7:1 normal
+/// and 4.5:1 large text stay just above 3:1. Thin strokes lose more than
+/// large text and non-text shapes.
+const LOW_VISION_NORMAL_TEXT: f64 = 0.6;
+const LOW_VISION_LARGE: f64 = 0.8;
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// `hex` as seen without cone vision: the gray of its Rec. 601 luma.
+pub fn achromatopsia(hex: &str) -> String {
+    let (r, g, b) = parse_hex_rgb(hex);
+    let luma = (0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", luma, luma, luma)
+}
+
+/// Contrast ratio of the pair under achromatopsia.
+pub fn achromatopsia_ratio(text_hex: &str, bg_hex: &str) -> f64 {
+    round2(contrast_ratio(&achromatopsia(text_hex), &achromatopsia(bg_hex)))
+}
+
+/// Contrast ratio left after the low-vision attenuation for the pair's size.
+pub fn low_vision_ratio(text_hex: &str, bg_hex: &str, large: bool) -> f64 {
+    let factor = if large { LOW_VISION_LARGE } else { LOW_VISION_NORMAL_TEXT };
+    round2(contrast_ratio(text_hex, bg_hex).powf(factor))
+}
+
+fn simulate(r: &mut ContrastResult, page_bg: &str) {
+    let (fg, bg) = effective_colors(&pair_of(r), page_bg);
+    let is_text = r.pair_type.as_deref().is_none_or(|t| t == "text");
+    let large = !is_text || r.is_large_text == Some(true);
+    let achromatopsia = achromatopsia_ratio(&fg, &bg);
+    let low_vision = low_vision_ratio(&fg, &bg, large);
+    r.achromatopsia_ratio = Some(achromatopsia);
+    r.achromatopsia_distinguishable = Some(achromatopsia >= DISTINGUISHABLE_RATIO);
+    r.low_vision_ratio = Some(low_vision);
+    r.low_vision_distinguishable = Some(low_vision >= DISTINGUISHABLE_RATIO);
+}
+
+/// Set the achromatopsia and low-vision fields on every result.
+pub fn annotate(result: &mut CheckResultJs, page_bg: &str) {
+    for r in result
+        .violations
+        .iter_mut()
+        .chain(result.passed.iter_mut())
+        .chain(result.ignored.iter_mut())
+    {
+        simulate(r, page_bg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn achromatopsia_grays() {
+        assert_eq!(achromatopsia("#ffffff"), "#ffffff");
+        assert_eq!(achromatopsia("#ff0000"), "#4c4c4c");
+        // Red on green: 1.28:1 in color, both nearly the same gray without cones
+        assert!(achromatopsia_ratio("#ff0000", "#008000") < 1.5);
+        assert_eq!(achromatopsia_ratio("#000000", "#ffffff"), 21.0);
+    }
+
+    #[test]
+    fn low_vision_attenuates_small_text_more() {
+        // #767676 on white: 4.54:1, AA only
+        assert_eq!(low_vision_ratio("#767676", "#ffffff", false), 2.48);
+        assert_eq!(low_vision_ratio("#767676", "#ffffff", true), 3.36);
+        // #595959 on white: 7.0:1, AAA
+        assert!(low_vision_ratio("#595959", "#ffffff", false) >= DISTINGUISHABLE_RATIO);
+        assert_eq!(low_vision_ratio("#ffffff", "#ffffff", false), 1.0);
+    }
+}
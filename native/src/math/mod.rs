@@ -4,3 +4,10 @@ pub mod wcag;
 pub mod apca;
 pub mod color_parse;
 pub mod checker;
+pub mod wcag3;
+pub mod state_delta;
+pub mod text_alpha;
+pub mod strict_profile;
+pub mod severity;
+pub mod alt_contrast;
+pub mod cvd;
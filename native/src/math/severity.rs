@@ -0,0 +1,122 @@
+//! Severity grading of violations by the element they are on.
+//!
+//! A failing contrast on body copy or a landmark matters more than on a
+//! decorative badge. Each violation is weighted by its tag (`p`), component
+//! (`Badge`) or `role` (`navigation`), the highest match winning, and graded
+//! "error" (weight >= 1), "warning" (>= 0.5) or "info". Unlisted elements
+//! weigh `DEFAULT_WEIGHT`.
+
+use crate::types::{CheckOptions, ContrastResult};
+
+/// Body text and landmarks escalate to errors; decorative inline text stays a warning.
+pub const DEFAULT_SEVERITY_WEIGHTS: &[(&str, f64)] = &[
+    ("p", 1.0), ("li", 1.0), ("dd", 1.0), ("dt", 1.0), ("td", 1.0), ("th", 1.0), ("blockquote", 1.0),
+    ("figcaption", 1.0), ("h1", 1.0), ("h2", 1.0), ("h3", 1.0), ("h4", 1.0), ("h5", 1.0), ("h6", 1.0),
+    ("a", 1.0), ("button", 1.0), ("label", 1.0), ("main", 1.0), ("nav", 1.0), ("header", 1.0),
+    ("footer", 1.0), ("aside", 1.0), ("article", 1.0), ("section", 1.0), ("paragraph", 1.0), ("heading", 1.0),
+    ("link", 1.0), ("navigation", 1.0), ("banner", 1.0), ("contentinfo", 1.0), ("complementary", 1.0),
+    ("region", 1.0), ("span", 0.5), ("small", 0.5), ("Badge", 0.5),
+];
+
+/// Weight of elements the map doesn't list.
+pub const DEFAULT_WEIGHT: f64 = 0.5;
+
+/// Tag/component/role → weight map violations are graded by.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeverityWeights {
+    weights: Vec<(String, f64)>,
+}
+
+impl SeverityWeights {
+    /// The map requested by `options`: `severity_weights` if given, else the
+    /// default map when `severity_escalation` is set.
+    pub fn from_options(options: &CheckOptions) -> Option<Self> {
+        match &options.severity_weights {
+            Some(weights) => Some(Self { weights: weights.iter().map(|w| (w.element.clone(), w.weight)).collect() }),
+            None if options.severity_escalation == Some(true) => Some(Self {
+                weights: DEFAULT_SEVERITY_WEIGHTS.iter().map(|(e, w)| (e.to_string(), *w)).collect(),
+            }),
+            None => None,
+        }
+    }
+
+    /// Weight of `result`'s element: the highest of its tag and role.
+    pub fn weight(&self, result: &ContrastResult) -> f64 {
+        [result.tag_name.as_deref(), result.role.as_deref()]
+            .into_iter()
+            .flatten()
+            .filter_map(|name| self.weights.iter().find(|(e, _)| e == name).map(|(_, w)| *w))
+            .reduce(f64::max)
+            .unwrap_or(DEFAULT_WEIGHT)
+    }
+
+    /// Set `severity` on every violation.
+    pub fn annotate(&self, violations: &mut [ContrastResult]) {
+        for result in violations {
+            result.severity = Some(grade(self.weight(result)).to_string());
+        }
+    }
+}
+
+fn grade(weight: f64) -> &'static str {
+    if weight >= 1.0 {
+        "error"
+    } else if weight >= 0.5 {
+        "warning"
+    } else {
+        "info"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::checker::check_contrast;
+    use crate::types::{ColorPair, SeverityWeight};
+
+    fn violation(tag: Option<&str>, role: Option<&str>) -> ContrastResult {
+        let pair = ColorPair {
+            text_hex: Some("#9ca3af".to_string()),
+            bg_hex: Some("#ffffff".to_string()),
+            tag_name: tag.map(str::to_string),
+            role: role.map(str::to_string),
+            ..Default::default()
+        };
+        check_contrast(&pair, "#ffffff")
+    }
+
+    #[test]
+    fn off_unless_requested() {
+        assert_eq!(SeverityWeights::from_options(&CheckOptions::default()), None);
+    }
+
+    #[test]
+    fn default_map_escalates_body_text_and_landmarks() {
+        let weights = SeverityWeights::from_options(&CheckOptions {
+            severity_escalation: Some(true),
+            ..Default::default()
+        })
+        .unwrap();
+        let mut violations = vec![
+            violation(Some("p"), None),
+            violation(Some("span"), None),
+            violation(Some("div"), Some("navigation")),
+            violation(Some("Badge"), None),
+            violation(None, None),
+        ];
+        weights.annotate(&mut violations);
+        let severities: Vec<&str> = violations.iter().map(|r| r.severity.as_deref().unwrap()).collect();
+        assert_eq!(severities, ["error", "warning", "error", "warning", "warning"]);
+    }
+
+    #[test]
+    fn custom_map_replaces_default() {
+        let weights = SeverityWeights::from_options(&CheckOptions {
+            severity_weights: Some(vec![SeverityWeight { element: "Tag".to_string(), weight: 0.2 }]),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(grade(weights.weight(&violation(Some("Tag"), None))), "info");
+        assert_eq!(grade(weights.weight(&violation(Some("p"), None))), "warning");
+    }
+}
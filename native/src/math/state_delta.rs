@@ -0,0 +1,192 @@
+//! Perceivability of interactive state changes (hover / focus vs rest).
+//!
+//! Absolute contrast says nothing about whether a hover or focus style is
+//! noticeable: `bg-gray-100 hover:bg-gray-50` passes contrast in both states
+//! yet barely changes. Each state pair is compared with the rest pair it
+//! derives from (same file, line, pair type and rest fg class); the change is
+//! imperceptible when neither the background nor the foreground moves by at
+//! least `min_delta_e` (CIE76 ΔE in CIELAB).
+
+use std::collections::HashMap;
+
+use crate::types::{ColorPair, StateChange};
+
+/// Default ΔE below which a state change is flagged (~1.5× the JND of 2.3).
+pub const DEFAULT_MIN_DELTA_E: f64 = 3.5;
+
+/// States compared against the rest state.
+const COMPARED_STATES: &[&str] = &["hover", "focus-visible"];
+
+/// sRGB hex → CIELAB (D65).
+pub fn hex_to_lab(hex: &str) -> (f64, f64, f64) {
+    let (r, g, b) = super::hex::parse_hex_rgb(hex);
+    let lin = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    };
+    let (r, g, b) = (lin(r), lin(g), lin(b));
+    let x = (0.4124564 * r + 0.3575761 * g + 0.1804375 * b) / 0.95047;
+    let y = 0.2126729 * r + 0.7151522 * g + 0.0721750 * b;
+    let z = (0.0193339 * r + 0.1191920 * g + 0.9503041 * b) / 1.08883;
+    let f = |t: f64| {
+        if t > 216.0 / 24389.0 { t.cbrt() } else { (24389.0 / 27.0 * t + 16.0) / 116.0 }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+
+/// CIE76 color difference between two hex colors.
+pub fn delta_e(a: &str, b: &str) -> f64 {
+    let (l1, a1, b1) = hex_to_lab(a);
+    let (l2, a2, b2) = hex_to_lab(b);
+    ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+}
+
+fn round2(v: f64) -> f64 {
+    (v * 100.0).round() / 100.0
+}
+
+/// Compare every hover/focus pair with its rest pair; returns the state
+/// changes below `min_delta_e`. Pairs with unresolved colors are skipped.
+pub fn imperceptible_state_changes(
+    pairs: &[ColorPair],
+    page_bg: &str,
+    min_delta_e: f64,
+) -> Vec<StateChange> {
+    type RestKey<'a> = (&'a str, u32, Option<&'a str>);
+    let resolved = |p: &&ColorPair| p.bg_hex.is_some() && p.text_hex.is_some();
+
+    let mut rest: HashMap<RestKey, Vec<&ColorPair>> = HashMap::new();
+    for pair in pairs.iter().filter(resolved).filter(|p| p.interactive_state.is_none()) {
+        rest.entry((pair.file.as_str(), pair.line, pair.pair_type.as_deref()))
+            .or_default()
+            .push(pair);
+    }
+
+    let mut flagged = Vec::new();
+    for state_pair in pairs.iter().filter(resolved) {
+        let Some(state) = state_pair.interactive_state.as_deref() else {
+            continue;
+        };
+        if !COMPARED_STATES.contains(&state) {
+            continue;
+        }
+        // Pairs that only inherit rest classes (the state styles another
+        // property, e.g. just its border) have nothing to compare.
+        let variant = format!("{}:", state);
+        if !state_pair.bg_class.contains(&variant) && !state_pair.text_class.contains(&variant) {
+            continue;
+        }
+        let key = (state_pair.file.as_str(), state_pair.line, state_pair.pair_type.as_deref());
+        let Some(candidates) = rest.get(&key) else {
+            continue;
+        };
+        // Prefer the rest pair with the same fg class (state inherited it)
+        let Some(rest_pair) = candidates
+            .iter()
+            .find(|p| p.text_class == state_pair.text_class)
+            .or_else(|| candidates.first())
+        else {
+            continue;
+        };
+
+        let (rest_fg, rest_bg) = super::checker::effective_colors(rest_pair, page_bg);
+        let (state_fg, state_bg) = super::checker::effective_colors(state_pair, page_bg);
+        let bg_delta = delta_e(&rest_bg, &state_bg);
+        let fg_delta = delta_e(&rest_fg, &state_fg);
+        if bg_delta.max(fg_delta) >= min_delta_e {
+            continue;
+        }
+        let luminance_delta = (super::wcag::relative_luminance(&rest_bg)
+            - super::wcag::relative_luminance(&state_bg))
+        .abs();
+        flagged.push(StateChange {
+            file: state_pair.file.clone(),
+            line: state_pair.line,
+            interactive_state: state.to_string(),
+            pair_type: state_pair.pair_type.clone(),
+            rest_bg_class: rest_pair.bg_class.clone(),
+            state_bg_class: state_pair.bg_class.clone(),
+            rest_text_class: rest_pair.text_class.clone(),
+            state_text_class: state_pair.text_class.clone(),
+            bg_delta_e: round2(bg_delta),
+            text_delta_e: round2(fg_delta),
+            luminance_delta: (luminance_delta * 10000.0).round() / 10000.0,
+        });
+    }
+    flagged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(bg_class: &str, bg_hex: &str, text_hex: &str, state: Option<&str>) -> ColorPair {
+        ColorPair {
+            bg_class: bg_class.to_string(),
+            bg_hex: Some(bg_hex.to_string()),
+            interactive_state: state.map(str::to_string),
+            ..ColorPair::fixture("text-gray-900", text_hex)
+        }
+    }
+
+    #[test]
+    fn delta_e_basics() {
+        assert_eq!(delta_e("#ffffff", "#ffffff"), 0.0);
+        assert!((delta_e("#000000", "#ffffff") - 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn subtle_hover_flagged() {
+        // gray-100 → gray-50: ΔE ~2
+        let pairs = [
+            pair("bg-gray-100", "#f3f4f6", "#111827", None),
+            pair("hover:bg-gray-50", "#f9fafb", "#111827", Some("hover")),
+        ];
+        let flagged = imperceptible_state_changes(&pairs, "#ffffff", DEFAULT_MIN_DELTA_E);
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(flagged[0].interactive_state, "hover");
+        assert_eq!(flagged[0].rest_bg_class, "bg-gray-100");
+        assert!(flagged[0].bg_delta_e < 3.5);
+        assert_eq!(flagged[0].text_delta_e, 0.0);
+    }
+
+    #[test]
+    fn visible_hover_passes() {
+        let pairs = [
+            pair("bg-gray-100", "#f3f4f6", "#111827", None),
+            pair("hover:bg-gray-300", "#d1d5db", "#111827", Some("hover")),
+        ];
+        assert!(imperceptible_state_changes(&pairs, "#ffffff", DEFAULT_MIN_DELTA_E).is_empty());
+    }
+
+    #[test]
+    fn fg_change_alone_is_enough() {
+        let mut hover = pair("bg-white", "#ffffff", "#2563eb", Some("hover"));
+        hover.text_class = "hover:text-blue-600".to_string();
+        let pairs = [pair("bg-white", "#ffffff", "#111827", None), hover];
+        assert!(imperceptible_state_changes(&pairs, "#ffffff", DEFAULT_MIN_DELTA_E).is_empty());
+    }
+
+    #[test]
+    fn inherited_state_pairs_skipped() {
+        // hover only changes the border: the text pair just inherits rest classes
+        let pairs = [
+            pair("bg-white", "#ffffff", "#111827", None),
+            pair("bg-white", "#ffffff", "#111827", Some("hover")),
+        ];
+        assert!(imperceptible_state_changes(&pairs, "#ffffff", DEFAULT_MIN_DELTA_E).is_empty());
+    }
+
+    #[test]
+    fn other_states_and_unmatched_pairs_ignored() {
+        let mut other_line = pair("hover:bg-gray-50", "#f9fafb", "#111827", Some("hover"));
+        other_line.line = 9;
+        let pairs = [
+            pair("bg-gray-100", "#f3f4f6", "#111827", None),
+            pair("bg-gray-100", "#f3f4f6", "#111827", Some("aria-disabled")),
+            other_line,
+        ];
+        assert!(imperceptible_state_changes(&pairs, "#ffffff", DEFAULT_MIN_DELTA_E).is_empty());
+    }
+}
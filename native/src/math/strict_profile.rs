@@ -0,0 +1,90 @@
+//! Stricter profile for headings, labels and form control text.
+//!
+//! Text of the listed elements is evaluated at AAA even when the run's
+//! threshold is AA. Elements are matched by tag (`h2`, `Label`) or by
+//! `role` (`heading`), so design-system components can be listed by name.
+
+use crate::types::{CheckOptions, ColorPair};
+
+/// Headings, labels and form control text (native tags, common component
+/// names and ARIA roles).
+pub const DEFAULT_STRICT_ELEMENTS: &[&str] = &[
+    "h1", "h2", "h3", "h4", "h5", "h6", "label", "legend", "input", "select", "textarea", "option",
+    "Label", "FormLabel", "Input", "Select", "SelectTrigger", "Textarea", "heading", "textbox",
+    "combobox", "listbox", "searchbox", "spinbutton",
+];
+
+/// Elements whose text is checked at AAA.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrictProfile {
+    elements: Vec<String>,
+}
+
+impl StrictProfile {
+    /// The profile requested by `options`: `strict_elements` if given, else
+    /// the default list when `strict_profile` is set.
+    pub fn from_options(options: &CheckOptions) -> Option<Self> {
+        match &options.strict_elements {
+            Some(elements) => Some(Self { elements: elements.clone() }),
+            None if options.strict_profile == Some(true) => Some(Self {
+                elements: DEFAULT_STRICT_ELEMENTS.iter().map(|e| e.to_string()).collect(),
+            }),
+            None => None,
+        }
+    }
+
+    /// Whether `pair` is text of a listed element (by tag or role).
+    pub fn applies(&self, pair: &ColorPair) -> bool {
+        let is_text = pair.pair_type.as_deref().is_none_or(|t| t == "text");
+        is_text
+            && [pair.tag_name.as_deref(), pair.role.as_deref()]
+                .into_iter()
+                .flatten()
+                .any(|name| self.elements.iter().any(|e| e == name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(tag: Option<&str>, role: Option<&str>, pair_type: Option<&str>) -> ColorPair {
+        ColorPair {
+            tag_name: tag.map(str::to_string),
+            role: role.map(str::to_string),
+            pair_type: pair_type.map(str::to_string),
+            ..ColorPair::fixture("text-gray-900", "#111827")
+        }
+    }
+
+    #[test]
+    fn off_unless_requested() {
+        assert_eq!(StrictProfile::from_options(&CheckOptions::default()), None);
+    }
+
+    #[test]
+    fn default_list_matches_headings_labels_and_roles() {
+        let profile = StrictProfile::from_options(&CheckOptions {
+            strict_profile: Some(true),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(profile.applies(&pair(Some("h2"), None, None)));
+        assert!(profile.applies(&pair(Some("Label"), None, Some("text"))));
+        assert!(profile.applies(&pair(Some("div"), Some("heading"), None)));
+        assert!(!profile.applies(&pair(Some("p"), None, None)));
+        assert!(!profile.applies(&pair(Some("input"), None, Some("border"))));
+        assert!(!profile.applies(&pair(None, None, None)));
+    }
+
+    #[test]
+    fn custom_list_replaces_default() {
+        let profile = StrictProfile::from_options(&CheckOptions {
+            strict_elements: Some(vec!["CardTitle".to_string()]),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(profile.applies(&pair(Some("CardTitle"), None, None)));
+        assert!(!profile.applies(&pair(Some("h1"), None, None)));
+    }
+}
@@ -0,0 +1,101 @@
+//! Near-invisible text (`text-white/20`, `opacity-20` ancestors).
+//!
+//! Translucent text composited over a dark card can still clear 4.5:1, and
+//! the cumulative `opacity` of an element isn't composited into its ratio at
+//! all, yet text rendered at a fifth of its opacity is a design escape hatch
+//! rather than readable copy. Text whose effective alpha (color alpha ×
+//! cumulative opacity) is below `min_alpha` is reported under
+//! `LOW-ALPHA-TEXT` whatever its ratio.
+
+use super::checker::is_suppressed;
+use crate::criteria::LOW_ALPHA_TEXT;
+use crate::types::{CheckResultJs, ContrastResult};
+
+/// Default effective alpha below which text is flagged.
+pub const DEFAULT_MIN_TEXT_ALPHA: f64 = 0.3;
+
+/// Alpha the text of `result` renders at: its color alpha times the
+/// cumulative opacity of its element.
+pub fn effective_alpha(result: &ContrastResult) -> f64 {
+    result.text_alpha.unwrap_or(1.0) * result.effective_opacity.unwrap_or(1.0)
+}
+
+/// Move passing text results below `min_alpha` to the violations, or to the
+/// ignored results when an `a11y-ignore` covers `LOW-ALPHA-TEXT`. Failing
+/// results already are violations of their contrast rule.
+pub fn flag_low_alpha(result: &mut CheckResultJs, min_alpha: f64) {
+    let (low, passed): (Vec<ContrastResult>, Vec<ContrastResult>) =
+        std::mem::take(&mut result.passed).into_iter().partition(|r| {
+            r.pair_type.as_deref().is_none_or(|t| t == "text") && effective_alpha(r) < min_alpha
+        });
+    result.passed = passed;
+    log::debug!("{} passing results below text alpha {}", low.len(), min_alpha);
+    for mut low in low {
+        low.rule_id = Some(LOW_ALPHA_TEXT.to_string());
+        low.wcag_criteria = Some(vec!["1.4.3".to_string()]);
+        low.wcag_techniques = Some(vec!["G18".to_string()]);
+        if is_suppressed(&low, LOW_ALPHA_TEXT) {
+            result.ignored_count += 1;
+            result.ignored.push(low);
+        } else {
+            result.violations.push(low);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::checker::check_all_pairs_with_options;
+    use crate::types::{CheckOptions, ColorPair};
+
+    fn pair(text_class: &str, text_alpha: Option<f64>, effective_opacity: Option<f64>) -> ColorPair {
+        ColorPair {
+            bg_class: "bg-zinc-950".to_string(),
+            bg_hex: Some("#09090b".to_string()),
+            text_alpha,
+            effective_opacity,
+            ..ColorPair::fixture(text_class, "#ffffff")
+        }
+    }
+
+    #[test]
+    fn passing_low_alpha_text_is_flagged() {
+        let pairs = vec![
+            pair("text-white/20", Some(0.2), None),
+            pair("text-white", None, Some(0.25)),
+            pair("text-white/60", Some(0.6), None),
+        ];
+        let options = CheckOptions { check_text_alpha: Some(true), ..Default::default() };
+        let result = check_all_pairs_with_options(&pairs, &options);
+        // Translucent white already fails its contrast rule; opacity isn't composited
+        let flagged: Vec<(&str, Option<&str>)> =
+            result.violations.iter().map(|r| (r.text_class.as_str(), r.rule_id.as_deref())).collect();
+        assert_eq!(flagged, [("text-white/20", Some("AA-TEXT-CONTRAST")), ("text-white", Some("LOW-ALPHA-TEXT"))]);
+        assert_eq!(result.passed.len(), 1);
+        assert_eq!(result.violations[1].wcag_criteria.as_deref().unwrap_or_default(), ["1.4.3"]);
+        assert_eq!(
+            result.violations[1].remediation.as_deref(),
+            Some("raise the opacity of the element or its ancestors (the text renders at 25%)")
+        );
+
+        let off = check_all_pairs_with_options(&pairs, &CheckOptions::default());
+        assert_eq!(off.violations.len(), 1);
+        let strict = CheckOptions { check_text_alpha: Some(true), min_text_alpha: Some(0.7), ..Default::default() };
+        let strict = check_all_pairs_with_options(&pairs, &strict);
+        assert_eq!(strict.violations.len(), 3);
+        let fix = strict.violations[2].remediation.as_deref().unwrap();
+        assert!(fix.starts_with("replace `text-white/60` with `text-white` (ratio 19."), "{}", fix);
+    }
+
+    #[test]
+    fn scoped_ignore_covers_low_alpha() {
+        let mut faded = pair("text-white", None, Some(0.2));
+        faded.ignored = Some(true);
+        faded.ignore_rules = Some(vec![LOW_ALPHA_TEXT.to_string()]);
+        let options = CheckOptions { check_text_alpha: Some(true), ..Default::default() };
+        let result = check_all_pairs_with_options(&[faded], &options);
+        assert!(result.violations.is_empty());
+        assert_eq!((result.ignored.len(), result.ignored_count), (1, 1));
+    }
+}
@@ -0,0 +1,125 @@
+//! EXPERIMENTAL: WCAG 3 (Working Draft) outcome scoring for visual contrast.
+//!
+//! WCAG 3 is a draft and its contrast method and rating scale are still
+//! changing; this module tracks our reading of the draft (APCA Lc minimums
+//! per use case, 0-4 outcome rating from the share of passing pairs) so we
+//! can watch the trend. It is enabled only via `CheckOptions.experimental`
+//! and its output must never gate CI.
+
+use crate::types::{CheckResultJs, ContrastResult, Wcag3Score};
+
+/// `CheckOptions.experimental` flag enabling this module.
+pub const FLAG: &str = "wcag3";
+
+/// Draft revision the scoring follows.
+pub const DRAFT: &str = "WCAG 3.0 Working Draft (APCA visual contrast outcome)";
+
+/// Minimum |Lc| for body text, large text and non-text elements.
+const MIN_LC_BODY: f64 = 75.0;
+const MIN_LC_LARGE: f64 = 60.0;
+const MIN_LC_NON_TEXT: f64 = 45.0;
+
+/// Rating thresholds: (minimum % of passing pairs, rating), best first.
+const RATING_BANDS: &[(f64, u32)] = &[(95.0, 4), (90.0, 3), (85.0, 2), (80.0, 1)];
+
+/// Whether a result meets the draft's Lc minimum for its use case.
+pub fn passes(result: &ContrastResult) -> Option<bool> {
+    let lc = result.apca_lc?.abs();
+    let is_non_text = result.pair_type.as_deref().is_some_and(|t| t != "text");
+    let min = if is_non_text {
+        MIN_LC_NON_TEXT
+    } else if result.is_large_text == Some(true) {
+        MIN_LC_LARGE
+    } else {
+        MIN_LC_BODY
+    };
+    Some(lc >= min)
+}
+
+/// 0-4 outcome rating for a pass percentage.
+pub fn rating(percent: f64) -> u32 {
+    RATING_BANDS
+        .iter()
+        .find(|(min, _)| percent >= *min)
+        .map_or(0, |(_, r)| *r)
+}
+
+/// Tag every result with `wcag3_pass` and score the checked (non-ignored) pairs.
+pub fn score(result: &mut CheckResultJs) -> Wcag3Score {
+    let mut passing = 0u32;
+    let mut total = 0u32;
+    for r in result.violations.iter_mut().chain(result.passed.iter_mut()) {
+        r.wcag3_pass = passes(r);
+        if let Some(pass) = r.wcag3_pass {
+            total += 1;
+            passing += pass as u32;
+        }
+    }
+    for r in result.ignored.iter_mut() {
+        r.wcag3_pass = passes(r);
+    }
+    let percent = if total == 0 {
+        100.0
+    } else {
+        (passing as f64 / total as f64 * 1000.0).round() / 10.0
+    };
+    Wcag3Score {
+        status: "experimental".to_string(),
+        draft: DRAFT.to_string(),
+        rating: rating(percent),
+        passing,
+        total,
+        percent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::checker::check_all_pairs;
+    use crate::types::ColorPair;
+
+    fn pair(text_hex: &str, large: bool, pair_type: Option<&str>) -> ColorPair {
+        ColorPair {
+            is_large_text: Some(large),
+            pair_type: pair_type.map(str::to_string),
+            ..ColorPair::fixture("", text_hex)
+        }
+    }
+
+    #[test]
+    fn minimum_depends_on_use_case() {
+        // #767676 on white: WCAG 2 AA pass (4.54), APCA Lc ~71
+        let r = check_all_pairs(
+            &[pair("#767676", false, None), pair("#767676", true, None), pair("#767676", false, Some("border"))],
+            "AA",
+            "#ffffff",
+        );
+        let all: Vec<_> = r.violations.iter().chain(&r.passed).map(passes).collect();
+        assert_eq!(all, vec![Some(false), Some(true), Some(true)]);
+    }
+
+    #[test]
+    fn rating_bands() {
+        assert_eq!(rating(100.0), 4);
+        assert_eq!(rating(92.0), 3);
+        assert_eq!(rating(85.0), 2);
+        assert_eq!(rating(80.0), 1);
+        assert_eq!(rating(50.0), 0);
+    }
+
+    #[test]
+    fn score_counts_checked_pairs_only() {
+        let mut ignored = pair("#eeeeee", false, None);
+        ignored.ignored = Some(true);
+        let mut js: CheckResultJs =
+            check_all_pairs(&[pair("#000000", false, None), pair("#999999", false, None), ignored], "AA", "#ffffff")
+                .into();
+        let score = score(&mut js);
+        assert_eq!((score.passing, score.total), (1, 2));
+        assert_eq!(score.percent, 50.0);
+        assert_eq!(score.rating, 0);
+        assert_eq!(score.status, "experimental");
+        assert_eq!(js.ignored[0].wcag3_pass, Some(false));
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// Snapshot of a single `extract_and_scan()` run, exported as OpenMetrics text.
+///
+/// All values describe the LAST run only (gauges, not cumulative counters) so
+/// CI observability can scrape the file after each audit without keeping state.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunMetrics {
+    pub files_scanned: u64,
+    pub bytes_scanned: u64,
+    pub regions_extracted: u64,
+    /// Regions pre-marked as ignored (a11y-ignore, disabled, invisible)
+    pub regions_ignored: u64,
+    /// Wall-clock duration of the whole run
+    pub total_duration_secs: f64,
+    /// Sum of per-file parse durations across all rayon workers (CPU time)
+    pub parse_duration_secs: f64,
+    /// Slowest single file parse
+    pub max_file_duration_secs: f64,
+}
+
+/// One exported metric: (name, unit, help, value).
+type MetricLine<'a> = (&'a str, Option<&'a str>, &'a str, f64);
+
+impl RunMetrics {
+    fn lines(&self) -> Vec<MetricLine<'static>> {
+        vec![
+            ("a11y_audit_files_scanned", None, "Files parsed in the last run.", self.files_scanned as f64),
+            ("a11y_audit_bytes_scanned", Some("bytes"), "Source bytes parsed in the last run.", self.bytes_scanned as f64),
+            ("a11y_audit_regions_extracted", None, "Class regions extracted in the last run.", self.regions_extracted as f64),
+            ("a11y_audit_regions_ignored", None, "Class regions pre-marked as ignored in the last run.", self.regions_ignored as f64),
+            ("a11y_audit_run_duration_seconds", Some("seconds"), "Wall-clock duration of the last run.", self.total_duration_secs),
+            ("a11y_audit_parse_duration_seconds", Some("seconds"), "Summed per-file parse time of the last run.", self.parse_duration_secs),
+            ("a11y_audit_max_file_duration_seconds", Some("seconds"), "Slowest single-file parse of the last run.", self.max_file_duration_secs),
+        ]
+    }
+}
+
+/// Render metrics in the OpenMetrics text exposition format (also accepted by
+/// Prometheus' text parser). Ends with the mandatory `# EOF` marker.
+pub fn render_openmetrics(metrics: &RunMetrics) -> String {
+    let mut out = String::new();
+    for (name, unit, help, value) in metrics.lines() {
+        let _ = writeln!(out, "# TYPE {} gauge", name);
+        if let Some(unit) = unit {
+            let _ = writeln!(out, "# UNIT {} {}", name, unit);
+        }
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "{} {}", name, format_value(value));
+    }
+    out.push_str("# EOF\n");
+    out
+}
+
+/// Write the OpenMetrics snapshot to `path`, replacing any previous snapshot.
+pub fn write_metrics(path: &Path, metrics: &RunMetrics) -> std::io::Result<()> {
+    std::fs::write(path, render_openmetrics(metrics))
+}
+
+/// Integers print without a fractional part; durations keep microsecond precision.
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:.6}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> RunMetrics {
+        RunMetrics {
+            files_scanned: 3,
+            bytes_scanned: 1024,
+            regions_extracted: 17,
+            regions_ignored: 2,
+            total_duration_secs: 0.0125,
+            parse_duration_secs: 0.03,
+            max_file_duration_secs: 0.01,
+        }
+    }
+
+    #[test]
+    fn renders_type_help_and_value() {
+        let text = render_openmetrics(&sample());
+        assert!(text.contains("# TYPE a11y_audit_files_scanned gauge\n"));
+        assert!(text.contains("# HELP a11y_audit_files_scanned "));
+        assert!(text.contains("\na11y_audit_files_scanned 3\n"));
+        assert!(text.contains("\na11y_audit_regions_extracted 17\n"));
+    }
+
+    #[test]
+    fn durations_carry_unit_and_fraction() {
+        let text = render_openmetrics(&sample());
+        assert!(text.contains("# UNIT a11y_audit_run_duration_seconds seconds\n"));
+        assert!(text.contains("\na11y_audit_run_duration_seconds 0.012500\n"));
+    }
+
+    #[test]
+    fn ends_with_eof_marker() {
+        let text = render_openmetrics(&RunMetrics::default());
+        assert!(text.ends_with("# EOF\n"));
+        assert_eq!(text.matches("# EOF").count(), 1);
+    }
+
+    #[test]
+    fn write_metrics_creates_file() {
+        let path = std::env::temp_dir().join(format!("a11y-metrics-{}.prom", std::process::id()));
+        write_metrics(&path, &sample()).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(written, render_openmetrics(&sample()));
+    }
+}
@@ -0,0 +1,709 @@
+/// Non-color `text-*` utilities
+const TEXT_NON_COLOR: &[&str] = &[
+    "text-xs", "text-sm", "text-base", "text-lg", "text-xl", "text-2xl", "text-3xl", "text-4xl",
+    "text-5xl", "text-6xl", "text-7xl", "text-8xl", "text-9xl", "text-left", "text-center",
+    "text-right", "text-justify", "text-start", "text-end", "text-wrap", "text-nowrap",
+    "text-balance", "text-pretty", "text-clip", "text-ellipsis", "text-truncate",
+    "text-underline", "text-overline", "text-line-through", "text-no-underline",
+    "text-uppercase", "text-lowercase", "text-capitalize", "text-normal-case",
+];
+
+/// Non-color `bg-*` utilities
+pub const BG_NON_COLOR: &[&str] = &[
+    "bg-clip-text", "bg-no-repeat", "bg-cover", "bg-contain", "bg-fixed", "bg-local", "bg-scroll",
+];
+
+/// Non-color utilities for SC 1.4.11 non-text contrast
+const BORDER_NON_COLOR: &[&str] = &[
+    "border", "border-0", "border-2", "border-4", "border-8", "border-x", "border-y", "border-t",
+    "border-b", "border-l", "border-r", "border-solid", "border-dashed", "border-dotted",
+    "border-double", "border-none", "border-hidden", "border-collapse", "border-separate",
+    "border-spacing-0", "border-spacing-px", "border-spacing-1", "border-spacing-2",
+    // directional widths (not colors)
+    "border-t-0", "border-t-2", "border-t-4", "border-t-8", "border-b-0", "border-b-2",
+    "border-b-4", "border-b-8", "border-l-0", "border-l-2", "border-l-4", "border-l-8",
+    "border-r-0", "border-r-2", "border-r-4", "border-r-8", "border-x-0", "border-x-2",
+    "border-x-4", "border-x-8", "border-y-0", "border-y-2", "border-y-4", "border-y-8",
+];
+
+const RING_NON_COLOR: &[&str] = &[
+    "ring-0", "ring-1", "ring-2", "ring-4", "ring-8", "ring-inset", "ring-offset-0",
+    "ring-offset-1", "ring-offset-2", "ring-offset-4", "ring-offset-8",
+];
+
+const OUTLINE_NON_COLOR: &[&str] = &[
+    "outline-none", "outline-hidden", "outline-0", "outline-1", "outline-2", "outline-4",
+    "outline-8", "outline-dashed", "outline-dotted", "outline-double", "outline-offset-0",
+    "outline-offset-1", "outline-offset-2", "outline-offset-4", "outline-offset-8",
+];
+
+/// Known Tailwind variant prefixes to strip
+const VARIANT_PREFIXES: &[&str] = &[
+    "dark:", "hover:", "focus:", "focus-visible:", "focus-within:", "active:", "visited:",
+    "disabled:", "group-hover:", "peer-hover:", "sm:", "md:", "lg:", "xl:", "2xl:", "first:",
+    "last:", "odd:", "even:", "placeholder:", "aria-selected:", "aria-disabled:", "aria-checked:",
+    "aria-expanded:", "aria-pressed:",
+];
+
+/// Responsive breakpoints, narrowest first (`md:` applies from md up)
+pub const BREAKPOINTS: &[&str] = &["sm", "md", "lg", "xl", "2xl"];
+
+/// Interactive states rendered as twin regions of their own (see `color_states`)
+pub const STATE_VARIANTS: &[&str] = &["hover", "focus", "focus-visible", "active"];
+
+/// `aria-*` state variants rendered as twin regions, like `data-[...]` /
+/// `aria-[...]` attribute variants (see `is_state_variant`)
+const ARIA_STATE_VARIANTS: &[&str] = &["aria-selected", "aria-checked", "aria-expanded", "aria-pressed"];
+
+/// Position of `breakpoint` in `BREAKPOINTS`, 1-based (0 = no breakpoint).
+fn breakpoint_rank(breakpoint: Option<&str>) -> usize {
+    breakpoint.and_then(|bp| BREAKPOINTS.iter().position(|b| *b == bp)).map_or(0, |i| i + 1)
+}
+
+/// ≥24px (18pt) any weight → always large
+const ALWAYS_LARGE: &[&str] = &[
+    "text-2xl", "text-3xl", "text-4xl", "text-5xl", "text-6xl", "text-7xl", "text-8xl", "text-9xl",
+];
+/// 20px (≥18.67px threshold) → large only if bold
+const LARGE_IF_BOLD: &[&str] = &["text-xl"];
+/// font-weight ≥700
+const BOLD_CLASSES: &[&str] = &["font-bold", "font-extrabold", "font-black"];
+
+/// Maps variant prefixes to their tracked interactive state name
+fn interactive_state_for(prefix: &str) -> Option<&'static str> {
+    match prefix {
+        "hover:" => Some("hover"),
+        "focus-visible:" => Some("focus-visible"),
+        "aria-disabled:" => Some("aria-disabled"),
+        _ => None,
+    }
+}
+
+/// A class extracted from source with its variant flags.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaggedClass {
+    pub raw: String,
+    /// true if dark: prefix was present
+    pub is_dark: bool,
+    /// true if any interactive/conditional prefix was present (hover:, focus:, sm:, etc.)
+    pub is_interactive: bool,
+    /// Which tracked interactive state, if any (hover, focus-visible)
+    pub interactive_state: Option<&'static str>,
+    /// Widest responsive breakpoint prefix, if any (sm:, md:, ...)
+    pub breakpoint: Option<&'static str>,
+    pub base: String,
+}
+
+impl TaggedClass {
+    /// A synthetic class (context bg, inline style, annotation) with no variants.
+    pub fn synthetic(raw: impl Into<String>, base: impl Into<String>) -> Self {
+        Self {
+            raw: raw.into(),
+            is_dark: false,
+            is_interactive: false,
+            interactive_state: None,
+            breakpoint: None,
+            base: base.into(),
+        }
+    }
+}
+
+/// bg/text/border/ring/outline class buckets.
+#[derive(Debug, Clone, Default)]
+pub struct ClassBuckets {
+    pub bg_classes: Vec<TaggedClass>,
+    pub text_classes: Vec<TaggedClass>,
+    pub border_classes: Vec<TaggedClass>,
+    pub ring_classes: Vec<TaggedClass>,
+    pub outline_classes: Vec<TaggedClass>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CategorizedClasses {
+    pub buckets: ClassBuckets,
+    pub dynamic_classes: Vec<String>,
+    /// Tailwind font-size class found in this region (e.g. "text-2xl")
+    pub font_size: Option<String>,
+    /// true if font-bold/font-extrabold/font-black present
+    pub is_bold: bool,
+    /// Per interactive state (hover, focus-visible) class overrides, in first-seen order
+    pub interactive_states: Vec<(&'static str, ClassBuckets)>,
+}
+
+/// Strips known variant prefixes. Tags with dark variant if `dark:` is present.
+/// Multiple prefixes are stripped (e.g., `sm:dark:bg-red-500` → `bg-red-500`).
+///
+/// Port of: src/plugins/jsx/categorizer.ts → stripVariants()
+pub fn strip_variants(cls: &str) -> TaggedClass {
+    let mut base = cls;
+    let mut is_dark = false;
+    let mut is_interactive = false;
+    let mut interactive_state = None;
+    let mut breakpoint = None;
+
+    while let Some(prefix) =
+        VARIANT_PREFIXES.iter().copied().find(|p| base.starts_with(p)).or_else(|| attribute_variant(base))
+    {
+        let variant = &prefix[..prefix.len() - 1];
+        if prefix == "dark:" {
+            is_dark = true;
+        } else {
+            if let Some(bp) = BREAKPOINTS.iter().find(|bp| **bp == variant) {
+                if breakpoint_rank(Some(bp)) > breakpoint_rank(breakpoint) {
+                    breakpoint = Some(*bp);
+                }
+            }
+            is_interactive = true;
+            if let Some(state) = interactive_state_for(prefix) {
+                interactive_state = Some(state);
+            }
+        }
+        base = &base[prefix.len()..];
+    }
+
+    TaggedClass {
+        raw: cls.to_string(),
+        is_dark,
+        is_interactive,
+        interactive_state,
+        breakpoint,
+        base: base.to_string(),
+    }
+}
+
+/// Leading `data-[...]:` / `aria-[...]:` attribute variant of `cls`, e.g.
+/// "data-[state=open]:" (Radix / shadcn state styling).
+fn attribute_variant(cls: &str) -> Option<&str> {
+    if !cls.starts_with("data-[") && !cls.starts_with("aria-[") {
+        return None;
+    }
+    cls.find("]:").map(|end| &cls[..end + 2])
+}
+
+/// Whether `variant` renders the element in a state of its own, checked by
+/// state twin regions: one of `STATE_VARIANTS`, an `aria-*` state
+/// (`aria-selected`) or an attribute variant (`data-[state=open]`).
+pub fn is_state_variant(variant: &str) -> bool {
+    STATE_VARIANTS.contains(&variant)
+        || ARIA_STATE_VARIANTS.contains(&variant)
+        || ((variant.starts_with("data-[") || variant.starts_with("aria-[")) && variant.ends_with(']'))
+}
+
+fn is_non_color_bg(base: &str) -> bool {
+    base.starts_with("bg-linear-") || base.starts_with("bg-gradient-") || BG_NON_COLOR.contains(&base)
+}
+
+fn is_non_color_text(base: &str) -> bool {
+    // text-[<digit>...] is an arbitrary font size
+    TEXT_NON_COLOR.contains(&base)
+        || base
+            .strip_prefix("text-[")
+            .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Routes a tagged class to the correct bucket based on its prefix.
+/// Returns false if the class doesn't match any known color category.
+///
+/// Port of: src/plugins/jsx/categorizer.ts → routeClassToTarget()
+pub fn route_class_to_target(tagged: TaggedClass, target: &mut ClassBuckets) -> bool {
+    let base = tagged.base.as_str();
+
+    if base.starts_with("bg-") {
+        if is_non_color_bg(base) {
+            return false;
+        }
+        target.bg_classes.push(tagged);
+        return true;
+    }
+
+    if base.starts_with("text-") {
+        if is_non_color_text(base) {
+            return false;
+        }
+        target.text_classes.push(tagged);
+        return true;
+    }
+
+    if base.starts_with("border-") || base.starts_with("divide-") {
+        if BORDER_NON_COLOR.contains(&base) {
+            return false;
+        }
+        target.border_classes.push(tagged);
+        return true;
+    }
+
+    if base.starts_with("ring-") {
+        if RING_NON_COLOR.contains(&base) || base.starts_with("ring-offset-") {
+            return false;
+        }
+        target.ring_classes.push(tagged);
+        return true;
+    }
+
+    if base.starts_with("outline-") {
+        if OUTLINE_NON_COLOR.contains(&base) {
+            return false;
+        }
+        target.outline_classes.push(tagged);
+        return true;
+    }
+
+    false
+}
+
+/// Categorizes classes by type (bg/text/...) and filters by theme mode.
+/// Light mode: skip `dark:`-prefixed classes.
+/// Dark mode: include `dark:`-prefixed and base classes; dark bg/text replace base ones.
+/// Tracked interactive variants (hover, focus-visible) go to per-state buckets;
+/// untracked ones (sm:, active:, ...) are skipped.
+///
+/// Port of: src/plugins/jsx/categorizer.ts → categorizeClasses()
+pub fn categorize_classes(classes: &[String], theme_mode: &str) -> CategorizedClasses {
+    categorize_classes_at(classes, theme_mode, None)
+}
+
+/// Like `categorize_classes`, as rendered at `breakpoint` and up: classes
+/// for that breakpoint or narrower ones apply, and in each bucket the widest
+/// breakpoint's classes replace the others (`md:bg-white` over `bg-black`).
+/// None = no breakpoint (breakpoint-prefixed classes are skipped).
+pub fn categorize_classes_at(classes: &[String], theme_mode: &str, breakpoint: Option<&str>) -> CategorizedClasses {
+    categorize_classes_in(classes, theme_mode, breakpoint, None)
+}
+
+/// Like `categorize_classes_at`, as rendered in interactive `state` (one of
+/// `STATE_VARIANTS`): its classes apply like base ones and replace the others
+/// in their bucket (`hover:bg-gray-100` over `bg-white`).
+pub fn categorize_classes_in(
+    classes: &[String],
+    theme_mode: &str,
+    breakpoint: Option<&str>,
+    state: Option<&str>,
+) -> CategorizedClasses {
+    let mut out = CategorizedClasses::default();
+    let max_rank = breakpoint_rank(breakpoint);
+    let is_dark_mode = theme_mode == "dark";
+
+    // Temp buckets for dark-mode override logic (bg/text only)
+    let mut dark_bg: Vec<TaggedClass> = Vec::new();
+    let mut dark_text: Vec<TaggedClass> = Vec::new();
+
+    for cls in classes {
+        if cls.is_empty() {
+            continue;
+        }
+
+        if cls.contains('$') {
+            out.dynamic_classes.push(cls.clone());
+            continue;
+        }
+
+        let tagged = strip_variants(cls);
+
+        // Capture font size/weight BEFORE any filtering
+        let base = tagged.base.as_str();
+        if ALWAYS_LARGE.contains(&base) || LARGE_IF_BOLD.contains(&base) {
+            out.font_size = Some(base.to_string());
+        }
+        if BOLD_CLASSES.contains(&base) {
+            out.is_bold = true;
+        }
+
+        // At a breakpoint, narrower breakpoint prefixes don't make a class
+        // conditional; neither does the state being rendered
+        if breakpoint.is_some() && breakpoint_rank(tagged.breakpoint) > max_rank {
+            continue;
+        }
+        let applies = |v: &str| (breakpoint.is_some() && BREAKPOINTS.contains(&v)) || Some(v) == state;
+        let tagged = if variants_of(cls).any(applies) {
+            TaggedClass {
+                raw: cls.clone(),
+                breakpoint: tagged.breakpoint,
+                ..strip_variants(&without_variants(cls, applies))
+            }
+        } else {
+            tagged
+        };
+        if tagged.is_interactive {
+            if let Some(state) = tagged.interactive_state {
+                let idx = match out.interactive_states.iter().position(|(s, _)| *s == state) {
+                    Some(idx) => idx,
+                    None => {
+                        out.interactive_states.push((state, ClassBuckets::default()));
+                        out.interactive_states.len() - 1
+                    }
+                };
+                route_class_to_target(tagged, &mut out.interactive_states[idx].1);
+            }
+            continue;
+        }
+
+        if tagged.is_dark && !is_dark_mode {
+            continue;
+        }
+
+        if is_dark_mode && tagged.base.starts_with("bg-") {
+            if !is_non_color_bg(&tagged.base) {
+                dark_bg.push(tagged);
+            }
+            continue;
+        }
+
+        if is_dark_mode && tagged.base.starts_with("text-") {
+            if !is_non_color_text(&tagged.base) {
+                dark_text.push(tagged);
+            }
+            continue;
+        }
+
+        route_class_to_target(tagged, &mut out.buckets);
+    }
+
+    // Dark mode override semantics — dark: variants replace base classes
+    if is_dark_mode {
+        let has_dark_bg = dark_bg.iter().any(|t| t.is_dark);
+        let has_dark_text = dark_text.iter().any(|t| t.is_dark);
+        out.buckets
+            .bg_classes
+            .extend(dark_bg.into_iter().filter(|t| !has_dark_bg || t.is_dark));
+        out.buckets
+            .text_classes
+            .extend(dark_text.into_iter().filter(|t| !has_dark_text || t.is_dark));
+    }
+
+    // State, then breakpoint cascade — the state's classes replace the
+    // others, the widest breakpoint present replaces narrower ones
+    if breakpoint.is_some() || state.is_some() {
+        let in_state = |t: &TaggedClass| state.is_some_and(|s| has_variant(&t.raw, s));
+        let buckets = std::iter::once(&mut out.buckets).chain(out.interactive_states.iter_mut().map(|(_, b)| b));
+        for bucket in buckets {
+            for classes in [
+                &mut bucket.bg_classes,
+                &mut bucket.text_classes,
+                &mut bucket.border_classes,
+                &mut bucket.ring_classes,
+                &mut bucket.outline_classes,
+            ] {
+                if classes.iter().any(in_state) {
+                    classes.retain(in_state);
+                }
+                let widest = classes.iter().map(|t| breakpoint_rank(t.breakpoint)).max().unwrap_or(0);
+                classes.retain(|t| breakpoint_rank(t.breakpoint) == widest);
+            }
+        }
+    }
+
+    out
+}
+
+/// Variants of `cls` (`md:hover:bg-x` → "md", "hover").
+fn variants_of(cls: &str) -> impl Iterator<Item = &str> {
+    cls.rsplit_once(':').map_or("", |(variants, _)| variants).split(':').filter(|v| !v.is_empty())
+}
+
+/// Whether `cls` carries `variant` (`md:hover:bg-x` carries "hover").
+pub fn has_variant(cls: &str, variant: &str) -> bool {
+    variants_of(cls).any(|v| v == variant)
+}
+
+/// `cls` without the variants `drop` matches (`md:hover:bg-x` → `hover:bg-x`
+/// for breakpoints).
+fn without_variants(cls: &str, drop: impl Fn(&str) -> bool) -> String {
+    let Some((variants, base)) = cls.rsplit_once(':') else {
+        return cls.to_string();
+    };
+    let mut out: String = variants.split(':').filter(|v| !drop(v)).map(|v| format!("{}:", v)).collect();
+    out.push_str(base);
+    out
+}
+
+/// Breakpoints with color classes among `classes` (`md:bg-white`), narrowest first.
+pub fn color_breakpoints(classes: &[String]) -> Vec<&'static str> {
+    let mut found: Vec<&'static str> = classes
+        .iter()
+        .map(|cls| strip_variants(cls))
+        .filter_map(|tagged| {
+            let breakpoint = tagged.breakpoint?;
+            route_class_to_target(tagged, &mut ClassBuckets::default()).then_some(breakpoint)
+        })
+        .collect();
+    found.sort_by_key(|bp| breakpoint_rank(Some(bp)));
+    found.dedup();
+    found
+}
+
+/// States with color classes among `classes` (`hover:bg-gray-100`,
+/// `data-[state=open]:bg-accent`, see `is_state_variant`): `STATE_VARIANTS`
+/// in order, then the others as they appear.
+pub fn color_states(classes: &[String]) -> Vec<String> {
+    let mut states: Vec<String> = Vec::new();
+    for cls in classes {
+        if !route_class_to_target(strip_variants(cls), &mut ClassBuckets::default()) {
+            continue;
+        }
+        for variant in variants_of(cls).filter(|v| is_state_variant(v)) {
+            if !states.iter().any(|s| s == variant) {
+                states.push(variant.to_string());
+            }
+        }
+    }
+    states.sort_by_key(|s| STATE_VARIANTS.iter().position(|v| v == s).unwrap_or(STATE_VARIANTS.len()));
+    states
+}
+
+/// Classes applied in another element's state, by tracked state:
+/// `group-hover:text-white` (`marker` "group") or
+/// `peer-focus-visible/email:border-red-500` (`marker` "peer"). Classes with
+/// other interactive variants, and `dark:` ones in light mode, are skipped.
+pub fn relational_state_classes(
+    classes: &[String],
+    marker: &str,
+    theme_mode: &str,
+) -> Vec<(&'static str, ClassBuckets)> {
+    let mut out: Vec<(&'static str, ClassBuckets)> = Vec::new();
+    for cls in classes {
+        let Some((variants, base)) = cls.rsplit_once(':') else {
+            continue;
+        };
+        let mut state = None;
+        let mut rest = String::new();
+        for variant in variants.split(':') {
+            let relational = variant
+                .strip_prefix(marker)
+                .and_then(|v| v.strip_prefix('-'))
+                .map(|v| v.split('/').next().unwrap_or(v));
+            match relational.and_then(|v| interactive_state_for(&format!("{}:", v))) {
+                Some(s) if state.is_none() => state = Some(s),
+                _ => {
+                    rest.push_str(variant);
+                    rest.push(':');
+                }
+            }
+        }
+        let Some(state) = state else {
+            continue;
+        };
+        let tagged = TaggedClass { raw: cls.clone(), ..strip_variants(&format!("{}{}", rest, base)) };
+        if tagged.is_interactive || (tagged.is_dark && theme_mode != "dark") {
+            continue;
+        }
+        let idx = match out.iter().position(|(s, _)| *s == state) {
+            Some(idx) => idx,
+            None => {
+                out.push((state, ClassBuckets::default()));
+                out.len() - 1
+            }
+        };
+        route_class_to_target(TaggedClass { interactive_state: Some(state), ..tagged }, &mut out[idx].1);
+    }
+    out
+}
+
+/// Whether text qualifies as "large" per WCAG SC 1.4.3: text-2xl+ at any
+/// weight, text-xl when bold; otherwise normal (conservative).
+///
+/// Port of: src/plugins/jsx/categorizer.ts → determineIsLargeText()
+pub fn determine_is_large_text(font_size: Option<&str>, is_bold: bool) -> bool {
+    match font_size {
+        Some(size) if ALWAYS_LARGE.contains(&size) => true,
+        Some(size) if LARGE_IF_BOLD.contains(&size) => is_bold,
+        _ => false,
+    }
+}
+
+/// Extracts all string literal contents from a cn()/clsx() body, split into
+/// classes. Template literal `${...}` holes are dropped.
+///
+/// Port of: src/plugins/jsx/categorizer.ts → extractStringLiterals()
+pub fn extract_string_literals(body: &str) -> Vec<String> {
+    let bytes = body.as_bytes();
+    let len = bytes.len();
+    let mut classes = Vec::new();
+    let mut i = 0;
+
+    while i < len {
+        let ch = bytes[i];
+        if ch == b'\'' || ch == b'"' || ch == b'`' {
+            let start = i + 1;
+            i += 1;
+            while i < len && bytes[i] != ch {
+                if bytes[i] == b'\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            if i < len {
+                let literal = &body[start..i];
+                let literal = if ch == b'`' {
+                    strip_template_holes(literal)
+                } else {
+                    literal.to_string()
+                };
+                classes.extend(literal.split_whitespace().map(str::to_string));
+            }
+        }
+        i += 1;
+    }
+
+    classes
+}
+
+/// `a ${x} b` → `a   b` (holes replaced by a space, like the TS regex `\$\{[^}]*\}`)
+fn strip_template_holes(literal: &str) -> String {
+    let mut out = String::with_capacity(literal.len());
+    let mut rest = literal;
+    while let Some(start) = rest.find("${") {
+        match rest[start..].find('}') {
+            Some(end) => {
+                out.push_str(&rest[..start]);
+                out.push(' ');
+                rest = &rest[start + end + 1..];
+            }
+            None => break,
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Split a region's content into classes: quoted content comes from
+/// cn()/clsx() bodies, otherwise it is a static className string.
+pub fn region_classes(content: &str) -> Vec<String> {
+    if content.contains(['\'', '"', '`']) {
+        extract_string_literals(content)
+    } else {
+        content.split_whitespace().map(str::to_string).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classes(s: &str) -> Vec<String> {
+        s.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn strip_multiple_variants() {
+        let t = strip_variants("sm:dark:hover:bg-red-500");
+        assert_eq!(t.base, "bg-red-500");
+        assert!(t.is_dark);
+        assert!(t.is_interactive);
+        assert_eq!(t.interactive_state, Some("hover"));
+    }
+
+    #[test]
+    fn routes_by_prefix_and_skips_non_color() {
+        let c = categorize_classes(&classes("bg-card text-sm text-[14px] text-white border border-red-500 ring-2 ring-blue-500 outline-none"), "light");
+        assert_eq!(c.buckets.bg_classes.len(), 1);
+        assert_eq!(c.buckets.text_classes.len(), 1);
+        assert_eq!(c.buckets.border_classes[0].base, "border-red-500");
+        assert_eq!(c.buckets.ring_classes[0].base, "ring-blue-500");
+        assert!(c.buckets.outline_classes.is_empty());
+    }
+
+    #[test]
+    fn light_mode_skips_dark_variants() {
+        let c = categorize_classes(&classes("bg-white dark:bg-black"), "light");
+        assert_eq!(c.buckets.bg_classes.len(), 1);
+        assert_eq!(c.buckets.bg_classes[0].base, "bg-white");
+    }
+
+    #[test]
+    fn dark_mode_dark_variant_replaces_base() {
+        let c = categorize_classes(&classes("bg-white dark:bg-black text-gray-900"), "dark");
+        assert_eq!(c.buckets.bg_classes.len(), 1);
+        assert_eq!(c.buckets.bg_classes[0].base, "bg-black");
+        assert_eq!(c.buckets.text_classes[0].base, "text-gray-900");
+    }
+
+    #[test]
+    fn interactive_states_bucketed() {
+        let c = categorize_classes(&classes("text-black hover:text-gray-500 focus-visible:ring-blue-500 sm:text-red-500"), "light");
+        assert_eq!(c.interactive_states.len(), 2);
+        assert_eq!(c.interactive_states[0].0, "hover");
+        assert_eq!(c.interactive_states[1].1.ring_classes.len(), 1);
+        assert_eq!(c.buckets.text_classes.len(), 1);
+    }
+
+    #[test]
+    fn breakpoint_cascade() {
+        let all = classes("bg-black text-white md:bg-white md:text-gray-400 lg:bg-red-500 md:hover:bg-gray-100 sm:p-4");
+        assert_eq!(color_breakpoints(&all), vec!["md", "lg"]);
+        let base = categorize_classes(&all, "light");
+        assert_eq!(base.buckets.bg_classes[0].raw, "bg-black");
+        assert_eq!(base.buckets.bg_classes.len(), 1);
+
+        let md = categorize_classes_at(&all, "light", Some("md"));
+        let raw = |b: &[TaggedClass]| b.iter().map(|t| t.raw.clone()).collect::<Vec<_>>();
+        assert_eq!(raw(&md.buckets.bg_classes), vec!["md:bg-white"]);
+        assert_eq!(raw(&md.buckets.text_classes), vec!["md:text-gray-400"]);
+        assert_eq!(raw(&md.interactive_states[0].1.bg_classes), vec!["md:hover:bg-gray-100"]);
+        let lg = categorize_classes_at(&all, "light", Some("lg"));
+        assert_eq!(raw(&lg.buckets.bg_classes), vec!["lg:bg-red-500"]);
+        assert_eq!(raw(&lg.buckets.text_classes), vec!["md:text-gray-400"]);
+    }
+
+    #[test]
+    fn state_cascade() {
+        let all = classes("bg-white text-black hover:bg-gray-100 active:text-gray-700 focus:ring-2 group-hover:bg-red-500");
+        assert_eq!(color_states(&all), vec!["hover", "active"]);
+        let raw = |b: &[TaggedClass]| b.iter().map(|t| t.raw.clone()).collect::<Vec<_>>();
+        let hover = categorize_classes_in(&all, "light", None, Some("hover"));
+        assert_eq!(raw(&hover.buckets.bg_classes), vec!["hover:bg-gray-100"]);
+        assert_eq!(raw(&hover.buckets.text_classes), vec!["text-black"]);
+        assert!(hover.interactive_states.is_empty());
+        let active = categorize_classes_in(&all, "light", None, Some("active"));
+        assert_eq!(raw(&active.buckets.bg_classes), vec!["bg-white"]);
+        assert_eq!(raw(&active.buckets.text_classes), vec!["active:text-gray-700"]);
+        assert_eq!(active.interactive_states[0].0, "hover");
+    }
+
+    #[test]
+    fn attribute_state_variants() {
+        let open = strip_variants("data-[state=open]:bg-accent");
+        assert_eq!((open.base.as_str(), open.is_interactive), ("bg-accent", true));
+        assert_eq!(strip_variants("aria-[sort=ascending]:text-white").base, "text-white");
+        let all = classes("bg-white aria-selected:text-white data-[state=open]:bg-accent data-[side=top]:p-2 hover:bg-muted");
+        assert_eq!(color_states(&all), vec!["hover", "aria-selected", "data-[state=open]"]);
+        let base = categorize_classes(&all, "light");
+        assert_eq!(base.buckets.bg_classes.len(), 1);
+        let state = categorize_classes_in(&all, "light", None, Some("data-[state=open]"));
+        assert_eq!(state.buckets.bg_classes[0].raw, "data-[state=open]:bg-accent");
+        assert!(is_state_variant("aria-[checked=true]") && !is_state_variant("group-hover"));
+    }
+
+    #[test]
+    fn relational_states_bucketed() {
+        let all = classes("text-black group-hover:text-white dark:group-hover:text-gray-100 group-hover/card:bg-primary sm:group-hover:text-red-500 peer-focus-visible:border-red-500");
+        let group = relational_state_classes(&all, "group", "light");
+        assert_eq!(group.len(), 1);
+        assert_eq!(group[0].0, "hover");
+        let text: Vec<&str> = group[0].1.text_classes.iter().map(|t| t.raw.as_str()).collect();
+        assert_eq!(text, vec!["group-hover:text-white"]);
+        assert_eq!(group[0].1.bg_classes[0].base, "bg-primary");
+        let peer = relational_state_classes(&all, "peer", "light");
+        assert_eq!((peer[0].0, peer[0].1.border_classes[0].base.as_str()), ("focus-visible", "border-red-500"));
+        // Plain categorization leaves them out
+        assert!(categorize_classes(&all, "light").interactive_states.is_empty());
+    }
+
+    #[test]
+    fn dynamic_and_font_metadata() {
+        let c = categorize_classes(&classes("text-xl font-bold ${color}"), "light");
+        assert_eq!(c.dynamic_classes, vec!["${color}"]);
+        assert!(determine_is_large_text(c.font_size.as_deref(), c.is_bold));
+        assert!(!determine_is_large_text(Some("text-xl"), false));
+        assert!(determine_is_large_text(Some("text-3xl"), false));
+    }
+
+    #[test]
+    fn string_literals_from_cn_body() {
+        let body = r#""bg-card p-4", active && 'text-white', `border ${x} ring-red-500`"#;
+        assert_eq!(
+            extract_string_literals(body),
+            classes("bg-card p-4 text-white border ring-red-500")
+        );
+        assert_eq!(region_classes("bg-card  text-white"), classes("bg-card text-white"));
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use crate::math::color_parse::to_hex;
+use crate::math::hex::{extract_hex_alpha, strip_hex_alpha};
+use crate::types::ResolvedColor;
+
+/// CSS variable (`--color-primary`) → resolved color, as built by the TS
+/// Tailwind resolver.
+pub type ColorMap = HashMap<String, ResolvedColor>;
+
+/// Combines two alpha values (both 0-1). None if fully opaque.
+pub fn combine_alpha(a1: Option<f64>, a2: Option<f64>) -> Option<f64> {
+    if a1.is_none() && a2.is_none() {
+        return None;
+    }
+    let combined = a1.unwrap_or(1.0) * a2.unwrap_or(1.0);
+    if combined < 0.999 { Some(combined) } else { None }
+}
+
+/// Strip the utility prefix: bg-, text-, border-[trblxy]-, divide-, ring-, outline-.
+fn strip_utility_prefix(class_name: &str) -> &str {
+    if let Some(rest) = class_name.strip_prefix("border-") {
+        let b = rest.as_bytes();
+        if b.len() >= 2 && matches!(b[0], b't' | b'r' | b'b' | b'l' | b'x' | b'y') && b[1] == b'-' {
+            return &rest[2..];
+        }
+        return rest;
+    }
+    for prefix in ["bg-", "text-", "divide-", "ring-", "outline-"] {
+        if let Some(rest) = class_name.strip_prefix(prefix) {
+            return rest;
+        }
+    }
+    class_name
+}
+
+/// JS `parseInt(s, 10)`: leading decimal digits, None if there are none.
+fn parse_leading_int(s: &str) -> Option<f64> {
+    let digits: String = s.trim_start().chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse::<f64>().ok()
+}
+
+/// Split `color/opacity` into (color name, opacity alpha), keeping `/`
+/// inside brackets intact.
+fn split_opacity(color_part: &str) -> (&str, Option<f64>) {
+    // Slash-bracket arbitrary opacity first: color/[value]
+    if color_part.ends_with(']') {
+        if let Some(idx) = color_part.rfind("/[") {
+            let raw = &color_part[idx + 2..color_part.len() - 1];
+            if idx > 0 && !raw.is_empty() && !raw.contains(']') {
+                let alpha = match raw.strip_suffix('%') {
+                    Some(pct) => pct.trim().parse::<f64>().ok().map(|v| v / 100.0),
+                    None => raw.trim().parse::<f64>().ok(),
+                };
+                return (&color_part[..idx], alpha);
+            }
+        }
+    }
+
+    if let Some(open) = color_part.find('[') {
+        return match color_part[open..].find(']') {
+            Some(rel_close) => {
+                let close = open + rel_close;
+                let after = &color_part[close + 1..];
+                match after.find('/') {
+                    Some(slash) => (
+                        &color_part[..close + 1],
+                        parse_leading_int(&after[slash + 1..]).map(|v| v / 100.0),
+                    ),
+                    None => (color_part, None),
+                }
+            }
+            None => (color_part, None),
+        };
+    }
+
+    match color_part.find('/') {
+        Some(slash) => (
+            &color_part[..slash],
+            parse_leading_int(&color_part[slash + 1..]).map(|v| v / 100.0),
+        ),
+        None => (color_part, None),
+    }
+}
+
+/// Theme variable a color class reads (`hover:text-muted-foreground/70` →
+/// `--color-muted-foreground`). None for arbitrary values and special colors.
+pub fn css_variable_of(class_name: &str) -> Option<String> {
+    let utility = class_name.rsplit(':').next()?.trim_start_matches('!');
+    let (color_name, _) = split_opacity(strip_utility_prefix(utility));
+    let is_token = !color_name.is_empty()
+        && color_name.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_'))
+        && !matches!(color_name, "transparent" | "current" | "inherit");
+    is_token.then(|| format!("--color-{}", color_name))
+}
+
+/// Resolve a Tailwind color class (`bg-primary/50`, `text-[#ff0000]`,
+/// `border-t-red-500`) to a hex color with optional alpha.
+///
+/// Port of: src/plugins/tailwind/css-resolver.ts → resolveClassToHex()
+pub fn resolve_class_to_hex(class_name: &str, color_map: &ColorMap) -> Option<ResolvedColor> {
+    let (color_name, opacity_alpha) = split_opacity(strip_utility_prefix(class_name));
+
+    // Arbitrary value: [#ff0000] or [oklch(...)]
+    if color_name.len() >= 2 && color_name.starts_with('[') && color_name.ends_with(']') {
+        let hex = to_hex(&color_name[1..color_name.len() - 1])?;
+        return Some(ResolvedColor {
+            hex: strip_hex_alpha(&hex),
+            alpha: combine_alpha(extract_hex_alpha(&hex), opacity_alpha),
+        });
+    }
+
+    if color_name.is_empty() || matches!(color_name, "transparent" | "current" | "inherit") {
+        return None;
+    }
+
+    let resolved = color_map.get(&format!("--color-{}", color_name))?;
+    Some(ResolvedColor {
+        hex: resolved.hex.clone(),
+        alpha: combine_alpha(resolved.alpha, opacity_alpha),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map() -> ColorMap {
+        let mut m = ColorMap::new();
+        m.insert("--color-primary".to_string(), ResolvedColor { hex: "#1d4ed8".to_string(), alpha: None });
+        m.insert("--color-red-500".to_string(), ResolvedColor { hex: "#ef4444".to_string(), alpha: None });
+        m.insert("--color-overlay".to_string(), ResolvedColor { hex: "#000000".to_string(), alpha: Some(0.5) });
+        m
+    }
+
+    #[test]
+    fn resolves_theme_colors_by_css_var() {
+        assert_eq!(resolve_class_to_hex("bg-primary", &map()).unwrap().hex, "#1d4ed8");
+        assert_eq!(resolve_class_to_hex("border-t-red-500", &map()).unwrap().hex, "#ef4444");
+        assert_eq!(resolve_class_to_hex("border-red-500", &map()).unwrap().hex, "#ef4444");
+        assert!(resolve_class_to_hex("text-unknown", &map()).is_none());
+    }
+
+    #[test]
+    fn opacity_modifiers_combine() {
+        let r = resolve_class_to_hex("bg-primary/50", &map()).unwrap();
+        assert_eq!(r.alpha, Some(0.5));
+        let r = resolve_class_to_hex("bg-overlay/50", &map()).unwrap();
+        assert_eq!(r.alpha, Some(0.25));
+        let r = resolve_class_to_hex("bg-primary/[35%]", &map()).unwrap();
+        assert!((r.alpha.unwrap() - 0.35).abs() < 1e-9);
+        assert_eq!(resolve_class_to_hex("bg-primary/100", &map()).unwrap().alpha, None);
+    }
+
+    #[test]
+    fn arbitrary_values() {
+        let r = resolve_class_to_hex("text-[#ff000080]", &map()).unwrap();
+        assert_eq!(r.hex, "#ff0000");
+        assert!((r.alpha.unwrap() - 128.0 / 255.0).abs() < 1e-9);
+        let r = resolve_class_to_hex("bg-[#fff]/50", &map()).unwrap();
+        assert_eq!(r.hex, "#ffffff");
+        assert_eq!(r.alpha, Some(0.5));
+    }
+
+    #[test]
+    fn special_values_unresolvable() {
+        assert!(resolve_class_to_hex("bg-transparent", &map()).is_none());
+        assert!(resolve_class_to_hex("text-current", &map()).is_none());
+        assert!(resolve_class_to_hex("text-inherit", &map()).is_none());
+    }
+
+    #[test]
+    fn css_variable_of_class() {
+        assert_eq!(css_variable_of("text-muted-foreground").as_deref(), Some("--color-muted-foreground"));
+        assert_eq!(css_variable_of("dark:hover:bg-primary/50").as_deref(), Some("--color-primary"));
+        assert_eq!(css_variable_of("border-t-red-500").as_deref(), Some("--color-red-500"));
+        assert_eq!(css_variable_of("text-[#ff0000]"), None);
+        assert_eq!(css_variable_of("text-current"), None);
+    }
+}
@@ -0,0 +1,7 @@
+//! Native region → color pair resolution (port of the TS JSX plugin's
+//! categorizer, Tailwind class resolver and region resolver), so pair
+//! building can run without a round trip through JS.
+
+pub mod categorizer;
+pub mod class_resolver;
+pub mod region_resolver;
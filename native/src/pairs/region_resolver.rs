@@ -0,0 +1,606 @@
+use std::collections::HashSet;
+
+use crate::types::{ClassRegion, ColorPair, SkippedClass};
+
+use super::categorizer::{
+    categorize_classes_in, determine_is_large_text, has_variant, is_state_variant, region_classes, relational_state_classes,
+    strip_variants, ClassBuckets, TaggedClass,
+};
+use super::class_resolver::{resolve_class_to_hex, ColorMap};
+
+/// A group of foreground classes (text or non-text) to pair against backgrounds.
+pub struct ForegroundGroup<'a> {
+    pub classes: &'a [TaggedClass],
+    /// None = text pair (SC 1.4.3). Some = non-text pair type (SC 1.4.11)
+    pub pair_type: Option<&'static str>,
+}
+
+/// Metadata shared across all pairs generated from one region.
+#[derive(Debug, Clone, Default)]
+pub struct PairMeta {
+    pub file: String,
+    pub line: u32,
+    pub ignore_reason: Option<String>,
+    pub ignore_rules: Option<Vec<String>>,
+    pub is_large_text: bool,
+    pub interactive_state: Option<String>,
+    pub effective_opacity: Option<f64>,
+    pub required_marker: bool,
+    pub role: Option<String>,
+    pub end_line: Option<u32>,
+    pub tag_name: Option<String>,
+    pub ancestry: Option<Vec<String>>,
+    pub theme: Option<String>,
+    pub provenance: Option<String>,
+    pub breakpoint: Option<String>,
+}
+
+/// Pairs and skips produced from a set of regions.
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedPairs {
+    pub pairs: Vec<ColorPair>,
+    pub skipped: Vec<SkippedClass>,
+}
+
+fn is_hex_literal(value: &str) -> bool {
+    value.starts_with('#') && value.len() >= 4
+}
+
+/// Effective background classes for a region: explicit bg classes, else the
+/// context bg; an inline `backgroundColor` hex overrides both.
+///
+/// Port of: src/plugins/jsx/region-resolver.ts → buildEffectiveBg()
+pub fn build_effective_bg(
+    bg_classes: &[TaggedClass],
+    context_bg: &str,
+    inline_background_color: Option<&str>,
+) -> Vec<TaggedClass> {
+    if let Some(hex) = inline_background_color.filter(|h| is_hex_literal(h)) {
+        return vec![TaggedClass::synthetic(format!("(inline) {}", hex), format!("bg-[{}]", hex))];
+    }
+    if bg_classes.is_empty() {
+        vec![TaggedClass::synthetic(context_bg, context_bg)]
+    } else {
+        bg_classes.to_vec()
+    }
+}
+
+/// Generate color pairs from foreground groups against background classes.
+///
+/// Skip behavior:
+/// - Interactive pairs: unresolvable classes skipped silently (base already reported them)
+/// - Base text: unresolvable explicit bg → skip with reason; unresolvable implicit bg → pair with no bg hex
+/// - Base non-text: unresolvable bg → silent skip; unresolvable fg → skip with reason
+///
+/// Port of: src/plugins/jsx/region-resolver.ts → generatePairs()
+pub fn generate_pairs(
+    fg_groups: &[ForegroundGroup],
+    effective_bg: &[TaggedClass],
+    meta: &PairMeta,
+    color_map: &ColorMap,
+    has_explicit_bg: bool,
+    context_bg: &str,
+    out: &mut ResolvedPairs,
+) {
+    let is_interactive = meta.interactive_state.is_some();
+
+    for group in fg_groups {
+        if group.classes.is_empty() {
+            continue;
+        }
+        let is_text = group.pair_type.is_none();
+
+        for bg in effective_bg {
+            let bg_resolved = resolve_class_to_hex(&bg.base, color_map);
+
+            if bg_resolved.is_none() {
+                if !is_interactive && is_text && has_explicit_bg {
+                    out.skipped.push(SkippedClass {
+                        file: meta.file.clone(),
+                        line: meta.line,
+                        class_name: bg.raw.clone(),
+                        reason: format!("Unresolvable background: {}", bg.raw),
+                    });
+                }
+                // Only base text + implicit bg falls through with no bg hex
+                if is_interactive || !is_text || has_explicit_bg {
+                    continue;
+                }
+            }
+
+            for fg in group.classes {
+                let Some(fg_resolved) = resolve_class_to_hex(&fg.base, color_map) else {
+                    if !is_interactive {
+                        out.skipped.push(SkippedClass {
+                            file: meta.file.clone(),
+                            line: meta.line,
+                            class_name: fg.raw.clone(),
+                            reason: format!(
+                                "Unresolvable {} color: {}",
+                                group.pair_type.unwrap_or("text"),
+                                fg.raw
+                            ),
+                        });
+                    }
+                    continue;
+                };
+
+                let mut pair = ColorPair {
+                    file: meta.file.clone(),
+                    line: meta.line,
+                    bg_class: if is_interactive || has_explicit_bg {
+                        bg.raw.clone()
+                    } else {
+                        format!("(implicit) {}", context_bg)
+                    },
+                    text_class: fg.raw.clone(),
+                    bg_hex: bg_resolved.as_ref().map(|r| r.hex.clone()),
+                    text_hex: Some(fg_resolved.hex),
+                    bg_alpha: bg_resolved.as_ref().and_then(|r| r.alpha),
+                    text_alpha: fg_resolved.alpha,
+                    ignored: Some(meta.ignore_reason.is_some()),
+                    ignore_reason: meta.ignore_reason.clone(),
+                    ignore_rules: meta.ignore_rules.clone().filter(|_| meta.ignore_reason.is_some()),
+                    interactive_state: meta.interactive_state.clone(),
+                    role: meta.role.clone(),
+                    end_line: meta.end_line,
+                    tag_name: meta.tag_name.clone(),
+                    ancestry: meta.ancestry.clone(),
+                    theme: meta.theme.clone(),
+                    provenance: meta.provenance.clone(),
+                    breakpoint: meta.breakpoint.clone(),
+                    ..Default::default()
+                };
+
+                if is_text {
+                    pair.is_large_text = Some(meta.is_large_text);
+                    pair.required_marker = meta.required_marker.then_some(true);
+                } else {
+                    pair.pair_type = group.pair_type.map(str::to_string);
+                }
+
+                // US-05: Apply effective opacity as alpha reduction
+                if let Some(opacity) = meta.effective_opacity.filter(|o| *o < 1.0) {
+                    pair.effective_opacity = Some(opacity);
+                    pair.text_alpha = Some(pair.text_alpha.unwrap_or(1.0) * opacity);
+                    pair.bg_alpha = Some(pair.bg_alpha.unwrap_or(1.0) * opacity);
+                }
+
+                out.pairs.push(pair);
+            }
+        }
+    }
+}
+
+/// Resolve one file's regions into color pairs for a theme.
+///
+/// Unlike the TS resolver, ignore reasons come from the regions themselves
+/// (the native parser already resolved a11y-ignore, disabled and opacity).
+///
+/// Port of: src/plugins/jsx/region-resolver.ts → resolveFileRegions()
+pub fn resolve_regions(
+    file: &str,
+    regions: &[ClassRegion],
+    color_map: &ColorMap,
+    theme_mode: &str,
+    out: &mut ResolvedPairs,
+) {
+    // States rendered by twin regions, which stand in for the state pairs of
+    // the region they twin
+    let twin_states: HashSet<(u32, &str, Option<&str>, &str)> = regions
+        .iter()
+        .filter_map(|r| {
+            let state = r.interactive_state.as_deref()?;
+            Some((r.start_line, r.content.as_str(), r.theme.as_deref(), state))
+        })
+        .collect();
+
+    for region in regions {
+        // A dark-mode twin renders dark in any run; in a dark run it stands
+        // in for the region it twins
+        let theme_mode = match region.theme.as_deref() {
+            Some("dark") => "dark",
+            Some("light") if theme_mode == "dark" => continue,
+            _ => theme_mode,
+        };
+        let line = region.start_line;
+        let classes = region_classes(&region.content);
+        let twin_state = region.interactive_state.as_deref().filter(|s| is_state_variant(s));
+        let categorized = categorize_classes_in(&classes, theme_mode, region.breakpoint.as_deref(), twin_state);
+        let is_large_text =
+            determine_is_large_text(categorized.font_size.as_deref(), categorized.is_bold);
+
+        for dc in &categorized.dynamic_classes {
+            out.skipped.push(SkippedClass {
+                file: file.to_string(),
+                line,
+                class_name: dc.clone(),
+                reason: "Dynamic class (template expression)".to_string(),
+            });
+        }
+
+        // Effective background (context fallback + inline override + annotation)
+        let has_annotation = region.context_override_bg.is_some()
+            || region.context_override_fg.is_some()
+            || region.context_override_no_inherit == Some(true);
+        let override_bg = region.context_override_bg.as_deref().filter(|b| !b.is_empty());
+        let hex_override_bg = override_bg.filter(|b| b.starts_with('#'));
+        let context_bg = match override_bg {
+            Some(bg) if hex_override_bg.is_none() => bg,
+            _ => region.context_bg.as_str(),
+        };
+        let inline_bg = hex_override_bg.or(region.inline_background_color.as_deref());
+
+        let buckets = &categorized.buckets;
+        let effective_bg = build_effective_bg(&buckets.bg_classes, context_bg, inline_bg);
+        let has_explicit_bg = !buckets.bg_classes.is_empty();
+
+        // Inline text color: synthetic text class
+        let mut text_classes = buckets.text_classes.clone();
+        if let Some(hex) = region.inline_color.as_deref().filter(|h| is_hex_literal(h)) {
+            text_classes.push(TaggedClass::synthetic(format!("(inline) {}", hex), format!("text-[{}]", hex)));
+        }
+
+        // fg override from @a11y-context annotation replaces all text classes
+        if let Some(fg) = region.context_override_fg.as_deref().filter(|f| !f.is_empty()) {
+            let base = if is_hex_literal(fg) { format!("text-[{}]", fg) } else { fg.to_string() };
+            text_classes = vec![TaggedClass::synthetic(format!("(@a11y-context) {}", fg), base)];
+        }
+
+        let meta = PairMeta {
+            file: file.to_string(),
+            line,
+            ignore_reason: region.ignore_reason.clone().filter(|_| region.ignored == Some(true)),
+            ignore_rules: region.ignored_rules.clone(),
+            is_large_text,
+            interactive_state: twin_state.map(str::to_string),
+            effective_opacity: region.effective_opacity,
+            required_marker: region.required_marker == Some(true),
+            role: region.role.clone(),
+            end_line: region.end_line,
+            tag_name: Some(region.tag_name.clone()).filter(|tag| !tag.is_empty()),
+            ancestry: Some(region.ancestry.clone()).filter(|path| !path.is_empty()),
+            theme: region.theme.clone().filter(|theme| theme == "dark"),
+            provenance: region.provenance.clone(),
+            breakpoint: region.breakpoint.clone(),
+        };
+
+        let first_pair = out.pairs.len();
+
+        // Inline border / outline colors: synthetic non-text classes
+        let with_inline = |classes: &[TaggedClass], inline: Option<&str>, prefix: &str| {
+            let mut classes = classes.to_vec();
+            if let Some(hex) = inline.filter(|h| is_hex_literal(h)) {
+                let base = format!("{}-[{}]", prefix, hex);
+                classes.push(TaggedClass::synthetic(format!("(inline) {}", hex), base));
+            }
+            classes
+        };
+        let border_classes =
+            with_inline(&buckets.border_classes, region.inline_border_color.as_deref(), "border");
+        let outline_classes =
+            with_inline(&buckets.outline_classes, region.inline_outline_color.as_deref(), "outline");
+
+        // Base pairs (text SC 1.4.3 + non-text SC 1.4.11)
+        let base_groups = [
+            ForegroundGroup { classes: &text_classes, pair_type: None },
+            ForegroundGroup { classes: &border_classes, pair_type: Some("border") },
+            ForegroundGroup { classes: &buckets.ring_classes, pair_type: Some("ring") },
+            ForegroundGroup { classes: &outline_classes, pair_type: Some("outline") },
+        ];
+        generate_pairs(&base_groups, &effective_bg, &meta, color_map, has_explicit_bg, context_bg, out);
+
+        // Interactive state pairs (CSS inheritance: state overrides base)
+        let mut state_pairs = |state: &str, state_classes: &ClassBuckets, base_bg: &[TaggedClass]| {
+            let state_bg = if state_classes.bg_classes.is_empty() {
+                base_bg
+            } else {
+                &state_classes.bg_classes
+            };
+            let state_text = if state_classes.text_classes.is_empty() {
+                &text_classes
+            } else {
+                &state_classes.text_classes
+            };
+            let state_meta = PairMeta {
+                interactive_state: Some(state.to_string()),
+                ..meta.clone()
+            };
+            let state_groups = [
+                ForegroundGroup { classes: state_text, pair_type: None },
+                ForegroundGroup { classes: &state_classes.border_classes, pair_type: Some("border") },
+                ForegroundGroup { classes: &state_classes.ring_classes, pair_type: Some("ring") },
+                ForegroundGroup { classes: &state_classes.outline_classes, pair_type: Some("outline") },
+            ];
+            generate_pairs(&state_groups, state_bg, &state_meta, color_map, has_explicit_bg, context_bg, out);
+        };
+        let twinned = |state: &str| {
+            region.breakpoint.is_none()
+                && twin_states.contains(&(line, region.content.as_str(), region.theme.as_deref(), state))
+        };
+        for (state, state_classes) in &categorized.interactive_states {
+            if twin_state.is_none() && !twinned(state) {
+                state_pairs(state, state_classes, &effective_bg);
+            }
+        }
+
+        // `group-hover:` classes sit on the group's hover bg unless the
+        // element paints its own; `peer-*` classes on the element's bg
+        if let Some(group_bg) = region.group_hover_bg.as_deref().filter(|_| twin_state.is_none()) {
+            let own_bg = has_explicit_bg || inline_bg.is_some() || override_bg.is_some();
+            let group_bg = [TaggedClass::synthetic(format!("(group) {}", group_bg), group_bg)];
+            let base_bg = if own_bg { &effective_bg[..] } else { &group_bg[..] };
+            for (state, state_classes) in relational_state_classes(&classes, "group", theme_mode) {
+                state_pairs(state, &state_classes, base_bg);
+            }
+        }
+        if region.peer_state == Some(true) && twin_state.is_none() {
+            for (state, state_classes) in relational_state_classes(&classes, "peer", theme_mode) {
+                state_pairs(state, &state_classes, &effective_bg);
+            }
+        }
+
+        // A breakpoint twin only adds the pairs its breakpoint classes change
+        if region.breakpoint.is_some() {
+            let pairs = out.pairs.split_off(first_pair);
+            let changed = |class: &str| strip_variants(class).breakpoint.is_some();
+            out.pairs.extend(pairs.into_iter().filter(|p| changed(&p.bg_class) || changed(&p.text_class)));
+        }
+
+        // So does a state twin, with the state's classes
+        if let Some(state) = twin_state {
+            let pairs = out.pairs.split_off(first_pair);
+            let changed = |class: &str| has_variant(class, state);
+            out.pairs.extend(pairs.into_iter().filter(|p| changed(&p.bg_class) || changed(&p.text_class)));
+        }
+
+        if has_annotation {
+            for pair in &mut out.pairs[first_pair..] {
+                pair.context_source = Some("annotation".to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ResolvedColor;
+
+    fn map() -> ColorMap {
+        [
+            ("--color-background", "#ffffff"),
+            ("--color-card", "#f4f4f5"),
+            ("--color-white", "#ffffff"),
+            ("--color-black", "#000000"),
+            ("--color-gray-400", "#9ca3af"),
+            ("--color-red-500", "#ef4444"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), ResolvedColor { hex: v.to_string(), alpha: None }))
+        .collect()
+    }
+
+    fn region(content: &str) -> ClassRegion {
+        ClassRegion {
+            content: content.to_string(),
+            start_line: 3,
+            context_bg: "bg-background".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn resolve(regions: &[ClassRegion]) -> ResolvedPairs {
+        let mut out = ResolvedPairs::default();
+        resolve_regions("a.tsx", regions, &map(), "light", &mut out);
+        out
+    }
+
+    #[test]
+    fn state_twins_replace_state_pairs() {
+        let r = region("bg-black text-white hover:bg-red-500 active:text-gray-400");
+        let hover = ClassRegion { interactive_state: Some("hover".to_string()), ..r.clone() };
+        let active = ClassRegion { interactive_state: Some("active".to_string()), ..r.clone() };
+        let out = resolve(&[r, hover, active]);
+        let pairs: Vec<(&str, &str, Option<&str>)> = out
+            .pairs
+            .iter()
+            .map(|p| (p.text_class.as_str(), p.bg_class.as_str(), p.interactive_state.as_deref()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("text-white", "bg-black", None),
+                ("text-white", "hover:bg-red-500", Some("hover")),
+                ("active:text-gray-400", "bg-black", Some("active")),
+            ]
+        );
+    }
+
+    #[test]
+    fn breakpoint_twins_add_only_changed_pairs() {
+        let r = region("bg-black text-white border-gray-400 md:bg-white md:text-gray-400");
+        let twin = ClassRegion { breakpoint: Some("md".to_string()), ..r.clone() };
+        let out = resolve(&[r, twin]);
+        let pairs: Vec<(&str, &str, Option<&str>)> = out
+            .pairs
+            .iter()
+            .map(|p| (p.text_class.as_str(), p.bg_class.as_str(), p.breakpoint.as_deref()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("text-white", "bg-black", None),
+                ("border-gray-400", "bg-black", None),
+                ("md:text-gray-400", "md:bg-white", Some("md")),
+                ("border-gray-400", "md:bg-white", Some("md")),
+            ]
+        );
+    }
+
+    #[test]
+    fn dark_twins_resolved_in_dark_mode() {
+        let light = ClassRegion { theme: Some("light".to_string()), ..region("text-black dark:text-white") };
+        let dark = ClassRegion {
+            context_bg: "black".to_string(),
+            theme: Some("dark".to_string()),
+            ..light.clone()
+        };
+        let regions = [light, dark];
+        let pairs = |out: &ResolvedPairs| -> Vec<(String, String, Option<String>)> {
+            out.pairs.iter().map(|p| (p.text_class.clone(), p.bg_class.clone(), p.theme.clone())).collect()
+        };
+        let both = vec![
+            ("text-black".to_string(), "(implicit) bg-background".to_string(), None),
+            ("dark:text-white".to_string(), "(implicit) black".to_string(), Some("dark".to_string())),
+        ];
+        assert_eq!(pairs(&resolve(&regions)), both);
+
+        // A dark run only checks the twin
+        let mut out = ResolvedPairs::default();
+        resolve_regions("a.tsx", &regions, &map(), "dark", &mut out);
+        assert_eq!(pairs(&out), both[1..]);
+    }
+
+    #[test]
+    fn required_marker_flag_reaches_text_pairs_only() {
+        let mut r = region("text-red-500 border-black");
+        r.required_marker = Some(true);
+        let out = resolve(&[r]);
+        let text = out.pairs.iter().find(|p| p.pair_type.is_none()).unwrap();
+        let border = out.pairs.iter().find(|p| p.pair_type.as_deref() == Some("border")).unwrap();
+        assert_eq!(text.required_marker, Some(true));
+        assert_eq!(border.required_marker, None);
+    }
+
+    #[test]
+    fn inline_border_and_outline_colors_pair_as_non_text() {
+        let r = ClassRegion {
+            inline_border_color: Some("#9ca3af".to_string()),
+            inline_outline_color: Some("#000000".to_string()),
+            inline_text_decoration_color: Some("#ef4444".to_string()),
+            ..region("bg-card text-black")
+        };
+        let out = resolve(&[r]);
+        let kinds: Vec<(Option<&str>, &str)> =
+            out.pairs.iter().map(|p| (p.pair_type.as_deref(), p.text_class.as_str())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (None, "text-black"),
+                (Some("border"), "(inline) #9ca3af"),
+                (Some("outline"), "(inline) #000000"),
+            ]
+        );
+        assert_eq!(out.pairs[1].text_hex.as_deref(), Some("#9ca3af"));
+    }
+
+    #[test]
+    fn group_hover_pairs_against_the_group_hover_bg() {
+        let r = ClassRegion {
+            group_hover_bg: Some("bg-black".to_string()),
+            ..region("text-black group-hover:text-white peer-hover:text-red-500")
+        };
+        let out = resolve(&[r]);
+        let pairs: Vec<(Option<&str>, &str, &str)> = out
+            .pairs
+            .iter()
+            .map(|p| (p.interactive_state.as_deref(), p.text_class.as_str(), p.bg_class.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (None, "text-black", "(implicit) bg-background"),
+                (Some("hover"), "group-hover:text-white", "(group) bg-black"),
+            ]
+        );
+
+        // The element's own bg covers the group's; peer classes need a peer
+        let r = ClassRegion {
+            group_hover_bg: Some("bg-black".to_string()),
+            peer_state: Some(true),
+            ..region("bg-card group-hover:text-gray-400 peer-hover:text-red-500")
+        };
+        let out = resolve(&[r]);
+        let hover: Vec<(&str, &str)> =
+            out.pairs.iter().map(|p| (p.text_class.as_str(), p.bg_hex.as_deref().unwrap())).collect();
+        assert_eq!(
+            hover,
+            vec![("group-hover:text-gray-400", "#f4f4f5"), ("peer-hover:text-red-500", "#f4f4f5")]
+        );
+    }
+
+    #[test]
+    fn tag_name_and_ancestry_carried_to_every_pair() {
+        let r = ClassRegion {
+            tag_name: "Button".to_string(),
+            ancestry: vec!["Card".to_string(), "Button".to_string()],
+            ..region("text-red-500 border-black")
+        };
+        let out = resolve(&[r]);
+        assert!(out.pairs.iter().all(|p| p.tag_name.as_deref() == Some("Button")));
+        assert!(out.pairs.iter().all(|p| p.ancestry.as_ref().is_some_and(|a| a.join(">") == "Card>Button")));
+        let bare = resolve(&[region("text-red-500")]);
+        assert_eq!((bare.pairs[0].tag_name.as_ref(), bare.pairs[0].ancestry.as_ref()), (None, None));
+    }
+
+    #[test]
+    fn implicit_context_bg_pair() {
+        let out = resolve(&[region("text-gray-400")]);
+        assert_eq!(out.pairs.len(), 1);
+        let p = &out.pairs[0];
+        assert_eq!(p.bg_class, "(implicit) bg-background");
+        assert_eq!(p.bg_hex.as_deref(), Some("#ffffff"));
+        assert_eq!(p.text_hex.as_deref(), Some("#9ca3af"));
+        assert_eq!(p.is_large_text, Some(false));
+        assert_eq!(p.line, 3);
+    }
+
+    #[test]
+    fn explicit_bg_and_non_text_pairs() {
+        let out = resolve(&[region("bg-card text-black border-red-500")]);
+        assert_eq!(out.pairs.len(), 2);
+        assert_eq!(out.pairs[0].bg_class, "bg-card");
+        assert_eq!(out.pairs[1].pair_type.as_deref(), Some("border"));
+    }
+
+    #[test]
+    fn unresolvable_classes_reported_as_skipped() {
+        let out = resolve(&[region("bg-mystery text-black"), region("text-unknown")]);
+        assert!(out.skipped.iter().any(|s| s.reason == "Unresolvable background: bg-mystery"));
+        assert!(out.skipped.iter().any(|s| s.reason == "Unresolvable text color: text-unknown"));
+        assert!(out.pairs.is_empty());
+        assert_eq!(out.skipped.len(), 2);
+    }
+
+    #[test]
+    fn hover_state_inherits_base_bg() {
+        let out = resolve(&[region("bg-white text-black hover:text-gray-400")]);
+        let hover = out.pairs.iter().find(|p| p.interactive_state.as_deref() == Some("hover")).unwrap();
+        assert_eq!(hover.bg_class, "bg-white");
+        assert_eq!(hover.text_class, "hover:text-gray-400");
+    }
+
+    #[test]
+    fn annotation_overrides_and_marks_source() {
+        let mut r = region("text-white");
+        r.context_override_bg = Some("#000000".to_string());
+        let out = resolve(&[r]);
+        assert_eq!(out.pairs[0].bg_hex.as_deref(), Some("#000000"));
+        assert_eq!(out.pairs[0].context_source.as_deref(), Some("annotation"));
+    }
+
+    #[test]
+    fn ignored_region_and_opacity_carried_to_pairs() {
+        let mut r = region("text-gray-400");
+        r.ignored = Some(true);
+        r.ignore_reason = Some("legacy".to_string());
+        r.effective_opacity = Some(0.5);
+        let out = resolve(&[r]);
+        let p = &out.pairs[0];
+        assert_eq!(p.ignored, Some(true));
+        assert_eq!(p.ignore_reason.as_deref(), Some("legacy"));
+        assert_eq!(p.text_alpha, Some(0.5));
+        assert_eq!(p.bg_alpha, Some(0.5));
+    }
+}
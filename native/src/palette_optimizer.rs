@@ -0,0 +1,228 @@
+//! Palette optimization: minimal OKLCH perturbations of failing tokens.
+//!
+//! Every token read by a violation (as text or background) is searched
+//! independently. Candidates keep the token's hue and walk its OKLCH
+//! lightness in small steps up and down, optionally with reduced chroma;
+//! out-of-gamut candidates and those further than `max_delta_e` (CIE76)
+//! from the current value are dropped. Each remaining candidate is replayed
+//! with `fix_plan::simulate_token_change`, and the smallest ΔE candidate
+//! whose pass rate over the token's pairs reaches `target_pass_rate` is
+//! proposed. Proposals are ranked by violations fixed, then regressions,
+//! then ΔE.
+
+use std::collections::BTreeMap;
+
+use csscolorparser::Color;
+
+use crate::fix_plan::simulate_token_change;
+use crate::math::checker::check_all_pairs_with_options;
+use crate::math::hex::strip_hex_alpha;
+use crate::math::state_delta::delta_e;
+use crate::pairs::class_resolver::css_variable_of;
+use crate::types::{
+    CheckOptions, PaletteOptimization, PaletteOptimizerOptions, PaletteProposal, TokenSimulation,
+    TokenSimulationInput,
+};
+
+/// Default share (0-1) of a token's pairs that must pass.
+pub const DEFAULT_TARGET_PASS_RATE: f64 = 1.0;
+
+/// Default ΔE (CIE76) a proposal may move a token; ~4× the JND of 2.3.
+pub const DEFAULT_MAX_DELTA_E: f64 = 10.0;
+
+/// OKLCH lightness step and the number of steps tried each way.
+const LIGHTNESS_STEP: f32 = 0.005;
+const LIGHTNESS_STEPS: u32 = 100;
+
+/// Chroma scales tried (1.0 keeps the token's saturation).
+const CHROMA_SCALES: &[f32] = &[1.0, 0.85, 0.7];
+
+/// In-gamut sRGB hex of an OKLCH color, None when it falls outside sRGB.
+fn oklch_to_hex(l: f32, c: f32, h: f32) -> Option<String> {
+    let color = Color::from_oklcha(l, c, h, 1.0);
+    let in_gamut = [color.r, color.g, color.b].iter().all(|v| (-0.0005..=1.0005).contains(v));
+    in_gamut.then(|| {
+        let [r, g, b, _] = color.clamp().to_rgba8();
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    })
+}
+
+/// CSS `oklch()` notation of a hex color (hue in degrees).
+fn oklch_css(hex: &str) -> String {
+    let [l, c, h, _] = hex.parse::<Color>().map(|c| c.to_oklcha()).unwrap_or_default();
+    let degrees = if c < 0.0005 { 0.0 } else { h.to_degrees().rem_euclid(360.0) };
+    format!("oklch({:.3} {:.3} {:.1})", l, c, degrees)
+}
+
+fn pass_rate(sim: &TokenSimulation) -> f64 {
+    if sim.affected == 0 {
+        1.0
+    } else {
+        sim.passed_after as f64 / sim.affected as f64
+    }
+}
+
+/// Smallest-ΔE candidate for `token` reaching `target`, with its simulation.
+fn best_candidate(
+    input: &TokenSimulationInput,
+    token: &str,
+    current: &str,
+    target: f64,
+    max_delta_e: f64,
+) -> Option<(String, f64, TokenSimulation)> {
+    let [l, c, h, _] = current.parse::<Color>().ok()?.to_oklcha();
+    let mut best: Option<(String, f64, TokenSimulation)> = None;
+    for &scale in CHROMA_SCALES {
+        for step in 1..=LIGHTNESS_STEPS {
+            for direction in [-1.0f32, 1.0] {
+                let lightness = l + direction * step as f32 * LIGHTNESS_STEP;
+                if !(0.0..=1.0).contains(&lightness) {
+                    continue;
+                }
+                let Some(hex) = oklch_to_hex(lightness, c * scale, h) else {
+                    continue;
+                };
+                let distance = delta_e(current, &hex);
+                let improves = best.as_ref().is_none_or(|(_, d, _)| distance < *d);
+                if distance > max_delta_e || !improves {
+                    continue;
+                }
+                let Some(sim) = simulate_token_change(input, token, &hex) else {
+                    continue;
+                };
+                if pass_rate(&sim) >= target {
+                    best = Some((hex, distance, sim));
+                }
+            }
+        }
+    }
+    best
+}
+
+/// Search minimal OKLCH changes of every token a violation reads.
+pub fn optimize_palette(input: &TokenSimulationInput, options: &PaletteOptimizerOptions) -> PaletteOptimization {
+    let target = options.target_pass_rate.unwrap_or(DEFAULT_TARGET_PASS_RATE).clamp(0.0, 1.0);
+    let max_delta_e = options.max_delta_e.unwrap_or(DEFAULT_MAX_DELTA_E);
+
+    // Replay against checked results, checking raw pairs only once
+    let input = match (&input.result, &input.pairs) {
+        (None, Some(pairs)) => {
+            let check = CheckOptions {
+                threshold: input.threshold.clone(),
+                page_bg: input.page_bg.clone(),
+                ..Default::default()
+            };
+            TokenSimulationInput {
+                result: Some(check_all_pairs_with_options(pairs, &check)),
+                pairs: None,
+                ..input.clone()
+            }
+        }
+        _ => input.clone(),
+    };
+    let Some(result) = &input.result else {
+        return PaletteOptimization { proposals: Vec::new(), unreachable: Vec::new() };
+    };
+
+    // Failing token → its current value
+    let mut tokens: BTreeMap<String, String> = BTreeMap::new();
+    for v in &result.violations {
+        for (class, hex) in [(&v.text_class, &v.text_hex), (&v.bg_class, &v.bg_hex)] {
+            if let (Some(token), Some(hex)) = (css_variable_of(class), hex) {
+                tokens.entry(token).or_insert_with(|| strip_hex_alpha(hex));
+            }
+        }
+    }
+
+    let mut proposals = Vec::new();
+    let mut unreachable = Vec::new();
+    for (token, current) in tokens {
+        let Some(before) = simulate_token_change(&input, &token, &current) else {
+            continue;
+        };
+        match best_candidate(&input, &token, &current, target, max_delta_e) {
+            Some((hex, distance, sim)) => proposals.push(PaletteProposal {
+                proposed_oklch: oklch_css(&hex),
+                token,
+                current_hex: current,
+                proposed_hex: hex,
+                delta_e: (distance * 100.0).round() / 100.0,
+                pass_rate_before: pass_rate(&before),
+                pass_rate_after: pass_rate(&sim),
+                fixed: sim.fixed.len() as u32,
+                regressions: sim.regressed.len() as u32,
+            }),
+            None => unreachable.push(token),
+        }
+    }
+
+    proposals.sort_by(|a, b| {
+        b.fixed
+            .cmp(&a.fixed)
+            .then(a.regressions.cmp(&b.regressions))
+            .then(a.delta_e.total_cmp(&b.delta_e))
+    });
+    if let Some(max) = options.max_proposals {
+        proposals.truncate(max as usize);
+    }
+    PaletteOptimization { proposals, unreachable }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::wcag::contrast_ratio;
+    use crate::types::ColorPair;
+
+    fn pair(text_class: &str, text_hex: &str, bg_class: &str, bg_hex: &str) -> ColorPair {
+        ColorPair {
+            bg_class: bg_class.to_string(),
+            bg_hex: Some(bg_hex.to_string()),
+            pair_type: Some("text".to_string()),
+            ..ColorPair::fixture(text_class, text_hex)
+        }
+    }
+
+    fn input(pairs: Vec<ColorPair>) -> TokenSimulationInput {
+        TokenSimulationInput { pairs: Some(pairs), ..Default::default() }
+    }
+
+    #[test]
+    fn near_miss_token_gets_minimal_change() {
+        // #78787f on white is 4.38:1, just short of 4.5
+        let pairs = vec![pair("text-muted-foreground", "#78787f", "bg-white", "#ffffff")];
+        let opt = optimize_palette(&input(pairs), &PaletteOptimizerOptions::default());
+        let p = opt.proposals.iter().find(|p| p.token == "--color-muted-foreground").unwrap();
+        assert_eq!((p.pass_rate_before, p.pass_rate_after), (0.0, 1.0));
+        assert_eq!(p.fixed, 1);
+        assert!(p.delta_e <= DEFAULT_MAX_DELTA_E);
+        let ratio = contrast_ratio(&p.proposed_hex, "#ffffff");
+        assert!((4.5..5.0).contains(&ratio), "{} → {}", p.proposed_hex, ratio);
+        assert!(p.delta_e < 3.0);
+        assert!(p.proposed_oklch.starts_with("oklch(0.5"));
+    }
+
+    #[test]
+    fn large_jumps_are_unreachable_within_delta_e() {
+        // Light gray on white needs a far bigger change than ΔE 3 allows
+        let pairs = vec![pair("text-gray-200", "#e5e7eb", "bg-white", "#ffffff")];
+        let options = PaletteOptimizerOptions { max_delta_e: Some(3.0), ..Default::default() };
+        let opt = optimize_palette(&input(pairs), &options);
+        assert!(opt.proposals.is_empty());
+        assert!(opt.unreachable.contains(&"--color-gray-200".to_string()));
+    }
+
+    #[test]
+    fn partial_target_accepts_smaller_change() {
+        let pairs = vec![
+            pair("text-brand", "#78787f", "bg-white", "#ffffff"),
+            pair("text-brand", "#78787f", "bg-gray-100", "#f3f4f6"),
+        ];
+        let brand = |opt: PaletteOptimization| opt.proposals.into_iter().find(|p| p.token == "--color-brand").unwrap();
+        let full = brand(optimize_palette(&input(pairs.clone()), &PaletteOptimizerOptions::default()));
+        let half = PaletteOptimizerOptions { target_pass_rate: Some(0.5), ..Default::default() };
+        let half = brand(optimize_palette(&input(pairs), &half));
+        assert!(half.delta_e < full.delta_e);
+        assert_eq!((half.pass_rate_after, full.pass_rate_after), (0.5, 1.0));
+    }
+}
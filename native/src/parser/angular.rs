@@ -0,0 +1,96 @@
+//! Angular component template support.
+//!
+//! A `.component.html` template is rewritten into JSX-shaped source with the
+//! same line numbering (see `markup`), merging a tag's class attributes into
+//! one static `className="..."`:
+//! - `class="..."` class lists
+//! - `[class.foo]="cond"` bindings, which contribute `foo` (conditional
+//!   classes count as present, like `cn()` arguments)
+//! - `[ngClass]`, `[class]` and `[attr.class]` expressions: object keys,
+//!   array items and string literals (component fields aren't resolved)
+//!
+//! Text nodes are neutralized as in plain HTML, so quotes inside `{{ ... }}`
+//! interpolations and prose can't swallow the tags after them.
+
+use std::collections::HashMap;
+
+use super::html::neutralize_text;
+use super::markup::{blank_blocks, rewrite_template, ClassAttr};
+
+/// Whether `path` is an Angular component template.
+pub fn is_angular_path(path: &str) -> bool {
+    path.ends_with(".component.html")
+}
+
+/// Rewrite an Angular template into JSX-shaped source with the same line numbering.
+pub fn to_jsx(source: &str) -> String {
+    let markup = neutralize_text(&blank_blocks(source, &["<script", "<style"]));
+    rewrite_template(&markup, &HashMap::new(), angular_class_attr)
+}
+
+fn angular_class_attr(name: &str) -> Option<ClassAttr<'_>> {
+    match name {
+        "class" => Some(ClassAttr::List),
+        "[ngClass]" | "[class]" | "[className]" | "[attr.class]" => Some(ClassAttr::Binding),
+        _ => name
+            .strip_prefix("[class.")
+            .and_then(|rest| rest.strip_suffix(']'))
+            .filter(|class| !class.is_empty())
+            .map(ClassAttr::Toggle),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = r#"<div class="bg-card p-4">
+  <!-- @a11y-context bg:#000000 -->
+  <p [class.text-muted-foreground]="!active" class="text-sm">{{ 'Don't' | translate }}</p>
+  <button class="rounded" [ngClass]="{ 'bg-red-600 text-white': danger, 'ring-2': focused }">Go</button>
+  @if (items.length > 0) {
+    <span [ngClass]="['text-green-700', size]">{{ items.length }}</span>
+  }
+</div>
+"#;
+
+    #[test]
+    fn non_ascii_template_text() {
+        let jsx = to_jsx("<p class=\"text-gray-500\" [class.font-bold]=\"x\">Caffè <!-- è --></p>");
+        assert!(jsx.contains(r#"className="text-gray-500 font-bold""#), "{}", jsx);
+        to_jsx("è</p><!-- è");
+    }
+
+    #[test]
+    fn angular_paths() {
+        assert!(is_angular_path("src/app/card/card.component.html"));
+        assert!(!is_angular_path("docs/index.html"));
+    }
+
+    #[test]
+    fn rewrite_merges_bindings_and_keeps_lines() {
+        let jsx = to_jsx(TEMPLATE);
+        assert_eq!(jsx.lines().count(), TEMPLATE.lines().count());
+        let lines: Vec<&str> = jsx.lines().collect();
+        assert!(lines[1].contains("{/* @a11y-context bg:#000000 */}"));
+        assert!(lines[2].contains(r#"className="text-muted-foreground text-sm""#));
+        assert!(!lines[2].contains("[class."));
+        assert!(lines[3].contains(r#"className="rounded bg-red-600 text-white ring-2""#));
+        assert!(lines[5].contains(r#"className="text-green-700""#));
+    }
+
+    #[test]
+    fn regions_inside_control_flow_blocks() {
+        let regions = crate::parser::scan_file_with_config(&to_jsx(TEMPLATE), &Default::default());
+        let found: Vec<(u32, &str)> = regions.iter().map(|r| (r.start_line, r.content.as_str())).collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, "bg-card p-4"),
+                (3, "text-muted-foreground text-sm"),
+                (4, "rounded bg-red-600 text-white ring-2"),
+                (6, "text-green-700"),
+            ]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use super::visitor::JsxVisitor;
+use crate::criteria;
 
 /// Override information parsed from `@a11y-context` annotations.
 #[derive(Debug, Clone)]
@@ -10,11 +11,22 @@ pub struct ContextOverride {
 
 /// Parses per-element annotations from JSX comments.
 ///
-/// Handles two annotation types:
+/// Handles three annotation types:
 /// - `@a11y-context bg:<class> [fg:<class>] [no-inherit]` — context override for next element
-/// - `a11y-ignore[: <reason>]` — suppression for next element
+/// - `a11y-ignore [<RULE-ID>, ...][: <reason>]` — suppression for next element,
+///   of the listed rules only when any (e.g. `a11y-ignore NONTEXT-CONTRAST: decorative`)
+/// - `a11y-ignore[<code>, ...][: <reason>]` — the same for rule codes in any case,
+///   including families (`a11y-ignore[contrast-text]`, see `criteria::rule_ids_of_code`).
+///   Rule ids may follow the brackets and add to them
+///   (`a11y-ignore[contrast-text] FOCUS-INDICATOR: ...`); before them, they leave
+///   the suppression unscoped like any other word there
+/// - `a11y-ignore-file[: <reason>]` — suppression for every element of the file,
+///   wherever the comment is (the first one wins)
+/// - `a11y-ignore-start [<RULE-ID>, ...][: <reason>]` … `a11y-ignore-end` — suppression
+///   for every element in between (to the end of the file when never closed)
 ///
-/// Block annotations (`@a11y-context-block`) are handled by ContextTracker, NOT here.
+/// Block annotations (`@a11y-context-block`) and named contexts
+/// (`@a11y-context use:<name>`) are handled by ContextTracker, NOT here.
 ///
 /// Port of: src/plugins/jsx/categorizer.ts → getContextOverrideForLine(), getIgnoreReasonForLine()
 pub struct AnnotationParser {
@@ -22,6 +34,20 @@ pub struct AnnotationParser {
     pending_context: Option<ContextOverride>,
     /// Pending a11y-ignore for next element (consumed on take)
     pending_ignore: Option<String>,
+    /// Rule ids the pending a11y-ignore is scoped to (consumed on take)
+    pending_ignore_rules: Option<Vec<String>>,
+    /// a11y-ignore-file reason (kept for the whole file)
+    file_ignore: Option<String>,
+    /// Open a11y-ignore-start range: start line, rule ids and reason
+    range_ignore: Option<(u32, Option<Vec<String>>, String)>,
+    /// Line of the most recent pending @a11y-context / a11y-ignore
+    pending_line: Option<u32>,
+}
+
+impl Default for AnnotationParser {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AnnotationParser {
@@ -29,6 +55,10 @@ impl AnnotationParser {
         Self {
             pending_context: None,
             pending_ignore: None,
+            pending_ignore_rules: None,
+            file_ignore: None,
+            range_ignore: None,
+            pending_line: None,
         }
     }
 
@@ -43,10 +73,58 @@ impl AnnotationParser {
     pub fn take_pending_ignore(&mut self) -> Option<String> {
         self.pending_ignore.take()
     }
+
+    /// Reason of the file's `a11y-ignore-file` annotation ("" without one), if any.
+    pub fn file_ignore(&self) -> Option<&str> {
+        self.file_ignore.as_deref()
+    }
+
+    /// Take and consume the rule ids the pending ignore is scoped to, if any.
+    pub fn take_pending_ignore_rules(&mut self) -> Option<Vec<String>> {
+        self.pending_ignore_rules.take()
+    }
+
+    /// Take the pending ignore reason and rule ids, falling back to the open
+    /// `a11y-ignore-start` range. An element suppression keeps its reason
+    /// unless scoped to fewer rules than the range.
+    pub fn take_ignore(&mut self) -> (Option<String>, Option<Vec<String>>) {
+        let reason = self.take_pending_ignore();
+        let rules = self.take_pending_ignore_rules();
+        match &self.range_ignore {
+            Some((_, range_rules, range_reason)) if reason.is_none() || (rules.is_some() && range_rules.is_none()) => {
+                (Some(range_reason.clone()), range_rules.clone())
+            }
+            _ => (reason, rules),
+        }
+    }
+
+    /// Line of the most recent pending annotation, if any is still pending.
+    pub fn pending_line(&self) -> Option<u32> {
+        self.pending_line.filter(|_| self.pending_context.is_some() || self.pending_ignore.is_some())
+    }
+
+    /// Drop pending annotations more than `max_distance` lines above an
+    /// element starting on `element_line` (blank lines included).
+    pub fn expire_pending(&mut self, element_line: u32, max_distance: u32) {
+        let Some(line) = self.pending_line() else {
+            return;
+        };
+        if element_line.saturating_sub(line) > max_distance {
+            log::debug!("annotation L{} too far from element L{}, dropped", line, element_line);
+            self.pending_context = None;
+            self.pending_ignore = None;
+            self.pending_ignore_rules = None;
+        }
+    }
+
+    /// Start line of an `a11y-ignore-start` never closed by `a11y-ignore-end`.
+    pub fn unclosed_range(&self) -> Option<u32> {
+        self.range_ignore.as_ref().map(|(line, _, _)| *line)
+    }
 }
 
 impl JsxVisitor for AnnotationParser {
-    fn on_comment(&mut self, content: &str, _line: u32) {
+    fn on_comment(&mut self, content: &str, line: u32) {
         let trimmed = content.trim();
 
         // Skip block annotations — those are handled by ContextTracker
@@ -58,33 +136,92 @@ impl JsxVisitor for AnnotationParser {
         if let Some(body) = trimmed.strip_prefix("@a11y-context") {
             if let Some(ctx) = parse_context_params(body) {
                 self.pending_context = Some(ctx);
+                self.pending_line = Some(line);
             }
             return;
         }
 
+        // Check for a11y-ignore-file (whole-file suppression)
+        if let Some(rest) = trimmed.strip_prefix("a11y-ignore-file") {
+            if rest.is_empty() || rest.starts_with([':', ' ']) {
+                let reason = rest.trim_start().strip_prefix(':').unwrap_or_default().trim();
+                self.file_ignore.get_or_insert_with(|| reason.to_string());
+                return;
+            }
+        }
+
+        // Check for a11y-ignore-start / a11y-ignore-end (ranged suppression)
+        if let Some(rest) = trimmed.strip_prefix("a11y-ignore-start") {
+            if rest.is_empty() || rest.starts_with([':', ' ', '[']) {
+                let (rules, reason) = parse_ignore_params(rest);
+                self.range_ignore = Some((line, rules, reason));
+                return;
+            }
+        }
+        if let Some(rest) = trimmed.strip_prefix("a11y-ignore-end") {
+            if rest.is_empty() || rest.starts_with([':', ' ']) {
+                self.range_ignore = None;
+                return;
+            }
+        }
+
         // Check for a11y-ignore (suppression)
         if let Some(rest) = trimmed.strip_prefix("a11y-ignore") {
-            let reason = if let Some(after_colon) = rest.strip_prefix(':') {
-                after_colon.trim().to_string()
-            } else {
-                String::new()
-            };
+            let (rules, reason) = parse_ignore_params(rest);
             self.pending_ignore = Some(reason);
+            self.pending_ignore_rules = rules;
+            self.pending_line = Some(line);
+        }
+    }
+}
+
+/// Rule ids and reason of an `a11y-ignore` body. Rule ids are upper-case
+/// words before the colon (`AA-TEXT-CONTRAST, FOCUS-INDICATOR: reason`),
+/// the codes in brackets (`[contrast-text]: reason`) or both, codes first
+/// (`[contrast-text] FOCUS-INDICATOR: reason`); anything else there leaves
+/// the suppression unscoped.
+fn parse_ignore_params(body: &str) -> (Option<Vec<String>>, String) {
+    if let Some((codes, rest)) = body.strip_prefix('[').and_then(|b| b.split_once(']')) {
+        let mut rules: Vec<String> = codes
+            .split([',', ' '])
+            .filter(|c| !c.trim().is_empty())
+            .flat_map(criteria::rule_ids_of_code)
+            .collect();
+        let rest = rest.trim_start();
+        let (ids, reason) = match rest.strip_prefix(':') {
+            Some(reason) => (None, reason.trim().to_string()),
+            None => parse_ignore_params(&format!(" {}", rest)),
+        };
+        for id in ids.into_iter().flatten() {
+            if !rules.contains(&id) {
+                rules.push(id);
+            }
         }
+        return ((!rules.is_empty()).then_some(rules), reason);
+    }
+    if let Some(reason) = body.strip_prefix(':') {
+        return (None, reason.trim().to_string());
     }
+    let (head, reason) = body.split_once(':').unwrap_or((body, ""));
+    let rules: Vec<String> = head.split([',', ' ']).filter(|r| !r.is_empty()).map(str::to_string).collect();
+    let is_rule_id = |r: &String| r.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'-');
+    if !body.starts_with(' ') || rules.is_empty() || !rules.iter().all(is_rule_id) {
+        return (None, String::new());
+    }
+    (Some(rules), reason.trim().to_string())
 }
 
 /// Parse `bg:<class> [fg:<class>] [no-inherit]` tokens from annotation body.
 ///
 /// Port of: src/plugins/jsx/categorizer.ts → parseContextParams()
-fn parse_context_params(param_string: &str) -> Option<ContextOverride> {
+pub(super) fn parse_context_params(param_string: &str) -> Option<ContextOverride> {
     let mut ctx = ContextOverride {
         bg: None,
         fg: None,
         no_inherit: false,
     };
 
-    for token in param_string.trim().split_whitespace() {
+    for token in param_string.split_whitespace() {
         if let Some(bg) = token.strip_prefix("bg:") {
             ctx.bg = Some(bg.to_string());
         } else if let Some(fg) = token.strip_prefix("fg:") {
@@ -183,6 +320,96 @@ mod tests {
         assert_eq!(reason, "no-space-reason");
     }
 
+    #[test]
+    fn parse_a11y_ignore_scoped_to_rules() {
+        let mut ap = AnnotationParser::new();
+        ap.on_comment(" a11y-ignore NONTEXT-CONTRAST, FOCUS-INDICATOR: decorative", 1);
+        assert_eq!(ap.take_pending_ignore().unwrap(), "decorative");
+        assert_eq!(ap.take_pending_ignore_rules().unwrap(), ["NONTEXT-CONTRAST", "FOCUS-INDICATOR"]);
+
+        // Prose before the colon is not a rule list
+        ap.on_comment(" a11y-ignore until redesign: tracked", 2);
+        assert_eq!(ap.take_pending_ignore().unwrap(), "");
+        assert!(ap.take_pending_ignore_rules().is_none());
+    }
+
+    #[test]
+    fn parse_a11y_ignore_bracketed_codes() {
+        let mut ap = AnnotationParser::new();
+        ap.on_comment(" a11y-ignore[contrast-text, use-of-color]: brand tagline", 1);
+        assert_eq!(ap.take_pending_ignore().unwrap(), "brand tagline");
+        assert_eq!(
+            ap.take_pending_ignore_rules().unwrap(),
+            ["AA-TEXT-CONTRAST", "AAA-TEXT-CONTRAST", "use-of-color"]
+        );
+        ap.on_comment(" a11y-ignore[focus-indicator]", 2);
+        assert_eq!(ap.take_pending_ignore().unwrap(), "");
+        assert_eq!(ap.take_pending_ignore_rules().unwrap(), ["FOCUS-INDICATOR"]);
+        // Empty brackets: unscoped
+        ap.on_comment(" a11y-ignore[]: reason", 3);
+        assert_eq!(ap.take_pending_ignore().unwrap(), "reason");
+        assert!(ap.take_pending_ignore_rules().is_none());
+    }
+
+    #[test]
+    fn parse_a11y_ignore_codes_and_rule_ids() {
+        let mut ap = AnnotationParser::new();
+        ap.on_comment(" a11y-ignore[contrast-text] FOCUS-INDICATOR, AA-TEXT-CONTRAST: brand", 1);
+        assert_eq!(ap.take_pending_ignore().unwrap(), "brand");
+        assert_eq!(
+            ap.take_pending_ignore_rules().unwrap(),
+            ["AA-TEXT-CONTRAST", "AAA-TEXT-CONTRAST", "FOCUS-INDICATOR"]
+        );
+        ap.on_comment(" a11y-ignore[] NONTEXT-CONTRAST", 2);
+        assert_eq!(ap.take_pending_ignore().unwrap(), "");
+        assert_eq!(ap.take_pending_ignore_rules().unwrap(), ["NONTEXT-CONTRAST"]);
+        // Rule ids first: the brackets aren't an id, so the suppression is unscoped
+        ap.on_comment(" a11y-ignore FOCUS-INDICATOR [contrast-text]: brand", 3);
+        assert_eq!(ap.take_pending_ignore().unwrap(), "");
+        assert!(ap.take_pending_ignore_rules().is_none());
+    }
+
+    #[test]
+    fn stale_pending_annotations_expire() {
+        let mut ap = AnnotationParser::new();
+        ap.on_comment(" a11y-ignore: reason", 2);
+        ap.on_comment(" @a11y-context bg:#000", 3);
+        assert_eq!(ap.pending_line(), Some(3));
+        ap.expire_pending(5, 2);
+        assert!(ap.take_pending_context().is_some());
+        ap.on_comment(" @a11y-context bg:#000", 6);
+        ap.expire_pending(9, 2);
+        assert!(ap.take_pending_context().is_none());
+        assert!(ap.take_pending_ignore().is_none());
+        assert_eq!(ap.pending_line(), None);
+    }
+
+    #[test]
+    fn parse_a11y_ignore_file() {
+        let mut ap = AnnotationParser::new();
+        assert_eq!(ap.file_ignore(), None);
+        ap.on_comment(" a11y-ignore-file: generated code", 1);
+        ap.on_comment(" a11y-ignore-file", 9);
+        assert_eq!(ap.file_ignore(), Some("generated code"));
+        // Not an element suppression
+        assert!(ap.take_pending_ignore().is_none());
+    }
+
+    #[test]
+    fn parse_a11y_ignore_range() {
+        let mut ap = AnnotationParser::new();
+        ap.on_comment(" a11y-ignore-start NONTEXT-CONTRAST: legacy widget", 3);
+        assert_eq!(ap.take_ignore(), (Some("legacy widget".into()), Some(vec!["NONTEXT-CONTRAST".into()])));
+        // Every element in the range, not just the next one
+        assert_eq!(ap.take_ignore().0.as_deref(), Some("legacy widget"));
+        ap.on_comment(" a11y-ignore: own reason", 5);
+        assert_eq!(ap.take_ignore(), (Some("own reason".into()), None));
+        assert_eq!(ap.unclosed_range(), Some(3));
+        ap.on_comment(" a11y-ignore-end", 8);
+        assert_eq!(ap.take_ignore(), (None, None));
+        assert_eq!(ap.unclosed_range(), None);
+    }
+
     #[test]
     fn pending_consumed_once() {
         let mut ap = AnnotationParser::new();
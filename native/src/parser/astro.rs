@@ -0,0 +1,127 @@
+//! Astro component support.
+//!
+//! An `.astro` file is rewritten into JSX-shaped source with the same line
+//! numbering (see `markup`):
+//! - the `---` frontmatter fence is blanked; its string constants resolve
+//!   identifiers in class expressions (`class={cardClass}`)
+//! - `class="..."`, `class={...}` and `class:list={[...]}` of a tag are merged
+//!   into one static `className="..."`
+//! - `<style>` / `<script>` blocks are blanked (`style_blocks` reads the
+//!   rules of `<style>` from the original source)
+//! - the rest of the template (component tags, `{...}` expressions) is kept,
+//!   so container backgrounds propagate through the context tracker as in JSX
+
+use std::collections::HashMap;
+
+use super::markup::{blank, blank_blocks, collect_constants, rewrite_template, ClassAttr};
+
+/// Whether `path` is an Astro component.
+pub fn is_astro_path(path: &str) -> bool {
+    path.ends_with(".astro")
+}
+
+/// Rewrite an Astro component into JSX-shaped source with the same line numbering.
+pub fn to_jsx(source: &str) -> String {
+    let mut constants = HashMap::new();
+    let (mut out, template_start) = match frontmatter_range(source) {
+        Some((body_start, body_end, fence_end)) => {
+            collect_constants(&source[body_start..body_end], &mut constants);
+            (blank(&source[..fence_end]), fence_end)
+        }
+        None => (String::new(), 0),
+    };
+    let template = blank_blocks(&source[template_start..], &["<style", "<script"]);
+    out.push_str(&rewrite_template(&template, &constants, astro_class_attr));
+    out
+}
+
+/// `class` and `class:list` (values in `{...}` are expressions).
+fn astro_class_attr(name: &str) -> Option<ClassAttr<'_>> {
+    match name {
+        "class" => Some(ClassAttr::List),
+        "class:list" => Some(ClassAttr::Binding),
+        _ => None,
+    }
+}
+
+/// (body start, body end, end of the closing fence) of a leading `---` block.
+fn frontmatter_range(source: &str) -> Option<(usize, usize, usize)> {
+    let lead = source.len() - source.trim_start().len();
+    let rest = source[lead..].strip_prefix("---")?;
+    let body_start = lead + 3 + rest.find('\n')? + 1;
+    let mut line_start = body_start;
+    for line in source[body_start..].split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some((body_start, line_start, line_start + 3));
+        }
+        line_start += line.len();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"---
+import Card from '../components/Card.astro';
+const accent = "text-sky-700 font-medium";
+const { title } = Astro.props;
+---
+<Layout title={title}>
+  <Card>
+    <h2 class="text-card-foreground">{title}</h2>
+    <a class:list={["underline", accent, { "opacity-50": disabled }]} href="/">Go</a>
+    <p class={accent}>x</p>
+  </Card>
+  <style>
+    h2 { color: red; }
+  </style>
+</Layout>
+"#;
+
+    #[test]
+    fn non_ascii_template_text() {
+        let jsx = to_jsx("---\nconst t = 'è'\n---\n<p class=\"text-gray-500\">Caffè <!-- è --></p>");
+        assert!(jsx.contains(r#"<p className="text-gray-500">Caffè {/* è */}</p>"#), "{}", jsx);
+        to_jsx("è</p><!-- è");
+    }
+
+    #[test]
+    fn astro_paths() {
+        assert!(is_astro_path("src/pages/index.astro"));
+        assert!(!is_astro_path("src/pages/index.tsx"));
+    }
+
+    #[test]
+    fn rewrite_keeps_line_numbers() {
+        let jsx = to_jsx(PAGE);
+        assert_eq!(jsx.lines().count(), PAGE.lines().count());
+        let lines: Vec<&str> = jsx.lines().collect();
+        assert!(lines[..5].iter().all(|l| l.is_empty()));
+        assert!(lines[7].contains(r#"<h2 className="text-card-foreground">"#));
+        assert!(lines[8].contains(r#"className="underline text-sky-700 font-medium opacity-50""#));
+        assert!(lines[9].contains(r#"className="text-sky-700 font-medium""#));
+        assert!(!jsx.contains("color: red"));
+        assert!(lines[14].contains("</Layout>"));
+    }
+
+    #[test]
+    fn no_frontmatter() {
+        assert_eq!(to_jsx(r#"<p class="text-white">x</p>"#), r#"<p className="text-white">x</p>"#);
+        assert!(frontmatter_range("<p>---</p>").is_none());
+    }
+
+    #[test]
+    fn container_context_propagates() {
+        let config = crate::parser::ScanConfig {
+            container_config: [("Card".to_string(), "bg-card".to_string())].into(),
+            default_bg: "bg-background".to_string(),
+            ..Default::default()
+        };
+        let regions = crate::parser::scan_file_with_config(&to_jsx(PAGE), &config);
+        assert_eq!(regions.len(), 3);
+        assert_eq!(regions[0].start_line, 8);
+        assert!(regions.iter().all(|r| r.context_bg == "bg-card"));
+    }
+}
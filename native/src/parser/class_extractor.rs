@@ -1,5 +1,17 @@
-use crate::types::ClassRegion;
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::i18n::{self, Locale, MessageKey};
+use crate::math::color_parse::to_hex;
+use crate::pairs::categorizer::{color_breakpoints, color_states, region_classes};
+use crate::types::{ClassLocation, ClassRegion};
 use super::annotation_parser::ContextOverride;
+use super::group_tracker::GroupContext;
+use super::style_blocks::Stylesheet;
+use super::tokenizer::{find_closing_bracket, strip_template_expressions};
+use super::visitor::ClassSource;
+use crate::rules::required_marker::is_marker_content;
+use crate::rules::static_attr_value;
 
 /// Collects className attribute data and builds ClassRegion objects.
 ///
@@ -11,15 +23,177 @@ use super::annotation_parser::ContextOverride;
 /// Port of: the ClassRegion construction logic in src/plugins/jsx/parser.ts
 pub struct ClassExtractor {
     regions: Vec<ClassRegion>,
+    /// Locale of the default ignore reason for bare `a11y-ignore` comments
+    locale: Locale,
+    /// When set, identical regions are folded into the first one (see `with_dedup`)
+    seen: Option<HashMap<DedupKey, usize>>,
+    /// Region the last `record` call landed in
+    last: Option<usize>,
+    /// Line of the last `record` call when it was folded into `last`
+    folded_line: Option<u32>,
+    /// CSS custom properties (`--primary` → value) for `var()` in inline styles
+    css_variables: HashMap<String, String>,
+    /// Region index → its dark-mode context bg, for regions that get a dark twin
+    dark_twins: HashMap<usize, String>,
+    /// Give regions with `md:`-style color classes a twin per breakpoint
+    breakpoint_variants: bool,
+    /// Give regions with `hover:`-style color classes a twin per state
+    state_variants: bool,
+    /// Rules of the file's `<style>` blocks, applied under inline styles
+    stylesheet: Stylesheet,
+}
+
+/// Everything that makes two regions check identically, plus the tag name.
+#[derive(Debug, Hash, PartialEq, Eq)]
+struct DedupKey {
+    tag: String,
+    content: String,
+    context_bg: String,
+    inline_color: Option<String>,
+    inline_background_color: Option<String>,
+    inline_border_color: Option<String>,
+    inline_outline_color: Option<String>,
+    inline_text_decoration_color: Option<String>,
+    context_override_bg: Option<String>,
+    context_override_fg: Option<String>,
+    context_override_no_inherit: Option<bool>,
+    ignored: Option<bool>,
+    ignore_reason: Option<String>,
+    ignored_rules: Option<Vec<String>>,
+    effective_opacity: Option<u64>,
+    required_marker: Option<bool>,
+    role: Option<String>,
+    variant_key: Option<String>,
+    conditional: Option<bool>,
+    group_hover_bg: Option<String>,
+    peer_state: Option<bool>,
+    dark_context_bg: Option<String>,
+}
+
+impl DedupKey {
+    fn new(raw_tag: &str, region: &ClassRegion, dark_context_bg: Option<&str>) -> Self {
+        Self {
+            tag: raw_tag_name(raw_tag).to_string(),
+            content: region.content.clone(),
+            context_bg: region.context_bg.clone(),
+            inline_color: region.inline_color.clone(),
+            inline_background_color: region.inline_background_color.clone(),
+            inline_border_color: region.inline_border_color.clone(),
+            inline_outline_color: region.inline_outline_color.clone(),
+            inline_text_decoration_color: region.inline_text_decoration_color.clone(),
+            context_override_bg: region.context_override_bg.clone(),
+            context_override_fg: region.context_override_fg.clone(),
+            context_override_no_inherit: region.context_override_no_inherit,
+            ignored: region.ignored,
+            ignore_reason: region.ignore_reason.clone(),
+            ignored_rules: region.ignored_rules.clone(),
+            effective_opacity: region.effective_opacity.map(f64::to_bits),
+            required_marker: region.required_marker,
+            role: region.role.clone(),
+            variant_key: region.variant_key.clone(),
+            conditional: region.conditional,
+            group_hover_bg: region.group_hover_bg.clone(),
+            peer_state: region.peer_state,
+            dark_context_bg: dark_context_bg.map(str::to_string),
+        }
+    }
+}
+
+/// What the parser knows about a region before recording it, beyond its
+/// classes and context. Part of the region's dedup identity.
+#[derive(Debug, Default)]
+pub struct RegionTraits {
+    /// The `cva()` variant branch it renders (see `ClassRegion.variant_key`)
+    pub variant_key: Option<String>,
+    /// One branch of a ternary class expression
+    pub conditional: bool,
+    /// Rules its `a11y-ignore` is scoped to (see `ClassRegion.ignored_rules`)
+    pub ignored_rules: Option<Vec<String>>,
+    /// How it relates to its `group` ancestor / `peer` siblings
+    pub group: GroupContext,
+    /// Its background in dark mode, when regions get dark twins (see
+    /// `add_dark_twin`)
+    pub dark_context_bg: Option<String>,
+}
+
+/// `div` for `<div className="...">`.
+fn raw_tag_name(raw_tag: &str) -> &str {
+    raw_tag
+        .strip_prefix('<')
+        .unwrap_or("")
+        .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .next()
+        .unwrap_or("")
+}
+
+impl Default for ClassExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ClassExtractor {
     pub fn new() -> Self {
+        Self::with_locale(Locale::En)
+    }
+
+    pub fn with_locale(locale: Locale) -> Self {
         Self {
             regions: Vec::new(),
+            locale,
+            seen: None,
+            last: None,
+            folded_line: None,
+            css_variables: HashMap::new(),
+            dark_twins: HashMap::new(),
+            breakpoint_variants: false,
+            state_variants: false,
+            stylesheet: Stylesheet::default(),
         }
     }
 
+    /// Resolve `var(--token)` in inline style colors through `variables`
+    /// (name with its leading `--` → CSS value), emitting the resulting hex.
+    pub fn with_css_variables(mut self, variables: HashMap<String, String>) -> Self {
+        self.css_variables = variables;
+        self
+    }
+
+    /// Color elements by the rules of the file's `<style>` blocks, under
+    /// their inline styles.
+    pub fn with_stylesheet(mut self, stylesheet: Stylesheet) -> Self {
+        self.stylesheet = stylesheet;
+        self
+    }
+
+    /// Whether a `<style>` block rule colors elements named `tag`.
+    pub fn styles_element(&self, tag: &str) -> bool {
+        self.stylesheet.styles_element(tag)
+    }
+
+    /// Emit, after each region with breakpoint color classes (`md:bg-white`),
+    /// a twin tagged with each such breakpoint (see `ClassRegion.breakpoint`).
+    pub fn with_breakpoint_variants(mut self, enabled: bool) -> Self {
+        self.breakpoint_variants = enabled;
+        self
+    }
+
+    /// Emit, after each region with interactive-state color classes
+    /// (`hover:bg-gray-100`), a twin tagged with each such state (see
+    /// `ClassRegion.interactive_state`).
+    pub fn with_state_variants(mut self, enabled: bool) -> Self {
+        self.state_variants = enabled;
+        self
+    }
+
+    /// Fold regions with the same (content, context, tag) into the first
+    /// occurrence, counting repeats in `occurrences`. Cuts the pair volume of
+    /// mapped lists and generated repetition before checking.
+    pub fn with_dedup(mut self, dedup: bool) -> Self {
+        self.seen = dedup.then(HashMap::new);
+        self
+    }
+
     /// Record a className attribute event, building a ClassRegion with full context.
     ///
     /// # Arguments
@@ -30,6 +204,7 @@ impl ClassExtractor {
     /// - `context_override`: pending @a11y-context override (consumed)
     /// - `ignore_reason`: pending a11y-ignore reason (consumed)
     /// - `effective_opacity`: US-05 cumulative opacity from ancestors (None = fully opaque)
+    #[allow(clippy::too_many_arguments)]
     pub fn record(
         &mut self,
         content: &str,
@@ -40,7 +215,39 @@ impl ClassExtractor {
         ignore_reason: Option<String>,
         effective_opacity: Option<f32>,
     ) {
-        let inline_styles = extract_inline_style_colors(raw_tag);
+        let traits = RegionTraits::default();
+        self.record_with_traits(
+            content,
+            line,
+            raw_tag,
+            context_bg,
+            context_override,
+            ignore_reason,
+            effective_opacity,
+            traits,
+        );
+    }
+
+    /// `record` a region with `traits`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_with_traits(
+        &mut self,
+        content: &str,
+        line: u32,
+        raw_tag: &str,
+        context_bg: &str,
+        context_override: Option<ContextOverride>,
+        ignore_reason: Option<String>,
+        effective_opacity: Option<f32>,
+        traits: RegionTraits,
+    ) {
+        let mut inline_styles = extract_inline_style_colors(raw_tag).unwrap_or_default();
+        if !raw_tag.is_empty() {
+            inline_styles = inline_styles.or(self.stylesheet.colors_for(raw_tag_name(raw_tag), content));
+        }
+        let inline_value = |value: Option<String>| {
+            value.map(|v| resolve_css_variables(&v, &self.css_variables).unwrap_or(v))
+        };
 
         // Only store opacity if < 1.0 (saves serialization overhead)
         let opacity = effective_opacity.and_then(|o| {
@@ -51,41 +258,217 @@ impl ClassExtractor {
             content: content.to_string(),
             start_line: line,
             context_bg: context_bg.to_string(),
-            inline_color: inline_styles.as_ref().and_then(|s| s.color.clone()),
-            inline_background_color: inline_styles.as_ref().and_then(|s| s.background_color.clone()),
+            inline_color: inline_value(inline_styles.color),
+            inline_background_color: inline_value(inline_styles.background_color),
+            inline_border_color: inline_value(inline_styles.border_color),
+            inline_outline_color: inline_value(inline_styles.outline_color),
+            inline_text_decoration_color: inline_value(inline_styles.text_decoration_color),
             context_override_bg: None,
             context_override_fg: None,
             context_override_no_inherit: None,
             ignored: None,
             ignore_reason: None,
+            ignored_rules: None,
             effective_opacity: opacity,
+            occurrences: None,
+            required_marker: is_marker_content(content).then_some(true),
+            role: static_attr_value(raw_tag, "role").map(str::to_string),
+            variant_key: traits.variant_key,
+            conditional: traits.conditional.then_some(true),
+            unresolved_dynamic_class: None,
+            end_line: None,
+            tag_name: String::new(),
+            ancestry: Vec::new(),
+            group_hover_bg: traits.group.group_hover_bg,
+            peer_state: traits.group.peer_state.then_some(true),
+            theme: None,
+            provenance: None,
+            breakpoint: None,
+            interactive_state: None,
+            class_locations: None,
         };
 
-        // Apply @a11y-context override
-        if let Some(ctx) = context_override {
-            region.context_override_bg = ctx.bg;
-            region.context_override_fg = ctx.fg;
-            if ctx.no_inherit {
-                region.context_override_no_inherit = Some(true);
+        apply_annotations(&mut region, context_override, ignore_reason, self.locale);
+        region.ignored_rules = traits.ignored_rules;
+
+        if let Some(seen) = &mut self.seen {
+            let key = DedupKey::new(raw_tag, &region, traits.dark_context_bg.as_deref());
+            if let Some(&index) = seen.get(&key) {
+                let first = &mut self.regions[index];
+                first.occurrences = Some(first.occurrences.unwrap_or(1) + 1);
+                self.last = Some(index);
+                self.folded_line = Some(line);
+                return;
             }
+            seen.insert(key, self.regions.len());
+            region.occurrences = Some(1);
         }
 
-        // Apply a11y-ignore suppression
-        if let Some(reason) = ignore_reason {
-            region.ignored = Some(true);
-            region.ignore_reason = Some(if reason.is_empty() {
-                "suppressed".to_string()
-            } else {
-                reason
-            });
+        self.last = Some(self.regions.len());
+        self.folded_line = None;
+        self.regions.push(region);
+        if let Some(dark_context_bg) = traits.dark_context_bg {
+            self.add_dark_twin(&dark_context_bg);
+        }
+    }
+
+    /// Stop folding duplicates into region `index`, whose dedup identity
+    /// changed after it was recorded.
+    fn forget(&mut self, index: usize) {
+        if let Some(seen) = &mut self.seen {
+            seen.retain(|_, &mut i| i != index);
         }
+    }
 
+    /// Split the last `record` call back out of the region it was folded
+    /// into, as its own region. Returns the region it now lands in.
+    fn unfold_last(&mut self) -> Option<usize> {
+        let index = self.last?;
+        let Some(line) = self.folded_line.take() else {
+            return Some(index);
+        };
+        let first = &mut self.regions[index];
+        first.occurrences = first.occurrences.map(|n| n - 1);
+        let region = ClassRegion { start_line: line, end_line: None, occurrences: Some(1), ..first.clone() };
+        if let Some(dark_context_bg) = self.dark_twins.get(&index).cloned() {
+            self.dark_twins.insert(self.regions.len(), dark_context_bg);
+        }
+        self.last = Some(self.regions.len());
         self.regions.push(region);
+        self.last
+    }
+
+    /// Flag the last recorded region as a required-field marker (its element's
+    /// text turned out to be `*`).
+    /// A duplicate folded into an unmarked region is split back out.
+    pub fn mark_required_marker(&mut self) {
+        if self.last.is_none_or(|i| self.regions[i].required_marker == Some(true)) {
+            return;
+        }
+        let Some(index) = self.unfold_last() else {
+            return;
+        };
+        self.forget(index);
+        self.regions[index].required_marker = Some(true);
+    }
+
+    /// Apply an annotation trailing an element on its line
+    /// (`<Badge className="…" /> {/* a11y-ignore */}`) to the element's regions.
+    pub fn annotate(
+        &mut self,
+        regions: Range<usize>,
+        context_override: Option<ContextOverride>,
+        ignore_reason: Option<String>,
+        ignore_rules: Option<Vec<String>>,
+    ) {
+        let end = regions.end.min(self.regions.len());
+        for index in regions.start.min(end)..end {
+            let region = &mut self.regions[index];
+            apply_annotations(region, context_override.clone(), ignore_reason.clone(), self.locale);
+            if ignore_reason.is_some() {
+                region.ignored_rules = ignore_rules.clone();
+            }
+            self.forget(index);
+        }
     }
 
-    /// Consume the extractor and return all accumulated ClassRegion objects.
-    pub fn into_regions(self) -> Vec<ClassRegion> {
-        self.regions
+    /// Set the tag or component name of the last recorded region.
+    pub fn set_tag_name(&mut self, tag_name: &str) {
+        if let Some(region) = self.last.and_then(|i| self.regions.get_mut(i)) {
+            region.tag_name = tag_name.to_string();
+        }
+    }
+
+    /// Record how the last region's classes were captured. A duplicate folded
+    /// into an earlier region keeps the first occurrence's.
+    pub fn set_provenance(&mut self, source: ClassSource) {
+        if let Some(region) = self.last.and_then(|i| self.regions.get_mut(i)) {
+            region.provenance.get_or_insert_with(|| source.as_str().to_string());
+        }
+    }
+
+    /// Record where each class of the last region sits in its `cn()` call. A
+    /// duplicate folded into an earlier region keeps the first occurrence's.
+    pub fn set_class_locations(&mut self, locations: Vec<ClassLocation>) {
+        if let Some(region) = self.last.and_then(|i| self.regions.get_mut(i)) {
+            region.class_locations.get_or_insert(locations);
+        }
+    }
+
+    /// Set the enclosing tag path of the last recorded region. A duplicate
+    /// folded into an earlier region keeps the first occurrence's path.
+    pub fn set_ancestry(&mut self, ancestry: Vec<String>) {
+        if let Some(region) = self.last.and_then(|i| self.regions.get_mut(i)) {
+            if region.ancestry.is_empty() {
+                region.ancestry = ancestry;
+            }
+        }
+    }
+
+    /// Record where the last region's class value ends when it wraps past
+    /// `line`. A duplicate folded into a region from another line keeps that
+    /// region's range.
+    pub fn set_end_line(&mut self, line: u32, end_line: u32) {
+        if let Some(region) = self.last.and_then(|i| self.regions.get_mut(i)) {
+            if region.start_line == line {
+                region.end_line = Some(end_line);
+            }
+        }
+    }
+
+    /// Flag the regions recorded since `since` (and the last one, which a
+    /// folded duplicate lands in) as having classes that can't be statically known.
+    pub fn mark_unresolved_dynamic_class(&mut self, since: usize) {
+        let last = self.last.filter(|&i| i < since);
+        for i in (since..self.regions.len()).chain(last) {
+            self.regions[i].unresolved_dynamic_class = Some(true);
+        }
+    }
+
+    /// Give the last recorded region a dark-mode twin on `dark_context_bg`,
+    /// if it has `dark:` classes or that bg differs from its light one.
+    fn add_dark_twin(&mut self, dark_context_bg: &str) {
+        let Some(index) = self.last else {
+            return;
+        };
+        let region = &self.regions[index];
+        let has_dark_classes = region_classes(&region.content)
+            .iter()
+            .any(|class| class.rsplit_once(':').is_some_and(|(variants, _)| variants.split(':').any(|v| v == "dark")));
+        if has_dark_classes || region.context_bg != dark_context_bg {
+            self.dark_twins.insert(index, dark_context_bg.to_string());
+        }
+    }
+
+    /// Consume the extractor and return all accumulated ClassRegion objects,
+    /// each dark twin and breakpoint twin right after the region it twins.
+    pub fn into_regions(mut self) -> Vec<ClassRegion> {
+        if self.dark_twins.is_empty() && !self.breakpoint_variants && !self.state_variants {
+            return self.regions;
+        }
+        let mut regions = Vec::with_capacity(self.regions.len() + self.dark_twins.len());
+        for (index, mut region) in self.regions.into_iter().enumerate() {
+            let dark_twin = self.dark_twins.remove(&index).map(|dark_context_bg| {
+                region.theme = Some("light".to_string());
+                ClassRegion { context_bg: dark_context_bg, theme: Some("dark".to_string()), ..region.clone() }
+            });
+            let classes = region_classes(&region.content);
+            let breakpoints = if self.breakpoint_variants { color_breakpoints(&classes) } else { Vec::new() };
+            let states = if self.state_variants { color_states(&classes) } else { Vec::new() };
+            let breakpoint_twins: Vec<ClassRegion> = breakpoints
+                .into_iter()
+                .map(|bp| ClassRegion { breakpoint: Some(bp.to_string()), ..region.clone() })
+                .collect();
+            let state_twins: Vec<ClassRegion> = states
+                .into_iter()
+                .map(|state| ClassRegion { interactive_state: Some(state), ..region.clone() })
+                .collect();
+            regions.push(region);
+            regions.extend(dark_twin);
+            regions.extend(breakpoint_twins);
+            regions.extend(state_twins);
+        }
+        regions
     }
 
     /// Get a reference to the accumulated regions (for testing/inspection).
@@ -94,20 +477,105 @@ impl ClassExtractor {
     }
 }
 
+/// Apply an @a11y-context override and an a11y-ignore suppression to `region`.
+fn apply_annotations(
+    region: &mut ClassRegion,
+    context_override: Option<ContextOverride>,
+    ignore_reason: Option<String>,
+    locale: Locale,
+) {
+    if let Some(ctx) = context_override {
+        region.context_override_bg = ctx.bg;
+        region.context_override_fg = ctx.fg;
+        if ctx.no_inherit {
+            region.context_override_no_inherit = Some(true);
+        }
+    }
+    if let Some(reason) = ignore_reason {
+        region.ignored = Some(true);
+        region.ignore_reason = Some(if reason.is_empty() {
+            i18n::message(locale, MessageKey::IgnoreSuppressed).to_string()
+        } else {
+            reason
+        });
+    }
+}
+
 /// Inline style colors extracted from a JSX tag.
-struct InlineStyleColors {
-    color: Option<String>,
-    background_color: Option<String>,
+#[derive(Default, Clone)]
+pub(super) struct InlineStyleColors {
+    pub(super) color: Option<String>,
+    pub(super) background_color: Option<String>,
+    pub(super) border_color: Option<String>,
+    outline_color: Option<String>,
+    text_decoration_color: Option<String>,
+}
+
+impl InlineStyleColors {
+    /// Style object keys and CSS property names of each field, in field order.
+    const PROPERTIES: [(&'static str, &'static str); 5] = [
+        ("color", "color"),
+        ("backgroundColor", "background-color"),
+        ("borderColor", "border-color"),
+        ("outlineColor", "outline-color"),
+        ("textDecorationColor", "text-decoration-color"),
+    ];
+
+    fn fields(&mut self) -> [&mut Option<String>; 5] {
+        [
+            &mut self.color,
+            &mut self.background_color,
+            &mut self.border_color,
+            &mut self.outline_color,
+            &mut self.text_decoration_color,
+        ]
+    }
+
+    /// Field set by the (lowercase) CSS property `name`.
+    fn css_field(&mut self, name: &str) -> Option<&mut Option<String>> {
+        let index = Self::PROPERTIES.iter().position(|(_, property)| name == *property)?;
+        self.fields().into_iter().nth(index)
+    }
+
+    /// None when no property was found.
+    fn non_empty(mut self) -> Option<Self> {
+        self.fields().iter().any(|field| field.is_some()).then_some(self)
+    }
+
+    /// Each property of `self`, falling back to `other`'s.
+    pub(super) fn or(mut self, mut other: Self) -> Self {
+        for (field, fallback) in self.fields().into_iter().zip(other.fields()) {
+            if field.is_none() {
+                *field = fallback.take();
+            }
+        }
+        self
+    }
 }
 
-/// Extract inline style color/backgroundColor from a raw JSX tag string.
+/// Extract inline style colors (text, background, border, outline and text
+/// decoration) from a raw JSX tag string.
 ///
-/// Looks for `style={{ color: "...", backgroundColor: "..." }}` patterns.
+/// Looks for `style={{ color: "...", backgroundColor: "..." }}` patterns, and
+/// for CSS strings (`style="color: #333; background-color: #fff"`) as written
+/// in HTML, Vue and Astro markup. Emotion `css` props fill in the properties
+/// `style` leaves unset (`style` wins, as it does in the browser).
 ///
 /// Port of: src/plugins/jsx/parser.ts → extractInlineStyleColors()
 fn extract_inline_style_colors(raw_tag: &str) -> Option<InlineStyleColors> {
+    let style = extract_style_prop_colors(raw_tag);
+    match (style, extract_css_prop_colors(raw_tag)) {
+        (Some(style), Some(css)) => Some(style.or(css)),
+        (style, css) => style.or(css),
+    }
+}
+
+/// Colors of the `style` prop, as an object or a CSS string.
+fn extract_style_prop_colors(raw_tag: &str) -> Option<InlineStyleColors> {
     // Find style={{ ... }} pattern
-    let style_start = raw_tag.find("style={{")?;
+    let Some(style_start) = raw_tag.find("style={{") else {
+        return extract_css_string_colors(raw_tag);
+    };
     let body_start = style_start + "style={{".len();
 
     // Find matching closing }}
@@ -129,19 +597,161 @@ fn extract_inline_style_colors(raw_tag: &str) -> Option<InlineStyleColors> {
         return None;
     }
 
-    let style_body = &raw_tag[body_start..i];
+    style_object_colors(&raw_tag[body_start..i])
+}
+
+/// Color string values (`color`, `backgroundColor`, `borderColor`, ...) of a
+/// style object body.
+fn style_object_colors(style_body: &str) -> Option<InlineStyleColors> {
+    let mut colors = InlineStyleColors::default();
+    for ((key, _), field) in InlineStyleColors::PROPERTIES.iter().zip(colors.fields()) {
+        *field = extract_style_property(style_body, key);
+    }
+    colors.non_empty()
+}
+
+/// Colors of an Emotion `css` prop: an object (`css={{ color: '#fff' }}`) or
+/// a CSS template (`` css={css`color: red`} ``, `` css={`...`} ``). Nested
+/// rules (`&:hover { ... }`) and `${...}` interpolations are ignored.
+fn extract_css_prop_colors(raw_tag: &str) -> Option<InlineStyleColors> {
+    let bytes = raw_tag.as_bytes();
+    let mut from = 0;
+    let value_start = loop {
+        let pos = from + raw_tag[from..].find("css={")?;
+        from = pos + "css={".len();
+        if pos > 0 && bytes[pos - 1].is_ascii_whitespace() {
+            break from;
+        }
+    };
+    let lead = raw_tag[value_start..].len() - raw_tag[value_start..].trim_start().len();
+    let value = &raw_tag[value_start + lead..];
+    if value.starts_with('{') {
+        let close = find_closing_bracket(bytes, value_start + lead)?;
+        return style_object_colors(&raw_tag[value_start + lead + 1..close]);
+    }
+    let template = value.strip_prefix("css").unwrap_or(value).strip_prefix('`')?;
+    let end = template.find('`')?;
+    css_declaration_colors(&top_level_declarations(&strip_template_expressions(&template[..end])))
+}
 
-    let color = extract_style_property(style_body, "color");
-    let background_color = extract_style_property(style_body, "backgroundColor");
+/// `css` with each nested rule block replaced by a `;`, so only the
+/// element's own declarations remain.
+fn top_level_declarations(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut depth = 0usize;
+    for ch in css.chars() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    out.push(';');
+                }
+            }
+            _ if depth == 0 => out.push(ch),
+            _ => {}
+        }
+    }
+    out
+}
 
-    if color.is_none() && background_color.is_none() {
+/// Colors of a CSS-string `style` attribute: `style="..."` as written in
+/// HTML, Vue and Astro markup, or a string expression (`style={"..."}`,
+/// `` style={`color: ${fg}`} ``) as JSX passes prebuilt style strings.
+fn extract_css_string_colors(raw_tag: &str) -> Option<InlineStyleColors> {
+    let bytes = raw_tag.as_bytes();
+    let mut from = 0;
+    let body = loop {
+        let pos = from + raw_tag[from..].find("style=")?;
+        from = pos + "style=".len();
+        if pos == 0 || !bytes[pos - 1].is_ascii_whitespace() {
+            continue;
+        }
+        let braced = bytes.get(from) == Some(&b'{');
+        let start = if braced {
+            from + 1 + raw_tag[from + 1..].len() - raw_tag[from + 1..].trim_start().len()
+        } else {
+            from
+        };
+        let quote = bytes
+            .get(start)
+            .copied()
+            .filter(|q| matches!(q, b'"' | b'\'') || (braced && *q == b'`'));
+        if let Some(quote) = quote {
+            let end = raw_tag[start + 1..].find(quote as char)? + start + 1;
+            let literal = &raw_tag[start + 1..end];
+            break if quote == b'`' { strip_template_expressions(literal) } else { literal.to_string() };
+        }
+    };
+    css_declaration_colors(&body)
+}
+
+/// `color`, `background-color`, `border-color`, ... of CSS declarations.
+/// `background` counts only when its value is a single color.
+pub(super) fn css_declaration_colors(body: &str) -> Option<InlineStyleColors> {
+    let mut colors = InlineStyleColors::default();
+    for declaration in body.split(';') {
+        let Some((name, value)) = declaration.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().trim_end_matches("!important").trim();
+        if value.is_empty() {
+            continue;
+        }
+        let name = name.trim().to_ascii_lowercase();
+        if name == "background" && is_single_color(value) {
+            colors.background_color = Some(value.to_string());
+        } else if let Some(field) = colors.css_field(&name) {
+            *field = Some(value.to_string());
+        }
+    }
+    colors.non_empty()
+}
+
+/// Custom properties may reference each other; deeper chains are treated as cycles.
+const MAX_VAR_DEPTH: u8 = 8;
+
+/// Hex of an inline style value containing `var(--token)` references, looked
+/// up in `variables` (falling back to `var(--token, fallback)`). None when
+/// there are no references, a token is undefined or the result isn't a
+/// color: the raw value is kept and the region stays unresolved.
+fn resolve_css_variables(value: &str, variables: &HashMap<String, String>) -> Option<String> {
+    if !value.contains("var(") {
+        return None;
+    }
+    to_hex(&substitute_vars(value, variables, MAX_VAR_DEPTH)?)
+}
+
+/// `value` with every `var()` reference replaced by its (recursively
+/// substituted) value.
+fn substitute_vars(value: &str, variables: &HashMap<String, String>, depth: u8) -> Option<String> {
+    let Some(start) = value.find("var(") else {
+        return Some(value.to_string());
+    };
+    if depth == 0 {
         return None;
     }
+    let close = find_closing_bracket(value.as_bytes(), start + "var".len())?;
+    let inner = &value[start + "var(".len()..close];
+    let (name, fallback) = match inner.split_once(',') {
+        Some((name, fallback)) => (name.trim(), Some(fallback.trim())),
+        None => (inner.trim(), None),
+    };
+    let replacement = variables.get(name).map(String::as_str).or(fallback)?;
+    Some(format!(
+        "{}{}{}",
+        &value[..start],
+        substitute_vars(replacement, variables, depth - 1)?,
+        substitute_vars(&value[close + 1..], variables, depth)?
+    ))
+}
 
-    Some(InlineStyleColors {
-        color,
-        background_color,
-    })
+/// `#fff`, `rgb(0 0 0 / 50%)`, `white`: one color, no images/positions.
+fn is_single_color(value: &str) -> bool {
+    let is_function = ["rgb", "hsl", "oklch", "oklab", "lab", "lch", "color("]
+        .iter()
+        .any(|f| value.starts_with(f));
+    (is_function && value.ends_with(')')) || !value.contains([' ', '(', ','])
 }
 
 /// Extract a string value for a CSS property from a style object body.
@@ -216,6 +826,99 @@ mod tests {
         assert_eq!(regions[0].context_bg, "bg-background");
     }
 
+    #[test]
+    fn dedup_folds_identical_regions_per_tag() {
+        let mut ext = make_extractor().with_dedup(true);
+        for line in 1..=3 {
+            ext.record("text-white", line, "<li className=\"text-white\">", "bg-card", None, None, None);
+        }
+        ext.record("text-white", 4, "<span>", "bg-card", None, None, None);
+        ext.record("text-white", 5, "<li>", "bg-muted", None, None, None);
+        ext.record("text-white", 6, "<li role=\"heading\">", "bg-card", None, None, None);
+        let regions = ext.into_regions();
+        assert_eq!(regions.len(), 4);
+        assert_eq!(regions[3].role.as_deref(), Some("heading"));
+        assert_eq!(regions[0].start_line, 1);
+        assert_eq!(regions[0].occurrences, Some(3));
+        assert_eq!(regions[1].occurrences, Some(1));
+    }
+
+    #[test]
+    fn dedup_keeps_variant_branches_apart() {
+        let mut ext = make_extractor().with_dedup(true);
+        for (line, key) in [(1, "intent=primary"), (2, "intent=primary"), (3, "intent=ghost")] {
+            let traits = RegionTraits { variant_key: Some(key.to_string()), ..Default::default() };
+            ext.record_with_traits("text-white", line, "", "bg-card", None, None, None, traits);
+        }
+        let regions = ext.into_regions();
+        let keys: Vec<(Option<&str>, Option<u32>)> =
+            regions.iter().map(|r| (r.variant_key.as_deref(), r.occurrences)).collect();
+        assert_eq!(keys, [(Some("intent=primary"), Some(2)), (Some("intent=ghost"), Some(1))]);
+    }
+
+    #[test]
+    fn dedup_keeps_scoped_ignores_and_branches_apart() {
+        let mut ext = make_extractor().with_dedup(true);
+        let ignored = |rules: &[&str]| RegionTraits {
+            ignored_rules: Some(rules.iter().map(|r| r.to_string()).collect()),
+            ..Default::default()
+        };
+        let reason = || Some("legacy".to_string());
+        ext.record_with_traits("text-white", 1, "<li>", "bg-card", None, reason(), None, ignored(&["AA-TEXT-CONTRAST"]));
+        ext.record_with_traits("text-white", 2, "<li>", "bg-card", None, reason(), None, ignored(&["FOCUS-VISIBLE"]));
+        ext.record("text-white", 3, "<li>", "bg-card", None, reason(), None);
+        let branch = RegionTraits { conditional: true, ..Default::default() };
+        ext.record_with_traits("text-white", 4, "<li>", "bg-card", None, None, None, branch);
+        ext.record("text-white", 5, "<li>", "bg-card", None, None, None);
+        assert_eq!(ext.into_regions().len(), 5);
+    }
+
+    #[test]
+    fn dedup_keeps_group_and_peer_contexts_apart() {
+        let mut ext = make_extractor().with_dedup(true);
+        let hovered = |bg: &str| RegionTraits {
+            group: GroupContext { group_hover_bg: Some(bg.to_string()), peer_state: false },
+            ..Default::default()
+        };
+        let content = "text-gray-900 group-hover:text-white peer-hover:text-white";
+        ext.record_with_traits(content, 1, "<p>", "bg-card", None, None, None, hovered("bg-primary"));
+        ext.record_with_traits(content, 2, "<p>", "bg-card", None, None, None, hovered("bg-yellow-200"));
+        ext.record_with_traits(content, 3, "<p>", "bg-card", None, None, None, hovered("bg-primary"));
+        let peer = RegionTraits { group: GroupContext { peer_state: true, ..Default::default() }, ..Default::default() };
+        ext.record_with_traits(content, 4, "<p>", "bg-card", None, None, None, peer);
+        let regions = ext.into_regions();
+        let found: Vec<(Option<&str>, Option<bool>, Option<u32>)> =
+            regions.iter().map(|r| (r.group_hover_bg.as_deref(), r.peer_state, r.occurrences)).collect();
+        assert_eq!(
+            found,
+            [(Some("bg-primary"), None, Some(2)), (Some("bg-yellow-200"), None, Some(1)), (None, Some(true), Some(1))]
+        );
+    }
+
+    #[test]
+    fn marker_folded_into_unmarked_region_is_split_out() {
+        let mut ext = make_extractor().with_dedup(true);
+        for line in 1..=2 {
+            ext.record("text-red-500", line, "<span>", "bg-card", None, None, None);
+        }
+        ext.mark_required_marker();
+        ext.record("text-red-500", 3, "<span>", "bg-card", None, None, None);
+        let regions = ext.into_regions();
+        let lines: Vec<(u32, Option<bool>, Option<u32>)> =
+            regions.iter().map(|r| (r.start_line, r.required_marker, r.occurrences)).collect();
+        assert_eq!(lines, [(1, None, Some(2)), (2, Some(true), Some(1))]);
+    }
+
+    #[test]
+    fn no_dedup_by_default() {
+        let mut ext = make_extractor();
+        ext.record("text-white", 1, "<li>", "bg-card", None, None, None);
+        ext.record("text-white", 2, "<li>", "bg-card", None, None, None);
+        let regions = ext.into_regions();
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].occurrences, None);
+    }
+
     #[test]
     fn record_with_context_bg() {
         let mut ext = make_extractor();
@@ -344,6 +1047,56 @@ mod tests {
         assert_eq!(regions[0].inline_background_color, None);
     }
 
+    #[test]
+    fn css_variables_resolved_to_hex() {
+        let variables: HashMap<String, String> = [
+            ("--primary", "#2563EB"),
+            ("--brand", "var(--primary)"),
+            ("--muted", "215 16% 47%"),
+            ("--loop", "var(--loop)"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+        let mut ext = make_extractor().with_css_variables(variables);
+        let tags = [
+            r#"<p style={{ color: "var(--brand)", backgroundColor: 'hsl(var(--muted))' }}>"#,
+            r#"<p style="color: var(--unknown, white); background-color: var(--unknown)">"#,
+            r#"<p style={{ color: "var(--loop)" }}>"#,
+        ];
+        for (line, tag) in tags.iter().enumerate() {
+            ext.record("text-sm", line as u32 + 1, tag, "bg-background", None, None, None);
+        }
+        let regions = ext.into_regions();
+        assert_eq!(regions[0].inline_color.as_deref(), Some("#2563eb"));
+        assert_eq!(regions[0].inline_background_color.as_deref(), Some("#65758b"));
+        assert_eq!(regions[1].inline_color.as_deref(), Some("#ffffff"));
+        assert_eq!(regions[1].inline_background_color.as_deref(), Some("var(--unknown)"));
+        assert_eq!(regions[2].inline_color.as_deref(), Some("var(--loop)"));
+    }
+
+    #[test]
+    fn non_text_inline_colors() {
+        let mut ext = make_extractor();
+        let tags = [
+            r##"<input style={{ borderColor: "#d4d4d8", outlineColor: '#2563eb', color: "#111" }}>"##,
+            r##"<a style="text-decoration-color: #f00; border-color: var(--x, #fff)"
+                css={{ outlineColor: "red" }}>"##,
+        ];
+        for (line, tag) in tags.iter().enumerate() {
+            ext.record("text-sm", line as u32 + 1, tag, "bg-background", None, None, None);
+        }
+        let regions = ext.into_regions();
+        assert_eq!(regions[0].inline_border_color.as_deref(), Some("#d4d4d8"));
+        assert_eq!(regions[0].inline_outline_color.as_deref(), Some("#2563eb"));
+        assert_eq!(regions[0].inline_color.as_deref(), Some("#111"));
+        assert_eq!(regions[0].inline_text_decoration_color, None);
+        assert_eq!(regions[1].inline_text_decoration_color.as_deref(), Some("#f00"));
+        assert_eq!(regions[1].inline_border_color.as_deref(), Some("#ffffff"));
+        assert_eq!(regions[1].inline_outline_color.as_deref(), Some("red"));
+        assert_eq!(regions[1].inline_color, None);
+    }
+
     // ── extract_inline_style_colors unit tests ──
 
     #[test]
@@ -382,8 +1135,54 @@ mod tests {
         assert_eq!(result.background_color, Some("#000".to_string()));
     }
 
+    #[test]
+    fn emotion_css_prop_colors() {
+        let c = extract_inline_style_colors(r#"<div css={{ color: '#fff', backgroundColor: 'var(--bg)' }}>"#).unwrap();
+        assert_eq!(c.color.as_deref(), Some("#fff"));
+        assert_eq!(c.background_color.as_deref(), Some("var(--bg)"));
+
+        let tagged = "<div css={css`\n  color: red;\n  &:hover { color: blue; }\n  background: #000;\n`}>";
+        let c = extract_inline_style_colors(tagged).unwrap();
+        assert_eq!(c.color.as_deref(), Some("red"));
+        assert_eq!(c.background_color.as_deref(), Some("#000"));
+
+        let c = extract_inline_style_colors("<p css={`color: ${theme.fg}; background-color: white`}>").unwrap();
+        assert_eq!((c.color, c.background_color.as_deref()), (None, Some("white")));
+        assert!(extract_inline_style_colors(r#"<p data-css={{ color: "red" }}>"#).is_none());
+    }
+
+    #[test]
+    fn style_prop_wins_over_css_prop() {
+        let tag = r##"<div style={{ color: "#111" }} css={{ color: '#222', backgroundColor: '#eee' }}>"##;
+        let c = extract_inline_style_colors(tag).unwrap();
+        assert_eq!(c.color.as_deref(), Some("#111"));
+        assert_eq!(c.background_color.as_deref(), Some("#eee"));
+    }
+
     // ── extract_style_property unit tests ──
 
+    #[test]
+    fn css_string_style_colors() {
+        let c = extract_inline_style_colors(r#"<td style="color: #333333; background-color:#FFF !important">"#).unwrap();
+        assert_eq!(c.color.as_deref(), Some("#333333"));
+        assert_eq!(c.background_color.as_deref(), Some("#FFF"));
+        let c = extract_inline_style_colors(r#"<p style='background: rgb(0 0 0 / 50%)'>"#).unwrap();
+        assert_eq!(c.background_color.as_deref(), Some("rgb(0 0 0 / 50%)"));
+        assert!(extract_inline_style_colors(r#"<p style="background: url(x.png) no-repeat">"#).is_none());
+        assert!(extract_inline_style_colors(r#"<p data-style="color: red">"#).is_none());
+    }
+
+    #[test]
+    fn style_string_expressions() {
+        let c = extract_inline_style_colors(r#"<p style={"color: #111; background-color: #fafafa"}>"#).unwrap();
+        assert_eq!(c.color.as_deref(), Some("#111"));
+        assert_eq!(c.background_color.as_deref(), Some("#fafafa"));
+        let c = extract_inline_style_colors("<p style={ `color: ${fg}; background: white` }>").unwrap();
+        assert_eq!((c.color, c.background_color.as_deref()), (None, Some("white")));
+        assert!(extract_inline_style_colors("<p style={styles.card}>").is_none());
+        assert!(extract_inline_style_colors("<p style=`color: red`>").is_none());
+    }
+
     #[test]
     fn property_with_spaces() {
         assert_eq!(
@@ -0,0 +1,292 @@
+//! Constants a class attribute can name: strings (`const headerClasses =
+//! "..."`, used as `className={headerClasses}`) and variant maps (`const
+//! variants = { primary: "..." }`, used as `className={variants[variant]}`),
+//! declared at the top level of the scanned file or imported from a library
+//! file (`ExtractOptions.library_files`, e.g. a shared `styles.ts`).
+//!
+//! Import specifiers match library files by path suffix, like CSS Modules:
+//! `./styles` and `@/lib/styles` both match `src/lib/styles.ts`, and a
+//! directory matches its `index` file. Re-exports are not followed.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::markup::constant_at;
+use super::tokenizer::{
+    find_closing_bracket, find_top_level_colon, find_unescaped, split_object_entries, strip_template_expressions,
+};
+use crate::types::FileInput;
+
+/// Source file extensions dropped from library paths and specifiers.
+const SOURCE_EXTENSIONS: [&str; 6] = [".tsx", ".ts", ".jsx", ".js", ".mjs", ".cjs"];
+
+/// Constants a class attribute can name, by binding (`ns.name` for
+/// namespace imports). A later declaration of the same name wins.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FileConstants {
+    /// `const headerClasses = "..."`, for `className={headerClasses}`
+    pub(super) strings: HashMap<String, String>,
+    /// `const variants = { primary: "...", ghost: "..." }`, for
+    /// `className={variants[variant]}`: (key, classes) in source order
+    pub(super) maps: HashMap<String, Vec<(String, String)>>,
+}
+
+pub(super) enum MapKey<'a> {
+    /// `variants.primary`, `variants["primary"]`
+    Literal(&'a str),
+    /// `variants[variant]`, `variants[props.size]`: the variant's name
+    Expr(&'a str),
+}
+
+/// Map name and key of a class attribute expression indexing an object; the
+/// map may be a namespace member (`styles.variants[variant]`).
+pub(super) fn map_access(expr: &str) -> Option<(&str, MapKey<'_>)> {
+    let is_name = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'$'));
+    let split = expr.find('[').or_else(|| expr.rfind('.'))?;
+    let map = &expr[..split];
+    if !map.split('.').all(is_name) {
+        return None;
+    }
+    if let Some(key) = expr[split..].strip_prefix('.') {
+        return is_name(key).then_some((map, MapKey::Literal(key)));
+    }
+    let index = expr[split..].strip_prefix('[')?.strip_suffix(']')?.trim();
+    let quoted = index.len() >= 2 && matches!(index.as_bytes()[0], b'"' | b'\'') && index.ends_with(&index[..1]);
+    if quoted {
+        return Some((map, MapKey::Literal(&index[1..index.len() - 1])));
+    }
+    // `props.size` names the variant "size"; other expressions the map
+    let is_path = index.split('.').all(is_name);
+    let variant = if is_path { index } else { map };
+    let variant = variant.rsplit('.').next().unwrap_or(variant);
+    Some((map, MapKey::Expr(variant)))
+}
+
+/// Name and entries of the `const name = { key: "...", ... }` object declared
+/// at `pos` whose values are string literals (others are skipped); None if
+/// none are.
+fn variant_map_at(source: &str, pos: usize) -> Option<(&str, Vec<(String, String)>)> {
+    let rest = source[pos..].strip_prefix("const ")?;
+    let name_len = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+        .unwrap_or(rest.len());
+    let name = &rest[..name_len];
+    // An optional type annotation: `const variants: Record<Variant, string> = {`
+    let after = &rest[name_len..];
+    let eq = after.find('=')?;
+    let annotation = after[..eq].trim();
+    if !(annotation.is_empty() || annotation.starts_with(':') && !annotation.contains([';', '{', '\n'])) {
+        return None;
+    }
+    let value = after[eq + 1..].trim_start();
+    if name.is_empty() || !value.starts_with('{') {
+        return None;
+    }
+    let open = source.len() - value.len();
+    let close = find_closing_bracket(source.as_bytes(), open)?;
+    let entries: Vec<(String, String)> = split_object_entries(source, open, close + 1)
+        .into_iter()
+        .filter_map(|(_, entry)| {
+            let colon = find_top_level_colon(entry)?;
+            let key = entry[..colon].trim().trim_matches(|c| matches!(c, '"' | '\''));
+            let value = entry[colon + 1..].trim();
+            let quote = value.bytes().next().filter(|b| matches!(b, b'"' | b'\'' | b'`'))?;
+            let end = find_unescaped(value.as_bytes(), quote, 1).filter(|&end| end == value.len() - 1)?;
+            let literal = &value[1..end];
+            let classes = if quote == b'`' { strip_template_expressions(literal) } else { literal.to_string() };
+            Some((key.to_string(), classes))
+        })
+        .collect();
+    (!entries.is_empty()).then_some((name, entries))
+}
+
+/// The string and variant map constants `source` declares outside any block.
+pub(super) fn top_level_constants(source: &str) -> FileConstants {
+    let bytes = source.as_bytes();
+    let mut constants = FileConstants::default();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' => depth = depth.saturating_sub(1),
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = source[i..].find('\n').map_or(bytes.len(), |p| i + p);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = source[i + 2..].find("*/").map_or(bytes.len(), |p| i + 2 + p + 1);
+            }
+            // Skip string literals; a quote in JSX text (`don't`) has no closing
+            // quote on its line and is left alone
+            quote @ (b'"' | b'\'' | b'`') => {
+                let end = find_unescaped(bytes, quote, i + 1);
+                if let Some(end) = end.filter(|&end| quote == b'`' || !source[i..end].contains('\n')) {
+                    i = end;
+                }
+            }
+            b'c' if depth == 0 && (i == 0 || !(bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_')) => {
+                if let Some((name, value)) = constant_at(source, i) {
+                    constants.strings.insert(name.to_string(), value);
+                } else if let Some((name, entries)) = variant_map_at(source, i) {
+                    constants.maps.insert(name.to_string(), entries);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    constants
+}
+
+/// Constants of the library files, by module path (see `module_path`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Library {
+    modules: HashMap<String, FileConstants>,
+}
+
+impl Library {
+    pub fn from_files(files: &[FileInput]) -> Self {
+        let modules = files.iter().map(|f| (module_path(&f.path), top_level_constants(&f.content))).collect();
+        Self { modules }
+    }
+
+    /// The constants of `source`: its own, plus those it imports from the
+    /// library (`import { headerClasses } from "./styles"`, `import * as s`).
+    pub(super) fn constants_of(&self, source: &str) -> FileConstants {
+        let mut constants = top_level_constants(source);
+        if self.modules.is_empty() {
+            return constants;
+        }
+        for import in imports(source) {
+            let Some(module) = self.module(import.specifier) else {
+                continue;
+            };
+            match import.imported {
+                None => {
+                    for (name, value) in &module.strings {
+                        constants.strings.insert(format!("{}.{}", import.local, name), value.clone());
+                    }
+                    for (name, entries) in &module.maps {
+                        constants.maps.insert(format!("{}.{}", import.local, name), entries.clone());
+                    }
+                }
+                Some(name) => {
+                    if let Some(value) = module.strings.get(name) {
+                        constants.strings.insert(import.local.to_string(), value.clone());
+                    } else if let Some(entries) = module.maps.get(name) {
+                        constants.maps.insert(import.local.to_string(), entries.clone());
+                    }
+                }
+            }
+        }
+        constants
+    }
+
+    /// The module `specifier` imports: an exact path match wins, then the
+    /// longest matching module path.
+    fn module(&self, specifier: &str) -> Option<&FileConstants> {
+        let path = module_path(specifier);
+        let matches = |module: &str| {
+            module == path || module.ends_with(&format!("/{}", path)) || path.ends_with(&format!("/{}", module))
+        };
+        self.modules
+            .iter()
+            .filter(|(module, _)| matches(module))
+            .max_by_key(|(module, _)| (**module == path, module.len(), module.as_str()))
+            .map(|(_, constants)| constants)
+    }
+}
+
+/// `path` without leading `.` / `..` / alias (`@`, `~`) segments, source
+/// extension and trailing `/index`.
+fn module_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let segments: Vec<&str> = path.split('/').skip_while(|s| matches!(*s, "" | "." | ".." | "@" | "~")).collect();
+    let path = segments.join("/");
+    let path = SOURCE_EXTENSIONS.iter().find_map(|ext| path.strip_suffix(ext)).unwrap_or(&path);
+    path.strip_suffix("/index").unwrap_or(path).to_string()
+}
+
+struct Import<'a> {
+    /// Imported name; None for a namespace import (`* as local`)
+    imported: Option<&'a str>,
+    local: &'a str,
+    specifier: &'a str,
+}
+
+/// Named and namespace imports of `source` (`import { a, b as c } from`,
+/// `import * as ns from`); type-only imports are skipped.
+fn imports(source: &str) -> Vec<Import<'_>> {
+    let mut out = Vec::new();
+    for (start, _) in source.match_indices("import ") {
+        // Import declarations start a statement
+        let line_before = source[..start].rsplit('\n').next().unwrap_or_default().trim_end();
+        if !line_before.is_empty() && !line_before.ends_with(';') {
+            continue;
+        }
+        let rest = &source[start + "import ".len()..];
+        // `from` after the braces: a name like `fromStyles` is inside them
+        let brace = rest.find('{').filter(|&open| rest.find("from").is_some_and(|from| open < from));
+        let clause_end = brace.and_then(|open| rest[open..].find('}').map(|close| open + close + 1)).unwrap_or(0);
+        let Some(from) = rest[clause_end..].find("from").map(|p| clause_end + p) else {
+            continue;
+        };
+        let clause = rest[..from].trim();
+        let after = rest[from + "from".len()..].trim_start();
+        let Some(quote) = after.chars().next().filter(|c| matches!(c, '"' | '\'')) else {
+            continue;
+        };
+        let Some(end) = after[1..].find(quote) else {
+            continue;
+        };
+        let specifier = &after[1..1 + end];
+        if clause.starts_with("type ") {
+            continue;
+        }
+        if let Some(local) = clause.strip_prefix('*').and_then(|c| c.trim_start().strip_prefix("as ")) {
+            out.push(Import { imported: None, local: local.trim(), specifier });
+            continue;
+        }
+        let Some(names) = clause.split_once('{').and_then(|(_, names)| names.strip_suffix('}')) else {
+            continue;
+        };
+        for name in names.split(',').map(str::trim).filter(|n| !n.is_empty() && !n.starts_with("type ")) {
+            let (imported, local) = name.split_once(" as ").map_or((name, name), |(i, l)| (i.trim(), l.trim()));
+            out.push(Import { imported: Some(imported), local, specifier });
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_imports_against_the_library() {
+        let library = Library::from_files(&[
+            FileInput {
+                path: "src/lib/styles.ts".to_string(),
+                content: r#"export const header = "bg-slate-900 text-white";
+export const buttons = { primary: "bg-blue-600 text-white", ghost: "text-blue-600" } as const;"#
+                    .to_string(),
+            },
+            FileInput {
+                path: "src/ui/index.ts".to_string(),
+                content: r#"export const muted = 'text-gray-500';"#.to_string(),
+            },
+        ]);
+        let source = r#"import { header as headerClasses, buttons, fromNowhere } from "@/lib/styles";
+import * as ui from "../ui";
+import type { Variant } from "./types";
+const local = "p-4";"#;
+        let constants = library.constants_of(source);
+        assert_eq!(constants.strings["headerClasses"], "bg-slate-900 text-white");
+        assert_eq!(constants.strings["ui.muted"], "text-gray-500");
+        assert_eq!(constants.strings["local"], "p-4");
+        assert_eq!(constants.maps["buttons"][1], ("ghost".to_string(), "text-blue-600".to_string()));
+        assert!(!constants.strings.contains_key("fromNowhere"));
+        assert_eq!(constants.strings.len(), 3);
+    }
+}
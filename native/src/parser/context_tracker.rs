@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::types::AncestorContext;
+
 use super::visitor::JsxVisitor;
 
 /// BG utility classes that are NOT color classes — skip these when detecting explicit bg.
@@ -31,11 +33,19 @@ pub struct ContextTracker {
     stack: Vec<StackEntry>,
     /// Pending @a11y-context-block annotation to apply on next tag open
     pending_block_override: Option<String>,
+    /// Context name → bg class, for `@a11y-context use:<name>`
+    named_contexts: HashMap<String, String>,
+    /// Bg of a pending `@a11y-context use:<name>`, applied on next tag open
+    pending_named: Option<String>,
+    /// Bg a self-closing `use:<name>` element renders on (see `take_detached_bg`)
+    detached_bg: Option<String>,
 }
 
 struct StackEntry {
     tag: String,
     bg_class: String,
+    /// Background in dark mode: the tag's `dark:bg-*`, else `bg_class`
+    dark_bg_class: String,
     #[allow(dead_code)]
     is_annotation: bool,
     cumulative_opacity: f32,
@@ -57,9 +67,26 @@ impl ContextTracker {
             default_bg,
             stack: Vec::new(),
             pending_block_override: None,
+            named_contexts: HashMap::new(),
+            pending_named: None,
+            detached_bg: None,
         }
     }
 
+    /// Contexts (name → bg class) elements can opt into with
+    /// `@a11y-context use:<name>`: the element and its children then render
+    /// on that bg instead of the local stack, like a portal (opacity resets).
+    pub fn with_named_contexts(mut self, named_contexts: HashMap<String, String>) -> Self {
+        self.named_contexts = named_contexts;
+        self
+    }
+
+    /// Bg of a self-closing element annotated with `use:<name>` that just
+    /// opened (its own classes render on it; there is no subtree to push for).
+    pub fn take_detached_bg(&mut self) -> Option<String> {
+        self.detached_bg.take()
+    }
+
     /// Get the current effective background class (top of stack or default).
     pub fn current_bg(&self) -> &str {
         self.stack
@@ -68,6 +95,15 @@ impl ContextTracker {
             .unwrap_or(&self.default_bg)
     }
 
+    /// Get the current background class in dark mode, following `dark:bg-*`
+    /// variants (top of stack or default).
+    pub fn current_dark_bg(&self) -> &str {
+        self.stack
+            .last()
+            .map(|e| e.dark_bg_class.as_str())
+            .unwrap_or(&self.default_bg)
+    }
+
     /// Get the current cumulative opacity (top of stack or 1.0 if empty).
     pub fn current_opacity(&self) -> f32 {
         self.stack
@@ -76,14 +112,58 @@ impl ContextTracker {
             .unwrap_or(1.0)
     }
 
+    /// Seed the stack with synthetic ancestors (outermost first), for scans of
+    /// partial sources that render inside containers not present in the text.
+    ///
+    /// Each ancestor's bg is its explicit `bg_class`, else its component's
+    /// portal/container config, else the bg it inherits. Seeded entries are
+    /// never popped by closing tags in the scanned source.
+    pub fn seed_ancestors(&mut self, ancestors: &[AncestorContext]) {
+        for ancestor in ancestors {
+            let component = ancestor.component.as_deref().unwrap_or("");
+            let portal_bg = self.portal_config.get(component).map(|bg| {
+                if bg == "reset" { self.default_bg.clone() } else { bg.clone() }
+            });
+            let own_bg = ancestor
+                .bg_class
+                .clone()
+                .or(portal_bg)
+                .or_else(|| self.container_config.get(component).cloned());
+            let bg = own_bg.clone().unwrap_or_else(|| self.current_bg().to_string());
+            let dark_bg = own_bg.unwrap_or_else(|| self.current_dark_bg().to_string());
+            let opacity = ancestor.opacity.map(|o| o.clamp(0.0, 1.0) as f32).unwrap_or(1.0);
+            self.stack.push(StackEntry {
+                tag: format!("_ancestor_{}", component),
+                bg_class: bg,
+                dark_bg_class: dark_bg,
+                is_annotation: false,
+                cumulative_opacity: self.current_opacity() * opacity,
+            });
+        }
+    }
+
     /// Resolve any pending @a11y-context-block annotation by pushing it onto the stack.
     /// Call this BEFORE capturing pre_tag_open_bg in the orchestrator, so that
     /// block annotations count as parent context (not as the tag's own bg).
     pub fn resolve_pending_block(&mut self, tag_name: &str, is_self_closing: bool) {
+        if let Some(bg) = self.pending_named.take() {
+            if is_self_closing {
+                self.detached_bg = Some(bg);
+            } else {
+                self.stack.push(StackEntry {
+                    tag: format!("_annotation_{}", tag_name),
+                    dark_bg_class: bg.clone(),
+                    bg_class: bg,
+                    is_annotation: true,
+                    cumulative_opacity: 1.0,
+                });
+            }
+        }
         if let Some(bg) = self.pending_block_override.take() {
             if !is_self_closing {
                 self.stack.push(StackEntry {
                     tag: format!("_annotation_{}", tag_name),
+                    dark_bg_class: bg.clone(),
                     bg_class: bg,
                     is_annotation: true,
                     cumulative_opacity: self.current_opacity(),
@@ -99,12 +179,15 @@ impl JsxVisitor for ContextTracker {
         // called by the orchestrator BEFORE this method. When used standalone
         // (without orchestrator), call resolve_pending_block manually first.
 
-        if is_self_closing {
+        // Fragments render no element: transparent to bg and opacity. A pending
+        // @a11y-context-block still attaches to them (covering all children).
+        if is_self_closing || is_fragment(tag_name) {
             return;
         }
 
         // Detect opacity-* class in the raw tag (US-05)
         let opacity = super::opacity::find_opacity_in_raw_tag(raw_tag);
+        let dark_bg = find_dark_bg_in_raw_tag(raw_tag);
 
         // Check portal config FIRST (portal takes priority over container)
         if let Some(portal_bg) = self.portal_config.get(tag_name).cloned() {
@@ -119,6 +202,7 @@ impl JsxVisitor for ContextTracker {
             let cumulative = opacity.unwrap_or(1.0);
             self.stack.push(StackEntry {
                 tag: tag_name.to_string(),
+                dark_bg_class: dark_bg.unwrap_or_else(|| bg.clone()),
                 bg_class: bg,
                 is_annotation: false,
                 cumulative_opacity: cumulative,
@@ -136,6 +220,7 @@ impl JsxVisitor for ContextTracker {
             let bg = explicit_bg.unwrap_or(config_bg);
             self.stack.push(StackEntry {
                 tag: tag_name.to_string(),
+                dark_bg_class: dark_bg.unwrap_or_else(|| bg.clone()),
                 bg_class: bg,
                 is_annotation: false,
                 cumulative_opacity: cumulative,
@@ -147,6 +232,7 @@ impl JsxVisitor for ContextTracker {
         if let Some(bg) = find_explicit_bg_in_raw_tag(raw_tag) {
             self.stack.push(StackEntry {
                 tag: tag_name.to_string(),
+                dark_bg_class: dark_bg.unwrap_or_else(|| bg.clone()),
                 bg_class: bg,
                 is_annotation: false,
                 cumulative_opacity: cumulative,
@@ -154,12 +240,14 @@ impl JsxVisitor for ContextTracker {
             return;
         }
 
-        // Opacity-only tag: no container config, no explicit bg-*
-        // Push an entry that inherits the parent's bg but tracks cumulative opacity
-        if opacity.is_some() {
+        // Opacity-only or dark-only tag: no container config, no explicit bg-*
+        // Push an entry that inherits the parent's bg but tracks cumulative
+        // opacity and its own dark:bg-*
+        if opacity.is_some() || dark_bg.is_some() {
             self.stack.push(StackEntry {
                 tag: tag_name.to_string(),
                 bg_class: self.current_bg().to_string(),
+                dark_bg_class: dark_bg.unwrap_or_else(|| self.current_dark_bg().to_string()),
                 is_annotation: false,
                 cumulative_opacity: cumulative,
             });
@@ -168,13 +256,17 @@ impl JsxVisitor for ContextTracker {
 
     fn on_tag_close(&mut self, tag_name: &str) {
         // Pop matching container or annotation entry
+        let annotation_key = format!("_annotation_{}", tag_name);
         if let Some(last) = self.stack.last() {
             if last.tag == tag_name {
                 self.stack.pop();
+                // An annotated tag with its own entry: the annotation sits below it
+                if self.stack.last().is_some_and(|e| e.tag == annotation_key) {
+                    self.stack.pop();
+                }
                 return;
             }
             // Check for annotation block pop
-            let annotation_key = format!("_annotation_{}", tag_name);
             if last.tag == annotation_key {
                 self.stack.pop();
                 return;
@@ -182,14 +274,12 @@ impl JsxVisitor for ContextTracker {
         }
 
         // Search deeper in the stack for a match (handles interleaved pops)
-        if let Some(idx) = self.stack.iter().rposition(|e| {
-            e.tag == tag_name || e.tag == format!("_annotation_{}", tag_name)
-        }) {
+        if let Some(idx) = self.stack.iter().rposition(|e| e.tag == tag_name || e.tag == annotation_key) {
             self.stack.truncate(idx);
         }
     }
 
-    fn on_comment(&mut self, content: &str, _line: u32) {
+    fn on_comment(&mut self, content: &str, line: u32) {
         // Detect @a11y-context-block annotations
         let trimmed = content.trim();
         if let Some(body) = trimmed.strip_prefix("@a11y-context-block") {
@@ -199,13 +289,26 @@ impl JsxVisitor for ContextTracker {
                     self.pending_block_override = Some(bg.to_string());
                 }
             }
+        } else if let Some(body) = trimmed.strip_prefix("@a11y-context") {
+            // `@a11y-context use:<name>`: the next element leaves the bg stack
+            for name in body.split_whitespace().filter_map(|token| token.strip_prefix("use:")) {
+                match self.named_contexts.get(name) {
+                    Some(bg) => self.pending_named = Some(bg.clone()),
+                    None => log::warn!("line {}: @a11y-context use:{} names no configured context", line, name),
+                }
+            }
         }
     }
 }
 
-/// Find first explicit bg-* color class in a raw tag string.
+/// `<>`, `<Fragment>` or `<React.Fragment>`.
+pub(super) fn is_fragment(tag_name: &str) -> bool {
+    matches!(tag_name, super::tokenizer::FRAGMENT | "React.Fragment")
+}
+
+/// Find first explicit bg-* color class in a raw tag string (or class list).
 /// Skips variant-prefixed (dark:bg-*, hover:bg-*) and non-color bg utilities.
-fn find_explicit_bg_in_raw_tag(raw_tag: &str) -> Option<String> {
+pub(crate) fn find_explicit_bg_in_raw_tag(raw_tag: &str) -> Option<String> {
     // Use a simple word-boundary scan for bg-* patterns
     let bytes = raw_tag.as_bytes();
     let len = bytes.len();
@@ -246,10 +349,7 @@ fn find_explicit_bg_in_raw_tag(raw_tag: &str) -> Option<String> {
             let cls = &raw_tag[start..i];
 
             // Skip non-color bg utilities
-            if cls.starts_with("bg-linear-")
-                || cls.starts_with("bg-gradient-")
-                || BG_NON_COLOR.contains(&cls)
-            {
+            if !is_color_bg(cls) {
                 continue;
             }
 
@@ -262,10 +362,55 @@ fn find_explicit_bg_in_raw_tag(raw_tag: &str) -> Option<String> {
     None
 }
 
+/// Whether a `bg-*` class sets a color (not a gradient or bg-size/-repeat utility).
+fn is_color_bg(cls: &str) -> bool {
+    !(cls.starts_with("bg-linear-") || cls.starts_with("bg-gradient-") || BG_NON_COLOR.contains(&cls))
+}
+
+/// Find the first `dark:bg-*` color class (dark as the only variant) in a raw tag string.
+fn find_dark_bg_in_raw_tag(raw_tag: &str) -> Option<String> {
+    raw_tag
+        .split(|c: char| c.is_ascii_whitespace() || matches!(c, '"' | '\'' | '`' | '(' | ')' | ','))
+        .filter_map(|token| token.strip_prefix("dark:"))
+        .find(|cls| cls.starts_with("bg-") && cls.len() > 3 && is_color_bg(cls))
+        .map(str::to_string)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn ancestor(component: Option<&str>, bg: Option<&str>) -> AncestorContext {
+        AncestorContext {
+            component: component.map(str::to_string),
+            bg_class: bg.map(str::to_string),
+            opacity: None,
+        }
+    }
+
+    #[test]
+    fn seeded_ancestors_set_context() {
+        let mut config = HashMap::new();
+        config.insert("Card".to_string(), "bg-card".to_string());
+        let mut tracker = ContextTracker::new(config, "bg-background".to_string());
+        tracker.seed_ancestors(&[ancestor(Some("section"), Some("bg-slate-900")), ancestor(Some("Card"), None)]);
+        assert_eq!(tracker.current_bg(), "bg-card");
+        // closing a same-named tag from the partial source doesn't pop the seed
+        tracker.on_tag_close("Card");
+        assert_eq!(tracker.current_bg(), "bg-card");
+    }
+
+    #[test]
+    fn seeded_unknown_component_inherits_and_tracks_opacity() {
+        let mut tracker = ContextTracker::new(HashMap::new(), "bg-background".to_string());
+        tracker.seed_ancestors(&[
+            ancestor(None, Some("bg-slate-900")),
+            AncestorContext { component: Some("Fade".to_string()), bg_class: None, opacity: Some(0.5) },
+        ]);
+        assert_eq!(tracker.current_bg(), "bg-slate-900");
+        assert_eq!(tracker.current_opacity(), 0.5);
+    }
+
     fn make_config() -> HashMap<String, String> {
         let mut m = HashMap::new();
         m.insert("Card".to_string(), "bg-card".to_string());
@@ -286,6 +431,22 @@ mod tests {
         assert_eq!(tracker.current_bg(), "bg-card");
     }
 
+    #[test]
+    fn fragments_push_nothing() {
+        let mut config = make_config();
+        config.insert("Fragment".to_string(), "bg-red-500".to_string());
+        let mut tracker = ContextTracker::new(config, "bg-background".to_string());
+        tracker.on_tag_open("Card", false, "<Card>");
+        tracker.on_tag_open("Fragment", false, "<>");
+        tracker.on_tag_open("React.Fragment", false, r#"<React.Fragment className="bg-black opacity-50">"#);
+        assert_eq!((tracker.current_bg(), tracker.current_opacity()), ("bg-card", 1.0));
+        tracker.on_tag_close("React.Fragment");
+        tracker.on_tag_close("Fragment");
+        assert_eq!(tracker.current_bg(), "bg-card");
+        tracker.on_tag_close("Card");
+        assert_eq!(tracker.current_bg(), "bg-background");
+    }
+
     #[test]
     fn pop_on_container_close() {
         let mut tracker = ContextTracker::new(make_config(), "bg-background".to_string());
@@ -348,6 +509,24 @@ mod tests {
         assert_eq!(tracker.current_bg(), "bg-red-500");
     }
 
+    #[test]
+    fn dark_bg_tracked_alongside() {
+        let mut tracker = ContextTracker::new(make_config(), "bg-background".to_string());
+        tracker.on_tag_open("Card", false, r#"<Card className="dark:bg-zinc-900">"#);
+        assert_eq!((tracker.current_bg(), tracker.current_dark_bg()), ("bg-card", "bg-zinc-900"));
+        // Only dark:bg-*: the light bg is inherited
+        tracker.on_tag_open("div", false, r#"<div className="p-4 dark:bg-zinc-800 dark:hover:bg-zinc-700">"#);
+        assert_eq!((tracker.current_bg(), tracker.current_dark_bg()), ("bg-card", "bg-zinc-800"));
+        // A plain bg-* covers both
+        tracker.on_tag_open("p", false, r#"<p className="bg-white">"#);
+        assert_eq!(tracker.current_dark_bg(), "bg-white");
+        tracker.on_tag_close("p");
+        tracker.on_tag_close("div");
+        assert_eq!(tracker.current_dark_bg(), "bg-zinc-900");
+        tracker.on_tag_close("Card");
+        assert_eq!(tracker.current_dark_bg(), "bg-background");
+    }
+
     #[test]
     fn bg_non_color_skipped() {
         let mut tracker = ContextTracker::new(make_config(), "bg-background".to_string());
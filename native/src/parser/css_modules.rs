@@ -0,0 +1,273 @@
+//! CSS Modules: `import styles from "./Card.module.css"` followed by
+//! `className={styles.card}`. The tokenizer tracks the module imports of a
+//! file (`module_imports`) and reports member accesses of their bindings;
+//! the accessed class resolves through a caller-supplied map of the colors
+//! each module class sets (`ExtractOptions.css_modules`, which
+//! `parse_css_module` builds from a stylesheet) into arbitrary-value classes
+//! (`text-[#333333] bg-[#ffffff]`) that check like any other.
+
+use std::collections::HashMap;
+
+use crate::math::color_parse::to_hex;
+use crate::types::CssModuleClass;
+use super::class_extractor::{css_declaration_colors, InlineStyleColors};
+use super::tokenizer::find_closing_bracket;
+
+/// Module path → class name → its colors as arbitrary-value classes.
+pub type CssModuleMap = HashMap<String, HashMap<String, String>>;
+
+/// Stylesheet extensions CSS Modules are written in.
+const MODULE_EXTENSIONS: [&str; 4] = [".module.css", ".module.scss", ".module.sass", ".module.less"];
+
+pub fn is_css_module(specifier: &str) -> bool {
+    MODULE_EXTENSIONS.iter().any(|ext| specifier.ends_with(ext))
+}
+
+/// Binding → specifier of each CSS module `source` imports, from
+/// `import styles from "..."` and `import * as styles from "..."`.
+pub fn module_imports(source: &str) -> HashMap<String, String> {
+    let mut imports = HashMap::new();
+    for (start, _) in source.match_indices("import ") {
+        // Import declarations start a statement
+        let line_before = source[..start].rsplit('\n').next().unwrap_or_default().trim_end();
+        if !line_before.is_empty() && !line_before.ends_with(';') {
+            continue;
+        }
+        let Some((clause, rest)) = source[start + "import ".len()..].split_once("from") else {
+            continue;
+        };
+        let clause = clause.trim();
+        let clause = clause.strip_prefix('*').and_then(|c| c.trim_start().strip_prefix("as ")).unwrap_or(clause);
+        // `import styles, { card } from` binds the default import first
+        let binding = clause.split(',').next().unwrap_or_default().trim();
+        if !is_identifier(binding) {
+            continue;
+        }
+        let rest = rest.trim_start();
+        let Some(quote) = rest.chars().next().filter(|c| matches!(c, '"' | '\'')) else {
+            continue;
+        };
+        let Some(end) = rest[1..].find(quote) else {
+            continue;
+        };
+        let specifier = &rest[1..1 + end];
+        if is_css_module(specifier) {
+            imports.insert(binding.to_string(), specifier.to_string());
+        }
+    }
+    imports
+}
+
+/// Specifier and class name of a class attribute expression accessing an
+/// imported module: `styles.card` or `styles["card-title"]`.
+pub fn member_access<'a>(expr: &'a str, imports: &'a HashMap<String, String>) -> Option<(&'a str, &'a str)> {
+    let expr = expr.trim();
+    let split = expr.find(['.', '['])?;
+    let specifier = imports.get(&expr[..split])?;
+    let member = &expr[split..];
+    let class_name = match member.strip_prefix('.') {
+        Some(name) => name,
+        None => member
+            .strip_prefix('[')?
+            .strip_suffix(']')?
+            .trim()
+            .strip_prefix(['"', '\''])?
+            .strip_suffix(['"', '\''])?,
+    };
+    let is_name = !class_name.is_empty() && class_name.chars().all(|c| c.is_alphanumeric() || matches!(c, '_' | '-'));
+    is_name.then_some((specifier.as_str(), class_name))
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || matches!(c, '_' | '$'))
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '$'))
+}
+
+/// Index `entries` by module and class. Entries without a parseable color
+/// are left out, so their accesses stay unresolved.
+pub fn build_map(entries: &[CssModuleClass]) -> CssModuleMap {
+    let mut map = CssModuleMap::new();
+    for entry in entries {
+        let colors = [("text", &entry.color), ("bg", &entry.background_color), ("border", &entry.border_color)];
+        let classes: Vec<String> = colors
+            .iter()
+            .filter_map(|(prefix, value)| Some(format!("{}-[{}]", prefix, to_hex(value.as_deref()?)?)))
+            .collect();
+        if classes.is_empty() {
+            continue;
+        }
+        let module = entry.module.replace('\\', "/");
+        map.entry(module).or_default().insert(entry.class_name.clone(), classes.join(" "));
+    }
+    map
+}
+
+/// Classes of `class_name` in the module `specifier` imports. Modules match
+/// by path suffix (`./Card.module.css` matches `src/components/Card.module.css`):
+/// an exact match wins, then the longest matching module path.
+pub fn resolve<'a>(map: &'a CssModuleMap, specifier: &str, class_name: &str) -> Option<&'a str> {
+    let path: Vec<&str> = specifier.split('/').skip_while(|s| matches!(*s, "." | "..")).collect();
+    let path = path.join("/");
+    let matches = |module: &str| {
+        module == path || module.ends_with(&format!("/{}", path)) || path.ends_with(&format!("/{}", module))
+    };
+    map.iter()
+        .filter(|(module, _)| matches(module))
+        .max_by_key(|(module, _)| (**module == path, module.len()))
+        .and_then(|(_, classes)| classes.get(class_name))
+        .map(String::as_str)
+}
+
+/// The colors of each plain class rule (`.card { ... }`, `.a, .b { ... }`)
+/// at the top level of the stylesheet `css`, for `ExtractOptions.css_modules`.
+/// Rules with pseudo-classes, combinators or inside at-rules (`@media`)
+/// don't apply to the default rendering and are skipped.
+pub fn parse_css_module(module: &str, css: &str) -> Vec<CssModuleClass> {
+    let mut out: Vec<CssModuleClass> = Vec::new();
+    for (selector, colors) in color_rules(css) {
+        for class_name in selector.split(',').filter_map(|s| plain_class(s.trim())) {
+            let entry = match out.iter().position(|e| e.class_name == class_name) {
+                Some(index) => &mut out[index],
+                None => {
+                    out.push(CssModuleClass {
+                        module: module.to_string(),
+                        class_name: class_name.to_string(),
+                        color: None,
+                        background_color: None,
+                        border_color: None,
+                    });
+                    out.last_mut().unwrap()
+                }
+            };
+            // Later rules win
+            let declared = [&colors.color, &colors.background_color, &colors.border_color];
+            let fields = [&mut entry.color, &mut entry.background_color, &mut entry.border_color];
+            for (field, value) in fields.into_iter().zip(declared) {
+                if value.is_some() {
+                    field.clone_from(value);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Selector and colors of each top-level rule of `css` declaring a color,
+/// in source order. At-rules (`@media { ... }`, `@import ...;`) are skipped.
+pub(super) fn color_rules(css: &str) -> Vec<(String, InlineStyleColors)> {
+    let css = strip_comments(css);
+    let bytes = css.as_bytes();
+    let mut rules = Vec::new();
+    let mut rule_start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => {
+                let Some(close) = find_closing_bracket(bytes, i) else {
+                    break;
+                };
+                let selector = css[rule_start..i].trim();
+                if !selector.starts_with('@') {
+                    if let Some(colors) = css_declaration_colors(&css[i + 1..close]) {
+                        rules.push((selector.to_string(), colors));
+                    }
+                }
+                i = close + 1;
+                rule_start = i;
+            }
+            b';' => {
+                i += 1;
+                rule_start = i;
+            }
+            _ => i += 1,
+        }
+    }
+    rules
+}
+
+/// `card` for the selector `.card`; None for anything else.
+pub(super) fn plain_class(selector: &str) -> Option<&str> {
+    let name = selector.strip_prefix('.')?;
+    let valid = name.chars().next().is_some_and(|c| c.is_alphabetic() || matches!(c, '_' | '-'))
+        && name.chars().all(|c| c.is_alphanumeric() || matches!(c, '_' | '-'));
+    valid.then_some(name)
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        rest = rest[start + 2..].split_once("*/").map_or("", |(_, after)| after);
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_module_imports() {
+        let source = r#"import styles from "./Card.module.css";
+import * as theme from '../theme.module.scss';
+import "./global.css";
+import { cn } from "@/lib/utils";
+const reimport = "import x from './y.module.css'";"#;
+        let imports = module_imports(source);
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports["styles"], "./Card.module.css");
+        assert_eq!(imports["theme"], "../theme.module.scss");
+    }
+
+    #[test]
+    fn member_access_forms() {
+        let imports = HashMap::from([("styles".to_string(), "./Card.module.css".to_string())]);
+        assert_eq!(member_access("styles.card", &imports), Some(("./Card.module.css", "card")));
+        assert_eq!(member_access(" styles['card-title'] ", &imports), Some(("./Card.module.css", "card-title")));
+        assert_eq!(member_access("styles[variant]", &imports), None);
+        assert_eq!(member_access("other.card", &imports), None);
+        assert_eq!(member_access("styles.card + ' x'", &imports), None);
+    }
+
+    #[test]
+    fn parses_plain_class_rules() {
+        let css = r#"
+@import "./base.css";
+/* .ignored { color: red } */
+.card, .panel { background: #fff; color: #333 }
+.card:hover { background: #eee }
+.title { color: rgb(17, 24, 39); }
+.card { border-color: #e5e7eb }
+@media (prefers-color-scheme: dark) { .card { background: #000 } }
+"#;
+        let classes = parse_css_module("Card.module.css", css);
+        let names: Vec<&str> = classes.iter().map(|c| c.class_name.as_str()).collect();
+        assert_eq!(names, ["card", "panel", "title"]);
+        assert_eq!(classes[0].background_color.as_deref(), Some("#fff"));
+        assert_eq!(classes[0].color.as_deref(), Some("#333"));
+        assert_eq!(classes[0].border_color.as_deref(), Some("#e5e7eb"));
+
+        let map = build_map(&classes);
+        assert_eq!(resolve(&map, "./Card.module.css", "card"), Some("text-[#333333] bg-[#ffffff] border-[#e5e7eb]"));
+        assert_eq!(resolve(&map, "./Card.module.css", "title"), Some("text-[#111827]"));
+        assert_eq!(resolve(&map, "./Card.module.css", "missing"), None);
+        assert_eq!(resolve(&map, "./Other.module.css", "card"), None);
+    }
+
+    #[test]
+    fn modules_match_by_path_suffix() {
+        let entry = |module: &str, color: &str| CssModuleClass {
+            module: module.to_string(),
+            class_name: "card".to_string(),
+            color: Some(color.to_string()),
+            background_color: None,
+            border_color: None,
+        };
+        let map = build_map(&[entry("Card.module.css", "#111111"), entry("src/admin/Card.module.css", "#222222")]);
+        assert_eq!(resolve(&map, "../admin/Card.module.css", "card"), Some("text-[#222222]"));
+        assert_eq!(resolve(&map, "./Card.module.css", "card"), Some("text-[#111111]"));
+    }
+}
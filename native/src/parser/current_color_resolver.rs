@@ -48,6 +48,12 @@ struct StackEntry {
     color_class: String,
 }
 
+impl Default for CurrentColorResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CurrentColorResolver {
     pub fn new() -> Self {
         Self {
@@ -0,0 +1,308 @@
+//! Variant-aware `cva()` extraction.
+//!
+//! `cva(base, { variants, compoundVariants, defaultVariants })` renders the
+//! base classes plus ONE option per variant, so scanning the whole call as a
+//! single class list pairs classes that never render together (the
+//! `destructive` bg with the `ghost` text). Instead the default rendering
+//! (`defaultVariants`) and every variant option become their own class
+//! list: base + the option + the default option of each other variant.
+//! Compound variants add their classes to every list whose options they
+//! match, and each combination they list becomes a class list too.
+
+use std::collections::HashMap;
+
+use super::markup::binding_classes;
+use super::tokenizer::{find_top_level_colon, split_object_entries};
+
+/// One class list a `cva()` call can render.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CvaBranch {
+    /// e.g. "intent=destructive", "intent=primary,size=sm" for a compound
+    /// variant, or "default" for the `defaultVariants` rendering
+    pub variant_key: String,
+    pub classes: String,
+    /// Source offset of the branch (the option's value or the compound entry)
+    pub offset: usize,
+}
+
+/// Branches of one `cva()` call.
+#[derive(Debug, Default)]
+pub struct CvaBranches {
+    pub branches: Vec<CvaBranch>,
+    /// (offset, combination count) of every compound variant listing more
+    /// than `MAX_COMPOUND_COMBINATIONS` combinations; only the first ones
+    /// become branches
+    pub truncated: Vec<(usize, usize)>,
+}
+
+/// A variant: (name, [(option, classes, offset)]).
+type Variant<'a> = (&'a str, Vec<(&'a str, Vec<String>, usize)>);
+
+/// A compound variant: offset, (variant, options it matches) conditions and
+/// its classes.
+struct Compound<'a> {
+    offset: usize,
+    conditions: Vec<(&'a str, Vec<&'a str>)>,
+    classes: Vec<String>,
+}
+
+/// Most class lists one compound variant expands to (`size: ["sm", "md"]`
+/// lists several combinations).
+pub const MAX_COMPOUND_COMBINATIONS: usize = 8;
+
+/// Branches of the `cva(...)` call whose parens are at `open..=close` in
+/// `source`. Empty when the call has no `variants` (the caller then scans the
+/// arguments as one class list).
+pub fn branches(source: &str, open: usize, close: usize) -> CvaBranches {
+    let args = split_object_entries(source, open, close + 1);
+    let base = args.first().map(|(_, a)| classes_of(a)).unwrap_or_default();
+    let Some(&(config_offset, config)) = args.get(1).filter(|(_, a)| a.starts_with('{')) else {
+        return CvaBranches::default();
+    };
+
+    let mut variants: Vec<Variant> = Vec::new();
+    let mut defaults: HashMap<&str, &str> = HashMap::new();
+    let mut defaults_offset = None;
+    let mut compounds: Vec<Compound> = Vec::new();
+    for (key, value, value_offset) in entries(source, config_offset, config) {
+        match key {
+            "variants" if value.starts_with('{') => {
+                for (name, options, options_offset) in entries(source, value_offset, value) {
+                    let options = entries(source, options_offset, options)
+                        .into_iter()
+                        .map(|(option, classes, offset)| (option, classes_of(classes), offset))
+                        .collect();
+                    variants.push((name, options));
+                }
+            }
+            "defaultVariants" if value.starts_with('{') => {
+                for (name, option, _) in entries(source, value_offset, value) {
+                    defaults.insert(name, unquote(option));
+                }
+                defaults_offset = Some(value_offset);
+            }
+            "compoundVariants" if value.starts_with('[') => {
+                let items = split_object_entries(source, value_offset, value_offset + value.len());
+                for (offset, compound) in items.into_iter().filter(|(_, c)| c.starts_with('{')) {
+                    compounds.push(compound_of(source, offset, compound));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let option_classes = |variant: &str, option: &str| -> Vec<String> {
+        variants
+            .iter()
+            .filter(|(name, _)| *name == variant)
+            .flat_map(|(_, options)| options.iter().filter(|(o, _, _)| *o == option))
+            .flat_map(|(_, classes, _)| classes.clone())
+            .collect()
+    };
+    // base + the chosen options + the default of every variant not chosen +
+    // the compound variants matching those options
+    let compose = |chosen: &[(&str, &str)]| -> String {
+        let mut classes = base.clone();
+        let mut effective: Vec<(&str, &str)> = Vec::new();
+        for (name, _) in &variants {
+            let option = chosen
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, o)| *o)
+                .or_else(|| defaults.get(name).copied());
+            if let Some(option) = option {
+                classes.extend(option_classes(name, option));
+                effective.push((name, option));
+            }
+        }
+        for compound in &compounds {
+            let matches = compound.conditions.iter().all(|(name, options)| {
+                effective.iter().any(|(n, o)| n == name && options.contains(o))
+            });
+            if matches {
+                classes.extend(compound.classes.iter().cloned());
+            }
+        }
+        classes.join(" ")
+    };
+
+    let mut out = Vec::new();
+    let mut truncated = Vec::new();
+    if let Some(offset) = defaults_offset.filter(|_| !defaults.is_empty() && !variants.is_empty()) {
+        out.push(CvaBranch { variant_key: "default".to_string(), classes: compose(&[]), offset });
+    }
+    for (name, options) in &variants {
+        for (option, _, offset) in options {
+            out.push(CvaBranch {
+                variant_key: format!("{}={}", name, option),
+                classes: compose(&[(name, option)]),
+                offset: *offset,
+            });
+        }
+    }
+    for compound in &compounds {
+        // One class list per combination of the options the compound lists
+        let count: usize = compound.conditions.iter().map(|(_, options)| options.len()).product();
+        if count > MAX_COMPOUND_COMBINATIONS {
+            truncated.push((compound.offset, count));
+        }
+        let mut combinations: Vec<Vec<(&str, &str)>> = vec![Vec::new()];
+        for (name, options) in &compound.conditions {
+            combinations = combinations
+                .iter()
+                .flat_map(|chosen| {
+                    options.iter().map(move |option| {
+                        let mut chosen = chosen.clone();
+                        chosen.push((*name, *option));
+                        chosen
+                    })
+                })
+                .take(MAX_COMPOUND_COMBINATIONS)
+                .collect();
+        }
+        for chosen in combinations {
+            let variant_key = chosen.iter().map(|(n, o)| format!("{}={}", n, o)).collect::<Vec<_>>().join(",");
+            out.push(CvaBranch { variant_key, classes: compose(&chosen), offset: compound.offset });
+        }
+    }
+    CvaBranches { branches: out, truncated }
+}
+
+/// The compound variant object `compound` found at `offset` in `source`.
+/// `size: ["sm", "md"]` matches either option.
+fn compound_of<'a>(source: &'a str, offset: usize, compound: &'a str) -> Compound<'a> {
+    let mut conditions = Vec::new();
+    let mut classes = Vec::new();
+    for (key, value, _) in entries(source, offset, compound) {
+        if matches!(key, "class" | "className") {
+            classes = classes_of(value);
+        } else {
+            let options = value.trim_start_matches('[').trim_end_matches(']').split(',');
+            let options: Vec<&str> = options.map(|o| unquote(o.trim())).filter(|o| !o.is_empty()).collect();
+            conditions.push((key, options));
+        }
+    }
+    Compound { offset, conditions, classes }
+}
+
+/// (key, value, value offset) entries of the object literal `object` found at
+/// `offset` in `source`; shorthand entries are skipped.
+fn entries<'a>(source: &'a str, offset: usize, object: &'a str) -> Vec<(&'a str, &'a str, usize)> {
+    split_object_entries(source, offset, offset + object.len())
+        .into_iter()
+        .filter_map(|(entry_offset, entry)| {
+            let colon = find_top_level_colon(entry)?;
+            let value = entry[colon + 1..].trim_start();
+            let value_offset = entry_offset + entry.len() - value.len();
+            Some((unquote(entry[..colon].trim()), value, value_offset))
+        })
+        .collect()
+}
+
+fn unquote(text: &str) -> &str {
+    text.trim_matches(|c| matches!(c, '"' | '\'' | '`'))
+}
+
+fn classes_of(value: &str) -> Vec<String> {
+    binding_classes(value, &HashMap::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUTTON: &str = r#"cva("inline-flex rounded-md", {
+  variants: {
+    intent: {
+      primary: "bg-primary text-primary-foreground",
+      destructive: ["bg-destructive", "text-white"],
+    },
+    size: { sm: "h-8 text-xs", lg: 'h-10' },
+  },
+  compoundVariants: [
+    { intent: "primary", size: ["sm", "lg"], class: "uppercase" },
+  ],
+  defaultVariants: { intent: "primary", size: "sm" },
+})"#;
+
+    fn call_branches(source: &str) -> Vec<CvaBranch> {
+        let open = source.find('(').unwrap();
+        branches(source, open, source.rfind(')').unwrap()).branches
+    }
+
+    #[test]
+    fn one_branch_per_option_with_defaults() {
+        let branches = call_branches(BUTTON);
+        let keyed: Vec<(&str, &str)> =
+            branches.iter().map(|b| (b.variant_key.as_str(), b.classes.as_str())).collect();
+        assert_eq!(
+            keyed[1..5],
+            [
+                ("intent=primary", "inline-flex rounded-md bg-primary text-primary-foreground h-8 text-xs uppercase"),
+                ("intent=destructive", "inline-flex rounded-md bg-destructive text-white h-8 text-xs"),
+                ("size=sm", "inline-flex rounded-md bg-primary text-primary-foreground h-8 text-xs uppercase"),
+                ("size=lg", "inline-flex rounded-md bg-primary text-primary-foreground h-10 uppercase"),
+            ]
+        );
+        assert!(BUTTON[branches[2].offset..].starts_with(r#"["bg-destructive""#));
+    }
+
+    #[test]
+    fn default_variants_render_first() {
+        let branches = call_branches(BUTTON);
+        assert_eq!(branches[0].variant_key, "default");
+        assert_eq!(branches[0].classes, "inline-flex rounded-md bg-primary text-primary-foreground h-8 text-xs uppercase");
+        assert!(BUTTON[branches[0].offset..].starts_with(r#"{ intent: "primary""#));
+    }
+
+    #[test]
+    fn compound_variants_add_their_classes() {
+        let branches = call_branches(BUTTON);
+        let keyed: Vec<(&str, &str)> =
+            branches[5..].iter().map(|b| (b.variant_key.as_str(), b.classes.as_str())).collect();
+        assert_eq!(
+            keyed,
+            [
+                ("intent=primary,size=sm", "inline-flex rounded-md bg-primary text-primary-foreground h-8 text-xs uppercase"),
+                ("intent=primary,size=lg", "inline-flex rounded-md bg-primary text-primary-foreground h-10 uppercase"),
+            ]
+        );
+    }
+
+    #[test]
+    fn oversized_compound_variants_are_reported() {
+        let source = r#"cva("p-2", {
+  variants: { intent: { a: "bg-white", b: "bg-black" }, size: { s: "h-1", m: "h-2", l: "h-3", x: "h-4", y: "h-5" } },
+  compoundVariants: [{ intent: ["a", "b"], size: ["s", "m", "l", "x", "y"], class: "uppercase" }],
+})"#;
+        let call = branches(source, source.find('(').unwrap(), source.rfind(')').unwrap());
+        let compound = source.find("{ intent: [").unwrap();
+        assert_eq!(call.truncated, [(compound, 10)]);
+        assert_eq!(call.branches.iter().filter(|b| b.offset == compound).count(), MAX_COMPOUND_COMBINATIONS);
+        assert!(branches(BUTTON, BUTTON.find('(').unwrap(), BUTTON.rfind(')').unwrap()).truncated.is_empty());
+
+        let (_, _, diagnostics, _) = crate::parser::scan_file_with_findings(source, &Default::default());
+        let kinds: Vec<(&str, u32)> = diagnostics.iter().map(|d| (d.kind.as_str(), d.line)).collect();
+        assert_eq!(kinds, [("truncated-compound-variant", 3)]);
+        assert_eq!(diagnostics[0].message, "compound variant lists 10 combinations, only the first 8 are checked");
+    }
+
+    #[test]
+    fn no_variants_yields_nothing() {
+        assert!(call_branches(r#"cva("bg-white text-black")"#).is_empty());
+        assert!(call_branches(r#"cva("bg-white", { defaultVariants: {} })"#).is_empty());
+    }
+
+    #[test]
+    fn scan_emits_one_region_per_branch() {
+        let source = format!("const button = {};\n", BUTTON);
+        let regions = crate::parser::scan_file_with_config(&source, &Default::default());
+        assert_eq!(regions.len(), 7);
+        assert_eq!(regions[0].variant_key.as_deref(), Some("default"));
+        assert_eq!(regions[0].start_line, 12);
+        assert_eq!(regions[2].variant_key.as_deref(), Some("intent=destructive"));
+        assert_eq!(regions[2].start_line, 5);
+        assert_eq!(regions[4].start_line, 7);
+        assert!(regions.iter().all(|r| !r.content.contains("primary\"")));
+    }
+}
@@ -15,6 +15,12 @@ pub struct DisabledDetector {
     current_line: u32,
 }
 
+impl Default for DisabledDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DisabledDetector {
     pub fn new() -> Self {
         Self {
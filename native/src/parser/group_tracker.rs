@@ -0,0 +1,198 @@
+use crate::pairs::categorizer::region_classes;
+
+use super::context_tracker::is_fragment;
+use super::visitor::JsxVisitor;
+
+/// States a `peer-*` variant can track (see `categorizer::interactive_state_for`).
+const PEER_STATES: &[&str] = &["hover", "focus-visible"];
+
+/// Tracks Tailwind `group` / `peer` markers across elements, so a child's
+/// `group-hover:text-white` can be checked against the background its `group`
+/// ancestor shows on hover, and `peer-*` variants are only tracked after a
+/// `peer` sibling.
+///
+/// Like `ContextTracker`, a LIFO stack of open elements. Each level records
+/// its own `group` marker and the `peer` markers among the children seen so
+/// far. Named markers (`group/card`, `peer/email`) only match variants with
+/// the same name (`group-hover/card:`).
+pub struct GroupTracker {
+    /// `stack[0]` is a synthetic root so top-level elements have a parent
+    stack: Vec<Level>,
+}
+
+#[derive(Default)]
+struct Level {
+    tag: String,
+    group: Option<GroupMarker>,
+    /// Names of `peer` markers among the children so far (None = unnamed)
+    peers: Vec<Option<String>>,
+}
+
+struct GroupMarker {
+    name: Option<String>,
+    /// Background of the group element itself
+    bg: String,
+    /// Base of its `hover:bg-*` class, e.g. "bg-primary"
+    hover_bg: Option<String>,
+}
+
+/// How a region's classes relate to its `group` / `peer` elements.
+#[derive(Debug, Default, PartialEq)]
+pub struct GroupContext {
+    /// Background behind the region while its `group` ancestor is hovered
+    /// (None = no `group-hover:` classes or no matching `group` ancestor)
+    pub group_hover_bg: Option<String>,
+    /// Its `peer-*` variants follow a matching `peer` sibling
+    pub peer_state: bool,
+}
+
+/// `group` → Some(None), `group/card` → Some(Some("card")).
+fn marker_name<'a>(class: &'a str, marker: &str) -> Option<Option<&'a str>> {
+    let rest = class.strip_prefix(marker)?;
+    match rest.strip_prefix('/') {
+        Some(name) if !name.is_empty() => Some(Some(name)),
+        None if rest.is_empty() => Some(None),
+        _ => None,
+    }
+}
+
+/// Name of the first `{marker}-{state}:` variant among `classes` whose state
+/// is in `states` (Some(None) = unnamed).
+fn variant_name<'a>(classes: &'a [String], marker: &str, states: &[&str]) -> Option<Option<&'a str>> {
+    classes.iter().find_map(|class| {
+        let (variants, _) = class.rsplit_once(':')?;
+        variants.split(':').find_map(|variant| {
+            let rest = variant.strip_prefix(marker)?.strip_prefix('-')?;
+            let (state, name) = match rest.split_once('/') {
+                Some((state, name)) => (state, Some(name)),
+                None => (rest, None),
+            };
+            states.contains(&state).then_some(name)
+        })
+    })
+}
+
+impl Default for GroupTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GroupTracker {
+    pub fn new() -> Self {
+        Self { stack: vec![Level::default()] }
+    }
+
+    /// Relate the classes of the element that just opened to its `group`
+    /// ancestors and `peer` siblings, then record its own markers.
+    /// `is_open`: the element has children (it's on the stack);
+    /// `context_bg`: its parent's background; `own_bg`: its own.
+    pub fn on_element_classes(
+        &mut self,
+        value: &str,
+        is_open: bool,
+        context_bg: &str,
+        own_bg: &str,
+    ) -> GroupContext {
+        let classes = region_classes(value);
+        let parent = self.stack.len() - if is_open { 2 } else { 1 };
+        let context = GroupContext {
+            group_hover_bg: variant_name(&classes, "group", &["hover"]).and_then(|name| {
+                let group = self.stack[..=parent]
+                    .iter()
+                    .rev()
+                    .find_map(|level| level.group.as_ref().filter(|g| g.name.as_deref() == name))?;
+                // An element with its own bg between them hides the group's hover bg
+                let hover_bg = group.hover_bg.as_ref().filter(|_| group.bg == context_bg);
+                Some(hover_bg.cloned().unwrap_or_else(|| context_bg.to_string()))
+            }),
+            peer_state: variant_name(&classes, "peer", PEER_STATES)
+                .is_some_and(|name| self.stack[parent].peers.iter().any(|p| p.as_deref() == name)),
+        };
+
+        let mut peers = Vec::new();
+        for class in &classes {
+            if let Some(name) = marker_name(class, "group").filter(|_| is_open) {
+                let hover_bg = classes.iter().find_map(|c| c.strip_prefix("hover:").filter(|b| b.starts_with("bg-")));
+                self.stack[parent + 1].group = Some(GroupMarker {
+                    name: name.map(str::to_string),
+                    bg: own_bg.to_string(),
+                    hover_bg: hover_bg.map(str::to_string),
+                });
+            }
+            if let Some(name) = marker_name(class, "peer") {
+                peers.push(name.map(str::to_string));
+            }
+        }
+        self.stack[parent].peers.extend(peers);
+        context
+    }
+}
+
+impl JsxVisitor for GroupTracker {
+    fn on_tag_open(&mut self, tag_name: &str, is_self_closing: bool, _raw_tag: &str) {
+        if !is_self_closing && !is_fragment(tag_name) {
+            self.stack.push(Level { tag: tag_name.to_string(), ..Default::default() });
+        }
+    }
+
+    fn on_tag_close(&mut self, tag_name: &str) {
+        if let Some(idx) = self.stack.iter().skip(1).rposition(|level| level.tag == tag_name) {
+            self.stack.truncate(idx + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open(tracker: &mut GroupTracker, tag: &str, classes: &str, bg: &str, own_bg: &str) -> GroupContext {
+        tracker.on_tag_open(tag, false, "");
+        tracker.on_element_classes(classes, true, bg, own_bg)
+    }
+
+    #[test]
+    fn group_hover_uses_the_nearest_matching_group() {
+        let mut t = GroupTracker::new();
+        open(&mut t, "a", "group/card bg-card hover:bg-primary", "bg-background", "bg-card");
+        open(&mut t, "div", "group p-2", "bg-card", "bg-card");
+        let ctx = open(&mut t, "p", "text-sm group-hover/card:text-white", "bg-card", "bg-card");
+        assert_eq!(ctx.group_hover_bg.as_deref(), Some("bg-primary"));
+        // The unnamed group has no hover bg: the region keeps its own context
+        let ctx = open(&mut t, "span", "group-hover:text-black", "bg-card", "bg-card");
+        assert_eq!(ctx.group_hover_bg.as_deref(), Some("bg-card"));
+
+        t.on_tag_close("span");
+        t.on_tag_close("p");
+        t.on_tag_close("div");
+        t.on_tag_close("a");
+        let ctx = open(&mut t, "p", "group-hover:text-white", "bg-background", "bg-background");
+        assert_eq!(ctx, GroupContext::default());
+    }
+
+    #[test]
+    fn own_bg_in_between_hides_the_group_hover_bg() {
+        let mut t = GroupTracker::new();
+        open(&mut t, "li", "group hover:bg-accent", "bg-background", "bg-background");
+        let ctx = open(&mut t, "div", "bg-muted group-hover:text-white", "bg-background", "bg-muted");
+        assert_eq!(ctx.group_hover_bg.as_deref(), Some("bg-accent"));
+        let ctx = open(&mut t, "p", "group-hover:text-white", "bg-muted", "bg-muted");
+        assert_eq!(ctx.group_hover_bg.as_deref(), Some("bg-muted"));
+    }
+
+    #[test]
+    fn peer_variants_need_a_preceding_peer_sibling() {
+        let mut t = GroupTracker::new();
+        open(&mut t, "form", "", "bg-background", "bg-background");
+        let before = t.on_element_classes("peer-hover:text-red-700", false, "bg-background", "bg-background");
+        assert!(!before.peer_state);
+        t.on_tag_open("input", true, "");
+        t.on_element_classes("peer/email border", false, "bg-background", "bg-background");
+        t.on_tag_open("p", false, "");
+        let named = t.on_element_classes("peer-focus-visible/email:text-red-700", true, "bg-background", "bg-background");
+        assert!(named.peer_state);
+        let other = t.on_element_classes("peer-hover:text-red-700", true, "bg-background", "bg-background");
+        assert!(!other.peer_state);
+    }
+}
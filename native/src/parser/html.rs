@@ -0,0 +1,110 @@
+//! Plain HTML support (documentation sites, email templates).
+//!
+//! An `.html` file is rewritten into JSX-shaped source with the same line
+//! numbering (see `markup`): `class="..."` becomes `className`, `<script>` /
+//! `<style>` blocks are blanked and `<!-- -->` comments carry annotations.
+//! Inline `style="color: ...; background-color: ..."` is read by the class
+//! extractor as in JSX, the rules of `<style>` blocks by `style_blocks`
+//! from the original source. Quotes and slashes in text nodes are blanked too:
+//! prose like "don't" or a bare URL would otherwise read as a string literal
+//! or a `//` comment and swallow the tags after it.
+
+use std::collections::HashMap;
+
+use super::markup::{blank_blocks, rewrite_template, ClassAttr};
+use super::tokenizer::find_tag_close;
+
+/// Whether `path` is a plain HTML file.
+pub fn is_html_path(path: &str) -> bool {
+    path.ends_with(".html") || path.ends_with(".htm")
+}
+
+/// Rewrite an HTML document into JSX-shaped source with the same line numbering.
+pub fn to_jsx(source: &str) -> String {
+    let markup = neutralize_text(&blank_blocks(source, &["<script", "<style"]));
+    rewrite_template(&markup, &HashMap::new(), html_class_attr)
+}
+
+fn html_class_attr(name: &str) -> Option<ClassAttr<'_>> {
+    (name == "class").then_some(ClassAttr::List)
+}
+
+/// Replace quotes and slashes in text nodes with spaces (tags, comments and
+/// `<!DOCTYPE>` are copied as they are).
+pub(super) fn neutralize_text(html: &str) -> String {
+    let bytes = html.as_bytes();
+    let mut out = String::with_capacity(html.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if html[i..].starts_with("<!--") {
+            let end = html[i..].find("-->").map_or(html.len(), |p| i + p + 3);
+            out.push_str(&html[i..end]);
+            i = end;
+            continue;
+        }
+        let next = bytes.get(i + 1).copied().unwrap_or(b' ');
+        if bytes[i] == b'<' && (next.is_ascii_alphabetic() || next == b'/' || next == b'!') {
+            let end = find_tag_close(html, i + 1);
+            out.push_str(&html[i..end]);
+            i = end;
+            continue;
+        }
+        let ch = html[i..].chars().next().unwrap_or(' ');
+        out.push(if matches!(ch, '\'' | '"' | '`' | '/') { ' ' } else { ch });
+        i += ch.len_utf8();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EMAIL: &str = r#"<!DOCTYPE html>
+<html>
+<head><style>.btn { color: red; }</style></head>
+<body class="bg-white">
+  <!-- @a11y-context bg:#0f172a -->
+  <td style="color: #94a3b8; background-color: #0f172a">Don't miss out</td>
+  <p class="text-gray-500">See https://example.com</p>
+  <script>const x = "<p class='text-red-500'>";</script>
+</body>
+</html>
+"#;
+
+    #[test]
+    fn non_ascii_template_text() {
+        let regions = crate::parser::scan_file_with_config(
+            &to_jsx("<p class=\"text-gray-500\">Caffè <!-- è --></p>"),
+            &Default::default(),
+        );
+        assert_eq!(regions[0].content, "text-gray-500");
+        to_jsx("è</p><!-- è");
+    }
+
+    #[test]
+    fn html_paths() {
+        assert!(is_html_path("docs/index.html"));
+        assert!(is_html_path("emails/welcome.htm"));
+        assert!(!is_html_path("src/App.tsx"));
+    }
+
+    #[test]
+    fn rewrite_keeps_lines_and_blanks_scripts() {
+        let jsx = to_jsx(EMAIL);
+        assert_eq!(jsx.lines().count(), EMAIL.lines().count());
+        assert!(jsx.contains(r#"<body className="bg-white">"#));
+        assert!(jsx.contains("{/* @a11y-context bg:#0f172a */}"));
+        assert!(!jsx.contains("color: red"));
+        assert!(!jsx.contains("text-red-500"));
+        assert!(jsx.contains("Don t miss out"));
+    }
+
+    #[test]
+    fn regions_after_prose_are_found() {
+        let regions = crate::parser::scan_file_with_config(&to_jsx(EMAIL), &Default::default());
+        let contents: Vec<&str> = regions.iter().map(|r| r.content.as_str()).collect();
+        assert_eq!(contents, vec!["bg-white", "text-gray-500"]);
+        assert_eq!(regions[1].start_line, 7);
+    }
+}
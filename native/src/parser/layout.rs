@@ -0,0 +1,160 @@
+//! `@a11y-layout` annotations: components that declare the bg their children
+//! render on, e.g. a theme provider or app shell defined in another file.
+//!
+//! ```tsx
+//! // @a11y-layout bg:bg-slate-950
+//! export function AppShell({ children }) { ... }
+//! ```
+//!
+//! Every file is pre-scanned for these annotations and the declared
+//! components are merged into the container config, so `<AppShell>` sets the
+//! context bg of its children wherever it's used. Explicit container config
+//! entries win over annotations.
+
+use std::collections::HashMap;
+
+use super::annotation_parser::parse_context_params;
+use super::tokenizer::scan_jsx;
+use super::visitor::JsxVisitor;
+use crate::types::FileInput;
+
+/// Annotation marker (`@a11y-layout bg:<class>`).
+const LAYOUT_MARKER: &str = "@a11y-layout";
+
+/// Collects `@a11y-layout` comments as (end line of the comment, bg class).
+#[derive(Default)]
+struct LayoutCommentCollector {
+    found: Vec<(u32, String)>,
+}
+
+impl JsxVisitor for LayoutCommentCollector {
+    fn on_comment(&mut self, content: &str, line: u32) {
+        // JSDoc blocks may put the marker on any line
+        let Some(params) = content.lines().find_map(|l| {
+            l.trim().trim_start_matches('*').trim_start().strip_prefix(LAYOUT_MARKER)
+        }) else {
+            return;
+        };
+        match parse_context_params(params).and_then(|ctx| ctx.bg) {
+            Some(bg) => {
+                let end_line = line + content.matches('\n').count() as u32;
+                self.found.push((end_line, bg));
+            }
+            None => log::warn!("ignoring {} without bg:<class> at line {}", LAYOUT_MARKER, line),
+        }
+    }
+}
+
+/// Component → bg declared by `@a11y-layout` annotations in `source`.
+///
+/// The annotated component is the first declaration after the comment
+/// (`function X`, `const X =`, `class X`, optionally exported).
+pub fn collect_layouts(source: &str) -> Vec<(String, String)> {
+    if !source.contains(LAYOUT_MARKER) {
+        return Vec::new();
+    }
+    let mut collector = LayoutCommentCollector::default();
+    scan_jsx(source, &mut [&mut collector as &mut dyn JsxVisitor]);
+
+    let lines: Vec<&str> = source.lines().collect();
+    let mut layouts = Vec::new();
+    for (end_line, bg) in collector.found {
+        let declared = lines
+            .iter()
+            .skip(end_line as usize)
+            .map(|l| l.trim())
+            .find(|l| !l.is_empty() && !is_comment_or_decorator(l))
+            .and_then(declared_component);
+        match declared {
+            Some(name) => layouts.push((name, bg)),
+            None => log::warn!(
+                "{} at line {} is not followed by a component declaration",
+                LAYOUT_MARKER,
+                end_line
+            ),
+        }
+    }
+    layouts
+}
+
+/// Merge annotations from every file into `container_config`; existing
+/// entries are kept.
+pub fn merge_layouts<'a>(
+    files: impl IntoIterator<Item = &'a FileInput>,
+    container_config: &mut HashMap<String, String>,
+) {
+    for file in files {
+        for (component, bg) in collect_layouts(&file.content) {
+            log::debug!("{}: {} provides {} to its children", file.path, component, bg);
+            container_config.entry(component).or_insert(bg);
+        }
+    }
+}
+
+fn is_comment_or_decorator(line: &str) -> bool {
+    ["//", "/*", "*", "{/*", "@"].iter().any(|p| line.starts_with(p))
+}
+
+/// Component name declared on `line`, if it is a declaration.
+fn declared_component(line: &str) -> Option<String> {
+    let mut rest = line;
+    for prefix in ["export ", "default ", "async "] {
+        rest = rest.strip_prefix(prefix).map_or(rest, str::trim_start);
+    }
+    let rest = ["function ", "const ", "let ", "var ", "class "]
+        .iter()
+        .find_map(|kw| rest.strip_prefix(kw))?
+        .trim_start();
+    let name: String = rest
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '$')
+        .collect();
+    name.starts_with(|c: char| c.is_ascii_uppercase()).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_annotated_function_component() {
+        let source = r#"import { ThemeProvider } from "./theme";
+
+// @a11y-layout bg:bg-slate-950
+export default function AppShell({ children }) {
+  return <ThemeProvider><main>{children}</main></ThemeProvider>;
+}"#;
+        assert_eq!(
+            collect_layouts(source),
+            vec![("AppShell".to_string(), "bg-slate-950".to_string())]
+        );
+    }
+
+    #[test]
+    fn block_comment_and_const_declaration() {
+        let source = "/**\n * Page chrome.\n * @a11y-layout bg:bg-muted\n */\nexport const PageLayout = ({ children }) => <div>{children}</div>;";
+        assert_eq!(
+            collect_layouts(source),
+            vec![("PageLayout".to_string(), "bg-muted".to_string())]
+        );
+    }
+
+    #[test]
+    fn annotation_without_declaration_or_bg_is_ignored() {
+        assert!(collect_layouts("// @a11y-layout bg:bg-card\nrender(<App />);").is_empty());
+        assert!(collect_layouts("// @a11y-layout\nfunction Shell() {}").is_empty());
+    }
+
+    #[test]
+    fn merge_keeps_explicit_config() {
+        let files = vec![FileInput {
+            path: "Shell.tsx".to_string(),
+            content: "// @a11y-layout bg:bg-slate-950\nfunction Shell() {}\n// @a11y-layout bg:bg-card\nfunction Panel() {}".to_string(),
+        }];
+        let mut config: HashMap<String, String> =
+            [("Panel".to_string(), "bg-popover".to_string())].into_iter().collect();
+        merge_layouts(&files, &mut config);
+        assert_eq!(config["Shell"], "bg-slate-950");
+        assert_eq!(config["Panel"], "bg-popover");
+    }
+}
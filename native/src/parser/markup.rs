@@ -0,0 +1,301 @@
+//! Shared rewriting of HTML-like templates (Vue, Astro, Angular) into JSX-shaped
+//! source the tokenizer already understands. Every line keeps its place so
+//! region lines match the original file.
+
+use std::collections::HashMap;
+
+use super::tokenizer::{
+    find_closing_bracket, find_tag_close, find_top_level_colon, find_unescaped, read_tag_name,
+    split_object_entries, strip_template_expressions, template_classes,
+};
+
+/// Only the line breaks of `text`.
+pub(super) fn blank(text: &str) -> String {
+    text.chars().filter(|&c| c == '\n').collect()
+}
+
+/// Offset of the next `<name` tag (not a longer tag name) at or after `from`.
+pub(super) fn find_block_tag(source: &str, open: &str, from: usize) -> Option<usize> {
+    let mut j = from;
+    while let Some(rel) = source[j..].find(open) {
+        let pos = j + rel;
+        let after = source.as_bytes().get(pos + open.len()).copied();
+        if after.is_none_or(|b| b.is_ascii_whitespace() || b == b'>' || b == b'/') {
+            return Some(pos);
+        }
+        j = pos + open.len();
+    }
+    None
+}
+
+/// Blank every `<tag ...>...</tag>` block for the given opening tags.
+pub(super) fn blank_blocks(template: &str, tags: &[&str]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut copied = 0;
+    loop {
+        let next = tags
+            .iter()
+            .filter_map(|t| find_block_tag(template, t, copied).map(|pos| (pos, *t)))
+            .min_by_key(|(pos, _)| *pos);
+        let Some((open, tag)) = next else {
+            break;
+        };
+        let close_tag = format!("</{}", &tag[1..]);
+        let body_start = find_tag_close(template, open + tag.len());
+        let end = template[body_start..]
+            .find(&close_tag)
+            .map(|p| find_tag_close(template, body_start + p + close_tag.len()))
+            .unwrap_or(template.len());
+        out.push_str(&template[copied..open]);
+        out.push_str(&blank(&template[open..end]));
+        copied = end;
+    }
+    out.push_str(&template[copied..]);
+    out
+}
+
+/// Collect `const name = '...'` string constants declared in `script`.
+pub(super) fn collect_constants(script: &str, constants: &mut HashMap<String, String>) {
+    let mut from = 0;
+    while let Some(rel) = script[from..].find("const ") {
+        let pos = from + rel;
+        from = pos + "const ".len();
+        if let Some((name, literal)) = constant_at(script, pos) {
+            constants.insert(name.to_string(), literal);
+        }
+    }
+}
+
+/// Name and value of the `const name = '...'` string constant declared at
+/// `pos` (template literals without their `${...}` expressions).
+pub(super) fn constant_at(script: &str, pos: usize) -> Option<(&str, String)> {
+    let bytes = script.as_bytes();
+    if pos > 0 && (bytes[pos - 1].is_ascii_alphanumeric() || bytes[pos - 1] == b'_') {
+        return None;
+    }
+    let rest = script[pos..].strip_prefix("const ")?;
+    let name_len = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+        .unwrap_or(rest.len());
+    let name = &rest[..name_len];
+    let value = rest[name_len..].trim_start().strip_prefix('=')?.trim_start();
+    let quote = value.bytes().next().filter(|b| matches!(b, b'"' | b'\'' | b'`'))?;
+    let end = find_unescaped(value.as_bytes(), quote, 1)?;
+    let literal = &value[1..end];
+    let literal = if quote == b'`' {
+        strip_template_expressions(literal)
+    } else {
+        literal.to_string()
+    };
+    (!name.is_empty()).then_some((name, literal))
+}
+
+/// How a template attribute contributes classes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum ClassAttr<'a> {
+    /// `class="a b"`: a class list
+    List,
+    /// `:class="..."`: an expression (see `binding_classes`)
+    Binding,
+    /// `[class.foo]="cond"`: the one class its name carries, whatever the value
+    Toggle(&'a str),
+}
+
+/// Classifies an attribute name (None = not a class attribute).
+pub(super) type ClassAttrFn = fn(&str) -> Option<ClassAttr<'_>>;
+
+/// Rewrite the class attributes and comments of a template's tags.
+/// `class_attr(name)` tells whether and how an attribute carries classes;
+/// `{...}` class lists are always expressions.
+pub(super) fn rewrite_template(template: &str, constants: &HashMap<String, String>, class_attr: ClassAttrFn) -> String {
+    let bytes = template.as_bytes();
+    let mut out = String::with_capacity(template.len());
+    let mut copied = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        // Bytes, not `template[i..]`: `i` steps through multi-byte characters
+        if bytes[i..].starts_with(b"<!--") {
+            let close = template[i + 2..].find("-->").map(|p| i + 2 + p);
+            let end = close.map_or(template.len(), |c| c + 3);
+            let body = template[i + 4..close.unwrap_or(template.len()).max(i + 4)].replace("*/", "* /");
+            out.push_str(&template[copied..i]);
+            out.push_str(&format!("{{/*{}*/}}", body));
+            copied = end;
+            i = end;
+            continue;
+        }
+        if bytes[i] == b'<' && bytes.get(i + 1).is_some_and(|b| b.is_ascii_alphabetic()) {
+            let (_, name_end) = read_tag_name(bytes, i + 1);
+            let tag_close = find_tag_close(template, name_end);
+            let attrs = class_attributes(template, name_end, tag_close, class_attr);
+            if !attrs.is_empty() {
+                out.push_str(&template[copied..attrs[0].start]);
+                let mut classes: Vec<String> = Vec::new();
+                for attr in &attrs {
+                    let found = match attr.kind {
+                        ClassAttr::List => attr.value.split_whitespace().map(str::to_string).collect(),
+                        ClassAttr::Binding => binding_classes(attr.value, constants),
+                        ClassAttr::Toggle(class) => vec![class.to_string()],
+                    };
+                    classes.extend(found);
+                }
+                out.push_str(&format!("className=\"{}\"", classes.join(" ")));
+                let mut prev_end = attrs[0].start;
+                for attr in &attrs {
+                    out.push_str(&template[prev_end..attr.start]);
+                    out.push_str(&blank(&template[attr.start..attr.end]));
+                    prev_end = attr.end;
+                }
+                copied = prev_end;
+            }
+            i = tag_close;
+            continue;
+        }
+        i += 1;
+    }
+    out.push_str(&template[copied..]);
+    out
+}
+
+/// A class attribute of a template tag.
+struct ClassAttribute<'a> {
+    start: usize,
+    end: usize,
+    value: &'a str,
+    kind: ClassAttr<'a>,
+}
+
+fn class_attributes(
+    template: &str,
+    from: usize,
+    tag_close: usize,
+    class_attr: ClassAttrFn,
+) -> Vec<ClassAttribute<'_>> {
+    let bytes = template.as_bytes();
+    let mut attrs = Vec::new();
+    let mut j = from;
+    while j < tag_close {
+        while j < tag_close && bytes[j].is_ascii_whitespace() {
+            j += 1;
+        }
+        if j >= tag_close || bytes[j] == b'>' || bytes[j] == b'/' {
+            break;
+        }
+        let start = j;
+        while j < tag_close && !bytes[j].is_ascii_whitespace() && !matches!(bytes[j], b'=' | b'>') {
+            j += 1;
+        }
+        let name = &template[start..j];
+        if j >= tag_close || bytes[j] != b'=' {
+            continue;
+        }
+        j += 1;
+        let value;
+        let mut braced = false;
+        if bytes.get(j) == Some(&b'{') {
+            let end = find_closing_bracket(bytes, j).unwrap_or(tag_close).min(tag_close);
+            value = &template[j + 1..end];
+            braced = true;
+            j = end + 1;
+        } else if matches!(bytes.get(j), Some(b'"') | Some(b'\'')) {
+            let end = find_unescaped(bytes, bytes[j], j + 1).unwrap_or(tag_close).min(tag_close);
+            value = &template[j + 1..end];
+            j = end + 1;
+        } else {
+            let value_start = j;
+            while j < tag_close && !bytes[j].is_ascii_whitespace() && bytes[j] != b'>' {
+                j += 1;
+            }
+            value = &template[value_start..j];
+        }
+        if let Some(kind) = class_attr(name) {
+            let kind = if braced && kind == ClassAttr::List { ClassAttr::Binding } else { kind };
+            attrs.push(ClassAttribute { start, end: j.min(tag_close), value, kind });
+        }
+    }
+    attrs
+}
+
+/// Classes a `:class` expression can apply: object keys, array items, string
+/// literals and `<script setup>` constants, wherever they appear.
+pub(super) fn binding_classes(expr: &str, constants: &HashMap<String, String>) -> Vec<String> {
+    let bytes = expr.as_bytes();
+    let mut classes = Vec::new();
+    let mut j = 0;
+    while j < bytes.len() {
+        match bytes[j] {
+            b'{' => {
+                let Some(close) = find_closing_bracket(bytes, j) else {
+                    break;
+                };
+                for (_, entry) in split_object_entries(expr, j, close + 1) {
+                    let key = find_top_level_colon(entry).map_or(entry, |c| &entry[..c]).trim();
+                    if key.starts_with("...") {
+                        continue;
+                    }
+                    if key.starts_with(['"', '\'', '`', '[']) {
+                        classes.extend(binding_classes(key, constants));
+                    } else {
+                        classes.extend(key.split_whitespace().map(str::to_string));
+                    }
+                }
+                j = close + 1;
+            }
+            b'[' | b'(' => {
+                let Some(close) = find_closing_bracket(bytes, j) else {
+                    break;
+                };
+                for (_, item) in split_object_entries(expr, j, close + 1) {
+                    classes.extend(binding_classes(item, constants));
+                }
+                j = close + 1;
+            }
+            quote @ (b'"' | b'\'' | b'`') => {
+                let end = find_unescaped(bytes, quote, j + 1).unwrap_or(bytes.len());
+                let literal = &expr[j + 1..end];
+                let literal = if quote == b'`' {
+                    template_classes(literal)
+                } else {
+                    literal.to_string()
+                };
+                classes.extend(literal.split_whitespace().map(str::to_string));
+                j = end + 1;
+            }
+            b if b.is_ascii_alphabetic() || b == b'_' || b == b'$' => {
+                let start = j;
+                while j < bytes.len() && (bytes[j].is_ascii_alphanumeric() || bytes[j] == b'_' || bytes[j] == b'$') {
+                    j += 1;
+                }
+                let is_member = start > 0 && bytes[start - 1] == b'.';
+                if let Some(value) = constants.get(&expr[start..j]).filter(|_| !is_member) {
+                    classes.extend(value.split_whitespace().map(str::to_string));
+                }
+            }
+            _ => j += 1,
+        }
+    }
+    classes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binding_forms() {
+        let constants = HashMap::from([("base".to_string(), "p-2 text-sm".to_string())]);
+        assert_eq!(binding_classes("'a b'", &constants), vec!["a", "b"]);
+        assert_eq!(binding_classes("{ active: isOn, 'text-red-500': err }", &constants), vec!["active", "text-red-500"]);
+        assert_eq!(binding_classes("[base, { c: x }, cond ? 'd' : 'e']", &constants), vec!["p-2", "text-sm", "c", "d", "e"]);
+        assert_eq!(binding_classes("props.base", &constants), Vec::<String>::new());
+        assert_eq!(binding_classes("unknownVar", &constants), Vec::<String>::new());
+    }
+
+    #[test]
+    fn braced_values_are_expressions() {
+        let class_attr = |name: &str| (name == "class").then_some(ClassAttr::List);
+        let constants = HashMap::from([("tone".to_string(), "text-white".to_string())]);
+        let out = rewrite_template(r#"<p class={tone} data-x="1">x</p>"#, &constants, class_attr);
+        assert_eq!(out, r#"<p className="text-white" data-x="1">x</p>"#);
+    }
+}
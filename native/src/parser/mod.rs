@@ -1,27 +1,154 @@
 pub mod visitor;
 pub mod tokenizer;
 pub mod context_tracker;
+pub mod group_tracker;
 pub mod annotation_parser;
 pub mod class_extractor;
 pub mod disabled_detector;
 pub mod current_color_resolver;
 pub mod opacity;
+pub mod layout;
+pub mod markup;
+pub mod astro;
+pub mod html;
+pub mod angular;
+pub mod cva;
+pub mod constants;
+pub mod css_modules;
+pub mod style_blocks;
+pub mod vue;
 
 use std::collections::HashMap;
+use std::ops::Range;
 
-use crate::types::ClassRegion;
+use serde::Serialize;
+
+use crate::i18n::{self, Locale, MessageKey};
+use crate::rules::required_marker::is_marker_text;
+use crate::rules::RuleVisitor;
+use crate::types::{AncestorContext, ClassLocation, ClassRegion, ExtractOptions, ParseDiagnostic, RuleFinding};
 use annotation_parser::AnnotationParser;
-use class_extractor::ClassExtractor;
-use context_tracker::ContextTracker;
+use class_extractor::{ClassExtractor, RegionTraits};
+use constants::Library;
+use context_tracker::{is_fragment, ContextTracker};
+use css_modules::CssModuleMap;
+use style_blocks::Stylesheet;
 use current_color_resolver::CurrentColorResolver;
+use group_tracker::{GroupContext, GroupTracker};
 use disabled_detector::{is_disabled_tag, has_disabled_variant};
-use visitor::JsxVisitor;
+use visitor::{ClassSource, JsxVisitor};
 
 /// Elements below this cumulative opacity threshold are considered invisible
 /// and excluded from contrast checking. WCAG does not require contrast for
 /// content that is not perceivable.
 const OPACITY_VISIBILITY_THRESHOLD: f32 = 0.1;
 
+/// Per-run parser settings, shared read-only by every file of a scan.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ScanConfig {
+    /// Container component → bg class
+    pub container_config: HashMap<String, String>,
+    /// US-04: Portal component → bg class or "reset"
+    pub portal_config: HashMap<String, String>,
+    pub default_bg: String,
+    /// Context name → bg class, for `@a11y-context use:<name>`
+    pub named_contexts: HashMap<String, String>,
+    /// Locale of generated ignore reasons
+    pub locale: Locale,
+    /// Synthetic ancestors (outermost first) the scanned source renders inside
+    pub ancestors: Vec<AncestorContext>,
+    /// Fold identical regions within a file (see `ClassExtractor::with_dedup`)
+    pub dedup_regions: bool,
+    /// Non-contrast rules to run (see `rules::RuleVisitor`)
+    pub rules: Vec<String>,
+    /// Custom props extracted like `className` (see `ExtractOptions.class_attributes`)
+    pub class_attributes: Vec<String>,
+    /// Helpers extracted like `cn()` (see `ExtractOptions.class_functions`)
+    pub class_functions: Vec<String>,
+    /// `--name` → value, for `var()` in inline styles (see `ExtractOptions.css_variables`)
+    pub css_variables: HashMap<String, String>,
+    /// CSS Modules class colors (see `ExtractOptions.css_modules`)
+    pub css_modules: CssModuleMap,
+    /// Class constants of the library files (see `ExtractOptions.library_files`)
+    pub library: Library,
+    /// Emit dark-mode twin regions (see `ExtractOptions.dark_variants`)
+    pub dark_variants: bool,
+    /// Standalone class helper calls policy (see `ExtractOptions.standalone_classes`)
+    pub standalone_classes: StandaloneClasses,
+    /// Emit per-breakpoint twin regions (see `ExtractOptions.breakpoint_variants`)
+    pub breakpoint_variants: bool,
+    /// Emit per-state twin regions (see `ExtractOptions.state_variants`)
+    pub state_variants: bool,
+    /// Max lines from an annotation to its element (see `ExtractOptions.annotation_max_distance`)
+    pub annotation_max_distance: Option<u32>,
+}
+
+/// What to do with standalone `cn()` / `clsx()` / `cva()` calls, which have
+/// no element and so fall back to the default bg.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum StandaloneClasses {
+    #[default]
+    Scan,
+    /// Scan them, but report their pairs as ignored
+    Ignore,
+    /// Only scan calls with a preceding `@a11y-context` annotation
+    Annotated,
+    Skip,
+}
+
+impl StandaloneClasses {
+    /// "scan" | "ignore" | "annotated" | "skip" (anything else scans).
+    pub fn parse(value: Option<&str>) -> Self {
+        match value {
+            Some("ignore") => Self::Ignore,
+            Some("annotated") => Self::Annotated,
+            Some("skip") => Self::Skip,
+            _ => Self::Scan,
+        }
+    }
+}
+
+impl ScanConfig {
+    pub fn from_options(options: &ExtractOptions) -> Self {
+        let to_map = |entries: &[crate::types::ContainerEntry]| {
+            entries
+                .iter()
+                .map(|e| (e.component.clone(), e.bg_class.clone()))
+                .collect()
+        };
+        Self {
+            container_config: to_map(&options.container_config),
+            portal_config: to_map(&options.portal_config),
+            default_bg: options.default_bg.clone(),
+            named_contexts: named_contexts(options.named_contexts.as_deref()),
+            locale: Locale::parse(options.locale.as_deref()),
+            ancestors: Vec::new(),
+            dedup_regions: options.dedup_regions == Some(true),
+            rules: options.rules.clone().unwrap_or_default(),
+            class_attributes: options.class_attributes.clone().unwrap_or_default(),
+            class_functions: options.class_functions.clone().unwrap_or_default(),
+            css_variables: options
+                .css_variables
+                .iter()
+                .flatten()
+                .map(|v| (format!("--{}", v.name.trim_start_matches("--")), v.value.clone()))
+                .collect(),
+            css_modules: css_modules::build_map(options.css_modules.as_deref().unwrap_or_default()),
+            library: Library::from_files(options.library_files.as_deref().unwrap_or_default()),
+            dark_variants: options.dark_variants == Some(true),
+            standalone_classes: StandaloneClasses::parse(options.standalone_classes.as_deref()),
+            breakpoint_variants: options.breakpoint_variants == Some(true),
+            state_variants: options.state_variants == Some(true),
+            annotation_max_distance: options.annotation_max_distance,
+        }
+    }
+}
+
+/// Name → bg class of `named_contexts`.
+pub fn named_contexts(named_contexts: Option<&[crate::types::NamedContext]>) -> HashMap<String, String> {
+    named_contexts.unwrap_or_default().iter().map(|c| (c.name.clone(), c.bg_class.clone())).collect()
+}
+
 /// Combined orchestrator that owns all parser sub-components and coordinates
 /// cross-visitor state flow during JSX scanning.
 ///
@@ -31,100 +158,307 @@ const OPACITY_VISIBILITY_THRESHOLD: f32 = 0.1;
 ///   2. AnnotationParser → provides pending `@a11y-context` / `a11y-ignore`
 ///   3. DisabledDetector → checks `disabled` / `aria-disabled` in the raw tag
 ///   4. CurrentColorResolver → tracks inherited text color (for US-08)
-///   5. ClassExtractor → receives all the above and builds ClassRegion objects
+///   5. GroupTracker → relates `group-hover:` / `peer-*` classes to their elements
+///   6. ClassExtractor → receives all the above and builds ClassRegion objects
 struct ScanOrchestrator {
     context_tracker: ContextTracker,
+    group_tracker: GroupTracker,
     annotation_parser: AnnotationParser,
     class_extractor: ClassExtractor,
     current_color: CurrentColorResolver,
-    /// The context_bg captured BEFORE the most recent on_tag_open.
+    /// The context_bg (light, dark) captured BEFORE the most recent on_tag_open.
     /// Used so a tag's own className region gets the parent's bg, not its own.
     /// Set in on_tag_open, consumed by the next on_class_attribute.
-    pre_tag_open_bg: Option<String>,
+    pre_tag_open_bg: Option<(String, String)>,
+    /// Name of the most recent tag opened, e.g. "Button"
+    tag_name: String,
+    /// Enclosing open tags, outermost first (synthetic ancestors included,
+    /// fragments skipped)
+    open_tags: Vec<String>,
+    /// Regions recorded before the most recent tag opened (its own regions follow)
+    tag_regions_start: usize,
+    /// A class attribute of the most recent tag was recorded
+    tag_has_region: bool,
+    /// Whether the most recent tag opened is not self-closing (its text follows)
+    open_tag_has_children: bool,
+    /// The last region belongs to the innermost open tag and no tag event
+    /// came since, so the next text is that element's own text
+    region_owns_next_text: bool,
+    /// Raw tag of the most recent tag opened when a `<style>` element rule
+    /// colors it, until its text (see `on_text`)
+    styled_tag: Option<String>,
+    /// How the class value being reported was captured (see `on_class_source`)
+    class_source: ClassSource,
+    /// Class positions of the `cn()` call being reported (see `on_class_locations`)
+    class_locations: Vec<ClassLocation>,
+    /// `cva()` variant branch of the class value being reported
+    variant_key: Option<String>,
+    /// The class value being reported is one branch of a ternary
+    conditional: bool,
+    /// Malformed source reported by the tokenizer (see `on_diagnostic`)
+    diagnostics: Vec<ParseDiagnostic>,
+    /// Give regions dark-mode twins (see `ScanConfig.dark_variants`)
+    dark_variants: bool,
+    /// Resolves `className={styles.card}` (see `ScanConfig.css_modules`)
+    css_modules: CssModuleMap,
+    standalone_classes: StandaloneClasses,
+    locale: Locale,
+    /// Element whose class attribute was recorded last (see `RecordedElement`)
+    last_element: Option<RecordedElement>,
+    /// Regions of the element a pending annotation trails on the same line,
+    /// with that line: the annotation is theirs unless an element follows on it
+    trailing: Option<(u32, Range<usize>)>,
+    annotation_max_distance: Option<u32>,
+}
+
+/// The element last given regions, for annotations formatters move onto
+/// its line after it (`<Badge className="…" /> {/* a11y-ignore */}`).
+struct RecordedElement {
+    /// Line the tag starts on
+    line: u32,
+    regions: Range<usize>,
+    /// Open tags while it is open
+    depth: usize,
+    closed: bool,
 }
 
 impl ScanOrchestrator {
-    fn new(
-        container_config: HashMap<String, String>,
-        portal_config: HashMap<String, String>,
-        default_bg: String,
-    ) -> Self {
+    fn new(config: &ScanConfig, stylesheet: Stylesheet) -> Self {
+        let mut context_tracker = ContextTracker::new_with_portals(
+            config.container_config.clone(),
+            config.portal_config.clone(),
+            config.default_bg.clone(),
+        )
+        .with_named_contexts(config.named_contexts.clone());
+        context_tracker.seed_ancestors(&config.ancestors);
         Self {
-            context_tracker: ContextTracker::new_with_portals(container_config, portal_config, default_bg),
+            context_tracker,
+            group_tracker: GroupTracker::new(),
             annotation_parser: AnnotationParser::new(),
-            class_extractor: ClassExtractor::new(),
+            class_extractor: ClassExtractor::with_locale(config.locale)
+                .with_dedup(config.dedup_regions)
+                .with_css_variables(config.css_variables.clone())
+                .with_stylesheet(stylesheet)
+                .with_breakpoint_variants(config.breakpoint_variants)
+                .with_state_variants(config.state_variants),
             current_color: CurrentColorResolver::new(),
             pre_tag_open_bg: None,
+            tag_name: String::new(),
+            open_tags: config
+                .ancestors
+                .iter()
+                .filter_map(|a| a.component.clone().filter(|c| !c.is_empty()))
+                .collect(),
+            tag_regions_start: 0,
+            tag_has_region: false,
+            open_tag_has_children: false,
+            region_owns_next_text: false,
+            styled_tag: None,
+            class_source: ClassSource::Attribute,
+            class_locations: Vec::new(),
+            variant_key: None,
+            conditional: false,
+            diagnostics: Vec::new(),
+            dark_variants: config.dark_variants,
+            css_modules: config.css_modules.clone(),
+            standalone_classes: config.standalone_classes,
+            locale: config.locale,
+            last_element: None,
+            trailing: None,
+            annotation_max_distance: config.annotation_max_distance,
         }
     }
 
-    fn into_regions(self) -> Vec<ClassRegion> {
-        self.class_extractor.into_regions()
+    /// Regions (all ignored under `a11y-ignore-file`), diagnostics (with any
+    /// unclosed `a11y-ignore-start`) and the file's `a11y-ignore-file` reason.
+    fn into_parts(mut self) -> (Vec<ClassRegion>, Vec<ParseDiagnostic>, Option<String>) {
+        if let Some((_, regions)) = self.trailing.take() {
+            self.annotate_trailing(regions);
+        }
+        if let Some(line) = self.annotation_parser.unclosed_range() {
+            self.diagnostics.push(ParseDiagnostic {
+                line,
+                kind: "unclosed-ignore-range".to_string(),
+                message: i18n::message(self.locale, MessageKey::IgnoreRangeUnclosed).to_string(),
+            });
+        }
+        let file_ignore = self.annotation_parser.file_ignore().map(|reason| {
+            if reason.is_empty() {
+                i18n::message(self.locale, MessageKey::IgnoreSuppressed).to_string()
+            } else {
+                reason.to_string()
+            }
+        });
+        let mut regions = self.class_extractor.into_regions();
+        if let Some(reason) = &file_ignore {
+            // Element suppressions keep their reason unless scoped to some rules
+            for region in regions.iter_mut().filter(|r| r.ignored != Some(true) || r.ignored_rules.is_some()) {
+                region.ignored = Some(true);
+                region.ignore_reason = Some(reason.clone());
+                region.ignored_rules = None;
+            }
+        }
+        (regions, self.diagnostics, file_ignore)
     }
+
+    /// Give the pending annotations to the element they trail (see `trailing`).
+    fn annotate_trailing(&mut self, regions: Range<usize>) {
+        let context_override = self.annotation_parser.take_pending_context();
+        let ignore_reason = self.annotation_parser.take_pending_ignore();
+        let ignore_rules = self.annotation_parser.take_pending_ignore_rules();
+        self.class_extractor.annotate(regions, context_override, ignore_reason, ignore_rules);
+    }
+}
+
+/// Line a tag starts on, given its class attribute `value` is on `line`:
+/// a tag wrapped by a formatter spans lines before its class attribute.
+fn tag_start_line(raw_tag: &str, value: &str, line: u32) -> u32 {
+    let at = Some(value)
+        .filter(|v| !v.is_empty())
+        .and_then(|v| raw_tag.find(v))
+        .or_else(|| raw_tag.find("class"))
+        .unwrap_or(0);
+    line.saturating_sub(raw_tag[..at].matches('\n').count() as u32)
 }
 
 impl JsxVisitor for ScanOrchestrator {
     fn on_tag_open(&mut self, tag_name: &str, is_self_closing: bool, raw_tag: &str) {
+        log::trace!("tag open <{}> self_closing={}", tag_name, is_self_closing);
         // 1. Resolve pending @a11y-context-block (part of parent context)
         self.context_tracker.resolve_pending_block(tag_name, is_self_closing);
         // 2. Capture bg AFTER block annotation, BEFORE tag's own bg modifies context
-        self.pre_tag_open_bg = Some(self.context_tracker.current_bg().to_string());
+        //    (a self-closing `@a11y-context use:<name>` element has no entry to push)
+        self.pre_tag_open_bg = Some(match self.context_tracker.take_detached_bg() {
+            Some(bg) => (bg.clone(), bg),
+            None => (
+                self.context_tracker.current_bg().to_string(),
+                self.context_tracker.current_dark_bg().to_string(),
+            ),
+        });
         // 3. Process tag's own bg (container config, explicit bg-* class)
         self.context_tracker.on_tag_open(tag_name, is_self_closing, raw_tag);
         self.current_color.on_tag_open(tag_name, is_self_closing, raw_tag);
+        self.group_tracker.on_tag_open(tag_name, is_self_closing, raw_tag);
+        self.open_tag_has_children = !is_self_closing;
+        self.region_owns_next_text = false;
+        tag_name.clone_into(&mut self.tag_name);
+        if !is_self_closing && !is_fragment(tag_name) {
+            self.open_tags.push(tag_name.to_string());
+        }
+        self.tag_regions_start = self.class_extractor.regions().len();
+        self.tag_has_region = false;
+        self.styled_tag = self.class_extractor.styles_element(tag_name).then(|| raw_tag.to_string());
     }
 
     fn on_tag_close(&mut self, tag_name: &str) {
+        log::trace!("tag close </{}>", tag_name);
+        self.region_owns_next_text = false;
+        self.styled_tag = None;
         self.context_tracker.on_tag_close(tag_name);
         self.current_color.on_tag_close(tag_name);
+        self.group_tracker.on_tag_close(tag_name);
+        if let Some(idx) = self.open_tags.iter().rposition(|t| t == tag_name) {
+            self.open_tags.truncate(idx);
+        }
+        if let Some(element) = self.last_element.as_mut().filter(|e| self.open_tags.len() < e.depth) {
+            element.closed = true;
+        }
     }
 
     fn on_comment(&mut self, content: &str, line: u32) {
+        log::trace!("comment L{}: {}", line, content.trim());
         self.context_tracker.on_comment(content, line);
         self.annotation_parser.on_comment(content, line);
+        if self.annotation_parser.pending_line() == Some(line) {
+            self.trailing = self
+                .last_element
+                .as_ref()
+                .filter(|e| e.closed && e.line == line)
+                .map(|e| (line, e.regions.clone()));
+        }
     }
 
     fn on_class_attribute(&mut self, value: &str, line: u32, raw_tag: &str) {
+        let class_locations = std::mem::take(&mut self.class_locations);
+        let variant_key = self.variant_key.take();
+        let conditional = std::mem::take(&mut self.conditional);
+
         // 1. Get context bg: use pre-open bg if this is on the same tag that just
         //    opened (the tag's own className should use the parent's bg, not its own).
         //    For standalone cn() calls (empty raw_tag), use the current tracker bg.
-        let context_bg = if !raw_tag.is_empty() {
-            self.pre_tag_open_bg.take()
-                .unwrap_or_else(|| self.context_tracker.current_bg().to_string())
+        let current = || {
+            (self.context_tracker.current_bg().to_string(), self.context_tracker.current_dark_bg().to_string())
+        };
+        let (context_bg, dark_context_bg) = if !raw_tag.is_empty() {
+            self.pre_tag_open_bg.take().unwrap_or_else(current)
         } else {
-            self.context_tracker.current_bg().to_string()
+            current()
         };
 
-        // 2. Consume pending annotations
+        // 2. Consume pending annotations: one trailing the previous element on its
+        //    line is that element's unless this one starts on the line too, and
+        //    ones too far above this element are dropped
+        let tag_line = tag_start_line(raw_tag, value, line);
+        if let Some((trailing_line, regions)) = self.trailing.take() {
+            if trailing_line != tag_line {
+                self.annotate_trailing(regions);
+            }
+        }
+        if let Some(max_distance) = self.annotation_max_distance {
+            self.annotation_parser.expire_pending(tag_line, max_distance);
+        }
         let context_override = self.annotation_parser.take_pending_context();
-        let ignore_reason = self.annotation_parser.take_pending_ignore();
-
-        // 3. Check for disabled elements (US-07)
-        let is_disabled = is_disabled_tag(raw_tag) || has_disabled_variant(value);
-        let final_ignore_reason = if is_disabled && ignore_reason.is_none() {
-            Some("disabled element (WCAG SC 1.4.3 exemption)".to_string())
-        } else {
-            ignore_reason
+        let (ignore_reason, ignore_rules) = self.annotation_parser.take_ignore();
+
+        // 3. Standalone helper calls policy; disabled elements (US-07)
+        let standalone = self.class_source == ClassSource::Standalone;
+        let ignore_key = match self.standalone_classes {
+            StandaloneClasses::Skip if standalone => return,
+            StandaloneClasses::Annotated if standalone && context_override.is_none() => return,
+            StandaloneClasses::Ignore if standalone => Some(MessageKey::IgnoreStandalone),
+            _ if is_disabled_tag(raw_tag) || has_disabled_variant(value) => Some(MessageKey::IgnoreDisabled),
+            _ => None,
+        };
+        let final_ignore_reason = match ignore_key {
+            Some(key) if ignore_reason.is_none() => Some(i18n::message(self.locale, key).to_string()),
+            _ => ignore_reason,
         };
 
         // 4. US-05: Get cumulative opacity (element's own, captured AFTER on_tag_open)
         let effective_opacity = Some(self.context_tracker.current_opacity());
 
         // 5. US-05: Visibility threshold — mark invisible elements as ignored
-        let final_ignore_reason = if final_ignore_reason.is_none()
-            && effective_opacity.map_or(false, |o| o < OPACITY_VISIBILITY_THRESHOLD)
-        {
-            Some(format!(
-                "invisible (effective opacity {:.0}% < {}% threshold)",
-                effective_opacity.unwrap() * 100.0,
-                (OPACITY_VISIBILITY_THRESHOLD * 100.0) as u32,
-            ))
-        } else {
-            final_ignore_reason
+        let final_ignore_reason = match effective_opacity {
+            Some(o) if final_ignore_reason.is_none() && o < OPACITY_VISIBILITY_THRESHOLD => {
+                Some(i18n::format_message(
+                    self.locale,
+                    MessageKey::IgnoreInvisible,
+                    &[
+                        &format!("{:.0}", o * 100.0),
+                        &((OPACITY_VISIBILITY_THRESHOLD * 100.0) as u32).to_string(),
+                    ],
+                ))
+            }
+            _ => final_ignore_reason,
         };
 
+        log::trace!(
+            "class attribute L{} bg={} ignored={:?}: {}",
+            line,
+            context_bg,
+            final_ignore_reason,
+            value
+        );
+
         // 6. Build ClassRegion via ClassExtractor
-        self.class_extractor.record(
+        let group = if raw_tag.is_empty() {
+            GroupContext::default()
+        } else {
+            let is_open = self.open_tag_has_children && !is_fragment(&self.tag_name);
+            let own_bg = self.context_tracker.current_bg();
+            self.group_tracker.on_element_classes(value, is_open, &context_bg, own_bg)
+        };
+        self.class_extractor.record_with_traits(
             value,
             line,
             raw_tag,
@@ -132,7 +466,112 @@ impl JsxVisitor for ScanOrchestrator {
             context_override,
             final_ignore_reason,
             effective_opacity,
+            RegionTraits {
+                variant_key,
+                conditional,
+                ignored_rules: ignore_rules,
+                group,
+                dark_context_bg: self.dark_variants.then_some(dark_context_bg),
+            },
         );
+        let mut ancestry = self.open_tags.clone();
+        if !raw_tag.is_empty() {
+            self.class_extractor.set_tag_name(&self.tag_name);
+            // A self-closing element isn't on the open-tag stack
+            if !self.open_tag_has_children && !is_fragment(&self.tag_name) {
+                ancestry.push(self.tag_name.clone());
+            }
+        }
+        self.class_extractor.set_ancestry(ancestry);
+        self.class_extractor.set_provenance(self.class_source);
+        if !class_locations.is_empty() {
+            self.class_extractor.set_class_locations(class_locations);
+        }
+        self.region_owns_next_text = !raw_tag.is_empty() && self.open_tag_has_children;
+        self.tag_has_region |= !raw_tag.is_empty();
+        if !raw_tag.is_empty() {
+            self.last_element = Some(RecordedElement {
+                line: tag_line,
+                regions: self.tag_regions_start..self.class_extractor.regions().len(),
+                depth: self.open_tags.len(),
+                closed: !self.open_tag_has_children,
+            });
+        }
+    }
+
+    fn on_class_source(&mut self, source: ClassSource) {
+        self.class_source = source;
+    }
+
+    fn on_class_locations(&mut self, locations: &[ClassLocation]) {
+        self.class_locations = locations.to_vec();
+    }
+
+    fn on_variant_class(&mut self, value: &str, line: u32, variant_key: &str) {
+        self.variant_key = Some(variant_key.to_string());
+        self.on_class_attribute(value, line, "");
+    }
+
+    fn on_conditional_class(&mut self, value: &str, line: u32, raw_tag: &str) {
+        // Every branch sits on the parent's bg, not only the first one
+        let pre_tag_open_bg = self.pre_tag_open_bg.clone();
+        self.conditional = true;
+        self.on_class_attribute(value, line, raw_tag);
+        self.pre_tag_open_bg = pre_tag_open_bg;
+    }
+
+    fn on_variant_map_class(&mut self, value: &str, line: u32, variant_key: &str, raw_tag: &str) {
+        // Like ternary branches, every entry sits on the parent's bg
+        let pre_tag_open_bg = self.pre_tag_open_bg.clone();
+        self.variant_key = Some(variant_key.to_string());
+        self.on_class_attribute(value, line, raw_tag);
+        self.pre_tag_open_bg = pre_tag_open_bg;
+    }
+
+    fn on_multiline_class(&mut self, value: &str, line: u32, end_line: u32, raw_tag: &str) {
+        self.on_class_attribute(value, line, raw_tag);
+        self.class_extractor.set_end_line(line, end_line);
+    }
+
+    fn on_css_module_class(&mut self, module: &str, class_name: &str, line: u32, raw_tag: &str) {
+        match css_modules::resolve(&self.css_modules, module, class_name) {
+            Some(classes) => {
+                let classes = classes.to_string();
+                self.class_source = ClassSource::CssModule;
+                self.on_class_attribute(&classes, line, raw_tag);
+            }
+            None => self.on_dynamic_class(line, raw_tag),
+        }
+    }
+
+    fn on_dynamic_class(&mut self, line: u32, raw_tag: &str) {
+        // No class attribute to flag: a synthetic empty region marks the gap
+        if !self.tag_has_region {
+            self.class_source = ClassSource::Attribute;
+            self.on_class_attribute("", line, raw_tag);
+        }
+        self.class_extractor.mark_unresolved_dynamic_class(self.tag_regions_start);
+    }
+
+    fn on_diagnostic(&mut self, diagnostic: &ParseDiagnostic) {
+        log::debug!("line {}: {}", diagnostic.line, diagnostic.message);
+        self.diagnostics.push(diagnostic.clone());
+    }
+
+    fn on_text(&mut self, text: &str, line: u32) {
+        // `p { color: ... }` in a <style> block: a classless element's text is checked too
+        if let Some(raw_tag) = self.styled_tag.take().filter(|_| !self.tag_has_region) {
+            self.class_source = ClassSource::Stylesheet;
+            self.on_class_attribute("", line, &raw_tag);
+        }
+        // `<span className="text-destructive">*</span>`: flag the marker's region
+        if std::mem::take(&mut self.region_owns_next_text) && is_marker_text(text) {
+            self.class_extractor.mark_required_marker();
+            // A duplicate split out of the region it was folded into is the element's
+            if let Some(element) = &mut self.last_element {
+                element.regions.end = self.class_extractor.regions().len();
+            }
+        }
     }
 }
 
@@ -153,15 +592,77 @@ pub fn scan_file(
     portal_config: &HashMap<String, String>,
     default_bg: &str,
 ) -> Vec<ClassRegion> {
-    let mut orchestrator = ScanOrchestrator::new(
-        container_config.clone(),
-        portal_config.clone(),
-        default_bg.to_string(),
-    );
+    let config = ScanConfig {
+        container_config: container_config.clone(),
+        portal_config: portal_config.clone(),
+        default_bg: default_bg.to_string(),
+        ..ScanConfig::default()
+    };
+    scan_file_with_config(source, &config)
+}
 
-    tokenizer::scan_jsx(source, &mut [&mut orchestrator as &mut dyn JsxVisitor]);
+/// Like `scan_file`, with every per-run setting taken from `config`.
+pub fn scan_file_with_config(source: &str, config: &ScanConfig) -> Vec<ClassRegion> {
+    scan_file_with_findings(source, config).0
+}
 
-    orchestrator.into_regions()
+/// Regions, rule findings, parse diagnostics and `a11y-ignore-file` reason of one file.
+pub type ScanOutput = (Vec<ClassRegion>, Vec<RuleFinding>, Vec<ParseDiagnostic>, Option<String>);
+
+/// File suffixes `scan_source` has a parser mode for (anything else is read
+/// as JSX/TSX).
+pub const FILE_TYPES: &[&str] = &[".tsx", ".jsx", ".ts", ".js", ".vue", ".astro", ".component.html", ".html", ".htm"];
+
+/// Scan `source` in the parser mode its path calls for: `.vue`, `.astro`,
+/// Angular `.component.html` and `.html` files are rewritten into JSX-shaped
+/// source by their module's `to_jsx` first, everything else is scanned as JSX/TSX.
+pub fn scan_source(path: &str, source: &str, config: &ScanConfig) -> ScanOutput {
+    // Templates blank their <style> blocks: read them from the original source
+    let jsx = if vue::is_vue_path(path) {
+        vue::to_jsx(source)
+    } else if astro::is_astro_path(path) {
+        astro::to_jsx(source)
+    } else if angular::is_angular_path(path) {
+        angular::to_jsx(source)
+    } else if html::is_html_path(path) {
+        html::to_jsx(source)
+    } else {
+        return scan_file_with_findings(source, config);
+    };
+    scan_with_stylesheet(&jsx, config, Stylesheet::of_source(source))
+}
+
+/// Like `scan_file_with_config`, also running the rules in `config.rules`
+/// over the same tokenizer pass.
+pub fn scan_file_with_findings(source: &str, config: &ScanConfig) -> ScanOutput {
+    scan_with_stylesheet(source, config, Stylesheet::of_source(source))
+}
+
+fn scan_with_stylesheet(source: &str, config: &ScanConfig, stylesheet: Stylesheet) -> ScanOutput {
+    let mut orchestrator = ScanOrchestrator::new(config, stylesheet);
+
+    if config.rules.is_empty() {
+        tokenizer::scan_jsx_with_library(
+            source,
+            &config.class_attributes,
+            &config.class_functions,
+            &config.library,
+            &mut [&mut orchestrator as &mut dyn JsxVisitor],
+        );
+        let (regions, diagnostics, file_ignore) = orchestrator.into_parts();
+        return (regions, Vec::new(), diagnostics, file_ignore);
+    }
+
+    let mut rules = RuleVisitor::new(&config.rules, config.locale);
+    tokenizer::scan_jsx_with_library(
+        source,
+        &config.class_attributes,
+        &config.class_functions,
+        &config.library,
+        &mut [&mut orchestrator as &mut dyn JsxVisitor, &mut rules as &mut dyn JsxVisitor],
+    );
+    let (regions, diagnostics, file_ignore) = orchestrator.into_parts();
+    (regions, rules.into_findings(), diagnostics, file_ignore)
 }
 
 #[cfg(test)]
@@ -284,6 +785,129 @@ mod integration_tests {
         assert_eq!(regions[0].context_bg, "bg-slate-900");
     }
 
+    #[test]
+    fn a11y_context_use_leaves_the_bg_stack() {
+        let source = r##"<Card>
+    {/* @a11y-context use:overlay */}
+    <div className="absolute text-white opacity-90">
+        <span className="text-gray-100">toast</span>
+    </div>
+    {/* @a11y-context use:overlay */}
+    <Badge className="absolute text-white" />
+    {/* @a11y-context use:unknown */}
+    <span className="text-black">card</span>
+</Card>"##;
+        let config = ScanConfig {
+            container_config: make_config(&[("Card", "bg-card")]),
+            named_contexts: HashMap::from([("overlay".to_string(), "bg-slate-900".to_string())]),
+            default_bg: "bg-background".to_string(),
+            ..ScanConfig::default()
+        };
+        let regions = scan_file_with_config(source, &config);
+        let bgs: Vec<&str> = regions.iter().map(|r| r.context_bg.as_str()).collect();
+        assert_eq!(bgs, vec!["bg-slate-900", "bg-slate-900", "bg-slate-900", "bg-card"]);
+    }
+
+    #[test]
+    fn style_block_rules_color_elements() {
+        let source = r##"<div className="bg-white">
+    <p>plain</p>
+    <p className="note" style={{ color: "#000000" }}>inline wins</p>
+    <span className="hint">hint</span>
+    <span>unstyled</span>
+</div>
+<style jsx>{`
+    p { color: #9ca3af; }
+    .hint { color: #d1d5db; }
+`}</style>"##;
+        let regions = scan_file(source, &make_config(&[]), &HashMap::new(), "bg-background");
+        let colors: Vec<(&str, Option<&str>)> =
+            regions.iter().map(|r| (r.content.as_str(), r.inline_color.as_deref())).collect();
+        assert_eq!(
+            colors,
+            vec![("bg-white", None), ("", Some("#9ca3af")), ("note", Some("#000000")), ("hint", Some("#d1d5db"))]
+        );
+        assert_eq!(regions[1].provenance.as_deref(), Some("stylesheet"));
+        assert_eq!(regions[1].context_bg, "bg-white");
+        assert_eq!(regions[1].tag_name, "p");
+    }
+
+    #[test]
+    fn fragments_are_transparent() {
+        let source = r##"{/* @a11y-context-block bg:bg-slate-900 */}
+<>
+    <p className="text-white">a</p>
+    <React.Fragment key={id}>
+        <Card><span className="text-black">b</span></Card>
+        <span className="text-white">c</span>
+    </React.Fragment>
+</>
+<p className="text-gray-900">d</p>"##;
+        let config = make_config(&[("Card", "bg-card"), ("Fragment", "bg-red-500")]);
+        let regions = scan_file(source, &config, &HashMap::new(), "bg-background");
+        let bgs: Vec<&str> = regions.iter().map(|r| r.context_bg.as_str()).collect();
+        assert_eq!(bgs, vec!["bg-slate-900", "bg-card", "bg-slate-900", "bg-background"]);
+    }
+
+    #[test]
+    fn multiline_class_values_keep_their_line_range() {
+        let source = r#"<div
+  className="bg-white
+    text-gray-900 p-4"
+>
+  <p className={cn(
+    "text-gray-500",
+    active && "font-bold",
+  )}>a</p>
+  <span className={`text-black ${tone}`}>b</span>
+</div>"#;
+        let regions = scan_file(source, &make_config(&[]), &HashMap::new(), "bg-background");
+        let ranges: Vec<(u32, Option<u32>)> = regions.iter().map(|r| (r.start_line, r.end_line)).collect();
+        assert_eq!(ranges, vec![(2, Some(3)), (5, Some(8)), (9, None)]);
+    }
+
+    #[test]
+    fn regions_record_their_tag_name() {
+        let source = r#"<Card className="bg-card">
+  <Button className="text-white" />
+  <>
+    <motion.p className="text-gray-900">a</motion.p>
+  </>
+</Card>
+const styles = cn("text-black");
+React.createElement("span", { className: "text-gray-500" });"#;
+        let regions = scan_file(source, &make_config(&[]), &HashMap::new(), "bg-background");
+        let tags: Vec<&str> = regions.iter().map(|r| r.tag_name.as_str()).collect();
+        assert_eq!(tags, vec!["Card", "Button", "motion.p", "", "span"]);
+    }
+
+    #[test]
+    fn regions_record_their_ancestry() {
+        let source = r#"<Card>
+  <CardContent>
+    <>
+      <p className="text-gray-500">a</p>
+      <Icon className="text-gray-400" />
+    </>
+  </CardContent>
+  <span className={cn("text-black")}>b</span>
+</Card>"#;
+        let config = ScanConfig {
+            ancestors: vec![AncestorContext { component: Some("Dialog".to_string()), ..Default::default() }],
+            ..Default::default()
+        };
+        let regions = scan_file_with_config(source, &config);
+        let paths: Vec<String> = regions.iter().map(|r| r.ancestry.join(" > ")).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "Dialog > Card > CardContent > p",
+                "Dialog > Card > CardContent > Icon",
+                "Dialog > Card > span",
+            ]
+        );
+    }
+
     #[test]
     fn a11y_context_consumed_once() {
         let source = "// @a11y-context bg:#09090b\n<div className=\"text-white\">x</div>\n<div className=\"text-gray\">y</div>";
@@ -313,6 +937,56 @@ mod integration_tests {
         assert_eq!(regions[0].ignore_reason, Some("suppressed".to_string()));
     }
 
+    // ── Formatter-moved annotations ──
+
+    fn annotated(source: &str, config: &ScanConfig) -> Vec<(String, Option<String>, Option<String>)> {
+        scan_file_with_config(source, config)
+            .into_iter()
+            .map(|r| (r.content, r.ignore_reason, r.context_override_bg))
+            .collect()
+    }
+
+    #[test]
+    fn comment_trailing_a_closed_element_is_its_own() {
+        let config = ScanConfig { default_bg: "bg-background".into(), ..Default::default() };
+        let source = r##"<Badge className="text-gray-400" /> {/* a11y-ignore: decorative */}
+<p className="text-white">x</p> {/* @a11y-context bg:#000 */}
+<span className="text-gray-500">y</span>"##;
+        assert_eq!(
+            annotated(source, &config),
+            [
+                ("text-gray-400".into(), Some("decorative".into()), None),
+                ("text-white".into(), None, Some("#000".into())),
+                ("text-gray-500".into(), None, None),
+            ]
+        );
+        // Before an element on the same line, or inside an open element: the next element's
+        let source = r##"<i className="text-gray-400" /> {/* a11y-ignore */} <b className="text-gray-300" />
+<div className="bg-white"> {/* a11y-ignore: child */}
+  <p className="text-gray-300">z</p>
+</div>"##;
+        let reasons: Vec<Option<String>> = annotated(source, &config).into_iter().map(|r| r.1).collect();
+        assert_eq!(reasons, [None, Some("suppressed".into()), None, Some("child".into())]);
+    }
+
+    #[test]
+    fn annotation_distance_counts_to_the_wrapped_tag_start() {
+        let source = r##"{/* @a11y-context bg:#000 */}
+
+<Button
+  variant="ghost"
+  className="text-white"
+>
+  x
+</Button>"##;
+        let within = ScanConfig { annotation_max_distance: Some(2), ..Default::default() };
+        assert_eq!(annotated(source, &within)[0].2.as_deref(), Some("#000"));
+        let too_far = ScanConfig { annotation_max_distance: Some(1), ..Default::default() };
+        assert_eq!(annotated(source, &too_far)[0].2, None);
+        // Unset: no limit
+        assert_eq!(annotated(source, &ScanConfig::default())[0].2.as_deref(), Some("#000"));
+    }
+
     // ── Disabled element detection (US-07) ──
 
     #[test]
@@ -324,6 +998,241 @@ mod integration_tests {
         assert!(regions[0].ignore_reason.as_ref().unwrap().contains("disabled"));
     }
 
+    #[test]
+    fn asterisk_element_flagged_as_required_marker() {
+        let source = r##"<label className="text-sm">Email <span className="text-destructive">*</span></label>
+<Input className="text-foreground" />*
+<label className="after:content-['*'] after:text-red-600">Name</label>"##;
+        let regions = scan_file(source, &make_config(&[]), &HashMap::new(), "bg-background");
+        let flags: Vec<Option<bool>> = regions.iter().map(|r| r.required_marker).collect();
+        assert_eq!(flags, vec![None, Some(true), None, Some(true)]);
+    }
+
+    #[test]
+    fn deduplicated_marker_keeps_its_own_line() {
+        let source = r##"<span className="text-destructive">Error</span>
+<span className="text-destructive">*</span> {/* a11y-ignore: decorative */}
+<span className="text-destructive">Error</span>"##;
+        let config = ScanConfig { dedup_regions: true, ..ScanConfig::default() };
+        let regions = scan_file_with_config(source, &config);
+        let flags: Vec<_> =
+            regions.iter().map(|r| (r.start_line, r.required_marker, r.ignored, r.occurrences)).collect();
+        assert_eq!(flags, [(1, None, None, Some(2)), (2, Some(true), Some(true), Some(1))]);
+    }
+
+    #[test]
+    fn static_role_recorded_on_region() {
+        let source = r##"<div role="heading" className="text-lg">T</div><div role={r} className="text-sm">x</div>"##;
+        let regions = scan_file(source, &make_config(&[]), &HashMap::new(), "bg-background");
+        assert_eq!(regions[0].role.as_deref(), Some("heading"));
+        assert_eq!(regions[1].role, None);
+    }
+
+    #[test]
+    fn solid_class_list_keeps_the_raw_tag() {
+        let source = r##"<button disabled classList={{ "text-gray-400": true }}>x</button>"##;
+        let regions = scan_file(source, &make_config(&[]), &HashMap::new(), "bg-background");
+        assert_eq!(regions[0].content, "text-gray-400");
+        assert_eq!(regions[0].ignored, Some(true));
+        assert!(regions[0].ignore_reason.as_ref().unwrap().contains("disabled"));
+    }
+
+    #[test]
+    fn group_and_peer_variants_related_to_their_elements() {
+        let source = r##"<a className="group bg-card hover:bg-primary">
+  <h3 className="text-foreground group-hover:text-primary-foreground">Title</h3>
+  <input className="peer border" />
+  <p className={cn("text-sm", "peer-focus-visible:text-red-700")}>Hint</p>
+</a>
+<p className="group-hover:text-white peer-hover:text-white">Outside</p>"##;
+        let regions = scan_file(source, &make_config(&[]), &HashMap::new(), "bg-background");
+        let context: Vec<(Option<&str>, Option<bool>)> =
+            regions.iter().map(|r| (r.group_hover_bg.as_deref(), r.peer_state)).collect();
+        assert_eq!(
+            context,
+            vec![(None, None), (Some("bg-primary"), None), (None, None), (None, Some(true)), (None, None)]
+        );
+    }
+
+    #[test]
+    fn deduplicated_regions_keep_their_own_group_hover_bg() {
+        let source = r##"<a className="group bg-card hover:bg-primary"><p className="group-hover:text-white">a</p></a>
+<a className="group bg-card hover:bg-yellow-200"><p className="group-hover:text-white">b</p></a>"##;
+        let config = ScanConfig { dedup_regions: true, ..ScanConfig::default() };
+        let regions = scan_file_with_config(source, &config);
+        let hover_bgs: Vec<(u32, Option<&str>)> = regions
+            .iter()
+            .filter(|r| r.content == "group-hover:text-white")
+            .map(|r| (r.start_line, r.group_hover_bg.as_deref()))
+            .collect();
+        assert_eq!(hover_bgs, [(1, Some("bg-primary")), (2, Some("bg-yellow-200"))]);
+    }
+
+    #[test]
+    fn cn_regions_locate_each_class_by_argument() {
+        let source = r##"<div className="bg-card">
+  <p className={cn("text-muted px-2", active && 'font-bold',
+    { "bg-red-500": error }, size)}>a</p>
+  <span className="text-sm">b</span>
+</div>"##;
+        let regions = scan_file(source, &make_config(&[]), &HashMap::new(), "bg-background");
+        let locations: Vec<(&str, u32, u32, u32)> = regions[1]
+            .class_locations
+            .iter()
+            .flatten()
+            .map(|l| (l.class_name.as_str(), l.argument, l.line, l.column))
+            .collect();
+        assert_eq!(
+            locations,
+            vec![
+                ("text-muted", 0, 2, 21),
+                ("px-2", 0, 2, 32),
+                ("font-bold", 1, 2, 50),
+                ("bg-red-500", 2, 3, 8),
+            ]
+        );
+        assert_eq!(regions[0].class_locations, None);
+        assert_eq!(regions[2].class_locations, None);
+    }
+
+    #[test]
+    fn regions_record_their_provenance() {
+        let source = r##"const base = cn("text-sm", "font-medium");
+const button = cva("rounded", { variants: { intent: { primary: "bg-blue-600" } } });
+const Title = tw`text-lg`;
+<div className="bg-card">
+  <p className={cn("text-muted", active && "font-bold")}>a</p>
+  <span className={`text-sm ${size}`}>b</span>
+  <em className={open ? "text-black" : "text-gray-400"}>c</em>
+  <Item {...props} />
+</div>"##;
+        let regions = scan_file(source, &make_config(&[]), &HashMap::new(), "bg-background");
+        let found: Vec<(u32, Option<&str>)> =
+            regions.iter().map(|r| (r.start_line, r.provenance.as_deref())).collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, Some("standalone")),
+                (2, Some("cva")),
+                (3, Some("template")),
+                (4, Some("attribute")),
+                (5, Some("cn")),
+                (6, Some("template")),
+                (7, Some("attribute")),
+                (7, Some("attribute")),
+                (8, Some("attribute")),
+            ]
+        );
+    }
+
+    #[test]
+    fn standalone_classes_policy() {
+        let source = r##"const base = cn("text-gray-400");
+// @a11y-context bg:bg-slate-900
+const dark = clsx("text-white");
+<p className={cn("text-sm")}>a</p>"##;
+        let scan = |policy: &str| {
+            let config = ScanConfig {
+                default_bg: "bg-background".to_string(),
+                standalone_classes: StandaloneClasses::parse(Some(policy)),
+                ..ScanConfig::default()
+            };
+            scan_file_with_config(source, &config)
+                .into_iter()
+                .map(|r| (r.start_line, r.ignore_reason))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(scan("scan"), vec![(1, None), (3, None), (4, None)]);
+        assert_eq!(scan("annotated"), vec![(3, None), (4, None)]);
+        assert_eq!(scan("skip"), vec![(4, None)]);
+        let ignored = scan("ignore");
+        assert_eq!(ignored.len(), 3);
+        assert!(ignored[0].1.as_deref().unwrap().contains("standalone"));
+        assert!(ignored[1].1.is_some() && ignored[2].1.is_none());
+    }
+
+    #[test]
+    fn breakpoint_variants_emit_twin_regions() {
+        let source = r##"<div className="bg-black text-white md:bg-white md:text-gray-900 lg:p-8 xl:border-red-500">x</div>"##;
+        let config = ScanConfig {
+            default_bg: "bg-background".to_string(),
+            breakpoint_variants: true,
+            ..ScanConfig::default()
+        };
+        let regions = scan_file_with_config(source, &config);
+        let breakpoints: Vec<Option<&str>> = regions.iter().map(|r| r.breakpoint.as_deref()).collect();
+        assert_eq!(breakpoints, vec![None, Some("md"), Some("xl")]);
+        assert!(regions.iter().all(|r| r.content == regions[0].content && r.tag_name == "div"));
+    }
+
+    #[test]
+    fn state_variants_emit_twin_regions() {
+        let source = r##"<button className="bg-white text-black hover:bg-gray-100 active:text-gray-700 focus:outline-none disabled:opacity-50">x</button>"##;
+        let config = ScanConfig {
+            default_bg: "bg-background".to_string(),
+            state_variants: true,
+            ..ScanConfig::default()
+        };
+        let regions = scan_file_with_config(source, &config);
+        let states: Vec<Option<&str>> = regions.iter().map(|r| r.interactive_state.as_deref()).collect();
+        assert_eq!(states, vec![None, Some("hover"), Some("active")]);
+        assert!(regions.iter().all(|r| r.content == regions[0].content));
+
+        let source = r##"<Item className="text-foreground data-[state=checked]:bg-primary data-[state=checked]:text-primary-foreground">x</Item>"##;
+        let regions = scan_file_with_config(source, &config);
+        let states: Vec<Option<&str>> = regions.iter().map(|r| r.interactive_state.as_deref()).collect();
+        assert_eq!(states, vec![None, Some("data-[state=checked]")]);
+    }
+
+    #[test]
+    fn dark_variants_emit_twin_regions() {
+        let source = r##"<section className="bg-white dark:bg-zinc-900">
+  <h2 className="text-zinc-900 dark:text-zinc-100">Title</h2>
+  <div className="bg-card">
+    <p className="text-sm">Body</p>
+  </div>
+</section>"##;
+        let config = ScanConfig {
+            default_bg: "bg-background".to_string(),
+            dark_variants: true,
+            ..ScanConfig::default()
+        };
+        let regions = scan_file_with_config(source, &config);
+        let found: Vec<(u32, &str, Option<&str>)> =
+            regions.iter().map(|r| (r.start_line, r.context_bg.as_str(), r.theme.as_deref())).collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, "bg-background", Some("light")),
+                (1, "bg-background", Some("dark")),
+                (2, "bg-white", Some("light")),
+                (2, "bg-zinc-900", Some("dark")),
+                (3, "bg-white", Some("light")),
+                (3, "bg-zinc-900", Some("dark")),
+                (4, "bg-card", None),
+            ]
+        );
+        assert_eq!(regions[3].tag_name, "h2");
+
+        let regions = scan_file(source, &make_config(&[]), &HashMap::new(), "bg-background");
+        assert!(regions.iter().all(|r| r.theme.is_none()));
+    }
+
+    #[test]
+    fn deduplicated_regions_keep_their_own_dark_context() {
+        let source = r##"<div className="bg-white dark:bg-black"><p className="text-gray-500">a</p></div>
+<div className="bg-white dark:bg-gray-100"><p className="text-gray-500">b</p></div>
+<div className="bg-white dark:bg-black"><p className="text-gray-500">c</p></div>"##;
+        let config = ScanConfig { dedup_regions: true, dark_variants: true, ..ScanConfig::default() };
+        let regions = scan_file_with_config(source, &config);
+        let dark: Vec<(u32, &str, Option<u32>)> = regions
+            .iter()
+            .filter(|r| r.content == "text-gray-500" && r.theme.as_deref() == Some("dark"))
+            .map(|r| (r.start_line, r.context_bg.as_str(), r.occurrences))
+            .collect();
+        assert_eq!(dark, [(1, "bg-black", Some(2)), (2, "bg-gray-100", Some(1))]);
+    }
+
     #[test]
     fn aria_disabled_true_flags_region() {
         let source = r##"<div aria-disabled="true" className="text-gray-400">x</div>"##;
@@ -366,6 +1275,26 @@ mod integration_tests {
         assert_eq!(regions[0].inline_color, Some("red".to_string()));
     }
 
+    #[test]
+    fn style_string_colors_extracted() {
+        let source = r##"<td style={`color: ${c}; background-color: #0f172a`} className="p-2">x</td>
+<p style="color: #94a3b8" className="text-sm">y</p>"##;
+        let regions = scan_file(source, &make_config(&[]), &HashMap::new(), "bg-background");
+        assert_eq!(regions[0].inline_background_color.as_deref(), Some("#0f172a"));
+        assert_eq!(regions[0].inline_color, None);
+        assert_eq!(regions[1].inline_color.as_deref(), Some("#94a3b8"));
+    }
+
+    #[test]
+    fn emotion_css_prop_colors_extracted() {
+        let source = "<div css={css`\n  background: #0f172a;\n  > p { color: red; }\n`} className=\"text-white\">x</div>";
+        let regions = scan_file(source, &make_config(&[]), &HashMap::new(), "bg-background");
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].start_line, 4);
+        assert_eq!(regions[0].inline_background_color.as_deref(), Some("#0f172a"));
+        assert_eq!(regions[0].inline_color, None);
+    }
+
     #[test]
     fn inline_style_background_color_extracted() {
         let source = r##"<div style={{ backgroundColor: '#ff0000' }} className="text-white">x</div>"##;
@@ -571,6 +1500,26 @@ mod integration_tests {
         assert!(span.ignore_reason.as_ref().unwrap().contains("opacity"));
     }
 
+    #[test]
+    fn ignore_reasons_follow_locale() {
+        let source = r##"<button disabled className="text-gray-400">x</button>
+<div className="opacity-0"><span className="text-white">y</span></div>
+{/* a11y-ignore */}
+<p className="text-red-500">z</p>"##;
+        let config = ScanConfig {
+            default_bg: "bg-background".to_string(),
+            locale: Locale::It,
+            ..ScanConfig::default()
+        };
+        let regions = scan_file_with_config(source, &config);
+        let reason = |content: &str| {
+            regions.iter().find(|r| r.content == content).unwrap().ignore_reason.clone().unwrap()
+        };
+        assert_eq!(reason("text-gray-400"), "elemento disabilitato (esenzione WCAG SC 1.4.3)");
+        assert!(reason("text-white").starts_with("invisibile (opacità effettiva 0%"));
+        assert_eq!(reason("text-red-500"), "soppresso");
+    }
+
     #[test]
     fn nearly_invisible_marked_ignored() {
         let source = r##"<div className="opacity-5">
@@ -638,4 +1587,117 @@ mod integration_tests {
         // Portal resets opacity -> span is fully opaque (None = 1.0)
         assert_eq!(span.effective_opacity, None);
     }
+
+    #[test]
+    fn ternary_branches_flagged_conditional() {
+        let source = r##"<Card className={on ? "bg-red-500 text-white" : "bg-green-500 text-black"}>
+    <p className="text-gray-700">x</p>
+</Card>"##;
+        let config = make_config(&[("Card", "bg-card")]);
+        let regions = scan_file(source, &config, &HashMap::new(), "bg-background");
+        assert_eq!(regions.len(), 3);
+        assert!(regions[..2]
+            .iter()
+            .all(|r| r.conditional == Some(true) && r.start_line == 1 && r.context_bg == "bg-background"));
+        assert_eq!(regions[1].content, "bg-green-500 text-black");
+        assert_eq!(regions[2].conditional, None);
+    }
+
+    #[test]
+    fn cn_arguments_guarded_both_ways_split_into_branches() {
+        let source = r##"<div className="bg-card">
+  <p className={cn("text-sm", isError && "text-red-500", !isError && "text-green-600", open && "font-bold")}>a</p>
+  <span className={cn("text-xs", a && "text-white", b && "bg-black")}>b</span>
+</div>"##;
+        let regions = scan_file(source, &make_config(&[]), &HashMap::new(), "bg-background");
+        let found: Vec<(&str, Option<bool>)> =
+            regions.iter().map(|r| (r.content.as_str(), r.conditional)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("bg-card", None),
+                ("text-sm text-red-500 font-bold", Some(true)),
+                ("text-sm text-green-600 font-bold", Some(true)),
+                ("text-xs text-white bg-black", None),
+            ]
+        );
+        assert!(regions[1..3].iter().all(|r| r.start_line == 2 && r.context_bg == "bg-card"));
+        let located: Vec<&str> =
+            regions[2].class_locations.iter().flatten().map(|l| l.class_name.as_str()).collect();
+        assert_eq!(located, vec!["text-sm", "text-green-600", "font-bold"]);
+    }
+
+    #[test]
+    fn malformed_jsx_reported_as_diagnostics() {
+        let source = r##"<div className="bg-red-500 text-white">ok</div>
+<p className={cn("text-white", active && "bg-black"}>
+  broken"##;
+        let (regions, _, diagnostics, _) = scan_file_with_findings(source, &ScanConfig::default());
+        assert_eq!(regions[0].content, "bg-red-500 text-white");
+        let kinds: Vec<(&str, u32)> = diagnostics.iter().map(|d| (d.kind.as_str(), d.line)).collect();
+        assert_eq!(kinds, vec![("unbalanced-brackets", 2)]);
+
+        let (_, _, diagnostics, _) = scan_file_with_findings("<p>x</p>\n<Card className=\"a\"", &ScanConfig::default());
+        let kinds: Vec<(&str, u32)> = diagnostics.iter().map(|d| (d.kind.as_str(), d.line)).collect();
+        assert_eq!(kinds, vec![("unclosed-tag", 2)]);
+
+        let (_, _, diagnostics, _) = scan_file_with_findings(r##"<p className="a">x</p>"##, &ScanConfig::default());
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn unterminated_comment_at_end_of_file() {
+        let (regions, _, diagnostics, _) = scan_file_with_findings("<p className=\"a\">x</p>\n/*", &ScanConfig::default());
+        assert_eq!(regions.len(), 1);
+        assert_eq!(diagnostics[0].kind, "unterminated-comment");
+        // The comment keeps its last characters
+        let source = "<p className=\"a\">x</p>\n/* a11y-ignore-file: legacy è";
+        let (_, _, _, file_ignore) = scan_file_with_findings(source, &ScanConfig::default());
+        assert_eq!(file_ignore.as_deref(), Some("legacy è"));
+    }
+
+    #[test]
+    fn dynamic_class_names_flagged_unresolved() {
+        let source = r##"<Card>
+    <Button className="text-white" {...props}>a</Button>
+    <Icon {...rest} />
+    <p className="text-gray-700">b</p>
+</Card>"##;
+        let config = make_config(&[("Card", "bg-card")]);
+        let regions = scan_file(source, &config, &HashMap::new(), "bg-background");
+        assert_eq!(regions.len(), 3);
+        assert_eq!(regions[0].unresolved_dynamic_class, Some(true));
+        assert_eq!((regions[1].content.as_str(), regions[1].start_line), ("", 3));
+        assert_eq!(regions[1].unresolved_dynamic_class, Some(true));
+        assert_eq!(regions[1].context_bg, "bg-card");
+        assert_eq!(regions[2].unresolved_dynamic_class, None);
+    }
+
+    // ── createElement() / h() render functions ──
+
+    #[test]
+    fn create_element_nesting_tracks_context() {
+        let config = make_config(&[("Card", "bg-card")]);
+        let source = r##"React.createElement(Card, null,
+    React.createElement("p", { className: "text-white" }, "x")
+);
+React.createElement("span", { className: "text-black" });"##;
+        let regions = scan_file(source, &config, &HashMap::new(), "bg-background");
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].start_line, 2);
+        assert_eq!(regions[0].context_bg, "bg-card");
+        // Card closed with its call
+        assert_eq!(regions[1].context_bg, "bg-background");
+    }
+
+    #[test]
+    fn h_props_feed_explicit_bg_and_disabled() {
+        let source = r##"h("section", { class: "bg-slate-900" }, [
+    h("button", { disabled: true, class: "text-gray-400" }, "Go")
+])"##;
+        let regions = scan_file(source, &HashMap::new(), &HashMap::new(), "bg-background");
+        let button = regions.iter().find(|r| r.content == "text-gray-400").unwrap();
+        assert_eq!(button.context_bg, "bg-slate-900");
+        assert_eq!(button.ignored, Some(true));
+    }
 }
@@ -28,7 +28,7 @@ pub fn parse_opacity_class(cls: &str) -> Option<f32> {
 
         // Float literal: opacity-[.33] or opacity-[0.33]
         let val: f32 = inner.parse().ok()?;
-        if val < 0.0 || val > 1.0 {
+        if !(0.0..=1.0).contains(&val) {
             return None;
         }
         return Some(val);
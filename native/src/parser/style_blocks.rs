@@ -0,0 +1,117 @@
+//! `<style>` and styled-jsx `<style jsx>{`...`}</style>` blocks of a file.
+//! Their element (`p { ... }`) and class (`.note { ... }`) rules color the
+//! file's elements like inline styles would, an element's own `style`
+//! winning, so colors set there are audited too. Rules with pseudo-classes,
+//! combinators or inside at-rules are skipped, as in `css_modules`.
+
+use super::class_extractor::InlineStyleColors;
+use super::css_modules::{color_rules, plain_class};
+use super::markup::find_block_tag;
+use super::tokenizer::{find_tag_close, strip_template_expressions};
+
+enum Selector {
+    Element(String),
+    Class(String),
+}
+
+/// Color rules of a file's style blocks, in source order.
+#[derive(Default)]
+pub struct Stylesheet {
+    rules: Vec<(Selector, InlineStyleColors)>,
+}
+
+impl Stylesheet {
+    /// The rules of every `<style>` block in `source`.
+    pub fn of_source(source: &str) -> Self {
+        let mut rules = Vec::new();
+        let mut from = 0;
+        while let Some(open) = find_block_tag(source, "<style", from) {
+            let body_start = find_tag_close(source, open + "<style".len());
+            let Some(body_len) = source[body_start..].find("</style") else {
+                break;
+            };
+            from = body_start + body_len;
+            for (selectors, colors) in color_rules(&block_css(&source[body_start..from])) {
+                for selector in selectors.split(',').filter_map(|s| parse_selector(s.trim())) {
+                    rules.push((selector, colors.clone()));
+                }
+            }
+        }
+        Self { rules }
+    }
+
+    /// Whether an element rule colors `tag` (so it matters without classes).
+    pub fn styles_element(&self, tag: &str) -> bool {
+        self.rules.iter().any(|(selector, _)| matches!(selector, Selector::Element(t) if t == tag))
+    }
+
+    /// Colors of an element `tag` with `classes`: class rules win over
+    /// element rules (specificity), later rules over earlier ones.
+    pub(super) fn colors_for(&self, tag: &str, classes: &str) -> InlineStyleColors {
+        let mut colors = InlineStyleColors::default();
+        let element_rules = self.rules.iter().filter(|(s, _)| matches!(s, Selector::Element(t) if t == tag));
+        let class_rules = self
+            .rules
+            .iter()
+            .filter(|(s, _)| matches!(s, Selector::Class(c) if classes.split_whitespace().any(|class| class == c)));
+        for (_, rule) in element_rules.chain(class_rules) {
+            colors = rule.clone().or(colors);
+        }
+        colors
+    }
+}
+
+/// CSS of a style block body: styled-jsx wraps it in `{`...`}`.
+fn block_css(body: &str) -> String {
+    let body = body.trim();
+    let template = body
+        .strip_prefix('{')
+        .and_then(|b| b.strip_suffix('}'))
+        .map(str::trim)
+        .and_then(|b| b.strip_prefix('`'))
+        .and_then(|b| b.strip_suffix('`'));
+    match template {
+        Some(template) => strip_template_expressions(template),
+        None => body.to_string(),
+    }
+}
+
+/// `.note`, `p` or styled-jsx `:global(.note)`; None for anything else.
+fn parse_selector(selector: &str) -> Option<Selector> {
+    let selector = selector.strip_prefix(":global(").and_then(|s| s.strip_suffix(')')).unwrap_or(selector);
+    if let Some(class) = plain_class(selector) {
+        return Some(Selector::Class(class.to_string()));
+    }
+    let is_element = selector.chars().next().is_some_and(|c| c.is_ascii_lowercase())
+        && selector.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    is_element.then(|| Selector::Element(selector.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_style_and_styled_jsx_blocks() {
+        let source = r#"<p className="note">a</p>
+<style>{`
+  p { color: #6b7280; background-color: ${bg}; }
+  .note, :global(.hint) { color: #111827 }
+  .note:hover { color: red }
+  p > span { color: blue }
+`}</style>
+<style jsx global>{`a { border-color: #e5e7eb }`}</style>"#;
+        let sheet = Stylesheet::of_source(source);
+        assert!(sheet.styles_element("p"));
+        assert!(sheet.styles_element("a"));
+        assert!(!sheet.styles_element("span"));
+
+        let p = sheet.colors_for("p", "");
+        assert_eq!(p.color.as_deref(), Some("#6b7280"));
+        let note = sheet.colors_for("p", "mt-2 note");
+        assert_eq!(note.color.as_deref(), Some("#111827"));
+        assert_eq!(sheet.colors_for("div", "hint").color.as_deref(), Some("#111827"));
+        assert_eq!(sheet.colors_for("a", "").border_color.as_deref(), Some("#e5e7eb"));
+        assert!(sheet.colors_for("span", "").color.is_none());
+    }
+}
@@ -1,18 +1,66 @@
-use super::visitor::JsxVisitor;
+use std::collections::HashMap;
+
+use super::constants::{map_access, FileConstants, Library, MapKey};
+use super::css_modules;
+use super::visitor::{ClassSource, JsxVisitor};
+use crate::types::{ClassLocation, ParseDiagnostic};
+
+/// Tag name `<>` / `</>` fragments are reported with (same as `<Fragment>`).
+pub const FRAGMENT: &str = "Fragment";
 
 /// Scan JSX source and emit events to all registered visitors.
 /// This is a "lossy" lexer — it recognizes tags, attributes, comments, and strings,
-/// but ignores everything else.
+/// but ignores everything else. `createElement()`/`h()` render-function calls
+/// are reported as tags too, with their props rendered as JSX attributes.
 ///
 /// Port of: src/plugins/jsx/parser.ts → extractClassRegions() (state machine core)
 pub fn scan_jsx(source: &str, visitors: &mut [&mut dyn JsxVisitor]) {
+    scan_jsx_with_attributes(source, &[], &[], visitors);
+}
+
+/// Like `scan_jsx`, also extracting classes from the custom attributes in
+/// `class_attributes` (e.g. `tw`, `innerClassName`) like `className`, and
+/// from calls to the helpers in `class_functions` (e.g. `twMerge`, `cx`)
+/// like `cn()`.
+pub fn scan_jsx_with_attributes(
+    source: &str,
+    class_attributes: &[String],
+    class_functions: &[String],
+    visitors: &mut [&mut dyn JsxVisitor],
+) {
+    scan_jsx_with_library(source, class_attributes, class_functions, &Library::default(), visitors);
+}
+
+/// Like `scan_jsx_with_attributes`, also resolving the class constants the
+/// source imports from `library` (see `constants`).
+pub fn scan_jsx_with_library(
+    source: &str,
+    class_attributes: &[String],
+    class_functions: &[String],
+    library: &Library,
+    visitors: &mut [&mut dyn JsxVisitor],
+) {
     let bytes = source.as_bytes();
     let len = bytes.len();
     let line_offsets = build_line_offsets(source);
+    let css_module_imports = css_modules::module_imports(source);
+    let constants = library.constants_of(source);
 
     let mut i = 0;
+    // Open createElement()/h() calls with children: (closing paren offset, tag)
+    let mut open_calls: Vec<(usize, String)> = Vec::new();
 
     while i < len {
+        while let Some((end, _)) = open_calls.last() {
+            if *end > i {
+                break;
+            }
+            let (_, tag) = open_calls.pop().unwrap();
+            for v in visitors.iter_mut() {
+                v.on_tag_close(&tag);
+            }
+        }
+
         // ── Single-line comment: // ... \n ──
         if i + 1 < len && bytes[i] == b'/' && bytes[i + 1] == b'/' {
             let comment_start = i;
@@ -35,10 +83,15 @@ pub fn scan_jsx(source: &str, visitors: &mut [&mut dyn JsxVisitor]) {
             while i + 1 < len && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
                 i += 1;
             }
-            if i + 1 < len {
+            let content_end = if i + 1 < len {
                 i += 2; // skip */
-            }
-            let content_end = if i >= 2 { i - 2 } else { i };
+                i - 2
+            } else {
+                let line = line_at_offset(&line_offsets, comment_start);
+                emit_diagnostic(visitors, line, "unterminated-comment", "block comment never closes".to_string());
+                i = len;
+                len
+            };
             let comment_text = &source[comment_start + 2..content_end]; // strip /* and */
             let line = line_at_offset(&line_offsets, comment_start);
             for v in visitors.iter_mut() {
@@ -63,8 +116,19 @@ pub fn scan_jsx(source: &str, visitors: &mut [&mut dyn JsxVisitor]) {
             continue;
         }
 
+        // ── twin.macro / styled-components tagged templates ──
+        if bytes[i] == b'`' {
+            if let Some(tag) = template_tag(source, i) {
+                let end = find_template_end(bytes, i + 1).unwrap_or(len);
+                emit_tagged_template(source, tag, i, end, &line_offsets, visitors);
+                i = (end + 1).min(len);
+                continue;
+            }
+        }
+
         // ── Template literal (skip, but we handle className={`...`} separately below) ──
         if bytes[i] == b'`' {
+            let tick = i;
             i += 1;
             while i < len && bytes[i] != b'`' {
                 if bytes[i] == b'\\' {
@@ -74,6 +138,9 @@ pub fn scan_jsx(source: &str, visitors: &mut [&mut dyn JsxVisitor]) {
             }
             if i < len {
                 i += 1;
+            } else {
+                let line = line_at_offset(&line_offsets, tick);
+                emit_diagnostic(visitors, line, "unterminated-template", "template literal never closes".to_string());
             }
             continue;
         }
@@ -82,10 +149,23 @@ pub fn scan_jsx(source: &str, visitors: &mut [&mut dyn JsxVisitor]) {
         if bytes[i] == b'<' && i + 1 < len {
             let next = bytes[i + 1];
 
-            // Closing tag: </TagName>
+            // Fragment: <> opens like <Fragment>
+            if next == b'>' {
+                for v in visitors.iter_mut() {
+                    v.on_tag_open(FRAGMENT, false, "<>");
+                }
+                emit_jsx_text(source, i + 2, &line_offsets, visitors);
+                i += 2;
+                continue;
+            }
+
+            // Closing tag: </TagName>, or </> closing a fragment
             if next == b'/' {
                 let tag_start = i + 2;
-                let (tag_name, tag_end) = read_tag_name(bytes, tag_start);
+                let (mut tag_name, tag_end) = read_tag_name(bytes, tag_start);
+                if tag_name.is_empty() && bytes.get(tag_start) == Some(&b'>') {
+                    tag_name = FRAGMENT.to_string();
+                }
                 if !tag_name.is_empty() {
                     for v in visitors.iter_mut() {
                         v.on_tag_close(&tag_name);
@@ -99,6 +179,9 @@ pub fn scan_jsx(source: &str, visitors: &mut [&mut dyn JsxVisitor]) {
                 if j < len {
                     j += 1;
                 }
+                if !tag_name.is_empty() {
+                    emit_jsx_text(source, j, &line_offsets, visitors);
+                }
                 i = j;
                 continue;
             }
@@ -112,6 +195,12 @@ pub fn scan_jsx(source: &str, visitors: &mut [&mut dyn JsxVisitor]) {
                     // Find the end of the tag (the closing > or />)
                     let tag_close = find_tag_close(source, name_end);
                     let raw_tag = &source[i..tag_close];
+                    if tag_close == len && !raw_tag.ends_with('>') {
+                        let line = line_at_offset(&line_offsets, i);
+                        let message =
+                            format!("<{}> never closes; the rest of the file was read as its attributes", tag_name);
+                        emit_diagnostic(visitors, line, "unclosed-tag", message);
+                    }
                     let is_self_closing = is_self_closing_tag(source, name_end);
 
                     for v in visitors.iter_mut() {
@@ -119,7 +208,16 @@ pub fn scan_jsx(source: &str, visitors: &mut [&mut dyn JsxVisitor]) {
                     }
 
                     // Now scan inside the tag for className= attributes
-                    scan_tag_attributes(source, bytes, name_end, tag_close, &line_offsets, raw_tag, visitors);
+                    let tag = TagSpan { name_end, tag_close, raw_tag };
+                    let class_names = ClassNames {
+                        attributes: class_attributes,
+                        functions: class_functions,
+                        css_modules: &css_module_imports,
+                        constants: &constants,
+                    };
+                    scan_tag_attributes(source, bytes, &tag, &line_offsets, &class_names, visitors);
+
+                    emit_jsx_text(source, tag_close, &line_offsets, visitors);
 
                     i = tag_close;
                     continue;
@@ -127,57 +225,170 @@ pub fn scan_jsx(source: &str, visitors: &mut [&mut dyn JsxVisitor]) {
             }
         }
 
-        // ── Standalone cn(), clsx(), cva() outside className= ──
+        // ── React.createElement(tag, props, ...children) / h(tag, props, ...children) ──
+        if let Some(call) = read_create_element_call(source, i) {
+            let raw_tag = create_element_raw_tag(source, &call);
+            let self_closing = call.children_start.is_none();
+            for v in visitors.iter_mut() {
+                v.on_tag_open(&call.tag, self_closing, &raw_tag);
+            }
+            if let Some((class_pos, content)) = call
+                .props
+                .and_then(|(start, end)| create_element_class_value(source, start, end))
+            {
+                let line = line_at_offset(&line_offsets, class_pos);
+                emit_class_source(visitors, ClassSource::Attribute);
+                for v in visitors.iter_mut() {
+                    v.on_class_attribute(&content, line, &raw_tag);
+                }
+            }
+            match call.children_start {
+                // Children (including nested calls) are scanned in place; the
+                // tag closes when the scan reaches the call's closing paren.
+                Some(children_start) => {
+                    open_calls.push((call.end, call.tag));
+                    i = children_start;
+                }
+                None => i = call.end + 1,
+            }
+            continue;
+        }
+
+        // ── Standalone cn(), clsx(), cva(), `class_functions` outside className= (cva per variant) ──
         if i + 3 <= len && !is_ident_char_before(bytes, i) {
-            let standalone_fn = if starts_with_at(bytes, i, b"cn(") {
-                Some(2)
-            } else if i + 5 <= len && starts_with_at(bytes, i, b"clsx(") {
-                Some(4)
-            } else if i + 4 <= len && starts_with_at(bytes, i, b"cva(") {
-                Some(3)
-            } else {
-                None
-            };
+            let is_cva = starts_with_at(bytes, i, b"cva(");
+            let standalone_fn = if is_cva { Some(i + 3) } else { class_function_at(bytes, i, class_functions) };
 
-            if let Some(fn_len) = standalone_fn {
-                let paren_start = i + fn_len;
+            if let Some(paren_start) = standalone_fn {
                 if let Some((content, end)) = extract_balanced_parens(source, paren_start) {
+                    let cva = if is_cva {
+                        super::cva::branches(source, paren_start, end)
+                    } else {
+                        Default::default()
+                    };
+                    for &(offset, count) in &cva.truncated {
+                        let line = line_at_offset(&line_offsets, offset);
+                        let message = format!(
+                            "compound variant lists {} combinations, only the first {} are checked",
+                            count,
+                            super::cva::MAX_COMPOUND_COMBINATIONS
+                        );
+                        emit_diagnostic(visitors, line, "truncated-compound-variant", message);
+                    }
+                    let branches = cva.branches;
+                    if !branches.is_empty() {
+                        emit_class_source(visitors, ClassSource::Variant);
+                        for branch in &branches {
+                            let line = line_at_offset(&line_offsets, branch.offset);
+                            for v in visitors.iter_mut() {
+                                v.on_variant_class(&branch.classes, line, &branch.variant_key);
+                            }
+                        }
+                        i = end + 1;
+                        continue;
+                    }
+                    let content = if is_cva { content } else { call_classes(&content) };
                     let line = line_at_offset(&line_offsets, i);
+                    if !is_cva {
+                        let locations = call_class_locations(source, paren_start, end, &content, &line_offsets);
+                        emit_class_locations(visitors, &locations);
+                    }
+                    emit_class_source(visitors, ClassSource::Standalone);
                     for v in visitors.iter_mut() {
                         v.on_class_attribute(&content, line, "");
                     }
                     i = end + 1;
                     continue;
                 }
+                let line = line_at_offset(&line_offsets, i);
+                let name = &source[i..paren_start];
+                emit_diagnostic(visitors, line, "unbalanced-parens", format!("{}() call never closes", name));
             }
         }
 
         i += 1;
     }
 
+    while let Some((_, tag)) = open_calls.pop() {
+        for v in visitors.iter_mut() {
+            v.on_tag_close(&tag);
+        }
+    }
+
     // Notify visitors that scanning is complete
     for v in visitors.iter_mut() {
         v.on_file_end();
     }
 }
 
+/// Offset of the `(` of a `cn(`, `clsx(` or `class_functions` call whose
+/// name starts at `pos`.
+fn class_function_at(bytes: &[u8], pos: usize, class_functions: &[String]) -> Option<usize> {
+    ["cn", "clsx"]
+        .into_iter()
+        .chain(class_functions.iter().map(String::as_str))
+        .find(|name| !name.is_empty() && starts_with_at(bytes, pos, name.as_bytes()))
+        .map(|name| pos + name.len())
+        .filter(|&open| bytes.get(open) == Some(&b'('))
+}
+
+/// Custom class-bearing attribute and helper names (see `scan_jsx_with_attributes`).
+struct ClassNames<'a> {
+    attributes: &'a [String],
+    functions: &'a [String],
+    /// Binding → specifier of the file's CSS module imports
+    css_modules: &'a HashMap<String, String>,
+    /// Top-level constants of the file (see `top_level_constants`)
+    constants: &'a FileConstants,
+}
+
+/// An opening tag: end of its name, offset of its closing `>` and raw text.
+struct TagSpan<'a> {
+    name_end: usize,
+    tag_close: usize,
+    raw_tag: &'a str,
+}
+
+/// End of a class-bearing attribute's `=` at `pos`: `className=`, or SolidJS
+/// `classList=` / one of `class_attributes` as a whole attribute name (`tw=`,
+/// not `data-tw=`).
+fn class_attribute_at(bytes: &[u8], pos: usize, class_attributes: &[String]) -> Option<usize> {
+    let at_name_start = pos > 0 && bytes[pos - 1].is_ascii_whitespace();
+    let name_len = if starts_with_at(bytes, pos, b"className") {
+        "className".len()
+    } else if at_name_start && starts_with_at(bytes, pos, b"classList") {
+        "classList".len()
+    } else {
+        class_attributes
+            .iter()
+            .find(|name| at_name_start && starts_with_at(bytes, pos, name.as_bytes()))?
+            .len()
+    };
+    (bytes.get(pos + name_len) == Some(&b'=')).then_some(pos + name_len + 1)
+}
+
 /// Scan tag attributes between name_end and tag_close for className= patterns.
 fn scan_tag_attributes(
     source: &str,
     bytes: &[u8],
-    name_end: usize,
-    tag_close: usize,
+    tag: &TagSpan,
     line_offsets: &[usize],
-    raw_tag: &str,
+    class_names: &ClassNames,
     visitors: &mut [&mut dyn JsxVisitor],
 ) {
+    let TagSpan { name_end, tag_close, raw_tag } = *tag;
     let mut j = name_end;
-    let class_name_prefix = b"className=";
+    // `{...props}` or an expression without static classes (`props.className`)
+    let mut dynamic = false;
 
-    while j + class_name_prefix.len() <= tag_close {
-        if starts_with_at(bytes, j, class_name_prefix) {
+    while j < tag_close {
+        if bytes[j] == b'{' && is_spread_attribute(bytes, j) {
+            dynamic = true;
+            j = find_closing_bracket(bytes, j).map_or(tag_close, |close| close + 1);
+            continue;
+        }
+        if let Some(eq_end) = class_attribute_at(bytes, j, class_names.attributes) {
             let line = line_at_offset(line_offsets, j);
-            let eq_end = j + class_name_prefix.len();
             let after_eq = skip_ws(bytes, eq_end);
 
             // className="..."
@@ -185,9 +396,8 @@ fn scan_tag_attributes(
                 let str_start = after_eq + 1;
                 if let Some(str_end) = find_unescaped(bytes, b'"', str_start) {
                     let content = &source[str_start..str_end];
-                    for v in visitors.iter_mut() {
-                        v.on_class_attribute(content, line, raw_tag);
-                    }
+                    let end_line = line_at_offset(line_offsets, str_end);
+                    emit_class_attribute(visitors, ClassSource::Attribute, content, line, end_line, raw_tag);
                     j = str_end + 1;
                     continue;
                 }
@@ -197,15 +407,16 @@ fn scan_tag_attributes(
             if after_eq < tag_close && bytes[after_eq] == b'{' {
                 let inner = skip_ws(bytes, after_eq + 1);
 
-                // className={'...'} or className={"..."}
+                // className={'...'} or className={"..."} (not `{"base " + ...}`)
                 if inner < tag_close && (bytes[inner] == b'\'' || bytes[inner] == b'"') {
                     let quote = bytes[inner];
                     let str_start = inner + 1;
-                    if let Some(str_end) = find_unescaped(bytes, quote, str_start) {
+                    let str_end = find_unescaped(bytes, quote, str_start)
+                        .filter(|&end| bytes.get(skip_ws(bytes, end + 1)) == Some(&b'}'));
+                    if let Some(str_end) = str_end {
                         let content = &source[str_start..str_end];
-                        for v in visitors.iter_mut() {
-                            v.on_class_attribute(content, line, raw_tag);
-                        }
+                        let end_line = line_at_offset(line_offsets, str_end);
+                        emit_class_attribute(visitors, ClassSource::Attribute, content, line, end_line, raw_tag);
                         j = str_end + 1;
                         continue;
                     }
@@ -218,35 +429,106 @@ fn scan_tag_attributes(
                         // Strip template expressions ${...} → space
                         let raw_template = &source[t_start..t_end];
                         let static_content = strip_template_expressions(raw_template);
-                        for v in visitors.iter_mut() {
-                            v.on_class_attribute(&static_content, line, raw_tag);
-                        }
+                        let end_line = line_at_offset(line_offsets, t_end);
+                        emit_class_attribute(
+                            visitors,
+                            ClassSource::Template,
+                            &static_content,
+                            line,
+                            end_line,
+                            raw_tag,
+                        );
                         j = t_end + 1;
                         continue;
                     }
                 }
 
-                // className={cn(...)} or className={clsx(...)}
-                if inner + 3 <= source.len() && starts_with_at(bytes, inner, b"cn(") {
-                    let paren_start = inner + 2;
-                    if let Some((content, end)) = extract_balanced_parens(source, paren_start) {
-                        for v in visitors.iter_mut() {
-                            v.on_class_attribute(&content, line, raw_tag);
-                        }
+                // className={cn(...)}, className={clsx(...)} or a `class_functions` call
+                if let Some(open) = class_function_at(bytes, inner, class_names.functions) {
+                    if let Some(end) = emit_class_call(source, open, line, raw_tag, line_offsets, visitors) {
                         j = end + 1;
                         continue;
                     }
                 }
-                if inner + 5 <= source.len() && starts_with_at(bytes, inner, b"clsx(") {
-                    let paren_start = inner + 4;
-                    if let Some((content, end)) = extract_balanced_parens(source, paren_start) {
+
+                // classList={{ "bg-red-500": cond() }} (SolidJS): the object's keys,
+                // present whatever their condition, like `cn()` object arguments
+                if inner < tag_close && bytes[inner] == b'{' {
+                    if let Some(close) = find_closing_bracket(bytes, inner) {
+                        let content = call_classes(&source[inner..=close]);
+                        if !content.is_empty() {
+                            let end_line = line_at_offset(line_offsets, close);
+                            emit_class_attribute(visitors, ClassSource::Attribute, &content, line, end_line, raw_tag);
+                            j = close + 1;
+                            continue;
+                        }
+                    }
+                }
+
+                // className={cond ? "..." : "..."}: one region per branch
+                let close = find_closing_bracket(bytes, after_eq);
+                if close.is_none() {
+                    let message = "class attribute expression has unbalanced brackets".to_string();
+                    emit_diagnostic(visitors, line, "unbalanced-brackets", message);
+                }
+                if let Some(close) = close {
+                    let expr_start = after_eq + 1;
+                    // className={styles.card}: a class of an imported CSS module
+                    let access = css_modules::member_access(&source[expr_start..close], class_names.css_modules);
+                    if let Some((module, class_name)) = access {
                         for v in visitors.iter_mut() {
-                            v.on_class_attribute(&content, line, raw_tag);
+                            v.on_css_module_class(module, class_name, line, raw_tag);
                         }
-                        j = end + 1;
+                        j = close + 1;
+                        continue;
+                    }
+                    // className={headerClasses}: a top-level string constant
+                    let expr = source[expr_start..close].trim();
+                    if let Some(value) = class_names.constants.strings.get(expr) {
+                        if !value.trim().is_empty() {
+                            emit_class_attribute(visitors, ClassSource::Attribute, value, line, line, raw_tag);
+                        }
+                        j = close + 1;
+                        continue;
+                    }
+                    // className={variants[variant]}: one region per entry of a
+                    // top-level variant map, `variants.primary` its entry only
+                    let access = map_access(expr).and_then(|(map, key)| Some((class_names.constants.maps.get(map)?, key)));
+                    if let Some((entries, key)) = access {
+                        match key {
+                            MapKey::Literal(key) => {
+                                let value = entries.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+                                if let Some(value) = value.filter(|v| !v.trim().is_empty()) {
+                                    emit_class_attribute(visitors, ClassSource::Attribute, value, line, line, raw_tag);
+                                }
+                            }
+                            MapKey::Expr(variant) => {
+                                emit_class_source(visitors, ClassSource::VariantMap);
+                                for (key, value) in entries {
+                                    let variant_key = format!("{}={}", variant, key);
+                                    for v in visitors.iter_mut() {
+                                        v.on_variant_map_class(value, line, &variant_key, raw_tag);
+                                    }
+                                }
+                            }
+                        }
+                        j = close + 1;
+                        continue;
+                    }
+                    let branches = conditional_classes(&source[expr_start..close]);
+                    if !branches.is_empty() {
+                        emit_class_source(visitors, ClassSource::Attribute);
+                        for (offset, content) in branches {
+                            let line = line_at_offset(line_offsets, expr_start + offset);
+                            for v in visitors.iter_mut() {
+                                v.on_conditional_class(&content, line, raw_tag);
+                            }
+                        }
+                        j = close + 1;
                         continue;
                     }
                 }
+                dynamic = true;
             }
 
             j = eq_end;
@@ -255,6 +537,445 @@ fn scan_tag_attributes(
 
         j += 1;
     }
+
+    if dynamic {
+        let line = line_at_offset(line_offsets, name_end);
+        for v in visitors.iter_mut() {
+            v.on_dynamic_class(line, raw_tag);
+        }
+    }
+}
+
+fn emit_class_source(visitors: &mut [&mut dyn JsxVisitor], source: ClassSource) {
+    for v in visitors.iter_mut() {
+        v.on_class_source(source);
+    }
+}
+
+fn emit_diagnostic(visitors: &mut [&mut dyn JsxVisitor], line: u32, kind: &str, message: String) {
+    let diagnostic = ParseDiagnostic { line, kind: kind.to_string(), message };
+    for v in visitors.iter_mut() {
+        v.on_diagnostic(&diagnostic);
+    }
+}
+
+fn emit_class_locations(visitors: &mut [&mut dyn JsxVisitor], locations: &[ClassLocation]) {
+    for v in visitors.iter_mut() {
+        v.on_class_locations(locations);
+    }
+}
+
+/// Emit the classes of the `cn`/`clsx` call in a class attribute on `line`
+/// whose `(` is at `open`: one conditional region per branch when arguments
+/// are guarded both ways by the same condition (see `call_branches`), else
+/// one region. Returns the closing paren position.
+fn emit_class_call(
+    source: &str,
+    open: usize,
+    line: u32,
+    raw_tag: &str,
+    line_offsets: &[usize],
+    visitors: &mut [&mut dyn JsxVisitor],
+) -> Option<usize> {
+    let (content, end) = class_call_content(source, open)?;
+    let branches = call_branches(&source[open + 1..end]);
+    if branches.is_empty() {
+        let end_line = line_at_offset(line_offsets, end);
+        let locations = call_class_locations(source, open, end, &content, line_offsets);
+        emit_class_locations(visitors, &locations);
+        emit_class_attribute(visitors, ClassSource::Call, &content, line, end_line, raw_tag);
+    }
+    for branch in &branches {
+        let locations = call_class_locations(source, open, end, branch, line_offsets);
+        emit_class_locations(visitors, &locations);
+        emit_class_source(visitors, ClassSource::Call);
+        for v in visitors.iter_mut() {
+            v.on_conditional_class(branch, line, raw_tag);
+        }
+    }
+    Some(end)
+}
+
+/// Emit a className value found on `line`: as a multiline class when it
+/// wraps to `end_line` (Prettier-formatted long class lists).
+fn emit_class_attribute(
+    visitors: &mut [&mut dyn JsxVisitor],
+    source: ClassSource,
+    content: &str,
+    line: u32,
+    end_line: u32,
+    raw_tag: &str,
+) {
+    for v in visitors.iter_mut() {
+        v.on_class_source(source);
+        if end_line > line {
+            v.on_multiline_class(content, line, end_line, raw_tag);
+        } else {
+            v.on_class_attribute(content, line, raw_tag);
+        }
+    }
+}
+
+/// Whether the `{` at `open` starts a `{...props}` spread attribute (not a
+/// spread inside an attribute value such as `style={{ ...base }}`).
+fn is_spread_attribute(bytes: &[u8], open: usize) -> bool {
+    let is_spread = bytes[skip_ws(bytes, open + 1)..].starts_with(b"...");
+    let before = bytes[..open].iter().rposition(|b| !b.is_ascii_whitespace());
+    is_spread && before.is_some_and(|k| bytes[k].is_ascii_alphanumeric() || matches!(bytes[k], b'"' | b'\'' | b'}' | b'`'))
+}
+
+/// Tag function of a class-bearing tagged template literal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TemplateTag {
+    /// twin.macro: `tw`, `tw.div`, `tw(Button)` tags
+    Tw,
+    /// styled-components: `styled.div`, `styled(Button)` tags; the body is
+    /// CSS, so only its `${tw...}` interpolations carry classes
+    Styled,
+}
+
+/// Tag of the template literal whose opening backtick is at `tick`, when it is
+/// `tw`/`styled` or a member/call chain rooted at one (`styled.div.attrs(...)`).
+fn template_tag(source: &str, tick: usize) -> Option<TemplateTag> {
+    let bytes = source.as_bytes();
+    let mut end = tick;
+    while end > 0 && bytes[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+    if end > 0 && bytes[end - 1] == b')' {
+        end = find_opening_paren(bytes, end - 1)?;
+    }
+    let mut start = end;
+    while start > 0 && (bytes[start - 1].is_ascii_alphanumeric() || matches!(bytes[start - 1], b'_' | b'$' | b'.')) {
+        start -= 1;
+    }
+    match source[start..end].split('.').next()? {
+        "tw" => Some(TemplateTag::Tw),
+        "styled" => Some(TemplateTag::Styled),
+        _ => None,
+    }
+}
+
+/// Emit the class content of a tagged template spanning `tick..end` (the
+/// closing backtick). `tw` bodies are class lists; `styled` bodies are CSS
+/// scanned for nested `tw` templates.
+fn emit_tagged_template(
+    source: &str,
+    tag: TemplateTag,
+    tick: usize,
+    end: usize,
+    line_offsets: &[usize],
+    visitors: &mut [&mut dyn JsxVisitor],
+) {
+    match tag {
+        TemplateTag::Tw => {
+            let content = strip_template_expressions(&source[tick + 1..end]);
+            let line = line_at_offset(line_offsets, tick);
+            emit_class_source(visitors, ClassSource::Template);
+            for v in visitors.iter_mut() {
+                v.on_class_attribute(&content, line, "");
+            }
+        }
+        TemplateTag::Styled => {
+            let bytes = source.as_bytes();
+            let mut j = tick + 1;
+            while j < end {
+                if bytes[j] == b'`' && template_tag(source, j) == Some(TemplateTag::Tw) {
+                    let nested_end = find_template_end(bytes, j + 1).unwrap_or(end);
+                    emit_tagged_template(source, TemplateTag::Tw, j, nested_end, line_offsets, visitors);
+                    j = nested_end + 1;
+                    continue;
+                }
+                j += 1;
+            }
+        }
+    }
+}
+
+/// Closing backtick of a template literal whose body starts at `start`,
+/// skipping nested templates and strings inside `${...}` expressions.
+fn find_template_end(bytes: &[u8], mut start: usize) -> Option<usize> {
+    while start < bytes.len() {
+        match bytes[start] {
+            b'\\' => start += 2,
+            b'`' => return Some(start),
+            b'$' if bytes.get(start + 1) == Some(&b'{') => {
+                let mut depth = 1;
+                start += 2;
+                while depth > 0 {
+                    match *bytes.get(start)? {
+                        b'{' => depth += 1,
+                        b'}' => depth -= 1,
+                        b'`' => start = find_template_end(bytes, start + 1)?,
+                        q @ (b'"' | b'\'') => start = find_unescaped(bytes, q, start + 1)?,
+                        _ => {}
+                    }
+                    start += 1;
+                }
+            }
+            _ => start += 1,
+        }
+    }
+    None
+}
+
+/// Opening paren matching the `)` at `close`, scanning backwards.
+fn find_opening_paren(bytes: &[u8], close: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut j = close + 1;
+    while j > 0 {
+        j -= 1;
+        match bytes[j] {
+            b')' => depth += 1,
+            b'(' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(j);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Emit the JSX text starting at `from` (up to the next `<` or `{`), if any.
+/// The main loop still scans that range, so nothing is skipped.
+fn emit_jsx_text(
+    source: &str,
+    from: usize,
+    line_offsets: &[usize],
+    visitors: &mut [&mut dyn JsxVisitor],
+) {
+    let bytes = source.as_bytes();
+    let mut end = from;
+    while end < bytes.len() && bytes[end] != b'<' && bytes[end] != b'{' {
+        end += 1;
+    }
+    let raw = &source[from..end];
+    let text = raw.trim();
+    if text.is_empty() {
+        return;
+    }
+    let start = from + (raw.len() - raw.trim_start().len());
+    let line = line_at_offset(line_offsets, start);
+    for v in visitors.iter_mut() {
+        v.on_text(text, line);
+    }
+}
+
+// ── createElement() / h() call sites ──────────────────────────────────
+
+/// A `createElement(tag, props, ...children)` or `h(tag, props, ...children)` call.
+struct CreateElementCall {
+    tag: String,
+    /// Byte range of the props object literal, braces included
+    props: Option<(usize, usize)>,
+    /// Start of the first child argument; None when the call has no children
+    children_start: Option<usize>,
+    /// Offset of the call's closing paren
+    end: usize,
+}
+
+/// Recognize a render-function call at `i`: `createElement(` (bare or as
+/// `React.createElement(`) or a bare `h(`. The first argument must be a string
+/// literal or a component identifier (`Card`, `motion.div`); `h()` only
+/// accepts string tags or capitalized components to avoid unrelated helpers.
+fn read_create_element_call(source: &str, i: usize) -> Option<CreateElementCall> {
+    let bytes = source.as_bytes();
+    if is_ident_char_before(bytes, i) {
+        return None;
+    }
+    let (paren, is_h) = if starts_with_at(bytes, i, b"createElement(") {
+        (i + "createElement".len(), false)
+    } else if starts_with_at(bytes, i, b"h(") && (i == 0 || bytes[i - 1] != b'.') {
+        (i + 1, true)
+    } else {
+        return None;
+    };
+    let (_, end) = extract_balanced_parens(source, paren)?;
+
+    // First argument: the tag
+    let mut j = skip_ws(bytes, paren + 1);
+    let tag = if j < end && (bytes[j] == b'"' || bytes[j] == b'\'') {
+        let close = find_unescaped(bytes, bytes[j], j + 1).filter(|&c| c < end)?;
+        let tag = source[j + 1..close].to_string();
+        j = close + 1;
+        tag
+    } else if j < end && bytes[j].is_ascii_alphabetic() {
+        let (tag, name_end) = read_tag_name(bytes, j);
+        if is_h && !bytes[j].is_ascii_uppercase() {
+            return None;
+        }
+        j = name_end;
+        tag
+    } else {
+        return None;
+    };
+    if tag.is_empty() || !tag.bytes().all(is_tag_name_ch) {
+        return None;
+    }
+    j = skip_ws(bytes, j);
+    if j == end {
+        return Some(CreateElementCall { tag, props: None, children_start: None, end });
+    }
+    if bytes[j] != b',' {
+        return None;
+    }
+
+    // Second argument: props object, or null/undefined/an identifier
+    j = skip_ws(bytes, j + 1);
+    let mut props = None;
+    if j < end && bytes[j] == b'{' {
+        let close = find_closing_bracket(bytes, j).filter(|&c| c < end)?;
+        props = Some((j, close + 1));
+        j = close + 1;
+    } else {
+        while j < end && bytes[j] != b',' {
+            j += 1;
+        }
+    }
+    j = skip_ws(bytes, j);
+    let children_start = if j < end && bytes[j] == b',' {
+        Some(j + 1)
+    } else {
+        None
+    };
+    Some(CreateElementCall { tag, props, children_start, end })
+}
+
+/// Render the call as a JSX opening tag so raw-tag consumers (explicit bg,
+/// opacity, disabled, inline style) work unchanged:
+/// `h("a", { class: "x", disabled: true })` → `<a class="x" disabled={true}>`.
+fn create_element_raw_tag(source: &str, call: &CreateElementCall) -> String {
+    let mut raw = format!("<{}", call.tag);
+    if let Some((start, end)) = call.props {
+        for (_, entry) in split_object_entries(source, start, end) {
+            if entry.starts_with("...") {
+                continue;
+            }
+            let (key, value) = match find_top_level_colon(entry) {
+                Some(colon) => (entry[..colon].trim(), entry[colon + 1..].trim()),
+                None => (entry, entry),
+            };
+            let key = key.trim_matches(|c| c == '"' || c == '\'');
+            let quoted = value.len() >= 2
+                && (value.starts_with('"') && value.ends_with('"')
+                    || value.starts_with('\'') && value.ends_with('\''));
+            if quoted {
+                raw.push_str(&format!(" {}={}", key, value));
+            } else {
+                raw.push_str(&format!(" {}={{{}}}", key, value));
+            }
+        }
+    }
+    raw.push('>');
+    raw
+}
+
+/// Class string of a `className:`/`class:` prop (string, template or
+/// `cn()`/`clsx()` call) with the offset of the key for line numbering.
+fn create_element_class_value(source: &str, start: usize, end: usize) -> Option<(usize, String)> {
+    let bytes = source.as_bytes();
+    for (pos, entry) in split_object_entries(source, start, end) {
+        let Some(colon) = find_top_level_colon(entry) else {
+            continue;
+        };
+        let key = entry[..colon].trim().trim_matches(|c| c == '"' || c == '\'');
+        if key != "className" && key != "class" {
+            continue;
+        }
+        let value_start = skip_ws(bytes, pos + colon + 1);
+        let value = source.get(value_start..pos + entry.len())?;
+        let content = match bytes.get(value_start)? {
+            b'"' | b'\'' if value.len() >= 2 => value[1..value.len() - 1].to_string(),
+            b'`' if value.len() >= 2 => strip_template_expressions(&value[1..value.len() - 1]),
+            _ => {
+                let fn_len = if value.starts_with("cn(") {
+                    2
+                } else if value.starts_with("clsx(") {
+                    4
+                } else {
+                    return None;
+                };
+                class_call_content(source, value_start + fn_len)?.0
+            }
+        };
+        return Some((pos, content));
+    }
+    None
+}
+
+/// Top-level entries of the object literal at `start..end` (braces included),
+/// as (offset of the trimmed entry, trimmed entry text).
+pub(super) fn split_object_entries(source: &str, start: usize, end: usize) -> Vec<(usize, &str)> {
+    let bytes = source.as_bytes();
+    let inner_end = end - 1;
+    let mut entries = Vec::new();
+    let mut entry_start = start + 1;
+    let mut j = start + 1;
+    while j <= inner_end {
+        let ch = bytes[j];
+        if j == inner_end || ch == b',' {
+            let from = skip_ws(bytes, entry_start);
+            let text = source[from.min(j)..j].trim_end();
+            if !text.is_empty() {
+                entries.push((from, text));
+            }
+            entry_start = j + 1;
+            j += 1;
+            continue;
+        }
+        if matches!(ch, b'{' | b'(' | b'[') {
+            j = find_closing_bracket(bytes, j).map_or(inner_end, |c| c.min(inner_end - 1)) + 1;
+            continue;
+        }
+        if matches!(ch, b'"' | b'\'' | b'`') {
+            j = find_unescaped(bytes, ch, j + 1).map_or(inner_end, |c| c.min(inner_end - 1)) + 1;
+            continue;
+        }
+        j += 1;
+    }
+    entries
+}
+
+/// Offset of the first `:` in an object entry outside strings and brackets.
+pub(super) fn find_top_level_colon(entry: &str) -> Option<usize> {
+    let bytes = entry.as_bytes();
+    let mut j = 0;
+    while j < bytes.len() {
+        match bytes[j] {
+            b':' => return Some(j),
+            b'"' | b'\'' | b'`' => j = find_unescaped(bytes, bytes[j], j + 1)? + 1,
+            b'{' | b'(' | b'[' => return None,
+            _ => j += 1,
+        }
+    }
+    None
+}
+
+/// Offset of the bracket closing the one at `open` (`{`, `(` or `[`),
+/// skipping string and template literals.
+pub(super) fn find_closing_bracket(bytes: &[u8], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut j = open;
+    while j < bytes.len() {
+        match bytes[j] {
+            b'{' | b'(' | b'[' => depth += 1,
+            b'}' | b')' | b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(j);
+                }
+            }
+            b'"' | b'\'' | b'`' => {
+                j = find_unescaped(bytes, bytes[j], j + 1)?;
+            }
+            _ => {}
+        }
+        j += 1;
+    }
+    None
 }
 
 // ── Helper Functions ──────────────────────────────────────────────────
@@ -284,7 +1005,7 @@ fn is_tag_name_ch(ch: u8) -> bool {
 }
 
 /// Read a JSX tag name starting at `start`. Returns (name, end_position).
-fn read_tag_name(bytes: &[u8], start: usize) -> (String, usize) {
+pub(super) fn read_tag_name(bytes: &[u8], start: usize) -> (String, usize) {
     let mut end = start;
     while end < bytes.len() && is_tag_name_ch(bytes[end]) {
         end += 1;
@@ -311,7 +1032,7 @@ fn skip_ws(bytes: &[u8], mut i: usize) -> usize {
 }
 
 /// Find next unescaped occurrence of `target` starting from `start`.
-fn find_unescaped(bytes: &[u8], target: u8, mut start: usize) -> Option<usize> {
+pub(super) fn find_unescaped(bytes: &[u8], target: u8, mut start: usize) -> Option<usize> {
     while start < bytes.len() {
         if bytes[start] == b'\\' {
             start += 2;
@@ -392,7 +1113,7 @@ fn is_self_closing_tag(source: &str, from_pos: usize) -> bool {
 
 /// Find the byte offset just past the closing `>` or `/>` of a tag.
 /// Respects braces and strings inside attributes.
-fn find_tag_close(source: &str, from_pos: usize) -> usize {
+pub(super) fn find_tag_close(source: &str, from_pos: usize) -> usize {
     let bytes = source.as_bytes();
     let len = bytes.len();
     let mut j = from_pos;
@@ -445,6 +1166,311 @@ fn find_tag_close(source: &str, from_pos: usize) -> usize {
     len // malformed — return end of source
 }
 
+/// Classes of the `cn(...)` / `clsx(...)` call whose `(` is at `open_pos`,
+/// and the closing paren position (see `call_classes`).
+fn class_call_content(source: &str, open_pos: usize) -> Option<(String, usize)> {
+    let (content, end) = extract_balanced_parens(source, open_pos)?;
+    Some((call_classes(&content), end))
+}
+
+/// Where each class of `classes` sits in the `cn`/`clsx` call whose `(` is at
+/// `open` and `)` at `close`: the string literal tokens of each top-level
+/// argument that made it into `classes` (see `call_classes`), in source order.
+/// Tokens of template literals touching a `${...}` expression are left out.
+fn call_class_locations(
+    source: &str,
+    open: usize,
+    close: usize,
+    classes: &str,
+    line_offsets: &[usize],
+) -> Vec<ClassLocation> {
+    let bytes = source.as_bytes();
+    let known: std::collections::HashSet<&str> = classes.split_whitespace().collect();
+    let mut locations = Vec::new();
+    let mut argument = 0;
+    let mut depth = 0usize;
+    let mut j = open + 1;
+    while j < close {
+        match bytes[j] {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+            b',' if depth == 0 => argument += 1,
+            quote @ (b'"' | b'\'' | b'`') => {
+                let Some(end) = find_unescaped(bytes, quote, j + 1).filter(|&end| end < close) else {
+                    break;
+                };
+                let mut token_start = None;
+                for k in j + 1..=end {
+                    let is_space = k == end || bytes[k].is_ascii_whitespace();
+                    match (token_start, is_space) {
+                        (None, false) => token_start = Some(k),
+                        (Some(start), true) => {
+                            let token = &source[start..k];
+                            if known.contains(token) && !token.contains(['$', '{', '}']) {
+                                let line = line_at_offset(line_offsets, start);
+                                locations.push(ClassLocation {
+                                    class_name: token.to_string(),
+                                    argument,
+                                    line,
+                                    column: (start - line_offsets[line as usize - 1]) as u32 + 1,
+                                });
+                            }
+                            token_start = None;
+                        }
+                        _ => {}
+                    }
+                }
+                j = end;
+            }
+            _ => {}
+        }
+        j += 1;
+    }
+    locations
+}
+
+/// Class list of `cn`/`clsx` arguments: string literals, array items and
+/// object keys. `{ "bg-red-500": isError }` contributes its key, never its
+/// condition; identifiers and calls contribute nothing.
+fn call_classes(arguments: &str) -> String {
+    super::markup::binding_classes(&format!("({})", arguments), &std::collections::HashMap::new()).join(" ")
+}
+
+/// Most conditions a `cn()` call is split on (2^n branches).
+const MAX_CALL_SWITCHES: usize = 3;
+
+/// Classes of each branch of `cn`/`clsx` `arguments` whose conditions guard
+/// arguments both ways (`isError && "a"`, `!isError && "b"`): one branch per
+/// combination of those conditions, true first, each with the unguarded
+/// arguments and the ones guarded by other conditions (which may hold
+/// together). Empty when no condition does, or too many.
+fn call_branches(arguments: &str) -> Vec<String> {
+    let args = split_arguments(arguments);
+    let guards: Vec<Option<(&str, bool)>> = args.iter().map(|arg| logical_and_guard(arg)).collect();
+    let mut switches: Vec<&str> = Vec::new();
+    for &(condition, negated) in guards.iter().flatten() {
+        let both_ways = guards.iter().flatten().any(|&(c, n)| c == condition && n != negated);
+        if both_ways && !switches.contains(&condition) {
+            switches.push(condition);
+        }
+    }
+    if switches.is_empty() || switches.len() > MAX_CALL_SWITCHES {
+        return Vec::new();
+    }
+    (0..1usize << switches.len())
+        .rev()
+        .map(|combination| {
+            let applied: Vec<&str> = args
+                .iter()
+                .zip(&guards)
+                .filter(|(_, guard)| match guard {
+                    Some((condition, negated)) => match switches.iter().position(|s| s == condition) {
+                        Some(bit) => (combination >> bit & 1 == 1) != *negated,
+                        None => true,
+                    },
+                    None => true,
+                })
+                .map(|(arg, _)| *arg)
+                .collect();
+            call_classes(&applied.join(","))
+        })
+        .filter(|classes| !classes.is_empty())
+        .collect()
+}
+
+/// Top-level comma-separated arguments of a call's `arguments` text.
+fn split_arguments(arguments: &str) -> Vec<&str> {
+    let bytes = arguments.as_bytes();
+    let mut args = Vec::new();
+    let mut start = 0;
+    let mut j = 0;
+    while j < bytes.len() {
+        match bytes[j] {
+            b'{' | b'(' | b'[' => j = find_closing_bracket(bytes, j).unwrap_or(bytes.len()),
+            b'"' | b'\'' | b'`' => j = find_unescaped(bytes, bytes[j], j + 1).unwrap_or(bytes.len()),
+            b',' => {
+                args.push(&arguments[start..j]);
+                start = j + 1;
+            }
+            _ => {}
+        }
+        j += 1;
+    }
+    args.push(&arguments[start.min(arguments.len())..]);
+    args
+}
+
+/// Condition of a `cond && ...` argument without its `!` prefixes, and
+/// whether it's negated. None for other arguments, or conditions mixing
+/// `||` / `?`.
+fn logical_and_guard(arg: &str) -> Option<(&str, bool)> {
+    let bytes = arg.as_bytes();
+    let mut j = 0;
+    while j + 1 < bytes.len() {
+        match bytes[j] {
+            b'{' | b'(' | b'[' => j = find_closing_bracket(bytes, j)?,
+            b'"' | b'\'' | b'`' => j = find_unescaped(bytes, bytes[j], j + 1)?,
+            b'|' | b'?' => return None,
+            b'&' if bytes[j + 1] == b'&' => {
+                let condition = arg[..j].trim();
+                let stripped = condition.trim_start_matches(|c: char| c == '!' || c.is_whitespace());
+                let negations = condition[..condition.len() - stripped.len()].matches('!').count();
+                return (!stripped.is_empty()).then_some((stripped, negations % 2 == 1));
+            }
+            _ => {}
+        }
+        j += 1;
+    }
+    None
+}
+
+/// Classes of each branch of a top-level ternary, with the branch's offset in
+/// `expr` (nested ternaries yield one entry per leaf), of the operand of a
+/// `cond && "..."` guard, or of each combination of a string concatenation
+/// (`"base " + (cond ? "a" : "b")`, see `concat_values`). Branches without
+/// static classes (`null`, identifiers) are dropped; empty for any other `expr`.
+fn conditional_classes(expr: &str) -> Vec<(usize, String)> {
+    let lead = expr.len() - expr.trim_start().len();
+    let Some(branches) = ternary_branches(expr) else {
+        if logical_and_guard(expr).is_some() {
+            let (start, operand) = *split_top_level(expr, "&&").last().unwrap();
+            let nested = conditional_classes(operand);
+            if !nested.is_empty() {
+                return nested.into_iter().map(|(offset, content)| (start + offset, content)).collect();
+            }
+            let content = call_classes(operand);
+            let lead = operand.len() - operand.trim_start().len();
+            return if content.is_empty() { Vec::new() } else { vec![(start + lead, content)] };
+        }
+        if split_top_level(expr, "+").len() < 2 {
+            return Vec::new();
+        }
+        let mut classes: Vec<(usize, String)> = Vec::new();
+        for value in concat_values(expr) {
+            let content: Vec<&str> = value.split_whitespace().filter(|token| !token.contains(DYNAMIC)).collect();
+            let content = content.join(" ");
+            if !content.is_empty() && !classes.iter().any(|(_, c)| *c == content) {
+                classes.push((lead, content));
+            }
+        }
+        return classes;
+    };
+    let mut classes = Vec::new();
+    for (start, branch) in branches {
+        let nested = conditional_classes(branch);
+        if !nested.is_empty() {
+            classes.extend(nested.into_iter().map(|(offset, content)| (start + offset, content)));
+            continue;
+        }
+        let content = call_classes(branch);
+        if !content.is_empty() {
+            let lead = branch.len() - branch.trim_start().len();
+            classes.push((start + lead, content));
+        }
+    }
+    classes
+}
+
+/// Stands in for a dynamic operand of a string concatenation; tokens
+/// touching it are partial classes (`"bg-" + color`).
+const DYNAMIC: char = '\u{0}';
+
+/// Most strings a concatenation is expanded to (see `concat_values`).
+const MAX_CONCAT_VALUES: usize = 1 << MAX_CALL_SWITCHES;
+
+/// Strings the expression `expr` can evaluate to: literals, each branch of a
+/// ternary, a `cond && ...` operand or "", and every combination of a `+`
+/// concatenation's operands. Anything else is `DYNAMIC`.
+fn concat_values(expr: &str) -> Vec<String> {
+    let expr = expr.trim();
+    if let Some([(_, then), (_, otherwise)]) = ternary_branches(expr) {
+        let mut values = concat_values(then);
+        values.extend(concat_values(otherwise));
+        return values;
+    }
+    if logical_and_guard(expr).is_some() {
+        let (_, operand) = *split_top_level(expr, "&&").last().unwrap();
+        let mut values = concat_values(operand);
+        values.push(String::new());
+        return values;
+    }
+    let operands = split_top_level(expr, "+");
+    if operands.len() > 1 {
+        let mut values = vec![String::new()];
+        for (_, operand) in operands {
+            let tails = concat_values(operand);
+            values = values
+                .iter()
+                .flat_map(|head| tails.iter().map(move |tail| format!("{}{}", head, tail)))
+                .take(MAX_CONCAT_VALUES)
+                .collect();
+        }
+        return values;
+    }
+    let bytes = expr.as_bytes();
+    match bytes.first() {
+        Some(b'(') if find_closing_bracket(bytes, 0) == Some(expr.len() - 1) => {
+            concat_values(&expr[1..expr.len() - 1])
+        }
+        Some(&quote @ (b'"' | b'\'')) if find_unescaped(bytes, quote, 1) == Some(expr.len() - 1) => {
+            vec![expr[1..expr.len() - 1].to_string()]
+        }
+        _ if matches!(expr, "null" | "undefined" | "false" | "") => vec![String::new()],
+        _ => vec![DYNAMIC.to_string()],
+    }
+}
+
+/// Top-level operands of `expr` separated by `op`, with their offsets in
+/// `expr` (brackets and string literals are skipped).
+fn split_top_level<'a>(expr: &'a str, op: &str) -> Vec<(usize, &'a str)> {
+    let bytes = expr.as_bytes();
+    let mut operands = Vec::new();
+    let mut start = 0;
+    let mut j = 0;
+    while j < bytes.len() {
+        match bytes[j] {
+            b'{' | b'(' | b'[' => j = find_closing_bracket(bytes, j).unwrap_or(bytes.len()),
+            b'"' | b'\'' | b'`' => j = find_unescaped(bytes, bytes[j], j + 1).unwrap_or(bytes.len()),
+            _ if bytes[j..].starts_with(op.as_bytes()) => {
+                operands.push((start, &expr[start..j]));
+                j += op.len();
+                start = j;
+                continue;
+            }
+            _ => {}
+        }
+        j += 1;
+    }
+    operands.push((start.min(expr.len()), &expr[start.min(expr.len())..]));
+    operands
+}
+
+/// (then, else) branches of a top-level `cond ? a : b`, with their offsets in
+/// `expr`. Optional chaining (`?.`) and nullish coalescing (`??`) are skipped.
+fn ternary_branches(expr: &str) -> Option<[(usize, &str); 2]> {
+    let bytes = expr.as_bytes();
+    let mut question = None;
+    let mut nested = 0usize;
+    let mut j = 0;
+    while j < bytes.len() {
+        match bytes[j] {
+            b'{' | b'(' | b'[' => j = find_closing_bracket(bytes, j)?,
+            b'"' | b'\'' | b'`' => j = find_unescaped(bytes, bytes[j], j + 1)?,
+            b'?' if matches!(bytes.get(j + 1), Some(b'.' | b'?')) => j += 1,
+            b'?' if question.is_some() => nested += 1,
+            b'?' => question = Some(j),
+            b':' => match question {
+                Some(q) if nested == 0 => return Some([(q + 1, &expr[q + 1..j]), (j + 1, &expr[j + 1..])]),
+                Some(_) => nested -= 1,
+                None => {}
+            },
+            _ => {}
+        }
+        j += 1;
+    }
+    None
+}
+
 /// Extract balanced parentheses content from position `open_pos`.
 /// The char at `open_pos` must be `(`.
 /// Returns (content_inside_parens, closing_paren_position).
@@ -509,14 +1535,28 @@ fn extract_balanced_parens(source: &str, open_pos: usize) -> Option<(String, usi
 }
 
 /// Strip `${...}` expressions from a template literal body, replacing with space.
-fn strip_template_expressions(template: &str) -> String {
+pub(super) fn strip_template_expressions(template: &str) -> String {
+    replace_template_expressions(template, ' ')
+}
+
+/// Classes of a template literal body no `${...}` expression is glued to:
+/// `px-${n} font-medium` has only `font-medium`.
+pub(super) fn template_classes(template: &str) -> String {
+    let replaced = replace_template_expressions(template, '$');
+    replaced.split_whitespace().filter(|token| !token.contains('$')).collect::<Vec<_>>().join(" ")
+}
+
+/// `template` with each `${...}` expression replaced by `with`.
+fn replace_template_expressions(template: &str, with: char) -> String {
     let bytes = template.as_bytes();
     let len = bytes.len();
     let mut result = String::with_capacity(len);
+    let mut copied = 0;
     let mut i = 0;
 
-    while i < len {
-        if i + 1 < len && bytes[i] == b'$' && bytes[i + 1] == b'{' {
+    while i + 1 < len {
+        if bytes[i] == b'$' && bytes[i + 1] == b'{' {
+            result.push_str(&template[copied..i]);
             // Skip the expression
             let mut depth = 1;
             i += 2;
@@ -528,13 +1568,14 @@ fn strip_template_expressions(template: &str) -> String {
                 }
                 i += 1;
             }
-            result.push(' ');
+            result.push(with);
+            copied = i;
         } else {
-            result.push(template.as_bytes()[i] as char);
             i += 1;
         }
     }
 
+    result.push_str(&template[copied..]);
     result
 }
 
@@ -606,6 +1647,11 @@ mod tests {
         );
     }
 
+    #[test]
+    fn template_classes_drop_glued_tokens() {
+        assert_eq!(template_classes("px-${n} font-medium ${a ? \"b c\" : d}-x è ${e}"), "font-medium è");
+    }
+
     // ── Tokenizer integration tests using a RecordingVisitor ──
 
     struct RecordingVisitor {
@@ -636,6 +1682,9 @@ mod tests {
         fn on_class_attribute(&mut self, value: &str, line: u32, _raw: &str) {
             self.events.push(format!("CLASS:L{}:{}", line, value));
         }
+        fn on_dynamic_class(&mut self, line: u32, _raw: &str) {
+            self.events.push(format!("DYNAMIC:L{}", line));
+        }
     }
 
     #[test]
@@ -652,6 +1701,56 @@ mod tests {
         assert_eq!(v.events, vec!["OPEN:br/"]);
     }
 
+    #[test]
+    fn custom_class_attributes() {
+        let mut v = RecordingVisitor::new();
+        let source = r#"<Field tw="p-2 text-black" innerClassName={cn("text-gray-500")} data-tw="x">a</Field>
+<Menu classes={`bg-white ${open}`} className="ring-1" />"#;
+        let attributes = ["tw", "innerClassName", "classes"].map(String::from);
+        scan_jsx_with_attributes(source, &attributes, &[], &mut [&mut v as &mut dyn JsxVisitor]);
+        let classes: Vec<&String> = v.events.iter().filter(|e| e.starts_with("CLASS:")).collect();
+        assert_eq!(
+            classes,
+            vec!["CLASS:L1:p-2 text-black", "CLASS:L1:text-gray-500", "CLASS:L2:bg-white  ", "CLASS:L2:ring-1"]
+        );
+
+        // Not extracted unless configured
+        let mut v = RecordingVisitor::new();
+        scan_jsx(source, &mut [&mut v as &mut dyn JsxVisitor]);
+        assert_eq!(v.events.iter().filter(|e| e.starts_with("CLASS:")).count(), 1);
+    }
+
+    #[test]
+    fn custom_class_functions() {
+        let mut v = RecordingVisitor::new();
+        let source = r#"<p className={twMerge("p-2", active && "text-white")}>a</p>
+const link = cx("text-sky-600", { underline: hover });
+const other = mycx("bg-black");"#;
+        let functions = ["twMerge", "cx"].map(String::from);
+        scan_jsx_with_attributes(source, &[], &functions, &mut [&mut v as &mut dyn JsxVisitor]);
+        let classes: Vec<&String> = v.events.iter().filter(|e| e.starts_with("CLASS:")).collect();
+        assert_eq!(classes, vec!["CLASS:L1:p-2 text-white", "CLASS:L2:text-sky-600 underline"]);
+
+        // Not extracted unless configured
+        let mut v = RecordingVisitor::new();
+        scan_jsx(source, &mut [&mut v as &mut dyn JsxVisitor]);
+        assert!(!v.events.iter().any(|e| e.starts_with("CLASS:")));
+    }
+
+    #[test]
+    fn solid_class_list_keys() {
+        let mut v = RecordingVisitor::new();
+        let source = r#"<li class="item" classList={{ "bg-red-500 text-white": err(), 'ring-2': sel() }}>a</li>
+<p classList={{
+  muted: !on(),
+}}>b</p>
+<i classList={{ [cls()]: true }} />"#;
+        scan_jsx(source, &mut [&mut v as &mut dyn JsxVisitor]);
+        let events: Vec<&String> =
+            v.events.iter().filter(|e| e.starts_with("CLASS:") || e.starts_with("DYNAMIC:")).collect();
+        assert_eq!(events, vec!["CLASS:L1:bg-red-500 text-white ring-2", "CLASS:L2:muted", "DYNAMIC:L5"]);
+    }
+
     #[test]
     fn class_name_static() {
         let mut v = RecordingVisitor::new();
@@ -788,6 +1887,120 @@ mod tests {
         assert_eq!(class_events.len(), 1);
     }
 
+    #[test]
+    fn twin_tagged_templates() {
+        let mut v = RecordingVisitor::new();
+        let source = "const Title = tw.h1`text-gray-900 ${big ? 'text-xl' : ''}`;\nconst Btn = tw(Button)`bg-primary`;\nconst plain = `text-red-500`;\nconst x = foo.tw`bg-red-500`;";
+        scan_jsx(source, &mut [&mut v as &mut dyn JsxVisitor]);
+        let class_events: Vec<_> = v.events.iter().filter(|e| e.starts_with("CLASS:")).collect();
+        assert_eq!(class_events.len(), 2);
+        assert!(class_events[0].starts_with("CLASS:L1:text-gray-900"));
+        assert!(!class_events[0].contains("text-xl"));
+        assert_eq!(class_events[1].as_str(), "CLASS:L2:bg-primary");
+    }
+
+    #[test]
+    fn styled_templates_yield_nested_tw_only() {
+        let mut v = RecordingVisitor::new();
+        let source = "const Card = styled.div.attrs({ role: 'note' })`\n  color: red;\n  ${tw`bg-white text-slate-600`}\n  ${({ on }) => on && tw`ring-2`}\n`;\n<Card className=\"p-4\" />";
+        scan_jsx(source, &mut [&mut v as &mut dyn JsxVisitor]);
+        let class_events: Vec<_> = v.events.iter().filter(|e| e.starts_with("CLASS:")).collect();
+        assert_eq!(class_events.len(), 3);
+        assert_eq!(class_events[0].as_str(), "CLASS:L3:bg-white text-slate-600");
+        assert!(class_events[1].contains("ring-2"));
+        assert!(class_events[2].contains("p-4"));
+        assert!(!class_events.iter().any(|e| e.contains("color")));
+    }
+
+    #[test]
+    fn clsx_object_keys_are_classes() {
+        let mut v = RecordingVisitor::new();
+        scan_jsx(
+            r#"<p className={clsx("p-2", { "bg-red-500": isError, 'hover:text-white': true, underline: link }, [cond && "ring-1"])}>x</p>
+const cls = cn({ "text-muted-foreground": !active }, `px-${n} font-medium`);"#,
+            &mut [&mut v as &mut dyn JsxVisitor],
+        );
+        let class_events: Vec<_> = v.events.iter().filter(|e| e.starts_with("CLASS:")).collect();
+        assert_eq!(
+            class_events,
+            vec![
+                "CLASS:L1:p-2 bg-red-500 hover:text-white underline ring-1",
+                "CLASS:L2:text-muted-foreground font-medium",
+            ]
+        );
+    }
+
+    #[test]
+    fn ternary_branches_are_separate_classes() {
+        let mut v = RecordingVisitor::new();
+        scan_jsx(
+            r#"<b className={active ? "bg-red-500 text-white" : 'bg-green-500 text-black'}>x</b>
+<i className={user?.admin ?? on
+  ? cn("ring-1", { "text-sky-600": hover })
+  : size === "sm" ? `p-1 ${gap}` : null}>y</i>
+<u className={styles.link}>z</u>"#,
+            &mut [&mut v as &mut dyn JsxVisitor],
+        );
+        let class_events: Vec<_> = v.events.iter().filter(|e| e.starts_with("CLASS:")).collect();
+        assert_eq!(
+            class_events,
+            vec![
+                "CLASS:L1:bg-red-500 text-white",
+                "CLASS:L1:bg-green-500 text-black",
+                "CLASS:L3:ring-1 text-sky-600",
+                "CLASS:L4:p-1",
+            ]
+        );
+    }
+
+    #[test]
+    fn logical_and_and_concatenated_classes() {
+        let mut v = RecordingVisitor::new();
+        scan_jsx(
+            r#"<b className={isActive && "bg-blue-600 text-white"}>x</b>
+<i className={"base " + (cond ? "bg-red-500" : 'bg-green-500')}>y</i>
+<u className={"p-2 text-" + tone + " " + (open && "ring-1")}>z</u>
+<s className={styles.a + " " + styles.b}>w</s>"#,
+            &mut [&mut v as &mut dyn JsxVisitor],
+        );
+        let class_events: Vec<_> = v.events.iter().filter(|e| e.starts_with("CLASS:")).collect();
+        assert_eq!(
+            class_events,
+            vec![
+                "CLASS:L1:bg-blue-600 text-white",
+                "CLASS:L2:base bg-red-500",
+                "CLASS:L2:base bg-green-500",
+                "CLASS:L3:p-2 ring-1",
+                "CLASS:L3:p-2",
+            ]
+        );
+        assert!(v.events.iter().any(|e| e.starts_with("DYNAMIC:L4")));
+    }
+
+    #[test]
+    fn spread_and_prop_class_names_are_dynamic() {
+        let mut v = RecordingVisitor::new();
+        scan_jsx(
+            r#"<Button className="bg-primary" {...props}>a</Button>
+<div
+  className={props.className}>b</div>
+<p style={{ ...base, color: "red" }} className={cn("p-2", { ...extra })}>c</p>"#,
+            &mut [&mut v as &mut dyn JsxVisitor],
+        );
+        let events: Vec<_> = v.events.iter().filter(|e| !e.starts_with("OPEN") && !e.starts_with("CLOSE")).collect();
+        assert_eq!(events, vec!["CLASS:L1:bg-primary", "DYNAMIC:L1", "DYNAMIC:L2", "CLASS:L4:p-2"]);
+    }
+
+    #[test]
+    fn short_fragments_emit_tag_events() {
+        let mut v = RecordingVisitor::new();
+        scan_jsx("<><p>x</p><React.Fragment></React.Fragment></>", &mut [&mut v as &mut dyn JsxVisitor]);
+        assert_eq!(
+            v.events,
+            vec!["OPEN:Fragment", "OPEN:p", "CLOSE:p", "OPEN:React.Fragment", "CLOSE:React.Fragment", "CLOSE:Fragment"]
+        );
+    }
+
     #[test]
     fn component_with_class() {
         let mut v = RecordingVisitor::new();
@@ -813,4 +2026,79 @@ mod tests {
         let class_events: Vec<_> = v.events.iter().filter(|e| e.starts_with("CLASS:")).collect();
         assert_eq!(class_events.len(), 0);
     }
+
+    #[test]
+    fn create_element_call() {
+        let mut v = RecordingVisitor::new();
+        scan_jsx(
+            r#"React.createElement("div", { className: "bg-red-500 text-white" }, "hi")"#,
+            &mut [&mut v as &mut dyn JsxVisitor],
+        );
+        assert_eq!(
+            v.events,
+            vec!["OPEN:div", "CLASS:L1:bg-red-500 text-white", "CLOSE:div"]
+        );
+    }
+
+    #[test]
+    fn nested_create_element_calls() {
+        let mut v = RecordingVisitor::new();
+        scan_jsx(
+            "createElement(Card, null,\n  createElement('p', { className: `text-${tone} p-2` }),\n  'after'\n)",
+            &mut [&mut v as &mut dyn JsxVisitor],
+        );
+        assert_eq!(
+            v.events,
+            vec!["OPEN:Card", "OPEN:p/", "CLASS:L2:text-  p-2", "CLOSE:Card"]
+        );
+    }
+
+    #[test]
+    fn h_call_with_class_key_and_cn() {
+        let mut v = RecordingVisitor::new();
+        scan_jsx(
+            r#"h("span", { key: 1, class: cn("text-white", active && "font-bold") }, [label])"#,
+            &mut [&mut v as &mut dyn JsxVisitor],
+        );
+        assert_eq!(v.events[0], "OPEN:span");
+        assert_eq!(v.events[1], "CLASS:L1:text-white font-bold");
+        assert_eq!(v.events.last().unwrap(), "CLOSE:span");
+    }
+
+    #[test]
+    fn h_requires_tag_argument() {
+        let mut v = RecordingVisitor::new();
+        scan_jsx("const x = h(value, 2); math.h(\"div\")", &mut [&mut v as &mut dyn JsxVisitor]);
+        assert!(v.events.is_empty());
+    }
+
+    #[test]
+    fn create_element_raw_tag_as_jsx() {
+        let source = r#"createElement("button", { disabled: true, "aria-label": 'x', ...rest, style: { color: "red" } })"#;
+        let call = read_create_element_call(source, 0).unwrap();
+        assert_eq!(
+            create_element_raw_tag(source, &call),
+            r#"<button disabled={true} aria-label='x' style={{ color: "red" }}>"#
+        );
+    }
+
+    struct TextVisitor {
+        texts: Vec<String>,
+    }
+
+    impl JsxVisitor for TextVisitor {
+        fn on_text(&mut self, text: &str, line: u32) {
+            self.texts.push(format!("L{}:{}", line, text));
+        }
+    }
+
+    #[test]
+    fn jsx_text_events() {
+        let mut v = TextVisitor { texts: vec![] };
+        scan_jsx(
+            "<p>\n  Hello <b>there</b> {name}!<br/>Bye\n</p>",
+            &mut [&mut v as &mut dyn JsxVisitor],
+        );
+        assert_eq!(v.texts, vec!["L2:Hello", "L2:there", "L2:Bye"]);
+    }
 }
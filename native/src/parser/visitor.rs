@@ -1,3 +1,43 @@
+use crate::types::{ClassLocation, ParseDiagnostic};
+
+/// How a class value was captured (see `JsxVisitor::on_class_source`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClassSource {
+    /// A static class attribute: `className="..."`, `className={'...'}`,
+    /// a ternary's string branches, a `classList` object, `createElement` props
+    Attribute,
+    /// `cn()` / `clsx()` inside a class attribute
+    Call,
+    /// A template literal class attribute or a `tw` tagged template
+    Template,
+    /// `cn()` / `clsx()` / `cva()` outside any attribute
+    Standalone,
+    /// One variant branch of a `cva()` call
+    Variant,
+    /// A CSS Modules class (`className={styles.card}`) resolved to its colors
+    CssModule,
+    /// No classes: an element colored by an element rule of a `<style>` block
+    Stylesheet,
+    /// One entry of a variant map object (`className={variants[variant]}`)
+    VariantMap,
+}
+
+impl ClassSource {
+    /// "attribute" | "cn" | "template" | "standalone" | "cva" | "css-module" | "stylesheet" | "variant-map"
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ClassSource::Attribute => "attribute",
+            ClassSource::Call => "cn",
+            ClassSource::Template => "template",
+            ClassSource::Standalone => "standalone",
+            ClassSource::Variant => "cva",
+            ClassSource::CssModule => "css-module",
+            ClassSource::Stylesheet => "stylesheet",
+            ClassSource::VariantMap => "variant-map",
+        }
+    }
+}
+
 /// Events emitted by the tokenizer for visitor consumption.
 /// Each visitor implements the methods it cares about; default impls are no-ops.
 #[allow(unused_variables)]
@@ -22,6 +62,69 @@ pub trait JsxVisitor {
     /// `raw_tag`: the full raw tag string for context (inline style extraction, etc.)
     fn on_class_attribute(&mut self, value: &str, line: u32, raw_tag: &str) {}
 
+    /// Called before the class event(s) of each class value, naming how it
+    /// was captured; applies to every event until the next call.
+    fn on_class_source(&mut self, source: ClassSource) {}
+
+    /// Called before the class event of a `cn()` / `clsx()` call with where
+    /// each of its classes sits; applies to that event only.
+    fn on_class_locations(&mut self, locations: &[ClassLocation]) {}
+
+    /// Called for each variant branch of a `cva()` call instead of one
+    /// `on_class_attribute` for the whole call (see `cva::branches`).
+    /// `value`: the branch's classes (base, the option, other variants' defaults)
+    /// `variant_key`: e.g. "intent=destructive", "intent=primary,size=sm"
+    fn on_variant_class(&mut self, value: &str, line: u32, variant_key: &str) {
+        self.on_class_attribute(value, line, "");
+    }
+
+    /// Called for each entry of a top-level variant map object indexed by a
+    /// class attribute (`const variants = { primary: "..." }` and
+    /// `className={variants[variant]}`) instead of one `on_class_attribute`.
+    /// `variant_key`: e.g. "variant=primary"; only one entry renders at a time
+    fn on_variant_map_class(&mut self, value: &str, line: u32, variant_key: &str, raw_tag: &str) {
+        self.on_conditional_class(value, line, raw_tag);
+    }
+
+    /// Called for each branch of a ternary class expression
+    /// (`className={cond ? "a" : "b"}`) instead of one `on_class_attribute`.
+    /// `value`: the branch's classes; only one branch renders at a time
+    fn on_conditional_class(&mut self, value: &str, line: u32, raw_tag: &str) {
+        self.on_class_attribute(value, line, raw_tag);
+    }
+
+    /// Called instead of `on_class_attribute` when the className value wraps
+    /// across lines (Prettier-formatted class lists).
+    /// `line`: 1-based line of the attribute; `end_line`: line where its value ends
+    fn on_multiline_class(&mut self, value: &str, line: u32, end_line: u32, raw_tag: &str) {
+        self.on_class_attribute(value, line, raw_tag);
+    }
+
+    /// Called for a class attribute accessing an imported CSS module
+    /// (`className={styles.card}`) instead of `on_dynamic_class`.
+    /// `module`: the import specifier, e.g. "./Card.module.css"
+    /// `class_name`: the accessed class, e.g. "card"
+    fn on_css_module_class(&mut self, module: &str, class_name: &str, line: u32, raw_tag: &str) {
+        self.on_dynamic_class(line, raw_tag);
+    }
+
+    /// Called after the attributes of a tag whose classes can't be statically
+    /// known: a `{...props}` spread, or a className expression without static
+    /// classes (`className={props.className}`).
+    /// `line`: 1-based line of the tag
+    fn on_dynamic_class(&mut self, line: u32, raw_tag: &str) {}
+
+    /// Called for JSX text that directly follows an opening or closing tag
+    /// (up to the next `<` or `{`). Text after the outermost closing tag may
+    /// be surrounding code, so visitors should only use it inside elements.
+    /// `text`: trimmed text, never empty
+    /// `line`: 1-based line of the first non-whitespace character
+    fn on_text(&mut self, text: &str, line: u32) {}
+
+    /// Called when the tokenizer hits malformed source it can't scan past
+    /// normally (a tag or expression left open until the end of the file).
+    fn on_diagnostic(&mut self, diagnostic: &ParseDiagnostic) {}
+
     /// Called when the scan of a file is complete.
     fn on_file_end(&mut self) {}
 }
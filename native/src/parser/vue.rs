@@ -0,0 +1,155 @@
+//! Vue single-file component support.
+//!
+//! A `.vue` file is rewritten into JSX-shaped source the tokenizer already
+//! understands, keeping every line in place so region lines match the SFC:
+//! - everything outside the top-level `<template>` is blanked
+//! - `class="..."` and `:class` / `v-bind:class` bindings of a tag are merged
+//!   into one static `className="..."` (object keys, array items and string
+//!   literals; conditional classes count as present, like `cn()` arguments)
+//! - identifiers in bindings resolve to `<script setup>` string constants
+//! - `<!-- ... -->` comments become `{/* ... */}` so annotations still apply
+
+use std::collections::HashMap;
+
+use super::markup::{blank, collect_constants, find_block_tag, rewrite_template, ClassAttr};
+use super::tokenizer::find_tag_close;
+
+/// Whether `path` is a Vue single-file component.
+pub fn is_vue_path(path: &str) -> bool {
+    path.ends_with(".vue")
+}
+
+/// Rewrite a Vue SFC into JSX-shaped source with the same line numbering.
+pub fn to_jsx(source: &str) -> String {
+    let constants = script_setup_constants(source);
+    let Some((start, end)) = template_range(source) else {
+        return blank(source);
+    };
+    let mut out = blank(&source[..start]);
+    out.push_str(&rewrite_template(&source[start..end], &constants, vue_class_attr));
+    out.push_str(&blank(&source[end..]));
+    out
+}
+
+/// `class` is a class list; `:class` / `v-bind:class` are expressions.
+fn vue_class_attr(name: &str) -> Option<ClassAttr<'_>> {
+    match name {
+        "class" => Some(ClassAttr::List),
+        ":class" | "v-bind:class" => Some(ClassAttr::Binding),
+        _ => None,
+    }
+}
+
+/// Content range of the top-level `<template>` block (nested `<template>`
+/// slots and conditionals included).
+fn template_range(source: &str) -> Option<(usize, usize)> {
+    let open = find_block_tag(source, "<template", 0)?;
+    let content_start = find_tag_close(source, open + "<template".len());
+    let mut depth = 1usize;
+    let mut j = content_start;
+    loop {
+        let next_open = find_block_tag(source, "<template", j);
+        let next_close = source[j..].find("</template").map(|p| j + p)?;
+        match next_open {
+            Some(o) if o < next_close => {
+                let close = find_tag_close(source, o + "<template".len());
+                if !source[..close].ends_with("/>") {
+                    depth += 1;
+                }
+                j = close;
+            }
+            _ => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some((content_start, next_close));
+                }
+                j = next_close + "</template".len();
+            }
+        }
+    }
+}
+
+/// String constants declared in `<script setup>` blocks (`const a = 'x'`).
+fn script_setup_constants(source: &str) -> HashMap<String, String> {
+    let mut constants = HashMap::new();
+    let mut j = 0;
+    while let Some(open) = find_block_tag(source, "<script", j) {
+        let body_start = find_tag_close(source, open + "<script".len());
+        let body_end = source[body_start..].find("</script").map_or(source.len(), |p| body_start + p);
+        let is_setup = source[open..body_start]
+            .split(|c: char| c.is_whitespace() || c == '>')
+            .any(|attr| attr == "setup");
+        if is_setup {
+            collect_constants(&source[body_start..body_end], &mut constants);
+        }
+        j = body_end;
+    }
+    constants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SFC: &str = r#"<script setup lang="ts">
+const danger = 'bg-red-600 text-white'
+defineProps<{ ok: boolean }>()
+</script>
+
+<template>
+  <div class="bg-card p-4">
+    <!-- @a11y-context bg:#000000 -->
+    <p :class="{ 'text-muted-foreground': !ok, 'font-bold': ok }">Hi</p>
+    <button class="rounded" :class="[danger, ok ? 'ring-2' : '']">Go</button>
+    <template v-if="ok"><span class="text-green-700">Yes</span></template>
+  </div>
+</template>
+
+<style scoped>
+.x { color: red; }
+</style>
+"#;
+
+    #[test]
+    fn non_ascii_template_text() {
+        let jsx = to_jsx("<template>\n  <p class=\"text-gray-500\">Caffè <!-- è --></p>\n</template>");
+        assert!(jsx.contains(r#"<p className="text-gray-500">Caffè {/* è */}</p>"#), "{}", jsx);
+        to_jsx("<template>è</p><!-- è");
+    }
+
+    #[test]
+    fn vue_paths() {
+        assert!(is_vue_path("src/components/Button.vue"));
+        assert!(!is_vue_path("src/Button.tsx"));
+    }
+
+    #[test]
+    fn rewrite_keeps_line_numbers() {
+        let jsx = to_jsx(SFC);
+        assert_eq!(jsx.lines().count(), SFC.lines().count());
+        let lines: Vec<&str> = jsx.lines().collect();
+        assert!(lines[0].is_empty() && lines[2].is_empty());
+        assert!(lines[6].contains(r#"className="bg-card p-4""#));
+        assert!(lines[7].contains("{/* @a11y-context bg:#000000 */}"));
+        assert!(lines[8].contains(r#"className="text-muted-foreground font-bold""#));
+        assert!(lines[9].contains(r#"className="rounded bg-red-600 text-white ring-2""#));
+        assert!(!lines[9].contains(":class"));
+        assert!(lines[10].contains(r#"className="text-green-700""#));
+        assert!(!jsx.contains("color: red"));
+    }
+
+    #[test]
+    fn script_setup_constants_only() {
+        let source = "<script>\nconst a = 'x'\n</script>\n<script setup>\nconst b = \"y z\"\nconst c = `w ${d}`\nconst n = 1\n</script>";
+        let constants = script_setup_constants(source);
+        assert_eq!(constants.get("b").map(String::as_str), Some("y z"));
+        assert_eq!(constants.get("c").map(|c| c.split_whitespace().collect::<Vec<_>>()), Some(vec!["w"]));
+        assert!(!constants.contains_key("a"));
+        assert!(!constants.contains_key("n"));
+    }
+
+    #[test]
+    fn no_template_blanks_everything() {
+        assert_eq!(to_jsx("<script setup>\nconst a = '<div>'\n</script>"), "\n\n");
+    }
+}
@@ -0,0 +1,211 @@
+//! Report profiles that regroup results by regulatory clause numbering.
+//!
+//! Built on the WCAG criteria tagged by `criteria::map_criteria()`: each
+//! profile maps a WCAG success criterion to the clause that incorporates it.
+//! Criteria a profile does not incorporate land in `uncovered`.
+
+use crate::i18n::{self, Locale};
+use crate::types::{ContrastResult, ProfileReport, ProfileSection};
+
+/// Supported report profiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportProfile {
+    /// Plain WCAG 2.x numbering
+    Wcag,
+    /// EN 301 549 V3.2.1, clause 9 (Web)
+    En301549,
+    /// Revised Section 508 (2017), E205.4 → WCAG 2.0 Level A/AA
+    Section508,
+}
+
+impl ReportProfile {
+    /// Parse "wcag" | "en301549" | "section508" (case-insensitive, `-`/`_`/space ignored).
+    pub fn parse(value: &str) -> Option<Self> {
+        let normalized: String = value
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' '))
+            .collect::<String>()
+            .to_lowercase();
+        match normalized.as_str() {
+            "wcag" => Some(Self::Wcag),
+            "en301549" => Some(Self::En301549),
+            "section508" | "508" => Some(Self::Section508),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Wcag => "wcag",
+            Self::En301549 => "en301549",
+            Self::Section508 => "section508",
+        }
+    }
+}
+
+/// Clause id that incorporates `criterion` in `profile`, if any.
+pub fn clause_for(profile: ReportProfile, criterion: &str) -> Option<&'static str> {
+    match profile {
+        ReportProfile::Wcag => match criterion {
+            "1.4.3" => Some("1.4.3"),
+            "1.4.6" => Some("1.4.6"),
+            "1.4.11" => Some("1.4.11"),
+            "2.4.7" => Some("2.4.7"),
+            _ => None,
+        },
+        // EN 301 549 incorporates WCAG 2.1 Level A/AA only (no 1.4.6).
+        ReportProfile::En301549 => match criterion {
+            "1.4.3" => Some("9.1.4.3"),
+            "1.4.11" => Some("9.1.4.11"),
+            "2.4.7" => Some("9.2.4.7"),
+            _ => None,
+        },
+        // Section 508 incorporates WCAG 2.0 Level A/AA only (no 1.4.6, no 2.1's 1.4.11).
+        ReportProfile::Section508 => match criterion {
+            "1.4.3" => Some("E205.4 / 1.4.3"),
+            "2.4.7" => Some("E205.4 / 2.4.7"),
+            _ => None,
+        },
+    }
+}
+
+/// Group results into the profile's clauses.
+///
+/// A result tagged with several criteria (e.g. a focus ring: 1.4.11 + 2.4.7)
+/// appears under every clause it maps to. Sections are sorted by clause number
+/// (numerically, so 9.1.4.3 precedes 9.1.4.11); results without any mapped
+/// clause go to `uncovered`. Section titles are the incorporated criterion's
+/// title in `locale`.
+pub fn group_by_profile(
+    results: &[ContrastResult],
+    profile: ReportProfile,
+    locale: Locale,
+) -> ProfileReport {
+    let mut sections: Vec<ProfileSection> = Vec::new();
+    let mut uncovered = Vec::new();
+
+    for result in results {
+        let mut mapped = false;
+        for criterion in result.wcag_criteria.iter().flatten() {
+            let Some(clause) = clause_for(profile, criterion) else {
+                continue;
+            };
+            mapped = true;
+            match sections.iter_mut().find(|s| s.clause == clause) {
+                Some(section) => section.results.push(result.clone()),
+                None => sections.push(ProfileSection {
+                    clause: clause.to_string(),
+                    title: i18n::criterion_title(locale, criterion)
+                        .unwrap_or_default()
+                        .to_string(),
+                    wcag_criterion: criterion.clone(),
+                    results: vec![result.clone()],
+                }),
+            }
+        }
+        if !mapped {
+            uncovered.push(result.clone());
+        }
+    }
+
+    sections.sort_by_key(|s| clause_sort_key(&s.clause));
+
+    ProfileReport {
+        profile: profile.as_str().to_string(),
+        sections,
+        uncovered,
+    }
+}
+
+/// Split a clause id into comparable segments: numeric runs compare as numbers.
+fn clause_sort_key(clause: &str) -> Vec<(u32, String)> {
+    clause
+        .split(['.', ' ', '/'])
+        .filter(|s| !s.is_empty())
+        .map(|s| match s.parse::<u32>() {
+            Ok(n) => (n, String::new()),
+            Err(_) => (u32::MAX, s.to_string()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::checker::check_all_pairs;
+    use crate::types::ColorPair;
+
+    fn make_pair(pair_type: &str, state: Option<&str>) -> ColorPair {
+        ColorPair {
+            is_large_text: Some(false),
+            pair_type: Some(pair_type.to_string()),
+            interactive_state: state.map(|s| s.to_string()),
+            ..ColorPair::fixture("text-gray-300", "#dddddd")
+        }
+    }
+
+    fn violations(threshold: &str) -> Vec<ContrastResult> {
+        let pairs = vec![
+            make_pair("text", None),
+            make_pair("border", None),
+            make_pair("ring", Some("focus-visible")),
+        ];
+        check_all_pairs(&pairs, threshold, "#ffffff").violations
+    }
+
+    #[test]
+    fn parse_profile_names() {
+        assert_eq!(ReportProfile::parse("EN 301 549"), Some(ReportProfile::En301549));
+        assert_eq!(ReportProfile::parse("section-508"), Some(ReportProfile::Section508));
+        assert_eq!(ReportProfile::parse("wcag"), Some(ReportProfile::Wcag));
+        assert_eq!(ReportProfile::parse("iso"), None);
+    }
+
+    #[test]
+    fn en301549_clause_numbering() {
+        let report = group_by_profile(&violations("AA"), ReportProfile::En301549, Locale::En);
+        let clauses: Vec<&str> = report.sections.iter().map(|s| s.clause.as_str()).collect();
+        assert_eq!(clauses, vec!["9.1.4.3", "9.1.4.11", "9.2.4.7"]);
+        assert!(report.uncovered.is_empty());
+    }
+
+    #[test]
+    fn focus_ring_listed_under_both_clauses() {
+        let report = group_by_profile(&violations("AA"), ReportProfile::En301549, Locale::En);
+        let non_text = report.sections.iter().find(|s| s.clause == "9.1.4.11").unwrap();
+        assert_eq!(non_text.results.len(), 2); // border + focus ring
+        let focus = report.sections.iter().find(|s| s.clause == "9.2.4.7").unwrap();
+        assert_eq!(focus.results.len(), 1);
+    }
+
+    #[test]
+    fn section508_leaves_wcag21_criteria_uncovered() {
+        let report = group_by_profile(&violations("AA"), ReportProfile::Section508, Locale::En);
+        let clauses: Vec<&str> = report.sections.iter().map(|s| s.clause.as_str()).collect();
+        assert_eq!(clauses, vec!["E205.4 / 1.4.3", "E205.4 / 2.4.7"]);
+        // border only maps to 1.4.11 (WCAG 2.1) → not in Section 508
+        assert_eq!(report.uncovered.len(), 1);
+        assert_eq!(report.uncovered[0].pair_type.as_deref(), Some("border"));
+    }
+
+    #[test]
+    fn aaa_text_uncovered_by_en301549() {
+        let report = group_by_profile(&violations("AAA"), ReportProfile::En301549, Locale::En);
+        assert!(report.sections.iter().all(|s| s.wcag_criterion != "1.4.6"));
+        assert_eq!(report.uncovered.len(), 1);
+    }
+
+    #[test]
+    fn wcag_profile_uses_plain_numbering() {
+        let report = group_by_profile(&violations("AA"), ReportProfile::Wcag, Locale::En);
+        assert_eq!(report.profile, "wcag");
+        assert!(report.sections.iter().any(|s| s.clause == "1.4.3" && s.title == "Contrast (Minimum)"));
+    }
+
+    #[test]
+    fn section_titles_follow_locale() {
+        let report = group_by_profile(&violations("AA"), ReportProfile::En301549, Locale::It);
+        let focus = report.sections.iter().find(|s| s.clause == "9.2.4.7").unwrap();
+        assert_eq!(focus.title, "Focus visibile");
+    }
+}
@@ -0,0 +1,348 @@
+//! Per-theme spatial index of class regions for editor queries.
+//!
+//! Each indexed file keeps its regions with the column span their classes
+//! occupy, sorted by position, so hover / code-action lookups and diff
+//! mapping are binary searches instead of scans over result arrays. Files
+//! are indexed under the theme handle they were audited with and re-scanned
+//! only when their content changes; releasing the theme drops its files.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::diff::{path_matches, ChangedLines};
+use crate::parser::scan_source;
+use crate::snippets::snippet_config;
+use crate::theme::{self, Theme};
+use crate::types::{ClassRegion, IndexedRegion, Snippet};
+
+/// Position of one region: its start line and, when its classes appear
+/// verbatim on that line, their 1-based inclusive column range.
+#[derive(Debug, Clone, Copy)]
+struct RegionSpan {
+    line: u32,
+    columns: Option<(u32, u32)>,
+    /// Index in `FileIndex::regions`
+    region: usize,
+}
+
+/// The regions of one file, with spans sorted by (line, column).
+#[derive(Debug, Clone, Default)]
+pub struct FileIndex {
+    content_hash: u64,
+    regions: Vec<ClassRegion>,
+    spans: Vec<RegionSpan>,
+    /// Source the regions were scanned from, kept for re-scans on config reload
+    snippet: Option<Snippet>,
+    /// `Theme::generation` of the theme it was scanned with
+    generation: u64,
+}
+
+/// 1-based column range covering the region's classes on `line_text`: from
+/// its first class to the end of its last one. None if either isn't there.
+pub fn class_span(line_text: &str, region: &ClassRegion) -> Option<(u32, u32)> {
+    let mut classes = region.content.split_whitespace();
+    let first = classes.next()?;
+    let last = classes.next_back().unwrap_or(first);
+    let start = line_text.find(first)?;
+    let end = line_text[start..].rfind(last)? + start + last.len();
+    Some((start as u32 + 1, end as u32))
+}
+
+fn content_hash(source: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl FileIndex {
+    /// Index `regions` scanned from `source`.
+    pub fn build(source: &str, regions: Vec<ClassRegion>) -> Self {
+        let lines: Vec<&str> = source.lines().collect();
+        let mut spans: Vec<RegionSpan> = regions
+            .iter()
+            .enumerate()
+            .map(|(i, region)| RegionSpan {
+                line: region.start_line,
+                columns: lines
+                    .get((region.start_line as usize).wrapping_sub(1))
+                    .and_then(|text| class_span(text, region)),
+                region: i,
+            })
+            .collect();
+        spans.sort_by_key(|s| (s.line, s.columns.map_or(0, |(start, _)| start)));
+        Self { content_hash: content_hash(source), regions, spans, snippet: None, generation: 0 }
+    }
+
+    /// Scan `snippet` with the theme's config and index its regions.
+    pub fn scan(theme: &Theme, snippet: &Snippet) -> Self {
+        let config = snippet_config(&theme.scan_config, snippet);
+        let (regions, ..) = scan_source(&snippet.path, &snippet.content, &config);
+        Self { snippet: Some(snippet.clone()), generation: theme.generation, ..Self::build(&snippet.content, regions) }
+    }
+
+    /// The same source scanned with `theme` (None when built from bare regions).
+    pub fn rescan(&self, theme: &Theme) -> Option<Self> {
+        self.snippet.as_ref().map(|snippet| Self::scan(theme, snippet))
+    }
+
+    pub fn regions(&self) -> &[ClassRegion] {
+        &self.regions
+    }
+
+    /// Spans starting on lines `start..=end`.
+    fn spans_in_lines(&self, start: u32, end: u32) -> &[RegionSpan] {
+        let from = self.spans.partition_point(|s| s.line < start);
+        let to = self.spans.partition_point(|s| s.line <= end);
+        &self.spans[from..to.max(from)]
+    }
+
+    /// Region id at (`line`, `column`), both 1-based, with its column range:
+    /// the region whose classes contain the column, else the only region on
+    /// the line.
+    pub fn at(&self, line: u32, column: u32) -> Option<(usize, Option<(u32, u32)>)> {
+        let on_line = self.spans_in_lines(line, line);
+        on_line
+            .iter()
+            .find(|s| s.columns.is_some_and(|(start, end)| (start..=end).contains(&column)))
+            .or_else(|| (on_line.len() == 1).then(|| &on_line[0]))
+            .map(|s| (s.region, s.columns))
+    }
+
+    /// Ids of the regions starting on lines `start..=end`, in position order.
+    pub fn in_lines(&self, start: u32, end: u32) -> impl Iterator<Item = usize> + '_ {
+        self.spans_in_lines(start, end).iter().map(|s| s.region)
+    }
+}
+
+type Registry = HashMap<u32, HashMap<String, Arc<FileIndex>>>;
+
+/// Indexed files of one theme as (path, index), sorted by path.
+pub type IndexedFiles = Vec<(String, Arc<FileIndex>)>;
+
+fn registry() -> &'static Mutex<Registry> {
+    static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Index of `snippet` under theme `handle`, re-scanned only if its content changed.
+pub fn file_index(handle: u32, theme: &Theme, snippet: &Snippet) -> Arc<FileIndex> {
+    let hash = content_hash(&snippet.content);
+    let cached = registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&handle)
+        .and_then(|files| files.get(&snippet.path))
+        .filter(|index| index.content_hash == hash)
+        .cloned();
+    if let Some(index) = cached {
+        return index;
+    }
+    let index = Arc::new(FileIndex::scan(theme, snippet));
+    store(handle, &snippet.path, index.clone());
+    index
+}
+
+/// Index `path` under theme `handle`, replacing any previous index, unless
+/// it was scanned with a theme a reload has since replaced.
+fn store(handle: u32, path: &str, index: Arc<FileIndex>) {
+    let mut registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+    if theme::get(handle).is_some_and(|theme| theme.generation == index.generation) {
+        registry.entry(handle).or_default().insert(path.to_string(), index);
+    } else {
+        log::debug!("{}: not indexing regions scanned under a replaced theme", path);
+    }
+}
+
+fn files_of(registry: &Registry, handle: u32) -> IndexedFiles {
+    let mut files: IndexedFiles = registry
+        .get(&handle)
+        .map(|files| files.iter().map(|(path, index)| (path.clone(), index.clone())).collect())
+        .unwrap_or_default();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    files
+}
+
+/// Every file indexed under theme `handle`, by path.
+pub fn indexed_files(handle: u32) -> IndexedFiles {
+    files_of(&registry().lock().unwrap_or_else(|e| e.into_inner()), handle)
+}
+
+/// The theme registered under `handle` with its indexed files, captured in
+/// one step (None = unknown handle). The indexes are immutable, so callers
+/// can check them while later updates replace the registry entries.
+pub fn capture(handle: u32) -> Option<(Arc<Theme>, IndexedFiles)> {
+    let registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+    let theme = theme::get(handle)?;
+    Some((theme, files_of(&registry, handle)))
+}
+
+/// A file re-indexed for a reload: (path, index `capture` returned, its rescan).
+pub type Rescanned = (String, Arc<FileIndex>, Arc<FileIndex>);
+
+/// Replace the theme registered under `handle` together with the files
+/// re-indexed for it, so `capture` never pairs the new theme with regions
+/// scanned under the old one. A rescan replaces only an index still the one
+/// captured (or of the same content): files re-indexed meanwhile are newer.
+/// Files left scanned under another generation are dropped, to be re-indexed
+/// on next use. Returns the previous theme (None = unknown handle).
+pub fn swap_theme(handle: u32, new: Theme, rescanned: Vec<Rescanned>) -> Option<Arc<Theme>> {
+    let mut registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+    let generation = new.generation;
+    let old = theme::replace(handle, new)?;
+    let files = registry.entry(handle).or_default();
+    for (path, captured, index) in rescanned {
+        let current = files.get(&path);
+        if current.is_some_and(|c| Arc::ptr_eq(c, &captured) || c.content_hash == index.content_hash) {
+            files.insert(path, index);
+        }
+    }
+    files.retain(|_, index| index.generation == generation);
+    Some(old)
+}
+
+/// Indexed file `path` under theme `handle`.
+pub fn indexed_file(handle: u32, path: &str) -> Option<Arc<FileIndex>> {
+    registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&handle)?
+        .get(path)
+        .cloned()
+}
+
+fn indexed_region(path: &str, index: &FileIndex, id: usize) -> IndexedRegion {
+    IndexedRegion {
+        path: path.to_string(),
+        id: id as u32,
+        region: index.regions[id].clone(),
+    }
+}
+
+/// Region at (`line`, `column`) of the indexed file `path`.
+pub fn region_at(handle: u32, path: &str, line: u32, column: u32) -> Option<IndexedRegion> {
+    let index = indexed_file(handle, path)?;
+    let (id, _) = index.at(line, column)?;
+    Some(indexed_region(path, &index, id))
+}
+
+/// Indexed regions starting on a changed line, by file path then position.
+pub fn regions_in_diff(handle: u32, changed: &ChangedLines) -> Vec<IndexedRegion> {
+    let mut regions = Vec::new();
+    for (path, index) in &indexed_files(handle) {
+        let mut ids: Vec<usize> = changed
+            .ranges()
+            .filter(|(file, _)| path_matches(path, file))
+            .flat_map(|(_, ranges)| ranges.iter().flat_map(|&(start, end)| index.in_lines(start, end)))
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        regions.extend(ids.into_iter().map(|id| indexed_region(path, index, id)));
+    }
+    regions.sort_by(|a, b| a.path.cmp(&b.path).then(a.region.start_line.cmp(&b.region.start_line)));
+    regions
+}
+
+/// Drop every file indexed under theme `handle`.
+pub fn release(handle: u32) {
+    registry().lock().unwrap_or_else(|e| e.into_inner()).remove(&handle);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DiffOptions, LineRange};
+
+    const SOURCE: &str = r#"<div className="bg-white">
+  <p className="text-gray-400">a</p> <b className="text-gray-900 font-bold">b</b>
+  <span className={cn(
+    "text-gray-500")}>c</span>
+</div>"#;
+
+    fn snippet(content: &str) -> Snippet {
+        Snippet { path: "src/Card.tsx".to_string(), content: content.to_string(), base_context: None, ancestors: None }
+    }
+
+    fn content_at(index: &FileIndex, line: u32, column: u32) -> Option<&str> {
+        index.at(line, column).map(|(id, _)| index.regions()[id].content.as_str())
+    }
+
+    #[test]
+    fn lookup_by_position() {
+        let index = FileIndex::scan(&Theme::default(), &snippet(SOURCE));
+        assert_eq!(content_at(&index, 1, 20), Some("bg-white"));
+        assert_eq!(content_at(&index, 2, 17), Some("text-gray-400"));
+        assert_eq!(content_at(&index, 2, 29), Some("text-gray-400"));
+        assert_eq!(content_at(&index, 2, 60), Some("text-gray-900 font-bold"));
+        assert_eq!(content_at(&index, 2, 30), None);
+        assert_eq!(content_at(&index, 3, 1), Some("text-gray-500"));
+        assert_eq!(content_at(&index, 5, 1), None);
+        let in_lines: Vec<&str> = index.in_lines(2, 3).map(|id| index.regions()[id].content.as_str()).collect();
+        assert_eq!(in_lines, vec!["text-gray-400", "text-gray-900 font-bold", "text-gray-500"]);
+    }
+
+    #[test]
+    fn files_rescanned_only_on_change_and_released_with_theme() {
+        let handle = crate::theme::register(Theme::default());
+        let theme = crate::theme::get(handle).unwrap();
+        let first = file_index(handle, &theme, &snippet(SOURCE));
+        assert!(Arc::ptr_eq(&first, &file_index(handle, &theme, &snippet(SOURCE))));
+        let edited = file_index(handle, &theme, &snippet("<p className=\"text-black\">x</p>"));
+        assert!(!Arc::ptr_eq(&first, &edited));
+        assert_eq!(region_at(handle, "src/Card.tsx", 1, 16).unwrap().region.content, "text-black");
+
+        release(handle);
+        assert!(indexed_file(handle, "src/Card.tsx").is_none());
+    }
+
+    #[test]
+    fn reload_keeps_files_reindexed_meanwhile_and_rejects_stale_scans() {
+        let handle = crate::theme::register(Theme::from_config(&Default::default()));
+        let old = crate::theme::get(handle).unwrap();
+        let other = Snippet { path: "src/Other.tsx".to_string(), ..snippet(SOURCE) };
+        file_index(handle, &old, &snippet(SOURCE));
+        file_index(handle, &old, &other);
+        let (_, files) = capture(handle).unwrap();
+
+        // Edited while the reload rescans what it captured
+        let edited = snippet("<p className=\"text-black\">x</p>");
+        file_index(handle, &old, &edited);
+        let new = Theme::from_config(&Default::default());
+        let rescanned = files
+            .into_iter()
+            .map(|(path, index)| {
+                let rescan = Arc::new(index.rescan(&new).unwrap());
+                (path, index, rescan)
+            })
+            .collect();
+        swap_theme(handle, new, rescanned).unwrap();
+        let new = crate::theme::get(handle).unwrap();
+        assert_eq!(indexed_file(handle, "src/Other.tsx").unwrap().generation, new.generation);
+        // The edit was scanned under the old theme: dropped, not overwritten
+        assert!(indexed_file(handle, "src/Card.tsx").is_none());
+
+        file_index(handle, &old, &edited);
+        assert!(indexed_file(handle, "src/Card.tsx").is_none());
+        file_index(handle, &new, &edited);
+        assert_eq!(region_at(handle, "src/Card.tsx", 1, 16).unwrap().region.content, "text-black");
+        release(handle);
+        crate::theme::release(handle);
+    }
+
+    #[test]
+    fn regions_touched_by_a_diff() {
+        let handle = crate::theme::register(Theme::default());
+        let theme = crate::theme::get(handle).unwrap();
+        file_index(handle, &theme, &snippet(SOURCE));
+        let diff = DiffOptions {
+            ranges: Some(vec![LineRange { path: "Card.tsx".to_string(), start_line: 3, end_line: 4 }]),
+            ..Default::default()
+        };
+        let regions = regions_in_diff(handle, &ChangedLines::from_options(&diff));
+        assert_eq!(regions.len(), 1);
+        assert_eq!((regions[0].path.as_str(), regions[0].id), ("src/Card.tsx", 3));
+        assert_eq!(regions[0].region.content, "text-gray-500");
+        release(handle);
+    }
+}
@@ -0,0 +1,174 @@
+//! Hot reload of a registered theme (tailwind.config / a11y config edits in
+//! watch mode) without restarting the daemon.
+//!
+//! The new theme replaces the old one atomically under the same handle.
+//! Indexed files are re-scanned only when the parser config changed;
+//! color, mode and threshold changes re-check the cached regions. Every
+//! indexed file is re-checked under both themes and only files whose
+//! violations differ are reported.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::math::checker::check_all_pairs;
+use crate::pairs::region_resolver::{resolve_regions, ResolvedPairs};
+use crate::region_index::{self, FileIndex};
+use crate::remediation::attach_remediation;
+use crate::theme::Theme;
+use crate::types::{ConfigDelta, ContrastResult, FileDelta, ThemeConfig};
+
+/// Whether regions scanned with `old` are stale under `new`.
+fn scan_changed(old: &Theme, new: &Theme) -> bool {
+    old.scan_config != new.scan_config
+}
+
+/// Whether results checked with `old` are stale under `new` (regions aside).
+fn check_changed(old: &Theme, new: &Theme) -> bool {
+    old.color_map != new.color_map
+        || old.theme_mode != new.theme_mode
+        || old.threshold != new.threshold
+        || old.page_bg != new.page_bg
+}
+
+fn violations(theme: &Theme, path: &str, index: &FileIndex) -> Vec<ContrastResult> {
+    let mut resolved = ResolvedPairs::default();
+    resolve_regions(path, index.regions(), &theme.color_map, &theme.theme_mode, &mut resolved);
+    let mut violations = check_all_pairs(&resolved.pairs, &theme.threshold, &theme.page_bg).violations;
+    attach_remediation(&mut violations, &theme.color_map, &theme.threshold, &theme.page_bg, theme.scan_config.locale);
+    violations
+}
+
+/// Identity of a violation across reloads: where it is and which classes
+/// pair up, not the colors they resolve to.
+fn identity(result: &ContrastResult) -> (u32, &str, &str, Option<&str>, Option<&str>) {
+    (
+        result.line,
+        result.bg_class.as_str(),
+        result.text_class.as_str(),
+        result.pair_type.as_deref(),
+        result.interactive_state.as_deref(),
+    )
+}
+
+/// Delta between a file's violations before and after; None when identical.
+fn file_delta(path: &str, before: Vec<ContrastResult>, after: Vec<ContrastResult>) -> Option<FileDelta> {
+    let ratios_before: HashMap<_, f64> = before.iter().map(|r| (identity(r), r.ratio)).collect();
+    let ratios_after: HashMap<_, f64> = after.iter().map(|r| (identity(r), r.ratio)).collect();
+    if ratios_before == ratios_after {
+        return None;
+    }
+    let introduced = after.iter().filter(|r| !ratios_before.contains_key(&identity(r))).cloned().collect();
+    let resolved = before.iter().filter(|r| !ratios_after.contains_key(&identity(r))).cloned().collect();
+    Some(FileDelta { path: path.to_string(), violations: after, introduced, resolved })
+}
+
+/// Replace the theme registered under `handle` with one built from
+/// `config`, refresh the files indexed under it and report what changed.
+/// None for unknown handles.
+pub fn update_config(handle: u32, config: &ThemeConfig) -> Option<ConfigDelta> {
+    let (old, files) = region_index::capture(handle)?;
+    let mut new = Theme::from_config(config);
+    let rescan = scan_changed(&old, &new);
+    if !rescan {
+        new.generation = old.generation;
+    }
+
+    let mut delta = ConfigDelta::default();
+    let mut rescanned = Vec::new();
+    if rescan || check_changed(&old, &new) {
+        for (path, index) in files {
+            let before = violations(&old, &path, &index);
+            let index = match rescan.then(|| index.rescan(&new)).flatten() {
+                Some(rescan) => {
+                    let rescan = Arc::new(rescan);
+                    rescanned.push((path.clone(), index, rescan.clone()));
+                    rescan
+                }
+                None => index,
+            };
+            let after = violations(&new, &path, &index);
+            delta.files.extend(file_delta(&path, before, after));
+        }
+    }
+    delta.rescanned = rescanned.len() as u32;
+    region_index::swap_theme(handle, new, rescanned)?;
+    Some(delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme;
+    use crate::types::{ColorMapEntry, ContainerEntry, Snippet};
+
+    const SOURCE: &str = r#"<Card>
+  <p className="text-gray-900">a</p>
+</Card>
+<p className="text-gray-400">b</p>"#;
+
+    fn config(gray_400: &str, card_bg: &str) -> ThemeConfig {
+        let colors = [
+            ("--color-background", "#ffffff"),
+            ("--color-card", "#ffffff"),
+            ("--color-dark", "#111827"),
+            ("--color-gray-400", gray_400),
+            ("--color-gray-900", "#111827"),
+        ];
+        ThemeConfig {
+            color_map: colors
+                .iter()
+                .map(|(name, hex)| ColorMapEntry { name: name.to_string(), hex: hex.to_string(), alpha: None })
+                .collect(),
+            container_config: vec![ContainerEntry { component: "Card".to_string(), bg_class: card_bg.to_string() }],
+            default_bg: "bg-background".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn indexed(theme_config: &ThemeConfig) -> u32 {
+        let handle = theme::register(Theme::from_config(theme_config));
+        let theme = theme::get(handle).unwrap();
+        let snippet =
+            Snippet { path: "src/Card.tsx".to_string(), content: SOURCE.to_string(), base_context: None, ancestors: None };
+        region_index::file_index(handle, &theme, &snippet);
+        handle
+    }
+
+    #[test]
+    fn color_changes_recheck_cached_regions() {
+        let handle = indexed(&config("#9ca3af", "bg-card"));
+        let delta = update_config(handle, &config("#4b5563", "bg-card")).unwrap();
+        assert_eq!(delta.rescanned, 0);
+        assert_eq!(delta.files.len(), 1);
+        let file = &delta.files[0];
+        assert!(file.violations.is_empty() && file.introduced.is_empty());
+        assert_eq!(file.resolved[0].text_class, "text-gray-400");
+        assert_eq!(theme::get(handle).unwrap().color_map["--color-gray-400"].hex, "#4b5563");
+
+        // Same config again: nothing to report
+        assert!(update_config(handle, &config("#4b5563", "bg-card")).unwrap().files.is_empty());
+        region_index::release(handle);
+        theme::release(handle);
+    }
+
+    #[test]
+    fn container_changes_rescan_indexed_files() {
+        let handle = indexed(&config("#9ca3af", "bg-card"));
+        let delta = update_config(handle, &config("#9ca3af", "bg-dark")).unwrap();
+        assert_eq!(delta.rescanned, 1);
+        let file = &delta.files[0];
+        assert_eq!(file.violations.len(), 2);
+        assert_eq!(file.introduced.len(), 1);
+        assert_eq!(file.introduced[0].bg_class, "(implicit) bg-dark");
+        assert!(file.resolved.is_empty());
+        let regions = region_index::indexed_file(handle, "src/Card.tsx").unwrap();
+        assert_eq!(regions.regions()[0].context_bg, "bg-dark");
+        region_index::release(handle);
+        theme::release(handle);
+    }
+
+    #[test]
+    fn unknown_handle() {
+        assert!(update_config(u32::MAX, &config("#9ca3af", "bg-card")).is_none());
+    }
+}
@@ -0,0 +1,242 @@
+//! Templated fix advice for violations, shared by every reporter.
+//!
+//! The foreground color is replaced first by the nearest shade of its own
+//! palette family that passes (`text-gray-400` → `text-gray-600`), then, when
+//! the family has none or the class isn't a palette shade, by the smallest
+//! mix toward black or white as an arbitrary value (`text-[#5f6368]`). Body
+//! text that would pass as large text also gets the WCAG large-text sizes.
+//! Near-invisible text (`LOW-ALPHA-TEXT`) is told to drop its alpha instead.
+
+use crate::criteria::LOW_ALPHA_TEXT;
+use crate::i18n::{format_message, message, Locale, MessageKey};
+use crate::math::checker::{check_contrast, is_violation, pair_of};
+use crate::math::composite::composite_over;
+use crate::math::text_alpha::effective_alpha;
+use crate::pairs::class_resolver::{css_variable_of, ColorMap};
+use crate::types::ContrastResult;
+
+/// Mix steps tried toward black / white (1% each), as in `fix_plan`.
+const MIX_STEPS: u32 = 100;
+
+/// Threshold a result was checked at: strict-profile results use AAA.
+fn threshold_of<'a>(result: &ContrastResult, threshold: &'a str) -> &'a str {
+    if result.strict_profile == Some(true) {
+        "AAA"
+    } else {
+        threshold
+    }
+}
+
+/// `result` re-checked with its foreground replaced by `hex`, if that passes.
+fn passing_with(result: &ContrastResult, hex: &str, threshold: &str, page_bg: &str) -> Option<ContrastResult> {
+    let mut pair = pair_of(result);
+    pair.text_hex = Some(hex.to_string());
+    let after = check_contrast(&pair, page_bg);
+    (!is_violation(&after, threshold)).then_some(after)
+}
+
+/// Nearest passing shade of the text class's palette family: (class, ratio).
+fn family_shade(result: &ContrastResult, palette: &ColorMap, threshold: &str, page_bg: &str) -> Option<(String, f64)> {
+    let color = css_variable_of(&result.text_class)?;
+    let (family, shade) = color.rsplit_once('-')?;
+    let shade: u32 = shade.parse().ok()?;
+    let color_name = &color["--color-".len()..];
+    let family_name = &family["--color-".len()..];
+
+    let mut shades: Vec<(u32, &str)> = palette
+        .iter()
+        .filter_map(|(name, resolved)| {
+            let (candidate_family, candidate) = name.rsplit_once('-')?;
+            let candidate: u32 = candidate.parse().ok()?;
+            (candidate_family == family && candidate != shade).then_some((candidate, resolved.hex.as_str()))
+        })
+        .collect();
+    shades.sort_by_key(|(candidate, _)| (candidate.abs_diff(shade), *candidate));
+
+    shades.into_iter().find_map(|(candidate, hex)| {
+        let after = passing_with(result, hex, threshold, page_bg)?;
+        let class = replace_last(&result.text_class, color_name, &format!("{}-{}", family_name, candidate));
+        Some((class, after.ratio))
+    })
+}
+
+/// Smallest mix of the foreground toward black or white that passes: (class, ratio).
+fn mixed_value(result: &ContrastResult, threshold: &str, page_bg: &str) -> Option<(String, f64)> {
+    let current = result.text_hex.as_deref()?;
+    let (hex, after) = (1..=MIX_STEPS).find_map(|step| {
+        let amount = step as f64 / MIX_STEPS as f64;
+        ["#000000", "#ffffff"].iter().find_map(|target| {
+            let candidate = composite_over(target, current, amount);
+            passing_with(result, &candidate, threshold, page_bg).map(|after| (candidate, after))
+        })
+    })?;
+    Some((arbitrary_class(&result.text_class, &hex), after.ratio))
+}
+
+/// `text_class` with its color swapped for the arbitrary value `[hex]`
+/// (just the hex when the class has no recognizable color).
+fn arbitrary_class(text_class: &str, hex: &str) -> String {
+    let arbitrary = format!("[{}]", hex);
+    if let Some(color) = css_variable_of(text_class) {
+        return replace_last(text_class, &color["--color-".len()..], &arbitrary);
+    }
+    match (text_class.rfind('['), text_class.rfind(']')) {
+        (Some(open), Some(close)) if open < close => {
+            format!("{}{}{}", &text_class[..open], arbitrary, &text_class[close + 1..])
+        }
+        _ => hex.to_string(),
+    }
+}
+
+/// `haystack` with the last occurrence of `from` replaced by `to`.
+fn replace_last(haystack: &str, from: &str, to: &str) -> String {
+    match haystack.rfind(from) {
+        Some(at) => format!("{}{}{}", &haystack[..at], to, &haystack[at + from.len()..]),
+        None => to.to_string(),
+    }
+}
+
+/// Whether the result is body text that would pass as large text.
+fn passes_as_large_text(result: &ContrastResult, threshold: &str) -> bool {
+    let is_text = result.pair_type.as_deref().is_none_or(|t| t == "text");
+    let passes_large = if threshold == "AAA" { result.pass_aaa_large } else { result.pass_aa_large };
+    is_text && result.is_large_text != Some(true) && passes_large
+}
+
+/// Advice for near-invisible text: the class without its alpha modifier
+/// (`text-white/20` → `text-white`), else more opacity on the element.
+fn opaque_text(result: &ContrastResult, page_bg: &str, locale: Locale) -> String {
+    let opaque_class = result.text_class.rsplit_once('/').filter(|_| result.text_alpha.is_some_and(|a| a < 0.999));
+    match opaque_class {
+        Some((opaque_class, _)) => {
+            let mut pair = pair_of(result);
+            pair.text_alpha = None;
+            let ratio = check_contrast(&pair, page_bg).ratio;
+            format_message(
+                locale,
+                MessageKey::RemediationReplace,
+                &[&result.text_class, opaque_class, &format!("{:.2}", ratio)],
+            )
+        }
+        None => format_message(
+            locale,
+            MessageKey::RemediationRaiseOpacity,
+            &[&format!("{:.0}", effective_alpha(result) * 100.0)],
+        ),
+    }
+}
+
+/// Fix advice for one violation, e.g. "replace `text-gray-400` with
+/// `text-gray-600` (ratio 7.56) or increase size to ≥18.66px bold or ≥24px".
+/// None when neither a color nor a size change makes it pass.
+pub fn remediation(
+    result: &ContrastResult,
+    palette: &ColorMap,
+    threshold: &str,
+    page_bg: &str,
+    locale: Locale,
+) -> Option<String> {
+    if result.rule_id.as_deref() == Some(LOW_ALPHA_TEXT) {
+        return Some(opaque_text(result, page_bg, locale));
+    }
+    let threshold = threshold_of(result, threshold);
+    let replace = family_shade(result, palette, threshold, page_bg)
+        .or_else(|| mixed_value(result, threshold, page_bg))
+        .map(|(class, ratio)| {
+            format_message(
+                locale,
+                MessageKey::RemediationReplace,
+                &[&result.text_class, &class, &format!("{:.2}", ratio)],
+            )
+        });
+    let resize = passes_as_large_text(result, threshold)
+        .then(|| message(locale, MessageKey::RemediationLargeText).to_string());
+    match (replace, resize) {
+        (Some(replace), Some(resize)) => {
+            Some(format_message(locale, MessageKey::RemediationEither, &[&replace, &resize]))
+        }
+        (replace, resize) => replace.or(resize),
+    }
+}
+
+/// Set `remediation` on every violation.
+pub fn attach_remediation(
+    violations: &mut [ContrastResult],
+    palette: &ColorMap,
+    threshold: &str,
+    page_bg: &str,
+    locale: Locale,
+) {
+    for violation in violations {
+        violation.remediation = remediation(violation, palette, threshold, page_bg, locale);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::checker::check_all_pairs_with_options;
+    use crate::types::{CheckOptions, ColorMapEntry, ColorPair};
+
+    const GRAYS: [(&str, &str); 3] = [
+        ("--color-gray-400", "#9ca3af"),
+        ("--color-gray-500", "#6b7280"),
+        ("--color-gray-600", "#4b5563"),
+    ];
+
+    fn pair(text_class: &str, text_hex: &str) -> ColorPair {
+        ColorPair { pair_type: Some("text".to_string()), ..ColorPair::fixture(text_class, text_hex) }
+    }
+
+    fn check(pairs: &[ColorPair], color_map: &[(&str, &str)], locale: Option<&str>) -> Vec<ContrastResult> {
+        let options = CheckOptions {
+            color_map: Some(
+                color_map
+                    .iter()
+                    .map(|(name, hex)| ColorMapEntry { name: name.to_string(), hex: hex.to_string(), alpha: None })
+                    .collect(),
+            ),
+            locale: locale.map(str::to_string),
+            ..Default::default()
+        };
+        check_all_pairs_with_options(pairs, &options).violations
+    }
+
+    #[test]
+    fn nearest_passing_shade_of_the_family() {
+        let violations = check(&[pair("hover:text-gray-400", "#9ca3af")], &GRAYS, None);
+        assert_eq!(
+            violations[0].remediation.as_deref(),
+            Some("replace `hover:text-gray-400` with `hover:text-gray-500` (ratio 4.83)")
+        );
+    }
+
+    #[test]
+    fn large_text_alternative_when_it_would_pass() {
+        let violations = check(&[pair("text-gray-450", "#888888")], &GRAYS, None);
+        let advice = violations[0].remediation.as_deref().unwrap();
+        assert!(advice.starts_with("replace `text-gray-450` with `text-gray-500`"), "{}", advice);
+        assert!(advice.ends_with(" or increase size to ≥18.66px bold or ≥24px"), "{}", advice);
+    }
+
+    #[test]
+    fn arbitrary_value_without_palette_shade() {
+        let violations = check(&[pair("text-[#aaaaaa]", "#aaaaaa"), pair("text-muted", "#aaaaaa")], &[], None);
+        for v in &violations {
+            let advice = v.remediation.as_deref().unwrap();
+            let replacement = advice.split('`').nth(3).unwrap();
+            let hex = &replacement[replacement.find('[').unwrap() + 1..replacement.len() - 1];
+            assert!(crate::math::wcag::contrast_ratio(hex, "#ffffff") >= 4.5, "{}", advice);
+        }
+        assert!(violations[1].remediation.as_deref().unwrap().contains("with `text-[#"));
+    }
+
+    #[test]
+    fn localized_and_violations_only() {
+        let pairs = [pair("text-gray-400", "#9ca3af"), pair("text-gray-600", "#4b5563")];
+        let options = CheckOptions { locale: Some("it".to_string()), ..Default::default() };
+        let result = check_all_pairs_with_options(&pairs, &options);
+        assert!(result.passed[0].remediation.is_none());
+        assert!(result.violations[0].remediation.as_deref().unwrap().starts_with("sostituire `text-gray-400` con `text-[#"));
+    }
+}
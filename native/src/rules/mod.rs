@@ -0,0 +1,403 @@
+//! Non-contrast rules evaluated on the JSX element tree.
+//!
+//! `RuleVisitor` runs next to the contrast orchestrator and rebuilds a light
+//! element tree from tokenizer events (tag, raw attributes, classes, text).
+//! Rules inspect an element when it closes. A rule that needs to know about
+//! the element's siblings files a `Deferred` finding on the parent, which is
+//! dropped or kept once the parent closes and all of its children are known.
+//! An element whose `a11y-ignore[<rule>]` (or `a11y-ignore <rule>`, or
+//! enclosing `a11y-ignore-start`) names a rule files no finding of it.
+
+pub mod required_marker;
+pub mod static_interaction;
+pub mod use_of_color;
+
+use crate::i18n::Locale;
+use crate::parser::annotation_parser::AnnotationParser;
+use crate::parser::visitor::JsxVisitor;
+use crate::types::RuleFinding;
+
+/// Color alone conveys information (WCAG 1.4.1 Use of Color).
+pub const USE_OF_COLOR: &str = "use-of-color";
+
+/// Required-field marker with no `required` / `aria-required` on its field
+/// (WCAG 1.3.1 Info and Relationships).
+pub const REQUIRED_MARKER: &str = "required-marker";
+
+/// `onClick` on a non-interactive element without `role`, `tabIndex` and a
+/// key handler (WCAG 2.1.1 Keyboard).
+pub const CLICK_ON_STATIC: &str = "click-on-static-element";
+
+/// Every rule id accepted in `ExtractOptions.rules`.
+pub const ALL_RULES: &[&str] = &[USE_OF_COLOR, REQUIRED_MARKER, CLICK_ON_STATIC];
+
+/// One element of the tree being scanned.
+#[derive(Debug, Default)]
+pub struct Element {
+    pub tag: String,
+    pub raw_tag: String,
+    /// Values of every class attribute of the tag, space-joined
+    pub classes: String,
+    /// Line of the class attribute (else of the closest event before it)
+    pub line: u32,
+    /// Direct text children, space-joined
+    pub text: String,
+    /// Any text in the element or its descendants
+    pub has_text: bool,
+    /// The element or a descendant is an icon (`<svg>`, `<img>`, `*Icon`, `role="img"`)
+    pub has_icon: bool,
+    /// The element or a descendant carries `aria-invalid`
+    pub has_aria_invalid: bool,
+    /// Form fields among the element and its descendants
+    pub fields: Vec<Field>,
+    /// Findings filed by children, resolved when this element closes
+    pub deferred: Vec<Deferred>,
+    /// Rules a scoped `a11y-ignore` suppresses on this element
+    pub ignore_rules: Vec<String>,
+}
+
+/// A form field (`input`, `select`, `textarea` or their components).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub id: Option<String>,
+    /// Has `required` or `aria-required` (dynamic values count as set)
+    pub required: bool,
+}
+
+/// What in the parent's subtree cancels a deferred finding.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Veto {
+    /// An icon, or `aria-invalid` anywhere in the parent's subtree
+    IconOrAriaInvalid,
+    /// Any text in the parent's subtree
+    Text,
+    /// A required field. With an id (`<label htmlFor>`), the field with that
+    /// id anywhere in the file decides; otherwise the fields of the closest
+    /// ancestor that has any. No field found at all keeps nothing.
+    RequiredField(Option<String>),
+}
+
+#[derive(Debug)]
+pub struct Deferred {
+    pub finding: RuleFinding,
+    pub veto: Veto,
+}
+
+impl Element {
+    /// `Some(keep)` once the deferred finding can be decided at this
+    /// element, `None` to pass it on to the parent.
+    fn decide(&self, veto: &Veto, is_root: bool) -> Option<bool> {
+        match veto {
+            Veto::IconOrAriaInvalid => Some(!(self.has_icon || self.has_aria_invalid)),
+            Veto::Text => Some(!self.has_text),
+            Veto::RequiredField(Some(id)) if is_root => Some(
+                self.fields
+                    .iter()
+                    .find(|f| f.id.as_deref() == Some(id.as_str()))
+                    .is_some_and(|f| !f.required),
+            ),
+            Veto::RequiredField(None) if !self.fields.is_empty() => {
+                Some(!self.fields.iter().any(|f| f.required))
+            }
+            Veto::RequiredField(_) => is_root.then_some(false),
+        }
+    }
+
+    /// Class names of the element, variant-free ones only (`hover:…` skipped).
+    pub fn base_classes(&self) -> impl Iterator<Item = &str> {
+        self.classes
+            .split(|c: char| c.is_whitespace() || "\"'`,(){}?&|!".contains(c))
+            .filter(|c| !c.is_empty() && !c.contains(':'))
+            .map(|c| c.split('/').next().unwrap_or(c))
+    }
+
+    pub fn has_class(&self, class: &str) -> bool {
+        self.base_classes().any(|c| c == class)
+    }
+}
+
+/// Value of attribute `name` in a raw JSX tag: `Some("")` for a bare boolean
+/// attribute, the unquoted value for `name="v"`, the expression for `name={v}`.
+pub fn attr_value<'a>(raw_tag: &'a str, name: &str) -> Option<&'a str> {
+    let bytes = raw_tag.as_bytes();
+    let mut from = 0;
+    while let Some(rel) = raw_tag[from..].find(name) {
+        let pos = from + rel;
+        let end = pos + name.len();
+        from = end;
+        let boundary_before = pos > 0 && bytes[pos - 1].is_ascii_whitespace();
+        let next = bytes.get(end).copied();
+        if !boundary_before || next.is_some_and(|b| b.is_ascii_alphanumeric() || b == b'-') {
+            continue;
+        }
+        if next != Some(b'=') {
+            return Some("");
+        }
+        let value = &raw_tag[end + 1..];
+        let (open, close) = match value.as_bytes().first() {
+            Some(b'"') => ('"', '"'),
+            Some(b'\'') => ('\'', '\''),
+            Some(b'{') => ('{', '}'),
+            _ => return Some(""),
+        };
+        let inner = &value[open.len_utf8()..];
+        let stop = inner.find(close).unwrap_or(inner.len());
+        return Some(inner[..stop].trim_matches(|c| c == '"' || c == '\''));
+    }
+    None
+}
+
+/// Like `attr_value`, for string-literal values only (`name="v"` / `name='v'`).
+pub fn static_attr_value<'a>(raw_tag: &'a str, name: &str) -> Option<&'a str> {
+    let value = attr_value(raw_tag, name)?;
+    let quoted = [format!("{}=\"{}\"", name, value), format!("{}='{}'", name, value)];
+    quoted.iter().any(|q| raw_tag.contains(q.as_str())).then_some(value)
+}
+
+/// Whether an element is (or directly renders) an icon.
+fn is_icon(tag: &str, raw_tag: &str) -> bool {
+    tag == "svg"
+        || tag == "img"
+        || (tag.starts_with(|c: char| c.is_ascii_uppercase()) && tag.ends_with("Icon"))
+        || attr_value(raw_tag, "role") == Some("img")
+}
+
+/// Run the enabled rules over one file.
+pub struct RuleVisitor {
+    enabled: Vec<String>,
+    locale: Locale,
+    /// `stack[0]` is a synthetic root so top-level elements have a parent
+    stack: Vec<Element>,
+    /// The top element was just opened self-closing: it closes on the next event
+    pending_self_close: bool,
+    last_line: u32,
+    findings: Vec<RuleFinding>,
+    /// Scoped a11y-ignore comments, taken by the next element
+    annotations: AnnotationParser,
+}
+
+impl RuleVisitor {
+    pub fn new(enabled: &[String], locale: Locale) -> Self {
+        Self {
+            enabled: enabled.to_vec(),
+            locale,
+            stack: vec![Element::default()],
+            pending_self_close: false,
+            last_line: 1,
+            findings: Vec::new(),
+            annotations: AnnotationParser::new(),
+        }
+    }
+
+    fn is_enabled(&self, rule: &str) -> bool {
+        self.enabled.iter().any(|r| r == rule)
+    }
+
+    /// Ids in `requested` that are not known rules.
+    pub fn unknown_rules(requested: &[String]) -> Vec<&str> {
+        requested
+            .iter()
+            .map(String::as_str)
+            .filter(|r| !ALL_RULES.contains(r))
+            .collect()
+    }
+
+    pub fn into_findings(mut self) -> Vec<RuleFinding> {
+        self.flush_self_close();
+        while self.stack.len() > 1 {
+            self.close_top();
+        }
+        let mut root = self.stack.pop().unwrap_or_default();
+        self.resolve_deferred(&mut root, true);
+        self.findings.sort_by_key(|f| f.line);
+        self.findings
+    }
+
+    fn flush_self_close(&mut self) {
+        if self.pending_self_close {
+            self.pending_self_close = false;
+            self.close_top();
+        }
+    }
+
+    /// Pop the top element, run the rules on it and bubble its facts up.
+    fn close_top(&mut self) {
+        if self.stack.len() < 2 {
+            return;
+        }
+        let mut el = self.stack.pop().unwrap_or_default();
+        el.has_text |= !el.text.is_empty();
+        el.has_icon |= is_icon(&el.tag, &el.raw_tag);
+        el.has_aria_invalid |= attr_value(&el.raw_tag, "aria-invalid").is_some_and(|v| v != "false");
+        if let Some(field) = required_marker::field_of(&el.tag, &el.raw_tag) {
+            el.fields.push(field);
+        }
+
+        let use_of_color = self.is_enabled(USE_OF_COLOR);
+        let required = self.is_enabled(REQUIRED_MARKER);
+        let ignore_rules = std::mem::take(&mut el.ignore_rules);
+        let is_suppressed = |f: &RuleFinding| ignore_rules.contains(&f.rule);
+        if self.is_enabled(CLICK_ON_STATIC) {
+            self.findings.extend(static_interaction::check(&el, self.locale).filter(|f| !is_suppressed(f)));
+        }
+        let passed_on = self.resolve_deferred(&mut el, false);
+        let parent = self.stack.last_mut().expect("root element");
+        let filed_before = parent.deferred.len();
+        if use_of_color {
+            use_of_color::check(&el, parent, self.locale);
+        }
+        if required {
+            required_marker::check(&el, parent, self.locale);
+        }
+        let mut filed = parent.deferred.split_off(filed_before);
+        filed.retain(|d| !is_suppressed(&d.finding));
+        parent.deferred.append(&mut filed);
+        parent.has_text |= el.has_text;
+        parent.has_icon |= el.has_icon;
+        parent.has_aria_invalid |= el.has_aria_invalid;
+        parent.fields.append(&mut el.fields);
+        parent.deferred.extend(passed_on);
+    }
+
+    /// Keep or drop the findings deferred on `el`; returns the undecided ones.
+    fn resolve_deferred(&mut self, el: &mut Element, is_root: bool) -> Vec<Deferred> {
+        let mut passed_on = Vec::new();
+        for d in std::mem::take(&mut el.deferred) {
+            match el.decide(&d.veto, is_root) {
+                Some(true) => self.findings.push(d.finding),
+                Some(false) => {}
+                None => passed_on.push(d),
+            }
+        }
+        passed_on
+    }
+}
+
+impl JsxVisitor for RuleVisitor {
+    fn on_tag_open(&mut self, tag_name: &str, is_self_closing: bool, raw_tag: &str) {
+        self.flush_self_close();
+        // Only rule-scoped suppressions apply: a bare `a11y-ignore` is contrast-only
+        let (_, ignore_rules) = self.annotations.take_ignore();
+        self.stack.push(Element {
+            tag: tag_name.to_string(),
+            raw_tag: raw_tag.to_string(),
+            line: self.last_line,
+            ignore_rules: ignore_rules.unwrap_or_default(),
+            ..Default::default()
+        });
+        self.pending_self_close = is_self_closing;
+    }
+
+    fn on_tag_close(&mut self, tag_name: &str) {
+        self.flush_self_close();
+        // Close up to the matching tag; ignore closes with no open match
+        let Some(depth) = self.stack.iter().skip(1).rposition(|e| e.tag == tag_name) else {
+            return;
+        };
+        while self.stack.len() > depth + 1 {
+            self.close_top();
+        }
+    }
+
+    fn on_comment(&mut self, content: &str, line: u32) {
+        self.flush_self_close();
+        self.last_line = line;
+        self.annotations.on_comment(content, line);
+    }
+
+    fn on_class_attribute(&mut self, value: &str, line: u32, raw_tag: &str) {
+        self.last_line = line;
+        // Standalone cn() calls (no tag) belong to no element
+        if raw_tag.is_empty() || self.stack.len() < 2 {
+            return;
+        }
+        let top = self.stack.last_mut().expect("element");
+        if top.classes.is_empty() {
+            top.line = line;
+        } else {
+            top.classes.push(' ');
+        }
+        top.classes.push_str(value);
+    }
+
+    fn on_text(&mut self, text: &str, line: u32) {
+        self.flush_self_close();
+        self.last_line = line;
+        if self.stack.len() < 2 {
+            return;
+        }
+        let top = self.stack.last_mut().expect("element");
+        if !top.text.is_empty() {
+            top.text.push(' ');
+        }
+        top.text.push_str(text);
+    }
+}
+
+/// Findings of rule `rule_id` over `source`, for rule tests.
+#[cfg(test)]
+pub(crate) fn findings(rule_id: &str, source: &str) -> Vec<RuleFinding> {
+    let mut v = RuleVisitor::new(&[rule_id.to_string()], Locale::En);
+    crate::parser::tokenizer::scan_jsx(source, &mut [&mut v as &mut dyn JsxVisitor]);
+    v.into_findings()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::tokenizer::scan_jsx;
+
+    #[test]
+    fn attr_value_forms() {
+        let raw = r#"<input required aria-invalid={hasError} role="img" data-x='y' aria-required>"#;
+        assert_eq!(attr_value(raw, "required"), Some(""));
+        assert_eq!(attr_value(raw, "aria-invalid"), Some("hasError"));
+        assert_eq!(attr_value(raw, "role"), Some("img"));
+        assert_eq!(attr_value(raw, "data-x"), Some("y"));
+        assert_eq!(attr_value(raw, "aria-required"), Some(""));
+        assert_eq!(attr_value(raw, "invalid"), None);
+        assert_eq!(static_attr_value(raw, "role"), Some("img"));
+        assert_eq!(static_attr_value(raw, "aria-invalid"), None);
+    }
+
+    #[test]
+    fn base_classes_skip_variants_and_opacity() {
+        let el = Element {
+            classes: r#""text-red-500/80 hover:text-red-700", active && "font-bold""#.to_string(),
+            ..Default::default()
+        };
+        let classes: Vec<&str> = el.base_classes().collect();
+        assert_eq!(classes, vec!["text-red-500", "active", "font-bold"]);
+    }
+
+    #[test]
+    fn unclosed_and_mismatched_tags_still_resolve() {
+        let source = r#"<div><p className="text-red-500">Wrong password</div>"#;
+        assert_eq!(findings(USE_OF_COLOR, source).len(), 1);
+    }
+
+    #[test]
+    fn scoped_ignore_suppresses_named_rules_only() {
+        let named =
+            r#"<div>{/* a11y-ignore[use-of-color]: by design */}<p className="text-red-500">Wrong password</p></div>"#;
+        assert!(findings(USE_OF_COLOR, named).is_empty());
+        let other = r#"<div>{/* a11y-ignore[contrast-text] */}<p className="text-red-500">Wrong password</p></div>"#;
+        assert_eq!(findings(USE_OF_COLOR, other).len(), 1);
+        // A bare a11y-ignore is contrast-only
+        let bare = r#"<div>{/* a11y-ignore */}<p className="text-red-500">Wrong password</p></div>"#;
+        assert_eq!(findings(USE_OF_COLOR, bare).len(), 1);
+    }
+
+    #[test]
+    fn unknown_rule_ids_reported() {
+        let requested = vec![USE_OF_COLOR.to_string(), "nope".to_string()];
+        assert_eq!(RuleVisitor::unknown_rules(&requested), vec!["nope"]);
+    }
+
+    #[test]
+    fn disabled_rule_finds_nothing() {
+        let mut v = RuleVisitor::new(&[], Locale::En);
+        scan_jsx(r#"<p className="text-red-500">Wrong</p>"#, &mut [&mut v as &mut dyn JsxVisitor]);
+        assert!(v.into_findings().is_empty());
+    }
+}
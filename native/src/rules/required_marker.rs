@@ -0,0 +1,142 @@
+//! Required-field markers (WCAG 1.3.1 Info and Relationships).
+//!
+//! A visual `*` tells sighted users a field is required; assistive technology
+//! only learns it from `required` / `aria-required` on the field itself. The
+//! marker's contrast is checked like any text: its region is flagged
+//! `required_marker` so those results can be reported as such.
+
+use super::{attr_value, Deferred, Element, Field, Veto, REQUIRED_MARKER};
+use crate::i18n::{self, Locale, MessageKey};
+use crate::types::RuleFinding;
+
+const CRITERION: &str = "1.3.1";
+
+/// Field tags, native and common component-library names.
+const FIELD_TAGS: &[&str] = &[
+    "input", "select", "textarea", "Input", "Select", "Textarea", "Checkbox", "RadioGroup", "Combobox",
+];
+
+/// `<input type>` values that are not data fields.
+const NON_FIELD_TYPES: &[&str] = &["hidden", "submit", "button", "reset", "image"];
+
+/// Whether JSX text is a required marker (`*`, optionally parenthesized).
+pub fn is_marker_text(text: &str) -> bool {
+    matches!(text.trim(), "*" | "(*)" | "\u{FF0A}")
+}
+
+/// Whether a class list renders a `*` through a pseudo-element
+/// (`after:content-['*']`).
+pub fn is_marker_content(classes: &str) -> bool {
+    classes.contains("content-['*']") || classes.contains("content-[\"*\"]")
+}
+
+/// The form field an element is, if any.
+pub fn field_of(tag: &str, raw_tag: &str) -> Option<Field> {
+    if !FIELD_TAGS.contains(&tag) {
+        return None;
+    }
+    if tag == "input" && attr_value(raw_tag, "type").is_some_and(|t| NON_FIELD_TYPES.contains(&t)) {
+        return None;
+    }
+    let is_set = |name| attr_value(raw_tag, name).is_some_and(|v| v != "false");
+    Some(Field {
+        id: attr_value(raw_tag, "id").map(str::to_string),
+        required: is_set("required") || is_set("aria-required"),
+    })
+}
+
+/// Inspect a closing element and file a deferred finding for markers.
+pub fn check(el: &Element, parent: &mut Element, locale: Locale) {
+    if !(is_marker_text(&el.text) || is_marker_content(&el.classes)) {
+        return;
+    }
+    // The marker is usually inside the label; `content-['*']` is on the label itself
+    let label = if is_label(&el.tag) { el } else { &*parent };
+    let html_for = if is_label(&label.tag) {
+        attr_value(&label.raw_tag, "htmlFor")
+            .or_else(|| attr_value(&label.raw_tag, "for"))
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+    } else {
+        None
+    };
+    let finding = RuleFinding {
+        rule: REQUIRED_MARKER.to_string(),
+        wcag_criterion: CRITERION.to_string(),
+        line: el.line,
+        tag: el.tag.clone(),
+        class_name: el.classes.clone(),
+        message: i18n::message(locale, MessageKey::RuleRequiredMarkerUnbound).to_string(),
+    };
+    parent.deferred.push(Deferred { finding, veto: Veto::RequiredField(html_for) });
+}
+
+fn is_label(tag: &str) -> bool {
+    tag == "label" || tag == "Label" || tag == "FormLabel"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::findings;
+
+    #[test]
+    fn marker_text_forms() {
+        assert!(is_marker_text(" * "));
+        assert!(is_marker_text("(*)"));
+        assert!(!is_marker_text("**"));
+        assert!(is_marker_content("after:content-['*'] after:ml-0.5"));
+    }
+
+    #[test]
+    fn marker_without_required_field_flagged() {
+        let source = r#"<div className="grid gap-2">
+  <label>Email <span className="text-destructive">*</span></label>
+  <input type="email" />
+</div>"#;
+        let found = findings(REQUIRED_MARKER, source);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].rule, "required-marker");
+        assert_eq!(found[0].wcag_criterion, "1.3.1");
+        assert_eq!(found[0].line, 2);
+    }
+
+    #[test]
+    fn required_or_aria_required_satisfies_marker() {
+        let required = r#"<div><label>Email<span className="text-red-600">*</span></label><Input required /></div>"#;
+        assert!(findings(REQUIRED_MARKER, required).is_empty());
+        let aria = r#"<label>Name <span className="text-red-600">*</span><input aria-required={isRequired} /></label>"#;
+        assert!(findings(REQUIRED_MARKER, aria).is_empty());
+    }
+
+    #[test]
+    fn html_for_targets_field_by_id() {
+        let source = r#"<form>
+  <div><Label htmlFor="email" className="after:content-['*'] after:text-red-500">Email</Label></div>
+  <div><Input id="name" required /></div>
+  <div><Input id="email" /></div>
+</form>"#;
+        let found = findings(REQUIRED_MARKER, source);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].line, 2);
+        assert_eq!(found[0].tag, "Label");
+
+        let ok = source.replace(r#"<Input id="email" />"#, r#"<Input id="email" aria-required="true" />"#);
+        assert!(findings(REQUIRED_MARKER, &ok).is_empty());
+    }
+
+    #[test]
+    fn marker_without_any_field_is_not_flagged() {
+        // the field lives in another component: nothing to check against
+        assert!(findings(REQUIRED_MARKER, r#"<label>Email <span>*</span></label>"#).is_empty());
+    }
+
+    #[test]
+    fn hidden_inputs_are_not_fields() {
+        assert_eq!(field_of("input", r#"<input type="hidden" name="x">"#), None);
+        assert_eq!(
+            field_of("input", r#"<input required={false}>"#),
+            Some(Field { id: None, required: false })
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! Click handlers on non-interactive elements (WCAG 2.1.1 Keyboard).
+//!
+//! `<div onClick>` fake buttons are unreachable and inoperable from the
+//! keyboard unless they also get a `role`, a `tabIndex` and a key handler.
+//! They are also where custom, often low-contrast, button styling lives.
+
+use super::{attr_value, Element, CLICK_ON_STATIC};
+use crate::i18n::{self, Locale, MessageKey};
+use crate::types::RuleFinding;
+
+const CRITERION: &str = "2.1.1";
+
+/// Native elements with no interactive semantics of their own.
+const STATIC_TAGS: &[&str] = &[
+    "div", "span", "p", "li", "section", "article", "header", "footer", "main", "aside", "td", "tr", "img",
+    "label",
+];
+
+const KEY_HANDLERS: &[&str] = &["onKeyDown", "onKeyUp", "onKeyPress"];
+
+/// Finding for an element with `onClick` that is missing any of `role`,
+/// `tabIndex` or a key handler.
+pub fn check(el: &Element, locale: Locale) -> Option<RuleFinding> {
+    if !STATIC_TAGS.contains(&el.tag.as_str()) || attr_value(&el.raw_tag, "onClick").is_none() {
+        return None;
+    }
+    let mut missing = Vec::new();
+    let role = attr_value(&el.raw_tag, "role");
+    if role.is_none_or(|r| r.is_empty() || r == "presentation" || r == "none") {
+        missing.push("role");
+    }
+    if attr_value(&el.raw_tag, "tabIndex").is_none() {
+        missing.push("tabIndex");
+    }
+    if !KEY_HANDLERS.iter().any(|h| attr_value(&el.raw_tag, h).is_some()) {
+        missing.push("onKeyDown");
+    }
+    if missing.is_empty() {
+        return None;
+    }
+    Some(RuleFinding {
+        rule: CLICK_ON_STATIC.to_string(),
+        wcag_criterion: CRITERION.to_string(),
+        line: el.line,
+        tag: el.tag.clone(),
+        class_name: el.classes.clone(),
+        message: i18n::format_message(locale, MessageKey::RuleClickOnStatic, &[&el.tag, &missing.join(", ")]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::findings;
+
+    #[test]
+    fn clickable_div_flagged_with_missing_parts() {
+        let source = r#"<ul>
+  <div onClick={() => open()} className="cursor-pointer bg-blue-500 text-white">Open</div>
+</ul>"#;
+        let found = findings(CLICK_ON_STATIC, source);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].rule, "click-on-static-element");
+        assert_eq!(found[0].wcag_criterion, "2.1.1");
+        assert_eq!(found[0].line, 2);
+        assert_eq!(found[0].class_name, "cursor-pointer bg-blue-500 text-white");
+        assert!(found[0].message.contains("role, tabIndex, onKeyDown"));
+    }
+
+    #[test]
+    fn partially_fixed_element_lists_what_is_left() {
+        let found = findings(CLICK_ON_STATIC, r#"<span role="button" onClick={go}>Go</span>"#);
+        assert_eq!(found.len(), 1);
+        assert!(found[0].message.ends_with("tabIndex, onKeyDown"));
+        let presentation = findings(CLICK_ON_STATIC, r#"<div role="presentation" tabIndex={0} onKeyUp={k} onClick={go} />"#);
+        assert!(presentation[0].message.ends_with("role"));
+    }
+
+    #[test]
+    fn keyboard_operable_or_native_elements_pass() {
+        assert!(findings(CLICK_ON_STATIC, r#"<div role="button" tabIndex={0} onKeyDown={k} onClick={go}>Go</div>"#).is_empty());
+        assert!(findings(CLICK_ON_STATIC, r#"<button onClick={go}>Go</button>"#).is_empty());
+        assert!(findings(CLICK_ON_STATIC, r#"<Card onClick={go}>Go</Card>"#).is_empty());
+        assert!(findings(CLICK_ON_STATIC, r#"<div onClickCapture={go}>Go</div>"#).is_empty());
+    }
+}
@@ -0,0 +1,196 @@
+//! WCAG 1.4.1 Use of Color heuristics.
+//!
+//! - Error text: text in a red/destructive color whose surroundings carry no
+//!   other cue (no icon next to it, no `aria-invalid` on the related field,
+//!   not announced via `role="alert"` / `aria-live`).
+//! - Status dots: small round colored elements with no text label of their
+//!   own and no text next to them.
+
+use super::{attr_value, Deferred, Element, Veto, USE_OF_COLOR};
+use crate::i18n::{self, Locale, MessageKey};
+use crate::types::RuleFinding;
+
+const CRITERION: &str = "1.4.1";
+
+/// Chromatic Tailwind hues (neutral grays carry no status meaning).
+const STATUS_HUES: &[&str] = &[
+    "red", "orange", "amber", "yellow", "lime", "green", "emerald", "teal", "cyan", "sky", "blue",
+    "indigo", "violet", "purple", "fuchsia", "pink", "rose",
+];
+
+/// Semantic status color tokens (shadcn and common design systems).
+const STATUS_TOKENS: &[&str] = &["destructive", "success", "warning", "danger", "error", "info"];
+
+/// Largest dot size in Tailwind spacing units (4 = 1rem = 16px).
+const MAX_DOT_SPACING: f32 = 4.0;
+
+/// Inspect a closing element and file deferred findings on its parent.
+pub fn check(el: &Element, parent: &mut Element, locale: Locale) {
+    if is_error_text(el) && !is_announced(&el.raw_tag) {
+        parent.deferred.push(Deferred {
+            finding: finding(el, locale, MessageKey::RuleUseOfColorErrorText),
+            veto: Veto::IconOrAriaInvalid,
+        });
+    }
+    if is_status_dot(el) && !has_accessible_name(&el.raw_tag) {
+        parent.deferred.push(Deferred {
+            finding: finding(el, locale, MessageKey::RuleUseOfColorStatusDot),
+            veto: Veto::Text,
+        });
+    }
+}
+
+fn finding(el: &Element, locale: Locale, key: MessageKey) -> RuleFinding {
+    RuleFinding {
+        rule: USE_OF_COLOR.to_string(),
+        wcag_criterion: CRITERION.to_string(),
+        line: el.line,
+        tag: el.tag.clone(),
+        class_name: el.classes.clone(),
+        message: i18n::message(locale, key).to_string(),
+    }
+}
+
+/// Red/destructive text color on an element that has text, with no icon inside.
+fn is_error_text(el: &Element) -> bool {
+    el.has_text && !el.has_icon && el.base_classes().any(is_error_color)
+}
+
+fn is_error_color(class: &str) -> bool {
+    if let Some(token) = class.strip_prefix("text-") {
+        if matches!(token, "destructive" | "error" | "danger") {
+            return true;
+        }
+        for hue in ["red-", "rose-"] {
+            if let Some(shade) = token.strip_prefix(hue) {
+                return shade.parse::<u32>().is_ok_and(|s| s >= 400);
+            }
+        }
+    }
+    false
+}
+
+/// `role="alert"` / `role="status"` / `aria-live` make the message announced.
+fn is_announced(raw_tag: &str) -> bool {
+    matches!(attr_value(raw_tag, "role"), Some("alert") | Some("status"))
+        || attr_value(raw_tag, "aria-live").is_some_and(|v| v != "off")
+}
+
+fn has_accessible_name(raw_tag: &str) -> bool {
+    ["aria-label", "aria-labelledby", "title", "alt"]
+        .iter()
+        .any(|a| attr_value(raw_tag, a).is_some_and(|v| !v.is_empty()))
+}
+
+/// Small `rounded-full` element with a status background and no text.
+fn is_status_dot(el: &Element) -> bool {
+    !el.has_text
+        && !el.has_icon
+        && el.has_class("rounded-full")
+        && el.base_classes().any(is_status_bg)
+        && el.base_classes().any(is_small_size)
+}
+
+fn is_status_bg(class: &str) -> bool {
+    let Some(color) = class.strip_prefix("bg-") else {
+        return false;
+    };
+    if STATUS_TOKENS.contains(&color) {
+        return true;
+    }
+    color
+        .rsplit_once('-')
+        .is_some_and(|(hue, shade)| STATUS_HUES.contains(&hue) && shade.parse::<u32>().is_ok())
+}
+
+/// `h-2`, `w-2.5`, `size-3`, `h-[8px]`... up to 1rem.
+fn is_small_size(class: &str) -> bool {
+    let Some(value) = ["h-", "w-", "size-"].iter().find_map(|p| class.strip_prefix(p)) else {
+        return false;
+    };
+    if let Some(px) = value.strip_prefix('[').and_then(|v| v.strip_suffix("px]")) {
+        return px.parse::<f32>().is_ok_and(|px| px <= MAX_DOT_SPACING * 4.0);
+    }
+    value.parse::<f32>().is_ok_and(|units| units <= MAX_DOT_SPACING)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rules::{findings, USE_OF_COLOR};
+
+    #[test]
+    fn red_error_text_without_cue_flagged() {
+        let source = r#"<div>
+  <input className="border" />
+  <p className="text-sm text-red-500">Password too short</p>
+</div>"#;
+        let found = findings(USE_OF_COLOR, source);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].rule, "use-of-color");
+        assert_eq!(found[0].wcag_criterion, "1.4.1");
+        assert_eq!(found[0].line, 3);
+        assert_eq!(found[0].tag, "p");
+    }
+
+    #[test]
+    fn aria_invalid_sibling_clears_error_text() {
+        let source = r#"<div>
+  <input aria-invalid={!!error} className="border" />
+  <p className="text-destructive">Invalid email</p>
+</div>"#;
+        assert!(findings(USE_OF_COLOR, source).is_empty());
+    }
+
+    #[test]
+    fn icon_next_to_error_text_clears_it() {
+        let source = r#"<div className="flex">
+  <AlertCircleIcon className="h-4 w-4" />
+  <span className="text-red-600">Required</span>
+</div>"#;
+        assert!(findings(USE_OF_COLOR, source).is_empty());
+        let inline = r#"<p className="text-red-600"><svg />Required</p>"#;
+        assert!(findings(USE_OF_COLOR, inline).is_empty());
+    }
+
+    #[test]
+    fn announced_error_text_not_flagged() {
+        assert!(findings(USE_OF_COLOR, r#"<p role="alert" className="text-red-500">Failed</p>"#).is_empty());
+        assert!(findings(USE_OF_COLOR, r#"<p aria-live="polite" className="text-red-500">Failed</p>"#).is_empty());
+    }
+
+    #[test]
+    fn light_or_variant_red_not_error_text() {
+        assert!(findings(USE_OF_COLOR, r#"<p className="text-red-200">Pink</p>"#).is_empty());
+        assert!(findings(USE_OF_COLOR, r#"<p className="hover:text-red-500">Link</p>"#).is_empty());
+    }
+
+    #[test]
+    fn unlabeled_status_dot_flagged() {
+        let source = r#"<td>
+  <span className="inline-block h-2 w-2 rounded-full bg-green-500" />
+</td>"#;
+        let found = findings(USE_OF_COLOR, source);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].line, 2);
+        assert!(found[0].message.contains("status"));
+    }
+
+    #[test]
+    fn status_dot_with_sibling_text_or_label_not_flagged() {
+        let with_text = r#"<div className="flex items-center gap-2">
+  <span className="size-2 rounded-full bg-emerald-500" />
+  Online
+</div>"#;
+        assert!(findings(USE_OF_COLOR, with_text).is_empty());
+        let sr_only = r#"<div><span className="h-2 w-2 rounded-full bg-red-500" /><span className="sr-only">Offline</span></div>"#;
+        assert!(findings(USE_OF_COLOR, sr_only).is_empty());
+        let labeled = r#"<span aria-label="Online" className="h-2 w-2 rounded-full bg-green-500" />"#;
+        assert!(findings(USE_OF_COLOR, labeled).is_empty());
+    }
+
+    #[test]
+    fn large_or_neutral_circles_not_dots() {
+        assert!(findings(USE_OF_COLOR, r#"<div className="h-10 w-10 rounded-full bg-blue-500" />"#).is_empty());
+        assert!(findings(USE_OF_COLOR, r#"<div className="h-2 w-2 rounded-full bg-gray-400" />"#).is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+//! Deterministic file sampling for smoke runs on very large repositories.
+//!
+//! Each file is kept or dropped from a hash of `(seed, path)`, so the choice
+//! is independent of file order and of the other files in the run: the same
+//! seed selects the same files on every machine, and adding a file never
+//! reshuffles the rest of the sample.
+
+use crate::types::{FileInput, PreExtractedFile, SamplingSummary};
+
+/// Seed used when `sample` is set without an explicit `seed`.
+pub const DEFAULT_SEED: u32 = 0;
+
+/// Effective sampling rate: `None` when every file must be scanned
+/// (option unset, >= 1, or not a valid fraction).
+pub fn effective_rate(sample: Option<f64>) -> Option<f64> {
+    match sample {
+        Some(rate) if rate > 0.0 && rate < 1.0 => Some(rate),
+        Some(rate) if rate.is_nan() || rate <= 0.0 => {
+            log::warn!("ignoring invalid sample rate {} (expected 0 < sample <= 1)", rate);
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Uniform value in [0, 1) derived from `seed` and `path`.
+fn unit_hash(path: &str, seed: u32) -> f64 {
+    // FNV-1a over seed + path, then a splitmix64 finalizer to spread short inputs.
+    let mut h: u64 = 0xcbf29ce484222325;
+    for b in seed.to_le_bytes().iter().chain(path.as_bytes()) {
+        h ^= *b as u64;
+        h = h.wrapping_mul(0x100000001b3);
+    }
+    h ^= h >> 30;
+    h = h.wrapping_mul(0xbf58476d1ce4e5b9);
+    h ^= h >> 27;
+    h = h.wrapping_mul(0x94d049bb133111eb);
+    h ^= h >> 31;
+    (h >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Whether `path` belongs to the sample at `rate` for `seed`.
+pub fn is_sampled(path: &str, rate: f64, seed: u32) -> bool {
+    unit_hash(path, seed) < rate
+}
+
+/// Files to scan for this run, in input order.
+pub fn select(files: &[FileInput], sample: Option<f64>, seed: Option<u32>) -> Vec<&FileInput> {
+    match effective_rate(sample) {
+        Some(rate) => {
+            let seed = seed.unwrap_or(DEFAULT_SEED);
+            files.iter().filter(|f| is_sampled(&f.path, rate, seed)).collect()
+        }
+        None => files.iter().collect(),
+    }
+}
+
+/// Exact counts for the sampled set plus counts extrapolated to the whole run.
+///
+/// `eligible_files` counts the inputs an unsampled run would scan (after
+/// `sniff_js`, file kind and `changed_only` filtering). The extrapolation
+/// factor is `eligible_files / scanned files`; multiply any per-sample count
+/// (e.g. violations from the check phase) by it for an estimate.
+pub fn summarize(
+    rate: f64,
+    seed: u32,
+    total_files: usize,
+    eligible_files: usize,
+    scanned: &[PreExtractedFile],
+) -> SamplingSummary {
+    let sampled_regions: usize = scanned.iter().map(|f| f.regions.len()).sum();
+    let sampled_ignored: usize = scanned
+        .iter()
+        .flat_map(|f| f.regions.iter())
+        .filter(|r| r.ignored == Some(true))
+        .count();
+    let factor = if scanned.is_empty() {
+        0.0
+    } else {
+        eligible_files as f64 / scanned.len() as f64
+    };
+    SamplingSummary {
+        rate,
+        seed,
+        total_files: total_files as u32,
+        eligible_files: eligible_files as u32,
+        sampled_files: scanned.len() as u32,
+        sampled_regions: sampled_regions as u32,
+        sampled_ignored_regions: sampled_ignored as u32,
+        extrapolation_factor: factor,
+        estimated_regions: (sampled_regions as f64 * factor).round(),
+        estimated_ignored_regions: (sampled_ignored as f64 * factor).round(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(n: usize) -> Vec<FileInput> {
+        (0..n)
+            .map(|i| FileInput {
+                path: format!("src/components/file{}.tsx", i),
+                content: String::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn invalid_or_full_rates_disable_sampling() {
+        assert_eq!(effective_rate(None), None);
+        assert_eq!(effective_rate(Some(1.0)), None);
+        assert_eq!(effective_rate(Some(0.0)), None);
+        assert_eq!(effective_rate(Some(f64::NAN)), None);
+        assert_eq!(effective_rate(Some(0.25)), Some(0.25));
+    }
+
+    #[test]
+    fn same_seed_same_selection() {
+        let input = files(200);
+        let a: Vec<&str> = select(&input, Some(0.1), Some(7)).iter().map(|f| f.path.as_str()).collect();
+        let b: Vec<&str> = select(&input, Some(0.1), Some(7)).iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(a, b);
+        let c: Vec<&str> = select(&input, Some(0.1), Some(8)).iter().map(|f| f.path.as_str()).collect();
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn selection_independent_of_order_and_other_files() {
+        let input = files(200);
+        let mut reversed = input.clone();
+        reversed.reverse();
+        let mut a: Vec<&str> = select(&input, Some(0.2), None).iter().map(|f| f.path.as_str()).collect();
+        let mut b: Vec<&str> = select(&reversed, Some(0.2), None).iter().map(|f| f.path.as_str()).collect();
+        a.sort();
+        b.sort();
+        assert_eq!(a, b);
+        assert_eq!(
+            is_sampled("src/components/file3.tsx", 0.2, 0),
+            select(&input[3..4], Some(0.2), None).len() == 1
+        );
+    }
+
+    #[test]
+    fn sample_size_close_to_rate() {
+        let input = files(2000);
+        let n = select(&input, Some(0.1), Some(1)).len();
+        assert!((150..=250).contains(&n), "sampled {}", n);
+    }
+
+    #[test]
+    fn summary_extrapolates_counts() {
+        use crate::types::ClassRegion;
+        let region = ClassRegion {
+            content: "text-white".to_string(),
+            start_line: 1,
+            ..Default::default()
+        };
+        let scanned = vec![PreExtractedFile {
+            path: "a.tsx".to_string(),
+            regions: vec![region.clone(), region],
+            ..Default::default()
+        }];
+        let s = summarize(0.1, 3, 12, 10, &scanned);
+        assert_eq!(s.sampled_files, 1);
+        assert_eq!(s.sampled_regions, 2);
+        assert_eq!(s.extrapolation_factor, 10.0);
+        assert_eq!(s.estimated_regions, 20.0);
+    }
+}
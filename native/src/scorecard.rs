@@ -0,0 +1,168 @@
+//! Per-component contrast scorecard for design-system teams.
+//!
+//! Each result is attributed to the innermost component (PascalCase tag) of
+//! its ancestry, so `<p>` inside `<CardContent>` counts for `CardContent`;
+//! results outside any component are left out. A component's variants are
+//! the distinct renderings its pairs were checked in: interactive state,
+//! theme and breakpoint (see `ClassRegion`), "base" for the plain one.
+
+use std::collections::BTreeMap;
+
+use crate::types::{CheckResultJs, ComponentScore, ContrastResult, Scorecard};
+
+/// Innermost component the result sits in (its own tag included).
+fn component_of(result: &ContrastResult) -> Option<&str> {
+    let is_component = |tag: &&str| tag.starts_with(|c: char| c.is_ascii_uppercase()) && *tag != "Fragment";
+    let own = result.tag_name.as_deref().filter(is_component);
+    let ancestry = result.ancestry.iter().flatten().map(String::as_str);
+    own.or_else(|| ancestry.rev().find(is_component))
+}
+
+/// Rendering the result was checked in, e.g. "base", "hover", "dark+md".
+fn variant_of(result: &ContrastResult) -> String {
+    let parts: Vec<&str> = [&result.theme, &result.breakpoint, &result.interactive_state]
+        .into_iter()
+        .filter_map(|part| part.as_deref().filter(|p| *p != "light"))
+        .collect();
+    if parts.is_empty() {
+        "base".to_string()
+    } else {
+        parts.join("+")
+    }
+}
+
+#[derive(Default)]
+struct Tally<'a> {
+    variants: Vec<String>,
+    passed: u32,
+    violations: u32,
+    suppressions: u32,
+    worst: Option<&'a ContrastResult>,
+}
+
+impl<'a> Tally<'a> {
+    fn checked(&mut self, result: &'a ContrastResult) {
+        let variant = variant_of(result);
+        if !self.variants.contains(&variant) {
+            self.variants.push(variant);
+        }
+        if self.worst.is_none_or(|worst| result.ratio < worst.ratio) {
+            self.worst = Some(result);
+        }
+    }
+}
+
+/// Score every component with results in `result`.
+pub fn generate_scorecard(result: &CheckResultJs) -> Scorecard {
+    let mut tallies: BTreeMap<&str, Tally> = BTreeMap::new();
+    for r in &result.passed {
+        if let Some(component) = component_of(r) {
+            let tally = tallies.entry(component).or_default();
+            tally.passed += 1;
+            tally.checked(r);
+        }
+    }
+    for r in &result.violations {
+        if let Some(component) = component_of(r) {
+            let tally = tallies.entry(component).or_default();
+            tally.violations += 1;
+            tally.checked(r);
+        }
+    }
+    for r in &result.ignored {
+        if let Some(component) = component_of(r) {
+            tallies.entry(component).or_default().suppressions += 1;
+        }
+    }
+
+    let components: Vec<ComponentScore> = tallies
+        .into_iter()
+        .map(|(component, t)| {
+            let checked = t.passed + t.violations;
+            let pass_rate =
+                if checked == 0 { 0.0 } else { (t.passed as f64 / checked as f64 * 1000.0).round() / 10.0 };
+            let mut variants = t.variants;
+            variants.sort_by_key(|v| (v != "base", v.clone()));
+            ComponentScore {
+                component: component.to_string(),
+                variants,
+                checked,
+                violations: t.violations,
+                pass_rate,
+                worst_pair: t
+                    .worst
+                    .map(|w| format!("{} on {} ({:.2}:1)", w.text_class, w.bg_class, w.ratio)),
+                worst_ratio: t.worst.map(|w| w.ratio),
+                suppressions: t.suppressions,
+            }
+        })
+        .collect();
+    let json = serde_json::to_string(&components).unwrap_or_default();
+    let markdown = render_markdown(&components);
+    Scorecard { components, json, markdown }
+}
+
+fn render_markdown(components: &[ComponentScore]) -> String {
+    let mut md = String::from("| Component | Variants | Pass rate | Worst pair | Suppressions |\n");
+    md.push_str("|---|---|---|---|---|\n");
+    for c in components {
+        md.push_str(&format!(
+            "| {} | {} | {}% ({} of {}) | {} | {} |\n",
+            c.component,
+            c.variants.join(", "),
+            c.pass_rate,
+            c.checked - c.violations,
+            c.checked,
+            c.worst_pair.as_deref().unwrap_or("—").replace('|', "\\|"),
+            c.suppressions
+        ));
+    }
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::checker::check_all_pairs;
+    use crate::types::ColorPair;
+
+    fn make_pair(ancestry: &[&str], text_hex: &str) -> ColorPair {
+        ColorPair {
+            tag_name: ancestry.last().map(|t| t.to_string()),
+            ancestry: Some(ancestry.iter().map(|t| t.to_string()).collect()),
+            ..ColorPair::fixture(&format!("text-[{}]", text_hex), text_hex)
+        }
+    }
+
+    #[test]
+    fn scores_by_innermost_component() {
+        let mut hover = make_pair(&["Card", "Button"], "#000000");
+        hover.interactive_state = Some("hover".to_string());
+        let mut dark = make_pair(&["Card", "Button"], "#000000");
+        dark.theme = Some("dark".to_string());
+        let mut ignored = make_pair(&["Button"], "#eeeeee");
+        ignored.ignored = Some(true);
+        let pairs = [
+            make_pair(&["Card", "Button"], "#000000"),
+            make_pair(&["Card", "Button"], "#aaaaaa"),
+            hover,
+            dark,
+            ignored,
+            make_pair(&["Card", "div", "p"], "#000000"),
+            make_pair(&["main", "p"], "#000000"),
+        ];
+        let card = generate_scorecard(&check_all_pairs(&pairs, "AA", "#ffffff").into());
+
+        let names: Vec<&str> = card.components.iter().map(|c| c.component.as_str()).collect();
+        assert_eq!(names, vec!["Button", "Card"]);
+        let button = &card.components[0];
+        assert_eq!(button.variants, vec!["base", "dark", "hover"]);
+        assert_eq!((button.checked, button.violations, button.suppressions), (4, 1, 1));
+        assert_eq!(button.pass_rate, 75.0);
+        assert_eq!(button.worst_pair.as_deref(), Some("text-[#aaaaaa] on bg-white (2.32:1)"));
+
+        assert!(card.json.starts_with(r#"[{"component":"Button","variants":["base","dark","hover"],"checked":4"#));
+        assert!(card.markdown.contains("| Button | base, dark, hover | 75% (3 of 4) | text-[#aaaaaa] on bg-white"));
+        assert!(card.markdown.contains("| Card | base | 100% (1 of 1) |"));
+    }
+}
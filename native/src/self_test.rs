@@ -0,0 +1,159 @@
+//! Built-in conformance suite runnable in an installed binary.
+//!
+//! A handful of known color pairs and JSX snippets with fixed expected
+//! results, exercising the math, the parser modes and a full theme audit.
+//! A prebuilt binary that loads but miscomputes (wrong target, ABI or
+//! float mismatch on an exotic platform) fails here with the expected and
+//! actual values instead of silently producing wrong audits. Each case runs
+//! under `catch_unwind`, so a panicking case is reported, not fatal.
+
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::math::apca::calc_apca_lc;
+use crate::math::color_parse::to_hex;
+use crate::math::wcag::contrast_ratio;
+use crate::parser::{scan_file_with_config, scan_source, ScanConfig};
+use crate::snippets::audit_snippets;
+use crate::theme::Theme;
+use crate::types::{ColorMapEntry, SelfTestCase, SelfTestReport, Snippet, ThemeConfig};
+
+type Case = fn() -> Result<(), String>;
+
+const CASES: &[(&str, Case)] = &[
+    ("wcag-ratio", wcag_ratio),
+    ("wcag-aa-boundary", wcag_aa_boundary),
+    ("apca-lc", apca_lc),
+    ("color-parsing", color_parsing),
+    ("jsx-extraction", jsx_extraction),
+    ("markup-modes", markup_modes),
+    ("theme-audit", theme_audit),
+];
+
+fn expect<T: PartialEq + Debug>(what: &str, actual: T, expected: T) -> Result<(), String> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(format!("{}: expected {:?}, got {:?}", what, expected, actual))
+    }
+}
+
+fn expect_near(what: &str, actual: f64, expected: f64, tolerance: f64) -> Result<(), String> {
+    if (actual - expected).abs() <= tolerance {
+        Ok(())
+    } else {
+        Err(format!("{}: expected {} ± {}, got {}", what, expected, tolerance, actual))
+    }
+}
+
+fn wcag_ratio() -> Result<(), String> {
+    expect_near("#000000 on #ffffff", contrast_ratio("#000000", "#ffffff"), 21.0, 0.01)?;
+    expect_near("#ffffff on #ffffff", contrast_ratio("#ffffff", "#ffffff"), 1.0, 0.001)
+}
+
+fn wcag_aa_boundary() -> Result<(), String> {
+    expect("#767676 on #ffffff passes 4.5:1", contrast_ratio("#767676", "#ffffff") >= 4.5, true)?;
+    expect("#777777 on #ffffff passes 4.5:1", contrast_ratio("#777777", "#ffffff") >= 4.5, false)
+}
+
+fn apca_lc() -> Result<(), String> {
+    expect_near("Lc #000000 on #ffffff", calc_apca_lc("#000000", "#ffffff"), 106.0, 1.0)?;
+    expect_near("Lc #ffffff on #000000", calc_apca_lc("#ffffff", "#000000"), -107.9, 1.0)
+}
+
+fn color_parsing() -> Result<(), String> {
+    expect("#F00", to_hex("#F00").as_deref(), Some("#ff0000"))?;
+    expect("rgb(255, 0, 128)", to_hex("rgb(255, 0, 128)").as_deref(), Some("#ff0080"))?;
+    expect("transparent", to_hex("transparent"), None)
+}
+
+fn jsx_extraction() -> Result<(), String> {
+    let source = r#"<div className="bg-white">
+  <p className={cn("text-gray-500", active && "font-bold")}>a</p>
+</div>"#;
+    let config = ScanConfig { default_bg: "bg-background".to_string(), ..Default::default() };
+    let regions = scan_file_with_config(source, &config);
+    let found: Vec<(u32, &str, &str)> =
+        regions.iter().map(|r| (r.start_line, r.content.as_str(), r.context_bg.as_str())).collect();
+    expect(
+        "regions",
+        found,
+        vec![(1, "bg-white", "bg-background"), (2, "text-gray-500 font-bold", "bg-white")],
+    )
+}
+
+fn markup_modes() -> Result<(), String> {
+    let vue = "<template>\n  <p :class=\"{ 'text-red-600': err }\">a</p>\n</template>";
+    let (regions, ..) = scan_source("App.vue", vue, &Default::default());
+    let found: Vec<(u32, &str)> = regions.iter().map(|r| (r.start_line, r.content.as_str())).collect();
+    expect("vue regions", found, vec![(2, "text-red-600")])
+}
+
+fn theme_audit() -> Result<(), String> {
+    let colors = [("--color-background", "#ffffff"), ("--color-gray-400", "#9ca3af"), ("--color-gray-900", "#111827")];
+    let theme = Theme::from_config(&ThemeConfig {
+        color_map: colors
+            .iter()
+            .map(|(name, hex)| ColorMapEntry { name: name.to_string(), hex: hex.to_string(), alpha: None })
+            .collect(),
+        default_bg: "bg-background".to_string(),
+        ..Default::default()
+    });
+    let snippet = Snippet {
+        path: "SelfTest.tsx".to_string(),
+        content: r#"<p className="text-gray-400">a</p><p className="text-gray-900">b</p>"#.to_string(),
+        base_context: None,
+        ancestors: None,
+    };
+    let violations = audit_snippets(&theme, &[snippet]);
+    let found: Vec<&str> = violations.iter().map(|v| v.text_class.as_str()).collect();
+    expect("violations", found, vec!["text-gray-400"])?;
+    expect_near("ratio", violations[0].ratio, 2.54, 0.01)
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Run every built-in case.
+pub fn self_test() -> SelfTestReport {
+    let cases: Vec<SelfTestCase> = CASES
+        .iter()
+        .map(|(name, case)| {
+            let outcome = panic::catch_unwind(AssertUnwindSafe(case))
+                .unwrap_or_else(|payload| Err(format!("panicked: {}", panic_message(payload.as_ref()))));
+            SelfTestCase { name: name.to_string(), passed: outcome.is_ok(), detail: outcome.err() }
+        })
+        .collect();
+    SelfTestReport {
+        passed: cases.iter().all(|c| c.passed),
+        engine_version: env!("CARGO_PKG_VERSION").to_string(),
+        platform: format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS),
+        cases,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_suite_passes() {
+        let report = self_test();
+        let failures: Vec<_> = report.cases.iter().filter(|c| !c.passed).collect();
+        assert!(failures.is_empty(), "{:?}", failures);
+        assert!(report.passed);
+        assert_eq!(report.cases.len(), CASES.len());
+    }
+
+    #[test]
+    fn failures_and_panics_are_reported() {
+        assert_eq!(expect("x", 1, 2).unwrap_err(), "x: expected 2, got 1");
+        let payload = panic::catch_unwind(|| panic!("boom")).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "boom");
+    }
+}
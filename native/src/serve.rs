@@ -0,0 +1,242 @@
+//! Read-only JSON API over the live results of a theme's indexed files, so
+//! dashboards can poll a long-running (watch mode / editor) process instead
+//! of waiting for a results file.
+//!
+//! Every request takes a fresh `snapshot`, so answers follow re-indexing and
+//! config reloads. Routes (GET only, JSON bodies):
+//! - `/summary`: file and result counts
+//! - `/results?status=violation`: flattened result rows (see `export`),
+//!   optionally of one status
+//! - `/files`: indexed paths with their violation counts
+//! - `/file?path=src/App.tsx`: result rows of one indexed file
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::export;
+use crate::snapshot::snapshot;
+use crate::types::ResultSnapshot;
+
+/// Running servers by theme handle: bound address and stop flag.
+type Servers = HashMap<u32, (SocketAddr, Arc<AtomicBool>)>;
+
+fn servers() -> &'static Mutex<Servers> {
+    static SERVERS: OnceLock<Mutex<Servers>> = OnceLock::new();
+    SERVERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Serve the results of theme `handle` on `address` ("127.0.0.1:0" picks a
+/// free port) from a background thread. Returns the bound address; a second
+/// call for the same handle returns the running server's address.
+pub fn start(handle: u32, address: &str) -> std::io::Result<SocketAddr> {
+    let mut servers = servers().lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((addr, _)) = servers.get(&handle) {
+        return Ok(*addr);
+    }
+    let listener = TcpListener::bind(address)?;
+    let addr = listener.local_addr()?;
+    let stop = Arc::new(AtomicBool::new(false));
+    servers.insert(handle, (addr, Arc::clone(&stop)));
+    std::thread::spawn(move || {
+        log::info!("serving results of theme {} on http://{}", handle, addr);
+        for stream in listener.incoming() {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+            match stream {
+                Ok(stream) => {
+                    if let Err(err) = respond(handle, stream) {
+                        log::debug!("results API request failed: {}", err);
+                    }
+                }
+                Err(err) => log::warn!("results API accept failed: {}", err),
+            }
+        }
+        log::info!("stopped serving results of theme {}", handle);
+    });
+    Ok(addr)
+}
+
+/// Stop the server of theme `handle`. Returns false if none was running.
+pub fn stop(handle: u32) -> bool {
+    let Some((addr, stop)) = servers().lock().unwrap_or_else(|e| e.into_inner()).remove(&handle) else {
+        return false;
+    };
+    stop.store(true, Ordering::Relaxed);
+    // Wake the accept loop so it sees the flag
+    let _ = TcpStream::connect_timeout(&addr, Duration::from_secs(1));
+    true
+}
+
+fn respond(handle: u32, mut stream: TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let (status, body) = if method != "GET" {
+        (405, json!({ "error": "only GET is supported" }))
+    } else {
+        match snapshot(handle) {
+            Some(snapshot) => route(&snapshot, target),
+            None => (410, json!({ "error": format!("theme {} is no longer registered", handle) })),
+        }
+    };
+    let body = body.to_string();
+    let reason = match status {
+        200 => "OK",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Gone",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )
+}
+
+/// Status code and body for GET `target` (path and query).
+fn route(snapshot: &ResultSnapshot, target: &str) -> (u16, Value) {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let param = |name: &str| {
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| percent_decode(value))
+    };
+    let rows = export::rows(&snapshot.results);
+    match path {
+        "/summary" => (
+            200,
+            json!({
+                "theme_handle": snapshot.theme_handle,
+                "files": snapshot.files.len(),
+                "violations": snapshot.results.violations.len(),
+                "passed": snapshot.results.passed.len(),
+                "ignored": snapshot.results.ignored.len(),
+                "skipped": snapshot.skipped.len(),
+            }),
+        ),
+        "/results" => {
+            let status = param("status");
+            let rows = rows.into_iter().filter(|(s, _)| status.as_deref().is_none_or(|status| *s == status));
+            (200, export::to_json_rows(rows))
+        }
+        "/files" => {
+            let files: Vec<Value> = snapshot
+                .files
+                .iter()
+                .map(|file| {
+                    let violations = snapshot.results.violations.iter().filter(|r| r.file == *file).count();
+                    json!({ "path": file, "violations": violations })
+                })
+                .collect();
+            (200, Value::Array(files))
+        }
+        "/file" => match param("path") {
+            Some(file) if snapshot.files.contains(&file) => {
+                (200, export::to_json_rows(rows.into_iter().filter(|(_, r)| r.file == file)))
+            }
+            Some(file) => (404, json!({ "error": format!("{} is not indexed", file) })),
+            None => (404, json!({ "error": "missing ?path=" })),
+        },
+        _ => (404, json!({ "error": format!("unknown route {}", path) })),
+    }
+}
+
+/// Decode `%XX` escapes and `+` (space) of a query value.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok());
+        match (bytes[i], hex.and_then(|h| u8::from_str_radix(h, 16).ok())) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+            (b'+', _) => out.push(b' '),
+            (byte, _) => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::region_index;
+    use crate::theme::{self, Theme};
+    use crate::types::{ColorMapEntry, Snippet, ThemeConfig};
+    use std::io::Read;
+
+    fn get(addr: SocketAddr, target: &str) -> (String, Value) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", target).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        (head.lines().next().unwrap().to_string(), serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    fn serves_live_results() {
+        let colors = [("--color-background", "#ffffff"), ("--color-gray-400", "#9ca3af"), ("--color-black", "#000000")];
+        let config = ThemeConfig {
+            color_map: colors
+                .iter()
+                .map(|(name, hex)| ColorMapEntry { name: name.to_string(), hex: hex.to_string(), alpha: None })
+                .collect(),
+            default_bg: "bg-background".to_string(),
+            ..Default::default()
+        };
+        let handle = theme::register(Theme::from_config(&config));
+        let theme = theme::get(handle).unwrap();
+        let snippet = |path: &str, content: &str| Snippet {
+            path: path.to_string(),
+            content: content.to_string(),
+            base_context: None,
+            ancestors: None,
+        };
+        region_index::file_index(handle, &theme, &snippet("src/My App.tsx", r#"<p className="text-gray-400">a</p>"#));
+        region_index::file_index(handle, &theme, &snippet("src/B.tsx", r#"<p className="text-black">b</p>"#));
+
+        let addr = start(handle, "127.0.0.1:0").unwrap();
+        assert_eq!(start(handle, "127.0.0.1:0").unwrap(), addr);
+        let (status, summary) = get(addr, "/summary");
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        assert_eq!(summary["files"], 2);
+        assert_eq!(summary["violations"], 1);
+
+        let (_, violations) = get(addr, "/results?status=violation");
+        assert_eq!(violations.as_array().unwrap().len(), 1);
+        assert_eq!(violations[0]["text_class"], "text-gray-400");
+        let (_, file) = get(addr, "/file?path=src%2FMy+App.tsx");
+        assert_eq!(file[0]["file"], "src/My App.tsx");
+        let (status, _) = get(addr, "/file?path=src/C.tsx");
+        assert_eq!(status, "HTTP/1.1 404 Not Found");
+
+        region_index::file_index(handle, &theme, &snippet("src/C.tsx", r#"<p className="text-gray-400">c</p>"#));
+        let (_, files) = get(addr, "/files");
+        assert_eq!(files.as_array().unwrap().len(), 3);
+
+        assert!(stop(handle));
+        assert!(!stop(handle));
+        region_index::release(handle);
+        theme::release(handle);
+    }
+}
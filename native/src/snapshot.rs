@@ -0,0 +1,84 @@
+//! Immutable point-in-time results for a theme's indexed files.
+//!
+//! The theme and its file indexes are captured together under the index
+//! lock and checked after it is released, so a workspace-diagnostic request
+//! neither blocks concurrent re-indexing or config reloads nor observes half
+//! of one.
+
+use crate::math::checker::check_all_pairs;
+use crate::pairs::region_resolver::{resolve_regions, ResolvedPairs};
+use crate::region_index;
+use crate::remediation::attach_remediation;
+use crate::types::{CheckResultJs, ResultSnapshot};
+
+/// Results of every file indexed under theme `handle` as of this call.
+/// None for unknown handles.
+pub fn snapshot(handle: u32) -> Option<ResultSnapshot> {
+    let (theme, files) = region_index::capture(handle)?;
+
+    let mut resolved = ResolvedPairs::default();
+    for (path, index) in &files {
+        resolve_regions(path, index.regions(), &theme.color_map, &theme.theme_mode, &mut resolved);
+    }
+    let mut results = CheckResultJs::from(check_all_pairs(&resolved.pairs, &theme.threshold, &theme.page_bg));
+    attach_remediation(&mut results.violations, &theme.color_map, &theme.threshold, &theme.page_bg, theme.scan_config.locale);
+
+    Some(ResultSnapshot {
+        theme_handle: handle,
+        files: files.into_iter().map(|(path, _)| path).collect(),
+        results,
+        skipped: resolved.skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::{self, Theme};
+    use crate::types::{ColorMapEntry, Snippet, ThemeConfig};
+
+    fn snippet(path: &str, content: &str) -> Snippet {
+        Snippet { path: path.to_string(), content: content.to_string(), base_context: None, ancestors: None }
+    }
+
+    fn theme_config(gray_400: &str) -> ThemeConfig {
+        let colors = [("--color-background", "#ffffff"), ("--color-gray-400", gray_400), ("--color-black", "#000000")];
+        ThemeConfig {
+            color_map: colors
+                .iter()
+                .map(|(name, hex)| ColorMapEntry { name: name.to_string(), hex: hex.to_string(), alpha: None })
+                .collect(),
+            default_bg: "bg-background".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn snapshots_are_unaffected_by_later_updates() {
+        let handle = theme::register(Theme::from_config(&theme_config("#9ca3af")));
+        let theme = theme::get(handle).unwrap();
+        region_index::file_index(handle, &theme, &snippet("src/B.tsx", r#"<p className="text-black">b</p>"#));
+        region_index::file_index(handle, &theme, &snippet("src/A.tsx", r#"<p className="text-gray-400">a</p><i className="text-blue">i</i>"#));
+
+        let before = snapshot(handle).unwrap();
+        crate::reload::update_config(handle, &theme_config("#4b5563")).unwrap();
+        region_index::file_index(handle, &theme, &snippet("src/C.tsx", r#"<p className="text-gray-400">c</p>"#));
+        let after = snapshot(handle).unwrap();
+
+        assert_eq!(before.files, vec!["src/A.tsx", "src/B.tsx"]);
+        assert_eq!(before.results.violations.len(), 1);
+        assert!(before.results.violations[0].remediation.is_some());
+        assert_eq!(before.results.passed.len(), 1);
+        assert_eq!(before.skipped[0].class_name, "text-blue");
+
+        assert_eq!(after.files.len(), 3);
+        assert!(after.results.violations.is_empty());
+        region_index::release(handle);
+        theme::release(handle);
+    }
+
+    #[test]
+    fn unknown_handle() {
+        assert!(snapshot(u32::MAX).is_none());
+    }
+}
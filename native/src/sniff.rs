@@ -0,0 +1,139 @@
+//! Content sniffing for JSX inside plain `.js` files.
+//!
+//! Legacy codebases often keep JSX in `.js`/`.mjs`/`.cjs` files. With a broad
+//! glob (e.g. `src/**/*.{js,jsx,tsx}`), sniffing keeps the JSX ones and drops
+//! plain modules (configs, utilities, bundles) without an extension allowlist.
+
+/// Extensions whose files are sniffed; every other extension is always scanned.
+const SNIFFED_EXTENSIONS: &[&str] = &[".js", ".mjs", ".cjs"];
+
+/// Modules whose import marks a file as a JSX candidate.
+const JSX_RUNTIME_MODULES: &[&str] = &["react", "preact", "solid-js", "react/jsx-runtime"];
+
+/// Modules whose import marks a file as carrying classes in `tw` templates.
+const CLASS_TEMPLATE_MODULES: &[&str] = &["twin.macro"];
+
+/// Whether `path` needs sniffing before it is scanned.
+pub fn is_sniffed_path(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    SNIFFED_EXTENSIONS.iter().any(|ext| lower.ends_with(ext))
+}
+
+/// Heuristic: does `source` contain JSX?
+///
+/// - a `@jsx` / `@jsxImportSource` / `@jsxRuntime` pragma → yes
+/// - a tag in expression position (`return <div`, `=> <Foo`, `(<>`) → yes
+/// - an import/require of a JSX runtime plus any tag-like `<Name ...>` → yes
+/// - an import/require of twin.macro (`tw` templates without JSX) → yes
+pub fn looks_like_jsx(source: &str) -> bool {
+    if source.contains("@jsx") || imports_any(source, CLASS_TEMPLATE_MODULES) {
+        return true;
+    }
+    let (any_tag, expression_tag) = scan_tags(source);
+    expression_tag || (any_tag && imports_any(source, JSX_RUNTIME_MODULES))
+}
+
+fn imports_any(source: &str, modules: &[&str]) -> bool {
+    modules.iter().any(|m| {
+        [
+            format!("from '{}'", m),
+            format!("from \"{}\"", m),
+            format!("require('{}')", m),
+            format!("require(\"{}\")", m),
+        ]
+        .iter()
+        .any(|pattern| source.contains(pattern.as_str()))
+    })
+}
+
+/// (saw a tag-like `<` anywhere, saw one in expression position)
+fn scan_tags(source: &str) -> (bool, bool) {
+    let bytes = source.as_bytes();
+    let mut any = false;
+    for (i, &b) in bytes.iter().enumerate() {
+        if b != b'<' {
+            continue;
+        }
+        let next = bytes.get(i + 1).copied().unwrap_or(b' ');
+        // <div, <Foo, <> (fragment)
+        if !(next.is_ascii_alphabetic() || next == b'>') {
+            continue;
+        }
+        // `a<b` / `Array<T>` are comparisons or generics, not tags
+        let prev = bytes[..i].iter().rev().find(|c| !c.is_ascii_whitespace()).copied();
+        if prev.is_some_and(|c| c.is_ascii_alphanumeric() || c == b'_' || c == b'$' || c == b')' || c == b']') {
+            if preceded_by_keyword(&bytes[..i]) {
+                return (true, true);
+            }
+            continue;
+        }
+        any = true;
+        if matches!(prev, Some(b'(' | b'=' | b'>' | b'?' | b':' | b'&' | b'|' | b',' | b'[' | b'{' | b'}')) {
+            return (true, true);
+        }
+    }
+    (any, false)
+}
+
+/// `return <div>` / `yield <Item>`: keyword directly before the tag.
+fn preceded_by_keyword(before: &[u8]) -> bool {
+    let trimmed = before.trim_ascii_end();
+    ["return", "yield", "default"].iter().any(|kw| {
+        // A keyword at the very start has nothing before it
+        trimmed.ends_with(kw.as_bytes())
+            && (trimmed.len() - kw.len())
+                .checked_sub(1)
+                .and_then(|at| trimmed.get(at))
+                .is_none_or(|c| !(c.is_ascii_alphanumeric() || *c == b'_' || *c == b'$'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sniffed_extensions() {
+        assert!(is_sniffed_path("src/App.js"));
+        assert!(is_sniffed_path("lib/x.MJS"));
+        assert!(!is_sniffed_path("src/App.jsx"));
+        assert!(!is_sniffed_path("src/App.tsx"));
+    }
+
+    #[test]
+    fn pragma_is_jsx() {
+        assert!(looks_like_jsx("/** @jsx h */\nexport default x;"));
+    }
+
+    #[test]
+    fn returned_tag_is_jsx() {
+        assert!(looks_like_jsx("export function App() {\n  return <div className=\"p-4\" />;\n}"));
+        assert!(looks_like_jsx("const App = () => (\n  <Card>x</Card>\n);"));
+        assert!(looks_like_jsx("const el = cond ? <b /> : null;"));
+        assert!(looks_like_jsx("render(<>hi</>);"));
+    }
+
+    #[test]
+    fn keyword_at_start_of_file() {
+        assert!(looks_like_jsx("return <>{children}</>; }"));
+    }
+
+    #[test]
+    fn react_import_with_tag_is_jsx() {
+        let src = "import React from 'react';\nfunction f() { if (x) return null; }\nconst el =\n<div />;";
+        assert!(looks_like_jsx(src));
+    }
+
+    #[test]
+    fn twin_import_is_scanned() {
+        assert!(looks_like_jsx("import tw from 'twin.macro';\nexport const Title = tw.h1`text-gray-900`;"));
+    }
+
+    #[test]
+    fn plain_modules_are_not_jsx() {
+        assert!(!looks_like_jsx("module.exports = { plugins: [] };"));
+        assert!(!looks_like_jsx("for (let i = 0; i<len; i++) { if (a < b) {} }"));
+        assert!(!looks_like_jsx("import { useState } from 'react';\nexport const useX = () => useState(0);"));
+        assert!(!looks_like_jsx("const returned = value <limit;"));
+    }
+}
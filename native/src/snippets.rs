@@ -0,0 +1,129 @@
+//! Pre-commit fast path: audit small in-memory snippets against a registered
+//! theme and return only violations.
+//!
+//! Inputs are tiny (staged files, single components), so snippets are
+//! processed sequentially: thread-pool dispatch would cost more than it saves.
+
+use std::borrow::Cow;
+
+use crate::math::checker::check_all_pairs;
+use crate::pairs::region_resolver::{resolve_regions, ResolvedPairs};
+use crate::parser::{scan_file_with_config, ScanConfig};
+use crate::remediation::attach_remediation;
+use crate::theme::Theme;
+use crate::types::{ContrastResult, Snippet};
+
+/// Scan, resolve and check every snippet; violations only, in snippet order.
+pub fn audit_snippets(theme: &Theme, snippets: &[Snippet]) -> Vec<ContrastResult> {
+    let mut resolved = ResolvedPairs::default();
+
+    for snippet in snippets {
+        let config = snippet_config(&theme.scan_config, snippet);
+        let regions = scan_file_with_config(&snippet.content, &config);
+        resolve_regions(&snippet.path, &regions, &theme.color_map, &theme.theme_mode, &mut resolved);
+    }
+
+    let mut violations = check_all_pairs(&resolved.pairs, &theme.threshold, &theme.page_bg).violations;
+    attach_remediation(&mut violations, &theme.color_map, &theme.threshold, &theme.page_bg, theme.scan_config.locale);
+    violations
+}
+
+/// Theme scan config, with the snippet's `base_context` as the starting bg and
+/// its synthetic ancestors seeded on top.
+pub(crate) fn snippet_config<'a>(base: &'a ScanConfig, snippet: &Snippet) -> Cow<'a, ScanConfig> {
+    let base_context = snippet.base_context.as_ref().filter(|bg| !bg.is_empty());
+    let ancestors = snippet.ancestors.as_ref().filter(|a| !a.is_empty());
+    if base_context.is_none() && ancestors.is_none() {
+        return Cow::Borrowed(base);
+    }
+    Cow::Owned(ScanConfig {
+        default_bg: base_context.cloned().unwrap_or_else(|| base.default_bg.clone()),
+        ancestors: ancestors.cloned().unwrap_or_default(),
+        ..base.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ColorMapEntry, ContainerEntry, ThemeConfig};
+
+    fn theme() -> Theme {
+        let colors = [
+            ("--color-background", "#ffffff"),
+            ("--color-dark", "#111111"),
+            ("--color-card", "#ffffff"),
+            ("--color-gray-300", "#d1d5db"),
+            ("--color-black", "#000000"),
+            ("--color-white", "#ffffff"),
+        ];
+        Theme::from_config(&ThemeConfig {
+            color_map: colors
+                .iter()
+                .map(|(name, hex)| ColorMapEntry {
+                    name: name.to_string(),
+                    hex: hex.to_string(),
+                    alpha: None,
+                })
+                .collect(),
+            container_config: vec![ContainerEntry {
+                component: "Card".to_string(),
+                bg_class: "bg-card".to_string(),
+            }],
+            default_bg: "bg-background".to_string(),
+            ..Default::default()
+        })
+    }
+
+    fn snippet(content: &str, base_context: Option<&str>) -> Snippet {
+        Snippet {
+            path: "src/Button.tsx".to_string(),
+            content: content.to_string(),
+            base_context: base_context.map(str::to_string),
+            ancestors: None,
+        }
+    }
+
+    #[test]
+    fn returns_only_violations() {
+        let violations = audit_snippets(
+            &theme(),
+            &[snippet(r##"<p className="text-black">ok</p><p className="text-gray-300">bad</p>"##, None)],
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].text_class, "text-gray-300");
+        assert_eq!(violations[0].file, "src/Button.tsx");
+    }
+
+    #[test]
+    fn base_context_replaces_default_bg() {
+        let s = snippet(r##"<p className="text-white">x</p>"##, Some("bg-dark"));
+        assert!(audit_snippets(&theme(), &[s]).is_empty());
+        let s = snippet(r##"<p className="text-white">x</p>"##, None);
+        assert_eq!(audit_snippets(&theme(), &[s]).len(), 1);
+    }
+
+    #[test]
+    fn container_config_applies_inside_snippet() {
+        let s = snippet(r##"<Card><p className="text-white">x</p></Card>"##, Some("bg-dark"));
+        let violations = audit_snippets(&theme(), &[s]);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].bg_class, "(implicit) bg-card");
+    }
+
+    #[test]
+    fn ancestor_stack_sets_snippet_context() {
+        use crate::types::AncestorContext;
+        // "renders inside Card inside a dark section"
+        let mut s = snippet(r##"<p className="text-white">x</p>"##, None);
+        s.ancestors = Some(vec![
+            AncestorContext { bg_class: Some("bg-dark".to_string()), ..Default::default() },
+            AncestorContext { component: Some("Card".to_string()), ..Default::default() },
+        ]);
+        let violations = audit_snippets(&theme(), &[s.clone()]);
+        assert_eq!(violations[0].bg_class, "(implicit) bg-card");
+        // dark section alone: white text passes
+        s.ancestors.as_mut().unwrap().pop();
+        assert!(audit_snippets(&theme(), &[s]).is_empty());
+    }
+}
@@ -0,0 +1,176 @@
+//! Registered theme handles.
+//!
+//! A theme bundles everything a native audit needs besides source text:
+//! resolved color map, container/portal config, theme mode and threshold.
+//! JS registers it once and passes the handle to later calls, so hot paths
+//! (pre-commit snippets, watch mode) skip re-sending and re-hashing config.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::i18n::Locale;
+use crate::pairs::class_resolver::ColorMap;
+use crate::parser::constants::Library;
+use crate::parser::{css_modules, ScanConfig};
+use crate::types::{ResolvedColor, ThemeConfig};
+
+/// Fallback page backgrounds for alpha compositing (same as the TS checker).
+pub const PAGE_BG_LIGHT: &str = "#ffffff";
+pub const PAGE_BG_DARK: &str = "#09090b";
+
+/// A theme ready for native audits.
+#[derive(Debug, Clone, Default)]
+pub struct Theme {
+    pub color_map: ColorMap,
+    pub scan_config: ScanConfig,
+    /// "light" | "dark"
+    pub theme_mode: String,
+    /// "AA" | "AAA"
+    pub threshold: String,
+    pub page_bg: String,
+    /// Version of `scan_config` regions indexed for this theme were scanned
+    /// with: a reload that keeps the scan config keeps it (see `reload`)
+    pub generation: u64,
+}
+
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+impl Theme {
+    pub fn from_config(config: &ThemeConfig) -> Self {
+        let to_map = |entries: &[crate::types::ContainerEntry]| -> HashMap<String, String> {
+            entries
+                .iter()
+                .map(|e| (e.component.clone(), e.bg_class.clone()))
+                .collect()
+        };
+        let theme_mode = config.theme_mode.clone().unwrap_or_else(|| "light".to_string());
+        let page_bg = config.page_bg.clone().unwrap_or_else(|| {
+            if theme_mode == "dark" { PAGE_BG_DARK } else { PAGE_BG_LIGHT }.to_string()
+        });
+        Self {
+            color_map: config
+                .color_map
+                .iter()
+                .map(|e| {
+                    (
+                        e.name.clone(),
+                        ResolvedColor {
+                            hex: e.hex.clone(),
+                            alpha: e.alpha,
+                        },
+                    )
+                })
+                .collect(),
+            scan_config: ScanConfig {
+                container_config: to_map(&config.container_config),
+                portal_config: to_map(&config.portal_config),
+                default_bg: config.default_bg.clone(),
+                named_contexts: crate::parser::named_contexts(config.named_contexts.as_deref()),
+                locale: Locale::parse(config.locale.as_deref()),
+                ancestors: Vec::new(),
+                dedup_regions: false,
+                rules: Vec::new(),
+                class_attributes: config.class_attributes.clone().unwrap_or_default(),
+                class_functions: config.class_functions.clone().unwrap_or_default(),
+                css_variables: HashMap::new(),
+                css_modules: css_modules::build_map(config.css_modules.as_deref().unwrap_or_default()),
+                library: Library::from_files(config.library_files.as_deref().unwrap_or_default()),
+                dark_variants: false,
+                standalone_classes: Default::default(),
+                breakpoint_variants: false,
+                state_variants: false,
+                annotation_max_distance: None,
+            },
+            theme_mode,
+            threshold: config.threshold.clone().unwrap_or_else(|| "AA".to_string()),
+            page_bg,
+            generation: NEXT_GENERATION.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+}
+
+fn registry() -> &'static Mutex<HashMap<u32, Arc<Theme>>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<u32, Arc<Theme>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+static NEXT_HANDLE: AtomicU32 = AtomicU32::new(1);
+
+/// Register a theme and return its handle (never 0).
+pub fn register(theme: Theme) -> u32 {
+    let handle = NEXT_HANDLE.fetch_add(1, Ordering::Relaxed);
+    registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(handle, Arc::new(theme));
+    handle
+}
+
+/// Theme registered under `handle`, if still registered.
+pub fn get(handle: u32) -> Option<Arc<Theme>> {
+    registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&handle)
+        .cloned()
+}
+
+/// Swap the theme registered under `handle` in one step, so no call sees a
+/// mix of old and new config. Returns the previous theme (None = unknown handle).
+pub fn replace(handle: u32, theme: Theme) -> Option<Arc<Theme>> {
+    let mut registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+    let slot = registry.get_mut(&handle)?;
+    Some(std::mem::replace(slot, Arc::new(theme)))
+}
+
+/// Drop a registered theme. Returns false for unknown handles.
+pub fn release(handle: u32) -> bool {
+    registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&handle)
+        .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ColorMapEntry;
+
+    #[test]
+    fn config_defaults() {
+        let theme = Theme::from_config(&ThemeConfig {
+            color_map: vec![ColorMapEntry {
+                name: "--color-card".to_string(),
+                hex: "#f4f4f5".to_string(),
+                alpha: None,
+            }],
+            default_bg: "bg-background".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(theme.threshold, "AA");
+        assert_eq!(theme.page_bg, PAGE_BG_LIGHT);
+        assert_eq!(theme.color_map["--color-card"].hex, "#f4f4f5");
+        assert_eq!(theme.scan_config.default_bg, "bg-background");
+    }
+
+    #[test]
+    fn dark_mode_uses_dark_page_bg() {
+        let theme = Theme::from_config(&ThemeConfig {
+            theme_mode: Some("dark".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(theme.page_bg, PAGE_BG_DARK);
+    }
+
+    #[test]
+    fn register_get_release() {
+        let handle = register(Theme::default());
+        assert_ne!(handle, 0);
+        assert!(get(handle).is_some());
+        assert!(release(handle));
+        assert!(get(handle).is_none());
+        assert!(!release(handle));
+    }
+}
@@ -1,26 +1,95 @@
 use napi_derive::napi;
+use serde::Serialize;
 
 /// Equivalent of TypeScript ClassRegion (src/core/types.ts)
 #[napi(object)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ClassRegion {
     pub content: String,
     pub start_line: u32,
     pub context_bg: String,
     pub inline_color: Option<String>,
     pub inline_background_color: Option<String>,
+    /// Inline `borderColor` / `border-color` (non-text pair, SC 1.4.11)
+    pub inline_border_color: Option<String>,
+    /// Inline `outlineColor` / `outline-color` (non-text pair, SC 1.4.11)
+    pub inline_outline_color: Option<String>,
+    /// Inline `textDecorationColor` / `text-decoration-color`
+    pub inline_text_decoration_color: Option<String>,
     pub context_override_bg: Option<String>,
     pub context_override_fg: Option<String>,
     pub context_override_no_inherit: Option<bool>,
     pub ignored: Option<bool>,
     pub ignore_reason: Option<String>,
+    /// Rule ids a scoped `a11y-ignore AA-TEXT-CONTRAST: ...` suppresses (None = every rule)
+    pub ignored_rules: Option<Vec<String>>,
     /// US-05: cumulative opacity from ancestor containers (0.0-1.0). None = fully opaque.
     pub effective_opacity: Option<f64>,
+    /// Identical regions (content, context, tag) folded into this one when
+    /// `ExtractOptions.dedup_regions` is set; None when dedup is off
+    pub occurrences: Option<u32>,
+    /// The element is a required-field marker (`*` text or `content-['*']`)
+    pub required_marker: Option<bool>,
+    /// Static `role` attribute of the element
+    pub role: Option<String>,
+    /// `cva()` variant branch the classes render in, e.g. "intent=destructive",
+    /// or variant map entry (`className={variants[variant]}`), e.g. "variant=primary"
+    pub variant_key: Option<String>,
+    /// One branch of a ternary class expression (`cond ? "a" : "b"`)
+    pub conditional: Option<bool>,
+    /// The element's classes can't be statically known (`{...props}` spread or
+    /// `className={props.className}`); may be an empty synthetic region
+    pub unresolved_dynamic_class: Option<bool>,
+    /// Last line of the class value when it wraps past `start_line`
+    /// (None = single line)
+    pub end_line: Option<u32>,
+    /// Tag or component the classes are on, e.g. "Button" or "p" (empty for
+    /// standalone `cn()`/`cva()` calls)
+    pub tag_name: String,
+    /// Enclosing tags and components, outermost first, ending with the
+    /// element itself, e.g. ["Card", "CardContent", "p"]
+    pub ancestry: Vec<String>,
+    /// Background behind the element while its `group` ancestor is hovered:
+    /// the group's `hover:bg-*`, else the element's context (set only for
+    /// `group-hover:` classes with a matching `group` ancestor)
+    pub group_hover_bg: Option<String>,
+    /// The element's `peer-hover:` / `peer-focus-visible:` classes follow a
+    /// matching `peer` sibling
+    pub peer_state: Option<bool>,
+    /// "dark" = twin of the region before it, rendered in dark mode (its
+    /// `context_bg` follows `dark:bg-*` ancestors); "light" = a region with
+    /// such a twin (see `ExtractOptions.dark_variants`)
+    pub theme: Option<String>,
+    /// How the classes were captured: "attribute" | "cn" | "template" |
+    /// "standalone" | "cva"
+    pub provenance: Option<String>,
+    /// Twin of the region before it, rendered at this breakpoint and up:
+    /// "sm" | "md" | "lg" | "xl" | "2xl" (see `ExtractOptions.breakpoint_variants`)
+    pub breakpoint: Option<String>,
+    /// Twin of the region before it, rendered in this state: "hover" |
+    /// "focus" | "focus-visible" | "active", an `aria-*` state or an attribute
+    /// variant such as "data-[state=open]" (see `ExtractOptions.state_variants`)
+    pub interactive_state: Option<String>,
+    /// Where each class of a `cn()` / `clsx()` call sits, by argument (None
+    /// for other sources)
+    pub class_locations: Option<Vec<ClassLocation>>,
+}
+
+/// Position of one class token inside a `cn()` / `clsx()` call
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassLocation {
+    pub class_name: String,
+    /// 0-based index of the call argument holding the class
+    pub argument: u32,
+    /// 1-based line and column of the class's first character
+    pub line: u32,
+    pub column: u32,
 }
 
 /// Equivalent of TypeScript ResolvedColor
 #[napi(object)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ResolvedColor {
     pub hex: String,
     pub alpha: Option<f64>,
@@ -28,7 +97,7 @@ pub struct ResolvedColor {
 
 /// Equivalent of TypeScript ColorPair
 #[napi(object)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ColorPair {
     pub file: String,
     pub line: u32,
@@ -41,10 +110,13 @@ pub struct ColorPair {
     pub is_large_text: Option<bool>,
     /// "text" | "border" | "ring" | "outline"
     pub pair_type: Option<String>,
-    /// "hover" | "focus-visible" | "aria-disabled"
+    /// "hover" | "focus" | "focus-visible" | "active" | "aria-disabled", or a
+    /// state twin's `aria-*` / `data-[...]` variant
     pub interactive_state: Option<String>,
     pub ignored: Option<bool>,
     pub ignore_reason: Option<String>,
+    /// Rule ids the suppression is scoped to (see `ClassRegion.ignored_rules`)
+    pub ignore_rules: Option<Vec<String>>,
     /// "inferred" | "annotation"
     pub context_source: Option<String>,
     /// US-05 (Phase 3, pre-wired)
@@ -53,6 +125,51 @@ pub struct ColorPair {
     pub is_disabled: Option<bool>,
     /// US-08: text-current/border-current that couldn't be resolved
     pub unresolved_current_color: Option<bool>,
+    /// Text pair of a required-field marker (see `ClassRegion.required_marker`)
+    pub required_marker: Option<bool>,
+    /// Tag or component the classes are on, e.g. "h2" or "Label"
+    pub tag_name: Option<String>,
+    /// ARIA `role` of the element, e.g. "heading"
+    pub role: Option<String>,
+    /// Last line of a class value that wraps past `line` (see `ClassRegion.end_line`)
+    pub end_line: Option<u32>,
+    /// Enclosing tag path, e.g. ["Card", "CardContent", "p"] (see `ClassRegion.ancestry`)
+    pub ancestry: Option<Vec<String>>,
+    /// Multiplier on every contrast threshold (see `CheckOptions.safety_margin`)
+    pub safety_margin: Option<f64>,
+    /// "dark" = pair of a dark-mode twin region (see `ClassRegion.theme`)
+    pub theme: Option<String>,
+    /// How the region's classes were captured (see `ClassRegion.provenance`)
+    pub provenance: Option<String>,
+    /// Breakpoint the pair renders at and up (see `ClassRegion.breakpoint`)
+    pub breakpoint: Option<String>,
+}
+
+#[cfg(test)]
+impl ColorPair {
+    /// Test pair: `text_class` (`text_hex`) on `bg-white` in `a.tsx`, line 1.
+    /// Override other fields with struct update syntax.
+    pub(crate) fn fixture(text_class: &str, text_hex: &str) -> Self {
+        ColorPair {
+            file: "a.tsx".to_string(),
+            line: 1,
+            bg_class: "bg-white".to_string(),
+            text_class: text_class.to_string(),
+            bg_hex: Some("#ffffff".to_string()),
+            text_hex: Some(text_hex.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Equivalent of TypeScript SkippedClass
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SkippedClass {
+    pub file: String,
+    pub line: u32,
+    pub class_name: String,
+    pub reason: String,
 }
 
 /// Equivalent of TypeScript ContrastResult (flattened — NAPI doesn't support struct inheritance)
@@ -73,6 +190,7 @@ pub struct ContrastResult {
     pub interactive_state: Option<String>,
     pub ignored: Option<bool>,
     pub ignore_reason: Option<String>,
+    pub ignore_rules: Option<Vec<String>>,
     pub context_source: Option<String>,
     pub effective_opacity: Option<f64>,
     pub is_disabled: Option<bool>,
@@ -84,52 +202,998 @@ pub struct ContrastResult {
     pub pass_aaa: bool,
     pub pass_aaa_large: bool,
     pub apca_lc: Option<f64>,
+    /// Polarity, use-case band and readable font sizes at the measured Lc
+    pub apca: Option<ApcaInfo>,
     /// Phase 5 (pre-wired)
     pub deuteranopia_ratio: Option<f64>,
     /// Phase 5 (pre-wired)
     pub protanopia_ratio: Option<f64>,
+    /// Ratio under achromatopsia (set only with `CheckOptions.vision_simulation`)
+    pub achromatopsia_ratio: Option<f64>,
+    /// `achromatopsia_ratio` still reaches 3:1
+    pub achromatopsia_distinguishable: Option<bool>,
+    /// Ratio under low-vision blur (set only with `CheckOptions.vision_simulation`)
+    pub low_vision_ratio: Option<f64>,
+    /// `low_vision_ratio` still reaches 3:1
+    pub low_vision_distinguishable: Option<bool>,
+    /// Stable id of the check, e.g. "AA-TEXT-CONTRAST" (see `criteria`)
+    pub rule_id: Option<String>,
+    /// WCAG success criteria this result is evidence for, primary first (e.g. ["1.4.3"]; None = untagged)
+    pub wcag_criteria: Option<Vec<String>>,
+    /// WCAG sufficient techniques for the check (e.g. ["G18"])
+    pub wcag_techniques: Option<Vec<String>>,
+    /// Whether the result's line was added/modified in the supplied diff (None = no diff given)
+    pub introduced_in_diff: Option<bool>,
+    /// Vendored package the file belongs to, as "name@version" (None = app code)
+    pub package: Option<String>,
+    /// Location-independent identity of the failure (see `fingerprint::fingerprint`)
+    pub fingerprint: Option<String>,
+    /// Stable id of the result, `<rule_id>-<hash>` derived from the fingerprint
+    /// and file (see `fingerprint::result_id`)
+    pub id: Option<String>,
+    /// Package whose own violation this result duplicates, e.g. "@ourorg/ui@1.2.3"
+    pub inherited_from: Option<String>,
+    /// EXPERIMENTAL: meets the WCAG 3 draft Lc minimum (set only with the "wcag3" flag)
+    pub wcag3_pass: Option<bool>,
+    /// Weber contrast (Lt - Lb) / Lb (set only with `CheckOptions.alternate_contrast`)
+    pub weber_contrast: Option<f64>,
+    /// Michelson contrast, 0-1 (set only with `CheckOptions.alternate_contrast`)
+    pub michelson_contrast: Option<f64>,
+    /// Text pair of a required-field marker (`*`)
+    pub required_marker: Option<bool>,
+    /// Evaluated at AAA by the strict heading/label profile (see `CheckOptions.strict_profile`)
+    pub strict_profile: Option<bool>,
+    /// Templated fix advice for a violation, e.g. "replace `text-gray-400` with
+    /// `text-gray-600` (ratio 7.56)" (see `remediation`)
+    pub remediation: Option<String>,
+    /// Last line of a class value that wraps past `line` (see `ClassRegion.end_line`)
+    pub end_line: Option<u32>,
+    /// Tag or component the classes are on, e.g. "Button"
+    pub tag_name: Option<String>,
+    /// Static `role` attribute of the element
+    pub role: Option<String>,
+    /// Enclosing tag path, e.g. ["Card", "CardContent", "p"] (see `ClassRegion.ancestry`)
+    pub ancestry: Option<Vec<String>>,
+    /// "error" | "warning" | "info" for a violation, from the weight of its
+    /// element (set only with `CheckOptions.severity_escalation`; see `math::severity`)
+    pub severity: Option<String>,
+    /// Threshold multiplier the pass/fail flags were evaluated with (None = nominal)
+    pub safety_margin: Option<f64>,
+    /// "dark" = pair of a dark-mode twin region (see `ClassRegion.theme`)
+    pub theme: Option<String>,
+    /// How the region's classes were captured (see `ClassRegion.provenance`)
+    pub provenance: Option<String>,
+    /// Breakpoint the pair renders at and up (see `ClassRegion.breakpoint`)
+    pub breakpoint: Option<String>,
+    /// "test" | "story" | "e2e" | "mock" for non-shipping files (see
+    /// `file_kinds::classify`; set by `apply_file_kind_policies`)
+    pub file_kind: Option<String>,
 }
 
-/// Configuration passed from JS to Rust
+/// APCA reporting metadata for one result
 #[napi(object)]
 #[derive(Debug, Clone)]
+pub struct ApcaInfo {
+    /// "BoW" (dark text on light bg) | "WoB" (light text on dark bg)
+    pub polarity: String,
+    /// "fluent" (Lc 90+) | "body" (75) | "content" (60) | "headline" (45) | "spot" (30) | "non-text" (15) | "invisible"
+    pub lc_band: String,
+    /// Smallest readable size per font weight at this Lc (text pairs only)
+    pub min_font_sizes: Vec<ApcaFontOption>,
+}
+
+/// Minimum font size for one weight
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ApcaFontOption {
+    /// CSS font-weight (100-900)
+    pub weight: u32,
+    pub size_px: f64,
+}
+
+/// Configuration passed from JS to Rust
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct ExtractOptions {
     pub file_contents: Vec<FileInput>,
+    /// Container component → bg class (wins over `@a11y-layout` annotations)
     pub container_config: Vec<ContainerEntry>,
     /// US-04: Portal components → bg class or "reset"
     pub portal_config: Vec<ContainerEntry>,
     pub default_bg: String,
+    /// Contexts elements opt into with `@a11y-context use:<name>`, leaving the
+    /// bg stack they are declared in (inline toasts, popovers)
+    pub named_contexts: Option<Vec<NamedContext>>,
+    /// When set, an OpenMetrics snapshot of the run is written to this path
+    pub metrics_path: Option<String>,
+    /// "off" | "error" | "warn" | "info" | "debug" | "trace" (falls back to A11Y_AUDIT_LOG env)
+    pub log_level: Option<String>,
+    /// Locale of generated report text, BCP 47 ("en" default, "it")
+    pub locale: Option<String>,
+    /// Fraction of files to audit (0 < sample < 1); unset or >= 1 scans everything
+    pub sample: Option<f64>,
+    /// Seed for `sample`; the same seed always selects the same files
+    pub seed: Option<u32>,
+    /// Paths changed in this CI run (repo-relative or absolute); scanned first
+    pub changed_paths: Option<Vec<String>>,
+    /// Only scan `changed_paths` (context config still applies in full)
+    pub changed_only: Option<bool>,
+    /// Skip .js/.mjs/.cjs files that don't look like JSX (pragma, react import, tags)
+    pub sniff_js: Option<bool>,
+    /// Fold identical regions within a file (same content, context and tag)
+    pub dedup_regions: Option<bool>,
+    /// Resource quotas; a run that hits one returns partial results
+    pub limits: Option<ScanLimits>,
+    /// Walk a directory natively; walked files are added to `file_contents`
+    pub walk: Option<WalkOptions>,
+    /// Non-contrast rules to run, e.g. ["use-of-color"] (see `rules::ALL_RULES`)
+    pub rules: Option<Vec<String>>,
+    /// Custom props holding Tailwind classes, extracted like `className`,
+    /// e.g. ["tw", "innerClassName", "containerClassName", "classes"]
+    pub class_attributes: Option<Vec<String>>,
+    /// Class-composition helpers extracted like `cn()` / `clsx()`, e.g.
+    /// ["classNames", "twMerge", "cx"]
+    pub class_functions: Option<Vec<String>>,
+    /// CSS custom properties for resolving `var(--token)` in inline styles
+    pub css_variables: Option<Vec<CssVariable>>,
+    /// Colors of CSS Modules classes, for `className={styles.card}` (see
+    /// `parse_css_module`); unmapped accesses stay unresolved dynamic classes
+    pub css_modules: Option<Vec<CssModuleClass>>,
+    /// Shared files (e.g. `src/styles.ts`) whose top-level class constants
+    /// and variant maps scanned files import; read for constants, not scanned
+    pub library_files: Option<Vec<FileInput>>,
+    /// Also emit a dark-mode twin of each region with `dark:` classes or a
+    /// `dark:bg-*` ancestor, so a light-theme run audits both renderings
+    pub dark_variants: Option<bool>,
+    /// Standalone `cn()` / `clsx()` / `cva()` calls outside class attributes:
+    /// "scan" (default) | "ignore" (reported as ignored) | "annotated" (only
+    /// after an `@a11y-context` annotation) | "skip"
+    pub standalone_classes: Option<String>,
+    /// Also emit a twin of each region with breakpoint color classes
+    /// (`md:bg-white`) per breakpoint, checked as rendered at that width
+    pub breakpoint_variants: Option<bool>,
+    /// Also emit a twin of each region with `hover:` / `focus:` /
+    /// `focus-visible:` / `active:` / `aria-selected:` / `data-[state=open]:`
+    /// style color classes per state, checked as rendered in that state
+    pub state_variants: Option<bool>,
+    /// Policies for test, story, e2e and mock files; "skip" kinds aren't scanned
+    pub file_kind_policies: Option<FileKindPolicies>,
+    /// Max lines between an `@a11y-context` / `a11y-ignore` comment and the
+    /// start of the element it applies to, blank lines included (unset = no
+    /// limit); a comment further away is dropped
+    pub annotation_max_distance: Option<u32>,
+}
+
+/// One CSS custom property, e.g. `{ name: "--primary", value: "#2563eb" }`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize)]
+pub struct CssVariable {
+    /// With or without the leading `--`
+    pub name: String,
+    pub value: String,
+}
+
+/// Colors one CSS Modules class sets, e.g.
+/// `{ module: "components/Card.module.css", className: "card", color: "#333" }`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize)]
+pub struct CssModuleClass {
+    /// Stylesheet path or its trailing segments, matched against import
+    /// specifiers by path suffix
+    pub module: String,
+    /// As accessed: `styles.card` → "card"
+    pub class_name: String,
+    pub color: Option<String>,
+    pub background_color: Option<String>,
+    pub border_color: Option<String>,
+}
+
+/// Native directory walk settings
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WalkOptions {
+    pub root: String,
+    /// File extensions to collect (default: tsx, jsx)
+    pub extensions: Option<Vec<String>>,
+    /// Extra excludes: directory names (`legacy`) or root-relative paths (`apps/old`), vendored packages included
+    pub exclude: Option<Vec<String>>,
+    /// Set false to walk node_modules, .next, dist, coverage and .git too
+    pub default_excludes: Option<bool>,
+    /// Packages under the root's node_modules to audit anyway (e.g. "@ourorg/ui")
+    pub include_vendored: Option<Vec<String>>,
+    /// Walk only the `include_vendored` packages (audit upstream components alone)
+    pub vendored_only: Option<bool>,
+    /// Source encoding: "auto" (default: BOM, UTF-8, UTF-16 or Windows-1252),
+    /// "utf-8", "utf-16le", "utf-16be" or "latin1"
+    pub encoding: Option<String>,
 }
 
+/// Resource quotas for one scan; unset fields are unlimited
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScanLimits {
+    pub max_files: Option<u32>,
+    /// Total source bytes across scanned files
+    pub max_total_bytes: Option<i64>,
+    pub max_regions_per_file: Option<u32>,
+    /// Wall-clock budget; files not started before it runs out are skipped
+    pub time_budget_ms: Option<u32>,
+}
+
+/// Limits reached by a scan (present only when its results are partial)
 #[napi(object)]
 #[derive(Debug, Clone)]
+pub struct LimitsHit {
+    /// "maxFiles" | "maxTotalBytes" | "maxRegionsPerFile" | "timeBudgetMs"
+    pub limits: Vec<String>,
+    /// Files not scanned because of a file, byte or time limit
+    pub skipped_files: Vec<String>,
+    /// Files whose regions were cut at `max_regions_per_file`
+    pub truncated_files: Vec<String>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FileInput {
     pub path: String,
     pub content: String,
 }
 
 #[napi(object)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ContainerEntry {
     pub component: String,
     pub bg_class: String,
 }
 
+/// A context elements can opt into by name, e.g. `{ name: "overlay", bgClass: "bg-popover" }`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize)]
+pub struct NamedContext {
+    pub name: String,
+    pub bg_class: String,
+}
+
 /// Pre-extracted file data returned from Rust to JS
 #[napi(object)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct PreExtractedFile {
     pub path: String,
     pub regions: Vec<ClassRegion>,
+    /// True when the file was listed in `ExtractOptions.changed_paths`
+    pub changed: Option<bool>,
+    /// Vendored package the file belongs to, as "name@version" (None = app code)
+    pub package: Option<String>,
+    /// Findings of the rules enabled via `ExtractOptions.rules`
+    pub findings: Option<Vec<RuleFinding>>,
+    /// "test" | "story" | "e2e" | "mock" for non-shipping files (None = app code)
+    pub file_kind: Option<String>,
+    /// Where the scan of the file gave up early (None = scanned through)
+    pub diagnostics: Option<Vec<ParseDiagnostic>>,
+    /// The file carries an `a11y-ignore-file` annotation: every region is ignored
+    pub ignored: Option<bool>,
 }
 
-/// NAPI-compatible version of CheckResult for returning to JS
+/// Malformed source the tokenizer stopped at; what follows may be unscanned
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseDiagnostic {
+    /// 1-based line where the malformed construct starts
+    pub line: u32,
+    /// "unclosed-tag" | "unbalanced-brackets" | "unbalanced-parens" |
+    /// "unterminated-comment" | "unterminated-template" | "unclosed-ignore-range" |
+    /// "truncated-compound-variant"
+    pub kind: String,
+    pub message: String,
+}
+
+/// Policy per kind of non-shipping file: "skip" | "informational" | "full"
+/// (default; see `file_kinds`)
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FileKindPolicies {
+    pub test: Option<String>,
+    pub story: Option<String>,
+    pub e2e: Option<String>,
+    pub mock: Option<String>,
+}
+
+/// Finding of a non-contrast rule (see `rules`)
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct RuleFinding {
+    /// Rule id, e.g. "use-of-color"
+    pub rule: String,
+    /// WCAG success criterion, e.g. "1.4.1"
+    pub wcag_criterion: String,
+    pub line: u32,
+    /// Tag of the flagged element
+    pub tag: String,
+    /// Class attribute value(s) of the flagged element
+    pub class_name: String,
+    pub message: String,
+}
+
+/// Per-file entry of a RunManifest
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ManifestFile {
+    pub path: String,
+    /// SHA-256 of the file content (hex)
+    pub content_hash: String,
+}
+
+/// Version of a single rule that contributed to the results
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct RuleVersion {
+    pub rule: String,
+    pub version: String,
+}
+
+/// Reproducibility manifest: two runs with equal manifests are comparable
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct RunManifest {
+    pub engine_version: String,
+    pub options_hash: String,
+    pub theme_hash: String,
+    pub files: Vec<ManifestFile>,
+    pub rule_versions: Vec<RuleVersion>,
+}
+
+/// Extraction results plus run-level metadata
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ScanReport {
+    pub files: Vec<PreExtractedFile>,
+    pub manifest: RunManifest,
+    /// Present when the run audited a sample of the files
+    pub sampling: Option<SamplingSummary>,
+    /// Present when a scan limit was reached and the results are partial
+    pub limits_hit: Option<LimitsHit>,
+}
+
+/// Sampled-run counts: exact for the sampled set, extrapolated to all files
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SamplingSummary {
+    pub rate: f64,
+    pub seed: u32,
+    pub total_files: u32,
+    /// Files an unsampled run would scan: `total_files` less the `sniff_js`,
+    /// file kind and `changed_only` skips
+    pub eligible_files: u32,
+    pub sampled_files: u32,
+    pub sampled_regions: u32,
+    pub sampled_ignored_regions: u32,
+    /// eligible_files / sampled_files; multiply any sampled count by it to estimate the full run
+    pub extrapolation_factor: f64,
+    pub estimated_regions: f64,
+    pub estimated_ignored_regions: f64,
+}
+
+/// Results filed under one clause of a report profile
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ProfileSection {
+    /// Clause id in the profile's numbering (e.g. "9.1.4.3")
+    pub clause: String,
+    pub title: String,
+    /// WCAG criterion the clause incorporates (e.g. "1.4.3")
+    pub wcag_criterion: String,
+    pub results: Vec<ContrastResult>,
+}
+
+/// Results regrouped by a regulatory profile (EN 301 549, Section 508, WCAG)
 #[napi(object)]
 #[derive(Debug, Clone)]
+pub struct ProfileReport {
+    pub profile: String,
+    pub sections: Vec<ProfileSection>,
+    /// Results whose criteria the profile does not incorporate
+    pub uncovered: Vec<ContrastResult>,
+}
+
+/// One row of an Accessibility Conformance Report (VPAT 2.x) table
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct AcrRow {
+    pub criterion: String,
+    pub title: String,
+    /// "A" | "AA" | "AAA"
+    pub level: String,
+    /// "Supports" | "Partially Supports" | "Does Not Support" | "Not Evaluated"
+    pub conformance: String,
+    pub remarks: String,
+}
+
+/// Pre-filled ACR skeleton for the color-related criteria
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct AcrReport {
+    pub rows: Vec<AcrRow>,
+    /// The same rows rendered as a Markdown table
+    pub markdown: String,
+}
+
+/// Contrast scorecard of one component (see `scorecard`)
+#[napi(object)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentScore {
+    /// Component the results are attributed to, e.g. "Button"
+    pub component: String,
+    /// Renderings audited: "base", or states/themes/breakpoints such as "hover", "dark+md"
+    pub variants: Vec<String>,
+    /// Pairs checked (passed + violations; suppressed pairs aside)
+    pub checked: u32,
+    pub violations: u32,
+    /// Passed share of `checked` in percent, one decimal
+    pub pass_rate: f64,
+    /// Lowest-ratio pair, e.g. "text-gray-400 on bg-white (2.54:1)"
+    pub worst_pair: Option<String>,
+    pub worst_ratio: Option<f64>,
+    /// Pairs suppressed via `a11y-ignore`
+    pub suppressions: u32,
+}
+
+/// Per-component contrast scorecard, for publishing next to a component library
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct Scorecard {
+    /// Sorted by component name
+    pub components: Vec<ComponentScore>,
+    /// `components` as a JSON array
+    pub json: String,
+    /// The same scores rendered as a Markdown table
+    pub markdown: String,
+}
+
+/// NAPI-compatible version of CheckResult for returning to JS
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
 pub struct CheckResultJs {
     pub violations: Vec<ContrastResult>,
     pub passed: Vec<ContrastResult>,
     pub ignored: Vec<ContrastResult>,
     pub ignored_count: u32,
     pub skipped_count: u32,
+    /// App violations collapsed into a library package's identical violation
+    pub inherited: Option<Vec<ContrastResult>>,
+    /// EXPERIMENTAL: WCAG 3 draft outcome score (only with the "wcag3" flag)
+    pub wcag3: Option<Wcag3Score>,
+    /// Hover/focus states barely distinguishable from rest (only with `check_state_changes`)
+    pub imperceptible_state_changes: Option<Vec<StateChange>>,
+    /// Overall color health score (set by `check_contrast_pairs_with_options`)
+    pub health: Option<HealthScore>,
+    /// Violations in files whose kind is "informational" (see `apply_file_kind_policies`)
+    pub informational: Option<Vec<ContrastResult>>,
+}
+
+/// A `container_config` / `portal_config` entry the scanned code disagrees
+/// with (see `config_lint.rs`)
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ConfigLintFinding {
+    /// "duplicate" | "conflict" | "unmatched" | "contradicted"
+    pub kind: String,
+    pub component: String,
+    /// Config the entry is in: "container" | "portal"
+    pub config: String,
+    pub message: String,
+    /// contradicted: the explicit bg classes seen on the component, most frequent first
+    pub explicit_bgs: Option<Vec<String>>,
+    /// contradicted: occurrences with a class attribute / with a contradicting bg
+    pub occurrences: Option<u32>,
+    pub contradictions: Option<u32>,
+}
+
+/// Single trending number for a check run (formula in `health.rs`)
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct HealthScore {
+    /// 0-100, higher is better
+    pub score: u32,
+    /// Bumped when the formula changes
+    pub formula_version: u32,
+    /// Pass rate of checked pairs, non-text and state pairs weighted 0.5
+    pub weighted_pass_rate: f64,
+    /// Suppressed violations / (checked + suppressed)
+    pub suppression_ratio: f64,
+    /// Pairs with an unresolved color / all non-disabled pairs
+    pub unresolved_ratio: f64,
+    pub checked: u32,
+    pub suppressed: u32,
+    pub unresolved: u32,
+    /// Suppressed violations per `a11y-ignore` reason, most frequent first
+    pub suppressions: Vec<SuppressionCount>,
+}
+
+/// Number of suppressed violations sharing one `a11y-ignore` reason
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SuppressionCount {
+    pub reason: String,
+    pub count: u32,
+}
+
+/// Options for `check_contrast_pairs_with_options`
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct CheckOptions {
+    /// "AA" (default) | "AAA"
+    pub threshold: Option<String>,
+    /// Page background for alpha compositing (default #ffffff)
+    pub page_bg: Option<String>,
+    /// Opt-in unstable checks, e.g. ["wcag3"]
+    pub experimental: Option<Vec<String>>,
+    /// Flag hover/focus states whose change from rest is imperceptible
+    pub check_state_changes: Option<bool>,
+    /// ΔE (CIE76) a state change must reach on bg or fg (default 3.5)
+    pub min_state_delta_e: Option<f64>,
+    /// Report text below `min_text_alpha` as `LOW-ALPHA-TEXT` violations,
+    /// whatever its ratio (see `text_alpha`)
+    pub check_text_alpha: Option<bool>,
+    /// Effective alpha text must reach: color alpha × cumulative opacity (default 0.3)
+    pub min_text_alpha: Option<f64>,
+    /// Evaluate headings, labels and form control text at AAA (see
+    /// `strict_profile::DEFAULT_STRICT_ELEMENTS`)
+    pub strict_profile: Option<bool>,
+    /// Tags/components/roles for the strict profile; replaces the default list
+    pub strict_elements: Option<Vec<String>>,
+    /// Theme colors searched for same-family replacement shades in `remediation`
+    pub color_map: Option<Vec<ColorMapEntry>>,
+    /// Locale of `remediation` text: "en" (default) | "it"
+    pub locale: Option<String>,
+    /// Add Weber and Michelson contrast to every result (informational only)
+    pub alternate_contrast: Option<bool>,
+    /// Add achromatopsia and low-vision simulation to every result (informational only)
+    pub vision_simulation: Option<bool>,
+    /// Require this multiple of every nominal threshold, e.g. 1.2 for sunlight
+    /// or dimmed displays (values <= 1 are ignored)
+    pub safety_margin: Option<f64>,
+    /// Grade violations by the weight of their element (see
+    /// `severity::DEFAULT_SEVERITY_WEIGHTS`)
+    pub severity_escalation: Option<bool>,
+    /// Tag/component/role weights for the severity grading; replaces the default map
+    pub severity_weights: Option<Vec<SeverityWeight>>,
+}
+
+/// Severity weight of the violations of one tag, component or role
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SeverityWeight {
+    /// Tag ("p"), component ("Badge") or ARIA role ("navigation")
+    pub element: String,
+    /// >= 1 grades "error", >= 0.5 "warning", below "info"
+    pub weight: f64,
+}
+
+/// A hover/focus state compared with its rest state
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct StateChange {
+    pub file: String,
+    pub line: u32,
+    /// "hover" | "focus-visible"
+    pub interactive_state: String,
+    pub pair_type: Option<String>,
+    pub rest_bg_class: String,
+    pub state_bg_class: String,
+    pub rest_text_class: String,
+    pub state_text_class: String,
+    /// CIE76 ΔE between rest and state backgrounds
+    pub bg_delta_e: f64,
+    /// CIE76 ΔE between rest and state foregrounds
+    pub text_delta_e: f64,
+    /// Absolute relative-luminance change of the background (0-1)
+    pub luminance_delta: f64,
+}
+
+/// EXPERIMENTAL WCAG 3 draft outcome score; unstable, do not gate on it
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct Wcag3Score {
+    /// Always "experimental"
+    pub status: String,
+    /// Draft revision the scoring follows
+    pub draft: String,
+    /// Outcome rating 0-4
+    pub rating: u32,
+    pub passing: u32,
+    pub total: u32,
+    /// Share of checked pairs passing (0-100)
+    pub percent: f64,
+}
+
+/// Options for `plan_fixes`
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct FixPlanOptions {
+    /// Threshold the result was checked at: "AA" (default) | "AAA"
+    pub threshold: Option<String>,
+    /// Page background for alpha compositing (default #ffffff)
+    pub page_bg: Option<String>,
+    /// Maximum proposals, biggest impact first (default 20)
+    pub max_fixes: Option<u32>,
+}
+
+/// One proposed token-level change and its projected impact
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct TokenFix {
+    /// CSS variable to change, e.g. "--color-muted-foreground"
+    pub token: String,
+    pub current_hex: String,
+    pub proposed_hex: String,
+    /// Token pair of the cluster (first violation's classes)
+    pub text_class: String,
+    pub bg_class: String,
+    /// Violations in the cluster
+    pub violations: u32,
+    /// Violations anywhere that pass with the proposed value
+    pub fixed: u32,
+    /// Passing pairs that would fail with the proposed value
+    pub regressions: u32,
+    /// Files of the cluster's violations
+    pub files: Vec<String>,
+}
+
+/// Result of `plan_fixes`
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct FixPlan {
+    /// Biggest impact first
+    pub fixes: Vec<TokenFix>,
+    /// Violations without a token to change (arbitrary colors) or no passing value
+    pub unplanned: u32,
+}
+
+/// Results or pairs to replay in `simulate_token_change`
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct TokenSimulationInput {
+    /// Checked results (violations and passed are replayed; wins over `pairs`)
+    pub result: Option<CheckResultJs>,
+    /// Raw pairs, checked before simulating
+    pub pairs: Option<Vec<ColorPair>>,
+    /// "AA" (default) | "AAA"
+    pub threshold: Option<String>,
+    /// Page background for alpha compositing (default #ffffff)
+    pub page_bg: Option<String>,
+}
+
+/// Before/after outcome of changing one token's value
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct TokenSimulation {
+    pub token: String,
+    pub new_hex: String,
+    /// Checked pairs reading the token as text or background
+    pub affected: u32,
+    pub passed_before: u32,
+    pub passed_after: u32,
+    /// Violations that pass with the new value (after values)
+    pub fixed: Vec<ContrastResult>,
+    /// Passing pairs that fail with the new value (after values)
+    pub regressed: Vec<ContrastResult>,
+}
+
+/// Options for `optimize_palette`
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct PaletteOptimizerOptions {
+    /// Share (0-1) of a token's pairs that must pass (default 1.0)
+    pub target_pass_rate: Option<f64>,
+    /// Largest CIE76 ΔE a proposal may move a token (default 10)
+    pub max_delta_e: Option<f64>,
+    /// Maximum proposals returned (default all)
+    pub max_proposals: Option<u32>,
+}
+
+/// A minimal token change found by `optimize_palette`
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct PaletteProposal {
+    /// CSS variable, e.g. "--color-muted-foreground"
+    pub token: String,
+    pub current_hex: String,
+    pub proposed_hex: String,
+    /// The proposed value as CSS `oklch()`
+    pub proposed_oklch: String,
+    /// CIE76 distance from the current value
+    pub delta_e: f64,
+    /// Share (0-1) of the token's pairs passing before / after
+    pub pass_rate_before: f64,
+    pub pass_rate_after: f64,
+    pub fixed: u32,
+    pub regressions: u32,
+}
+
+/// Result of `optimize_palette`
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct PaletteOptimization {
+    /// Most violations fixed first, then fewest regressions, then smallest ΔE
+    pub proposals: Vec<PaletteProposal>,
+    /// Failing tokens with no candidate reaching the target within the ΔE limit
+    pub unreachable: Vec<String>,
+}
+
+/// Inclusive line range of a file, e.g. from blame output
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct LineRange {
+    pub path: String,
+    pub start_line: u32,
+    pub end_line: u32,
+}
+
+/// Changed-line sources for `introduced_in_diff` attribution (merged when both set)
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct DiffOptions {
+    /// `git diff` output (unified format)
+    pub unified_diff: Option<String>,
+    pub ranges: Option<Vec<LineRange>>,
+}
+
+/// Review-bot inline comment for the violations on one line a diff adds
+/// (see `diff::annotate`)
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct DiffAnnotation {
+    /// File path as the diff names it (new side)
+    pub path: String,
+    /// New-side line the comment is anchored to: the first added line of the
+    /// violations' lines
+    pub line: u32,
+    /// 1-based line count below the file's first hunk header, later hunk
+    /// headers included (GitHub's legacy review comment `position`)
+    pub position: u32,
+    /// 0-based hunk index within the file's diff
+    pub hunk: u32,
+    /// 1-based line within the hunk, header excluded
+    pub hunk_line: u32,
+    /// Markdown comment body, one entry per violation
+    pub body: String,
+    pub violations: Vec<ContrastResult>,
+}
+
+/// One resolved theme color: CSS variable name → hex (+ alpha)
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ColorMapEntry {
+    /// e.g. "--color-primary"
+    pub name: String,
+    pub hex: String,
+    pub alpha: Option<f64>,
+}
+
+/// Theme + config registered once via `register_theme` and reused by handle
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct ThemeConfig {
+    pub color_map: Vec<ColorMapEntry>,
+    pub container_config: Vec<ContainerEntry>,
+    pub portal_config: Vec<ContainerEntry>,
+    pub default_bg: String,
+    /// Contexts for `@a11y-context use:<name>` (see `ExtractOptions.named_contexts`)
+    pub named_contexts: Option<Vec<NamedContext>>,
+    /// "light" (default) | "dark"
+    pub theme_mode: Option<String>,
+    /// "AA" (default) | "AAA"
+    pub threshold: Option<String>,
+    /// Page background for alpha compositing (default: #ffffff light, #09090b dark)
+    pub page_bg: Option<String>,
+    pub locale: Option<String>,
+    /// Custom class-bearing props (see `ExtractOptions.class_attributes`)
+    pub class_attributes: Option<Vec<String>>,
+    /// Class-composition helpers (see `ExtractOptions.class_functions`)
+    pub class_functions: Option<Vec<String>>,
+    /// CSS Modules class colors (see `ExtractOptions.css_modules`)
+    pub css_modules: Option<Vec<CssModuleClass>>,
+    /// Class constant files imported across files (see `ExtractOptions.library_files`)
+    pub library_files: Option<Vec<FileInput>>,
+}
+
+/// In-memory source for `audit_snippets`
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct Snippet {
+    pub path: String,
+    pub content: String,
+    /// Background class the snippet renders on (default: the theme's default bg)
+    pub base_context: Option<String>,
+    /// Synthetic ancestors the snippet renders inside, outermost first (applied on top of `base_context`)
+    pub ancestors: Option<Vec<AncestorContext>>,
+}
+
+/// One synthetic ancestor of a partial source, e.g. `{ component: "Card" }`
+/// or `{ bgClass: "bg-slate-900" }` for "inside a dark section"
+#[napi(object)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AncestorContext {
+    /// Component name, resolved through container/portal config
+    pub component: Option<String>,
+    /// Explicit bg class (wins over the component's configured bg)
+    pub bg_class: Option<String>,
+    /// Opacity applied by this ancestor (0-1)
+    pub opacity: Option<f64>,
+}
+
+/// Contrast of one autocomplete candidate text class on a context bg
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct CandidateContrast {
+    pub class_name: String,
+    /// None when the class doesn't resolve to a color (no verdict)
+    pub hex: Option<String>,
+    pub ratio: Option<f64>,
+    /// Passes the theme threshold as body text
+    pub pass: Option<bool>,
+    /// Passes the theme threshold as large text (≥18.66px bold or ≥24px)
+    pub pass_large: Option<bool>,
+    pub apca_lc: Option<f64>,
+}
+
+/// A workspace project: its root directory and the theme it is audited with
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct WorkspaceRoot {
+    pub root: String,
+    pub theme_handle: u32,
+}
+
+/// A file routed to its workspace project by `workspace::update_files`
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct RoutedFile {
+    pub path: String,
+    /// None when no registered root contains the file
+    pub root: Option<WorkspaceRoot>,
+    /// Regions indexed for the file
+    pub regions: u32,
+}
+
+/// A region of a file indexed by `region_index`
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct IndexedRegion {
+    pub path: String,
+    /// Position in the file's region list (stable until the file changes)
+    pub id: u32,
+    pub region: ClassRegion,
+}
+
+/// Editor hover payload: the class region at a position with its checked
+/// pairs (see `hover::hover_info`)
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct HoverInfo {
+    pub file: String,
+    pub line: u32,
+    /// 1-based column range of the region's classes on the line (None when
+    /// they don't appear verbatim, e.g. `cn()` calls spanning lines)
+    pub column_start: Option<u32>,
+    pub column_end: Option<u32>,
+    pub region: ClassRegion,
+    /// Violations carry `remediation`
+    pub violations: Vec<ContrastResult>,
+    pub passed: Vec<ContrastResult>,
+    pub ignored: Vec<ContrastResult>,
+    pub skipped: Vec<SkippedClass>,
+}
+
+/// Violations of one indexed file recomputed after a config reload
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct FileDelta {
+    pub path: String,
+    /// All violations under the new config (with `remediation`)
+    pub violations: Vec<ContrastResult>,
+    /// Violations the new config introduced
+    pub introduced: Vec<ContrastResult>,
+    /// Violations the new config fixed, as reported under the old one
+    pub resolved: Vec<ContrastResult>,
+}
+
+/// Outcome of `reload::update_config`
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct ConfigDelta {
+    /// Indexed files re-scanned because the container/portal/default-bg config changed
+    pub rescanned: u32,
+    /// Files whose violations changed, by path
+    pub files: Vec<FileDelta>,
+}
+
+/// Point-in-time results of every file indexed under a theme (see `snapshot::snapshot`)
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct ResultSnapshot {
+    pub theme_handle: u32,
+    /// Paths of the files included, sorted
+    pub files: Vec<String>,
+    /// Violations carry `remediation`
+    pub results: CheckResultJs,
+    pub skipped: Vec<SkippedClass>,
+}
+
+/// What this engine build supports, for wrappers that feature-detect
+/// instead of pinning an exact native version
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct Capabilities {
+    pub engine_version: String,
+    /// File suffixes with a parser mode, e.g. ".vue", ".component.html"
+    pub file_types: Vec<String>,
+    /// Every versioned rule (see `RunManifest.rule_versions`)
+    pub rules: Vec<RuleVersion>,
+    /// Rules `ExtractOptions.rules` accepts
+    pub selectable_rules: Vec<String>,
+    /// Optional features of this build, e.g. "cache", "walk", "hot-reload"
+    pub features: Vec<String>,
+    /// Header version of `write_cache_file` files
+    pub cache_format_version: u32,
+}
+
+/// One built-in conformance case of `self_test`
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SelfTestCase {
+    pub name: String,
+    pub passed: bool,
+    /// Expected vs. actual values (or the panic message) when it failed
+    pub detail: Option<String>,
+}
+
+/// Outcome of the built-in conformance suite run in the installed binary
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SelfTestReport {
+    pub passed: bool,
+    pub engine_version: String,
+    /// Architecture and OS the binary runs on, e.g. "aarch64-linux"
+    pub platform: String,
+    pub cases: Vec<SelfTestCase>,
+}
+
+/// Options for `migrate_ignores`
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreMigrationOptions {
+    /// Reason written into each comment (default "baseline")
+    pub reason: Option<String>,
+    /// Match file paths case-insensitively, as the baseline was written
+    pub case_insensitive_paths: Option<bool>,
+}
+
+/// One `a11y-ignore` comment to insert (see `ignore_migration.rs`)
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct IgnoreEdit {
+    pub file: String,
+    /// 1-based line and column the text is inserted at (column 1 with a
+    /// trailing newline = a line of its own)
+    pub line: u32,
+    pub column: u32,
+    pub text: String,
+    /// Baseline violations of the element the comment suppresses
+    pub violations: u32,
+}
+
+/// `a11y-ignore` insertions grandfathering a baseline's violations
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct IgnoreMigration {
+    /// Sorted by file and position: apply from last to first so earlier
+    /// positions stay valid
+    pub edits: Vec<IgnoreEdit>,
+    /// Violations matched in the baseline
+    pub grandfathered: u32,
+    /// Files of matched violations with no supplied source (or no locatable
+    /// element), left without edits
+    pub missing_files: Vec<String>,
 }
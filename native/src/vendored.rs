@@ -0,0 +1,205 @@
+//! Attribution of vendored (node_modules) sources to their package.
+//!
+//! A file under `.../node_modules/<name>/...` (or `@scope/<name>`) belongs to
+//! that package; its version is read from the package's `package.json` next
+//! to the file, so results can be reported as `@ourorg/ui@1.2.3` instead of
+//! against the app that happens to install it.
+//!
+//! `collapse_inherited()` then moves app violations that repeat a package's
+//! own violation (same fingerprint) out of `violations`, so app teams are
+//! only told about failures they own.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::fingerprint;
+use crate::types::CheckResultJs;
+
+const NODE_MODULES: &str = "node_modules/";
+
+/// Package directory and name of a path inside node_modules (innermost one).
+pub fn package_of(path: &str) -> Option<(String, String)> {
+    let normalized = path.replace('\\', "/");
+    let start = normalized.rfind(NODE_MODULES)? + NODE_MODULES.len();
+    let mut segments = normalized[start..].split('/');
+    let first = segments.next().filter(|s| !s.is_empty())?;
+    let name = if first.starts_with('@') {
+        format!("{}/{}", first, segments.next().filter(|s| !s.is_empty())?)
+    } else {
+        first.to_string()
+    };
+    // The package directory must contain the file, not be the file itself
+    segments.next()?;
+    let dir = format!("{}{}", &normalized[..start], name);
+    Some((dir, name))
+}
+
+/// `version` field of `<dir>/package.json`.
+fn read_version(dir: &str) -> Option<String> {
+    let text = std::fs::read_to_string(Path::new(dir).join("package.json")).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&text).ok()?;
+    json.get("version")?.as_str().map(str::to_string)
+}
+
+/// Resolves file paths to `name@version` labels, reading each package.json once.
+#[derive(Debug, Default)]
+pub struct PackageResolver {
+    versions: HashMap<String, Option<String>>,
+}
+
+impl PackageResolver {
+    /// `name@version` (or just `name` when the version can't be read) for a
+    /// vendored path; None for app files.
+    pub fn resolve(&mut self, path: &str) -> Option<String> {
+        let (dir, name) = package_of(path)?;
+        let version = self
+            .versions
+            .entry(dir)
+            .or_insert_with_key(|dir| read_version(dir));
+        Some(match version {
+            Some(v) => format!("{}@{}", name, v),
+            None => name,
+        })
+    }
+}
+
+/// Set `package` on every result that comes from a vendored file.
+pub fn attribute_packages(result: &mut CheckResultJs) {
+    let mut resolver = PackageResolver::default();
+    for r in result
+        .violations
+        .iter_mut()
+        .chain(result.passed.iter_mut())
+        .chain(result.ignored.iter_mut())
+    {
+        r.package = resolver.resolve(&r.file);
+    }
+}
+
+/// Move app violations whose fingerprint matches a vendored package's
+/// violation into `inherited`, tagged with `inherited_from`. Packages and
+/// fingerprints are assigned first when missing.
+pub fn collapse_inherited(result: &mut CheckResultJs) {
+    if result.violations.iter().any(|r| r.package.is_none()) {
+        let mut resolver = PackageResolver::default();
+        for r in result.violations.iter_mut().filter(|r| r.package.is_none()) {
+            r.package = resolver.resolve(&r.file);
+        }
+    }
+    for r in result.violations.iter_mut().filter(|r| r.fingerprint.is_none()) {
+        r.fingerprint = Some(fingerprint::fingerprint(r));
+    }
+
+    // fingerprint → first package (in result order) that owns it
+    let mut owners: HashMap<String, String> = HashMap::new();
+    for r in &result.violations {
+        if let (Some(package), Some(fp)) = (&r.package, &r.fingerprint) {
+            owners.entry(fp.clone()).or_insert_with(|| package.clone());
+        }
+    }
+    if owners.is_empty() {
+        return;
+    }
+
+    let mut inherited = result.inherited.take().unwrap_or_default();
+    let mut kept = Vec::with_capacity(result.violations.len());
+    for mut r in std::mem::take(&mut result.violations) {
+        let owner = r
+            .fingerprint
+            .as_ref()
+            .filter(|_| r.package.is_none())
+            .and_then(|fp| owners.get(fp));
+        match owner {
+            Some(package) => {
+                r.inherited_from = Some(package.clone());
+                inherited.push(r);
+            }
+            None => kept.push(r),
+        }
+    }
+    log::debug!("collapsed {} app violations into package violations", inherited.len());
+    result.violations = kept;
+    result.inherited = Some(inherited);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn package_of_plain_and_scoped() {
+        assert_eq!(
+            package_of("/app/node_modules/react-aria/dist/Button.jsx"),
+            Some(("/app/node_modules/react-aria".to_string(), "react-aria".to_string()))
+        );
+        assert_eq!(
+            package_of("node_modules/@ourorg/ui/dist/Button.jsx").map(|(_, n)| n),
+            Some("@ourorg/ui".to_string())
+        );
+        assert_eq!(
+            package_of(r"C:\app\node_modules\@ourorg\ui\Button.jsx").map(|(_, n)| n),
+            Some("@ourorg/ui".to_string())
+        );
+    }
+
+    #[test]
+    fn innermost_package_wins() {
+        assert_eq!(
+            package_of("node_modules/@ourorg/ui/node_modules/clsx/index.js").map(|(_, n)| n),
+            Some("clsx".to_string())
+        );
+    }
+
+    #[test]
+    fn app_files_have_no_package() {
+        assert_eq!(package_of("src/components/Button.tsx"), None);
+        assert_eq!(package_of("node_modules/@ourorg"), None);
+    }
+
+    #[test]
+    fn resolver_reads_version_from_package_json() {
+        let root = std::env::temp_dir().join(format!("a11y-vendored-{}", std::process::id()));
+        let pkg = root.join("node_modules/@ourorg/ui");
+        std::fs::create_dir_all(pkg.join("dist")).unwrap();
+        std::fs::write(pkg.join("package.json"), r#"{"name":"@ourorg/ui","version":"1.2.3"}"#).unwrap();
+
+        let mut resolver = PackageResolver::default();
+        let file = pkg.join("dist/Button.jsx");
+        assert_eq!(resolver.resolve(&file.to_string_lossy()), Some("@ourorg/ui@1.2.3".to_string()));
+        // Missing package.json → name only
+        assert_eq!(resolver.resolve("/nowhere/node_modules/x/a.jsx"), Some("x".to_string()));
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn app_duplicates_collapse_into_package_violation() {
+        use crate::math::checker::check_all_pairs;
+        use crate::types::ColorPair;
+        let pair = |file: &str, text_hex: &str| ColorPair {
+            file: file.to_string(),
+            line: 3,
+            text_class: "text-gray-300".to_string(),
+            bg_hex: Some("#ffffff".to_string()),
+            text_hex: Some(text_hex.to_string()),
+            ..Default::default()
+        };
+        let r = check_all_pairs(
+            &[
+                pair("node_modules/@ourorg/ui/dist/Badge.jsx", "#d1d5db"),
+                pair("src/Page.tsx", "#d1d5db"),
+                pair("src/Other.tsx", "#e5e7eb"),
+            ],
+            "AA",
+            "#ffffff",
+        );
+        let mut js = CheckResultJs::from(r);
+        collapse_inherited(&mut js);
+
+        let files: Vec<&str> = js.violations.iter().map(|v| v.file.as_str()).collect();
+        assert_eq!(files, vec!["node_modules/@ourorg/ui/dist/Badge.jsx", "src/Other.tsx"]);
+        let inherited = js.inherited.unwrap();
+        assert_eq!(inherited.len(), 1);
+        assert_eq!(inherited[0].file, "src/Page.tsx");
+        assert_eq!(inherited[0].inherited_from.as_deref(), Some("@ourorg/ui"));
+    }
+}
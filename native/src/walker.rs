@@ -0,0 +1,376 @@
+//! Native file walking for `ExtractOptions.walk`.
+//!
+//! Dependency and build output directories (`node_modules`, `.next`, `dist`,
+//! `coverage`, `.git`) are skipped by default. Packages listed in
+//! `include_vendored` are the exception: their directory under the root's
+//! `node_modules` is walked in full (including `dist`, which is usually all a
+//! published design-system package ships), except its own `node_modules` and
+//! the user's `exclude` entries. A file is reported once even when the main
+//! walk already reached it (`default_excludes: false`).
+//!
+//! On Windows the walk runs under the canonical `\\?\` form of the root so
+//! deep trees past MAX_PATH are still read; walked files are reported under
+//! the root as given, with `/` separators.
+//!
+//! Files are read as bytes and decoded by `decode` (BOM, then UTF-8, BOM-less
+//! UTF-16 or Windows-1252), or in the encoding `WalkOptions.encoding` forces.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::decode::{self, Encoding};
+use crate::types::{ExtractOptions, FileInput, WalkOptions};
+
+/// Directories skipped unless `default_excludes` is false.
+pub const DEFAULT_EXCLUDES: &[&str] = &["node_modules", ".next", "dist", "coverage", ".git"];
+
+/// File extensions walked when `extensions` is unset.
+pub const DEFAULT_EXTENSIONS: &[&str] = &["tsx", "jsx"];
+
+const NODE_MODULES: &str = "node_modules";
+
+/// Walk settings resolved from `WalkOptions`.
+struct Walk<'a> {
+    /// Root the directories are read under (see `long_path_root`)
+    root: Cow<'a, Path>,
+    /// Root as given, prefixed to reported paths
+    display_root: &'a Path,
+    extensions: Vec<String>,
+    excludes: Vec<String>,
+    /// Forced source encoding; detected per file when None
+    encoding: Option<Encoding>,
+}
+
+/// `root` in the form `fs` calls accept past MAX_PATH: the canonical
+/// `\\?\C:\...` path on Windows, `root` itself elsewhere.
+#[cfg(windows)]
+fn long_path_root(root: &Path) -> io::Result<Cow<'_, Path>> {
+    fs::canonicalize(root).map(Cow::Owned)
+}
+
+#[cfg(not(windows))]
+fn long_path_root(root: &Path) -> io::Result<Cow<'_, Path>> {
+    Ok(Cow::Borrowed(root))
+}
+
+impl Walk<'_> {
+    fn matches_extension(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| self.extensions.iter().any(|x| x == e))
+    }
+
+    /// Excluded by name (`dist`) or by root-relative path (`apps/legacy`).
+    fn is_excluded(&self, dir: &Path, excludes: &[String]) -> bool {
+        let name = dir.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let rel = dir
+            .strip_prefix(&self.root)
+            .unwrap_or(dir)
+            .to_string_lossy()
+            .replace('\\', "/");
+        excludes.iter().any(|e| {
+            let e = e.trim_end_matches('/');
+            if e.contains('/') {
+                rel == e || rel.starts_with(&format!("{}/", e))
+            } else {
+                name == e
+            }
+        })
+    }
+
+    fn visit(&self, dir: &Path, excludes: &[String], out: &mut Vec<FileInput>) {
+        let mut entries: Vec<PathBuf> = match fs::read_dir(dir) {
+            Ok(rd) => rd.filter_map(|e| e.ok()).map(|e| e.path()).collect(),
+            Err(err) => {
+                log::warn!("cannot read directory {}: {}", dir.display(), err);
+                return;
+            }
+        };
+        entries.sort();
+        for path in entries {
+            // Never follow symlinks inside the walk (cycles); vendored package
+            // roots are resolved separately.
+            let Ok(meta) = fs::symlink_metadata(&path) else {
+                continue;
+            };
+            if meta.is_dir() {
+                if !self.is_excluded(&path, excludes) {
+                    self.visit(&path, excludes, out);
+                }
+            } else if meta.is_file() && self.matches_extension(&path) {
+                self.read_file(&path, out);
+            }
+        }
+    }
+
+    /// `path` under the root as given, `/`-separated on every platform.
+    fn reported_path(&self, path: &Path) -> String {
+        let rel = path.strip_prefix(&self.root).unwrap_or(path);
+        let shown = self.display_root.join(rel).to_string_lossy().into_owned();
+        if cfg!(windows) {
+            shown.replace('\\', "/")
+        } else {
+            shown
+        }
+    }
+
+    fn read_file(&self, path: &Path, out: &mut Vec<FileInput>) {
+        let reported = self.reported_path(path);
+        match fs::read(path) {
+            Ok(bytes) => {
+                let (content, encoding) = decode::decode(&bytes, self.encoding);
+                if encoding != Encoding::Utf8 {
+                    log::debug!("decoded {} as {}", reported, encoding.as_str());
+                }
+                out.push(FileInput { path: reported, content })
+            }
+            Err(err) => log::warn!("skipping {}: {}", reported, err),
+        }
+    }
+}
+
+/// Collect the source files under `options.root`.
+pub fn walk(options: &WalkOptions) -> io::Result<Vec<FileInput>> {
+    let root = Path::new(&options.root);
+    if !fs::metadata(root)?.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("walk root is not a directory: {}", options.root),
+        ));
+    }
+    let encoding = match options.encoding.as_deref() {
+        None | Some("auto") => None,
+        Some(label) => Some(Encoding::from_label(label).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, format!("unknown source encoding: {}", label))
+        })?),
+    };
+
+    let mut excludes: Vec<String> = if options.default_excludes == Some(false) {
+        Vec::new()
+    } else {
+        DEFAULT_EXCLUDES.iter().map(|s| s.to_string()).collect()
+    };
+    excludes.extend(options.exclude.iter().flatten().cloned());
+
+    let walk = Walk {
+        root: long_path_root(root)?,
+        display_root: root,
+        extensions: match &options.extensions {
+            Some(exts) => exts.iter().map(|e| e.trim_start_matches('.').to_string()).collect(),
+            None => DEFAULT_EXTENSIONS.iter().map(|s| s.to_string()).collect(),
+        },
+        excludes,
+        encoding,
+    };
+
+    let mut out = Vec::new();
+    if options.vendored_only != Some(true) {
+        walk.visit(&walk.root, &walk.excludes, &mut out);
+    }
+
+    let mut vendored_excludes = vec![NODE_MODULES.to_string()];
+    vendored_excludes.extend(options.exclude.iter().flatten().cloned());
+    let mut seen: HashSet<String> = out.iter().map(|f| f.path.clone()).collect();
+    for package in options.include_vendored.iter().flatten() {
+        let package_root = walk.root.join(NODE_MODULES).join(package);
+        // Package roots may be symlinks (pnpm, workspaces): follow this one.
+        if fs::metadata(&package_root).is_ok_and(|m| m.is_dir()) {
+            let mut files = Vec::new();
+            walk.visit(&package_root, &vendored_excludes, &mut files);
+            out.extend(files.into_iter().filter(|f| seen.insert(f.path.clone())));
+        } else {
+            log::warn!("vendored package {} not found under {}", package, root.display());
+        }
+    }
+
+    log::debug!("walked {} files under {}", out.len(), root.display());
+    Ok(out)
+}
+
+/// `options` with the walked files appended to `file_contents` (borrowed
+/// unchanged when `walk` is unset). A failed walk is logged and the explicit
+/// `file_contents` are scanned alone.
+pub fn resolve_inputs(options: &ExtractOptions) -> Cow<'_, ExtractOptions> {
+    let Some(walk_options) = &options.walk else {
+        return Cow::Borrowed(options);
+    };
+    match walk(walk_options) {
+        Ok(files) => {
+            let mut resolved = options.clone();
+            resolved.file_contents.extend(files);
+            Cow::Owned(resolved)
+        }
+        Err(err) => {
+            log::error!("could not walk {}: {}", walk_options.root, err);
+            Cow::Borrowed(options)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str, files: &[&str]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("a11y-walker-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for f in files {
+            let path = root.join(f);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, "<div />").unwrap();
+        }
+        root
+    }
+
+    fn walked(root: &Path, options: WalkOptions) -> Vec<String> {
+        let files = walk(&WalkOptions {
+            root: root.to_string_lossy().into_owned(),
+            ..options
+        })
+        .unwrap();
+        files
+            .iter()
+            .map(|f| Path::new(&f.path).strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect()
+    }
+
+    const TREE: &[&str] = &[
+        "src/App.tsx",
+        "src/util.ts",
+        "dist/App.jsx",
+        ".next/page.jsx",
+        "coverage/lcov.jsx",
+        "node_modules/react/index.jsx",
+        "node_modules/@ourorg/ui/dist/Button.jsx",
+        "node_modules/@ourorg/ui/node_modules/dep/x.jsx",
+    ];
+
+    #[test]
+    fn default_excludes_skip_dependencies_and_build_output() {
+        let root = fixture("defaults", TREE);
+        assert_eq!(walked(&root, WalkOptions::default()), vec!["src/App.tsx"]);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn vendored_package_opt_in_walks_its_dist() {
+        let root = fixture("vendored", TREE);
+        let files = walked(
+            &root,
+            WalkOptions {
+                include_vendored: Some(vec!["@ourorg/ui".to_string()]),
+                ..Default::default()
+            },
+        );
+        assert_eq!(files, vec!["src/App.tsx", "node_modules/@ourorg/ui/dist/Button.jsx"]);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn vendored_only_skips_app_files() {
+        let root = fixture("vendored-only", TREE);
+        let files = walked(
+            &root,
+            WalkOptions {
+                include_vendored: Some(vec!["@ourorg/ui".to_string()]),
+                vendored_only: Some(true),
+                ..Default::default()
+            },
+        );
+        assert_eq!(files, vec!["node_modules/@ourorg/ui/dist/Button.jsx"]);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn vendored_walk_honours_excludes_and_reports_files_once() {
+        let root = fixture(
+            "vendored-excludes",
+            &["src/App.tsx", "node_modules/@ourorg/ui/dist/Button.jsx", "node_modules/@ourorg/ui/stories/B.jsx"],
+        );
+        let files = walked(
+            &root,
+            WalkOptions {
+                include_vendored: Some(vec!["@ourorg/ui".to_string(), "@ourorg/ui".to_string()]),
+                exclude: Some(vec!["stories".to_string()]),
+                default_excludes: Some(false),
+                ..Default::default()
+            },
+        );
+        assert_eq!(files, vec!["node_modules/@ourorg/ui/dist/Button.jsx", "src/App.tsx"]);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn extra_excludes_and_extensions() {
+        let root = fixture("extra", &["src/App.tsx", "src/legacy/Old.tsx", "src/util.ts"]);
+        let files = walked(
+            &root,
+            WalkOptions {
+                exclude: Some(vec!["src/legacy".to_string()]),
+                extensions: Some(vec![".tsx".to_string(), "ts".to_string()]),
+                ..Default::default()
+            },
+        );
+        assert_eq!(files, vec!["src/App.tsx", "src/util.ts"]);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn defaults_can_be_disabled() {
+        let root = fixture("nodefaults", &["src/App.tsx", "dist/App.jsx"]);
+        let files = walked(
+            &root,
+            WalkOptions {
+                default_excludes: Some(false),
+                ..Default::default()
+            },
+        );
+        assert_eq!(files, vec!["dist/App.jsx", "src/App.tsx"]);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn files_reported_under_the_root_as_given() {
+        let root = fixture("given-root", &["src/App.tsx"]);
+        let given = root.join("src").join("..");
+        let files = walk(&WalkOptions { root: given.to_string_lossy().into_owned(), ..Default::default() }).unwrap();
+        let expected = given.join("src").join("App.tsx").to_string_lossy().replace('\\', "/");
+        assert_eq!(files.iter().map(|f| f.path.replace('\\', "/")).collect::<Vec<_>>(), vec![expected]);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn missing_root_is_an_error() {
+        let options = WalkOptions {
+            root: "/nonexistent/a11y-walker".to_string(),
+            ..Default::default()
+        };
+        assert!(walk(&options).is_err());
+    }
+
+    #[test]
+    fn legacy_encodings_are_decoded() {
+        let root = fixture("encodings", &[]);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("Latin.jsx"), b"<p className=\"text-gray-400\">Caff\xE8</p>").unwrap();
+        let wide: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("<p className=\"text-gray-400\">Caffè</p>".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        fs::write(root.join("Wide.jsx"), wide).unwrap();
+        let files = walk(&WalkOptions { root: root.to_string_lossy().into_owned(), ..Default::default() }).unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().all(|f| f.content == "<p className=\"text-gray-400\">Caffè</p>"));
+
+        let unknown = WalkOptions {
+            root: root.to_string_lossy().into_owned(),
+            encoding: Some("ebcdic".into()),
+            ..Default::default()
+        };
+        assert!(walk(&unknown).is_err());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
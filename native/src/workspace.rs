@@ -0,0 +1,143 @@
+//! Multi-root workspaces: several projects open at once (monorepo apps),
+//! each audited with its own registered theme.
+//!
+//! Roots map to theme handles; a file is routed to the project with the
+//! longest root containing it, so editor updates reach the right config.
+//! Releasing a theme removes the roots that used it.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use crate::region_index;
+use crate::theme;
+use crate::types::{RoutedFile, Snippet, WorkspaceRoot};
+
+fn registry() -> &'static Mutex<HashMap<String, u32>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<String, u32>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Forward slashes, no trailing slash.
+fn normalize(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    match path.trim_end_matches('/') {
+        "" => path,
+        trimmed => trimmed.to_string(),
+    }
+}
+
+/// Whether `path` is `root` or inside it (on a whole path segment).
+fn contains(root: &str, path: &str) -> bool {
+    path == root || path.strip_prefix(root).is_some_and(|rest| rest.starts_with('/') || root.ends_with('/'))
+}
+
+/// Audit files under `root` with theme `handle` (replaces the root's theme).
+pub fn add_root(root: &str, handle: u32) {
+    registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(normalize(root), handle);
+}
+
+/// Stop routing files to `root`. Returns false for unknown roots.
+pub fn remove_root(root: &str) -> bool {
+    registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&normalize(root))
+        .is_some()
+}
+
+/// Remove the roots audited with theme `handle`.
+pub fn release_theme(handle: u32) {
+    registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .retain(|_, h| *h != handle);
+}
+
+/// Project `path` belongs to: the longest registered root containing it.
+pub fn root_of(path: &str) -> Option<WorkspaceRoot> {
+    let path = normalize(path);
+    registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .filter(|(root, _)| contains(root, &path))
+        .max_by_key(|(root, _)| root.len())
+        .map(|(root, &handle)| WorkspaceRoot { root: root.clone(), theme_handle: handle })
+}
+
+/// Route every file to its project and (re-)index it under that project's
+/// theme (see `region_index::file_index`). Files outside every root, or whose
+/// theme was released, are returned unrouted.
+pub fn update_files(snippets: &[Snippet]) -> Vec<RoutedFile> {
+    snippets
+        .iter()
+        .map(|snippet| {
+            let routed = root_of(&snippet.path)
+                .and_then(|root| theme::get(root.theme_handle).map(|theme| (root, theme)));
+            match routed {
+                Some((root, theme)) => RoutedFile {
+                    path: snippet.path.clone(),
+                    regions: region_index::file_index(root.theme_handle, &theme, snippet).regions().len() as u32,
+                    root: Some(root),
+                },
+                None => RoutedFile { path: snippet.path.clone(), root: None, regions: 0 },
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::Theme;
+    use crate::types::ThemeConfig;
+
+    fn snippet(path: &str) -> Snippet {
+        Snippet {
+            path: path.to_string(),
+            content: r#"<p className="text-white">x</p>"#.to_string(),
+            base_context: None,
+            ancestors: None,
+        }
+    }
+
+    #[test]
+    fn routes_to_the_innermost_root() {
+        let web = theme::register(Theme::default());
+        let dark = ThemeConfig { theme_mode: Some("dark".to_string()), ..Default::default() };
+        let admin = theme::register(Theme::from_config(&dark));
+        add_root("/repo-routing/apps/web/", web);
+        add_root("C:\\repo-routing\\apps\\admin", admin);
+        add_root("/repo-routing/apps/web/legacy", admin);
+
+        assert_eq!(root_of("/repo-routing/apps/web/src/App.tsx").unwrap().theme_handle, web);
+        assert_eq!(root_of("/repo-routing/apps/web/legacy/Old.tsx").unwrap().root, "/repo-routing/apps/web/legacy");
+        assert_eq!(root_of("C:/repo-routing/apps/admin/Page.tsx").unwrap().theme_handle, admin);
+        // sibling directory sharing a name prefix
+        assert!(root_of("/repo-routing/apps/website/App.tsx").is_none());
+
+        assert!(remove_root("/repo-routing/apps/web/legacy/"));
+        assert!(!remove_root("/repo-routing/apps/web/legacy"));
+        assert_eq!(root_of("/repo-routing/apps/web/legacy/Old.tsx").unwrap().theme_handle, web);
+        remove_root("/repo-routing/apps/web");
+        remove_root("C:/repo-routing/apps/admin");
+    }
+
+    #[test]
+    fn updates_index_under_the_project_theme() {
+        let handle = theme::register(Theme::default());
+        add_root("/repo-updates/apps/web", handle);
+        let routed = update_files(&[snippet("/repo-updates/apps/web/App.tsx"), snippet("/elsewhere/App.tsx")]);
+        assert_eq!(routed[0].root.as_ref().unwrap().theme_handle, handle);
+        assert_eq!(routed[0].regions, 1);
+        assert!(region_index::indexed_file(handle, "/repo-updates/apps/web/App.tsx").is_some());
+        assert!(routed[1].root.is_none());
+
+        // releasing the theme drops its roots
+        release_theme(handle);
+        assert!(root_of("/repo-updates/apps/web/App.tsx").is_none());
+    }
+}
@@ -1,5 +1,6 @@
 #!/usr/bin/env node
 import { Command } from 'commander';
+import { readFileSync } from 'node:fs';
 import { resolve } from 'node:path';
 import { loadConfig } from '../config/loader.js';
 import { runAudit, type PipelineOptions } from '../core/pipeline.js';
@@ -29,6 +30,7 @@ program
   .option('--max-suggestions <n>', 'Maximum suggestions per violation (default: 3)')
   .option('--cva', 'Enable CVA variant expansion for static analysis')
   .option('--check-all-variants', 'Check all CVA variant combinations (not just defaults)')
+  .option('--stdin-filepath <path>', 'Audit source read from stdin as this file; print the report to stdout')
   .action(async (opts) => {
     try {
       // 1. Load config file (if any), then merge CLI flags as overrides
@@ -50,6 +52,7 @@ program
       const baselinePath: string =
         (opts.baselinePath as string | undefined) ?? fileConfig.baseline?.path ?? '.a11y-baseline.json';
       const baselineEnabled: boolean = fileConfig.baseline?.enabled ?? false;
+      const stdinPath = opts.stdinFilepath as string | undefined;
 
       const suggestEnabled: boolean =
         opts.suggest === true || (fileConfig.suggestions?.enabled ?? false);
@@ -91,18 +94,31 @@ program
           path: baselinePath,
           updateBaseline,
           failOnImprovement,
+          caseInsensitivePaths: fileConfig.baseline?.caseInsensitivePaths ?? false,
         } : undefined,
         suggestions: suggestEnabled ? {
           enabled: true,
           maxSuggestions,
+          constraints: {
+            families: fileConfig.suggestions?.families,
+            exclude: fileConfig.suggestions?.exclude,
+          },
         } : undefined,
         cva: cvaEnabled ? {
           enabled: true,
           checkAllVariants,
         } : undefined,
+        stdin: stdinPath !== undefined ? { path: stdinPath, content: readFileSync(0, 'utf-8') } : undefined,
       };
 
-      const { totalViolations, baselineSummary, baselineUpdated, results } = runAudit(pipelineOpts);
+      const { totalViolations, baselineSummary, baselineUpdated, results, report } = runAudit(pipelineOpts);
+
+      // Stdin audits (editors, git hooks): the report is the only stdout output
+      if (stdinPath !== undefined) {
+        process.stdout.write(report);
+        const failing = baselineSummary ? baselineSummary.newCount : totalViolations;
+        process.exit(failing > 0 ? 1 : 0);
+      }
 
       if (baselineUpdated) {
         console.log(`[a11y-audit] Baseline updated: ${totalViolations} violations baselined.`);
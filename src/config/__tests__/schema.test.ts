@@ -45,6 +45,7 @@ describe('auditConfigSchema', () => {
     expect(result.baseline).toEqual({
       enabled: false,
       path: '.a11y-baseline.json',
+      caseInsensitivePaths: false,
     });
   });
 
@@ -121,6 +122,18 @@ describe('auditConfigSchema', () => {
       });
     });
 
+    it('accepts family and exclusion constraints', () => {
+      const result = auditConfigSchema.parse({
+        suggestions: { enabled: true, families: ['gray', 'brand'], exclude: ['#000000'] },
+      });
+      expect(result.suggestions?.families).toEqual(['gray', 'brand']);
+      expect(result.suggestions?.exclude).toEqual(['#000000']);
+    });
+
+    it('rejects an empty families list', () => {
+      expect(() => auditConfigSchema.parse({ suggestions: { families: [] } })).toThrow();
+    });
+
     it('suggestions field is optional', () => {
       const result = auditConfigSchema.parse({});
       expect(result.suggestions).toBeUndefined();
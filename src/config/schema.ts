@@ -46,6 +46,8 @@ export const auditConfigSchema = z.object({
     enabled: z.boolean().default(false),
     /** Path to baseline file (relative to project root) */
     path: z.string().default('.a11y-baseline.json'),
+    /** Match file paths case-insensitively (mixed macOS/Windows/Linux checkouts) */
+    caseInsensitivePaths: z.boolean().default(false),
   }).optional(),
 
   /** Suggestion engine for auto-fix hints */
@@ -54,6 +56,10 @@ export const auditConfigSchema = z.object({
     enabled: z.boolean().default(false),
     /** Maximum suggestions per violation */
     maxSuggestions: z.number().min(1).max(10).default(3),
+    /** Only suggest shades from these families, e.g. ['gray', 'brand'] (default: the violating class's family) */
+    families: z.array(z.string()).min(1).optional(),
+    /** Never suggest these classes or colors, e.g. ['gray-950', '#000000'] */
+    exclude: z.array(z.string()).optional(),
   }).optional(),
 
   /** CVA variant expansion for static analysis */
@@ -11,6 +11,7 @@ vi.mock('node:fs', () => ({
   existsSync: vi.fn(),
   readFileSync: vi.fn(),
   writeFileSync: vi.fn(),
+  renameSync: vi.fn(),
 }));
 
 import { existsSync, readFileSync, writeFileSync } from 'node:fs';
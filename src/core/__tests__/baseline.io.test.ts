@@ -5,10 +5,11 @@ vi.mock('node:fs', () => ({
   existsSync: vi.fn(),
   readFileSync: vi.fn(),
   writeFileSync: vi.fn(),
+  renameSync: vi.fn(),
 }));
 
-import { existsSync, readFileSync, writeFileSync } from 'node:fs';
-import { loadBaseline, saveBaseline } from '../baseline.js';
+import { existsSync, readFileSync, renameSync, writeFileSync } from 'node:fs';
+import { baselineChecksum, generateViolationHash, loadBaseline, readBaseline, saveBaseline } from '../baseline.js';
 
 function makeViolation(overrides: Partial<ContrastResult> = {}): ContrastResult {
   return {
@@ -59,6 +60,71 @@ describe('loadBaseline', () => {
     vi.mocked(readFileSync).mockReturnValue(JSON.stringify({ version: '1.0' }));
     expect(loadBaseline('/path/.a11y-baseline.json')).toBeNull();
   });
+
+  test('returns null when the checksum does not match the violations', () => {
+    const violations = { 'src/Button.tsx': { abc123: 2 } };
+    const data: BaselineData = {
+      version: '1.2.0',
+      generatedAt: '2026-02-14T00:00:00.000Z',
+      checksum: baselineChecksum(violations),
+      violations: { 'src/Button.tsx': { abc123: 1 } },
+    };
+    vi.mocked(existsSync).mockReturnValue(true);
+    vi.mocked(readFileSync).mockReturnValue(JSON.stringify(data));
+    expect(loadBaseline('/path/.a11y-baseline.json')).toBeNull();
+
+    vi.mocked(readFileSync).mockReturnValue(JSON.stringify({ ...data, violations }));
+    expect(loadBaseline('/path/.a11y-baseline.json')).not.toBeNull();
+  });
+
+  test('checksum ignores the order of file and hash keys', () => {
+    const violations = { 'src/A.tsx': { aaa: 1, bbb: 2 }, 'src/B.tsx': { ccc: 1 } };
+    const reordered = { 'src/B.tsx': { ccc: 1 }, 'src/A.tsx': { bbb: 2, aaa: 1 } };
+    expect(baselineChecksum(reordered)).toBe(baselineChecksum(violations));
+
+    const data: BaselineData = {
+      version: '1.2.0',
+      generatedAt: '2026-02-14T00:00:00.000Z',
+      checksum: baselineChecksum(violations),
+      violations: reordered,
+    };
+    vi.mocked(existsSync).mockReturnValue(true);
+    vi.mocked(readFileSync).mockReturnValue(JSON.stringify(data));
+    expect(loadBaseline('/path/.a11y-baseline.json')).toEqual(data);
+  });
+
+  test('readBaseline tells a missing file from a corrupt one', () => {
+    vi.mocked(existsSync).mockReturnValue(false);
+    expect(readBaseline('/path/.a11y-baseline.json')).toEqual({ status: 'missing' });
+
+    vi.mocked(existsSync).mockReturnValue(true);
+    vi.mocked(readFileSync).mockReturnValue(JSON.stringify({
+      version: '1.2.0',
+      generatedAt: '',
+      checksum: 'stale',
+      violations: { 'src/A.tsx': { aaa: 1 } },
+    }));
+    expect(readBaseline('/path/.a11y-baseline.json')).toEqual({
+      status: 'invalid',
+      reason: 'checksum mismatch (edited or truncated file)',
+    });
+
+    vi.mocked(readFileSync).mockImplementation(() => {
+      throw new Error('EACCES: permission denied');
+    });
+    expect(readBaseline('/path/.a11y-baseline.json')).toEqual({
+      status: 'invalid',
+      reason: 'EACCES: permission denied',
+    });
+  });
+
+  test('returns null for another major format version', () => {
+    vi.mocked(existsSync).mockReturnValue(true);
+    vi.mocked(readFileSync).mockReturnValue(
+      JSON.stringify({ version: '2.0.0', generatedAt: '', violations: {} }),
+    );
+    expect(loadBaseline('/path/.a11y-baseline.json')).toBeNull();
+  });
 });
 
 describe('saveBaseline', () => {
@@ -73,7 +139,11 @@ describe('saveBaseline', () => {
     expect(writeFileSync).toHaveBeenCalledOnce();
     const content = vi.mocked(writeFileSync).mock.calls[0]![1] as string;
     const parsed = JSON.parse(content) as BaselineData;
-    expect(parsed.version).toBe('1.1.0');
+    expect(parsed.version).toBe('1.2.0');
+    expect(parsed.checksum).toBe(baselineChecksum(parsed.violations));
+    // Written to a temporary sibling, then renamed over the baseline
+    expect(vi.mocked(writeFileSync).mock.calls[0]![0]).toBe(`/path/.a11y-baseline.json.tmp-${process.pid}`);
+    expect(renameSync).toHaveBeenCalledWith(`/path/.a11y-baseline.json.tmp-${process.pid}`, '/path/.a11y-baseline.json');
     expect(Object.keys(parsed.violations)).toEqual(['src/A.tsx', 'src/Z.tsx']);
   });
 
@@ -87,4 +157,19 @@ describe('saveBaseline', () => {
     const count = Object.values(fileCounts)[0];
     expect(count).toBe(2);
   });
+
+  test('groups by /-separated paths and records case folding', () => {
+    const violations = [
+      makeViolation({ file: 'src\\components\\Button.tsx' }),
+      makeViolation({ file: 'src/components/Button.tsx' }),
+    ];
+    saveBaseline('/path/.a11y-baseline.json', violations, { caseInsensitivePaths: true });
+
+    const content = vi.mocked(writeFileSync).mock.calls[0]![1] as string;
+    const parsed = JSON.parse(content) as BaselineData;
+    expect(parsed.caseInsensitivePaths).toBe(true);
+    expect(parsed.violations).toEqual({
+      'src/components/Button.tsx': { [generateViolationHash(violations[1]!, true)]: 2 },
+    });
+  });
 });
@@ -81,9 +81,37 @@ describe('generateViolationHash', () => {
     const v2 = makeViolation({ pairType: undefined, interactiveState: undefined });
     expect(generateViolationHash(v1)).toBe(generateViolationHash(v2));
   });
+
+  test('hash is stable across path separators', () => {
+    const posix = makeViolation({ file: 'src/components/Button.tsx' });
+    const windows = makeViolation({ file: 'src\\components\\Button.tsx' });
+    const dotted = makeViolation({ file: './src/components/Button.tsx' });
+    expect(generateViolationHash(windows)).toBe(generateViolationHash(posix));
+    expect(generateViolationHash(dotted)).toBe(generateViolationHash(posix));
+  });
+
+  test('path case only matters without caseInsensitivePaths', () => {
+    const v1 = makeViolation({ file: 'src/components/Button.tsx' });
+    const v2 = makeViolation({ file: 'src/Components/button.tsx' });
+    expect(generateViolationHash(v1)).not.toBe(generateViolationHash(v2));
+    expect(generateViolationHash(v1, true)).toBe(generateViolationHash(v2, true));
+  });
 });
 
 describe('reconcileViolations', () => {
+  test('uses the path case mode the baseline was written with', () => {
+    const baseline = buildBaseline([makeViolation({ file: 'src/Button.tsx' })]);
+    const current = [makeViolation({ file: 'SRC/button.tsx' })];
+    expect(reconcileViolations(current, baseline).knownCount).toBe(0);
+
+    const folded: BaselineData = {
+      ...baseline,
+      caseInsensitivePaths: true,
+      violations: { 'src/Button.tsx': { [generateViolationHash(makeViolation({ file: 'src/Button.tsx' }), true)]: 1 } },
+    };
+    expect(reconcileViolations(current, folded).knownCount).toBe(1);
+  });
+
   test('no baseline → all violations are new', () => {
     const violations = [makeViolation(), makeViolation({ file: 'src/Other.tsx' })];
     const result = reconcileViolations(violations, null);
@@ -258,6 +258,54 @@ describe('generateSuggestions', () => {
   });
 });
 
+describe('generateSuggestions with constraints', () => {
+  const palette: RawPalette = new Map([
+    ...makeGrayPalette(),
+    ['--color-red-400', '#f87171'],
+    ['--color-red-600', '#dc2626'],
+    ['--color-red-700', '#b91c1c'],
+    ['--color-brand-400', '#60a5fa'],
+    ['--color-brand-700', '#1d4ed8'],
+    ['--color-brand-950', '#000000'],
+  ]);
+  const families = extractShadeFamilies(palette);
+
+  test('own family is kept when it is allowed', () => {
+    const violation = makeViolation({ textClass: 'text-gray-400', textHex: '#9ca3af' });
+    const suggestions = generateSuggestions(violation, families, 'AA', 'light', 10, {
+      families: ['gray', 'brand'],
+    });
+    expect(suggestions.length).toBeGreaterThan(0);
+    expect(suggestions.every((s) => s.suggestedClass.startsWith('text-gray-'))).toBe(true);
+  });
+
+  test('off-brand family is redirected to the allowed families', () => {
+    const violation = makeViolation({ textClass: 'text-red-400', textHex: '#f87171' });
+    const suggestions = generateSuggestions(violation, families, 'AA', 'light', 10, {
+      families: ['brand'],
+    });
+    expect(suggestions.map((s) => s.suggestedClass)).toEqual(['text-brand-700', 'text-brand-950']);
+  });
+
+  test('excluded colors and classes are never suggested', () => {
+    const violation = makeViolation({ textClass: 'text-red-400', textHex: '#f87171' });
+    const suggestions = generateSuggestions(violation, families, 'AA', 'light', 10, {
+      families: ['brand', 'gray'],
+      exclude: ['#000', 'gray-950', 'text-gray-900'],
+    });
+    const classes = suggestions.map((s) => s.suggestedClass);
+    expect(classes).toContain('text-brand-700');
+    expect(classes).not.toContain('text-brand-950');
+    expect(classes).not.toContain('text-gray-950');
+    expect(classes).not.toContain('text-gray-900');
+  });
+
+  test('no allowed family in the palette yields no suggestions', () => {
+    const violation = makeViolation({ textClass: 'text-red-400', textHex: '#f87171' });
+    expect(generateSuggestions(violation, families, 'AA', 'light', 3, { families: ['teal'] })).toEqual([]);
+  });
+});
+
 describe('suggestion engine integration', () => {
   test('produces correct results for real gray palette', () => {
     const palette: RawPalette = new Map([
@@ -1,54 +1,106 @@
 import { createHash } from 'node:crypto';
-import { existsSync, readFileSync, writeFileSync } from 'node:fs';
+import { existsSync, readFileSync, renameSync, writeFileSync } from 'node:fs';
 import type { BaselineData, BaselineSummary, ContrastResult } from './types.js';
+import { pathKey, toPortablePath } from './paths.js';
 
 /**
  * Generates a content-addressable hash for a violation.
  * Excludes line numbers and ratio for refactoring stability.
  * Class names are sorted alphabetically for reordering stability.
  * Theme mode is NOT included — the flat baseline tracks combined counts.
+ * The file path is separator-normalized, and case-folded when `caseInsensitivePaths`.
  * @internal
  */
-export function generateViolationHash(violation: ContrastResult): string {
+export function generateViolationHash(violation: ContrastResult, caseInsensitivePaths = false): string {
   const bgSorted = violation.bgClass.split(/\s+/).sort().join(' ');
   const fgSorted = violation.textClass.split(/\s+/).sort().join(' ');
   const pairType = violation.pairType ?? 'text';
   const state = violation.interactiveState ?? 'base';
 
-  const identity = [violation.file, bgSorted, fgSorted, pairType, state].join('::');
+  const identity = [pathKey(violation.file, caseInsensitivePaths), bgSorted, fgSorted, pairType, state].join('::');
 
   return createHash('sha256').update(identity).digest('hex');
 }
 
+/** Baseline format version written by saveBaseline(); other major versions are not read. */
+const BASELINE_VERSION = '1.2.0';
+
 /**
- * Loads a baseline file from disk. Returns null if missing or invalid.
+ * Checksum of the baseline's violation counts, so a truncated or mangled
+ * file (e.g. from a partial CI cache restore) is detected on load.
+ * File and hash keys are sorted first: reordering the JSON keeps it valid.
+ * @internal
  */
-export function loadBaseline(baselinePath: string): BaselineData | null {
-  if (!existsSync(baselinePath)) return null;
+export function baselineChecksum(violations: BaselineData['violations']): string {
+  const canonical = Object.keys(violations).sort().map(file => {
+    const hashes = violations[file]!;
+    return [file, Object.keys(hashes).sort().map(hash => [hash, hashes[hash]])];
+  });
+  return createHash('sha256').update(JSON.stringify(canonical)).digest('hex');
+}
+
+/** Outcome of reading a baseline file (see readBaseline()). */
+export type BaselineLoad =
+  | { status: 'loaded'; data: BaselineData }
+  | { status: 'missing' }
+  | { status: 'invalid'; reason: string };
 
+/**
+ * Reads a baseline file from disk, telling a missing file apart from one that
+ * is unreadable, invalid, from another major format version or failing its
+ * checksum. Baselines written before checksums were added are accepted as-is.
+ */
+export function readBaseline(baselinePath: string): BaselineLoad {
+  if (!existsSync(baselinePath)) return { status: 'missing' };
+
+  let data: BaselineData;
   try {
-    const raw = readFileSync(baselinePath, 'utf-8');
-    const data = JSON.parse(raw) as BaselineData;
-    if (!data.version || !data.violations) return null;
-    return data;
-  } catch {
-    return null;
+    data = JSON.parse(readFileSync(baselinePath, 'utf-8')) as BaselineData;
+  } catch (err) {
+    return { status: 'invalid', reason: err instanceof Error ? err.message : String(err) };
+  }
+  if (!data?.version || !data.violations) {
+    return { status: 'invalid', reason: 'missing version or violations' };
   }
+  if (data.version.split('.')[0] !== BASELINE_VERSION.split('.')[0]) {
+    return { status: 'invalid', reason: `format version ${data.version} is not readable by ${BASELINE_VERSION}` };
+  }
+  if (data.checksum && data.checksum !== baselineChecksum(data.violations)) {
+    return { status: 'invalid', reason: 'checksum mismatch (edited or truncated file)' };
+  }
+  return { status: 'loaded', data };
+}
+
+/**
+ * Loads a baseline file from disk. Returns null if missing or invalid (see
+ * readBaseline()), so the run degrades to reporting every violation as new
+ * instead of erroring.
+ */
+export function loadBaseline(baselinePath: string): BaselineData | null {
+  const load = readBaseline(baselinePath);
+  return load.status === 'loaded' ? load.data : null;
+}
+
+export interface SaveBaselineOptions {
+  /** Match file paths case-insensitively (recorded in the baseline, used on reconcile) */
+  caseInsensitivePaths?: boolean;
 }
 
 /**
  * Saves current violations as a baseline file.
- * Groups hashes by file for diff-friendly JSON output.
+ * Groups hashes by file (`/`-separated) for diff-friendly JSON output.
  */
 export function saveBaseline(
   baselinePath: string,
   violations: ContrastResult[],
+  options: SaveBaselineOptions = {},
 ): void {
+  const caseInsensitivePaths = options.caseInsensitivePaths ?? false;
   const byFile: Record<string, Record<string, number>> = {};
 
   for (const violation of violations) {
-    const hash = generateViolationHash(violation);
-    const file = violation.file;
+    const hash = generateViolationHash(violation, caseInsensitivePaths);
+    const file = toPortablePath(violation.file);
     byFile[file] ??= {};
     byFile[file]![hash] = (byFile[file]![hash] ?? 0) + 1;
   }
@@ -60,12 +112,17 @@ export function saveBaseline(
   }
 
   const data: BaselineData = {
-    version: '1.1.0',
+    version: BASELINE_VERSION,
     generatedAt: new Date().toISOString(),
+    checksum: baselineChecksum(sorted),
+    ...(caseInsensitivePaths ? { caseInsensitivePaths } : {}),
     violations: sorted,
   };
 
-  writeFileSync(baselinePath, JSON.stringify(data, null, 2) + '\n', 'utf-8');
+  // Write a sibling and rename it into place: a crash never leaves a half-written baseline
+  const tmpPath = `${baselinePath}.tmp-${process.pid}`;
+  writeFileSync(tmpPath, JSON.stringify(data, null, 2) + '\n', 'utf-8');
+  renameSync(tmpPath, baselinePath);
 }
 
 export interface ReconciliationResult extends BaselineSummary {
@@ -76,6 +133,7 @@ export interface ReconciliationResult extends BaselineSummary {
  * Annotates each violation as baseline (known) or new.
  * Uses leaky-bucket counting: per hash, consumes baseline count in input order.
  * Preserves input array order for downstream theme redistribution.
+ * Paths are compared the way the baseline was written (see `caseInsensitivePaths`).
  */
 export function reconcileViolations(
   violations: ContrastResult[],
@@ -108,8 +166,9 @@ export function reconcileViolations(
   let knownCount = 0;
   const annotated: ContrastResult[] = [];
 
+  const caseInsensitivePaths = baseline.caseInsensitivePaths ?? false;
   for (const v of violations) {
-    const hash = generateViolationHash(v);
+    const hash = generateViolationHash(v, caseInsensitivePaths);
     const remaining = remainingCounts.get(hash) ?? 0;
 
     if (remaining > 0) {
@@ -0,0 +1,19 @@
+/**
+ * Portable file paths for reports and baselines.
+ *
+ * Paths are reported with `/` separators on every platform, so a baseline
+ * generated on Windows matches CI on Linux. Case folding (for teams mixing
+ * case-insensitive macOS/Windows checkouts) only applies to identity keys,
+ * never to the paths shown in reports.
+ */
+
+/** `path` with `/` separators and no leading `./`. */
+export function toPortablePath(path: string): string {
+  return path.replace(/\\/g, '/').replace(/^(\.\/)+/, '');
+}
+
+/** Identity key of `path` for baseline matching. */
+export function pathKey(path: string, caseInsensitive = false): string {
+  const portable = toPortablePath(path);
+  return caseInsensitive ? portable.toLowerCase() : portable;
+}
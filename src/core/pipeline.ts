@@ -1,21 +1,22 @@
 import { existsSync, mkdirSync, readFileSync, writeFileSync } from 'node:fs';
 import { resolve, relative } from 'node:path';
 import { globSync } from 'glob';
-import type { AuditResult, SkippedClass, ThemeMode } from './types.js';
+import type { AuditResult, ColorPair, HealthScore, SkippedClass, ThemeMode } from './types.js';
 import type { ContainerConfig } from '../plugins/interfaces.js';
 import { buildThemeColorMaps, type TailwindResolverOptions } from '../plugins/tailwind/css-resolver.js';
-import { extractAllFileRegions, resolveFileRegions } from '../plugins/jsx/region-resolver.js';
+import { extractAllFileRegions, extractSourceRegions, resolveFileRegions } from '../plugins/jsx/region-resolver.js';
 import type { PreExtracted } from '../plugins/jsx/region-resolver.js';
 import { checkAllPairs } from './contrast-checker.js';
 import { generateReport } from './report/markdown.js';
 import { generateJsonReport } from './report/json.js';
 import { isNativeAvailable, getNativeModule } from '../native/index.js';
 import { convertNativeResult } from '../native/converter.js';
-import { loadBaseline, saveBaseline, reconcileViolations } from './baseline.js';
+import { readBaseline, saveBaseline, reconcileViolations } from './baseline.js';
+import { pathKey, toPortablePath } from './paths.js';
 import { extractShadeFamilies, generateSuggestions } from './suggestions.js';
 import { extractTailwindPalette } from '../plugins/tailwind/palette.js';
 import { expandCvaInPreExtracted } from '../plugins/jsx/cva-expander.js';
-import type { BaselineSummary } from './types.js';
+import type { BaselineData, BaselineSummary, SuggestionConstraints } from './types.js';
 
 const MAX_REPORT_COUNTER = 100;
 
@@ -71,12 +72,16 @@ export interface PipelineOptions {
     path: string;
     updateBaseline: boolean;
     failOnImprovement: boolean;
+    /** Case-fold file paths in baseline hashes */
+    caseInsensitivePaths?: boolean;
   };
 
   /** Suggestion engine configuration */
   suggestions?: {
     enabled: boolean;
     maxSuggestions: number;
+    /** Allowed families / excluded classes and colors */
+    constraints?: SuggestionConstraints;
   };
 
   /** CVA variant expansion configuration */
@@ -84,6 +89,17 @@ export interface PipelineOptions {
     enabled: boolean;
     checkAllVariants: boolean;
   };
+
+  /**
+   * Audit this in-memory file instead of the `src` globs (editor buffers,
+   * git hooks). The report is returned but not written to `reportDir`, and
+   * baseline reconciliation only considers the file's own entries.
+   */
+  stdin?: {
+    /** Path the content belongs to (relative to cwd or absolute) */
+    path: string;
+    content: string;
+  };
 }
 
 function log(verbose: boolean | undefined, msg: string): void {
@@ -135,6 +151,11 @@ export function runAudit(options: PipelineOptions): AuditRunResult {
     verbose,
   } = options;
 
+  if (options.stdin && options.baseline?.updateBaseline) {
+    // A one-file baseline would drop every other file's entries
+    throw new Error('Baseline updates need a full audit, not a stdin audit');
+  }
+
   // Phase 0: Build color maps
   log(verbose, '[a11y-audit] Building color maps...');
   const resolverOpts: TailwindResolverOptions = { cssPaths: css, palettePath };
@@ -144,10 +165,19 @@ export function runAudit(options: PipelineOptions): AuditRunResult {
 
   // Phase 1: Extract once (theme-agnostic file I/O + state machine parsing)
   let preExtracted: PreExtracted;
+  const stdinFile = options.stdin && {
+    path: toPortablePath(relative(cwd, resolve(cwd, options.stdin.path))),
+    content: options.stdin.content,
+  };
 
-  if (isNativeAvailable()) {
+  if (stdinFile) {
+    log(verbose, `[a11y-audit] Extracting regions of ${stdinFile.path} (stdin)...`);
+    preExtracted = isNativeAvailable()
+      ? extractWithNativeEngine({ fileContents: [stdinFile], readErrors: [], filesScanned: 1 }, containerConfig)
+      : extractSourceRegions([stdinFile], containerConfig.containers, containerConfig.defaultBg);
+  } else if (isNativeAvailable()) {
     log(verbose, '[a11y-audit] Extracting file regions (native Rust engine)...');
-    preExtracted = extractWithNativeEngine(src, cwd, containerConfig, verbose);
+    preExtracted = extractWithNativeEngine(readSourceFiles(src, cwd, verbose), containerConfig);
   } else {
     log(verbose, '[a11y-audit] Extracting file regions (legacy TypeScript engine)...');
     log(verbose, '  ⚠ Native module not available. Disabled detection (US-07) and currentColor resolution (US-08) will be skipped.');
@@ -186,6 +216,10 @@ export function runAudit(options: PipelineOptions): AuditRunResult {
     log(verbose, `[a11y-audit] Checking contrast (${mode} mode)...`);
     const result = checkAllPairs(pairs, skipped, filesScanned, mode, threshold);
     log(verbose, `  ${result.violations.length} violations, ${result.passed.length} passed`);
+    result.health = healthScore(pairs, mode, threshold);
+    if (result.health) {
+      log(verbose, `  Health score: ${result.health.score}/100`);
+    }
 
     results.push({ mode, result });
   }
@@ -201,6 +235,7 @@ export function runAudit(options: PipelineOptions): AuditRunResult {
       for (const violation of result.violations) {
         violation.suggestions = generateSuggestions(
           violation, shadeFamilies, threshold, mode, maxSuggestions,
+          options.suggestions.constraints,
         );
       }
     }
@@ -221,13 +256,17 @@ export function runAudit(options: PipelineOptions): AuditRunResult {
     if (options.baseline.updateBaseline) {
       log(verbose, '[a11y-audit] Updating baseline...');
       const allViolations = results.flatMap(r => r.result.violations);
-      saveBaseline(resolvedPath, allViolations);
+      saveBaseline(resolvedPath, allViolations, {
+        caseInsensitivePaths: options.baseline.caseInsensitivePaths,
+      });
       log(verbose, `  Baseline updated: ${allViolations.length} violations across ${new Set(allViolations.map(v => v.file)).size} files`);
       log(verbose, `  Saved to: ${options.baseline.path}`);
       baselineUpdated = true;
     } else if (options.baseline.enabled) {
       log(verbose, '[a11y-audit] Loading baseline...');
-      const baseline = loadBaseline(resolvedPath);
+      const load = readBaseline(resolvedPath);
+      const loaded = load.status === 'loaded' ? load.data : null;
+      const baseline = loaded && stdinFile ? baselineForFile(loaded, stdinFile.path) : loaded;
 
       if (baseline) {
         const allViolations = results.flatMap(r => r.result.violations);
@@ -248,6 +287,9 @@ export function runAudit(options: PipelineOptions): AuditRunResult {
           baselineTotal: reconciled.baselineTotal,
         };
         log(verbose, `  Baseline: ${reconciled.baselineTotal} total, ${reconciled.newCount} new, ${reconciled.knownCount} known, ${reconciled.fixedCount} fixed`);
+      } else if (load.status === 'invalid') {
+        // Not verbose-only: every known violation now counts as new and may fail CI
+        console.warn(`[a11y-audit] ⚠ Baseline ${options.baseline.path} ignored (${load.reason}) — all violations treated as new`);
       } else {
         log(verbose, '  ⚠ Baseline file not found — all violations treated as new');
       }
@@ -260,13 +302,15 @@ export function runAudit(options: PipelineOptions): AuditRunResult {
     ? generateJsonReport(results, baselineSummary)
     : generateReport(results, baselineSummary);
 
-  // Write report to disk
-  const resolvedReportDir = resolve(cwd, reportDir);
-  const outputPath = getOutputPath(resolvedReportDir, format);
-  writeFileSync(outputPath, report, 'utf-8');
+  // Write report to disk (stdin audits only return it)
+  if (!stdinFile) {
+    const resolvedReportDir = resolve(cwd, reportDir);
+    const outputPath = getOutputPath(resolvedReportDir, format);
+    writeFileSync(outputPath, report, 'utf-8');
 
-  const relPath = relative(cwd, outputPath);
-  log(verbose, `Report saved to: ${relPath}`);
+    const relPath = relative(cwd, outputPath);
+    log(verbose, `Report saved to: ${relPath}`);
+  }
 
   const totalViolations = results.reduce((s, r) => s + r.result.violations.length, 0);
 
@@ -274,30 +318,52 @@ export function runAudit(options: PipelineOptions): AuditRunResult {
 }
 
 /**
- * Reads files via glob + readFileSync, then passes content to the Rust
- * native engine for parallel parsing. Converts the flat Rust output back
- * to the TS PreExtracted format for downstream resolution.
+ * Color health score of `pairs`, computed by the native engine (unset
+ * without it).
  */
-function extractWithNativeEngine(
-  srcPatterns: string[],
-  cwd: string,
-  containerConfig: ContainerConfig,
-  verbose: boolean | undefined,
-): PreExtracted {
+function healthScore(pairs: ColorPair[], mode: ThemeMode, threshold: 'AA' | 'AAA'): HealthScore | undefined {
+  if (!isNativeAvailable()) return undefined;
+  const pageBg = mode === 'light' ? '#ffffff' : '#09090b';
+  return getNativeModule().checkContrastPairsWithOptions(pairs, { threshold, pageBg }).health ?? undefined;
+}
+
+/**
+ * The baseline narrowed to `file`'s entries, so a single-file audit doesn't
+ * count every other file's baselined violations as fixed.
+ */
+function baselineForFile(baseline: BaselineData, file: string): BaselineData {
+  const caseInsensitive = baseline.caseInsensitivePaths ?? false;
+  const key = pathKey(file, caseInsensitive);
+  const violations = Object.fromEntries(
+    Object.entries(baseline.violations).filter(([path]) => pathKey(path, caseInsensitive) === key),
+  );
+  return { ...baseline, violations };
+}
+
+/** Source files read for the native engine. */
+interface SourceFiles {
+  fileContents: Array<{ path: string; content: string }>;
+  readErrors: SkippedClass[];
+  filesScanned: number;
+}
+
+/**
+ * Reads the files matched by `srcPatterns` (relative, `/`-separated paths).
+ * Unreadable files become `(file)` skip entries.
+ */
+function readSourceFiles(srcPatterns: string[], cwd: string, verbose: boolean | undefined): SourceFiles {
   const filePaths = srcPatterns.flatMap((pattern) =>
     globSync(pattern, { cwd, absolute: true }),
   );
 
   const fileContents: Array<{ path: string; content: string }> = [];
-  const sourceLines = new Map<string, string[]>();
   const readErrors: SkippedClass[] = [];
 
   for (const filePath of filePaths) {
-    const relPath = relative(cwd, filePath);
+    const relPath = toPortablePath(relative(cwd, filePath));
     try {
       const content = readFileSync(filePath, 'utf-8');
       fileContents.push({ path: relPath, content });
-      sourceLines.set(relPath, content.split('\n'));
     } catch (err) {
       const message = err instanceof Error ? err.message : String(err);
       log(verbose, `  Skipping ${relPath}: ${message}`);
@@ -310,6 +376,20 @@ function extractWithNativeEngine(
     }
   }
 
+  return { fileContents, readErrors, filesScanned: filePaths.length };
+}
+
+/**
+ * Passes source content to the Rust native engine for parallel parsing.
+ * Converts the flat Rust output back to the TS PreExtracted format for
+ * downstream resolution.
+ */
+function extractWithNativeEngine(
+  { fileContents, readErrors, filesScanned }: SourceFiles,
+  containerConfig: ContainerConfig,
+): PreExtracted {
+  const sourceLines = new Map<string, string[]>(fileContents.map(({ path, content }) => [path, content.split('\n')]));
+
   const containerEntries = Array.from(containerConfig.containers.entries()).map(
     ([component, bgClass]) => ({ component, bgClass }),
   );
@@ -325,5 +405,5 @@ function extractWithNativeEngine(
     defaultBg: containerConfig.defaultBg,
   });
 
-  return convertNativeResult(nativeResult, sourceLines, readErrors, filePaths.length);
+  return convertNativeResult(nativeResult, sourceLines, readErrors, filesScanned);
 }
@@ -60,6 +60,20 @@ describe('generateJsonReport', () => {
     expect(parsed.themes[1].pairsChecked).toBe(7);
   });
 
+  it('includes the health score of themes that have one', () => {
+    const health = {
+      score: 87, formulaVersion: 1, weightedPassRate: 0.9, suppressionRatio: 0.1, unresolvedRatio: 0.05,
+      checked: 20, suppressed: 2, unresolved: 1, suppressions: [{ reason: 'legacy', count: 2 }],
+    };
+    const result = generateJsonReport([
+      { mode: 'light', result: makeResult({ health }) },
+      { mode: 'dark', result: makeResult() },
+    ]);
+    const parsed = JSON.parse(result);
+    expect(parsed.themes[0].health).toEqual(health);
+    expect(parsed.themes[1]).not.toHaveProperty('health');
+  });
+
   it('includes timestamp', () => {
     vi.useFakeTimers();
     vi.setSystemTime(new Date('2026-02-13T12:00:00Z'));
@@ -91,6 +91,21 @@ describe('generateReport', () => {
     expect(report).not.toContain('## Ignored Violations');
   });
 
+  test('health score renders per theme in the summary', () => {
+    const health = {
+      score: 87, formulaVersion: 1, weightedPassRate: 0.9, suppressionRatio: 0.1, unresolvedRatio: 0.05,
+      checked: 20, suppressed: 2, unresolved: 1, suppressions: [{ reason: 'legacy', count: 2 }],
+    };
+    const input: ThemedAuditResult[] = [
+      { mode: 'light', result: makeResult({ health }) },
+      { mode: 'dark', result: makeResult() }
+    ];
+    const report = generateReport(input);
+
+    expect(report).toContain('| Health score (light) | 87/100 |');
+    expect(report).not.toContain('Health score (dark)');
+  });
+
   // ── Text violations ─────────────────────────────────────────────
 
   test('text violations render in correct table format', () => {
@@ -355,6 +370,34 @@ describe('generateReport', () => {
 
   // ── Snapshot test ───────────────────────────────────────────────
 
+  test('native remediation renders as a fix row', () => {
+    const remediation = 'replace `text-gray-400` with `text-gray-500` (ratio 4.83)';
+    const input: ThemedAuditResult[] = [
+      {
+        mode: 'light',
+        result: makeResult({
+          violations: [
+            makeViolation({ remediation }),
+            makeViolation({ line: 50, pairType: 'border', textClass: 'border-gray-200', remediation }),
+          ]
+        })
+      }
+    ];
+    const report = generateReport(input);
+
+    expect(report).toContain(`| | | | **Fix:** ${remediation} | | | | | | |`);
+    expect(report).toContain(`| | | | **Fix:** ${remediation} | | | |`);
+  });
+
+  test('native tag name is shown next to the line', () => {
+    const input: ThemedAuditResult[] = [
+      { mode: 'light', result: makeResult({ violations: [makeViolation({ line: 42, tagName: 'Button' })] }) }
+    ];
+    const report = generateReport(input);
+
+    expect(report).toContain('| 42 `<Button>` | base |');
+  });
+
   test('full report snapshot with mixed violations', () => {
     const textViolation = makeViolation();
     const borderViolation = makeViolation({
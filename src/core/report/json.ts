@@ -45,6 +45,7 @@ export function generateJsonReport(
         passed: result.passed,
         skipped: result.skipped,
         ignored: result.ignored,
+        ...(result.health ? { health: result.health } : {}),
       })),
     },
     null,
@@ -5,6 +5,12 @@ interface ThemedAuditResult {
   result: AuditResult
 }
 
+/** Line number with the element it's on and the result id when known, e.g. "42 `<Button>` `AA-TEXT-CONTRAST-3f9c0a1b2d4e`" */
+function lineWithTag(v: ContrastResult): string {
+  const line = v.tagName ? `${v.line} \`<${v.tagName}>\`` : `${v.line}`;
+  return v.id ? `${line} \`${v.id}\`` : line;
+}
+
 function renderTextViolationTable(
   violations: ContrastResult[],
   lines: string[],
@@ -17,6 +23,7 @@ function renderTextViolationTable(
     lines.push('| Line | State | Background | Foreground | Size | Ratio | AA | AAA | AA Large | APCA Lc |');
     lines.push('|------|:-----:|-----------|------------|:----:|------:|:---:|:---:|:--------:|--------:|');
     for (const v of fileViolations) {
+      const lineLabel = lineWithTag(v);
       const stateLabel = v.interactiveState ?? 'base';
       const annotationMark = v.contextSource === 'annotation' ? '†' : '';
       if (annotationMark) trackAnnotations.value = true;
@@ -28,7 +35,7 @@ function renderTextViolationTable(
       const aaLargeIcon = v.passAALarge ? 'PASS' : '**FAIL**';
       const apcaLabel = v.apcaLc != null ? `${v.apcaLc}` : '—';
       lines.push(
-        `| ${v.line} | ${stateLabel} | ${bgLabel} | ${fgLabel} | ${sizeLabel} | ${v.ratio}:1 | ${aaIcon} | ${aaaIcon} | ${aaLargeIcon} | ${apcaLabel} |`
+        `| ${lineLabel} | ${stateLabel} | ${bgLabel} | ${fgLabel} | ${sizeLabel} | ${v.ratio}:1 | ${aaIcon} | ${aaaIcon} | ${aaLargeIcon} | ${apcaLabel} |`
       );
       // Suggestion row
       if (v.suggestions && v.suggestions.length > 0) {
@@ -37,6 +44,9 @@ function renderTextViolationTable(
           .join(' or ');
         lines.push(`| | | | **Suggestion:** use ${hints} | | | | | | |`);
       }
+      if (v.remediation) {
+        lines.push(`| | | | **Fix:** ${v.remediation} | | | | | | |`);
+      }
     }
     lines.push('');
   }
@@ -54,6 +64,7 @@ function renderNonTextViolationTable(
     lines.push('| Line | State | Type | Element | Against | Ratio | 3:1 |');
     lines.push('|------|:-----:|:----:|---------|---------|------:|:---:|');
     for (const v of fileViolations) {
+      const lineLabel = lineWithTag(v);
       const stateLabel = v.interactiveState ?? 'base';
       const typeLabel = v.pairType ?? 'border';
       const annotationMark = v.contextSource === 'annotation' ? '†' : '';
@@ -62,7 +73,7 @@ function renderNonTextViolationTable(
       const againstLabel = `${v.bgClass}${annotationMark} (${v.bgHex})`;
       const passIcon = v.passAALarge ? 'PASS' : '**FAIL**';
       lines.push(
-        `| ${v.line} | ${stateLabel} | ${typeLabel} | ${elementLabel} | ${againstLabel} | ${v.ratio}:1 | ${passIcon} |`
+        `| ${lineLabel} | ${stateLabel} | ${typeLabel} | ${elementLabel} | ${againstLabel} | ${v.ratio}:1 | ${passIcon} |`
       );
       // Suggestion row
       if (v.suggestions && v.suggestions.length > 0) {
@@ -71,6 +82,9 @@ function renderNonTextViolationTable(
           .join(' or ');
         lines.push(`| | | | **Suggestion:** use ${hints} | | | |`);
       }
+      if (v.remediation) {
+        lines.push(`| | | | **Fix:** ${v.remediation} | | | |`);
+      }
     }
     lines.push('');
   }
@@ -132,6 +146,12 @@ export function generateReport(
   lines.push(`| Ignored (a11y-ignore) | ${totalIgnored} |`);
   lines.push(`| Skipped (dynamic/unresolvable) | ${totalSkipped} |`);
 
+  for (const { mode, result } of results) {
+    if (result.health) {
+      lines.push(`| Health score (${mode}) | ${result.health.score}/100 |`);
+    }
+  }
+
   if (baselineSummary) {
     lines.push(`| **New violations** | **${baselineSummary.newCount}** |`);
     lines.push(`| Baseline violations | ${baselineSummary.knownCount} |`);
@@ -6,6 +6,7 @@ import type {
   ContrastResult,
   RawPalette,
   ShadeFamily,
+  SuggestionConstraints,
   ThemeMode,
 } from './types.js';
 import { compositeOver } from './contrast-checker.js';
@@ -76,6 +77,34 @@ const PAGE_BG_DARK = '#09090b';
 
 const DEFAULT_MAX_SUGGESTIONS = 3;
 
+/**
+ * Families to search for a violating class: its own family unless
+ * `constraints.families` is set and doesn't list it, in which case every
+ * listed family present in the palette.
+ */
+function searchFamilies(
+  own: string,
+  families: Map<string, ShadeFamily>,
+  constraints?: SuggestionConstraints,
+): ShadeFamily[] {
+  const allowed = constraints?.families;
+  const names = !allowed || allowed.includes(own) ? [own] : allowed;
+  return names.flatMap((name) => families.get(name) ?? []);
+}
+
+/** Whether a candidate matches an `exclude` entry (class with or without prefix, or color). */
+function isExcluded(className: string, prefix: string, hex: string, exclude: string[]): boolean {
+  const bare = className.slice(prefix.length);
+  const normalizedHex = colord(hex).toHex();
+  return exclude.some((entry) => {
+    const value = entry.trim().toLowerCase();
+    if (value.startsWith('#')) {
+      return colord(value).isValid() && colord(value).toHex() === normalizedHex;
+    }
+    return value === className || value === bare;
+  });
+}
+
 /**
  * Generates shade-family suggestions for a contrast violation.
  *
@@ -84,6 +113,7 @@ const DEFAULT_MAX_SUGGESTIONS = 3;
  * 2. Compute effective bg (with alpha compositing)
  * 3. Determine search direction based on bg luminance
  * 4. Walk shade family, filtering candidates by luminosity direction + threshold
+ *    (and by `constraints`: allowed families, excluded classes/colors)
  * 5. Return closest passing shades (sorted by distance from original)
  */
 export function generateSuggestions(
@@ -92,13 +122,15 @@ export function generateSuggestions(
   threshold: ConformanceLevel,
   themeMode: ThemeMode,
   maxSuggestions: number = DEFAULT_MAX_SUGGESTIONS,
+  constraints?: SuggestionConstraints,
 ): ColorSuggestion[] {
   // 1. Parse the foreground class
   const parsed = parseFamilyAndShade(violation.textClass);
   if (!parsed) return [];
 
-  const family = families.get(parsed.family);
-  if (!family) return [];
+  const candidateFamilies = searchFamilies(parsed.family, families, constraints);
+  if (candidateFamilies.length === 0) return [];
+  const exclude = constraints?.exclude ?? [];
 
   // 2. Compute effective background (same logic as contrast-checker)
   if (!violation.bgHex) return [];
@@ -123,23 +155,28 @@ export function generateSuggestions(
   // 5. Walk all shades, collect passing candidates
   const candidates: ColorSuggestion[] = [];
 
-  for (const [shade, hex] of family.shades) {
-    if (shade === parsed.shade) continue; // skip current shade
+  for (const family of candidateFamilies) {
+    for (const [shade, hex] of family.shades) {
+      if (family.family === parsed.family && shade === parsed.shade) continue; // skip current shade
 
-    // Verify luminosity direction: on light bg, want darker fg (lower luminance)
-    const candidateLuminance = colord(hex).luminance();
-    if (bgLuminance > 0.5 && candidateLuminance >= bgLuminance) continue;
-    if (bgLuminance <= 0.5 && candidateLuminance <= bgLuminance) continue;
+      const suggestedClass = `${parsed.prefix}${family.family}-${shade}`;
+      if (isExcluded(suggestedClass, parsed.prefix, hex, exclude)) continue;
 
-    const ratio = Math.round(bgColor.contrast(colord(hex)) * 100) / 100;
-    if (ratio < requiredRatio) continue;
+      // Verify luminosity direction: on light bg, want darker fg (lower luminance)
+      const candidateLuminance = colord(hex).luminance();
+      if (bgLuminance > 0.5 && candidateLuminance >= bgLuminance) continue;
+      if (bgLuminance <= 0.5 && candidateLuminance <= bgLuminance) continue;
 
-    candidates.push({
-      suggestedClass: `${parsed.prefix}${parsed.family}-${shade}`,
-      suggestedHex: hex,
-      newRatio: ratio,
-      shadeDistance: Math.abs(shade - parsed.shade),
-    });
+      const ratio = Math.round(bgColor.contrast(colord(hex)) * 100) / 100;
+      if (ratio < requiredRatio) continue;
+
+      candidates.push({
+        suggestedClass,
+        suggestedHex: hex,
+        newRatio: ratio,
+        shadeDistance: Math.abs(shade - parsed.shade),
+      });
+    }
   }
 
   // 6. Sort by shade distance (closest first), then by ratio (lower = minimal visual change)
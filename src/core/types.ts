@@ -30,15 +30,39 @@ export interface ColorPair {
   isLargeText?: boolean;
   /** 'text' = text/bg (SC 1.4.3), 'border'|'ring'|'outline' = non-text/bg (SC 1.4.11, 3:1) */
   pairType?: 'text' | 'border' | 'ring' | 'outline';
-  /** null = base state, 'hover' | 'focus-visible' = interactive state */
+  /** null = base state, otherwise the interactive state the pair renders in */
   interactiveState?: InteractiveState | null;
   /** true when suppressed via // a11y-ignore */
   ignored?: boolean;
   ignoreReason?: string;
+  /** Native engine only: rule ids a scoped `a11y-ignore <RULE-ID>: ...` suppresses (unset = every rule) */
+  ignoreRules?: string[] | null;
   /** 'inferred' = bg determined by parser stack, 'annotation' = overridden via @a11y-context */
   contextSource?: 'inferred' | 'annotation';
   /** US-05: Cumulative opacity applied to this pair (0.0-1.0). undefined = fully opaque. */
   effectiveOpacity?: number;
+  /** 'dark' = pair of a dark-mode twin region (native `darkVariants` extraction) */
+  theme?: 'dark' | null;
+  /** How the region's classes were captured (native engine regions only) */
+  provenance?: RegionProvenance | null;
+  /** Breakpoint the pair applies from (native `breakpointVariants` extraction) */
+  breakpoint?: string | null;
+}
+
+/** Kind of a non-shipping file, classified by path (`*.test.tsx`, `*.stories.tsx`, `e2e/`, `__mocks__/`, ...) */
+export type FileKind = 'test' | 'story' | 'e2e' | 'mock';
+
+/** How a region's classes were captured: static attribute, cn()/clsx() in an attribute, template literal, standalone helper call, cva() variant */
+export type RegionProvenance = 'attribute' | 'cn' | 'template' | 'standalone' | 'cva' | 'css-module' | 'stylesheet' | 'variant-map';
+
+/** Position of one class token inside a cn()/clsx() call */
+export interface ClassLocation {
+  className: string;
+  /** 0-based index of the call argument holding the class */
+  argument: number;
+  /** 1-based line and column of the class's first character */
+  line: number;
+  column: number;
 }
 
 /** Result of a WCAG contrast check */
@@ -52,10 +76,69 @@ export interface ContrastResult extends ColorPair {
   passAAALarge: boolean;
   /** APCA Lightness Contrast value (Lc). null if APCA calculation disabled */
   apcaLc?: number | null;
+  /** Native engine only: APCA polarity, Lc band and readable font sizes at this Lc */
+  apca?: {
+    /** 'BoW' (dark on light) | 'WoB' (light on dark) */
+    polarity: string;
+    /** 'fluent' | 'body' | 'content' | 'headline' | 'spot' | 'non-text' | 'invisible' */
+    lcBand: string;
+    /** Smallest readable size per font weight (text pairs only) */
+    minFontSizes: Array<{ weight: number; sizePx: number }>;
+  } | null;
   /** true = known baseline violation, false = new violation, undefined = baseline not active */
   isBaseline?: boolean;
   /** Auto-generated suggestions for fixing this violation (empty if none available) */
   suggestions?: ColorSuggestion[];
+  /** Native engine only: stable id of the check, e.g. 'AA-TEXT-CONTRAST', 'NONTEXT-CONTRAST', 'FOCUS-INDICATOR' */
+  ruleId?: string | null;
+  /** Native engine only: WCAG success criteria this result is evidence for, primary first */
+  wcagCriteria?: string[];
+  /** Native engine only: WCAG sufficient techniques for the check (e.g. 'G18') */
+  wcagTechniques?: string[];
+  /** Native engine only: line added/modified in the diff passed to markIntroducedInDiff() */
+  introducedInDiff?: boolean | null;
+  /** Native engine only: vendored package as 'name@version' (see attributeVendoredPackages()) */
+  package?: string | null;
+  /** Native engine only: non-shipping file kind (see applyFileKindPolicies()) */
+  fileKind?: FileKind | null;
+  /** Native engine only: location-independent identity of the failure */
+  fingerprint?: string | null;
+  /** Native engine only: stable result id, '<ruleId>-<hash>' (set with the fingerprint) */
+  id?: string | null;
+  /** Native engine only: package whose identical violation this one repeats */
+  inheritedFrom?: string | null;
+  /** Native engine only, EXPERIMENTAL: meets the WCAG 3 draft Lc minimum */
+  wcag3Pass?: boolean | null;
+  /** Native engine only: Weber contrast (Lt - Lb) / Lb, with `alternateContrast` */
+  weberContrast?: number | null;
+  /** Native engine only: Michelson contrast (0-1), with `alternateContrast` */
+  michelsonContrast?: number | null;
+  /** Native engine only: ratio under achromatopsia, with `visionSimulation` */
+  achromatopsiaRatio?: number | null;
+  /** Native engine only: `achromatopsiaRatio` still reaches 3:1 */
+  achromatopsiaDistinguishable?: boolean | null;
+  /** Native engine only: ratio under low-vision blur, with `visionSimulation` */
+  lowVisionRatio?: number | null;
+  /** Native engine only: `lowVisionRatio` still reaches 3:1 */
+  lowVisionDistinguishable?: boolean | null;
+  /** Native engine only: threshold multiplier the pass/fail flags were evaluated with */
+  safetyMargin?: number | null;
+  /** Native engine only: text pair of a required-field marker (`*`) */
+  requiredMarker?: boolean | null;
+  /** Native engine only: evaluated at AAA by the strict heading/label profile */
+  strictProfile?: boolean | null;
+  /** Native engine only: templated fix advice for a violation, shared by every reporter */
+  remediation?: string | null;
+  /** Native engine only: last line of a class value that wraps past `line` */
+  endLine?: number | null;
+  /** Native engine only: tag or component the classes are on, e.g. 'Button' */
+  tagName?: string | null;
+  /** Native engine only: static ARIA role of the element */
+  role?: string | null;
+  /** Native engine only: 'error' | 'warning' | 'info' by element weight, with `severityEscalation` */
+  severity?: string | null;
+  /** Native engine only: enclosing tag path, outermost first, e.g. ['Card', 'CardContent', 'p'] */
+  ancestry?: string[] | null;
 }
 
 /** A class that couldn't be resolved */
@@ -79,6 +162,26 @@ export interface AuditResult {
   passed: ContrastResult[];
   skipped: SkippedClass[];
   ignored: IgnoredViolation[];
+  /** Native engine only: color health score of the run */
+  health?: HealthScore;
+}
+
+/**
+ * Color health score, 0-100 (see native/src/health.rs for the formula).
+ * Suppressed and unresolved pairs each halve their share of the score.
+ */
+export interface HealthScore {
+  score: number;
+  /** Bumped when the formula changes */
+  formulaVersion: number;
+  weightedPassRate: number;
+  suppressionRatio: number;
+  unresolvedRatio: number;
+  checked: number;
+  suppressed: number;
+  unresolved: number;
+  /** Suppressed violations per a11y-ignore reason, most frequent first */
+  suppressions: Array<{ reason: string; count: number }>;
 }
 
 /** Raw palette: CSS var name → hex string (no alpha, all opaque) */
@@ -88,7 +191,16 @@ export type RawPalette = Map<string, string>;
 export type ThemeMode = 'light' | 'dark';
 
 /** Tracked interactive states for contrast auditing */
-export type InteractiveState = 'hover' | 'focus-visible' | 'aria-disabled';
+export type InteractiveState =
+  | 'hover'
+  | 'focus'
+  | 'focus-visible'
+  | 'active'
+  | 'aria-disabled'
+  | AttributeState;
+
+/** Attribute variant states of native state twins, e.g. 'aria-selected', 'data-[state=open]' */
+export type AttributeState = `aria-${string}` | `data-[${string}]`;
 
 /** WCAG conformance level for violation threshold */
 export type ConformanceLevel = 'AA' | 'AAA';
@@ -113,11 +225,31 @@ export interface ClassRegion {
   inlineStyles?: {
     color?: string;
     backgroundColor?: string;
+    /** Native engine only: paired as a non-text 'border' foreground */
+    borderColor?: string;
+    /** Native engine only: paired as a non-text 'outline' foreground */
+    outlineColor?: string;
+    /** Native engine only */
+    textDecorationColor?: string;
   };
   /** Context override from an @a11y-context annotation on the same/preceding line */
   contextOverride?: ContextOverride;
   /** US-05: Cumulative opacity from ancestor containers (0.0-1.0). undefined = fully opaque. */
   effectiveOpacity?: number;
+  /** Native engine only: hover background of the `group` ancestor its `group-hover:` classes refer to */
+  groupHoverBg?: string;
+  /** Native engine only: its `peer-*` classes follow a matching `peer` sibling */
+  peerState?: boolean;
+  /** Native engine only: 'dark' = dark-mode twin of the region before it, 'light' = a region with such a twin */
+  theme?: 'light' | 'dark';
+  /** Native engine only: how the classes were captured */
+  provenance?: RegionProvenance;
+  /** Native engine only: breakpoint twin of the region before it (e.g. 'md') */
+  breakpoint?: string;
+  /** Native engine only: interactive-state twin of the region before it (e.g. 'hover') */
+  interactiveState?: InteractiveState;
+  /** Native engine only: where each class of a cn()/clsx() call sits, by argument */
+  classLocations?: ClassLocation[];
 }
 
 /** Pre-extracted file data, theme-agnostic. Used for extract-once/resolve-twice pattern. */
@@ -131,6 +263,10 @@ export interface FileRegions {
 export interface BaselineData {
   version: string;
   generatedAt: string;
+  /** SHA-256 of `violations` as JSON (absent in baselines older than 1.2.0) */
+  checksum?: string;
+  /** File paths were case-folded when hashing (`baseline.caseInsensitivePaths`) */
+  caseInsensitivePaths?: boolean;
   violations: Record<string, Record<string, number>>;
 }
 
@@ -162,6 +298,18 @@ export interface ColorSuggestion {
   shadeDistance: number;
 }
 
+/** Config constraints keeping suggestions on-brand */
+export interface SuggestionConstraints {
+  /**
+   * Only suggest shades from these families (e.g. ["gray", "brand"]).
+   * A violation whose own family is listed stays within it; otherwise all
+   * listed families are searched.
+   */
+  families?: string[];
+  /** Never suggest these classes or colors (e.g. "gray-950", "text-gray-950", "#000000") */
+  exclude?: string[];
+}
+
 /** A parsed variant option within a CVA definition */
 export interface CvaVariantOption {
   /** Option name (e.g., "destructive", "sm") */
@@ -19,6 +19,7 @@ export type {
   ContextOverride,
   ShadeFamily,
   ColorSuggestion,
+  SuggestionConstraints,
 } from './types/public.js';
 
 // ── Plugin interfaces ─────────────────────────────────────────────────
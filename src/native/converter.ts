@@ -1,4 +1,4 @@
-import type { ClassRegion, ContextOverride } from '../core/types.js';
+import type { ClassRegion, ContextOverride, InteractiveState, RegionProvenance } from '../core/types.js';
 import type { NativeClassRegion, NativePreExtractedFile } from './index.js';
 import type { PreExtracted } from '../plugins/jsx/region-resolver.js';
 import type { FileRegions, SkippedClass } from '../core/types.js';
@@ -15,14 +15,24 @@ function convertNativeRegion(native: NativeClassRegion): ClassRegion {
     };
 
     // Reconstruct nested inlineStyles from flat fields
-    if (native.inlineColor || native.inlineBackgroundColor) {
-        region.inlineStyles = {};
-        if (native.inlineColor) {
-            region.inlineStyles.color = native.inlineColor;
-        }
-        if (native.inlineBackgroundColor) {
-            region.inlineStyles.backgroundColor = native.inlineBackgroundColor;
-        }
+    const inlineStyles: NonNullable<ClassRegion['inlineStyles']> = {};
+    if (native.inlineColor) {
+        inlineStyles.color = native.inlineColor;
+    }
+    if (native.inlineBackgroundColor) {
+        inlineStyles.backgroundColor = native.inlineBackgroundColor;
+    }
+    if (native.inlineBorderColor) {
+        inlineStyles.borderColor = native.inlineBorderColor;
+    }
+    if (native.inlineOutlineColor) {
+        inlineStyles.outlineColor = native.inlineOutlineColor;
+    }
+    if (native.inlineTextDecorationColor) {
+        inlineStyles.textDecorationColor = native.inlineTextDecorationColor;
+    }
+    if (Object.keys(inlineStyles).length > 0) {
+        region.inlineStyles = inlineStyles;
     }
 
     // Reconstruct nested contextOverride from flat fields
@@ -45,6 +55,28 @@ function convertNativeRegion(native: NativeClassRegion): ClassRegion {
         region.effectiveOpacity = native.effectiveOpacity;
     }
 
+    if (native.groupHoverBg) {
+        region.groupHoverBg = native.groupHoverBg;
+    }
+    if (native.peerState) {
+        region.peerState = true;
+    }
+    if (native.theme === 'light' || native.theme === 'dark') {
+        region.theme = native.theme;
+    }
+    if (native.provenance) {
+        region.provenance = native.provenance as RegionProvenance;
+    }
+    if (native.breakpoint) {
+        region.breakpoint = native.breakpoint;
+    }
+    if (native.interactiveState) {
+        region.interactiveState = native.interactiveState as InteractiveState;
+    }
+    if (native.classLocations?.length) {
+        region.classLocations = native.classLocations;
+    }
+
     return region;
 }
 
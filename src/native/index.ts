@@ -1,4 +1,4 @@
-import type { ContrastResult } from '../core/types.js';
+import type { ClassLocation, ContrastResult, FileKind } from '../core/types.js';
 
 /** ClassRegion as returned by the Rust parser (flattened vs TS nested structure) */
 export interface NativeClassRegion {
@@ -7,17 +7,335 @@ export interface NativeClassRegion {
     contextBg: string;
     inlineColor?: string | null;
     inlineBackgroundColor?: string | null;
+    /** Inline `borderColor` / `border-color` */
+    inlineBorderColor?: string | null;
+    /** Inline `outlineColor` / `outline-color` */
+    inlineOutlineColor?: string | null;
+    /** Inline `textDecorationColor` / `text-decoration-color` */
+    inlineTextDecorationColor?: string | null;
     contextOverrideBg?: string | null;
     contextOverrideFg?: string | null;
     contextOverrideNoInherit?: boolean | null;
     ignored?: boolean | null;
     ignoreReason?: string | null;
+    /** Rule ids a scoped `a11y-ignore AA-TEXT-CONTRAST: ...` suppresses (unset = every rule) */
+    ignoredRules?: string[] | null;
     effectiveOpacity?: number | null;
+    /** Identical regions folded into this one (set only with `dedupRegions`) */
+    occurrences?: number | null;
+    /** The element is a required-field marker (`*` text or `content-['*']`) */
+    requiredMarker?: boolean | null;
+    /** Static `role` attribute of the element */
+    role?: string | null;
+    /** `cva()` branch the classes render in, e.g. 'intent=destructive', or 'default' for `defaultVariants`; for a variant map entry (`variants[variant]`), e.g. 'variant=primary' */
+    variantKey?: string | null;
+    /** One branch of a ternary class expression (`cond ? 'a' : 'b'`) */
+    conditional?: boolean | null;
+    /** Classes can't be statically known (`{...props}`, `className={props.className}`); may be an empty synthetic region */
+    unresolvedDynamicClass?: boolean | null;
+    /** Last line of a class value that wraps past `startLine` (null = single line) */
+    endLine?: number | null;
+    /** Tag or component the classes are on, e.g. 'Button' ('' for standalone `cn()`/`cva()` calls) */
+    tagName: string;
+    /** Enclosing tags and components, outermost first, ending with the element, e.g. ['Card', 'CardContent', 'p'] */
+    ancestry: string[];
+    /** Hover background of the `group` ancestor its `group-hover:` classes refer to */
+    groupHoverBg?: string | null;
+    /** Its `peer-*` classes follow a matching `peer` sibling */
+    peerState?: boolean | null;
+    /** 'dark' = dark-mode twin of the region before it (context follows `dark:bg-*`), 'light' = a region with such a twin */
+    theme?: string | null;
+    /** How the classes were captured: 'attribute' | 'cn' | 'template' | 'standalone' | 'cva' | 'css-module' | 'stylesheet' | 'variant-map' */
+    provenance?: string | null;
+    /** Breakpoint twin of the region before it: its classes as applied at this breakpoint and up */
+    breakpoint?: string | null;
+    /** State twin of the region before it: 'hover' | 'focus' | 'focus-visible' | 'active' | 'aria-selected' | 'data-[state=open]' | ... */
+    interactiveState?: string | null;
+    /** Where each class of a `cn()`/`clsx()` call sits, by argument */
+    classLocations?: ClassLocation[] | null;
 }
 
 export interface NativePreExtractedFile {
     path: string;
     regions: NativeClassRegion[];
+    /** True when the file was listed in `changedPaths` */
+    changed?: boolean | null;
+    /** Vendored package of the file as 'name@version' (null = app code) */
+    package?: string | null;
+    /** Set when `rules` is given, even if empty */
+    findings?: NativeRuleFinding[] | null;
+    /** Non-shipping file kind by path (null = app code) */
+    fileKind?: FileKind | null;
+    /** Where the scan gave up early on malformed source (null = scanned through) */
+    diagnostics?: NativeParseDiagnostic[] | null;
+    /** The file carries an `a11y-ignore-file[: reason]` annotation: every region is ignored */
+    ignored?: boolean | null;
+}
+
+/** Malformed source the tokenizer stopped at; what follows it may be unscanned */
+export interface NativeParseDiagnostic {
+    line: number;
+    kind: 'unclosed-tag' | 'unbalanced-brackets' | 'unbalanced-parens' | 'unterminated-comment' | 'unterminated-template' | 'unclosed-ignore-range' | 'truncated-compound-variant';
+    message: string;
+}
+
+/** Finding of a non-contrast rule: 'use-of-color' (WCAG 1.4.1), 'required-marker' (1.3.1), 'click-on-static-element' (2.1.1) */
+export interface NativeRuleFinding {
+    rule: string;
+    wcagCriterion: string;
+    line: number;
+    tag: string;
+    className: string;
+    message: string;
+}
+
+/** Options accepted by extractAndScan / extractAndScanWithReport */
+export interface NativeExtractOptions {
+    fileContents: Array<{ path: string; content: string }>;
+    /** Container component → bg class (wins over `@a11y-layout` annotations) */
+    containerConfig: Array<{ component: string; bgClass: string }>;
+    portalConfig: Array<{ component: string; bgClass: string }>;
+    defaultBg: string;
+    /** Contexts elements opt into with `@a11y-context use:<name>`, leaving the local bg stack (inline toasts, popovers) */
+    namedContexts?: Array<{ name: string; bgClass: string }> | null;
+    /** Write an OpenMetrics snapshot of the run to this path */
+    metricsPath?: string | null;
+    /** 'off' | 'error' | 'warn' | 'info' | 'debug' | 'trace' (env: A11Y_AUDIT_LOG) */
+    logLevel?: string | null;
+    /** Locale of generated report text: 'en' (default) | 'it' */
+    locale?: string | null;
+    /** Audit only this fraction of files (0 < sample < 1), chosen deterministically */
+    sample?: number | null;
+    /** Seed for `sample`: the same seed always selects the same files */
+    seed?: number | null;
+    /** Paths changed in this CI run: parsed first, as their own batch */
+    changedPaths?: string[] | null;
+    /** Only parse `changedPaths` (container/portal context still applies) */
+    changedOnly?: boolean | null;
+    /** Skip .js/.mjs/.cjs files that don't look like JSX (pragma, react import, tags) */
+    sniffJs?: boolean | null;
+    /** Fold identical regions within a file (same content, context and tag) */
+    dedupRegions?: boolean | null;
+    /** Resource quotas; a run that hits one returns partial results */
+    limits?: NativeScanLimits | null;
+    /** Walk a directory natively; walked files are added to `fileContents` */
+    walk?: NativeWalkOptions | null;
+    /** Non-contrast rules to run: 'use-of-color' | 'required-marker' | 'click-on-static-element' */
+    rules?: string[] | null;
+    /** Custom props holding Tailwind classes, extracted like `className` (e.g. 'tw', 'innerClassName') */
+    classAttributes?: string[] | null;
+    /** Class-composition helpers extracted like cn()/clsx(), e.g. ['classNames', 'twMerge', 'cx'] */
+    classFunctions?: string[] | null;
+    /** CSS custom properties for `var(--token)` in inline styles (name with or without `--`) */
+    cssVariables?: Array<{ name: string; value: string }> | null;
+    /** Colors of CSS Modules classes, for `className={styles.card}` (see parseCssModule) */
+    cssModules?: NativeCssModuleClass[] | null;
+    /** Shared files (e.g. `src/styles.ts`) whose top-level class constants and variant maps scanned files import; not scanned themselves */
+    libraryFiles?: Array<{ path: string; content: string }> | null;
+    /** Also emit a dark-mode twin of each region with `dark:` classes or a `dark:bg-*` ancestor */
+    darkVariants?: boolean | null;
+    /** Standalone cn()/clsx()/cva() calls: 'scan' (default) | 'ignore' (reported as ignored) | 'annotated' (only after @a11y-context) | 'skip' */
+    standaloneClasses?: 'scan' | 'ignore' | 'annotated' | 'skip' | null;
+    /** Also emit a twin region per breakpoint with `sm:`/`md:`/... color classes */
+    breakpointVariants?: boolean | null;
+    /** Also emit a twin region per state with `hover:`/`focus:`/`active:`/`aria-selected:`/`data-[state=open]:`-style color classes */
+    stateVariants?: boolean | null;
+    /** Per file kind: 'skip' (not scanned) | 'informational' | 'full' (default) */
+    fileKindPolicies?: NativeFileKindPolicies | null;
+    /** Max lines between an `@a11y-context` / `a11y-ignore` comment and the start of its element (unset = no limit) */
+    annotationMaxDistance?: number | null;
+}
+
+/** 'skip' drops the kind, 'informational' reports its violations apart, 'full' audits it like app code */
+export type NativeFilePolicy = 'skip' | 'informational' | 'full';
+
+export interface NativeFileKindPolicies {
+    test?: NativeFilePolicy | null;
+    story?: NativeFilePolicy | null;
+    e2e?: NativeFilePolicy | null;
+    mock?: NativeFilePolicy | null;
+}
+
+/** Native directory walk (node_modules, .next, dist, coverage, .git skipped by default) */
+export interface NativeWalkOptions {
+    root: string;
+    /** Default: ['tsx', 'jsx']; add 'vue' / 'astro' / 'html' to audit Vue, Astro and plain HTML files */
+    extensions?: string[] | null;
+    /** Directory names ('legacy') or root-relative paths ('apps/old'), vendored packages included */
+    exclude?: string[] | null;
+    /** false also walks the default-excluded directories */
+    defaultExcludes?: boolean | null;
+    /** node_modules packages to audit anyway, e.g. ['@ourorg/ui'] */
+    includeVendored?: string[] | null;
+    /** Walk only the `includeVendored` packages */
+    vendoredOnly?: boolean | null;
+    /** Source encoding: "auto" (default: BOM, UTF-8, UTF-16 or Windows-1252), "utf-8", "utf-16le", "utf-16be" or "latin1" */
+    encoding?: string | null;
+}
+
+/** Resource quotas for one scan; unset fields are unlimited */
+export interface NativeScanLimits {
+    maxFiles?: number | null;
+    maxTotalBytes?: number | null;
+    maxRegionsPerFile?: number | null;
+    /** Files not started within the budget are skipped */
+    timeBudgetMs?: number | null;
+}
+
+/** What the loaded engine supports: feature-detect instead of pinning native versions */
+export interface NativeCapabilities {
+    engineVersion: string;
+    /** File suffixes with a parser mode, e.g. '.vue', '.component.html' */
+    fileTypes: string[];
+    rules: Array<{ rule: string; version: string }>;
+    /** Rules accepted by `NativeExtractOptions.rules` */
+    selectableRules: string[];
+    /** e.g. 'cache', 'walk', 'editor', 'hot-reload', 'snapshot' ('wasm' on wasm builds) */
+    features: string[];
+    cacheFormatVersion: number;
+}
+
+/** Built-in conformance suite outcome; failed cases carry expected vs. actual values */
+export interface NativeSelfTestReport {
+    passed: boolean;
+    engineVersion: string;
+    /** e.g. 'aarch64-linux' */
+    platform: string;
+    cases: Array<{ name: string; passed: boolean; detail?: string | null }>;
+}
+
+/** Reproducibility manifest: equal manifests prove two runs are comparable */
+export interface NativeRunManifest {
+    engineVersion: string;
+    optionsHash: string;
+    themeHash: string;
+    files: Array<{ path: string; contentHash: string }>;
+    ruleVersions: Array<{ rule: string; version: string }>;
+}
+
+/** Sampled-run counts: exact for the sample, extrapolated to all files */
+export interface NativeSamplingSummary {
+    rate: number;
+    seed: number;
+    totalFiles: number;
+    /** Files an unsampled run would scan (after sniffJs, file kind and changedOnly skips) */
+    eligibleFiles: number;
+    sampledFiles: number;
+    sampledRegions: number;
+    sampledIgnoredRegions: number;
+    /** eligibleFiles / sampledFiles: multiply any sampled count to estimate the full run */
+    extrapolationFactor: number;
+    estimatedRegions: number;
+    estimatedIgnoredRegions: number;
+}
+
+/** Limits reached by a scan whose results are partial */
+export interface NativeLimitsHit {
+    /** 'maxFiles' | 'maxTotalBytes' | 'maxRegionsPerFile' | 'timeBudgetMs' */
+    limits: string[];
+    skippedFiles: string[];
+    truncatedFiles: string[];
+}
+
+export interface NativeScanReport {
+    files: NativePreExtractedFile[];
+    manifest: NativeRunManifest;
+    sampling?: NativeSamplingSummary | null;
+    limitsHit?: NativeLimitsHit | null;
+}
+
+/** Color pair accepted by checkContrastPairs */
+export interface NativeColorPair {
+    file: string;
+    line: number;
+    bgClass: string;
+    textClass: string;
+    bgHex?: string | null;
+    textHex?: string | null;
+    bgAlpha?: number | null;
+    textAlpha?: number | null;
+    isLargeText?: boolean | null;
+    pairType?: string | null;
+    interactiveState?: string | null;
+    ignored?: boolean | null;
+    ignoreReason?: string | null;
+    /** Rule ids a scoped `a11y-ignore <RULE-ID>: ...` suppresses (unset = every rule) */
+    ignoreRules?: string[] | null;
+    contextSource?: string | null;
+    effectiveOpacity?: number | null;
+    isDisabled?: boolean | null;
+    unresolvedCurrentColor?: boolean | null;
+    requiredMarker?: boolean | null;
+    /** Tag or component the classes are on, e.g. 'h2' or 'Label' */
+    tagName?: string | null;
+    /** ARIA role, e.g. 'heading' */
+    role?: string | null;
+    /** Last line of a class value that wraps past `line` */
+    endLine?: number | null;
+    /** Enclosing tag path, e.g. ['Card', 'CardContent', 'p'] */
+    ancestry?: string[] | null;
+}
+
+export interface NativeCheckOptions {
+    /** 'AA' (default) | 'AAA' */
+    threshold?: string | null;
+    /** Default '#ffffff' */
+    pageBg?: string | null;
+    /** Opt-in unstable checks, e.g. ['wcag3'] */
+    experimental?: string[] | null;
+    /** Flag hover/focus states whose change from rest is imperceptible */
+    checkStateChanges?: boolean | null;
+    /** ΔE (CIE76) a state change must reach on bg or fg (default 3.5) */
+    minStateDeltaE?: number | null;
+    /** Report text below `minTextAlpha` as `LOW-ALPHA-TEXT` violations, whatever its ratio */
+    checkTextAlpha?: boolean | null;
+    /** Effective alpha text must reach: color alpha × cumulative opacity (default 0.3) */
+    minTextAlpha?: number | null;
+    /** Evaluate headings, labels and form control text at AAA even at AA */
+    strictProfile?: boolean | null;
+    /** Tags/components/roles for the strict profile (replaces the default list) */
+    strictElements?: string[] | null;
+    /** Theme colors searched for same-family replacement shades in `remediation` */
+    colorMap?: Array<{ name: string; hex: string; alpha?: number | null }> | null;
+    /** Locale of `remediation` text: 'en' (default) | 'it' */
+    locale?: string | null;
+    /** Add Weber and Michelson contrast to every result (informational only) */
+    alternateContrast?: boolean | null;
+    /** Add achromatopsia and low-vision simulation to every result (informational only) */
+    visionSimulation?: boolean | null;
+    /** Require this multiple of every nominal threshold, e.g. 1.2 for sunlight or dimmed displays (<= 1 ignored) */
+    safetyMargin?: number | null;
+    /** Grade violations 'error' | 'warning' | 'info' by the weight of their element (body text and landmarks escalate) */
+    severityEscalation?: boolean | null;
+    /** Tag/component/role weights for the grading, >= 1 error, >= 0.5 warning (replaces the default map) */
+    severityWeights?: Array<{ element: string; weight: number }> | null;
+}
+
+/** A hover/focus state barely distinguishable from its rest state */
+export interface NativeStateChange {
+    file: string;
+    line: number;
+    /** 'hover' | 'focus-visible' */
+    interactiveState: string;
+    pairType?: string | null;
+    restBgClass: string;
+    stateBgClass: string;
+    restTextClass: string;
+    stateTextClass: string;
+    bgDeltaE: number;
+    textDeltaE: number;
+    luminanceDelta: number;
+}
+
+/** EXPERIMENTAL: WCAG 3 draft outcome score — unstable, never gate CI on it */
+export interface NativeWcag3Score {
+    status: 'experimental';
+    draft: string;
+    /** 0-4 */
+    rating: number;
+    passing: number;
+    total: number;
+    percent: number;
 }
 
 export interface NativeCheckResult {
@@ -26,39 +344,433 @@ export interface NativeCheckResult {
     ignored: ContrastResult[];
     ignoredCount: number;
     skippedCount: number;
+    /** App violations collapsed into a vendored package's identical violation */
+    inherited?: ContrastResult[] | null;
+    /** Set only with `experimental: ['wcag3']` */
+    wcag3?: NativeWcag3Score | null;
+    /** Set only with `checkStateChanges` */
+    imperceptibleStateChanges?: NativeStateChange[] | null;
+    /** Set by `checkContrastPairsWithOptions` */
+    health?: NativeHealthScore | null;
+    /** Violations in 'informational' file kinds (see `applyFileKindPolicies`) */
+    informational?: ContrastResult[] | null;
+}
+
+/** A container/portal config entry the scanned code disagrees with (see lintConfig) */
+export interface NativeConfigLintFinding {
+    kind: 'duplicate' | 'conflict' | 'unmatched' | 'contradicted';
+    component: string;
+    config: 'container' | 'portal';
+    message: string;
+    /** contradicted: explicit bg classes seen on the component, most frequent first */
+    explicitBgs?: string[] | null;
+    /** contradicted: occurrences with a class attribute / with a contradicting bg */
+    occurrences?: number | null;
+    contradictions?: number | null;
+}
+
+/** One `a11y-ignore` comment to insert before a grandfathered violation's element (see migrateIgnores) */
+export interface NativeIgnoreEdit {
+    file: string;
+    /** 1-based insertion point; column 1 with a trailing newline in `text` = a line of its own */
+    line: number;
+    column: number;
+    text: string;
+    /** Baseline violations of the element the comment suppresses */
+    violations: number;
+}
+
+/** Inline review comment for the violations on one line a diff adds */
+export interface NativeDiffAnnotation {
+    /** File path as the diff names it (new side) */
+    path: string;
+    /** New-side line: the first added line of the violations' lines */
+    line: number;
+    /** GitHub legacy review `position`: lines below the file's first hunk header, later headers included */
+    position: number;
+    /** 0-based hunk index within the file's diff */
+    hunk: number;
+    /** 1-based line within the hunk, header excluded */
+    hunkLine: number;
+    /** Markdown comment body, one entry per violation */
+    body: string;
+    violations: ContrastResult[];
+}
+
+export interface NativeIgnoreMigration {
+    /** Sorted by file and position: apply from last to first */
+    edits: NativeIgnoreEdit[];
+    /** Violations matched in the baseline */
+    grandfathered: number;
+    /** Files of matched violations with no supplied source, left without edits */
+    missingFiles: string[];
+}
+
+/**
+ * Overall color health score, 0-100:
+ * round(100 × weightedPassRate × (1 − suppressionRatio/2) × (1 − unresolvedRatio/2)).
+ * Non-text and hover/focus pairs weigh 0.5 in the pass rate.
+ */
+export interface NativeHealthScore {
+    score: number;
+    /** Bumped when the formula changes */
+    formulaVersion: number;
+    weightedPassRate: number;
+    suppressionRatio: number;
+    unresolvedRatio: number;
+    checked: number;
+    suppressed: number;
+    unresolved: number;
+    /** Suppressed violations per a11y-ignore reason, most frequent first */
+    suppressions: Array<{ reason: string; count: number }>;
+}
+
+/** Results regrouped by EN 301 549 / Section 508 / WCAG clause numbering */
+export interface NativeProfileReport {
+    profile: string;
+    sections: Array<{
+        clause: string;
+        title: string;
+        wcagCriterion: string;
+        results: ContrastResult[];
+    }>;
+    uncovered: ContrastResult[];
+}
+
+/** One component of a scorecard: results attributed to the innermost component of their ancestry */
+export interface NativeComponentScore {
+    component: string;
+    /** 'base', or renderings such as 'hover', 'dark', 'dark+md' */
+    variants: string[];
+    checked: number;
+    violations: number;
+    /** Percent, one decimal */
+    passRate: number;
+    /** e.g. 'text-gray-400 on bg-white (2.54:1)' */
+    worstPair?: string | null;
+    worstRatio?: number | null;
+    suppressions: number;
+}
+
+/** Per-component contrast scorecard, for publishing next to a component library */
+export interface NativeScorecard {
+    components: NativeComponentScore[];
+    json: string;
+    markdown: string;
+}
+
+/** Pre-filled ACR (VPAT 2.x) rows for the color-related criteria */
+export interface NativeAcrReport {
+    rows: Array<{
+        criterion: string;
+        title: string;
+        level: string;
+        conformance: 'Supports' | 'Partially Supports' | 'Does Not Support' | 'Not Evaluated';
+        remarks: string;
+    }>;
+    markdown: string;
+}
+
+/** One token-level change proposed by planFixes() and its projected impact */
+export interface NativeTokenFix {
+    /** CSS variable to change, e.g. '--color-muted-foreground' */
+    token: string;
+    currentHex: string;
+    proposedHex: string;
+    /** Token pair of the cluster */
+    textClass: string;
+    bgClass: string;
+    /** Violations in the cluster */
+    violations: number;
+    /** Violations anywhere that pass with the proposed value */
+    fixed: number;
+    /** Passing pairs that would fail with the proposed value */
+    regressions: number;
+    files: string[];
+}
+
+export interface NativeFixPlan {
+    /** Biggest impact first */
+    fixes: NativeTokenFix[];
+    /** Violations on arbitrary colors, or without a passing value */
+    unplanned: number;
+}
+
+/** Before/after outcome of simulateTokenChange() */
+export interface NativeTokenSimulation {
+    token: string;
+    newHex: string;
+    /** Checked pairs reading the token as text or background */
+    affected: number;
+    passedBefore: number;
+    passedAfter: number;
+    /** Violations that pass with the new value (after values) */
+    fixed: ContrastResult[];
+    /** Passing pairs that fail with the new value (after values) */
+    regressed: ContrastResult[];
+}
+
+/** Minimal token change found by optimizePalette() */
+export interface NativePaletteProposal {
+    token: string;
+    currentHex: string;
+    proposedHex: string;
+    /** The proposed value as CSS oklch() */
+    proposedOklch: string;
+    /** CIE76 distance from the current value */
+    deltaE: number;
+    /** Share (0-1) of the token's pairs passing before / after */
+    passRateBefore: number;
+    passRateAfter: number;
+    fixed: number;
+    regressions: number;
+}
+
+/** Theme + config registered once via registerTheme() and reused by handle */
+export interface NativeThemeConfig {
+    /** Resolved theme colors keyed by CSS variable (e.g. '--color-primary') */
+    colorMap: Array<{ name: string; hex: string; alpha?: number | null }>;
+    containerConfig: Array<{ component: string; bgClass: string }>;
+    portalConfig: Array<{ component: string; bgClass: string }>;
+    defaultBg: string;
+    /** Contexts for `@a11y-context use:<name>` (see NativeExtractOptions.namedContexts) */
+    namedContexts?: Array<{ name: string; bgClass: string }> | null;
+    themeMode?: 'light' | 'dark' | null;
+    threshold?: 'AA' | 'AAA' | null;
+    /** Page background for alpha compositing (default: #ffffff light, #09090b dark) */
+    pageBg?: string | null;
+    locale?: string | null;
+    /** Custom class-bearing props (see NativeExtractOptions.classAttributes) */
+    classAttributes?: string[] | null;
+    /** Class-composition helpers (see NativeExtractOptions.classFunctions) */
+    classFunctions?: string[] | null;
+    /** CSS Modules class colors (see NativeExtractOptions.cssModules) */
+    cssModules?: NativeCssModuleClass[] | null;
+    /** Class constant files imported across files (see NativeExtractOptions.libraryFiles) */
+    libraryFiles?: Array<{ path: string; content: string }> | null;
+}
+
+/** Colors one CSS Modules class sets; `module` is matched against import specifiers by path suffix */
+export interface NativeCssModuleClass {
+    module: string;
+    /** As accessed: `styles.card` → 'card' */
+    className: string;
+    color?: string | null;
+    backgroundColor?: string | null;
+    borderColor?: string | null;
+}
+
+/** In-memory source for auditSnippets() */
+export interface NativeSnippet {
+    path: string;
+    content: string;
+    /** Background class the snippet renders on (default: the theme's defaultBg) */
+    baseContext?: string | null;
+    /** Synthetic ancestors the snippet renders inside, outermost first */
+    ancestors?: NativeAncestorContext[] | null;
+}
+
+/** e.g. `{ component: 'Card' }`, or `{ bgClass: 'bg-slate-900' }` for "inside a dark section" */
+export interface NativeAncestorContext {
+    /** Resolved through container/portal config */
+    component?: string | null;
+    /** Wins over the component's configured bg */
+    bgClass?: string | null;
+    opacity?: number | null;
+}
+
+/** Contrast of an autocomplete candidate text class (see rankCandidateClasses()) */
+export interface NativeCandidateContrast {
+    className: string;
+    /** null when the class doesn't resolve to a color (no verdict) */
+    hex?: string | null;
+    ratio?: number | null;
+    /** Passes the theme threshold as body text */
+    pass?: boolean | null;
+    /** Passes as large text (≥18.66px bold or ≥24px) */
+    passLarge?: boolean | null;
+    apcaLc?: number | null;
+}
+
+/** Workspace project: root directory and the theme it is audited with */
+export interface NativeWorkspaceRoot {
+    root: string;
+    themeHandle: number;
+}
+
+/** Result of updateWorkspaceFiles() for one file */
+export interface NativeRoutedFile {
+    path: string;
+    /** null when no registered root contains the file */
+    root?: NativeWorkspaceRoot | null;
+    /** Regions indexed for the file */
+    regions: number;
+}
+
+/** Region of a file indexed by indexFiles() */
+export interface NativeIndexedRegion {
+    path: string;
+    /** Position in the file's region list (stable until the file changes) */
+    id: number;
+    region: NativeClassRegion;
+}
+
+/** Class region at an editor position with its checked pairs (see hoverInfo()) */
+export interface NativeHoverInfo {
+    file: string;
+    line: number;
+    /** 1-based column range of the region's classes on the line (null when not found verbatim) */
+    columnStart?: number | null;
+    columnEnd?: number | null;
+    region: NativeClassRegion;
+    /** Violations carry `remediation` */
+    violations: ContrastResult[];
+    passed: ContrastResult[];
+    ignored: ContrastResult[];
+    skipped: Array<{ file: string; line: number; className: string; reason: string }>;
+}
+
+/** Violations of one indexed file recomputed by updateConfig() */
+export interface NativeFileDelta {
+    path: string;
+    /** All violations under the new config */
+    violations: ContrastResult[];
+    introduced: ContrastResult[];
+    /** Fixed by the new config, as reported under the old one */
+    resolved: ContrastResult[];
+}
+
+/** Result of updateConfig() */
+export interface NativeConfigDelta {
+    /** Indexed files re-scanned because the container/portal/default-bg config changed */
+    rescanned: number;
+    /** Files whose violations changed */
+    files: NativeFileDelta[];
+}
+
+/** Result of snapshot(): results of every file indexed under a theme at one point in time */
+export interface NativeResultSnapshot {
+    themeHandle: number;
+    /** Paths of the files included, sorted */
+    files: string[];
+    /** Violations carry `remediation` */
+    results: NativeCheckResult;
+    skipped: Array<{ file: string; line: number; className: string; reason: string }>;
 }
 
 interface NativeModule {
     healthCheck(): string;
-    extractAndScan(options: {
-        fileContents: Array<{ path: string; content: string }>;
-        containerConfig: Array<{ component: string; bgClass: string }>;
-        portalConfig: Array<{ component: string; bgClass: string }>;
-        defaultBg: string;
-    }): NativePreExtractedFile[];
-    checkContrastPairs(
-        pairs: Array<{
-            file: string;
-            line: number;
-            bgClass: string;
-            textClass: string;
-            bgHex?: string | null;
-            textHex?: string | null;
-            bgAlpha?: number | null;
-            textAlpha?: number | null;
-            isLargeText?: boolean | null;
-            pairType?: string | null;
-            interactiveState?: string | null;
-            ignored?: boolean | null;
-            ignoreReason?: string | null;
-            contextSource?: string | null;
-            effectiveOpacity?: number | null;
-            isDisabled?: boolean | null;
-            unresolvedCurrentColor?: boolean | null;
-        }>,
-        threshold: string,
-        pageBg: string,
+    getCapabilities(): NativeCapabilities;
+    /** Run known color pairs and snippets through this binary (diagnoses broken installs) */
+    selfTest(): NativeSelfTestReport;
+    extractAndScan(options: NativeExtractOptions): NativePreExtractedFile[];
+    extractAndScanWithReport(options: NativeExtractOptions): NativeScanReport;
+    extractAndScanPrioritized(
+        options: NativeExtractOptions,
+        onBatch: (files: NativePreExtractedFile[]) => void,
+    ): NativePreExtractedFile[];
+    looksLikeJsx(content: string): boolean;
+    checkContrastPairs(pairs: NativeColorPair[], threshold: string, pageBg: string): NativeCheckResult;
+    /** Like checkContrastPairs with optional settings; `experimental: ['wcag3']` adds draft WCAG 3 scoring */
+    checkContrastPairsWithOptions(pairs: NativeColorPair[], options: NativeCheckOptions): NativeCheckResult;
+    groupResultsByProfile(
+        results: ContrastResult[],
+        profile: 'wcag' | 'en301549' | 'section508',
+        locale?: string | null,
+    ): NativeProfileReport;
+    generateAcr(result: NativeCheckResult, locale?: string | null): NativeAcrReport;
+    generateScorecard(result: NativeCheckResult): NativeScorecard;
+    registerTheme(config: NativeThemeConfig): number;
+    releaseTheme(handle: number): boolean;
+    /** Watch mode: atomically swap a registered theme for edited config; returns files whose violations changed */
+    updateConfig(themeHandle: number, config: NativeThemeConfig): NativeConfigDelta;
+    /** Immutable copy of the current results of a theme's indexed files (LSP workspace diagnostics) */
+    snapshot(themeHandle: number): NativeResultSnapshot;
+    /** Serve a theme's live results as JSON (GET /summary, /results?status=, /files, /file?path=); returns 'host:port' */
+    serveResults(themeHandle: number, address?: string | null): string;
+    /** Stop the results API of a theme (also stopped by releaseTheme) */
+    stopServingResults(themeHandle: number): boolean;
+    /** Versioned, checksummed cache file payload; null when missing, unreadable, another kind, truncated, corrupt or stale (only truncated, corrupt and stale files are deleted) */
+    readCacheFile(path: string, kind: string): string | null;
+    /** Atomically replace a cache file, writing its versioned, checksummed header */
+    writeCacheFile(path: string, kind: string, payload: string): void;
+    /** Pre-commit fast path: violations only */
+    auditSnippets(themeHandle: number, snippets: NativeSnippet[]): ContrastResult[];
+    /** Index open documents' regions under a theme (unchanged files aren't re-scanned); returns the region count */
+    indexFiles(themeHandle: number, snippets: NativeSnippet[]): number;
+    /** Indexed region at (line, column), both 1-based */
+    regionAt(themeHandle: number, path: string, line: number, column: number): NativeIndexedRegion | null;
+    /** Indexed regions starting on a line the diff added or modified */
+    regionsInDiff(
+        themeHandle: number,
+        diff: Parameters<NativeModule['markIntroducedInDiff']>[1],
+    ): NativeIndexedRegion[];
+    /** Autocomplete hints: each candidate text class on `contextBg` (bg class or hex), in input order */
+    rankCandidateClasses(themeHandle: number, contextBg: string, candidates: string[]): NativeCandidateContrast[];
+    /** Multi-root workspaces: audit files under `root` with a registered theme */
+    addWorkspaceRoot(root: string, themeHandle: number): void;
+    removeWorkspaceRoot(root: string): boolean;
+    /** Project of a file: the longest registered root containing it */
+    workspaceRootOf(path: string): NativeWorkspaceRoot | null;
+    /** Route changed documents to their projects and re-index them under each project's theme */
+    updateWorkspaceFiles(snippets: NativeSnippet[]): NativeRoutedFile[];
+    /** Editor hover: region at (line, column), both 1-based, of an open document (re-indexed if changed) */
+    hoverInfo(themeHandle: number, snippet: NativeSnippet, line: number, column: number): NativeHoverInfo | null;
+    markIntroducedInDiff(
+        result: NativeCheckResult,
+        diff: {
+            /** `git diff` output (unified format) */
+            unifiedDiff?: string | null;
+            /** Explicit inclusive line ranges, e.g. from blame */
+            ranges?: Array<{ path: string; startLine: number; endLine: number }> | null;
+        },
     ): NativeCheckResult;
+    /** Review-bot comments for the violations on lines the unified diff adds; `locale` 'en' (default) | 'it' */
+    annotateDiff(unifiedDiff: string, result: NativeCheckResult, locale?: string | null): NativeDiffAnnotation[];
+    /** Set `package` ('name@version') on results from node_modules files */
+    attributeVendoredPackages(result: NativeCheckResult): NativeCheckResult;
+    /** Set `fileKind` on results, drop 'skip' kinds and move 'informational' violations apart */
+    applyFileKindPolicies(result: NativeCheckResult, policies: NativeFileKindPolicies): NativeCheckResult;
+    /** Move app violations that repeat a package's own violation into `inherited` */
+    collapseInheritedViolations(result: NativeCheckResult): NativeCheckResult;
+    /** Set `fingerprint` and the stable result `id` on every result */
+    fingerprintResults(result: NativeCheckResult): NativeCheckResult;
+    /** Duplicated, conflicting, never-rendered or mostly-overridden config entries, given the extractAndScan result */
+    lintConfig(options: NativeExtractOptions, files: NativePreExtractedFile[]): NativeConfigLintFinding[];
+    /** `a11y-ignore` insertions grandfathering the violations a baseline (its `violations` map) records */
+    migrateIgnores(
+        files: Array<{ path: string; content: string }>,
+        violations: ContrastResult[],
+        baseline: Record<string, Record<string, number>>,
+        options?: { reason?: string | null; caseInsensitivePaths?: boolean | null } | null,
+    ): NativeIgnoreMigration;
+    /** Colors of each plain class rule of a CSS module stylesheet, for NativeExtractOptions.cssModules */
+    parseCssModule(module: string, css: string): NativeCssModuleClass[];
+    /** Flattened result table (one row per result, `status` = its list) as CSV */
+    exportResultsCsv(result: NativeCheckResult): string;
+    /** Same table written to `path` as Parquet; only in builds with the 'parquet-export' feature */
+    exportResultsParquet?(result: NativeCheckResult, path: string): void;
+    /** Cluster violations by token pair and propose one token change per cluster */
+    planFixes(
+        result: NativeCheckResult,
+        options?: { threshold?: 'AA' | 'AAA' | null; pageBg?: string | null; maxFixes?: number | null } | null,
+    ): NativeFixPlan;
+    /** What-if: replay results (or check pairs) with `token` set to `newValue` (any CSS color) */
+    simulateTokenChange(
+        input: {
+            result?: NativeCheckResult | null;
+            pairs?: NativeColorPair[] | null;
+            threshold?: 'AA' | 'AAA' | null;
+            pageBg?: string | null;
+        },
+        token: string,
+        newValue: string,
+    ): NativeTokenSimulation;
+    /** Search small OKLCH changes of failing tokens; proposals ranked by violations fixed */
+    optimizePalette(
+        input: Parameters<NativeModule['simulateTokenChange']>[0],
+        options?: { targetPassRate?: number | null; maxDeltaE?: number | null; maxProposals?: number | null } | null,
+    ): { proposals: NativePaletteProposal[]; unreachable: string[] };
 }
 
 let nativeModule: NativeModule | null = null;
@@ -3,6 +3,7 @@ import {
   stripVariants,
   routeClassToTarget,
   categorizeClasses,
+  relationalStateClasses,
   determineIsLargeText,
   extractStringLiterals,
   extractBalancedParens,
@@ -91,6 +92,13 @@ describe('stripVariants', () => {
     expect(result.interactiveState).toBe('hover');
   });
 
+  test('data-[state=open]: → isInteractive=true, attribute variant stripped', () => {
+    const result = stripVariants('data-[state=open]:bg-accent');
+    expect(result.base).toBe('bg-accent');
+    expect(result.isInteractive).toBe(true);
+    expect(result.interactiveState).toBeNull();
+  });
+
   test('active: → isInteractive=true, interactiveState=null (untracked)', () => {
     const result = stripVariants('active:bg-red-700');
     expect(result.base).toBe('bg-red-700');
@@ -399,6 +407,50 @@ describe('categorizeClasses', () => {
     expect(result.bgClasses).toHaveLength(1);
     expect(result.textClasses).toHaveLength(1);
   });
+
+  test('at a breakpoint, the widest applicable breakpoint class wins', () => {
+    const classes = ['bg-black', 'text-white', 'md:bg-white', 'md:hover:bg-gray-100', 'lg:text-gray-400'];
+    const md = categorizeClasses(classes, 'light', 'md');
+    expect(md.bgClasses.map((t) => t.raw)).toEqual(['md:bg-white']);
+    expect(md.textClasses.map((t) => t.raw)).toEqual(['text-white']);
+    expect(md.interactiveStates.get('hover')!.bgClasses[0].breakpoint).toBe('md');
+    const lg = categorizeClasses(classes, 'light', 'lg');
+    expect(lg.textClasses.map((t) => t.raw)).toEqual(['lg:text-gray-400']);
+  });
+
+  test('in an interactive state, its classes replace the base ones', () => {
+    const classes = ['bg-white', 'text-black', 'hover:bg-gray-100', 'active:text-gray-700'];
+    const active = categorizeClasses(classes, 'light', null, 'active');
+    expect(active.bgClasses.map((t) => t.raw)).toEqual(['bg-white']);
+    expect(active.textClasses.map((t) => t.raw)).toEqual(['active:text-gray-700']);
+    expect([...active.interactiveStates.keys()]).toEqual(['hover']);
+  });
+});
+
+// ── relationalStateClasses ────────────────────────────────────────────
+
+describe('relationalStateClasses', () => {
+  const classes = [
+    'text-black',
+    'group-hover:text-white',
+    'dark:group-hover:text-gray-100',
+    'group-hover/card:bg-primary',
+    'sm:group-hover:text-red-500',
+    'peer-focus-visible:border-red-500',
+  ];
+
+  test('group-hover classes bucketed by state, keeping their raw class', () => {
+    const group = relationalStateClasses(classes, 'group', 'light');
+    expect([...group.keys()]).toEqual(['hover']);
+    const hover = group.get('hover')!;
+    expect(hover.textClasses.map((t) => t.raw)).toEqual(['group-hover:text-white']);
+    expect(hover.bgClasses[0].base).toBe('bg-primary');
+  });
+
+  test('peer classes tracked separately', () => {
+    const peer = relationalStateClasses(classes, 'peer', 'light');
+    expect(peer.get('focus-visible')!.borderClasses[0].base).toBe('border-red-500');
+  });
 });
 
 // ── determineIsLargeText ──────────────────────────────────────────────
@@ -1,5 +1,5 @@
 import { describe, test, expect } from 'vitest';
-import { buildEffectiveBg, generatePairs, resolveFileRegions } from '../region-resolver.js';
+import { buildEffectiveBg, extractSourceRegions, generatePairs, resolveFileRegions } from '../region-resolver.js';
 import type { PreExtracted } from '../region-resolver.js';
 import type { TaggedClass, ForegroundGroup, PairMeta } from '../categorizer.js';
 import type { ColorMap, ClassRegion } from '../../../core/types.js';
@@ -490,6 +490,22 @@ describe('contextOverride in resolveFileRegions', () => {
     expect(result.pairs[0]!.contextSource).toBeUndefined();
   });
 
+  test('inline border and outline colors pair as non-text', () => {
+    const pre = makePreExtracted([{
+      content: 'text-white',
+      startLine: 1,
+      contextBg: 'bg-slate-900',
+      inlineStyles: { borderColor: '#000000', outlineColor: '#ffffff', textDecorationColor: '#ffffff' },
+    }]);
+    const result = resolveFileRegions(pre, colorMap);
+
+    expect(result.pairs.map((p) => [p.pairType, p.textClass])).toEqual([
+      [undefined, 'text-white'],
+      ['border', '(inline) #000000'],
+      ['outline', '(inline) #ffffff'],
+    ]);
+  });
+
   test('effectiveOpacity flows from region to pair', () => {
     const opacityColorMap: ColorMap = new Map([
       ['--color-background', { hex: '#ffffff' }],
@@ -515,3 +531,15 @@ describe('contextOverride in resolveFileRegions', () => {
     expect(pairs[0]!.textAlpha).toBe(0.5);
   });
 });
+
+describe('extractSourceRegions', () => {
+  test('extracts in-memory sources under their given path', () => {
+    const content = '<p className="text-gray-500">a</p>\n<span className="text-white">b</span>';
+    const extracted = extractSourceRegions([{ path: 'src/Draft.tsx', content }], new Map(), 'bg-background');
+    expect(extracted.filesScanned).toBe(1);
+    expect(extracted.readErrors).toEqual([]);
+    expect(extracted.files[0]!.relPath).toBe('src/Draft.tsx');
+    expect(extracted.files[0]!.lines).toHaveLength(2);
+    expect(extracted.files[0]!.regions.map(r => r.content)).toEqual(['text-gray-500', 'text-white']);
+  });
+});
@@ -159,8 +159,14 @@ const VARIANT_PREFIXES = [
   'placeholder:',
   'aria-selected:',
   'aria-disabled:',
+  'aria-checked:',
+  'aria-expanded:',
+  'aria-pressed:',
 ];
 
+/** Leading `data-[...]:` / `aria-[...]:` attribute variant, e.g. 'data-[state=open]:' */
+const ATTRIBUTE_VARIANT = /^(?:data|aria)-\[[^\]]*\]:/;
+
 // ── Large text detection (WCAG SC 1.4.3) ─────────────────────────────
 // ≥24px (18pt) any weight → always large
 const ALWAYS_LARGE = new Set([
@@ -185,6 +191,15 @@ const INTERACTIVE_PREFIX_MAP = new Map<string, InteractiveState>([
   ['aria-disabled:', 'aria-disabled'],
 ]);
 
+/** Tailwind breakpoints, narrowest first */
+export const BREAKPOINTS = ['sm', 'md', 'lg', 'xl', '2xl'] as const;
+export type Breakpoint = (typeof BREAKPOINTS)[number];
+
+/** Position of `breakpoint` in BREAKPOINTS, 1-based (0 = no breakpoint) */
+function breakpointRank(breakpoint: string | null | undefined): number {
+  return BREAKPOINTS.indexOf(breakpoint as Breakpoint) + 1;
+}
+
 // ── Exported interfaces ──────────────────────────────────────────────
 
 /** A class extracted from source with its variant flags */
@@ -196,6 +211,8 @@ export interface TaggedClass {
   isInteractive: boolean;
   /** Which tracked interactive state, if any (hover, focus-visible). null for non-tracked variants */
   interactiveState: InteractiveState | null;
+  /** Widest breakpoint prefix (md:, lg:, ...), if any */
+  breakpoint?: Breakpoint | null;
   base: string;
 }
 
@@ -236,6 +253,9 @@ export interface PairMeta {
   isLargeText: boolean;
   interactiveState?: InteractiveState | null;
   effectiveOpacity?: number;
+  theme?: 'dark';
+  breakpoint?: string;
+  provenance?: import('../../core/types.js').RegionProvenance;
 }
 
 /** Result of generating pairs for a single set of fg/bg classes */
@@ -257,11 +277,13 @@ export function stripVariants(cls: string): TaggedClass {
   let isDark = false;
   let isInteractive = false;
   let interactiveState: InteractiveState | null = null;
+  let breakpoint: Breakpoint | null = null;
 
   let changed = true;
   while (changed) {
     changed = false;
-    for (const prefix of VARIANT_PREFIXES) {
+    const attribute = ATTRIBUTE_VARIANT.exec(base)?.[0];
+    for (const prefix of attribute ? [attribute] : VARIANT_PREFIXES) {
       if (base.startsWith(prefix)) {
         if (prefix === 'dark:') {
           isDark = true;
@@ -269,6 +291,8 @@ export function stripVariants(cls: string): TaggedClass {
           isInteractive = true;
           const tracked = INTERACTIVE_PREFIX_MAP.get(prefix);
           if (tracked) interactiveState = tracked;
+          const bp = prefix.slice(0, -1);
+          if (breakpointRank(bp) > breakpointRank(breakpoint)) breakpoint = bp as Breakpoint;
         }
         base = base.slice(prefix.length);
         changed = true;
@@ -277,7 +301,7 @@ export function stripVariants(cls: string): TaggedClass {
     }
   }
 
-  return { raw, isDark, isInteractive, interactiveState, base };
+  return { raw, isDark, isInteractive, interactiveState, breakpoint, base };
 }
 
 // ── Class routing ─────────────────────────────────────────────────────
@@ -356,12 +380,19 @@ function routeToStateBucket(
  * Dark mode: include dark:-prefixed and base classes.
  * Tracked interactive variants (hover, focus-visible) go to per-state buckets.
  * Untracked interactive variants (sm:, active:, etc.) are still skipped.
+ * At a `breakpoint`, classes up to that breakpoint apply and the widest one
+ * present wins per bucket; wider breakpoints are skipped. In an interactive
+ * `state` (hover, focus, focus-visible, active), its classes apply like base ones and
+ * replace the others in their bucket.
  * @internal Exported for unit testing
  */
 export function categorizeClasses(
   classes: string[],
   themeMode: import('../../core/types.js').ThemeMode,
+  breakpoint?: string | null,
+  state?: InteractiveState | null,
 ): CategorizedClasses {
+  const maxRank = breakpointRank(breakpoint);
   const bgClasses: TaggedClass[] = [];
   const textClasses: TaggedClass[] = [];
   const borderClasses: TaggedClass[] = [];
@@ -384,13 +415,26 @@ export function categorizeClasses(
       continue;
     }
 
-    const tagged = stripVariants(cls);
+    let tagged = stripVariants(cls);
 
     // Capture font size/weight BEFORE any filtering
     if (ALWAYS_LARGE.has(tagged.base) || LARGE_IF_BOLD.has(tagged.base))
       fontSize = tagged.base;
     if (BOLD_CLASSES.has(tagged.base)) isBold = true;
 
+    // At a breakpoint, narrower breakpoint prefixes don't make a class
+    // conditional; neither does the state being rendered
+    if (breakpoint && breakpointRank(tagged.breakpoint) > maxRank) continue;
+    const applies = (v: string): boolean =>
+      (!!breakpoint && breakpointRank(v) > 0) || v === state;
+    if (variantsOf(cls).some(applies)) {
+      tagged = {
+        ...stripVariants(withoutVariants(cls, applies)),
+        raw: cls,
+        breakpoint: tagged.breakpoint,
+      };
+    }
+
     // Route tracked interactive states to per-state buckets
     if (tagged.isInteractive) {
       if (tagged.interactiveState) {
@@ -447,6 +491,23 @@ export function categorizeClasses(
     }
   }
 
+  // State, then breakpoint cascade — the state's classes replace the
+  // others, the widest breakpoint present replaces narrower ones
+  if (breakpoint || state) {
+    const inState = (t: TaggedClass): boolean => !!state && hasVariant(t.raw, state);
+    for (const bucket of [
+      { bgClasses, textClasses, borderClasses, ringClasses, outlineClasses },
+      ...interactiveStates.values(),
+    ]) {
+      for (const list of Object.values(bucket) as TaggedClass[][]) {
+        if (list.some(inState)) list.splice(0, list.length, ...list.filter(inState));
+        const widest = Math.max(0, ...list.map((t) => breakpointRank(t.breakpoint)));
+        const kept = list.filter((t) => breakpointRank(t.breakpoint) === widest);
+        list.splice(0, list.length, ...kept);
+      }
+    }
+  }
+
   return {
     bgClasses,
     textClasses,
@@ -460,6 +521,67 @@ export function categorizeClasses(
   };
 }
 
+/** Variants of `cls` (`md:hover:bg-x` → ['md', 'hover']) */
+function variantsOf(cls: string): string[] {
+  return cls.split(':').slice(0, -1);
+}
+
+/**
+ * Whether `cls` carries `variant` (`md:hover:bg-x` carries 'hover').
+ * @internal Exported for unit testing
+ */
+export function hasVariant(cls: string, variant: string): boolean {
+  return variantsOf(cls).includes(variant);
+}
+
+/** `cls` without the variants `drop` matches (`md:hover:bg-x` → `hover:bg-x` for breakpoints) */
+function withoutVariants(cls: string, drop: (variant: string) => boolean): string {
+  const parts = cls.split(':');
+  const base = parts.pop()!;
+  return [...parts.filter((v) => !drop(v)), base].join(':');
+}
+
+/**
+ * Classes applied in another element's state, by tracked state:
+ * `group-hover:text-white` (marker 'group') or
+ * `peer-focus-visible/email:border-red-500` (marker 'peer'). Classes with
+ * other interactive variants, and dark: ones in light mode, are skipped.
+ * @internal Exported for unit testing
+ */
+export function relationalStateClasses(
+  classes: string[],
+  marker: 'group' | 'peer',
+  themeMode: import('../../core/types.js').ThemeMode,
+): Map<InteractiveState, ClassBuckets> {
+  const states = new Map<InteractiveState, ClassBuckets>();
+
+  for (const cls of classes) {
+    const variantEnd = cls.lastIndexOf(':');
+    if (variantEnd < 0) continue;
+
+    let state: InteractiveState | null = null;
+    let rest = '';
+    for (const variant of cls.slice(0, variantEnd).split(':')) {
+      const relational = variant.startsWith(`${marker}-`)
+        ? variant.slice(marker.length + 1).split('/')[0]
+        : null;
+      const tracked = relational ? INTERACTIVE_PREFIX_MAP.get(`${relational}:`) : undefined;
+      if (tracked && !state) {
+        state = tracked;
+      } else {
+        rest += `${variant}:`;
+      }
+    }
+    if (!state) continue;
+
+    const tagged = { ...stripVariants(rest + cls.slice(variantEnd + 1)), raw: cls };
+    if (tagged.isInteractive || (tagged.isDark && themeMode === 'light')) continue;
+    routeToStateBucket({ ...tagged, interactiveState: state }, states);
+  }
+
+  return states;
+}
+
 // ── Large Text Determination ──────────────────────────────────────────
 
 /**
@@ -1,19 +1,25 @@
 import { readFileSync } from 'node:fs';
 import { globSync } from 'glob';
 import { relative } from 'node:path';
+import { toPortablePath } from '../../core/paths.js';
 import { resolveClassToHex } from '../tailwind/css-resolver.js';
 import { extractClassRegions } from './parser.js';
 import {
   categorizeClasses,
+  hasVariant,
+  stripVariants,
   determineIsLargeText,
   extractStringLiterals,
   getIgnoreReasonForLine,
+  relationalStateClasses,
 } from './categorizer.js';
-import type { TaggedClass, ForegroundGroup, PairMeta } from './categorizer.js';
+import type { TaggedClass, ClassBuckets, ForegroundGroup, PairMeta } from './categorizer.js';
 import type {
+  ClassRegion,
   ColorMap,
   ColorPair,
   FileRegions,
+  InteractiveState,
   SkippedClass,
   ThemeMode,
 } from '../../core/types.js';
@@ -67,6 +73,29 @@ export function buildEffectiveBg(
   return effective;
 }
 
+/**
+ * Appends an inline border/outline color (hex literal) as a synthetic
+ * `${prefix}-[hex]` class, so it pairs like a non-text class.
+ * @internal Exported for unit testing
+ */
+export function withInlineColor(
+  classes: TaggedClass[],
+  hex: string | undefined,
+  prefix: 'border' | 'outline',
+): TaggedClass[] {
+  if (!hex || !hex.startsWith('#') || hex.length < 4) return classes;
+  return [
+    ...classes,
+    {
+      raw: `(inline) ${hex}`,
+      isDark: false,
+      isInteractive: false,
+      interactiveState: null,
+      base: `${prefix}-[${hex}]`,
+    },
+  ];
+}
+
 /**
  * Generates color pairs from foreground groups against background classes.
  * Unified function for text (SC 1.4.3), non-text (SC 1.4.11), and interactive states.
@@ -151,6 +180,18 @@ export function generatePairs(
           pair.interactiveState = meta.interactiveState;
         }
 
+        if (meta.theme) {
+          pair.theme = meta.theme;
+        }
+
+        if (meta.provenance) {
+          pair.provenance = meta.provenance;
+        }
+
+        if (meta.breakpoint) {
+          pair.breakpoint = meta.breakpoint;
+        }
+
         // US-05: Apply effective opacity as alpha reduction
         if (meta.effectiveOpacity != null && meta.effectiveOpacity < 1) {
           pair.effectiveOpacity = meta.effectiveOpacity;
@@ -184,7 +225,19 @@ export function resolveFileRegions(
   for (const file of preExtracted.files) {
     const { relPath, lines, regions } = file;
 
+    // States rendered by twin regions, which stand in for the state pairs of
+    // the region they twin
+    const twinKey = (region: ClassRegion, state: InteractiveState): string =>
+      [region.startLine, region.content, region.theme ?? '', state].join('\u0000');
+    const twinStates = new Set(
+      regions.filter((r) => r.interactiveState).map((r) => twinKey(r, r.interactiveState!)),
+    );
+
     for (const region of regions) {
+      // A dark-mode twin renders dark in any run; in a dark run it stands
+      // in for the region it twins
+      if (region.theme === 'light' && themeMode === 'dark') continue;
+      const regionTheme: ThemeMode = region.theme === 'dark' ? 'dark' : themeMode;
       const lineNum = region.startLine;
       const ignoreReason = getIgnoreReasonForLine(lines, lineNum);
 
@@ -197,7 +250,8 @@ export function resolveFileRegions(
         ? extractStringLiterals(region.content)
         : region.content.split(/\s+/).filter(Boolean);
 
-      const categorized = categorizeClasses(allClasses, themeMode);
+      const twinState = region.interactiveState ?? null;
+      const categorized = categorizeClasses(allClasses, regionTheme, region.breakpoint, twinState);
       const isLargeText = determineIsLargeText(categorized.fontSize, categorized.isBold);
 
       for (const dc of categorized.dynamicClasses) {
@@ -261,15 +315,31 @@ export function resolveFileRegions(
         line: lineNum,
         ignoreReason,
         isLargeText,
+        interactiveState: twinState,
         effectiveOpacity: region.effectiveOpacity,
+        theme: region.theme === 'dark' ? 'dark' : undefined,
+        provenance: region.provenance,
+        breakpoint: region.breakpoint,
       };
 
+      // Inline border / outline colors: synthetic non-text classes
+      const borderClasses = withInlineColor(
+        categorized.borderClasses,
+        region.inlineStyles?.borderColor,
+        'border',
+      );
+      const outlineClasses = withInlineColor(
+        categorized.outlineClasses,
+        region.inlineStyles?.outlineColor,
+        'outline',
+      );
+
       // Base pairs (text SC 1.4.3 + non-text SC 1.4.11)
       const baseFgGroups: ForegroundGroup[] = [
         { classes: textClasses },
-        { classes: categorized.borderClasses, pairType: 'border' },
+        { classes: borderClasses, pairType: 'border' },
         { classes: categorized.ringClasses, pairType: 'ring' },
-        { classes: categorized.outlineClasses, pairType: 'outline' },
+        { classes: outlineClasses, pairType: 'outline' },
       ];
       const baseResult = generatePairs(
         baseFgGroups,
@@ -279,6 +349,7 @@ export function resolveFileRegions(
         hasExplicitBg,
         contextBg,
       );
+      const firstPair = allPairs.length;
       allPairs.push(...baseResult.pairs);
       if (hasAnnotation) {
         for (const pair of baseResult.pairs) {
@@ -288,9 +359,13 @@ export function resolveFileRegions(
       allSkipped.push(...baseResult.skipped);
 
       // Interactive state pairs (CSS inheritance: state overrides base)
-      for (const [state, stateClasses] of categorized.interactiveStates) {
+      const addStatePairs = (
+        state: InteractiveState,
+        stateClasses: ClassBuckets,
+        baseBg: TaggedClass[],
+      ): void => {
         const stateBg =
-          stateClasses.bgClasses.length > 0 ? stateClasses.bgClasses : effectiveBg;
+          stateClasses.bgClasses.length > 0 ? stateClasses.bgClasses : baseBg;
         const stateText =
           stateClasses.textClasses.length > 0 ? stateClasses.textClasses : textClasses;
         const stateMeta: PairMeta = { ...meta, interactiveState: state };
@@ -316,6 +391,49 @@ export function resolveFileRegions(
           }
         }
         allSkipped.push(...stateResult.skipped);
+      };
+      for (const [state, stateClasses] of categorized.interactiveStates) {
+        const twinned = !region.breakpoint && twinStates.has(twinKey(region, state));
+        if (!twinState && !twinned) addStatePairs(state, stateClasses, effectiveBg);
+      }
+
+      // group-hover: classes sit on the group's hover bg unless the
+      // element paints its own; peer-* classes on the element's bg
+      if (region.groupHoverBg && !twinState) {
+        const ownBg =
+          hasExplicitBg || !!inlineStyles?.backgroundColor || !!region.contextOverride?.bg;
+        const groupBg: TaggedClass[] = [
+          {
+            raw: `(group) ${region.groupHoverBg}`,
+            isDark: false,
+            isInteractive: false,
+            interactiveState: null,
+            base: region.groupHoverBg,
+          },
+        ];
+        for (const [state, stateClasses] of relationalStateClasses(allClasses, 'group', regionTheme)) {
+          addStatePairs(state, stateClasses, ownBg ? effectiveBg : groupBg);
+        }
+      }
+      if (region.peerState && !twinState) {
+        for (const [state, stateClasses] of relationalStateClasses(allClasses, 'peer', regionTheme)) {
+          addStatePairs(state, stateClasses, effectiveBg);
+        }
+      }
+
+      // A breakpoint twin only adds the pairs its breakpoint classes change
+      if (region.breakpoint) {
+        const changed = (cls: string): boolean => !!stripVariants(cls).breakpoint;
+        const pairs = allPairs.splice(firstPair);
+        allPairs.push(...pairs.filter((p) => changed(p.bgClass) || changed(p.textClass)));
+      }
+
+      // So does a state twin, with the state's classes
+      if (twinState) {
+        const pairs = allPairs.splice(firstPair);
+        allPairs.push(
+          ...pairs.filter((p) => hasVariant(p.bgClass, twinState) || hasVariant(p.textClass, twinState)),
+        );
       }
     }
   }
@@ -349,7 +467,7 @@ export function extractAllFileRegions(
   const readErrors: SkippedClass[] = [];
 
   for (const filePath of filePaths) {
-    const relPath = relative(cwd, filePath);
+    const relPath = toPortablePath(relative(cwd, filePath));
 
     let content: string;
     try {
@@ -377,3 +495,20 @@ export function extractAllFileRegions(
     filesScanned: filePaths.length,
   };
 }
+
+/**
+ * Like extractAllFileRegions, for sources already in memory (e.g. an
+ * unsaved editor buffer piped on stdin). Paths are used as given.
+ */
+export function extractSourceRegions(
+  sources: Array<{ path: string; content: string }>,
+  containerMap: ReadonlyMap<string, string>,
+  defaultBg: string,
+): PreExtracted {
+  const files: FileRegions[] = sources.map(({ path, content }) => ({
+    relPath: path,
+    lines: content.split('\n'),
+    regions: extractClassRegions(content, containerMap, defaultBg),
+  }));
+  return { files, readErrors: [], filesScanned: sources.length };
+}
@@ -33,4 +33,5 @@ export type {
   // Suggestion types
   ShadeFamily,
   ColorSuggestion,
+  SuggestionConstraints,
 } from '../core/types.js';